@@ -1,17 +1,37 @@
 package dev.dioxus.main
 
 import android.Manifest
+import android.app.Dialog
+import android.app.NotificationChannel
+import android.app.NotificationManager
+import android.content.ClipData
+import android.content.Context
+import android.content.Intent
 import android.content.pm.PackageManager
 import android.net.Uri
 import android.os.Bundle
+import android.provider.OpenableColumns
+import android.webkit.WebView
+import android.webkit.WebViewClient
 import androidx.activity.result.ActivityResultLauncher
 import androidx.activity.result.contract.ActivityResultContracts
 import androidx.core.app.ActivityCompat
+import androidx.core.app.NotificationCompat
+import androidx.core.app.NotificationManagerCompat
 import androidx.core.content.ContextCompat
 import androidx.core.content.FileProvider
+import androidx.work.BackoffPolicy
+import androidx.work.Constraints
+import androidx.work.ExistingPeriodicWorkPolicy
+import androidx.work.NetworkType
+import androidx.work.PeriodicWorkRequestBuilder
+import androidx.work.WorkManager
+import androidx.work.Worker
+import androidx.work.WorkerParameters
 import java.io.File
 import java.text.SimpleDateFormat
 import java.util.*
+import java.util.concurrent.TimeUnit
 
 /**
  * MainActivity für Stalltagebuch
@@ -26,6 +46,9 @@ import java.util.*
  * - Verwaltung temporärer Dateien/URIs über `FileProvider` und sichere Ablage im App-spezifischen Verzeichnis.
  * - Permission-Handling (Kamera, Storage) mit Unterstützung für moderne Android-Versionen
  *   (z. B. READ_MEDIA_IMAGES ab API 33 / Android 13).
+ * - In-App-Anmeldung (Nextcloud Login Flow v2) in einem WebView-Dialog, damit der Nutzer
+ *   die App für die Bestätigung nicht verlassen muss.
+ * - Berechtigung für Benachrichtigungen der Hintergrund-Synchronisation (siehe `SyncWorker`).
  * - Austausch von Ergebnissen über statische `@JvmStatic`-Methoden und `@Volatile` Felder
  *   damit die Rust-Seite (oder andere Java/Kotlin-Klassen) synchron auf das Ergebnis zugreifen können.
  *
@@ -45,6 +68,7 @@ class MainActivity : WryActivity() {
     companion object {
         private const val CAMERA_PERMISSION_CODE = 1001
         private const val STORAGE_PERMISSION_CODE = 1002
+        private const val NOTIFICATION_PERMISSION_CODE = 1003
         
         // Singleton-Referenz auf die Activity
         @Volatile
@@ -62,6 +86,9 @@ class MainActivity : WryActivity() {
         @Volatile
         private var currentPhotoPaths: String? = null // Newline-separated paths for multi-select
         
+        @Volatile
+        private var currentDocumentPath: String? = null
+        
         @Volatile
         private var lastError: String? = null
         
@@ -87,6 +114,13 @@ class MainActivity : WryActivity() {
         @JvmStatic
         fun getLastPhotoPaths(): String? = currentPhotoPaths
         
+        /**
+         * Gibt den Pfad der zuletzt ausgewählten Import-Datei (Kopie im Cache) zurück.
+         * - `null` bedeutet: noch keine Auswahl, Fehler oder Abbruch.
+         */
+        @JvmStatic
+        fun getLastDocumentPath(): String? = currentDocumentPath
+        
         /**
          * Setzt den letzten Fehler zurück. Kann von JNI-Aufrufern genutzt werden,
          * um Fehlerzustand nach erfolgreichem Lesen zu löschen.
@@ -111,9 +145,18 @@ class MainActivity : WryActivity() {
     // - `TakePicture()` benötigt eine URI (z. B. über FileProvider) in die die Kamera-App schreibt.
     private lateinit var takePictureLauncher: ActivityResultLauncher<Uri>
     
+    // ActivityResultLauncher für Import-Archive
+    // - `OpenDocument()` öffnet den System-Dateiauswahldialog (SAF), der auch Downloads,
+    //   Drive oder Nextcloud anbietet. Die Datei wird über `contentResolver` gelesen und
+    //   in `cacheDir/imports` kopiert, damit Rust sie wie eine normale Datei öffnen kann.
+    private lateinit var pickDocumentLauncher: ActivityResultLauncher<Array<String>>
+    
     // Temporäre URI für Kamera-Foto
     private var photoUri: Uri? = null
     
+    // Vollbild-Dialog mit WebView für die In-App-Anmeldung (null = nicht geöffnet)
+    private var loginDialog: Dialog? = null
+    
     // Pending action nach Permission-Grant
     // - Wenn die benötigten Berechtigungen noch nicht erteilt sind, setzen wir `pendingAction`
     //   auf eine lambda, die die eigentliche Aktion ausführt (z. B. `launchCameraInternal`).
@@ -194,6 +237,34 @@ class MainActivity : WryActivity() {
         }
         
         // Register Kamera
+        // Register Dokument-Picker für Import-Archive
+        pickDocumentLauncher = registerForActivityResult(
+            ActivityResultContracts.OpenDocument()
+        ) { uri: Uri? ->
+            if (uri != null) {
+                try {
+                    val importDir = File(cacheDir, "imports")
+                    // Frühere Kopien entfernen, der Cache soll nicht anwachsen
+                    importDir.deleteRecursively()
+                    importDir.mkdirs()
+                    val target = File(importDir, displayName(uri) ?: "import.zip")
+                    contentResolver.openInputStream(uri)?.use { input ->
+                        target.outputStream().use { output ->
+                            input.copyTo(output)
+                        }
+                    } ?: throw IllegalStateException("Datei kann nicht gelesen werden")
+                    currentDocumentPath = target.absolutePath
+                    lastError = null
+                } catch (e: Exception) {
+                    lastError = "Fehler beim Lesen der Datei: ${e.message}"
+                    currentDocumentPath = null
+                }
+            } else {
+                lastError = "Keine Datei ausgewählt"
+                currentDocumentPath = null
+            }
+        }
+        
         takePictureLauncher = registerForActivityResult(
             ActivityResultContracts.TakePicture()
         ) { success: Boolean ->
@@ -217,6 +288,8 @@ class MainActivity : WryActivity() {
     }
     
     override fun onDestroy() {
+        loginDialog?.dismiss()
+        loginDialog = null
         super.onDestroy()
         if (instance == this) {
             instance = null
@@ -293,6 +366,28 @@ class MainActivity : WryActivity() {
         )
     }
     
+    /**
+     * Fordert ab Android 13 die Berechtigung für Benachrichtigungen an.
+     * - Ohne sie meldet der `SyncWorker` seine Ergebnisse still; die Synchronisation läuft trotzdem.
+     */
+    fun requestNotificationPermission() {
+        if (android.os.Build.VERSION.SDK_INT < android.os.Build.VERSION_CODES.TIRAMISU) {
+            return
+        }
+        if (ContextCompat.checkSelfPermission(this, Manifest.permission.POST_NOTIFICATIONS)
+            == PackageManager.PERMISSION_GRANTED
+        ) {
+            return
+        }
+        runOnUiThread {
+            ActivityCompat.requestPermissions(
+                this,
+                arrayOf(Manifest.permission.POST_NOTIFICATIONS),
+                NOTIFICATION_PERMISSION_CODE
+            )
+        }
+    }
+    
     /**
      * Callback nachdem Benutzer auf einen Permission-Dialog reagiert hat.
      * - Wird sowohl für Kamera- als auch Storage-Permissions verwendet.
@@ -436,6 +531,103 @@ class MainActivity : WryActivity() {
         }
     }
     
+    /**
+     * Öffnet den System-Dateiauswahldialog für ein Import-Archiv (ZIP oder verschlüsseltes ZIP).
+     * - Braucht keine Speicher-Berechtigung: SAF gewährt Lesezugriff auf genau die gewählte Datei.
+     * - Ergebnis über `getLastDocumentPath()` bzw. `getLastError()`.
+     *
+     * Hinweis: Aufrufbar aus Rust via JNI, läuft intern auf dem UI-Thread.
+     */
+    fun launchDocumentPicker() {
+        currentDocumentPath = null
+        lastError = null
+        runOnUiThread {
+            try {
+                // Verschlüsselte Sicherungen (`.zip.enc`) haben keinen eigenen MIME-Typ
+                pickDocumentLauncher.launch(
+                    arrayOf("application/zip", "application/x-zip-compressed", "application/octet-stream")
+                )
+            } catch (e: Exception) {
+                lastError = "Fehler beim Öffnen der Dateiauswahl: ${e.message}"
+            }
+        }
+    }
+    
+    // Anzeigename einer `content://`-URI (ohne Pfadanteile), falls der Anbieter ihn kennt
+    private fun displayName(uri: Uri): String? =
+        contentResolver.query(uri, arrayOf(OpenableColumns.DISPLAY_NAME), null, null, null)?.use { cursor ->
+            if (cursor.moveToFirst()) cursor.getString(0) else null
+        }?.substringAfterLast('/')?.takeIf { it.isNotBlank() }
+    
+    /**
+     * Öffnet die Login-URL des Nextcloud Login Flow v2 in einem Vollbild-Dialog mit WebView.
+     * - Das Polling läuft währenddessen in Rust weiter; nach erfolgreicher Anmeldung schließt
+     *   die Rust-Seite den Dialog über `closeLoginView()`.
+     * - Schließt der Nutzer den Dialog (Zurück-Taste), bleibt das Polling aktiv und die Anmeldung
+     *   kann weiterhin über den externen Browser abgeschlossen werden.
+     *
+     * Hinweis: Aufrufbar aus Rust via JNI, läuft intern auf dem UI-Thread.
+     */
+    fun openLoginView(url: String) {
+        runOnUiThread {
+            try {
+                loginDialog?.dismiss()
+                val webView = WebView(this).apply {
+                    settings.javaScriptEnabled = true
+                    settings.domStorageEnabled = true
+                    // Alle Navigationen (Login, 2FA, Bestätigung) im Dialog behalten
+                    webViewClient = WebViewClient()
+                    loadUrl(url)
+                }
+                loginDialog = Dialog(this, android.R.style.Theme_DeviceDefault_Light_NoActionBar).apply {
+                    setContentView(webView)
+                    setOnDismissListener { webView.destroy() }
+                    show()
+                }
+            } catch (e: Exception) {
+                lastError = "Fehler beim Öffnen der Anmeldung: ${e.message}"
+            }
+        }
+    }
+    
+    /**
+     * Öffnet das Android-Teilen-Menü für eine exportierte Datei (ZIP, CSV, PDF).
+     *
+     * - Die Datei muss im Export-Ordner (`getExternalFilesDir("exports")`) liegen und wird über den
+     *   `FileProvider` als `content://`-URI mit Leserecht an die Ziel-App übergeben.
+     * - Fehler werden in `lastError` abgelegt.
+     *
+     * Hinweis: Aufrufbar aus Rust via JNI, läuft intern auf dem UI-Thread.
+     */
+    fun shareFile(path: String, mimeType: String, title: String) {
+        runOnUiThread {
+            try {
+                val file = File(path)
+                val uri = FileProvider.getUriForFile(this, "${packageName}.fileprovider", file)
+                val send = Intent(Intent.ACTION_SEND).apply {
+                    type = mimeType
+                    putExtra(Intent.EXTRA_STREAM, uri)
+                    putExtra(Intent.EXTRA_SUBJECT, file.name)
+                    clipData = ClipData.newRawUri(file.name, uri)
+                    addFlags(Intent.FLAG_GRANT_READ_URI_PERMISSION)
+                }
+                startActivity(Intent.createChooser(send, title))
+            } catch (e: Exception) {
+                lastError = "Fehler beim Teilen: ${e.message}"
+            }
+        }
+    }
+
+    /**
+     * Schließt den Anmelde-Dialog (falls offen) und gibt den Fokus an die App-Oberfläche zurück.
+     */
+    fun closeLoginView() {
+        runOnUiThread {
+            loginDialog?.dismiss()
+            loginDialog = null
+        }
+    }
+    
     /**
      * Erstelle eindeutige Datei für Foto
      *
@@ -462,3 +654,112 @@ class MainActivity : WryActivity() {
         )
     }
 }
+
+/**
+ * Hintergrund-Synchronisation über WorkManager
+ *
+ * Die Rust-Seite plant diesen Worker über `schedule()` ein, statt selbst eine Schleife
+ * im App-Prozess zu betreiben. WorkManager startet den Prozess bei Bedarf neu, so dass
+ * auch im Hintergrund oder nach dem Beenden der App synchronisiert wird.
+ *
+ * Bedingungen & Ergebnis:
+ * - Läuft nur mit Netzwerkverbindung; je nach Einstellung nur ohne getaktete Verbindung
+ *   und nicht bei niedrigem Akkustand.
+ * - `runSync()` führt einen Sync-Zyklus in Rust aus und liefert `status|ops|fotos` bzw.
+ *   `status|meldung` zurück (Status: `ok`, `skip`, `retry`, `fail`).
+ * - Neue Daten und Fehler, die der Nutzer beheben muss, werden als Benachrichtigung gemeldet.
+ *   Netzwerkfehler werden still über das Backoff von WorkManager wiederholt.
+ */
+class SyncWorker(context: Context, params: WorkerParameters) : Worker(context, params) {
+    
+    companion object {
+        private const val WORK_NAME = "stalltagebuch-sync"
+        private const val CHANNEL_ID = "sync"
+        private const val NOTIFICATION_ID = 2001
+        
+        init {
+            // Startet WorkManager den Prozess ohne Activity, ist die Rust-Bibliothek noch nicht geladen
+            System.loadLibrary("dioxusmain")
+        }
+        
+        @JvmStatic
+        private external fun runSync(context: Context): String?
+        
+        /**
+         * Plant die periodische Synchronisation ein (bestehende Planung wird aktualisiert).
+         * - Android erlaubt minimal 15 Minuten; kürzere Intervalle werden angehoben.
+         * - `unmetered` / `batteryNotLow` entsprechen den Pausen-Einstellungen des Syncs.
+         * Aufrufbar aus Rust via JNI.
+         */
+        @JvmStatic
+        fun schedule(context: Context, intervalSeconds: Long, unmetered: Boolean, batteryNotLow: Boolean) {
+            val constraints = Constraints.Builder()
+                .setRequiredNetworkType(if (unmetered) NetworkType.UNMETERED else NetworkType.CONNECTED)
+                .setRequiresBatteryNotLow(batteryNotLow)
+                .build()
+            val request = PeriodicWorkRequestBuilder<SyncWorker>(intervalSeconds, TimeUnit.SECONDS)
+                .setConstraints(constraints)
+                .setBackoffCriteria(BackoffPolicy.EXPONENTIAL, 1, TimeUnit.MINUTES)
+                .build()
+            WorkManager.getInstance(context).enqueueUniquePeriodicWork(
+                WORK_NAME,
+                ExistingPeriodicWorkPolicy.UPDATE,
+                request
+            )
+            (context as? MainActivity)?.requestNotificationPermission()
+        }
+        
+        /**
+         * Beendet die periodische Synchronisation. Aufrufbar aus Rust via JNI.
+         */
+        @JvmStatic
+        fun cancel(context: Context) {
+            WorkManager.getInstance(context).cancelUniqueWork(WORK_NAME)
+        }
+    }
+    
+    override fun doWork(): Result {
+        val parts = (runSync(applicationContext) ?: "retry").split("|", limit = 3)
+        return when (parts[0]) {
+            "ok" -> {
+                val ops = parts.getOrNull(1)?.toIntOrNull() ?: 0
+                val photos = parts.getOrNull(2)?.toIntOrNull() ?: 0
+                if (ops > 0 || photos > 0) {
+                    notify("Synchronisiert", "$ops Änderungen empfangen, $photos Fotos hochgeladen")
+                }
+                Result.success()
+            }
+            "skip" -> Result.success()
+            "retry" -> Result.retry()
+            else -> {
+                notify("Synchronisierung fehlgeschlagen", parts.getOrNull(1) ?: "")
+                Result.failure()
+            }
+        }
+    }
+    
+    /**
+     * Zeigt das Ergebnis als Benachrichtigung (ersetzt die vorherige).
+     * - Ohne Berechtigung (Android 13+) wird nichts angezeigt.
+     */
+    private fun notify(title: String, text: String) {
+        val manager = NotificationManagerCompat.from(applicationContext)
+        if (!manager.areNotificationsEnabled()) {
+            return
+        }
+        manager.createNotificationChannel(
+            NotificationChannel(CHANNEL_ID, "Synchronisierung", NotificationManager.IMPORTANCE_LOW)
+        )
+        val notification = NotificationCompat.Builder(applicationContext, CHANNEL_ID)
+            .setSmallIcon(applicationContext.applicationInfo.icon)
+            .setContentTitle(title)
+            .setContentText(text)
+            .setAutoCancel(true)
+            .build()
+        try {
+            manager.notify(NOTIFICATION_ID, notification)
+        } catch (e: SecurityException) {
+            // Berechtigung wurde zwischenzeitlich entzogen
+        }
+    }
+}
@@ -6,7 +6,8 @@ set -euo pipefail
 # This script handles:
 # 1) Running dx build
 # 2) Copying res/xml resources (file_paths.xml for FileProvider)
-# 3) APK signing for release builds
+# 3) Adding Gradle dependencies the dx template lacks (WorkManager for the SyncWorker)
+# 4) APK signing for release builds
 
 ROOT_DIR="$(cd "$(dirname "${BASH_SOURCE[0]}")" && pwd)"
 
@@ -28,6 +29,8 @@ DX_APP_DIR="$ROOT_DIR/target/dx/stalltagebuch/$BUILD_TYPE/android/app"
 APP_SRC_MAIN="$DX_APP_DIR/app/src/main"
 RES_XML_DIR="$APP_SRC_MAIN/res/xml"
 BUILD_CONFIG_FILE="$APP_SRC_MAIN/kotlin/dev/dioxus/main/BuildConfig.kt"
+APP_GRADLE_FILE="$DX_APP_DIR/app/build.gradle.kts"
+WORK_DEPENDENCY='implementation("androidx.work:work-runtime-ktx:2.9.1")'
 
 prepare_android_overrides() {
     local step_label="$1"
@@ -46,6 +49,15 @@ EOF
     fi
 }
 
+# dx regenerates build.gradle.kts on every build; returns 0 if the dependency had to be added
+add_gradle_dependencies() {
+    if [[ ! -f "$APP_GRADLE_FILE" ]] || grep -qF "$WORK_DEPENDENCY" "$APP_GRADLE_FILE"; then
+        return 1
+    fi
+    echo "Adding WorkManager dependency to $APP_GRADLE_FILE"
+    sed -i "0,/^dependencies[[:space:]]*{/s//dependencies {\n    $WORK_DEPENDENCY/" "$APP_GRADLE_FILE"
+}
+
 # Find Android SDK build-tools (zipalign and apksigner)
 find_build_tools() {
     local sdk_path="${ANDROID_HOME:-${ANDROID_SDK_ROOT:-}}"
@@ -83,9 +95,17 @@ dx build --platform android ${RELEASE_FLAG} 2>&1 | tee "$DX_LOG"
 DX_EXIT=${PIPESTATUS[0]}
 set -e
 
+# Without the WorkManager dependency the Kotlin compile inside dx fails; Gradle is re-run below
+GRADLE_PATCHED=false
+if add_gradle_dependencies; then
+    GRADLE_PATCHED=true
+fi
+
 if [[ $DX_EXIT -ne 0 ]]; then
     if [[ "$BUILD_TYPE" == "release" ]] && grep -qi "lint" "$DX_LOG"; then
         echo "⚠ dx build reported a lint failure; continuing with lint disabled for release"
+    elif [[ "$GRADLE_PATCHED" == "true" ]]; then
+        echo "⚠ dx build failed before the Gradle dependencies were added; re-running Gradle"
     else
         echo "dx build failed (see $DX_LOG)"
         exit $DX_EXIT
@@ -104,6 +124,11 @@ if [[ "$BUILD_TYPE" == "release" ]]; then
         -x lintVitalRelease \
         assembleRelease
     popd >/dev/null
+elif [[ "$GRADLE_PATCHED" == "true" ]]; then
+    echo "[3/3] Running Gradle assembleDebug with the added dependencies"
+    pushd "$DX_APP_DIR" >/dev/null
+    ./gradlew assembleDebug
+    popd >/dev/null
 fi
 
 # Emit install hint for the generated APK
@@ -1,16 +1,62 @@
+use dioxus::html::geometry::PixelsRect;
 use dioxus::prelude::*;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+mod theme;
+
+use theme::{classes, ThemeStylesheet};
+pub use theme::{GalleryTheme, GALLERY_CSS};
+
+/// Loading state of the image of a [`GalleryItem`]
+#[derive(Clone, PartialEq, Debug, Default)]
+pub enum GalleryItemState {
+    /// Not available yet; shown as a shimmering skeleton tile
+    #[default]
+    Loading,
+    /// Image source: a URL served by the app (preferred for big galleries)
+    /// or a base64 data URL
+    Ready(String),
+    /// Loading failed with the given message; shown as a broken image with
+    /// a retry button
+    Error(String),
+}
+
+impl GalleryItemState {
+    /// Image source, if the image is ready
+    pub fn data_url(&self) -> Option<&str> {
+        match self {
+            GalleryItemState::Ready(data_url) => Some(data_url),
+            _ => None,
+        }
+    }
+}
 
 /// Represents a single item in the gallery
 #[derive(Clone, PartialEq, Debug)]
 pub struct GalleryItem {
     /// Unique identifier for the item
     pub id: String,
-    /// Data URL or path to the image (can be base64-encoded)
-    pub data_url: String,
+    /// The image or why it is missing
+    pub state: GalleryItemState,
     /// Optional caption or description
     pub caption: Option<String>,
 }
 
+/// How the [`Gallery`] arranges its items
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub enum GalleryLayout {
+    /// Square tiles in a grid; images are cropped to fill them
+    #[default]
+    Grid,
+    /// Columns of tiles with the original aspect ratio (no cropping); items
+    /// run top to bottom through the columns
+    Masonry,
+    /// Rows of equal height filled edge to edge; tiles keep roughly the
+    /// original aspect ratio and only lose a little at the sides
+    Justified,
+}
+
 /// Configuration for the Gallery component
 #[derive(Clone, PartialEq, Default)]
 pub struct GalleryConfig {
@@ -20,6 +66,15 @@ pub struct GalleryConfig {
     pub allow_select: bool,
     /// ID of the currently selected item (for selection mode)
     pub selected_id: Option<String>,
+    /// Whether captions can be edited on the tiles (reported through
+    /// `on_caption_change`)
+    pub allow_caption_edit: bool,
+    /// Arrangement of the items (only used by [`Gallery`]; the
+    /// [`VirtualGallery`] always shows a square grid)
+    pub layout: GalleryLayout,
+    /// Whether items can be dragged to a new position (mouse drag or touch
+    /// long-press; only used by [`Gallery`], reported through `on_reorder`)
+    pub allow_reorder: bool,
 }
 
 /// A reusable photo gallery component for Dioxus
@@ -33,7 +88,7 @@ pub struct GalleryConfig {
 ///     items: vec![
 ///         GalleryItem {
 ///             id: "1".to_string(),
-///             data_url: "data:image/jpeg;base64,...".to_string(),
+///             state: GalleryItemState::Ready("data:image/jpeg;base64,...".to_string()),
 ///             caption: None,
 ///         }
 ///     ],
@@ -41,6 +96,9 @@ pub struct GalleryConfig {
 ///         allow_delete: true,
 ///         allow_select: false,
 ///         selected_id: None,
+///         allow_caption_edit: false,
+///         layout: GalleryLayout::Grid,
+///         allow_reorder: false,
 ///     },
 ///     on_delete: move |id| {
 ///         // Handle deletion in parent
@@ -60,6 +118,18 @@ pub fn Gallery(
     /// Gallery configuration
     #[props(default)]
     config: GalleryConfig,
+    /// Smallest tile width in pixels
+    #[props(default = MIN_CELL_PX)]
+    min_tile_px: f64,
+    /// Upper limit for the number of columns
+    #[props(default)]
+    max_columns: Option<usize>,
+    /// Space between tiles in pixels
+    #[props(default = GRID_GAP)]
+    gap: f64,
+    /// Overrides of the sizes above for wider galleries
+    #[props(default)]
+    breakpoints: Vec<Breakpoint>,
     /// Callback when user requests to delete an item
     #[props(default)]
     on_delete: Option<EventHandler<String>>,
@@ -69,76 +139,601 @@ pub fn Gallery(
     /// Callback when user wants to view an item in fullscreen
     #[props(default)]
     on_view_fullscreen: Option<EventHandler<String>>,
+    /// Callback with `(id, caption)` when user edits a caption; the caption
+    /// is trimmed and may be empty
+    #[props(default)]
+    on_caption_change: Option<EventHandler<(String, String)>>,
+    /// Callback when user wants to load a failed item again
+    #[props(default)]
+    on_retry: Option<EventHandler<String>>,
+    /// Whether more items can be loaded with `on_load_more`
+    #[props(default)]
+    has_more: bool,
+    /// Shows a loading footer while the parent fetches the next items
+    #[props(default)]
+    loading_more: bool,
+    /// Callback when user scrolled near the end and `has_more` is set
+    #[props(default)]
+    on_load_more: Option<EventHandler<()>>,
+    /// Callback with `(from_index, to_index)` when user dropped an item at
+    /// a new position
+    #[props(default)]
+    on_reorder: Option<EventHandler<(usize, usize)>>,
+    /// CSS classes of the gallery
+    #[props(default)]
+    theme: GalleryTheme,
 ) -> Element {
+    // Index of the focused item and the width of the gallery (for the
+    // column count, unknown until the first resize event)
+    let mut focused = use_signal(|| None::<usize>);
+    let mut width = use_signal(|| None::<f64>);
+
+    let sizing = GridSizing {
+        min_tile_px,
+        max_columns,
+        gap,
+    }
+    .at_width(&breakpoints, width().unwrap_or(0.0));
+    let gap = sizing.gap;
+    let columns = width().map(|width| sizing.columns(width));
+
+    // Reordering: the tiles report mouse and touch input, the gallery keeps
+    // the drag state as (from, over) while an item is dragged
+    let mut drag = use_signal(|| None::<(usize, usize)>);
+    let mut mouse_pressed = use_signal(|| None::<usize>);
+    // A touch that becomes a drag when held still: (tile, x, y, generation)
+    let mut touch_press = use_signal(|| None::<(usize, f64, f64, u64)>);
+    let mut touch_generation = use_signal(|| 0u64);
+    let mut tiles = use_signal(HashMap::<usize, Rc<MountedData>>::new);
+    let mut tile_rects = use_signal(Vec::<(usize, PixelsRect)>::new);
+    // Set by a drag so the click that ends it does not open the item
+    let mut suppress_click = use_signal(|| false);
+
+    let mut finish_drag = move |drop: bool| {
+        mouse_pressed.set(None);
+        touch_press.set(None);
+        if let Some((from, over)) = drag.take() {
+            suppress_click.set(true);
+            if drop && from != over {
+                if let Some(handler) = &on_reorder {
+                    handler.call((from, over));
+                }
+            }
+        }
+    };
+    let mut handle_drag = move |index: usize, input: TileDrag| match input {
+        TileDrag::MouseDown => {
+            suppress_click.set(false);
+            mouse_pressed.set(Some(index));
+        }
+        TileDrag::MouseEnter => {
+            if let Some(from) = mouse_pressed() {
+                drag.set(Some((from, index)));
+            }
+        }
+        TileDrag::TouchStart(x, y) => {
+            suppress_click.set(false);
+            let generation = touch_generation() + 1;
+            touch_generation.set(generation);
+            touch_press.set(Some((index, x, y, generation)));
+            spawn(async move {
+                let _ = document::eval(&format!(
+                    "await new Promise(resolve => setTimeout(resolve, {LONG_PRESS_MS})); return true;"
+                ))
+                .await;
+                if !matches!(touch_press(), Some((.., g)) if g == generation) {
+                    return;
+                }
+                drag.set(Some((index, index)));
+                // Nothing scrolls during the drag, so the positions stay valid
+                let mounted: Vec<_> = tiles
+                    .peek()
+                    .iter()
+                    .map(|(i, tile)| (*i, tile.clone()))
+                    .collect();
+                let mut rects = Vec::new();
+                for (i, tile) in mounted {
+                    if let Ok(rect) = tile.get_client_rect().await {
+                        rects.push((i, rect));
+                    }
+                }
+                tile_rects.set(rects);
+            });
+        }
+        TileDrag::TouchMove(x, y) => {
+            if let Some((from, _)) = drag() {
+                let over = tile_rects.peek().iter().find_map(|(i, rect)| {
+                    let inside = x >= rect.origin.x
+                        && x <= rect.origin.x + rect.size.width
+                        && y >= rect.origin.y
+                        && y <= rect.origin.y + rect.size.height;
+                    inside.then_some(*i)
+                });
+                if let Some(over) = over {
+                    drag.set(Some((from, over)));
+                }
+            } else if let Some((_, start_x, start_y, _)) = touch_press() {
+                // Moved before the long press: the user scrolls
+                if (x - start_x).hypot(y - start_y) > TOUCH_SLOP_PX {
+                    touch_press.set(None);
+                }
+            }
+        }
+        TileDrag::TouchEnd => finish_drag(true),
+        TileDrag::TouchCancel => finish_drag(false),
+    };
+
     if items.is_empty() {
         return rsx! {
-            div {
-                style: "padding: 24px; text-align: center; background: #f5f5f5; border-radius: 8px; color: #999;",
-                "No photos available"
-            }
+            ThemeStylesheet { theme: theme.clone() }
+            div { class: "{theme.empty}", "No photos available" }
         };
     }
 
+    let count = items.len();
+    let layout = config.layout;
+    let (grid_class, grid_style) = match (layout, columns) {
+        (GalleryLayout::Grid, Some(columns)) => (
+            theme.grid.clone(),
+            format!("display: grid; grid-template-columns: repeat({columns}, 1fr); gap: {gap}px;"),
+        ),
+        // Until the width is known the browser picks the columns
+        (GalleryLayout::Grid, None) => (
+            theme.grid.clone(),
+            format!(
+                "display: grid; grid-template-columns: repeat(auto-fill, minmax({}px, 1fr)); gap: {gap}px;",
+                sizing.min_tile_px
+            ),
+        ),
+        (GalleryLayout::Masonry, columns) => {
+            let count = columns.map_or("auto".to_string(), |count| count.to_string());
+            (
+                classes(&theme.grid, &theme.grid_masonry),
+                format!(
+                    "column-width: {}px; column-count: {count}; column-gap: {gap}px; --dg-gap: {gap}px;",
+                    sizing.min_tile_px
+                ),
+            )
+        }
+        (GalleryLayout::Justified, _) => (
+            classes(&theme.grid, &theme.grid_justified),
+            format!("display: flex; flex-wrap: wrap; gap: {gap}px;"),
+        ),
+    };
     rsx! {
+        ThemeStylesheet { theme: theme.clone() }
         div {
-            style: "display: grid; grid-template-columns: repeat(auto-fill, minmax(120px, 1fr)); gap: 12px;",
-            for item in items {
+            class: "{grid_class}",
+            style: "{grid_style}",
+            onresize: move |e| {
+                if let Ok(size) = e.data().get_content_box_size() {
+                    width.set(Some(size.width));
+                }
+            },
+            onpointerup: move |e| {
+                if e.pointer_type() != "touch" {
+                    finish_drag(true);
+                }
+            },
+            onpointerleave: move |e| {
+                if e.pointer_type() != "touch" {
+                    finish_drag(false);
+                }
+            },
+            onkeydown: move |e| {
+                suppress_click.set(false);
+                let Some(current) = focused() else {
+                    return;
+                };
+                // Only the square grid has fixed rows; the other layouts
+                // step through the items in order
+                let row = match layout {
+                    GalleryLayout::Grid => columns.unwrap_or(1),
+                    _ => 1,
+                };
+                let next = match e.key() {
+                    Key::ArrowLeft => current.saturating_sub(1),
+                    Key::ArrowRight => current + 1,
+                    Key::ArrowUp => current.saturating_sub(row),
+                    Key::ArrowDown => current + row,
+                    Key::Home => 0,
+                    Key::End => count - 1,
+                    _ => return,
+                };
+                e.prevent_default();
+                focused.set(Some(next.min(count - 1)));
+            },
+            for (index, item) in items.into_iter().enumerate() {
                 GalleryItemView {
-                    item: item.clone(),
-                    is_selected: config.selected_id.as_ref().map(|s| s.as_str()) == Some(&item.id),
+                    is_selected: config.selected_id.as_deref() == Some(&item.id),
+                    item,
                     allow_delete: config.allow_delete,
                     allow_select: config.allow_select,
-                    on_delete: on_delete.clone(),
-                    on_select: on_select.clone(),
-                    on_view_fullscreen: on_view_fullscreen.clone(),
+                    allow_caption_edit: config.allow_caption_edit,
+                    has_focus: focused() == Some(index),
+                    on_focus: move |_| focused.set(Some(index)),
+                    reorderable: config.allow_reorder,
+                    dragging: drag().is_some_and(|(from, _)| from == index),
+                    drop_target: drag().is_some_and(|(from, over)| over == index && from != index),
+                    on_drag: move |input| handle_drag(index, input),
+                    on_mounted: move |tile| {
+                        tiles.write().insert(index, tile);
+                    },
+                    on_delete,
+                    on_select: move |id| {
+                        if !suppress_click.replace(false) {
+                            if let Some(handler) = &on_select {
+                                handler.call(id);
+                            }
+                        }
+                    },
+                    on_view_fullscreen: move |id| {
+                        if !suppress_click.replace(false) {
+                            if let Some(handler) = &on_view_fullscreen {
+                                handler.call(id);
+                            }
+                        }
+                    },
+                    on_caption_change,
+                    on_retry,
+                    theme: theme.clone(),
                 }
             }
         }
+        LoadMoreSentinel {
+            has_more,
+            loading_more,
+            on_load_more,
+            theme: theme.clone(),
+        }
     }
 }
 
+/// Default smallest tile width of the [`Gallery`] in pixels
+const MIN_CELL_PX: f64 = 120.0;
+
+/// Tile sizes of a [`Gallery`] from a given gallery width on
+///
+/// Breakpoints refer to the width of the gallery itself, not the window.
+/// All matching breakpoints apply in order; unset fields keep the value of
+/// the smaller breakpoint or the gallery props.
+///
+/// # Example
+/// ```rust,ignore
+/// Gallery {
+///     items,
+///     max_columns: 3,
+///     breakpoints: vec![
+///         Breakpoint { min_width: 600.0, max_columns: Some(6), ..Default::default() },
+///         Breakpoint { min_width: 1000.0, max_columns: Some(8), gap: Some(16.0), ..Default::default() },
+///     ],
+/// }
+/// ```
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct Breakpoint {
+    /// Smallest gallery width in pixels this breakpoint applies to
+    pub min_width: f64,
+    pub min_tile_px: Option<f64>,
+    pub max_columns: Option<usize>,
+    pub gap: Option<f64>,
+}
+
+/// Tile sizes of a [`Gallery`] after applying the breakpoints
+#[derive(Clone, Copy, PartialEq, Debug)]
+struct GridSizing {
+    min_tile_px: f64,
+    max_columns: Option<usize>,
+    gap: f64,
+}
+
+impl GridSizing {
+    /// Sizes for a gallery of the given width
+    fn at_width(mut self, breakpoints: &[Breakpoint], width: f64) -> Self {
+        let mut matching: Vec<&Breakpoint> = breakpoints
+            .iter()
+            .filter(|breakpoint| breakpoint.min_width <= width)
+            .collect();
+        matching.sort_by(|a, b| a.min_width.total_cmp(&b.min_width));
+        for breakpoint in matching {
+            self.min_tile_px = breakpoint.min_tile_px.unwrap_or(self.min_tile_px);
+            self.max_columns = breakpoint.max_columns.or(self.max_columns);
+            self.gap = breakpoint.gap.unwrap_or(self.gap);
+        }
+        self
+    }
+
+    /// Columns of a `repeat(auto-fill, minmax(min_tile_px, 1fr))` grid,
+    /// limited to `max_columns`
+    fn columns(&self, width: f64) -> usize {
+        let fitting =
+            ((width + self.gap) / (self.min_tile_px.max(1.0) + self.gap)).floor() as usize;
+        fitting.min(self.max_columns.unwrap_or(usize::MAX)).max(1)
+    }
+}
+
+/// Distance from the end of a gallery (in pixels) at which more items are
+/// requested
+const LOAD_MORE_MARGIN_PX: f64 = 600.0;
+
+/// End of a gallery: asks for more items once it comes near the visible
+/// area and shows the loading footer
+///
+/// The area is checked again after each load, so a page that does not fill
+/// the screen leads to the next request right away.
+#[component]
+fn LoadMoreSentinel(
+    has_more: bool,
+    loading_more: bool,
+    on_load_more: Option<EventHandler<()>>,
+    theme: GalleryTheme,
+) -> Element {
+    let mut near_end = use_signal(|| false);
+
+    use_effect(use_reactive!(|has_more, loading_more| {
+        if near_end() && has_more && !loading_more {
+            if let Some(handler) = &on_load_more {
+                handler.call(());
+            }
+        }
+    }));
+
+    if !has_more && !loading_more {
+        return rsx! {};
+    }
+    let footer_class = classes(&theme.placeholder, &theme.loading_footer);
+    rsx! {
+        div { style: "position: relative; height: 0;",
+            div {
+                style: "position: absolute; left: 0; bottom: 0; width: 1px; height: {LOAD_MORE_MARGIN_PX}px; pointer-events: none;",
+                onvisible: move |e| near_end.set(e.is_intersecting().unwrap_or(false)),
+            }
+        }
+        if loading_more {
+            div { class: "{footer_class}", role: "status", aria_busy: "true" }
+        }
+    }
+}
+
+/// How long a finger has to rest on a tile before it can be dragged
+const LONG_PRESS_MS: u32 = 450;
+
+/// Movement in pixels that turns a touch into a scroll instead of a drag
+const TOUCH_SLOP_PX: f64 = 10.0;
+
+/// Mouse and touch input of a tile, handled by the [`Gallery`] for
+/// reordering
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum TileDrag {
+    MouseDown,
+    MouseEnter,
+    /// Client coordinates of the finger
+    TouchStart(f64, f64),
+    TouchMove(f64, f64),
+    TouchEnd,
+    TouchCancel,
+}
+
 /// Internal component for rendering a single gallery item
+///
+/// The tile is focusable: Enter/Space act like a click, Delete removes the
+/// item (if allowed). Arrow keys are handled by the surrounding gallery.
+/// With `allow_caption_edit` a pencil button turns the caption into a text
+/// input; Enter or leaving the input saves, Esc discards. A `reorderable`
+/// tile forwards its mouse and touch input through `on_drag`.
 #[component]
 fn GalleryItemView(
     item: GalleryItem,
     is_selected: bool,
     allow_delete: bool,
     allow_select: bool,
+    #[props(default)] allow_caption_edit: bool,
+    /// Moves the keyboard focus to this tile
+    #[props(default)]
+    has_focus: bool,
+    #[props(default)] on_focus: Option<EventHandler<()>>,
+    #[props(default)] reorderable: bool,
+    /// This tile is being dragged
+    #[props(default)]
+    dragging: bool,
+    /// The dragged tile would be dropped here
+    #[props(default)]
+    drop_target: bool,
+    #[props(default)] on_drag: Option<EventHandler<TileDrag>>,
+    #[props(default)] on_mounted: Option<EventHandler<Rc<MountedData>>>,
     on_delete: Option<EventHandler<String>>,
     on_select: Option<EventHandler<String>>,
     on_view_fullscreen: Option<EventHandler<String>>,
+    #[props(default)] on_caption_change: Option<EventHandler<(String, String)>>,
+    #[props(default)] on_retry: Option<EventHandler<String>>,
+    theme: GalleryTheme,
 ) -> Element {
-    let border_color = if is_selected { "#0066cc" } else { "#e0e0e0" };
-    let photo_style = format!(
-        "position: relative; aspect-ratio: 1/1; border-radius: 8px; overflow: hidden; border: 2px solid {};",
-        border_color
-    );
+    let mut tile_class = theme.item.clone();
+    for (active, modifier) in [
+        (is_selected, &theme.item_selected),
+        (dragging, &theme.item_dragging),
+        (drop_target, &theme.item_drop_target),
+    ] {
+        if active {
+            tile_class = classes(&tile_class, modifier);
+        }
+    }
+    let drag_input = move |input: TileDrag| {
+        if let Some(handler) = &on_drag {
+            handler.call(input);
+        }
+    };
+
+    let mut tile = use_signal(|| None::<Rc<MountedData>>);
+    // Draft of the caption while the input is open
+    let mut caption_draft = use_signal(|| None::<String>);
+
+    let current_caption = item.caption.clone().unwrap_or_default();
+    let mut finish_caption_edit = {
+        let item_id = item.id.clone();
+        let current_caption = current_caption.clone();
+        move |save: bool| {
+            // Enter closes the input, which then also fires blur
+            let Some(draft) = caption_draft.take() else {
+                return;
+            };
+            let caption = draft.trim().to_string();
+            if save && caption != current_caption.trim() {
+                if let Some(handler) = &on_caption_change {
+                    handler.call((item_id.clone(), caption));
+                }
+            }
+        }
+    };
+    use_effect(use_reactive!(|has_focus| {
+        if let Some(tile) = tile.peek().clone().filter(|_| has_focus) {
+            spawn(async move {
+                let _ = tile.set_focus(true).await;
+            });
+        }
+    }));
+
+    let activate = {
+        let item_id = item.id.clone();
+        move || {
+            if allow_select {
+                if let Some(handler) = &on_select {
+                    handler.call(item_id.clone());
+                }
+            } else if let Some(handler) = &on_view_fullscreen {
+                handler.call(item_id.clone());
+            }
+        }
+    };
 
     rsx! {
         div {
             key: "{item.id}",
-            style: "{photo_style}",
-            // Image
-            img {
-                src: "{item.data_url}",
-                style: "width: 100%; height: 100%; object-fit: cover; cursor: pointer;",
-                onclick: {
-                    let item_id = item.id.clone();
-                    move |_| {
-                        if allow_select {
-                            if let Some(handler) = &on_select {
-                                handler.call(item_id.clone());
-                            }
-                        } else if let Some(handler) = &on_view_fullscreen {
+            class: "{tile_class}",
+            tabindex: "0",
+            role: "button",
+            aria_label: item.caption.clone().unwrap_or_else(|| item.id.clone()),
+            aria_pressed: if allow_select { Some(is_selected.to_string()) } else { None },
+            onmounted: move |e| {
+                if let Some(handler) = &on_mounted {
+                    handler.call(e.data());
+                }
+                tile.set(Some(e.data()));
+            },
+            onpointerdown: move |e| {
+                if reorderable && e.pointer_type() != "touch" && e.is_primary() {
+                    drag_input(TileDrag::MouseDown);
+                }
+            },
+            onpointerenter: move |e| {
+                if reorderable && e.pointer_type() != "touch" {
+                    drag_input(TileDrag::MouseEnter);
+                }
+            },
+            ontouchstart: move |e| {
+                if let Some(touch) = e.touches().first().filter(|_| reorderable) {
+                    let point = touch.client_coordinates();
+                    drag_input(TileDrag::TouchStart(point.x, point.y));
+                }
+            },
+            ontouchmove: move |e| {
+                if !reorderable {
+                    return;
+                }
+                // Keep the page from scrolling while the tile is dragged
+                if dragging {
+                    e.prevent_default();
+                }
+                if let Some(touch) = e.touches().first() {
+                    let point = touch.client_coordinates();
+                    drag_input(TileDrag::TouchMove(point.x, point.y));
+                }
+            },
+            ontouchend: move |_| {
+                if reorderable {
+                    drag_input(TileDrag::TouchEnd);
+                }
+            },
+            ontouchcancel: move |_| {
+                if reorderable {
+                    drag_input(TileDrag::TouchCancel);
+                }
+            },
+            oncontextmenu: move |e| {
+                // A long press starts a drag instead of the context menu
+                if reorderable {
+                    e.prevent_default();
+                }
+            },
+            onfocus: move |_| {
+                if let Some(handler) = &on_focus {
+                    handler.call(());
+                }
+            },
+            onkeydown: {
+                let activate = activate.clone();
+                let item_id = item.id.clone();
+                let current_caption = current_caption.clone();
+                move |e: KeyboardEvent| match e.key() {
+                    Key::Enter => activate(),
+                    Key::Character(c) if c == " " => {
+                        e.prevent_default();
+                        activate();
+                    }
+                    Key::F2 if allow_caption_edit => {
+                        caption_draft.set(Some(current_caption.clone()));
+                    }
+                    Key::Delete if allow_delete => {
+                        if let Some(handler) = &on_delete {
                             handler.call(item_id.clone());
                         }
                     }
+                    _ => {}
+                }
+            },
+            match &item.state {
+                GalleryItemState::Loading => rsx! {
+                    div { class: "{theme.placeholder}", aria_busy: "true" }
+                },
+                GalleryItemState::Ready(data_url) => rsx! {
+                    img {
+                        src: "{data_url}",
+                        class: "{theme.image}",
+                        // The browser's own image drag would swallow the pointer events
+                        draggable: "false",
+                        onclick: {
+                            let activate = activate.clone();
+                            move |_| activate()
+                        },
+                    }
+                },
+                GalleryItemState::Error(message) => rsx! {
+                    div { class: "{theme.error_tile}", title: "{message}",
+                        span { "🖼️" }
+                        if on_retry.is_some() {
+                            button {
+                                class: "{theme.retry_button}",
+                                tabindex: "-1",
+                                aria_label: "Retry",
+                                onclick: {
+                                    let item_id = item.id.clone();
+                                    move |_| {
+                                        if let Some(handler) = &on_retry {
+                                            handler.call(item_id.clone());
+                                        }
+                                    }
+                                },
+                                "↻"
+                            }
+                        }
+                    }
                 },
             }
             // Delete button
             if allow_delete {
                 button {
-                    style: "position: absolute; top: 4px; right: 4px; width: 28px; height: 28px; background: rgba(204, 0, 0, 0.9); color: white; border-radius: 50%; font-size: 14px; display: flex; align-items: center; justify-content: center; cursor: pointer; border: none;",
+                    // Reached via the Delete key on the tile instead of Tab
+                    tabindex: "-1",
+                    class: "{theme.delete_button}",
                     onclick: {
                         let item_id = item.id.clone();
                         move |_| {
@@ -152,18 +747,264 @@ fn GalleryItemView(
             }
             // Selection indicator
             if is_selected && allow_select {
+                div { class: "{theme.selected_badge}", "✓" }
+            }
+            // Caption
+            if let Some(draft) = caption_draft() {
+                input {
+                    class: "{theme.caption_input}",
+                    r#type: "text",
+                    value: "{draft}",
+                    aria_label: "Caption",
+                    onmounted: move |e| async move {
+                        let _ = e.data().set_focus(true).await;
+                    },
+                    oninput: move |e| caption_draft.set(Some(e.value())),
+                    onkeydown: {
+                        let mut finish_caption_edit = finish_caption_edit.clone();
+                        move |e: KeyboardEvent| {
+                            // Keep Space, Delete and the arrows away from the tile
+                            e.stop_propagation();
+                            match e.key() {
+                                Key::Enter => finish_caption_edit(true),
+                                Key::Escape => finish_caption_edit(false),
+                                _ => {}
+                            }
+                        }
+                    },
+                    onblur: move |_| finish_caption_edit(true),
+                }
+            } else if allow_caption_edit || !current_caption.is_empty() {
                 div {
-                    style: "position: absolute; bottom: 4px; right: 4px; width: 24px; height: 24px; background: #0066cc; border-radius: 50%; display: flex; align-items: center; justify-content: center; color: white; font-size: 16px;",
-                    "✓"
+                    class: "{theme.caption_bar}",
+                    span { "{current_caption}" }
+                    if allow_caption_edit {
+                        button {
+                            class: "{theme.caption_edit_button}",
+                            tabindex: "-1",
+                            aria_label: "Edit caption",
+                            onclick: {
+                                let current_caption = current_caption.clone();
+                                move |_| caption_draft.set(Some(current_caption.clone()))
+                            },
+                            "✏️"
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Gap between gallery cells in pixels (default of the [`Gallery`])
+const GRID_GAP: f64 = 12.0;
+
+/// Items inside the scrolled window of a [`VirtualGallery`], extended by
+/// `overscan` rows above and below
+fn visible_range(
+    scroll_top: f64,
+    viewport_height: f64,
+    row_height: f64,
+    columns: usize,
+    overscan: usize,
+    len: usize,
+) -> std::ops::Range<usize> {
+    let first_row = ((scroll_top.max(0.0) / row_height).floor() as usize).saturating_sub(overscan);
+    let last_row =
+        ((scroll_top.max(0.0) + viewport_height) / row_height).ceil() as usize + overscan;
+    (first_row * columns).min(len)..(last_row * columns).min(len)
+}
+
+/// A gallery that only renders the cells near the visible part of its
+/// scroll area
+///
+/// Meant for collections with hundreds of photos. Items may be passed in
+/// the [`GalleryItemState::Loading`] state; a skeleton is shown and the ids
+/// of such items are reported through `on_request_item_data` once they
+/// scroll into view (again on every scroll while they keep loading), so the
+/// parent only produces image data for cells the user can see.
+///
+/// # Example
+/// ```rust,ignore
+/// VirtualGallery {
+///     items: photos.iter().map(|p| GalleryItem {
+///         id: p.id.clone(),
+///         state: loaded.get(&p.id).cloned().unwrap_or_default(),
+///         caption: None,
+///     }).collect(),
+///     height: "70vh",
+///     on_request_item_data: move |ids: Vec<String>| {
+///         // Load the images for `ids`
+///     },
+/// }
+/// ```
+#[component]
+pub fn VirtualGallery(
+    /// List of gallery items to display
+    items: Vec<GalleryItem>,
+    /// Gallery configuration
+    #[props(default)]
+    config: GalleryConfig,
+    /// Number of columns of the grid
+    #[props(default = 3)]
+    columns: usize,
+    /// CSS height of the scroll area
+    #[props(default = "60vh".to_string())]
+    height: String,
+    /// Rows rendered above and below the visible ones
+    #[props(default = 2)]
+    overscan_rows: usize,
+    /// Callback with the ids of visible items that are still loading
+    #[props(default)]
+    on_request_item_data: Option<EventHandler<Vec<String>>>,
+    /// Callback when user requests to delete an item
+    #[props(default)]
+    on_delete: Option<EventHandler<String>>,
+    /// Callback when user selects an item (for profile photo selection, etc.)
+    #[props(default)]
+    on_select: Option<EventHandler<String>>,
+    /// Callback when user wants to view an item in fullscreen
+    #[props(default)]
+    on_view_fullscreen: Option<EventHandler<String>>,
+    /// Callback with `(id, caption)` when user edits a caption; the caption
+    /// is trimmed and may be empty
+    #[props(default)]
+    on_caption_change: Option<EventHandler<(String, String)>>,
+    /// Callback when user wants to load a failed item again
+    #[props(default)]
+    on_retry: Option<EventHandler<String>>,
+    /// Whether more items can be loaded with `on_load_more`
+    #[props(default)]
+    has_more: bool,
+    /// Shows a loading footer while the parent fetches the next items
+    #[props(default)]
+    loading_more: bool,
+    /// Callback when user scrolled near the end and `has_more` is set
+    #[props(default)]
+    on_load_more: Option<EventHandler<()>>,
+    /// CSS classes of the gallery
+    #[props(default)]
+    theme: GalleryTheme,
+) -> Element {
+    let mut scroll_top = use_signal(|| 0.0f64);
+    // Size of the scroll area (width, height); a phone-sized guess until
+    // the first resize event arrives
+    let mut viewport = use_signal(|| (360.0f64, 480.0f64));
+
+    let columns = columns.max(1);
+    let (width, viewport_height) = viewport();
+    let cell = ((width - GRID_GAP * (columns - 1) as f64) / columns as f64).max(1.0);
+    let row_height = cell + GRID_GAP;
+    let total_rows = items.len().div_ceil(columns);
+    let range = visible_range(
+        scroll_top(),
+        viewport_height,
+        row_height,
+        columns,
+        overscan_rows,
+        items.len(),
+    );
+    let first_row = range.start / columns;
+    let rendered_rows = range.len().div_ceil(columns);
+    let top_spacer = first_row as f64 * row_height;
+    let bottom_spacer = total_rows.saturating_sub(first_row + rendered_rows) as f64 * row_height;
+
+    let missing: Vec<String> = items[range.clone()]
+        .iter()
+        .filter(|item| item.state == GalleryItemState::Loading)
+        .map(|item| item.id.clone())
+        .collect();
+    use_effect(use_reactive!(|missing| {
+        if let Some(handler) = &on_request_item_data {
+            if !missing.is_empty() {
+                handler.call(missing);
+            }
+        }
+    }));
+
+    if items.is_empty() {
+        return rsx! {
+            ThemeStylesheet { theme: theme.clone() }
+            div { class: "{theme.empty}", "No photos available" }
+        };
+    }
+
+    rsx! {
+        ThemeStylesheet { theme: theme.clone() }
+        div {
+            style: "height: {height}; overflow-y: auto;",
+            onscroll: move |e| scroll_top.set(e.data().scroll_top()),
+            onresize: move |e| {
+                if let Ok(size) = e.data().get_content_box_size() {
+                    viewport.set((size.width, size.height));
                 }
+            },
+            div { style: "height: {top_spacer}px;" }
+            div {
+                class: "{theme.grid}",
+                style: "display: grid; grid-template-columns: repeat({columns}, 1fr); gap: {GRID_GAP}px;",
+                for item in items[range].iter().cloned() {
+                    GalleryItemView {
+                        key: "{item.id}",
+                        is_selected: config.selected_id.as_deref() == Some(&item.id),
+                        item,
+                        allow_delete: config.allow_delete,
+                        allow_select: config.allow_select,
+                        allow_caption_edit: config.allow_caption_edit,
+                        on_delete,
+                        on_select,
+                        on_view_fullscreen,
+                        on_caption_change,
+                        on_retry,
+                        theme: theme.clone(),
+                    }
+                }
+            }
+            div { style: "height: {bottom_spacer}px;" }
+            LoadMoreSentinel {
+                has_more,
+                loading_more,
+                on_load_more,
+                theme: theme.clone(),
             }
         }
     }
 }
 
+/// An extra button in the footer of the [`FullscreenViewer`]
+///
+/// Handlers should be stable across renders, e.g. created with
+/// `use_callback`.
+#[derive(Clone, PartialEq)]
+pub struct ViewerAction {
+    /// Button text (may start with an icon)
+    pub label: String,
+    /// Called with the id of the item being viewed
+    pub on_click: EventHandler<String>,
+}
+
 /// A fullscreen photo viewer component
 ///
-/// Displays a single photo in fullscreen with navigation and action buttons
+/// Displays a single photo in fullscreen with navigation and action buttons.
+/// Apps can add their own buttons (share, set as profile photo, ...) to the
+/// footer through `actions`.
+///
+/// # Example
+/// ```rust,ignore
+/// let make_profile_photo = use_callback(move |id: String| {
+///     // Store `id` as profile photo
+/// });
+///
+/// FullscreenViewer {
+///     current_item,
+///     all_items,
+///     actions: vec![ViewerAction {
+///         label: "⭐ Profile photo".to_string(),
+///         on_click: make_profile_photo,
+///     }],
+///     on_close: move |_| show_viewer.set(false),
+/// }
+/// ```
 #[component]
 pub fn FullscreenViewer(
     /// Current item being viewed
@@ -184,6 +1025,12 @@ pub fn FullscreenViewer(
     /// Callback when user navigates to next item
     #[props(default)]
     on_navigate_next: Option<EventHandler<()>>,
+    /// Extra buttons in the footer, shown before the delete button
+    #[props(default)]
+    actions: Vec<ViewerAction>,
+    /// CSS classes of the viewer
+    #[props(default)]
+    theme: GalleryTheme,
 ) -> Element {
     let current_index = all_items
         .iter()
@@ -193,14 +1040,45 @@ pub fn FullscreenViewer(
     let has_prev = current_index > 0;
     let has_next = current_index < all_items.len().saturating_sub(1);
 
+    let prev_class = classes(&theme.icon_button, &theme.nav_prev);
+    let next_class = classes(&theme.icon_button, &theme.nav_next);
+    let footer_class = classes(&theme.bar, &theme.bar_centered);
+    let delete_class = classes(&theme.button, &theme.button_danger);
+    let stage_placeholder_class = classes(&theme.placeholder, &theme.stage_placeholder);
+
     rsx! {
+        ThemeStylesheet { theme: theme.clone() }
         div {
-            style: "position: fixed; top: 0; left: 0; right: 0; bottom: 0; background: rgba(0, 0, 0, 0.95); z-index: 1000; display: flex; flex-direction: column;",
+            class: "{theme.viewer}",
+            tabindex: "0",
+            role: "dialog",
+            aria_modal: "true",
+            // Take the focus so the keys below work without clicking first
+            onmounted: move |e| async move {
+                let _ = e.data().set_focus(true).await;
+            },
+            onkeydown: move |e| {
+                match e.key() {
+                    Key::Escape => on_close.call(()),
+                    Key::ArrowLeft if has_prev => {
+                        if let Some(handler) = &on_navigate_prev {
+                            handler.call(());
+                        }
+                    }
+                    Key::ArrowRight if has_next => {
+                        if let Some(handler) = &on_navigate_next {
+                            handler.call(());
+                        }
+                    }
+                    _ => return,
+                }
+                e.prevent_default();
+            },
             // Header with close button
             div {
-                style: "display: flex; justify-content: space-between; align-items: center; padding: 16px; background: rgba(0, 0, 0, 0.7);",
+                class: "{theme.bar}",
                 div {
-                    style: "color: white; font-size: 16px;",
+                    class: "{theme.caption}",
                     if let Some(caption) = &current_item.caption {
                         "{caption}"
                     } else {
@@ -208,18 +1086,18 @@ pub fn FullscreenViewer(
                     }
                 }
                 button {
-                    style: "width: 40px; height: 40px; background: rgba(255, 255, 255, 0.2); color: white; border-radius: 50%; font-size: 24px; cursor: pointer; border: none;",
+                    class: "{theme.icon_button}",
                     onclick: move |_| on_close.call(()),
                     "×"
                 }
             }
             // Main image area
             div {
-                style: "flex: 1; display: flex; align-items: center; justify-content: center; padding: 20px; position: relative;",
+                class: "{theme.stage}",
                 // Previous button
                 if has_prev {
                     button {
-                        style: "position: absolute; left: 20px; width: 50px; height: 50px; background: rgba(255, 255, 255, 0.3); color: white; border-radius: 50%; font-size: 24px; cursor: pointer; border: none;",
+                        class: "{prev_class}",
                         onclick: move |_| {
                             if let Some(handler) = &on_navigate_prev {
                                 handler.call(());
@@ -229,14 +1107,21 @@ pub fn FullscreenViewer(
                     }
                 }
                 // Image
-                img {
-                    src: "{current_item.data_url}",
-                    style: "max-width: 100%; max-height: 100%; object-fit: contain;",
+                match &current_item.state {
+                    GalleryItemState::Loading => rsx! {
+                        div { class: "{stage_placeholder_class}", aria_busy: "true" }
+                    },
+                    GalleryItemState::Ready(data_url) => rsx! {
+                        img { src: "{data_url}", class: "{theme.stage_image}" }
+                    },
+                    GalleryItemState::Error(message) => rsx! {
+                        div { class: "{theme.caption}", "🖼️ {message}" }
+                    },
                 }
                 // Next button
                 if has_next {
                     button {
-                        style: "position: absolute; right: 20px; width: 50px; height: 50px; background: rgba(255, 255, 255, 0.3); color: white; border-radius: 50%; font-size: 24px; cursor: pointer; border: none;",
+                        class: "{next_class}",
                         onclick: move |_| {
                             if let Some(handler) = &on_navigate_next {
                                 handler.call(());
@@ -248,10 +1133,20 @@ pub fn FullscreenViewer(
             }
             // Footer with actions
             div {
-                style: "display: flex; justify-content: center; gap: 16px; padding: 16px; background: rgba(0, 0, 0, 0.7);",
+                class: "{footer_class}",
+                for action in actions {
+                    button {
+                        class: "{theme.button}",
+                        onclick: {
+                            let item_id = current_item.id.clone();
+                            move |_| action.on_click.call(item_id.clone())
+                        },
+                        "{action.label}"
+                    }
+                }
                 if allow_delete {
                     button {
-                        style: "padding: 12px 24px; background: #cc0000; color: white; border-radius: 8px; font-size: 16px; cursor: pointer; border: none;",
+                        class: "{delete_class}",
                         onclick: {
                             let item_id = current_item.id.clone();
                             move |_| {
@@ -267,3 +1162,143 @@ pub fn FullscreenViewer(
         }
     }
 }
+
+/// Crop and rotation chosen in the [`CropRotateDialog`]
+///
+/// The margins are fractions (0.0–1.0) cut off each side of the upright
+/// image; the crop is applied first, then the rotation.
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub struct CropRotate {
+    /// Clockwise rotation in degrees (0, 90, 180 or 270)
+    pub rotate: u16,
+    pub left: f32,
+    pub top: f32,
+    pub right: f32,
+    pub bottom: f32,
+}
+
+impl CropRotate {
+    /// Whether the image would be cropped at all
+    pub fn is_cropped(&self) -> bool {
+        self.left > 0.0 || self.top > 0.0 || self.right > 0.0 || self.bottom > 0.0
+    }
+}
+
+/// Largest margin per side in percent, so at least 10% of the image remains
+const MAX_MARGIN_PERCENT: u32 = 45;
+
+/// A simple crop and rotate dialog
+///
+/// Shows a live preview (CSS `clip-path` and `rotate`) and reports the chosen
+/// [`CropRotate`] via `on_apply`; the actual image processing is left to the
+/// parent.
+#[component]
+pub fn CropRotateDialog(
+    /// Image source of the photo being edited
+    src: String,
+    /// Label of the apply button
+    #[props(default = "Apply".to_string())]
+    apply_label: String,
+    /// Label of the cancel button
+    #[props(default = "Cancel".to_string())]
+    cancel_label: String,
+    /// Disables the apply button (e.g. while the parent is saving)
+    #[props(default)]
+    busy: bool,
+    /// Callback when user closes the dialog without changes
+    on_cancel: EventHandler<()>,
+    /// Callback with the chosen edit
+    on_apply: EventHandler<CropRotate>,
+    /// CSS classes of the dialog
+    #[props(default)]
+    theme: GalleryTheme,
+) -> Element {
+    let mut edit = use_signal(CropRotate::default);
+    let current = edit();
+    let percent = |fraction: f32| (fraction * 100.0).round() as u32;
+    let preview_style = format!(
+        "transition: transform 0.2s; clip-path: inset({}% {}% {}% {}%); transform: rotate({}deg);",
+        percent(current.top),
+        percent(current.right),
+        percent(current.bottom),
+        percent(current.left),
+        current.rotate
+    );
+    let unchanged = current.rotate == 0 && !current.is_cropped();
+
+    let slider_class = theme.slider.clone();
+    let slider = move |label: &'static str, value: f32, set: fn(&mut CropRotate, f32)| {
+        rsx! {
+            label {
+                class: "{slider_class}",
+                span { "{label}" }
+                input {
+                    r#type: "range",
+                    min: "0",
+                    max: "{MAX_MARGIN_PERCENT}",
+                    value: "{percent(value)}",
+                    oninput: move |e| {
+                        if let Ok(value) = e.value().parse::<f32>() {
+                            set(&mut edit.write(), value / 100.0);
+                        }
+                    },
+                }
+            }
+        }
+    };
+
+    let controls_class = classes(&theme.bar, &theme.bar_stacked);
+    let row_class = classes(&theme.bar, &theme.bar_centered);
+    let apply_class = classes(&theme.button, &theme.button_primary);
+
+    rsx! {
+        ThemeStylesheet { theme: theme.clone() }
+        div {
+            class: "{theme.editor}",
+            // Preview
+            div {
+                class: "{theme.stage}",
+                img {
+                    src: "{src}",
+                    class: "{theme.stage_image}",
+                    style: "{preview_style}",
+                }
+            }
+            // Controls
+            div {
+                class: "{controls_class}",
+                div {
+                    class: "{row_class}",
+                    button {
+                        class: "{theme.icon_button}",
+                        onclick: move |_| edit.with_mut(|e| e.rotate = (e.rotate + 270) % 360),
+                        "⟲"
+                    }
+                    button {
+                        class: "{theme.icon_button}",
+                        onclick: move |_| edit.with_mut(|e| e.rotate = (e.rotate + 90) % 360),
+                        "⟳"
+                    }
+                }
+                {slider("←", current.left, |e, v| e.left = v)}
+                {slider("→", current.right, |e, v| e.right = v)}
+                {slider("↑", current.top, |e, v| e.top = v)}
+                {slider("↓", current.bottom, |e, v| e.bottom = v)}
+                div {
+                    class: "{row_class}",
+                    button {
+                        class: "{theme.button}",
+                        onclick: move |_| on_cancel.call(()),
+                        "{cancel_label}"
+                    }
+                    button {
+                        class: "{apply_class}",
+                        disabled: busy || unchanged,
+                        onclick: move |_| on_apply.call(edit()),
+                        "{apply_label}"
+                    }
+                }
+            }
+        }
+    }
+}
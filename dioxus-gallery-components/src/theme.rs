@@ -0,0 +1,154 @@
+use dioxus::prelude::*;
+
+/// The bundled default stylesheet (`assets/gallery.css`)
+pub const GALLERY_CSS: Asset = asset!("/assets/gallery.css");
+
+/// CSS class names used by the gallery components
+///
+/// The defaults refer to the classes in [`GALLERY_CSS`], which every
+/// component loads unless `include_default_stylesheet` is turned off.
+/// Fields hold one or more space separated classes, so a consumer can either
+/// add its own classes next to the defaults or replace them entirely:
+///
+/// ```rust,ignore
+/// let theme = GalleryTheme {
+///     item: "dg-item my-photo-tile".to_string(),
+///     ..Default::default()
+/// };
+/// ```
+///
+/// Only layout that the components compute at runtime (grid columns of the
+/// virtual gallery, scroll spacers, the crop preview) stays inline.
+#[derive(Clone, PartialEq, Debug)]
+pub struct GalleryTheme {
+    /// Load [`GALLERY_CSS`]; turn off when styling all classes yourself
+    pub include_default_stylesheet: bool,
+    /// Message shown for an empty gallery
+    pub empty: String,
+    /// Grid container of [`crate::Gallery`]
+    pub grid: String,
+    /// Added to the container in [`crate::GalleryLayout::Masonry`]
+    pub grid_masonry: String,
+    /// Added to the container in [`crate::GalleryLayout::Justified`]
+    pub grid_justified: String,
+    /// A gallery tile
+    pub item: String,
+    /// Added to the tile of the selected item
+    pub item_selected: String,
+    /// Added to the tile that is being dragged
+    pub item_dragging: String,
+    /// Added to the tile the dragged item would be dropped on
+    pub item_drop_target: String,
+    /// Skeleton shown while an item is loading
+    pub placeholder: String,
+    /// Added to the skeleton at the end while more items are loading
+    pub loading_footer: String,
+    /// Broken image shown for an item that failed to load
+    pub error_tile: String,
+    /// Retry button on the broken image
+    pub retry_button: String,
+    /// Image inside a tile
+    pub image: String,
+    /// Delete button of a tile
+    pub delete_button: String,
+    /// Check mark of the selected tile
+    pub selected_badge: String,
+    /// Caption strip at the bottom of a tile
+    pub caption_bar: String,
+    /// Pencil button in the caption strip
+    pub caption_edit_button: String,
+    /// Text input replacing the caption strip while editing
+    pub caption_input: String,
+    /// Root of the [`crate::FullscreenViewer`]
+    pub viewer: String,
+    /// Root of the [`crate::CropRotateDialog`]
+    pub editor: String,
+    /// Header/footer bar of the viewer and editor
+    pub bar: String,
+    /// Bar with centered content
+    pub bar_centered: String,
+    /// Bar with stacked rows (editor controls)
+    pub bar_stacked: String,
+    /// Caption in the viewer header
+    pub caption: String,
+    /// Area holding the large image
+    pub stage: String,
+    /// The large image
+    pub stage_image: String,
+    /// Added to the skeleton while the large image is loading
+    pub stage_placeholder: String,
+    /// Round icon button (close, rotate, navigation)
+    pub icon_button: String,
+    /// Added to the "previous" navigation button
+    pub nav_prev: String,
+    /// Added to the "next" navigation button
+    pub nav_next: String,
+    /// Text button
+    pub button: String,
+    /// Added to the confirming button
+    pub button_primary: String,
+    /// Added to destructive buttons
+    pub button_danger: String,
+    /// A labelled range input of the editor
+    pub slider: String,
+}
+
+impl Default for GalleryTheme {
+    fn default() -> Self {
+        let class = |name: &str| name.to_string();
+        GalleryTheme {
+            include_default_stylesheet: true,
+            empty: class("dg-empty"),
+            grid: class("dg-grid"),
+            grid_masonry: class("dg-grid--masonry"),
+            grid_justified: class("dg-grid--justified"),
+            item: class("dg-item"),
+            item_selected: class("dg-item--selected"),
+            item_dragging: class("dg-item--dragging"),
+            item_drop_target: class("dg-item--drop-target"),
+            placeholder: class("dg-placeholder"),
+            loading_footer: class("dg-loading-footer"),
+            error_tile: class("dg-error"),
+            retry_button: class("dg-retry"),
+            image: class("dg-image"),
+            delete_button: class("dg-delete"),
+            selected_badge: class("dg-badge"),
+            caption_bar: class("dg-caption-bar"),
+            caption_edit_button: class("dg-caption-edit"),
+            caption_input: class("dg-caption-input"),
+            viewer: class("dg-overlay"),
+            editor: class("dg-overlay dg-editor"),
+            bar: class("dg-bar"),
+            bar_centered: class("dg-bar--center"),
+            bar_stacked: class("dg-bar--column"),
+            caption: class("dg-caption"),
+            stage: class("dg-stage"),
+            stage_image: class("dg-stage-image"),
+            stage_placeholder: class("dg-stage-placeholder"),
+            icon_button: class("dg-icon-button"),
+            nav_prev: class("dg-nav-prev"),
+            nav_next: class("dg-nav-next"),
+            button: class("dg-button"),
+            button_primary: class("dg-button--primary"),
+            button_danger: class("dg-button--danger"),
+            slider: class("dg-slider"),
+        }
+    }
+}
+
+/// Joins a base class with a modifier class
+pub(crate) fn classes(base: &str, modifier: &str) -> String {
+    format!("{} {}", base, modifier)
+}
+
+/// Loads [`GALLERY_CSS`] if the theme asks for it (links are deduplicated,
+/// so every component can render this)
+#[component]
+pub(crate) fn ThemeStylesheet(theme: GalleryTheme) -> Element {
+    if !theme.include_default_stylesheet {
+        return rsx! {};
+    }
+    rsx! {
+        document::Stylesheet { href: GALLERY_CSS }
+    }
+}
@@ -18,6 +18,9 @@ action-cancel = Abbrechen
 # Source: ./src/components/profile_detail.rs:500
 action-close = Schließen
 
+# Source: ./src/components/profile_detail.rs:530
+action-crop-rotate = Zuschneiden
+
 # Source: ./src/components/event_edit.rs:474
 action-delete = Löschen
 
@@ -40,9 +43,15 @@ action-loading = Lädt...
 # Source: ./src/components/profile_list.rs:72
 action-new = Neu
 
+# Source: ./src/components/photo_paste.rs:49
+action-paste-image = Einfügen
+
 # Source: ./src/components/event_edit.rs:441
 action-photo = Foto
 
+# Source: ./src/components/photo_trash.rs:40
+action-restore = Wiederherstellen
+
 # Source: ./src/components/settings.rs:143
 action-retry = Erneut versuchen
 
@@ -52,6 +61,9 @@ action-save = Speichern
 # Source: ./src/components/profile_edit.rs:373
 action-saving = Speichere...
 
+# Source: ./src/components/profile_detail.rs:585
+action-set-profile-photo = Als Profilbild
+
 # Source: ./src/components/egg_tracking.rs:249
 action-update = Aktualisieren
 
@@ -62,7 +74,7 @@ app-title = Wachtel-Stallbuch
 backup-cleanup-button = 🧹 Verwaiste Fotos löschen
 
 # Source: ./src/components/settings.rs:866
-backup-cleanup-description = Entfernt Fotos ohne gültige Wachtel- oder Ereigniszuordnung.
+backup-cleanup-description = Entfernt Fotos ohne gültige Wachtel- oder Ereigniszuordnung sowie liegengebliebene Fotodateien ohne Datensatz.
 
 # Source: ./src/components/settings.rs:884
 # Parameters: $error
@@ -89,6 +101,12 @@ backup-export-description = Erstellt eine ZIP-Sicherung mit Wachteln, Ereignisse
 # Parameters: $error
 backup-export-error = ❌ Fehler beim Export: { $error }
 
+# Source: ./src/components/settings.rs:1165
+backup-export-json-button = 🧾 Alle Daten als JSON exportieren (für Auswertungen)
+
+# Source: ./src/components/settings.rs:1173
+backup-export-reduced-photos = Verkleinerte Fotos (nur Vorschaubilder, klein genug für E-Mails; Originale holt der Sync nach dem Import)
+
 # Source: ./src/components/settings.rs:917
 # Parameters: $path
 backup-export-success = ✅ Export erzeugt: { $path }
@@ -97,23 +115,252 @@ backup-export-success = ✅ Export erzeugt: { $path }
 backup-export-title = 📦 Daten sichern & wiederherstellen
 
 # Source: ./src/components/settings.rs:965
-backup-import-button = 📥 Daten aus import.zip einspielen
+backup-import-button = 📥 Sicherung auswählen und einspielen
 
-# Source: ./src/components/settings.rs:955
+# Source: ./src/components/backup_import.rs:167
+backup-import-choice-both = Beide behalten (Kopie anlegen)
+
+# Source: ./src/components/backup_import.rs:165
+backup-import-choice-import = Aus der Sicherung übernehmen
+
+# Source: ./src/components/backup_import.rs:164
+backup-import-choice-local = Lokalen Stand behalten
+
+# Source: ./src/components/backup_import.rs:136
+backup-import-encrypted = 🔒 Diese Sicherung ist verschlüsselt. Bitte die Passphrase eingeben, die beim Export gewählt wurde.
+
+# Source: ./src/components/backup_import.rs:34
 # Parameters: $error
 backup-import-error = ❌ Fehler beim Import: { $error }
 
-# Source: ./src/components/settings.rs:946
+# Source: ./src/components/backup_import.rs:29
 # Parameters: $path
 backup-import-missing = ❌ Importdatei nicht gefunden: { $path }
 
-# Source: ./src/components/settings.rs:952
+# Source: ./src/components/settings.rs:1168
+# Parameters: $error
+backup-import-pick-error = ❌ Keine Sicherung ausgewählt: { $error }
+
+# Source: ./src/components/backup_import.rs:87
+backup-import-preview-added = Neu
+
+# Source: ./src/components/backup_import.rs:116
+backup-import-preview-apply = 📥 Mit dieser Auswahl einspielen
+
+# Source: ./src/components/backup_import.rs:134
+backup-import-preview-conflict = Konflikt: lokal neuer
+
+# Source: ./src/components/backup_import.rs:90
+backup-import-preview-conflicts = Konflikte
+
+# Source: ./src/components/backup_import.rs:75
+backup-import-preview-title = 📥 Sicherung einspielen
+
+# Source: ./src/components/backup_import.rs:89
+backup-import-preview-unchanged = Unverändert
+
+# Source: ./src/components/backup_import.rs:136
+backup-import-preview-update = Sicherung neuer
+
+# Source: ./src/components/backup_import.rs:88
+backup-import-preview-updated = Aktualisiert
+
+# Source: ./src/components/backup_import.rs:54
 # Parameters: $path
 backup-import-success = ✅ Import erfolgreich aus { $path }
 
+# Source: ./src/components/backup_import.rs:148
+backup-import-unlock = Entschlüsseln
+
+# Source: ./src/components/selective_export.rs:33
+# Parameters: $min
+backup-passphrase-placeholder = Passphrase zum Verschlüsseln (optional, mind. { $min } Zeichen)
+
+# Source: ./src/components/settings.rs:964
+# Parameters: $files, $mb, $missing
+backup-sweep-success = ✅ { $files } verwaiste Dateien entfernt ({ $mb } MB), { $missing } Fotos ohne Datei bereinigt
+
+# Source: ./src/components/batch_actions.rs:106
+batch-action-coop = Stall
+
+# Source: ./src/components/batch_actions.rs:99
+batch-action-event = Ereignis
+
+# Source: ./src/components/batch_actions.rs:177
+# Parameters: $count
+batch-apply = Für { $count } Wachteln ausführen
+
+# Source: ./src/components/batch_actions.rs:62
+# Parameters: $count
+batch-coop-done = Stall bei { $count } Wachteln geändert
+
+# Source: ./src/components/batch_actions.rs:157
+# Parameters: $count
+batch-delete-confirm = { $count } Wachteln samt Ereignissen endgültig löschen?
+
+# Source: ./src/components/batch_actions.rs:65
+# Parameters: $count
+batch-delete-done = { $count } Wachteln gelöscht
+
+# Source: ./src/components/batch_actions.rs:59
+# Parameters: $count
+batch-event-done = Ereignis bei { $count } Wachteln eingetragen
+
+# Source: ./src/components/profile_list.rs:177
+batch-select-all = Alle auswählen
+
+# Source: ./src/components/profile_list.rs:175
+batch-select-none = Auswahl aufheben
+
+# Source: ./src/components/profile_list.rs:162
+# Parameters: $count
+batch-selected = { $count } ausgewählt
+
+# Source: ./src/components/calendar_export.rs:72
+calendar-export-button = 📅 Kalenderdatei (.ics) erstellen
+
+# Source: ./src/components/calendar_export.rs:63
+calendar-export-description = Schlupf, geplante Schlachtungen und andere Ereignisse als Termine für den eigenen Kalender. In der Nextcloud lässt sich die Datei über einen Freigabe-Link abonnieren und wird bei jedem Hochladen aktualisiert.
+
+# Source: ./src/components/calendar_export.rs:32
+# Parameters: $error
+calendar-export-error = ❌ Kalender-Export fehlgeschlagen: { $error }
+
+# Source: ./src/components/calendar_export.rs:100
+calendar-export-subscribe-hint = Tipp: In der Nextcloud einen Freigabe-Link für die Datei erstellen und ihn in Nextcloud Calendar unter „Abonnement aus Link“ eintragen.
+
+# Source: ./src/components/calendar_export.rs:27
+# Parameters: $path
+calendar-export-success = ✅ Kalender gespeichert: { $path }
+
+# Source: ./src/components/calendar_export.rs:60
+calendar-export-title = Kalender (iCal)
+
+# Source: ./src/components/calendar_export.rs:80
+calendar-export-upload-button = ☁️ Kalender in die Nextcloud hochladen
+
+# Source: ./src/components/calendar_export.rs:45
+# Parameters: $path
+calendar-export-uploaded = ✅ Kalender hochgeladen: { $path }
+
+# Source: ./src/components/calendar_export.rs:40
+calendar-export-uploading = ⏳ Kalender wird hochgeladen …
+
+# Source: ./src/components/cloud_export.rs:104
+cloud-export-button = ☁️ Export hochladen
+
+# Source: ./src/components/cloud_export.rs:70
+cloud-export-description = Erstellt eine vollständige Sicherung und lädt sie in einen Ordner deiner Nextcloud hoch. Praktisch auf Android, wo der lokale Export-Ordner schwer zu finden ist.
+
+# Source: ./src/components/cloud_export.rs:57
+# Parameters: $error
+cloud-export-error = ❌ Fehler beim Hochladen: { $error }
+
+# Source: ./src/components/cloud_export.rs:42
+cloud-export-exporting = ⏳ Export wird erstellt …
+
+# Source: ./src/components/cloud_export.rs:74
+cloud-export-folder = Nextcloud-Ordner
+
+# Source: ./src/components/cloud_export.rs:116
+cloud-export-open = 🌐 In der Nextcloud öffnen
+
+# Source: ./src/components/cloud_export.rs:54
+# Parameters: $path
+cloud-export-success = ✅ Hochgeladen nach { $path }
+
+# Source: ./src/components/cloud_export.rs:68
+cloud-export-title = Export in die Nextcloud
+
+# Source: ./src/components/cloud_export.rs:45
+# Parameters: $size
+cloud-export-uploading = ⏳ Wird hochgeladen ({ $size }) …
+
 # Source: ./src/components/profile_edit.rs:394
 confirm-delete-quail = Möchten Sie diese Wachtel wirklich löschen? Diese Aktion kann nicht rückgängig gemacht werden.
 
+# Source: ./src/components/profile_list.rs:118
+coop-filter-all = Alle Ställe
+
+# Source: ./src/components/profile_add.rs:191
+coop-none = Kein Stall
+
+# Source: ./src/components/egg_tracking.rs:237
+coop-whole-flock = Ganzer Bestand
+
+# Source: ./src/components/coops.rs:200
+coops-add = Stall hinzufügen
+
+# Source: ./src/components/coops.rs:117
+coops-description = Wachteln und Eier-Einträge lassen sich einem Stall oder Gehege zuordnen und danach filtern.
+
+# Source: ./src/components/coops.rs:127
+# Parameters: $count
+coops-members = { $count } Wachteln
+
+# Source: ./src/components/coops.rs:156
+coops-name-placeholder = Name des Stalls
+
+# Source: ./src/components/coops.rs:114
+coops-title = Ställe
+
+# Source: ./src/components/data_location.rs:77
+data-location-browse = Auswählen…
+
+# Source: ./src/components/data_location.rs:121
+data-location-busy = Daten werden kopiert…
+
+# Source: ./src/components/data_location.rs:84
+data-location-confirm = Alle Daten werden nach { $path } kopiert und ab sofort dort gespeichert.
+
+# Source: ./src/components/data_location.rs:53
+data-location-current = Aktuell: { $path }
+
+# Source: ./src/components/data_location.rs:50
+data-location-description = Ordner für Datenbank, Sicherungen und Fotos, z.B. ein synchronisierter Ordner oder ein externes Laufwerk. Beim Umziehen werden alle Daten in den neuen Ordner kopiert; der alte Ordner bleibt unverändert.
+
+# Source: ./src/components/data_location.rs:37
+data-location-error = ❌ Umzug fehlgeschlagen: { $error }
+
+# Source: ./src/components/data_location.rs:91
+data-location-move = Daten umziehen
+
+# Source: ./src/components/data_location.rs:28
+data-location-moved = ✅ Umgezogen: { $photos } Foto(s), { $size } kopiert. Der alte Ordner { $previous } kann gelöscht werden.
+
+# Source: ./src/components/data_location.rs:60
+data-location-placeholder = Neuer Ordner
+
+# Source: ./src/components/data_location.rs:117
+data-location-reset = Standardort verwenden
+
+# Source: ./src/components/data_location.rs:47
+data-location-title = Speicherort der Daten
+
+# Source: ./src/components/database_encryption.rs:58
+db-encryption-disable = Verschlüsselung aufheben
+
+# Source: ./src/components/database_encryption.rs:51
+db-encryption-disable-confirm = Die Datenbank wird beim nächsten Start wieder unverschlüsselt gespeichert. Fortfahren?
+
+# Source: ./src/components/database_encryption.rs:46
+db-encryption-enable = Datenbank verschlüsseln
+
+# Source: ./src/components/database_encryption.rs:28
+db-encryption-off = Gesundheitsnotizen und Sync-Zugangsdaten liegen unverschlüsselt im App-Speicher. Die Verschlüsselung wird beim nächsten Start der App eingerichtet.
+
+# Source: ./src/components/database_encryption.rs:25
+db-encryption-on = Die Datenbank auf diesem Gerät ist mit SQLCipher verschlüsselt. Der Schlüssel liegt im Schlüsselspeicher des Systems.
+
+# Source: ./src/components/database_encryption.rs:27
+db-encryption-pending-off = Die Datenbank wird beim nächsten Start der App entschlüsselt.
+
+# Source: ./src/components/database_encryption.rs:26
+db-encryption-pending-on = Die Datenbank wird beim nächsten Start der App verschlüsselt.
+
+# Source: ./src/components/database_encryption.rs:39
+db-encryption-title = Datenbank verschlüsseln
+
 # Source: ./src/components/egg_history.rs:124
 # Parameters: $count
 egg-history-eggs-count = { $count } Eier
@@ -121,6 +368,17 @@ egg-history-eggs-count = { $count } Eier
 # Source: ./src/components/egg_history.rs:70
 egg-history-empty = Keine Einträge vorhanden
 
+# Source: ./src/components/egg_history.rs:76
+egg-history-export-csv = CSV
+
+# Source: ./src/components/egg_history.rs:45
+# Parameters: $error
+egg-history-export-error = CSV-Export fehlgeschlagen: { $error }
+
+# Source: ./src/components/egg_history.rs:41
+# Parameters: $path
+egg-history-export-success = CSV gespeichert: { $path }
+
 # Source: ./src/components/egg_history.rs:17
 # Parameters: $count
 egg-history-loaded = { $count } Einträge geladen
@@ -140,6 +398,54 @@ egg-tracking-success = Eintrag erfolgreich gespeichert!
 # Source: ./src/components/home.rs:59
 egg-tracking-title = Eier eintragen
 
+# Source: ./src/components/photo_encryption.rs:90
+encryption-accept-plaintext = Unverschlüsselte Altdateien annehmen (Migration)
+
+# Source: ./src/components/photo_encryption.rs:93
+encryption-accept-plaintext-hint = Nur einschalten, solange der Sync-Ordner noch Dateien von vor dem Aktivieren enthält. Sonst werden unverschlüsselte Dateien abgelehnt, damit der Server keine Änderungen unterschieben kann.
+
+# Source: ./src/components/photo_encryption.rs:115
+encryption-disable = Verschlüsselung deaktivieren
+
+# Source: ./src/components/photo_encryption.rs:98
+encryption-disable-confirm = Ohne den exportierten Schlüssel oder die Passphrase sind bereits verschlüsselte Fotos und Sync-Daten verloren. Wirklich deaktivieren?
+
+# Source: ./src/components/photo_encryption.rs:39
+encryption-enabled = Verschlüsselung aktiviert
+
+# Source: ./src/components/photo_encryption.rs:62
+encryption-export-hint = Diesen Schlüssel auf allen anderen Geräten importieren und sicher aufbewahren. Ohne ihn sind die verschlüsselten Fotos und Sync-Daten nicht mehr lesbar.
+
+# Source: ./src/components/photo_encryption.rs:205
+encryption-from-passphrase = Aus Passphrase ableiten
+
+# Source: ./src/components/photo_encryption.rs:141
+encryption-generate = Schlüssel erzeugen
+
+# Source: ./src/components/photo_encryption.rs:158
+encryption-import = Importieren
+
+# Source: ./src/components/photo_encryption.rs:132
+encryption-off-hint = Fotos und Sync-Daten werden unverschlüsselt auf den Server geladen. Mit einem Schlüssel werden sie vor dem Upload auf diesem Gerät verschlüsselt. Alle Geräte brauchen denselben Schlüssel.
+
+# Source: ./src/components/photo_encryption.rs:53
+encryption-on-hint = Neue Fotos, Änderungen und Snapshots werden vor dem Upload verschlüsselt. Bereits hochgeladene Dateien bleiben unverändert.
+
+# Source: ./src/components/photo_encryption.rs:165
+encryption-passphrase = Passphrase
+
+# Source: ./src/components/photo_encryption.rs:209
+encryption-passphrase-hint = Alternativ auf jedem Gerät dieselbe Passphrase (mindestens { $min } Zeichen) eingeben.
+
+# Source: ./src/components/photo_encryption.rs:176
+encryption-passphrase-needs-sync = Für die Passphrase zuerst den Sync einrichten: Salt und Schlüsselprüfung liegen im Sync-Ordner.
+
+# Source: ./src/components/photo_encryption.rs:75
+encryption-show-key = Schlüssel exportieren
+
+# Source: ./src/components/photo_encryption.rs:49
+encryption-title = Ende-zu-Ende-Verschlüsselung
+
 # Source: ./src/components/profile_add.rs:235
 error = Fehler
 
@@ -155,12 +461,46 @@ error-android-only-camera = Kamera nur auf Android verfügbar
 # Source: ./src/components/event_edit.rs:378
 error-android-only-gallery = Galerie nur auf Android verfügbar
 
+# Source: ./src/components/manual_credentials.rs:115
+error-auth-cancelled = Anmeldung abgebrochen
+
+# Source: ./src/components/manual_credentials.rs:124
+# Parameters: $fingerprint
+error-auth-certificate-changed = Das Zertifikat des Servers hat sich seit der Anmeldung geändert (neuer Fingerabdruck { $fingerprint }). Falls das erwartet ist, bitte erneut anmelden.
+
+# Source: ./src/components/manual_credentials.rs:110
+error-auth-dns = Server nicht gefunden. Bitte Adresse und Internetverbindung prüfen.
+
+# Source: ./src/components/manual_credentials.rs:124
+error-auth-maintenance = Die Nextcloud befindet sich gerade im Wartungsmodus. Bitte später erneut versuchen.
+
+# Source: ./src/components/manual_credentials.rs:121
+# Parameters: $url
+error-auth-not-nextcloud = Unter { $url } wurde keine Nextcloud gefunden. Bitte die Adresse prüfen.
+
+# Source: ./src/components/manual_credentials.rs:114
+error-auth-parse = Unerwartete Antwort vom Server. Ist die Adresse ein Nextcloud-Server?
+
+# Source: ./src/components/manual_credentials.rs:113
+# Parameters: $status
+error-auth-status = Server antwortete mit Status { $status }
+
+# Source: ./src/components/manual_credentials.rs:112
+error-auth-timeout = Zeitüberschreitung: Der Server hat nicht rechtzeitig geantwortet bzw. die Anmeldung wurde nicht bestätigt.
+
+# Source: ./src/components/manual_credentials.rs:111
+error-auth-tls = Sichere Verbindung fehlgeschlagen. Bitte das Zertifikat des Servers prüfen.
+
 # Source: ./src/components/statistics.rs:54
 error-calculation = Fehler beim Berechnen
 
 # Source: ./src/components/profile_detail.rs:292
 error-camera-android-only = Kamera nur auf Android verfügbar
 
+# Source: ./src/components/profile_edit.rs:403
+# Parameters: $error
+error-caption-save-failed = Bildunterschrift konnte nicht gespeichert werden: { $error }
+
 # Source: ./src/components/profile_detail.rs:286
 error-capture-failed = Fehler bei Aufnahme
 
@@ -171,6 +511,9 @@ error-capture-photo = Aufnahmefehler: { $error }
 # Source: ./src/components/settings.rs:65
 error-client = Client-Fehler
 
+# Source: ./src/components/photo_paste.rs:59
+error-clipboard-no-image = Die Zwischenablage enthält kein Bild
+
 # Source: ./src/components/settings.rs:593
 error-connection = Verbindungsfehler
 
@@ -215,6 +558,10 @@ error-empty-date = Datum darf nicht leer sein
 # Parameters: $error
 error-event-save = Fehler beim Speichern: { $error }
 
+# Source: ./src/components/profile_detail.rs:219
+# Parameters: $failed, $total
+error-import-failed = { $failed } von { $total } Fotos konnten nicht importiert werden
+
 # Source: ./src/components/event_add.rs:50
 error-invalid-date = Ungültiges Datumsformat
 
@@ -254,6 +601,13 @@ error-name-required = Name darf nicht leer sein
 # Source: ./src/components/settings.rs:56
 error-network = Fehler
 
+# Source: ./src/components/photo_paste.rs:40
+error-paste-failed = Einfügen fehlgeschlagen
+
+# Source: ./src/components/profile_detail.rs:641
+# Parameters: $error
+error-photo-edit-failed = Foto konnte nicht bearbeitet werden: { $error }
+
 # Source: ./src/components/event_edit.rs:372
 # Parameters: $error
 error-pick-images = Auswahlfehler: { $error }
@@ -262,6 +616,10 @@ error-pick-images = Auswahlfehler: { $error }
 # UNUSED: This key is no longer used in the code
 error-poll = Poll-Fehler
 
+# Source: ./src/components/profile_detail.rs:70
+# Parameters: $error
+error-profile-photo-failed = Profilbild konnte nicht gesetzt werden: { $error }
+
 # Source: ./src/components/profile_add.rs:90
 # Parameters: $error
 error-save = Speicherfehler
@@ -319,6 +677,9 @@ event-type-sick = 🤒 Krank
 # Source: ./src/components/event_add.rs:136
 event-type-slaughtered = 🥩 Geschlachtet
 
+# Source: ./src/components/event_add.rs:149
+event-type-treatment = 💊 Behandlung
+
 # No events available
 # Source: ./src/components/profile_detail.rs:415
 events-empty = Keine Ereignisse vorhanden
@@ -326,6 +687,74 @@ events-empty = Keine Ereignisse vorhanden
 # Source: ./src/components/profile_detail.rs:395
 events-timeline-title = Ereignisse
 
+# Source: ./src/components/export_templates.rs:22
+export-template-column-date = Datum
+
+# Source: ./src/components/export_templates.rs:24
+export-template-column-event-type = Ereignis
+
+# Source: ./src/components/export_templates.rs:20
+export-template-column-gender = Geschlecht
+
+# Source: ./src/components/export_templates.rs:19
+export-template-column-name = Name
+
+# Source: ./src/components/export_templates.rs:26
+export-template-column-notes = Notizen
+
+# Source: ./src/components/export_templates.rs:23
+export-template-column-quail = Wachtel
+
+# Source: ./src/components/export_templates.rs:21
+export-template-column-ring-color = Ringfarbe
+
+# Source: ./src/components/export_templates.rs:25
+export-template-column-total-eggs = Anzahl Eier
+
+# Source: ./src/components/export_templates.rs:151
+export-template-columns = Spalten
+
+# Source: ./src/components/export_templates.rs:117
+export-template-description = Gespeicherte Auswahl für CSV-Exporte: welche Einträge und Spalten in die Datei kommen, z.B. ein Tierarzt-Bericht nur mit Krankheit, Genesung und Todesfällen.
+
+# Source: ./src/components/export_templates.rs:13
+export-template-entity-eggs = Eier-Einträge
+
+# Source: ./src/components/export_templates.rs:12
+export-template-entity-events = Ereignisse
+
+# Source: ./src/components/export_templates.rs:11
+export-template-entity-quails = Wachteln
+
+# Source: ./src/components/export_templates.rs:74
+# Parameters: $error
+export-template-error = ❌ Export fehlgeschlagen: { $error }
+
+# Source: ./src/components/export_templates.rs:174
+export-template-event-types = Ereignistypen
+
+# Source: ./src/components/export_templates.rs:197
+export-template-event-types-hint = Keine Auswahl exportiert alle Ereignisse.
+
+# Source: ./src/components/export_templates.rs:243
+export-template-export-button = CSV exportieren
+
+# Source: ./src/components/export_templates.rs:124
+export-template-name-placeholder = Name der Vorlage
+
+# Source: ./src/components/export_templates.rs:270
+export-template-new-button = Neue Vorlage
+
+# Source: ./src/components/export_templates.rs:69
+# Parameters: $path
+export-template-success = ✅ CSV gespeichert: { $path }
+
+# Source: ./src/components/export_templates.rs:114
+export-template-title = CSV-Vorlagen
+
+# Source: ./src/components/profile_add.rs:190
+field-coop = Stall
+
 # Source: ./src/components/event_add.rs:144
 field-date = Datum
 
@@ -335,6 +764,12 @@ field-date-format-hint = Format: YYYY-MM-DD
 # Source: ./src/components/egg_tracking.rs:189
 field-date-required = Datum *
 
+# Source: ./src/components/treatment.rs:65
+field-dose = Dosis
+
+# Source: ./src/components/treatment.rs:79
+field-egg-withdrawal-days = Wartezeit Eier (Tage)
+
 # Source: ./src/components/egg_tracking.rs:217
 field-eggs-count-placeholder = z.B. 12
 
@@ -344,10 +779,22 @@ field-eggs-count-required = Anzahl Eier *
 # Source: ./src/components/event_add.rs:121
 field-event-type = Ereignistyp
 
+# Source: ./src/components/pedigree.rs:73
+field-father = Vater
+
 # Gender
 # Source: ./src/components/profile_edit.rs:221
 field-gender = Geschlecht
 
+# Source: ./src/components/treatment.rs:80
+field-meat-withdrawal-days = Wartezeit Fleisch (Tage)
+
+# Source: ./src/components/treatment.rs:56
+field-medication = Medikament
+
+# Source: ./src/components/pedigree.rs:56
+field-mother = Mutter
+
 # e.g. Hen 1
 # Source: ./src/components/profile_edit.rs:211
 field-name-placeholder = z.B. Flecki
@@ -373,6 +820,9 @@ field-photos = Fotos
 # Source: ./src/components/profile_edit.rs:236
 field-ring-color = Ringfarbe
 
+# Source: ./src/components/treatment.rs:78
+field-treatment-days = Dauer (Tage)
+
 # Source: ./src/components/event_edit.rs:277
 field-type = Typ
 
@@ -388,6 +838,39 @@ gender-male = Männlich
 # Source: ./src/components/profile_edit.rs:227
 gender-unknown = Unbekannt
 
+# Source: ./src/components/entity_history.rs:99
+history-changed = Geändert
+
+# Source: ./src/components/entity_history.rs:97
+history-created = Angelegt
+
+# Source: ./src/components/entity_history.rs:95
+history-deleted = Gelöscht
+
+# Source: ./src/components/entity_history.rs:65
+history-empty = Noch keine Änderungen aufgezeichnet.
+
+# Source: ./src/components/entity_history.rs:131
+history-field-eggs = Anzahl Eier
+
+# Source: ./src/components/entity_history.rs:123
+history-field-name = Name
+
+# Source: ./src/components/entity_history.rs:126
+history-field-profile-photo = Profilfoto
+
+# Source: ./src/components/entity_history.rs:127
+history-field-quail = Wachtel
+
+# Source: ./src/components/entity_history.rs:90
+history-other-device = Gerät { $device }
+
+# Source: ./src/components/entity_history.rs:29
+history-tab = Verlauf
+
+# Source: ./src/components/entity_history.rs:87
+history-this-device = Dieses Gerät
+
 # DB: {path}
 # Source: ./src/components/home.rs:90
 # Parameters: $path, $database
@@ -400,6 +883,33 @@ info-photos-detail-view = Fotos werden in der Detailansicht hinzugefügt. Hier k
 # Source: ./src/components/profile_edit.rs:359
 info-tap-photo-to-mark = Tippen Sie auf ein Foto, um es als Profilbild zu markieren.
 
+# Source: ./src/components/database_integrity.rs:71
+integrity-check-button = Jetzt prüfen
+
+# Source: ./src/components/database_integrity.rs:64
+integrity-description = Prüft die Datenbank auf beschädigte Seiten und Verweise ins Leere. Das Ergebnis wird im Protokoll gespeichert.
+
+# Source: ./src/components/database_integrity.rs:30
+integrity-error = ❌ Prüfung fehlgeschlagen: { $error }
+
+# Source: ./src/components/database_integrity.rs:82
+integrity-issues = { $count } Problem(e) gefunden
+
+# Source: ./src/components/database_integrity.rs:77
+integrity-ok = Keine Probleme gefunden
+
+# Source: ./src/components/database_integrity.rs:92
+integrity-repair-button = Reparieren
+
+# Source: ./src/components/database_integrity.rs:95
+integrity-repair-hint = Baut die Indizes neu auf und entfernt verwaiste Verweise. Bleiben danach Probleme, hilft nur eine lokale Sicherung.
+
+# Source: ./src/components/database_integrity.rs:42
+integrity-repaired = ✅ Repariert: { $cleared } Verweis(e) entfernt, { $deleted } Zeile(n) gelöscht
+
+# Source: ./src/components/database_integrity.rs:61
+integrity-title = Datenbank prüfen
+
 # Source: ./src/components/event_edit.rs:478
 loading-event = Lade Ereignis...
 
@@ -407,8 +917,35 @@ loading-event = Lade Ereignis...
 # Source: ./src/components/profile_detail.rs:480
 loading-profile = Lade Profil...
 
+# Source: ./src/components/local_backup.rs:111
+local-backup-create = Jetzt sichern
+
+# Source: ./src/components/local_backup.rs:30
+local-backup-created = ✅ Sicherung angelegt
+
+# Source: ./src/components/local_backup.rs:61
+local-backup-description = Die Datenbank wird täglich auf dem Gerät gesichert; die letzten { $keep } Sicherungen bleiben erhalten. Bei Problemen nach einem Sync lässt sich ein früherer Stand wiederherstellen.
+
+# Source: ./src/components/local_backup.rs:64
+local-backup-empty = Noch keine Sicherung vorhanden.
+
+# Source: ./src/components/local_backup.rs:31
+local-backup-error = ❌ Sicherung fehlgeschlagen: { $error }
+
+# Source: ./src/components/local_backup.rs:80
+local-backup-restore = Wiederherstellen
+
+# Source: ./src/components/local_backup.rs:89
+local-backup-restore-confirm = Die aktuellen Daten werden durch die Sicherung ersetzt. Die bisherige Datenbank bleibt als Datei erhalten.
+
+# Source: ./src/components/local_backup.rs:45
+local-backup-restored = ✅ Sicherung wiederhergestellt. Vorherige Datenbank: { $previous }
+
+# Source: ./src/components/local_backup.rs:58
+local-backup-title = Lokale Sicherungen
+
 # the format string for the chrono format time.
-# Source: ./src/components/settings.rs:13
+# Source: ./src/components/sync_history.rs:142
 log-time-format = %H:%M:%S
 
 # Eier
@@ -423,6 +960,9 @@ nav-home = Startseite
 # Source: ./src/components/navigation.rs:30
 nav-profiles = Profile
 
+# Source: ./src/components/navigation.rs:56
+nav-search = Suche
+
 # Statistik
 # Source: ./src/components/navigation.rs:50
 nav-statistics = Statistik
@@ -435,6 +975,22 @@ network-checking = Netzwerkverbindung prüfen...
 # Source: ./src/components/settings.rs:136
 network-offline = Keine Internet-Verbindung
 
+# Source: ./src/components/pedigree.rs:61
+parent-unknown = Unbekannt
+
+# Source: ./src/components/pedigree.rs:92
+# Parameters: $ancestors
+pedigree-inbreeding-warning = Inzucht: Die Eltern sind verwandt (gemeinsame Vorfahren: { $ancestors })
+
+# Source: ./src/components/pedigree.rs:186
+pedigree-offspring-empty = Keine Nachkommen erfasst
+
+# Source: ./src/components/pedigree.rs:182
+pedigree-offspring-title = Nachkommen
+
+# Source: ./src/components/pedigree.rs:166
+pedigree-title = Stammbaum
+
 # Time period filter buttons
 # Source: ./src/components/statistics.rs:92
 period-all = Alle
@@ -459,6 +1015,10 @@ photo-none-selected = Kein Foto ausgewählt
 # Source: ./src/components/profile_add.rs:202
 photo-selected = Foto ausgewählt
 
+# Source: ./src/components/photo_trash.rs:23
+# Parameters: $days
+photo-trashed = Foto gelöscht – { $days } Tage lang wiederherstellbar
+
 # Source: ./src/components/event_edit.rs:321
 # Parameters: $count
 photos-count = Fotos ({ $count })
@@ -467,6 +1027,9 @@ photos-count = Fotos ({ $count })
 # Source: ./src/components/profile_edit.rs:269
 photos-empty = Keine Fotos vorhanden. Fügen Sie Fotos in der Detailansicht hinzu.
 
+# Source: ./src/components/treatment.rs:69
+placeholder-dose = z. B. 0,5 ml je Liter Trinkwasser
+
 # Source: ./src/components/event_add.rs:163
 placeholder-event-notes = Weitere Informationen zum Ereignis...
 
@@ -514,6 +1077,106 @@ profile-photo-label = Foto
 # Source: ./src/components/profile_add.rs:164
 profile-ring-color-label = Ringfarbe
 
+# Source: ./src/components/state_rebuild.rs:39
+rebuild-backup = 💾 Sicherung: { $path }
+
+# Source: ./src/components/state_rebuild.rs:85
+rebuild-button = 🛠️ Neu aufbauen
+
+# Source: ./src/components/state_rebuild.rs:78
+rebuild-confirm = Alle lokalen Daten werden verworfen und neu aufgebaut. Fortfahren?
+
+# Source: ./src/components/state_rebuild.rs:48
+rebuild-consistent = Anzahl der Einträge unverändert
+
+# Source: ./src/components/state_rebuild.rs:73
+rebuild-description = Leert Wachteln, Ereignisse, Eier und Fotos und baut sie aus dem Operationsprotokoll neu auf; danach wird alles vom Server neu übernommen. Hilft, wenn die Daten nach einem Sync inkonsistent sind. Vorher wird automatisch ein Export angelegt.
+
+# Source: ./src/components/state_rebuild.rs:25
+rebuild-error = ❌ Neuaufbau fehlgeschlagen: { $error }
+
+# Source: ./src/components/state_rebuild.rs:59
+rebuild-remote-error = ⚠️ Server-Abgleich fehlgeschlagen: { $error }
+
+# Source: ./src/components/state_rebuild.rs:20
+rebuild-running = ⏳ Neuaufbau läuft…
+
+# Source: ./src/components/state_rebuild.rs:43
+rebuild-success = ✅ { $ops } Operationen neu angewendet, { $remote } vom Server übernommen
+
+# Source: ./src/components/state_rebuild.rs:71
+rebuild-title = 🛠️ Lokalen Stand neu aufbauen
+
+# Source: ./src/components/recovery.rs:118
+recovery-continue = Trotzdem fortfahren
+
+# Source: ./src/components/recovery.rs:65
+recovery-description = Beim Start wurden Probleme gefunden. Wählen Sie eine der folgenden Aktionen, um die App wieder nutzbar zu machen.
+
+# Source: ./src/components/recovery.rs:138
+# Parameters: $error
+recovery-issue-credentials = Sync-Zugangsdaten können nicht gelesen werden: { $error }
+
+# Source: ./src/components/recovery.rs:129
+# Parameters: $error
+recovery-issue-database = Datenbank kann nicht geöffnet werden: { $error }
+
+# Source: ./src/components/recovery.rs:132
+# Parameters: $found, $expected
+recovery-issue-schema = Unerwartete Datenbankversion { $found } (erwartet: { $expected })
+
+# Source: ./src/components/recovery.rs:135
+# Parameters: $path, $error
+recovery-issue-storage = Speicherordner { $path } ist nicht beschreibbar: { $error }
+
+# Source: ./src/components/recovery.rs:77
+recovery-issues-title = Gefundene Probleme
+
+# Source: ./src/components/recovery.rs:111
+recovery-recheck = Erneut prüfen
+
+# Source: ./src/components/recovery.rs:103
+recovery-reset-sync = Sync-Status zurücksetzen
+
+# Source: ./src/components/recovery.rs:44
+recovery-reset-sync-success = Sync-Status zurückgesetzt. Bitte Synchronisierung neu einrichten.
+
+# Source: ./src/components/recovery.rs:94
+recovery-restore-backup = Aus Backup wiederherstellen
+
+# Source: ./src/components/recovery.rs:97
+# Parameters: $path
+recovery-restore-hint = Erwartet die Backup-Datei unter { $path }. Die defekte Datenbank wird beiseite verschoben, nicht gelöscht.
+
+# Source: ./src/components/recovery.rs:27
+recovery-restore-success = Backup wiederhergestellt
+
+# Source: ./src/components/recovery.rs:62
+recovery-title = Wiederherstellung
+
+# Source: ./src/components/statistics.rs:221
+report-button = PDF-Bericht erstellen
+
+# Source: ./src/components/statistics.rs:51
+report-creating = Bericht wird erstellt …
+
+# Source: ./src/components/statistics.rs:206
+report-description = Bestand, Status jeder Wachtel und Eier-Statistik des gewählten Zeitraums als PDF im Export-Ordner.
+
+# Source: ./src/components/statistics.rs:60
+# Parameters: $error
+report-error = Bericht fehlgeschlagen: { $error }
+
+# Source: ./src/components/statistics.rs:215
+report-include-photos = Profilfotos einbeziehen
+
+# Source: ./src/components/statistics.rs:56
+# Parameters: $path
+report-success = Bericht gespeichert: { $path }
+
+# Source: ./src/components/statistics.rs:202
+report-title = Bestandsbericht
+
 # Black
 # Source: ./src/components/profile_edit.rs:251
 ring-color-black = Schwarz
@@ -558,12 +1221,173 @@ ring-color-white = Weiß
 # Source: ./src/components/profile_edit.rs:250
 ring-color-yellow = Gelb
 
+# Source: ./src/components/scheduled_export.rs:62
+scheduled-export-description = Erstellt regelmäßig eine vollständige Sicherung als ZIP. Ältere automatische Exporte werden aufgeräumt, manuelle bleiben liegen. Schützt auch, falls ein Fehler alle synchronisierten Geräte gleichzeitig trifft.
+
+# Source: ./src/components/scheduled_export.rs:94
+scheduled-export-destination-both = Lokal und in der Nextcloud
+
+# Source: ./src/components/scheduled_export.rs:87
+scheduled-export-destination-local = Nur lokal speichern
+
+# Source: ./src/components/scheduled_export.rs:91
+scheduled-export-destination-nextcloud = Nur in die Nextcloud hochladen
+
+# Source: ./src/components/scheduled_export.rs:75
+scheduled-export-interval-monthly = Monatlich
+
+# Source: ./src/components/scheduled_export.rs:73
+scheduled-export-interval-off = Aus
+
+# Source: ./src/components/scheduled_export.rs:74
+scheduled-export-interval-weekly = Wöchentlich
+
+# Source: ./src/components/scheduled_export.rs:99
+scheduled-export-keep = Aufbewahren:
+
+# Source: ./src/components/scheduled_export.rs:128
+# Parameters: $time, $message
+scheduled-export-last = Zuletzt ({ $time }): { $message }
+
+# Source: ./src/components/scheduled_export.rs:120
+# Parameters: $time
+scheduled-export-next = Nächster Export: { $time }
+
+# Source: ./src/components/scheduled_export.rs:118
+scheduled-export-soon = Nächster Export: in Kürze
+
+# Source: ./src/components/scheduled_export.rs:59
+scheduled-export-title = Automatischer Export
+
+# Source: ./src/components/search.rs:53
+search-hint = Sucht in Wachtelnamen, Notizen der Ereignisse und der Eier-Einträge.
+
+# Source: ./src/components/search.rs:94
+search-kind-egg-record = Eier-Eintrag
+
+# Source: ./src/components/search.rs:55
+search-no-results = Keine Treffer
+
+# Source: ./src/components/search.rs:40
+search-placeholder-all = Namen und Notizen durchsuchen…
+
 # Source: ./src/components/profile_list.rs:82
 search-placeholder-name = Suche...
 
+# Source: ./src/components/search.rs:35
+search-title = Suche
+
+# Source: ./src/components/selective_export.rs:129
+selective-export-button = 📤 Auswahl exportieren
+
+# Source: ./src/components/selective_export.rs:60
+selective-export-description = Exportiert nur einen Zeitraum oder einzelne Wachteln, z.B. die Krankengeschichte einer Wachtel für den Tierarzt. Ohne Auswahl werden alle Wachteln exportiert.
+
+# Source: ./src/components/selective_export.rs:65
+selective-export-from = Von
+
+# Source: ./src/components/selective_export.rs:99
+selective-export-include-eggs = Eier-Einträge mitexportieren
+
+# Source: ./src/components/selective_export.rs:121
+selective-export-photos-none = Ohne Fotos
+
+# Source: ./src/components/selective_export.rs:119
+selective-export-photos-originals = Fotos in Originalgröße
+
+# Source: ./src/components/selective_export.rs:165
+selective-export-photos-reduced = Verkleinerte Fotos (mittlere Vorschaubilder)
+
+# Source: ./src/components/selective_export.rs:120
+selective-export-photos-thumbnails = Nur Vorschaubilder
+
+# Source: ./src/components/selective_export.rs:85
+selective-export-quails = Wachteln
+
+# Source: ./src/components/selective_export.rs:58
+selective-export-title = 🗂️ Teil-Export
+
+# Source: ./src/components/selective_export.rs:74
+selective-export-to = Bis
+
 # Source: ./src/components/home.rs:74
 settings-title = Einstellungen
 
+# Source: ./src/components/share_button.rs:25
+share-button = Teilen
+
+# Source: ./src/components/share_button.rs:20
+share-chooser-title = Export teilen
+
+# Source: ./src/components/share_button.rs:22
+# Parameters: $error
+share-error = Teilen fehlgeschlagen: { $error }
+
+# Source: ./src/components/spreadsheet_import.rs:139
+# Parameters: $app
+spreadsheet-import-adapter-detected = 🔎 Export aus einer anderen App erkannt: { $app }. Hennen und Eier-Einträge werden direkt übernommen, die Eier eines Tages zusammengezählt.
+
+# Source: ./src/components/spreadsheet_import.rs:145
+spreadsheet-import-adapter-manual = Spalten stattdessen selbst zuordnen
+
+# Source: ./src/components/spreadsheet_import.rs:113
+spreadsheet-import-browse = Durchsuchen…
+
+# Source: ./src/components/spreadsheet_import.rs:246
+# Parameters: $count
+spreadsheet-import-button = 📥 { $count } Zeilen importieren
+
+# Source: ./src/components/spreadsheet_import.rs:72
+spreadsheet-import-description = Wachteln oder Eier-Einträge aus einer CSV- oder Excel-Datei (.xlsx) übernehmen. Spalten werden anhand der Überschriften zugeordnet und können angepasst werden. CSV-Exporte anderer Geflügel-Apps (z.B. FlockStar-Legelisten) werden automatisch erkannt.
+
+# Source: ./src/components/spreadsheet_import.rs:236
+# Parameters: $error
+spreadsheet-import-error = ❌ Fehler beim Import: { $error }
+
+# Source: ./src/components/spreadsheet_import.rs:159
+spreadsheet-import-ignore = — ignorieren —
+
+# Source: ./src/components/spreadsheet_import.rs:91
+spreadsheet-import-kind-eggs = Eier-Einträge
+
+# Source: ./src/components/spreadsheet_import.rs:92
+spreadsheet-import-kind-quails = Wachteln
+
+# Source: ./src/components/spreadsheet_import.rs:122
+spreadsheet-import-load = Datei laden
+
+# Source: ./src/components/spreadsheet_import.rs:51
+# Parameters: $error
+spreadsheet-import-load-error = ❌ Datei nicht lesbar: { $error }
+
+# Source: ./src/components/spreadsheet_import.rs:129
+spreadsheet-import-mapping-title = Spaltenzuordnung (* = Pflichtfeld)
+
+# Source: ./src/components/spreadsheet_import.rs:210
+# Parameters: $count
+spreadsheet-import-more-errors = … und { $count } weitere Fehler
+
+# Source: ./src/components/settings.rs:1181
+spreadsheet-import-open = 📊 Wachteln/Eier aus CSV oder Excel importieren
+
+# Source: ./src/components/spreadsheet_import.rs:98
+spreadsheet-import-path-placeholder = Pfad zur .csv- oder .xlsx-Datei
+
+# Source: ./src/components/spreadsheet_import.rs:181
+# Parameters: $valid, $total
+spreadsheet-import-preview-valid = { $valid } von { $total } Zeilen können importiert werden
+
+# Source: ./src/components/spreadsheet_import.rs:205
+# Parameters: $row, $message
+spreadsheet-import-row-error = Zeile { $row }: { $message }
+
+# Source: ./src/components/spreadsheet_import.rs:229
+# Parameters: $created, $updated, $skipped
+spreadsheet-import-success = ✅ { $created } neu, { $updated } aktualisiert, { $skipped } übersprungen
+
+# Source: ./src/components/spreadsheet_import.rs:67
+spreadsheet-import-title = 📊 Tabellen-Import
+
 # Source: ./src/components/statistics.rs:208
 stats-add-entry = Eier eintragen
 
@@ -649,15 +1473,24 @@ status-sick = Krank
 # Source: ./src/components/profile_detail.rs:365
 status-slaughtered = Geschlachtet
 
+# Source: ./src/components/profile_detail.rs:480
+status-treatment = Behandlung
+
 # Source: ./src/components/profile_edit.rs:188
 success-profile-updated = Profil erfolgreich aktualisiert!
 
+# Source: ./src/components/settings.rs:664
+sync-certificate = Zertifikat
+
 # Source: ./src/components/settings.rs:656
 sync-configured = Synchronisierung konfiguriert
 
 # Source: ./src/components/settings.rs:1025
 sync-connecting = Verbinde mit Server...
 
+# Source: ./src/components/settings.rs:555
+sync-credentials-invalid = Die Nextcloud hat die Zugangsdaten abgelehnt (App-Passwort widerrufen?). Die Synchronisation ist pausiert.
+
 # Source: ./src/components/settings.rs:744
 sync-delete-config = Konfiguration löschen
 
@@ -667,6 +1500,33 @@ sync-error = Fehler bei der Anmeldung
 # Source: ./src/components/settings.rs:731
 sync-failed = Sync fehlgeschlagen
 
+# Source: ./src/components/sync_history.rs:70
+sync-history-clear = Verlauf löschen
+
+# Source: ./src/components/sync_history.rs:80
+sync-history-empty = Noch keine Synchronisierung aufgezeichnet
+
+# Source: ./src/components/sync_history.rs:59
+sync-history-failures-only = Nur Fehler
+
+# Source: ./src/components/sync_history.rs:168
+sync-history-manual = manuell
+
+# Source: ./src/components/sync_history.rs:91
+sync-history-retention = Einträge werden nach { $days } Tagen gelöscht.
+
+# Source: ./src/components/sync_history.rs:130
+sync-history-show-all = Gesamten Verlauf anzeigen
+
+# Source: ./src/components/sync_history.rs:148
+sync-history-summary = ↑ { $ops_up } Ops · ↓ { $ops_down } Ops · Fotos ↑ { $photos_up } ↓ { $photos_down }
+
+# Source: ./src/components/sync_history.rs:48
+sync-history-title = Sync-Verlauf
+
+# Source: ./src/components/sync_history.rs:148
+sync-history-transfer = ↑ { $up } · ↓ { $down } · { $requests } Anfragen
+
 # Source: ./src/components/settings.rs:697
 sync-last-sync = Letzte Sync
 
@@ -676,6 +1536,20 @@ sync-login = Mit Nextcloud anmelden
 # Source: ./src/components/settings.rs:1046
 sync-login-browser = Im Browser öffnen
 
+# Source: ./src/components/settings.rs:914
+# Parameters: $minutes
+sync-login-expires = { $minutes ->
+    [0] Der Anmeldelink ist abgelaufen.
+    [one] Der Anmeldelink ist noch eine Minute gültig.
+   *[other] Der Anmeldelink ist noch { $minutes } Minuten gültig.
+}
+
+# Source: ./src/components/settings.rs:901
+sync-login-in-app = Anmeldung in der App anzeigen (statt im Browser)
+
+# Source: ./src/components/settings.rs:956
+sync-login-in-app-open = In der App anmelden
+
 # Source: ./src/components/settings.rs:1078
 sync-login-info-title = Wie funktioniert die Anmeldung?
 
@@ -709,12 +1583,63 @@ sync-login-success = Anmeldung erfolgreich!
 # Source: ./src/components/settings.rs:458
 sync-login-success-folder = Anmeldung erfolgreich! Ordner erstellt.
 
+# Source: ./src/components/manual_credentials.rs:78
+sync-manual-app-password = App-Passwort
+
+# Source: ./src/components/manual_credentials.rs:88
+sync-manual-app-password-hint = Erstellen unter Nextcloud → Einstellungen → Sicherheit → Geräte & Sitzungen
+
+# Source: ./src/components/manual_credentials.rs:23
+sync-manual-fields-required = Bitte Benutzername und App-Passwort eingeben
+
+# Source: ./src/components/settings.rs:871
+sync-manual-hide = Manuelle Anmeldung ausblenden
+
+# Source: ./src/components/manual_credentials.rs:54
+sync-manual-hint = Falls die Anmeldung im Browser nicht funktioniert (z.B. hinter einem SSO-Proxy), können Sie Benutzername und App-Passwort direkt eingeben.
+
+# Source: ./src/components/manual_credentials.rs:37
+sync-manual-invalid = Benutzername oder App-Passwort ungültig
+
+# Source: ./src/components/settings.rs:873
+sync-manual-show = 🔑 Manuell mit App-Passwort anmelden
+
+# Source: ./src/components/manual_credentials.rs:102
+sync-manual-submit = Prüfen und anmelden
+
+# Source: ./src/components/manual_credentials.rs:51
+sync-manual-title = Manuelle Anmeldung
+
+# Source: ./src/components/manual_credentials.rs:65
+sync-manual-username = Benutzername
+
+# Source: ./src/components/manual_credentials.rs:99
+sync-manual-validating = Prüfe Zugangsdaten...
+
 # Source: ./src/components/settings.rs:280
 sync-not-configured = Noch keine Synchronisierung konfiguriert
 
 # Source: ./src/components/settings.rs:737
 sync-now = Jetzt synchronisieren
 
+# Source: ./src/components/settings.rs:890
+sync-originals-policy-always = Immer
+
+# Source: ./src/components/settings.rs:896
+sync-originals-policy-hint = Daten und Vorschaubilder werden immer synchronisiert. Bei „Nur auf Anfrage“ werden Originale nur mit „Jetzt synchronisieren“ hochgeladen und erst beim Öffnen eines Fotos heruntergeladen.
+
+# Source: ./src/components/settings.rs:868
+sync-originals-policy-label = Original-Fotos übertragen
+
+# Source: ./src/components/settings.rs:893
+sync-originals-policy-on-demand = Nur auf Anfrage
+
+# Source: ./src/components/settings.rs:892
+sync-originals-policy-wifi-and-charging = Nur im WLAN beim Laden
+
+# Source: ./src/components/settings.rs:891
+sync-originals-policy-wifi-only = Nur im WLAN
+
 # Source: ./src/components/settings.rs:674
 sync-path = Pfad
 
@@ -726,14 +1651,94 @@ sync-path-hint = Ordner auf dem Server, in dem die Fotos gespeichert werden
 # Source: ./src/components/settings.rs:997
 sync-path-label = Speicherpfad
 
+# Source: ./src/components/settings.rs:952
+sync-pause-hint = Gilt nur für die automatische Synchronisierung; „Jetzt synchronisieren“ läuft immer. Niedriger Akkustand: höchstens { $percent } % ohne Ladegerät.
+
+# Source: ./src/components/settings.rs:903
+sync-pause-label = Hintergrund-Synchronisierung aussetzen
+
+# Source: ./src/components/settings.rs:947
+sync-pause-on-low-battery = Bei niedrigem Akkustand
+
+# Source: ./src/components/settings.rs:925
+sync-pause-on-metered = Bei getakteter Verbindung (Mobilfunk)
+
+# Source: ./src/components/settings.rs:990
+sync-paused = Synchronisierung anhalten
+
+# Source: ./src/components/sync_progress.rs:11
+sync-phase-applying = { $ops } Änderungen werden übernommen
+
+# Source: ./src/components/sync_progress.rs:9
+sync-phase-downloading-ops = Änderungsdateien { $done } von { $total } geladen
+
+# Source: ./src/components/sync_progress.rs:13
+sync-phase-downloading-photos = Fotos { $done } von { $total } geladen
+
+# Source: ./src/components/sync_progress.rs:7
+sync-phase-listing = Server wird abgefragt
+
+# Source: ./src/components/sync_progress.rs:53
+sync-phase-title = Synchronisation läuft…
+
+# Source: ./src/components/sync_progress.rs:15
+sync-phase-uploading-ops = Eigene Änderungen werden hochgeladen
+
+# Source: ./src/components/sync_progress.rs:17
+sync-phase-uploading-photos = Fotos { $done } von { $total } hochgeladen
+
+# Source: ./src/components/settings.rs:995
+sync-pin-certificate = Server-Zertifikat merken
+
+# Source: ./src/components/settings.rs:998
+sync-pin-certificate-hint = Für selbst gehostete Server: Ändert sich das Zertifikat später, wird die Synchronisation angehalten.
+
+# Source: ./src/components/settings.rs:1017
+# Parameters: $status
+sync-poll-last-status = letzte Antwort: HTTP { $status }
+
+# Source: ./src/components/settings.rs:1011
+# Parameters: $attempt, $elapsed, $next
+sync-poll-progress = Versuch { $attempt } · seit { $elapsed } s · nächste Abfrage in { $next } s
+
 # Polling in background message
 # Source: ./src/components/settings.rs:1036
 sync-polling-background = Polling läuft im Hintergrund (max. 5 Minuten)
 
+# Source: ./src/components/settings.rs:994
+sync-proxy-hint = HTTP- oder SOCKS5-Proxy für Anmeldung und Synchronisation, z.B. socks5://127.0.0.1:1080. Leer lassen für eine direkte Verbindung.
+
+# Source: ./src/components/settings.rs:984
+sync-proxy-label = Proxy (optional)
+
+# Source: ./src/components/settings.rs:572
+sync-relogin = Erneut anmelden
+
 # Source: ./src/components/settings.rs:572
 # UNUSED: This key is no longer used in the code
 sync-running = Vollständige synchronisierung läuft
 
+# Source: ./src/components/settings.rs:39
+sync-scope-coop = Ställe
+
+# Source: ./src/components/settings.rs:30
+sync-scope-egg = Eier-Einträge
+
+# Source: ./src/components/settings.rs:29
+sync-scope-event = Ereignisse
+
+# Source: ./src/components/settings.rs:1019
+sync-scope-hint = Angehalten werden Änderungen lokal gesammelt und beim Fortsetzen übertragen. Abgewählte Bereiche bleiben nur auf diesem Gerät; beim erneuten Einschalten wird alles noch einmal abgeglichen.
+
+# Source: ./src/components/settings.rs:973
+sync-scope-label = Was wird synchronisiert
+
+# Source: ./src/components/settings.rs:31
+sync-scope-photo = Fotos
+
+# Source: ./src/components/settings.rs:28
+sync-scope-quail = Wachteln
+
 # Source: ./src/components/settings.rs:660
 sync-server = Server
 
@@ -752,11 +1757,25 @@ sync-settings-deleted = Einstellungen gelöscht
 # Source: ./src/components/settings.rs:974
 sync-setup-title = Nextcloud Synchronisierung einrichten
 
+# Parameters: $count, $mb
+sync-storage-evicted = { $count } Originale entfernt ({ $mb } MB frei)
+
+sync-storage-limit-hint = Leer = unbegrenzt. Wird das Limit überschritten, werden bereits synchronisierte Originale entfernt, die am längsten nicht angesehen wurden. Vorschaubilder bleiben erhalten, Originale werden beim Öffnen neu geladen.
+
+sync-storage-limit-label = Max. Fotospeicher auf diesem Gerät (MB)
+
 # Source: ./src/components/settings.rs:582
 # Parameters: $eggs, $quails, $photos, $events
 # UNUSED: This key is no longer used in the code
 sync-success = Synchronisierung abgeschlossen: { $quails } Wachteln, { $events } Ereignisse, { $eggs } Eier-Einträge, { $photos } Fotos
 
+# Source: ./src/components/settings.rs:856
+# Parameters: $default
+sync-upload-concurrency-hint = Leer = Standard ({ $default }). Vorschaubilder werden vor den Originalen hochgeladen; mehr gleichzeitige Uploads beschleunigen die erste Synchronisierung bei guter Verbindung.
+
+# Source: ./src/components/settings.rs:824
+sync-upload-concurrency-label = Gleichzeitige Foto-Uploads
+
 # Source: ./src/components/settings.rs:667
 sync-username = Benutzer
 
@@ -764,6 +1783,34 @@ sync-username = Benutzer
 # Source: ./src/components/settings.rs:1034
 sync-waiting = Warte auf Anmeldung...
 
+# Source: ./src/components/transfer_stats.rs:90
+transfer-empty = Noch kein Datenverkehr aufgezeichnet
+
+# Source: ./src/components/transfer_stats.rs:132
+transfer-hint = Gezählt werden die übertragenen Dateien ohne Protokoll-Overhead. Tageswerte werden { $days } Tage aufbewahrt.
+
+# Source: ./src/components/transfer_stats.rs:70
+transfer-month = 30 Tage
+
+# Source: ./src/components/transfer_stats.rs:127
+transfer-reset = Statistik zurücksetzen
+
+# Source: ./src/components/transfer_stats.rs:98
+transfer-summary = ↑ { $up } · ↓ { $down } · { $requests } Anfragen · { $runs } Syncs
+
+# Source: ./src/components/transfer_stats.rs:87
+transfer-title = Datenverbrauch
+
+# Source: ./src/components/transfer_stats.rs:68
+transfer-today = Heute
+
+# Source: ./src/components/transfer_stats.rs:69
+transfer-week = 7 Tage
+
+# Source: ./src/components/treatment.rs:25
+# Parameters: $medication, $days, $eggs, $meat
+treatment-summary = { $medication } – { $days } Tage, Eier bis { $eggs }, Fleisch bis { $meat }
+
 # Source: ./src/components/event_edit.rs:270
 updated = Aktualisiert
 
@@ -788,3 +1835,15 @@ weekday-tue = Di
 # Source: ./src/components/egg_history.rs:93
 weekday-wed = Mi
 
+# Source: ./src/components/treatment.rs:109
+# Parameters: $coop, $date
+withdrawal-eggs-coop = Eier aus { $coop } nicht verkaufen bis { $date }
+
+# Source: ./src/components/treatment.rs:110
+# Parameters: $date
+withdrawal-eggs-without-coop = Eier von Wachteln ohne Stall nicht verkaufen bis { $date }
+
+# Source: ./src/components/profile_detail.rs:427
+# Parameters: $date
+withdrawal-meat = Nicht schlachten bis { $date }
+
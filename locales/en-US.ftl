@@ -18,6 +18,9 @@ action-cancel =
 # Source: ./src/components/profile_detail.rs:500
 action-close = 
 
+# Source: ./src/components/profile_detail.rs:530
+action-crop-rotate = 
+
 # Source: ./src/components/event_edit.rs:474
 action-delete = 
 
@@ -40,9 +43,15 @@ action-loading =
 # Source: ./src/components/profile_list.rs:72
 action-new = 
 
+# Source: ./src/components/photo_paste.rs:49
+action-paste-image = 
+
 # Source: ./src/components/event_edit.rs:441
 action-photo = 
 
+# Source: ./src/components/photo_trash.rs:40
+action-restore = 
+
 # Source: ./src/components/settings.rs:143
 action-retry = 
 
@@ -52,6 +61,9 @@ action-save =
 # Source: ./src/components/profile_edit.rs:373
 action-saving = 
 
+# Source: ./src/components/profile_detail.rs:585
+action-set-profile-photo = 
+
 # Source: ./src/components/egg_tracking.rs:249
 action-update = 
 
@@ -62,7 +74,7 @@ app-title =
 backup-cleanup-button = 🧹 Delete orphaned photos
 
 # Source: ./src/components/settings.rs:866
-backup-cleanup-description = Removes photos without a valid quail or event assignment.
+backup-cleanup-description = Removes photos without a valid quail or event assignment and leftover photo files without a database entry.
 
 # Source: ./src/components/settings.rs:884
 # Parameters: $error
@@ -89,6 +101,12 @@ backup-export-description = Creates a ZIP backup with quails, events, eggs and p
 # Parameters: $error
 backup-export-error = ❌ Export error: { $error }
 
+# Source: ./src/components/settings.rs:1165
+backup-export-json-button = 
+
+# Source: ./src/components/settings.rs:1173
+backup-export-reduced-photos = 
+
 # Source: ./src/components/settings.rs:917
 # Parameters: $path
 backup-export-success = ✅ Export created: { $path }
@@ -99,21 +117,250 @@ backup-export-title = 📦 Backup & restore data
 # Source: ./src/components/settings.rs:965
 backup-import-button = 📥 Import data from import.zip
 
-# Source: ./src/components/settings.rs:955
+# Source: ./src/components/backup_import.rs:167
+backup-import-choice-both = 
+
+# Source: ./src/components/backup_import.rs:165
+backup-import-choice-import = 
+
+# Source: ./src/components/backup_import.rs:164
+backup-import-choice-local = 
+
+# Source: ./src/components/backup_import.rs:136
+backup-import-encrypted = 
+
+# Source: ./src/components/backup_import.rs:34
 # Parameters: $error
 backup-import-error = ❌ Import error: { $error }
 
-# Source: ./src/components/settings.rs:946
+# Source: ./src/components/backup_import.rs:29
 # Parameters: $path
 backup-import-missing = ❌ Import file not found: { $path }
 
-# Source: ./src/components/settings.rs:952
+# Source: ./src/components/settings.rs:1168
+# Parameters: $error
+backup-import-pick-error = 
+
+# Source: ./src/components/backup_import.rs:87
+backup-import-preview-added = 
+
+# Source: ./src/components/backup_import.rs:116
+backup-import-preview-apply = 
+
+# Source: ./src/components/backup_import.rs:134
+backup-import-preview-conflict = 
+
+# Source: ./src/components/backup_import.rs:90
+backup-import-preview-conflicts = 
+
+# Source: ./src/components/backup_import.rs:75
+backup-import-preview-title = 
+
+# Source: ./src/components/backup_import.rs:89
+backup-import-preview-unchanged = 
+
+# Source: ./src/components/backup_import.rs:136
+backup-import-preview-update = 
+
+# Source: ./src/components/backup_import.rs:88
+backup-import-preview-updated = 
+
+# Source: ./src/components/backup_import.rs:54
 # Parameters: $path
 backup-import-success = ✅ Import successful from { $path }
 
+# Source: ./src/components/backup_import.rs:148
+backup-import-unlock = 
+
+# Source: ./src/components/selective_export.rs:33
+# Parameters: $min
+backup-passphrase-placeholder = 
+
+# Source: ./src/components/settings.rs:964
+# Parameters: $files, $mb, $missing
+backup-sweep-success = 
+
+# Source: ./src/components/batch_actions.rs:106
+batch-action-coop = 
+
+# Source: ./src/components/batch_actions.rs:99
+batch-action-event = 
+
+# Source: ./src/components/batch_actions.rs:177
+# Parameters: $count
+batch-apply = 
+
+# Source: ./src/components/batch_actions.rs:62
+# Parameters: $count
+batch-coop-done = 
+
+# Source: ./src/components/batch_actions.rs:157
+# Parameters: $count
+batch-delete-confirm = 
+
+# Source: ./src/components/batch_actions.rs:65
+# Parameters: $count
+batch-delete-done = 
+
+# Source: ./src/components/batch_actions.rs:59
+# Parameters: $count
+batch-event-done = 
+
+# Source: ./src/components/profile_list.rs:177
+batch-select-all = 
+
+# Source: ./src/components/profile_list.rs:175
+batch-select-none = 
+
+# Source: ./src/components/profile_list.rs:162
+# Parameters: $count
+batch-selected = 
+
+# Source: ./src/components/calendar_export.rs:72
+calendar-export-button = 
+
+# Source: ./src/components/calendar_export.rs:63
+calendar-export-description = 
+
+# Source: ./src/components/calendar_export.rs:32
+# Parameters: $error
+calendar-export-error = 
+
+# Source: ./src/components/calendar_export.rs:100
+calendar-export-subscribe-hint = 
+
+# Source: ./src/components/calendar_export.rs:27
+# Parameters: $path
+calendar-export-success = 
+
+# Source: ./src/components/calendar_export.rs:60
+calendar-export-title = 
+
+# Source: ./src/components/calendar_export.rs:80
+calendar-export-upload-button = 
+
+# Source: ./src/components/calendar_export.rs:45
+# Parameters: $path
+calendar-export-uploaded = 
+
+# Source: ./src/components/calendar_export.rs:40
+calendar-export-uploading = 
+
+# Source: ./src/components/cloud_export.rs:104
+cloud-export-button = 
+
+# Source: ./src/components/cloud_export.rs:70
+cloud-export-description = 
+
+# Source: ./src/components/cloud_export.rs:57
+# Parameters: $error
+cloud-export-error = 
+
+# Source: ./src/components/cloud_export.rs:42
+cloud-export-exporting = 
+
+# Source: ./src/components/cloud_export.rs:74
+cloud-export-folder = 
+
+# Source: ./src/components/cloud_export.rs:116
+cloud-export-open = 
+
+# Source: ./src/components/cloud_export.rs:54
+# Parameters: $path
+cloud-export-success = 
+
+# Source: ./src/components/cloud_export.rs:68
+cloud-export-title = 
+
+# Source: ./src/components/cloud_export.rs:45
+# Parameters: $size
+cloud-export-uploading = 
+
 # Source: ./src/components/profile_edit.rs:394
 confirm-delete-quail = 
 
+# Source: ./src/components/profile_list.rs:118
+coop-filter-all = 
+
+# Source: ./src/components/profile_add.rs:191
+coop-none = 
+
+# Source: ./src/components/egg_tracking.rs:237
+coop-whole-flock = 
+
+# Source: ./src/components/coops.rs:200
+coops-add = 
+
+# Source: ./src/components/coops.rs:117
+coops-description = 
+
+# Source: ./src/components/coops.rs:127
+# Parameters: $count
+coops-members = 
+
+# Source: ./src/components/coops.rs:156
+coops-name-placeholder = 
+
+# Source: ./src/components/coops.rs:114
+coops-title = 
+
+# Source: ./src/components/data_location.rs:77
+data-location-browse = 
+
+# Source: ./src/components/data_location.rs:121
+data-location-busy = 
+
+# Source: ./src/components/data_location.rs:84
+data-location-confirm = 
+
+# Source: ./src/components/data_location.rs:53
+data-location-current = 
+
+# Source: ./src/components/data_location.rs:50
+data-location-description = 
+
+# Source: ./src/components/data_location.rs:37
+data-location-error = 
+
+# Source: ./src/components/data_location.rs:91
+data-location-move = 
+
+# Source: ./src/components/data_location.rs:28
+data-location-moved = 
+
+# Source: ./src/components/data_location.rs:60
+data-location-placeholder = 
+
+# Source: ./src/components/data_location.rs:117
+data-location-reset = 
+
+# Source: ./src/components/data_location.rs:47
+data-location-title = 
+
+# Source: ./src/components/database_encryption.rs:58
+db-encryption-disable = 
+
+# Source: ./src/components/database_encryption.rs:51
+db-encryption-disable-confirm = 
+
+# Source: ./src/components/database_encryption.rs:46
+db-encryption-enable = 
+
+# Source: ./src/components/database_encryption.rs:28
+db-encryption-off = 
+
+# Source: ./src/components/database_encryption.rs:25
+db-encryption-on = 
+
+# Source: ./src/components/database_encryption.rs:27
+db-encryption-pending-off = 
+
+# Source: ./src/components/database_encryption.rs:26
+db-encryption-pending-on = 
+
+# Source: ./src/components/database_encryption.rs:39
+db-encryption-title = 
+
 # Source: ./src/components/egg_history.rs:124
 # Parameters: $count
 egg-history-eggs-count = 
@@ -121,6 +368,17 @@ egg-history-eggs-count =
 # Source: ./src/components/egg_history.rs:70
 egg-history-empty = 
 
+# Source: ./src/components/egg_history.rs:76
+egg-history-export-csv = 
+
+# Source: ./src/components/egg_history.rs:45
+# Parameters: $error
+egg-history-export-error = 
+
+# Source: ./src/components/egg_history.rs:41
+# Parameters: $path
+egg-history-export-success = 
+
 # Source: ./src/components/egg_history.rs:17
 # Parameters: $count
 egg-history-loaded = 
@@ -140,6 +398,54 @@ egg-tracking-success =
 # Source: ./src/components/home.rs:59
 egg-tracking-title = 
 
+# Source: ./src/components/photo_encryption.rs:90
+encryption-accept-plaintext = 
+
+# Source: ./src/components/photo_encryption.rs:93
+encryption-accept-plaintext-hint = 
+
+# Source: ./src/components/photo_encryption.rs:115
+encryption-disable = 
+
+# Source: ./src/components/photo_encryption.rs:98
+encryption-disable-confirm = 
+
+# Source: ./src/components/photo_encryption.rs:39
+encryption-enabled = 
+
+# Source: ./src/components/photo_encryption.rs:62
+encryption-export-hint = 
+
+# Source: ./src/components/photo_encryption.rs:205
+encryption-from-passphrase = 
+
+# Source: ./src/components/photo_encryption.rs:141
+encryption-generate = 
+
+# Source: ./src/components/photo_encryption.rs:158
+encryption-import = 
+
+# Source: ./src/components/photo_encryption.rs:132
+encryption-off-hint = 
+
+# Source: ./src/components/photo_encryption.rs:53
+encryption-on-hint = 
+
+# Source: ./src/components/photo_encryption.rs:165
+encryption-passphrase = 
+
+# Source: ./src/components/photo_encryption.rs:209
+encryption-passphrase-hint = 
+
+# Source: ./src/components/photo_encryption.rs:176
+encryption-passphrase-needs-sync = 
+
+# Source: ./src/components/photo_encryption.rs:75
+encryption-show-key = 
+
+# Source: ./src/components/photo_encryption.rs:49
+encryption-title = 
+
 # Source: ./src/components/profile_add.rs:235
 error = 
 
@@ -155,12 +461,46 @@ error-android-only-camera =
 # Source: ./src/components/event_edit.rs:378
 error-android-only-gallery = 
 
+# Source: ./src/components/manual_credentials.rs:115
+error-auth-cancelled = 
+
+# Source: ./src/components/manual_credentials.rs:124
+# Parameters: $fingerprint
+error-auth-certificate-changed = 
+
+# Source: ./src/components/manual_credentials.rs:110
+error-auth-dns = 
+
+# Source: ./src/components/manual_credentials.rs:124
+error-auth-maintenance = 
+
+# Source: ./src/components/manual_credentials.rs:121
+# Parameters: $url
+error-auth-not-nextcloud = 
+
+# Source: ./src/components/manual_credentials.rs:114
+error-auth-parse = 
+
+# Source: ./src/components/manual_credentials.rs:113
+# Parameters: $status
+error-auth-status = 
+
+# Source: ./src/components/manual_credentials.rs:112
+error-auth-timeout = 
+
+# Source: ./src/components/manual_credentials.rs:111
+error-auth-tls = 
+
 # Source: ./src/components/statistics.rs:54
 error-calculation = 
 
 # Source: ./src/components/profile_detail.rs:292
 error-camera-android-only = 
 
+# Source: ./src/components/profile_edit.rs:403
+# Parameters: $error
+error-caption-save-failed = 
+
 # Source: ./src/components/profile_detail.rs:286
 error-capture-failed = 
 
@@ -171,6 +511,9 @@ error-capture-photo =
 # Source: ./src/components/settings.rs:65
 error-client = 
 
+# Source: ./src/components/photo_paste.rs:59
+error-clipboard-no-image = 
+
 # Source: ./src/components/settings.rs:593
 error-connection = 
 
@@ -215,6 +558,10 @@ error-empty-date =
 # Parameters: $error
 error-event-save = 
 
+# Source: ./src/components/profile_detail.rs:219
+# Parameters: $failed, $total
+error-import-failed = 
+
 # Source: ./src/components/event_add.rs:50
 error-invalid-date = 
 
@@ -254,10 +601,21 @@ error-name-required =
 # Source: ./src/components/settings.rs:56
 error-network = 
 
+# Source: ./src/components/photo_paste.rs:40
+error-paste-failed = 
+
+# Source: ./src/components/profile_detail.rs:641
+# Parameters: $error
+error-photo-edit-failed = 
+
 # Source: ./src/components/event_edit.rs:372
 # Parameters: $error
 error-pick-images = 
 
+# Source: ./src/components/profile_detail.rs:70
+# Parameters: $error
+error-profile-photo-failed = 
+
 # Source: ./src/components/profile_add.rs:90
 # Parameters: $error
 error-save = 
@@ -315,6 +673,9 @@ event-type-sick =
 # Source: ./src/components/event_add.rs:136
 event-type-slaughtered = 
 
+# Source: ./src/components/event_add.rs:149
+event-type-treatment = 
+
 # No events available
 # Source: ./src/components/profile_detail.rs:415
 events-empty = 
@@ -322,6 +683,74 @@ events-empty =
 # Source: ./src/components/profile_detail.rs:395
 events-timeline-title = 
 
+# Source: ./src/components/export_templates.rs:22
+export-template-column-date = 
+
+# Source: ./src/components/export_templates.rs:24
+export-template-column-event-type = 
+
+# Source: ./src/components/export_templates.rs:20
+export-template-column-gender = 
+
+# Source: ./src/components/export_templates.rs:19
+export-template-column-name = 
+
+# Source: ./src/components/export_templates.rs:26
+export-template-column-notes = 
+
+# Source: ./src/components/export_templates.rs:23
+export-template-column-quail = 
+
+# Source: ./src/components/export_templates.rs:21
+export-template-column-ring-color = 
+
+# Source: ./src/components/export_templates.rs:25
+export-template-column-total-eggs = 
+
+# Source: ./src/components/export_templates.rs:151
+export-template-columns = 
+
+# Source: ./src/components/export_templates.rs:117
+export-template-description = 
+
+# Source: ./src/components/export_templates.rs:13
+export-template-entity-eggs = 
+
+# Source: ./src/components/export_templates.rs:12
+export-template-entity-events = 
+
+# Source: ./src/components/export_templates.rs:11
+export-template-entity-quails = 
+
+# Source: ./src/components/export_templates.rs:74
+# Parameters: $error
+export-template-error = 
+
+# Source: ./src/components/export_templates.rs:174
+export-template-event-types = 
+
+# Source: ./src/components/export_templates.rs:197
+export-template-event-types-hint = 
+
+# Source: ./src/components/export_templates.rs:243
+export-template-export-button = 
+
+# Source: ./src/components/export_templates.rs:124
+export-template-name-placeholder = 
+
+# Source: ./src/components/export_templates.rs:270
+export-template-new-button = 
+
+# Source: ./src/components/export_templates.rs:69
+# Parameters: $path
+export-template-success = 
+
+# Source: ./src/components/export_templates.rs:114
+export-template-title = 
+
+# Source: ./src/components/profile_add.rs:190
+field-coop = 
+
 # Source: ./src/components/event_add.rs:144
 field-date = 
 
@@ -331,6 +760,12 @@ field-date-format-hint =
 # Source: ./src/components/egg_tracking.rs:189
 field-date-required = 
 
+# Source: ./src/components/treatment.rs:65
+field-dose = 
+
+# Source: ./src/components/treatment.rs:79
+field-egg-withdrawal-days = 
+
 # Source: ./src/components/egg_tracking.rs:217
 field-eggs-count-placeholder = 
 
@@ -340,10 +775,22 @@ field-eggs-count-required =
 # Source: ./src/components/event_add.rs:121
 field-event-type = 
 
+# Source: ./src/components/pedigree.rs:73
+field-father = 
+
 # Gender
 # Source: ./src/components/profile_edit.rs:221
 field-gender = 
 
+# Source: ./src/components/treatment.rs:80
+field-meat-withdrawal-days = 
+
+# Source: ./src/components/treatment.rs:56
+field-medication = 
+
+# Source: ./src/components/pedigree.rs:56
+field-mother = 
+
 # e.g. Hen 1
 # Source: ./src/components/profile_edit.rs:211
 field-name-placeholder = 
@@ -369,6 +816,9 @@ field-photos =
 # Source: ./src/components/profile_edit.rs:236
 field-ring-color = 
 
+# Source: ./src/components/treatment.rs:78
+field-treatment-days = 
+
 # Source: ./src/components/event_edit.rs:277
 field-type = 
 
@@ -384,6 +834,39 @@ gender-male =
 # Source: ./src/components/profile_edit.rs:227
 gender-unknown = 
 
+# Source: ./src/components/entity_history.rs:99
+history-changed = 
+
+# Source: ./src/components/entity_history.rs:97
+history-created = 
+
+# Source: ./src/components/entity_history.rs:95
+history-deleted = 
+
+# Source: ./src/components/entity_history.rs:65
+history-empty = 
+
+# Source: ./src/components/entity_history.rs:131
+history-field-eggs = 
+
+# Source: ./src/components/entity_history.rs:123
+history-field-name = 
+
+# Source: ./src/components/entity_history.rs:126
+history-field-profile-photo = 
+
+# Source: ./src/components/entity_history.rs:127
+history-field-quail = 
+
+# Source: ./src/components/entity_history.rs:90
+history-other-device = 
+
+# Source: ./src/components/entity_history.rs:29
+history-tab = 
+
+# Source: ./src/components/entity_history.rs:87
+history-this-device = 
+
 # DB: {path}
 # Source: ./src/components/home.rs:90
 # Parameters: $database, $path
@@ -396,6 +879,33 @@ info-photos-detail-view =
 # Source: ./src/components/profile_edit.rs:359
 info-tap-photo-to-mark = 
 
+# Source: ./src/components/database_integrity.rs:71
+integrity-check-button = 
+
+# Source: ./src/components/database_integrity.rs:64
+integrity-description = 
+
+# Source: ./src/components/database_integrity.rs:30
+integrity-error = 
+
+# Source: ./src/components/database_integrity.rs:82
+integrity-issues = 
+
+# Source: ./src/components/database_integrity.rs:77
+integrity-ok = 
+
+# Source: ./src/components/database_integrity.rs:92
+integrity-repair-button = 
+
+# Source: ./src/components/database_integrity.rs:95
+integrity-repair-hint = 
+
+# Source: ./src/components/database_integrity.rs:42
+integrity-repaired = 
+
+# Source: ./src/components/database_integrity.rs:61
+integrity-title = 
+
 # Source: ./src/components/event_edit.rs:478
 loading-event = 
 
@@ -403,8 +913,35 @@ loading-event =
 # Source: ./src/components/profile_detail.rs:480
 loading-profile = 
 
+# Source: ./src/components/local_backup.rs:111
+local-backup-create = 
+
+# Source: ./src/components/local_backup.rs:30
+local-backup-created = 
+
+# Source: ./src/components/local_backup.rs:61
+local-backup-description = 
+
+# Source: ./src/components/local_backup.rs:64
+local-backup-empty = 
+
+# Source: ./src/components/local_backup.rs:31
+local-backup-error = 
+
+# Source: ./src/components/local_backup.rs:80
+local-backup-restore = 
+
+# Source: ./src/components/local_backup.rs:89
+local-backup-restore-confirm = 
+
+# Source: ./src/components/local_backup.rs:45
+local-backup-restored = 
+
+# Source: ./src/components/local_backup.rs:58
+local-backup-title = 
+
 # the format string for the chrono format time.
-# Source: ./src/components/settings.rs:13
+# Source: ./src/components/sync_history.rs:142
 log-time-format = %H:%M:%S
 
 # Eier
@@ -419,6 +956,9 @@ nav-home =
 # Source: ./src/components/navigation.rs:30
 nav-profiles = 
 
+# Source: ./src/components/navigation.rs:56
+nav-search = 
+
 # Statistik
 # Source: ./src/components/navigation.rs:50
 nav-statistics = 
@@ -431,6 +971,22 @@ network-checking =
 # Source: ./src/components/settings.rs:136
 network-offline = 
 
+# Source: ./src/components/pedigree.rs:61
+parent-unknown = 
+
+# Source: ./src/components/pedigree.rs:92
+# Parameters: $ancestors
+pedigree-inbreeding-warning = 
+
+# Source: ./src/components/pedigree.rs:186
+pedigree-offspring-empty = 
+
+# Source: ./src/components/pedigree.rs:182
+pedigree-offspring-title = 
+
+# Source: ./src/components/pedigree.rs:166
+pedigree-title = 
+
 # Time period filter buttons
 # Source: ./src/components/statistics.rs:92
 period-all = 
@@ -455,6 +1011,10 @@ photo-none-selected =
 # Source: ./src/components/profile_add.rs:202
 photo-selected = 
 
+# Source: ./src/components/photo_trash.rs:23
+# Parameters: $days
+photo-trashed = 
+
 # Source: ./src/components/event_edit.rs:321
 # Parameters: $count
 photos-count = 
@@ -463,6 +1023,9 @@ photos-count =
 # Source: ./src/components/profile_edit.rs:269
 photos-empty = 
 
+# Source: ./src/components/treatment.rs:69
+placeholder-dose = 
+
 # Source: ./src/components/event_add.rs:163
 placeholder-event-notes = 
 
@@ -510,6 +1073,106 @@ profile-photo-label =
 # Source: ./src/components/profile_add.rs:164
 profile-ring-color-label = 
 
+# Source: ./src/components/state_rebuild.rs:39
+rebuild-backup = 
+
+# Source: ./src/components/state_rebuild.rs:85
+rebuild-button = 
+
+# Source: ./src/components/state_rebuild.rs:78
+rebuild-confirm = 
+
+# Source: ./src/components/state_rebuild.rs:48
+rebuild-consistent = 
+
+# Source: ./src/components/state_rebuild.rs:73
+rebuild-description = 
+
+# Source: ./src/components/state_rebuild.rs:25
+rebuild-error = 
+
+# Source: ./src/components/state_rebuild.rs:59
+rebuild-remote-error = 
+
+# Source: ./src/components/state_rebuild.rs:20
+rebuild-running = 
+
+# Source: ./src/components/state_rebuild.rs:43
+rebuild-success = 
+
+# Source: ./src/components/state_rebuild.rs:71
+rebuild-title = 
+
+# Source: ./src/components/recovery.rs:118
+recovery-continue = 
+
+# Source: ./src/components/recovery.rs:65
+recovery-description = 
+
+# Source: ./src/components/recovery.rs:138
+# Parameters: $error
+recovery-issue-credentials = 
+
+# Source: ./src/components/recovery.rs:129
+# Parameters: $error
+recovery-issue-database = 
+
+# Source: ./src/components/recovery.rs:132
+# Parameters: $found, $expected
+recovery-issue-schema = 
+
+# Source: ./src/components/recovery.rs:135
+# Parameters: $path, $error
+recovery-issue-storage = 
+
+# Source: ./src/components/recovery.rs:77
+recovery-issues-title = 
+
+# Source: ./src/components/recovery.rs:111
+recovery-recheck = 
+
+# Source: ./src/components/recovery.rs:103
+recovery-reset-sync = 
+
+# Source: ./src/components/recovery.rs:44
+recovery-reset-sync-success = 
+
+# Source: ./src/components/recovery.rs:94
+recovery-restore-backup = 
+
+# Source: ./src/components/recovery.rs:97
+# Parameters: $path
+recovery-restore-hint = 
+
+# Source: ./src/components/recovery.rs:27
+recovery-restore-success = 
+
+# Source: ./src/components/recovery.rs:62
+recovery-title = 
+
+# Source: ./src/components/statistics.rs:221
+report-button = 
+
+# Source: ./src/components/statistics.rs:51
+report-creating = 
+
+# Source: ./src/components/statistics.rs:206
+report-description = 
+
+# Source: ./src/components/statistics.rs:60
+# Parameters: $error
+report-error = 
+
+# Source: ./src/components/statistics.rs:215
+report-include-photos = 
+
+# Source: ./src/components/statistics.rs:56
+# Parameters: $path
+report-success = 
+
+# Source: ./src/components/statistics.rs:202
+report-title = 
+
 # Black
 # Source: ./src/components/profile_edit.rs:251
 ring-color-black = 
@@ -554,12 +1217,173 @@ ring-color-white =
 # Source: ./src/components/profile_edit.rs:250
 ring-color-yellow = 
 
+# Source: ./src/components/scheduled_export.rs:62
+scheduled-export-description = 
+
+# Source: ./src/components/scheduled_export.rs:94
+scheduled-export-destination-both = 
+
+# Source: ./src/components/scheduled_export.rs:87
+scheduled-export-destination-local = 
+
+# Source: ./src/components/scheduled_export.rs:91
+scheduled-export-destination-nextcloud = 
+
+# Source: ./src/components/scheduled_export.rs:75
+scheduled-export-interval-monthly = 
+
+# Source: ./src/components/scheduled_export.rs:73
+scheduled-export-interval-off = 
+
+# Source: ./src/components/scheduled_export.rs:74
+scheduled-export-interval-weekly = 
+
+# Source: ./src/components/scheduled_export.rs:99
+scheduled-export-keep = 
+
+# Source: ./src/components/scheduled_export.rs:128
+# Parameters: $time, $message
+scheduled-export-last = 
+
+# Source: ./src/components/scheduled_export.rs:120
+# Parameters: $time
+scheduled-export-next = 
+
+# Source: ./src/components/scheduled_export.rs:118
+scheduled-export-soon = 
+
+# Source: ./src/components/scheduled_export.rs:59
+scheduled-export-title = 
+
+# Source: ./src/components/search.rs:53
+search-hint = 
+
+# Source: ./src/components/search.rs:94
+search-kind-egg-record = 
+
+# Source: ./src/components/search.rs:55
+search-no-results = 
+
+# Source: ./src/components/search.rs:40
+search-placeholder-all = 
+
 # Source: ./src/components/profile_list.rs:82
 search-placeholder-name = 
 
+# Source: ./src/components/search.rs:35
+search-title = 
+
+# Source: ./src/components/selective_export.rs:129
+selective-export-button = 
+
+# Source: ./src/components/selective_export.rs:60
+selective-export-description = 
+
+# Source: ./src/components/selective_export.rs:65
+selective-export-from = 
+
+# Source: ./src/components/selective_export.rs:99
+selective-export-include-eggs = 
+
+# Source: ./src/components/selective_export.rs:121
+selective-export-photos-none = 
+
+# Source: ./src/components/selective_export.rs:119
+selective-export-photos-originals = 
+
+# Source: ./src/components/selective_export.rs:165
+selective-export-photos-reduced = 
+
+# Source: ./src/components/selective_export.rs:120
+selective-export-photos-thumbnails = 
+
+# Source: ./src/components/selective_export.rs:85
+selective-export-quails = 
+
+# Source: ./src/components/selective_export.rs:58
+selective-export-title = 
+
+# Source: ./src/components/selective_export.rs:74
+selective-export-to = 
+
 # Source: ./src/components/home.rs:74
 settings-title = 
 
+# Source: ./src/components/share_button.rs:25
+share-button = 
+
+# Source: ./src/components/share_button.rs:20
+share-chooser-title = 
+
+# Source: ./src/components/share_button.rs:22
+# Parameters: $error
+share-error = 
+
+# Source: ./src/components/spreadsheet_import.rs:139
+# Parameters: $app
+spreadsheet-import-adapter-detected = 
+
+# Source: ./src/components/spreadsheet_import.rs:145
+spreadsheet-import-adapter-manual = 
+
+# Source: ./src/components/spreadsheet_import.rs:113
+spreadsheet-import-browse = 
+
+# Source: ./src/components/spreadsheet_import.rs:246
+# Parameters: $count
+spreadsheet-import-button = 
+
+# Source: ./src/components/spreadsheet_import.rs:72
+spreadsheet-import-description = 
+
+# Source: ./src/components/spreadsheet_import.rs:236
+# Parameters: $error
+spreadsheet-import-error = 
+
+# Source: ./src/components/spreadsheet_import.rs:159
+spreadsheet-import-ignore = 
+
+# Source: ./src/components/spreadsheet_import.rs:91
+spreadsheet-import-kind-eggs = 
+
+# Source: ./src/components/spreadsheet_import.rs:92
+spreadsheet-import-kind-quails = 
+
+# Source: ./src/components/spreadsheet_import.rs:122
+spreadsheet-import-load = 
+
+# Source: ./src/components/spreadsheet_import.rs:51
+# Parameters: $error
+spreadsheet-import-load-error = 
+
+# Source: ./src/components/spreadsheet_import.rs:129
+spreadsheet-import-mapping-title = 
+
+# Source: ./src/components/spreadsheet_import.rs:210
+# Parameters: $count
+spreadsheet-import-more-errors = 
+
+# Source: ./src/components/settings.rs:1181
+spreadsheet-import-open = 
+
+# Source: ./src/components/spreadsheet_import.rs:98
+spreadsheet-import-path-placeholder = 
+
+# Source: ./src/components/spreadsheet_import.rs:181
+# Parameters: $valid, $total
+spreadsheet-import-preview-valid = 
+
+# Source: ./src/components/spreadsheet_import.rs:205
+# Parameters: $row, $message
+spreadsheet-import-row-error = 
+
+# Source: ./src/components/spreadsheet_import.rs:229
+# Parameters: $created, $updated, $skipped
+spreadsheet-import-success = 
+
+# Source: ./src/components/spreadsheet_import.rs:67
+spreadsheet-import-title = 
+
 # Source: ./src/components/statistics.rs:208
 stats-add-entry = 
 
@@ -645,15 +1469,24 @@ status-sick =
 # Source: ./src/components/profile_detail.rs:365
 status-slaughtered = 
 
+# Source: ./src/components/profile_detail.rs:480
+status-treatment = 
+
 # Source: ./src/components/profile_edit.rs:188
 success-profile-updated = 
 
+# Source: ./src/components/settings.rs:664
+sync-certificate = 
+
 # Source: ./src/components/settings.rs:656
 sync-configured = 
 
 # Source: ./src/components/settings.rs:1025
 sync-connecting = 
 
+# Source: ./src/components/settings.rs:555
+sync-credentials-invalid = 
+
 # Source: ./src/components/settings.rs:744
 sync-delete-config = 
 
@@ -663,6 +1496,33 @@ sync-error =
 # Source: ./src/components/settings.rs:731
 sync-failed = 
 
+# Source: ./src/components/sync_history.rs:70
+sync-history-clear = 
+
+# Source: ./src/components/sync_history.rs:80
+sync-history-empty = 
+
+# Source: ./src/components/sync_history.rs:59
+sync-history-failures-only = 
+
+# Source: ./src/components/sync_history.rs:168
+sync-history-manual = 
+
+# Source: ./src/components/sync_history.rs:91
+sync-history-retention = 
+
+# Source: ./src/components/sync_history.rs:130
+sync-history-show-all = 
+
+# Source: ./src/components/sync_history.rs:148
+sync-history-summary = 
+
+# Source: ./src/components/sync_history.rs:48
+sync-history-title = 
+
+# Source: ./src/components/sync_history.rs:148
+sync-history-transfer = 
+
 # Source: ./src/components/settings.rs:697
 sync-last-sync = 
 
@@ -672,6 +1532,16 @@ sync-login =
 # Source: ./src/components/settings.rs:1046
 sync-login-browser = 
 
+# Source: ./src/components/settings.rs:914
+# Parameters: $minutes
+sync-login-expires = 
+
+# Source: ./src/components/settings.rs:901
+sync-login-in-app = 
+
+# Source: ./src/components/settings.rs:956
+sync-login-in-app-open = 
+
 # Source: ./src/components/settings.rs:1078
 sync-login-info-title = 
 
@@ -705,12 +1575,63 @@ sync-login-success =
 # Source: ./src/components/settings.rs:458
 sync-login-success-folder = 
 
+# Source: ./src/components/manual_credentials.rs:78
+sync-manual-app-password = 
+
+# Source: ./src/components/manual_credentials.rs:88
+sync-manual-app-password-hint = 
+
+# Source: ./src/components/manual_credentials.rs:23
+sync-manual-fields-required = 
+
+# Source: ./src/components/settings.rs:871
+sync-manual-hide = 
+
+# Source: ./src/components/manual_credentials.rs:54
+sync-manual-hint = 
+
+# Source: ./src/components/manual_credentials.rs:37
+sync-manual-invalid = 
+
+# Source: ./src/components/settings.rs:873
+sync-manual-show = 
+
+# Source: ./src/components/manual_credentials.rs:102
+sync-manual-submit = 
+
+# Source: ./src/components/manual_credentials.rs:51
+sync-manual-title = 
+
+# Source: ./src/components/manual_credentials.rs:65
+sync-manual-username = 
+
+# Source: ./src/components/manual_credentials.rs:99
+sync-manual-validating = 
+
 # Source: ./src/components/settings.rs:280
 sync-not-configured = 
 
 # Source: ./src/components/settings.rs:737
 sync-now = 
 
+# Source: ./src/components/settings.rs:890
+sync-originals-policy-always = 
+
+# Source: ./src/components/settings.rs:896
+sync-originals-policy-hint = 
+
+# Source: ./src/components/settings.rs:868
+sync-originals-policy-label = 
+
+# Source: ./src/components/settings.rs:893
+sync-originals-policy-on-demand = 
+
+# Source: ./src/components/settings.rs:892
+sync-originals-policy-wifi-and-charging = 
+
+# Source: ./src/components/settings.rs:891
+sync-originals-policy-wifi-only = 
+
 # Source: ./src/components/settings.rs:674
 sync-path = 
 
@@ -722,10 +1643,90 @@ sync-path-hint =
 # Source: ./src/components/settings.rs:997
 sync-path-label = 
 
+# Source: ./src/components/settings.rs:952
+sync-pause-hint = 
+
+# Source: ./src/components/settings.rs:903
+sync-pause-label = 
+
+# Source: ./src/components/settings.rs:947
+sync-pause-on-low-battery = 
+
+# Source: ./src/components/settings.rs:925
+sync-pause-on-metered = 
+
+# Source: ./src/components/settings.rs:990
+sync-paused = 
+
+# Source: ./src/components/sync_progress.rs:11
+sync-phase-applying = 
+
+# Source: ./src/components/sync_progress.rs:9
+sync-phase-downloading-ops = 
+
+# Source: ./src/components/sync_progress.rs:13
+sync-phase-downloading-photos = 
+
+# Source: ./src/components/sync_progress.rs:7
+sync-phase-listing = 
+
+# Source: ./src/components/sync_progress.rs:53
+sync-phase-title = 
+
+# Source: ./src/components/sync_progress.rs:15
+sync-phase-uploading-ops = 
+
+# Source: ./src/components/sync_progress.rs:17
+sync-phase-uploading-photos = 
+
+# Source: ./src/components/settings.rs:995
+sync-pin-certificate = 
+
+# Source: ./src/components/settings.rs:998
+sync-pin-certificate-hint = 
+
+# Source: ./src/components/settings.rs:1017
+# Parameters: $status
+sync-poll-last-status = 
+
+# Source: ./src/components/settings.rs:1011
+# Parameters: $attempt, $elapsed, $next
+sync-poll-progress = 
+
 # Polling in background message
 # Source: ./src/components/settings.rs:1036
 sync-polling-background = 
 
+# Source: ./src/components/settings.rs:994
+sync-proxy-hint = 
+
+# Source: ./src/components/settings.rs:984
+sync-proxy-label = 
+
+# Source: ./src/components/settings.rs:572
+sync-relogin = 
+
+# Source: ./src/components/settings.rs:39
+sync-scope-coop = 
+
+# Source: ./src/components/settings.rs:30
+sync-scope-egg = 
+
+# Source: ./src/components/settings.rs:29
+sync-scope-event = 
+
+# Source: ./src/components/settings.rs:1019
+sync-scope-hint = 
+
+# Source: ./src/components/settings.rs:973
+sync-scope-label = 
+
+# Source: ./src/components/settings.rs:31
+sync-scope-photo = 
+
+# Source: ./src/components/settings.rs:28
+sync-scope-quail = 
+
 # Source: ./src/components/settings.rs:660
 sync-server = 
 
@@ -744,6 +1745,20 @@ sync-settings-deleted =
 # Source: ./src/components/settings.rs:974
 sync-setup-title = 
 
+# Parameters: $count, $mb
+sync-storage-evicted = 
+
+sync-storage-limit-hint = 
+
+sync-storage-limit-label = 
+
+# Source: ./src/components/settings.rs:856
+# Parameters: $default
+sync-upload-concurrency-hint = 
+
+# Source: ./src/components/settings.rs:824
+sync-upload-concurrency-label = 
+
 # Source: ./src/components/settings.rs:667
 sync-username = 
 
@@ -751,6 +1766,34 @@ sync-username =
 # Source: ./src/components/settings.rs:1034
 sync-waiting = 
 
+# Source: ./src/components/transfer_stats.rs:90
+transfer-empty = 
+
+# Source: ./src/components/transfer_stats.rs:132
+transfer-hint = 
+
+# Source: ./src/components/transfer_stats.rs:70
+transfer-month = 
+
+# Source: ./src/components/transfer_stats.rs:127
+transfer-reset = 
+
+# Source: ./src/components/transfer_stats.rs:98
+transfer-summary = 
+
+# Source: ./src/components/transfer_stats.rs:87
+transfer-title = 
+
+# Source: ./src/components/transfer_stats.rs:68
+transfer-today = 
+
+# Source: ./src/components/transfer_stats.rs:69
+transfer-week = 
+
+# Source: ./src/components/treatment.rs:25
+# Parameters: $medication, $days, $eggs, $meat
+treatment-summary = 
+
 # Source: ./src/components/event_edit.rs:270
 updated = 
 
@@ -775,3 +1818,15 @@ weekday-tue =
 # Source: ./src/components/egg_history.rs:93
 weekday-wed = 
 
+# Source: ./src/components/treatment.rs:109
+# Parameters: $coop, $date
+withdrawal-eggs-coop = 
+
+# Source: ./src/components/treatment.rs:110
+# Parameters: $date
+withdrawal-eggs-without-coop = 
+
+# Source: ./src/components/profile_detail.rs:427
+# Parameters: $date
+withdrawal-meat = 
+
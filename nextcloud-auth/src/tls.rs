@@ -0,0 +1,203 @@
+//! Zertifikats-Pinning im TLS-Handshake
+//!
+//! Ohne gemerkten Fingerabdruck wird das Server-Zertifikat gegen die
+//! Wurzelzertifikate des Systems geprüft (private CAs, selbst importierte
+//! Zertifikate), ergänzt um die Mozilla-Wurzeln (`webpki-roots`) für Systeme
+//! ohne lesbaren Zertifikatsspeicher wie Android. Ist ein Fingerabdruck
+//! gemerkt, ersetzt der Vergleich damit die Prüfung der Kette, so bleiben
+//! auch selbstsignierte Server erreichbar. Weicht er ab, bricht bereits der
+//! Handshake ab, also bevor Zugangsdaten oder App-Passwort über die Leitung
+//! gehen.
+
+use crate::{AuthError, CertificatePin};
+use rustls::client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier};
+use rustls::client::WebPkiServerVerifier;
+use rustls::pki_types::{CertificateDer, ServerName, UnixTime};
+use rustls::{DigitallySignedStruct, RootCertStore, SignatureScheme};
+use std::sync::{Arc, OnceLock};
+
+/// Vergleich mit dem [`CertificatePin`], sonst normale Zertifikatsprüfung
+#[derive(Debug)]
+struct PinningVerifier {
+    inner: Arc<WebPkiServerVerifier>,
+    pin: CertificatePin,
+}
+
+impl ServerCertVerifier for PinningVerifier {
+    fn verify_server_cert(
+        &self,
+        end_entity: &CertificateDer<'_>,
+        intermediates: &[CertificateDer<'_>],
+        server_name: &ServerName<'_>,
+        ocsp_response: &[u8],
+        now: UnixTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        let fingerprint = crate::format_fingerprint(end_entity.as_ref());
+        let pinned = self
+            .pin
+            .check(&fingerprint)
+            .map_err(|e| rustls::Error::General(e.to_string()))?;
+        if !pinned {
+            let verified = self.inner.verify_server_cert(
+                end_entity,
+                intermediates,
+                server_name,
+                ocsp_response,
+                now,
+            );
+            match verified {
+                Ok(_) => {}
+                Err(e) if self.pin.accepts_untrusted() => {
+                    log::warn!(
+                        "TLS: Zertifikat nicht vertrauenswürdig ({}), akzeptiere {}",
+                        e,
+                        fingerprint
+                    );
+                }
+                Err(e) => return Err(e),
+            }
+        }
+        self.pin.accept(fingerprint);
+        Ok(ServerCertVerified::assertion())
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        self.inner.verify_tls12_signature(message, cert, dss)
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        self.inner.verify_tls13_signature(message, cert, dss)
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.inner.supported_verify_schemes()
+    }
+}
+
+/// Wurzelzertifikate des Systems plus `webpki-roots`, einmal pro Prozess
+/// geladen
+fn root_store() -> Arc<RootCertStore> {
+    static ROOTS: OnceLock<Arc<RootCertStore>> = OnceLock::new();
+    ROOTS
+        .get_or_init(|| {
+            let mut roots = RootCertStore {
+                roots: webpki_roots::TLS_SERVER_ROOTS.to_vec(),
+            };
+            let native = rustls_native_certs::load_native_certs();
+            for e in &native.errors {
+                log::warn!("TLS: System-Zertifikate nicht vollständig lesbar: {}", e);
+            }
+            let (added, ignored) = roots.add_parsable_certificates(native.certs);
+            log::debug!(
+                "TLS: {} System-Zertifikate geladen, {} ignoriert",
+                added,
+                ignored
+            );
+            Arc::new(roots)
+        })
+        .clone()
+}
+
+fn verifier(
+    pin: &CertificatePin,
+    provider: Arc<rustls::crypto::CryptoProvider>,
+) -> Result<PinningVerifier, AuthError> {
+    let inner = WebPkiServerVerifier::builder_with_provider(root_store(), provider)
+        .build()
+        .map_err(|e| AuthError::Client(format!("TLS: {}", e)))?;
+    Ok(PinningVerifier {
+        inner,
+        pin: pin.clone(),
+    })
+}
+
+/// TLS-Konfiguration, deren Handshakes `pin` prüfen und aktualisieren
+pub(crate) fn client_config(pin: &CertificatePin) -> Result<rustls::ClientConfig, AuthError> {
+    let provider = Arc::new(rustls::crypto::ring::default_provider());
+    let verifier = verifier(pin, provider.clone())?;
+    let config = rustls::ClientConfig::builder_with_provider(provider)
+        .with_safe_default_protocol_versions()
+        .map_err(|e| AuthError::Client(format!("TLS: {}", e)))?
+        .dangerous()
+        .with_custom_certificate_verifier(Arc::new(verifier))
+        .with_no_client_auth();
+    Ok(config)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn verify(pin: &CertificatePin, der: &[u8]) -> Result<ServerCertVerified, rustls::Error> {
+        let provider = Arc::new(rustls::crypto::ring::default_provider());
+        verifier(pin, provider).unwrap().verify_server_cert(
+            &CertificateDer::from(der.to_vec()),
+            &[],
+            &ServerName::try_from("cloud.example.com").unwrap(),
+            &[],
+            UnixTime::now(),
+        )
+    }
+
+    fn self_signed() -> Vec<u8> {
+        rcgen::generate_simple_self_signed(vec!["cloud.example.com".to_string()])
+            .unwrap()
+            .cert
+            .der()
+            .to_vec()
+    }
+
+    #[test]
+    fn test_changed_certificate_fails_handshake() {
+        let pin = CertificatePin::new(Some(crate::format_fingerprint(b"bekannt")));
+        let result = verify(&pin, b"anderes Zertifikat");
+        assert!(matches!(result, Err(rustls::Error::General(_))));
+        assert_eq!(
+            pin.rejected(),
+            Some(crate::format_fingerprint(b"anderes Zertifikat"))
+        );
+        assert_eq!(pin.seen(), None);
+    }
+
+    #[test]
+    fn test_matching_pin_replaces_chain_check() {
+        let der = self_signed();
+        let fingerprint = crate::format_fingerprint(&der);
+        let pin = CertificatePin::new(Some(fingerprint.clone()));
+        assert!(verify(&pin, &der).is_ok());
+        assert_eq!(pin.seen(), Some(fingerprint));
+    }
+
+    #[test]
+    fn test_untrusted_certificate_without_pin() {
+        let der = self_signed();
+        let fingerprint = crate::format_fingerprint(&der);
+
+        // Ohne Pin zählt die normale Prüfung, die kennt den Aussteller nicht
+        let pin = CertificatePin::default();
+        assert!(matches!(
+            verify(&pin, &der),
+            Err(rustls::Error::InvalidCertificate(_))
+        ));
+        assert_eq!(pin.seen(), None);
+
+        // Beim ersten Login wird es akzeptiert und gemerkt
+        let pin = CertificatePin::trust_on_first_use();
+        assert!(verify(&pin, &der).is_ok());
+        assert_eq!(pin.seen(), Some(fingerprint.clone()));
+
+        let pin = CertificatePin::danger_accept_invalid_certs();
+        assert!(verify(&pin, &der).is_ok());
+        assert_eq!(pin.seen(), Some(fingerprint));
+    }
+}
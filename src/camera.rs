@@ -82,7 +82,7 @@ fn load_class<'a>(
 }
 
 #[cfg(target_os = "android")]
-fn get_activity_instance<'a>(
+pub(crate) fn get_activity_instance<'a>(
     env: &mut jni::JNIEnv<'a>,
 ) -> Result<(JObject<'a>, JClass<'a>), AppError> {
     let loader = get_app_class_loader(env)?;
@@ -312,15 +312,55 @@ pub fn has_camera_permission() -> Result<bool, AppError> {
         .z()
         .map_err(|e| AppError::PermissionDenied(format!("Boolean conversion failed: {}", e)))
 }
+/// File types offered by the desktop file dialog (HEIC/AVIF are converted on import)
+#[cfg(all(not(target_os = "android"), feature = "desktop"))]
+const DESKTOP_IMAGE_EXTENSIONS: &[&str] = &[
+    "jpg", "jpeg", "JPG", "JPEG", "png", "webp", "heic", "HEIC", "heif", "avif",
+];
+
+#[cfg(all(not(target_os = "android"), feature = "desktop"))]
+fn image_dialog() -> rfd::FileDialog {
+    rfd::FileDialog::new().add_filter("Images", DESKTOP_IMAGE_EXTENSIONS)
+}
+
+/// Copies a picked file into the photo directory. The import moves the file
+/// it gets, so the user's original must never be handed to it directly.
+#[cfg(all(not(target_os = "android"), feature = "desktop"))]
+fn copy_into_photo_dir(path: &std::path::Path) -> Result<PathBuf, AppError> {
+    let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("jpg");
+    let target = PathBuf::from(crate::services::photo_service::get_absolute_photo_path(
+        &format!("import-{}.{}", uuid::Uuid::new_v4(), ext),
+    ));
+    std::fs::copy(path, &target)
+        .map_err(|e| AppError::PermissionDenied(format!("Copying {:?} failed: {}", path, e)))?;
+    Ok(target)
+}
+
+#[cfg(all(not(target_os = "android"), feature = "desktop"))]
+pub fn pick_image() -> Result<PathBuf, AppError> {
+    let path = image_dialog()
+        .pick_file()
+        .ok_or_else(|| AppError::PermissionDenied("No image selected".to_string()))?;
+    copy_into_photo_dir(&path)
+}
+
+#[cfg(all(not(target_os = "android"), feature = "desktop"))]
+pub fn pick_images() -> Result<Vec<PathBuf>, AppError> {
+    let paths = image_dialog()
+        .pick_files()
+        .ok_or_else(|| AppError::PermissionDenied("No images selected".to_string()))?;
+    paths.iter().map(|p| copy_into_photo_dir(p)).collect()
+}
+
 #[allow(dead_code)]
-#[cfg(not(target_os = "android"))]
+#[cfg(all(not(target_os = "android"), not(feature = "desktop")))]
 pub fn pick_image() -> Result<PathBuf, AppError> {
     Err(AppError::PermissionDenied(
         "Image picker not available on this platform".to_string(),
     ))
 }
 #[allow(dead_code)]
-#[cfg(not(target_os = "android"))]
+#[cfg(all(not(target_os = "android"), not(feature = "desktop")))]
 pub fn pick_images() -> Result<Vec<PathBuf>, AppError> {
     Err(AppError::PermissionDenied(
         "Multi image picker not available on this platform".to_string(),
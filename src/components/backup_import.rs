@@ -0,0 +1,245 @@
+use crate::components::settings::entity_type_label;
+use crate::database;
+use crate::services::backup_crypto::{self, Passphrase};
+use crate::services::export_import_service::{
+    self, ConflictChoice, ImportChange, ImportDecisions, ImportEntity, ImportItem, ImportMode,
+    ZipImportPreview,
+};
+use crate::Screen;
+use dioxus::prelude::*;
+use dioxus_i18n::t;
+use std::path::{Path, PathBuf};
+
+fn choice_key(choice: ConflictChoice) -> &'static str {
+    match choice {
+        ConflictChoice::KeepLocal => "local",
+        ConflictChoice::TakeImport => "import",
+        ConflictChoice::KeepBoth => "both",
+    }
+}
+
+/// `import.zip` im Export-Ordner, sonst `import.zip.enc` (verschlüsselt);
+/// nur wo es keinen Dateiauswahldialog gibt
+pub(crate) fn default_import_file() -> PathBuf {
+    let base = export_import_service::get_export_base_dir();
+    let plain = base.join("import.zip");
+    let encrypted = base.join(format!("import.zip.{}", backup_crypto::ENCRYPTED_EXTENSION));
+    if !plain.exists() && encrypted.exists() {
+        encrypted
+    } else {
+        plain
+    }
+}
+
+fn load_preview(
+    import_path: &Path,
+    passphrase: Option<&Passphrase>,
+) -> Result<ZipImportPreview, String> {
+    if !import_path.exists() {
+        return Err(t!(
+            "backup-import-missing",
+            path: import_path.display().to_string()
+        ));
+    }
+    database::with_db(|conn| {
+        export_import_service::preview_zip_import(conn, import_path, passphrase)
+    })
+    .map_err(|e| t!("backup-import-error", error: e.to_string()))
+}
+
+/// Vorschau vor dem Einspielen eines Sicherungs-ZIPs: neue, geänderte und
+/// widersprüchliche Einträge, je Unterschied mit Auswahl. Verschlüsselte
+/// Sicherungen erst nach Eingabe der Passphrase.
+#[component]
+pub fn BackupImportScreen(import_path: PathBuf, on_navigate: EventHandler<Screen>) -> Element {
+    let encrypted = use_hook({
+        let import_path = import_path.clone();
+        move || export_import_service::is_encrypted_backup(&import_path).unwrap_or(false)
+    });
+    let mut passphrase_input = use_signal(String::new);
+    let mut passphrase = use_signal(|| None::<Passphrase>);
+    // `None` solange die Passphrase fehlt
+    let mut preview = use_signal({
+        let import_path = import_path.clone();
+        move || (!encrypted).then(|| load_preview(&import_path, None))
+    });
+    let mut decisions = use_signal(|| match preview() {
+        Some(Ok(preview)) => ImportDecisions::from_preview(&preview),
+        _ => ImportDecisions::default(),
+    });
+    let mut running = use_signal(|| false);
+    let mut message = use_signal(String::new);
+
+    let unlock = {
+        let import_path = import_path.clone();
+        move |_| {
+            let entered = match Passphrase::new(&passphrase_input()) {
+                Ok(entered) => entered,
+                Err(e) => {
+                    message.set(format!("❌ {}", e));
+                    return;
+                }
+            };
+            match load_preview(&import_path, Some(&entered)) {
+                Ok(loaded) => {
+                    decisions.set(ImportDecisions::from_preview(&loaded));
+                    preview.set(Some(Ok(loaded)));
+                    passphrase.set(Some(entered));
+                    message.set(String::new());
+                }
+                Err(e) => message.set(e),
+            }
+        }
+    };
+
+    let start_import = move |_| {
+        let import_path = import_path.clone();
+        running.set(true);
+        spawn(async move {
+            match database::connection() {
+                Ok(conn) => {
+                    let mode = ImportMode::Decisions(decisions());
+                    match export_import_service::import_from_zip(
+                        &conn,
+                        &import_path,
+                        mode,
+                        passphrase().as_ref(),
+                    )
+                    .await
+                    {
+                        Ok(()) => message.set(t!(
+                            "backup-import-success",
+                            path: import_path.display().to_string()
+                        )),
+                        Err(e) => message.set(t!("backup-import-error", error: e.to_string())),
+                    }
+                }
+                Err(e) => message.set(t!("backup-db-error", error: e.to_string())),
+            }
+            running.set(false);
+        });
+    };
+
+    rsx! {
+        div { style: "padding: 16px; max-width: 600px; margin: 0 auto;",
+            div { style: "display: flex; align-items: center; margin-bottom: 16px;",
+                button {
+                    class: "btn-back",
+                    onclick: move |_| on_navigate.call(Screen::Settings),
+                    "← "
+                }
+                h1 { style: "margin: 0; font-size: 24px; color: #0066cc;",
+                    {t!("backup-import-preview-title")}
+                }
+            }
+
+            match preview() {
+                None => rsx! {
+                    p { style: "font-size: 14px;", {t!("backup-import-encrypted")} }
+                    input {
+                        r#type: "password",
+                        style: "width: 100%; padding: 8px; border: 1px solid #ccc; border-radius: 4px; margin-bottom: 8px;",
+                        value: "{passphrase_input}",
+                        oninput: move |e| passphrase_input.set(e.value()),
+                    }
+                    button {
+                        class: "btn-primary",
+                        style: "width: 100%;",
+                        disabled: passphrase_input().is_empty(),
+                        onclick: unlock,
+                        {t!("backup-import-unlock")}
+                    }
+                },
+                Some(Err(e)) => rsx! {
+                    p { style: "font-size: 14px;", "{e}" }
+                },
+                Some(Ok(preview)) => rsx! {
+                    table { style: "width: 100%; font-size: 14px; border-collapse: collapse; margin-bottom: 16px;",
+                        tr { style: "text-align: left; color: #666;",
+                            th {}
+                            th { {t!("backup-import-preview-added")} }
+                            th { {t!("backup-import-preview-updated")} }
+                            th { {t!("backup-import-preview-unchanged")} }
+                            th { {t!("backup-import-preview-conflicts")} }
+                        }
+                        for entity in ImportEntity::ALL {
+                            tr { key: "{entity.entity_type()}",
+                                td { {entity_type_label(entity.entity_type())} }
+                                td { "{preview.count(entity, ImportChange::Add)}" }
+                                td { "{preview.count(entity, ImportChange::Update)}" }
+                                td { "{preview.count(entity, ImportChange::Unchanged)}" }
+                                td { "{preview.count(entity, ImportChange::Conflict)}" }
+                            }
+                        }
+                    }
+
+                    for item in preview
+                        .items
+                        .iter()
+                        .filter(|item| matches!(item.change, ImportChange::Update | ImportChange::Conflict))
+                    {
+                        ImportItemRow { key: "{item.uuid}", item: item.clone(), decisions }
+                    }
+
+                    button {
+                        class: "btn-danger",
+                        style: "width: 100%; margin-top: 12px;",
+                        disabled: running(),
+                        onclick: start_import.clone(),
+                        {t!("backup-import-preview-apply")}
+                    }
+                },
+            }
+
+            if !message().is_empty() {
+                p { style: "margin: 12px 0 0 0; font-size: 14px; word-break: break-all;",
+                    "{message}"
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn ImportItemRow(item: ImportItem, decisions: Signal<ImportDecisions>) -> Element {
+    let uuid = item.uuid.clone();
+    let (background, badge) = if item.change == ImportChange::Conflict {
+        ("#fff3f3", t!("backup-import-preview-conflict"))
+    } else {
+        ("#f3f8ff", t!("backup-import-preview-update"))
+    };
+
+    rsx! {
+        div { style: "padding: 8px; margin-bottom: 8px; border-radius: 6px; background: {background}; font-size: 13px;",
+            div { style: "display: flex; justify-content: space-between; gap: 8px; margin-bottom: 4px;",
+                strong {
+                    {entity_type_label(item.entity.entity_type())}
+                    ": {item.label}"
+                }
+                span { style: "color: #666;", "{badge}" }
+            }
+            for (field , local , imported) in item.differences.iter() {
+                div { key: "{field}", style: "color: #333;",
+                    "{field}: „{local}“ → „{imported}“"
+                }
+            }
+            select {
+                style: "width: 100%; margin-top: 6px; padding: 6px; border: 1px solid #ccc; border-radius: 4px;",
+                value: choice_key(decisions().choice(&item.uuid)),
+                onchange: move |e| {
+                    let choice = match e.value().as_str() {
+                        "import" => ConflictChoice::TakeImport,
+                        "both" => ConflictChoice::KeepBoth,
+                        _ => ConflictChoice::KeepLocal,
+                    };
+                    decisions.write().set(&uuid, choice);
+                },
+                option { value: "local", {t!("backup-import-choice-local")} }
+                option { value: "import", {t!("backup-import-choice-import")} }
+                if item.entity.can_keep_both() {
+                    option { value: "both", {t!("backup-import-choice-both")} }
+                }
+            }
+        }
+    }
+}
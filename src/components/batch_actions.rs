@@ -0,0 +1,191 @@
+use crate::components::coops::{coop_names, CoopSelect};
+use crate::components::treatment::TreatmentFields;
+use crate::database;
+use crate::models::{EventType, Treatment};
+use crate::services::batch_service;
+use chrono::{Local, NaiveDate};
+use dioxus::prelude::*;
+use dioxus_i18n::t;
+use uuid::Uuid;
+
+const INPUT_STYLE: &str = "width: 100%; padding: 10px; border: 1px solid #ccc; border-radius: 6px; margin-bottom: 8px; box-sizing: border-box; font-size: 15px;";
+
+#[derive(Clone, Copy, PartialEq)]
+enum BatchAction {
+    Event,
+    Coop,
+    Delete,
+}
+
+/// Sammelaktionen für die gewählten Wachteln: Ereignis eintragen, Stall
+/// wechseln oder löschen
+///
+/// `on_done` bekommt die Erfolgsmeldung, danach ist die Auswahl hinfällig.
+#[component]
+pub fn BatchActions(quail_ids: Vec<Uuid>, on_done: EventHandler<String>) -> Element {
+    let mut action = use_signal(|| None::<BatchAction>);
+    let mut event_type = use_signal(|| EventType::Healthy);
+    let mut event_date = use_signal(|| Local::now().format("%Y-%m-%d").to_string());
+    let mut notes = use_signal(String::new);
+    let treatment = use_signal(Treatment::default);
+    let coop = use_signal(|| None::<Uuid>);
+    let has_coops = use_signal(|| !coop_names().is_empty());
+    let mut error = use_signal(String::new);
+    let mut running = use_signal(|| false);
+    let count = quail_ids.len();
+
+    let ids = quail_ids.clone();
+    let apply = move |_| {
+        let Some(current) = action() else {
+            return;
+        };
+        let date = NaiveDate::parse_from_str(&event_date(), "%Y-%m-%d");
+        if current == BatchAction::Event && date.is_err() {
+            error.set(t!("error-date-format"));
+            return;
+        }
+        let ids = ids.clone();
+        let notes_value = notes().trim().to_string();
+        running.set(true);
+        spawn(async move {
+            let result = match database::connection() {
+                Ok(conn) => match current {
+                    BatchAction::Event => batch_service::add_event(
+                        &conn,
+                        &ids,
+                        event_type(),
+                        date.unwrap_or_default(),
+                        (!notes_value.is_empty()).then_some(notes_value),
+                        (event_type() == EventType::Treatment).then(|| treatment.cloned()),
+                    )
+                    .await
+                    .map(|events| t!("batch-event-done", count: events.len())),
+                    BatchAction::Coop => batch_service::assign_coop(&conn, &ids, coop())
+                        .await
+                        .map(|changed| t!("batch-coop-done", count: changed)),
+                    BatchAction::Delete => batch_service::delete_quails(&conn, &ids)
+                        .await
+                        .map(|_| t!("batch-delete-done", count: ids.len())),
+                },
+                Err(e) => Err(e),
+            };
+            running.set(false);
+            match result {
+                Ok(message) => {
+                    error.set(String::new());
+                    action.set(None);
+                    on_done.call(message);
+                }
+                Err(e) => error.set(format!("{}: {}", t!("error-save-failed"), e)),
+            }
+        });
+    };
+
+    let tab_style = move |tab: BatchAction| {
+        format!(
+            "flex: 1; padding: 10px; font-size: 14px; border-radius: 8px; {}",
+            if action() == Some(tab) {
+                "background: #0066cc; color: white; border: 1px solid #0066cc;"
+            } else {
+                "background: white; color: #333; border: 1px solid #ddd;"
+            }
+        )
+    };
+
+    rsx! {
+        div { style: "margin-bottom: 16px; padding: 12px; background: white; border-radius: 10px; border: 1px solid #cce0f5;",
+            div { style: "display: flex; gap: 8px;",
+                button {
+                    style: tab_style(BatchAction::Event),
+                    onclick: move |_| action.set(Some(BatchAction::Event)),
+                    "📅 "
+                    {t!("batch-action-event")}
+                }
+                if has_coops() {
+                    button {
+                        style: tab_style(BatchAction::Coop),
+                        onclick: move |_| action.set(Some(BatchAction::Coop)),
+                        "🏠 "
+                        {t!("batch-action-coop")}
+                    }
+                }
+                button {
+                    style: tab_style(BatchAction::Delete),
+                    onclick: move |_| action.set(Some(BatchAction::Delete)),
+                    "🗑️ "
+                    {t!("action-delete")}
+                }
+            }
+
+            if let Some(current) = action() {
+                div { style: "margin-top: 12px;",
+                    match current {
+                        BatchAction::Event => rsx! {
+                            select {
+                                style: INPUT_STYLE,
+                                value: "{event_type().as_str()}",
+                                onchange: move |e| event_type.set(EventType::from_str(&e.value())),
+                                option { value: "born", {t!("event-type-born")} }
+                                option { value: "alive", {t!("event-type-alive")} }
+                                option { value: "sick", {t!("event-type-sick")} }
+                                option { value: "healthy", {t!("event-type-healthy")} }
+                                option { value: "marked_for_slaughter", {t!("event-type-marked")} }
+                                option { value: "slaughtered", {t!("event-type-slaughtered")} }
+                                option { value: "died", {t!("event-type-died")} }
+                                option { value: "treatment", {t!("event-type-treatment")} }
+                            }
+                            if event_type() == EventType::Treatment {
+                                TreatmentFields { treatment }
+                            }
+                            input {
+                                r#type: "date",
+                                style: INPUT_STYLE,
+                                value: "{event_date}",
+                                oninput: move |e| event_date.set(e.value()),
+                            }
+                            input {
+                                r#type: "text",
+                                style: INPUT_STYLE,
+                                placeholder: t!("placeholder-event-notes"),
+                                value: "{notes}",
+                                oninput: move |e| notes.set(e.value()),
+                            }
+                        },
+                        BatchAction::Coop => rsx! {
+                            CoopSelect {
+                                label: String::new(),
+                                none_label: t!("coop-none"),
+                                coop,
+                            }
+                        },
+                        BatchAction::Delete => rsx! {
+                            div { style: "margin-bottom: 8px; padding: 12px; background: #fff3cd; border-radius: 8px; color: #856404; font-size: 14px;",
+                                "⚠️ "
+                                {t!("batch-delete-confirm", count: count)}
+                            }
+                        },
+                    }
+
+                    if !error().is_empty() {
+                        div { style: "margin-bottom: 8px; padding: 10px; background: #fee; border-radius: 6px; color: #c33; font-size: 13px;",
+                            "{error}"
+                        }
+                    }
+
+                    button {
+                        class: if current == BatchAction::Delete { "btn-danger" } else { "btn-primary" },
+                        style: "width: 100%; padding: 12px; font-size: 15px;",
+                        disabled: running(),
+                        onclick: apply,
+                        if running() {
+                            "⏳ "
+                            {t!("action-saving")}
+                        } else {
+                            {t!("batch-apply", count: count)}
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,105 @@
+use crate::components::share_button::ShareButton;
+use crate::database;
+use crate::services::{ical_export, sync_service};
+use dioxus::prelude::*;
+use dioxus_i18n::t;
+use std::path::PathBuf;
+
+/// Ereignisse als `.ics` für den normalen Kalender; mit Nextcloud auch zum
+/// Abonnieren über den Export-Ordner
+#[component]
+pub fn CalendarExportCard() -> Element {
+    let has_nextcloud = use_hook(|| {
+        database::with_db(sync_service::load_sync_settings)
+            .ok()
+            .flatten()
+            .is_some()
+    });
+    let mut running = use_signal(|| false);
+    let mut message = use_signal(String::new);
+    let mut exported = use_signal(|| None::<PathBuf>);
+    let mut link = use_signal(|| None::<String>);
+
+    let export_local = move |_| {
+        link.set(None);
+        match database::with_db(ical_export::export_ical) {
+            Ok(path) => {
+                message.set(t!("calendar-export-success", path: path.display().to_string()));
+                exported.set(Some(path));
+            }
+            Err(e) => {
+                exported.set(None);
+                message.set(t!("calendar-export-error", error: e.to_string()));
+            }
+        }
+    };
+
+    let upload = move |_| {
+        running.set(true);
+        link.set(None);
+        message.set(t!("calendar-export-uploading"));
+        spawn(async move {
+            match database::connection() {
+                Ok(conn) => match ical_export::export_ical_to_nextcloud(&conn).await {
+                    Ok(export) => {
+                        message.set(t!("calendar-export-uploaded", path: export.remote_path));
+                        link.set(export.web_url);
+                    }
+                    Err(e) => message.set(t!("calendar-export-error", error: e.to_string())),
+                },
+                Err(e) => message.set(t!("backup-db-error", error: e.to_string())),
+            }
+            running.set(false);
+        });
+    };
+
+    rsx! {
+        div { style: "margin-top: 16px; padding: 12px; background: #fff; border-radius: 8px; border: 1px solid #ddd;",
+            h3 { style: "margin: 0 0 8px 0; font-size: 16px;",
+                "📅 "
+                {t!("calendar-export-title")}
+            }
+            p { style: "margin: 0 0 12px 0; font-size: 13px; color: #666;",
+                {t!("calendar-export-description")}
+            }
+
+            div { style: "display: flex; flex-direction: column; gap: 8px;",
+                button {
+                    class: "btn-secondary",
+                    style: "width: 100%;",
+                    disabled: running(),
+                    onclick: export_local,
+                    {t!("calendar-export-button")}
+                }
+                if has_nextcloud {
+                    button {
+                        class: "btn-primary",
+                        style: "width: 100%;",
+                        disabled: running(),
+                        onclick: upload,
+                        {t!("calendar-export-upload-button")}
+                    }
+                }
+            }
+            if !message().is_empty() {
+                p { style: "margin: 8px 0 0 0; font-size: 13px; word-break: break-all;",
+                    "{message}"
+                }
+            }
+            if let Some(path) = exported() {
+                ShareButton { path }
+            }
+            if let Some(url) = link() {
+                a {
+                    href: "{url}",
+                    target: "_blank",
+                    style: "display: block; margin-top: 8px; font-size: 13px; color: #0066cc;",
+                    {t!("cloud-export-open")}
+                }
+                p { style: "margin: 4px 0 0 0; font-size: 12px; color: #666;",
+                    {t!("calendar-export-subscribe-hint")}
+                }
+            }
+        }
+    }
+}
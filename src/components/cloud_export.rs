@@ -0,0 +1,135 @@
+use crate::components::selective_export::{parse_passphrase, PassphraseField};
+use crate::components::transfer_stats::format_bytes;
+use crate::database;
+use crate::services::cloud_export::{self, CloudExportProgress};
+use crate::services::export_import_service::ExportFilter;
+use crate::services::sync_service;
+use dioxus::prelude::*;
+use dioxus_i18n::t;
+
+/// Sicherung direkt in einen Nextcloud-Ordner hochladen (auf Android ist der
+/// lokale Export-Ordner kaum zu finden)
+#[component]
+pub fn CloudExportCard() -> Element {
+    let mut settings = use_signal(|| {
+        database::with_db(sync_service::load_sync_settings)
+            .ok()
+            .flatten()
+    });
+    let mut running = use_signal(|| false);
+    let mut message = use_signal(String::new);
+    let mut link = use_signal(|| None::<String>);
+    let passphrase = use_signal(String::new);
+
+    let Some(current) = settings() else {
+        return rsx! {};
+    };
+    let default_folder = {
+        let mut defaults = current.clone();
+        defaults.export_path = None;
+        defaults.export_folder()
+    };
+
+    let upload =
+        move |_| {
+            let filter = match parse_passphrase(&passphrase()) {
+                Ok(passphrase) => ExportFilter {
+                    passphrase,
+                    ..Default::default()
+                },
+                Err(e) => {
+                    message.set(format!("❌ {}", e));
+                    return;
+                }
+            };
+            running.set(true);
+            link.set(None);
+            spawn(async move {
+                match database::connection() {
+                    Ok(conn) => {
+                        let result =
+                            cloud_export::export_to_nextcloud(&conn, &filter, |progress| {
+                                match progress {
+                                    CloudExportProgress::Exporting => {
+                                        message.set(t!("cloud-export-exporting"))
+                                    }
+                                    CloudExportProgress::Uploading { bytes } => message.set(t!(
+                                        "cloud-export-uploading",
+                                        size: format_bytes(bytes)
+                                    )),
+                                    CloudExportProgress::Done(_) => {}
+                                }
+                            })
+                            .await;
+                        match result {
+                            Ok(export) => {
+                                message.set(t!("cloud-export-success", path: export.remote_path));
+                                link.set(export.web_url);
+                            }
+                            Err(e) => message.set(t!("cloud-export-error", error: e.to_string())),
+                        }
+                    }
+                    Err(e) => message.set(t!("backup-db-error", error: e.to_string())),
+                }
+                running.set(false);
+            });
+        };
+
+    rsx! {
+        div { style: "margin-top: 16px; padding: 12px; background: #fff; border-radius: 8px; border: 1px solid #ddd;",
+            h3 { style: "margin: 0 0 8px 0; font-size: 16px;", {t!("cloud-export-title")} }
+            p { style: "margin: 0 0 12px 0; font-size: 13px; color: #666;",
+                {t!("cloud-export-description")}
+            }
+
+            label { style: "display: block; margin-bottom: 4px; font-size: 13px; font-weight: 600;",
+                {t!("cloud-export-folder")}
+            }
+            input {
+                r#type: "text",
+                style: "width: 100%; padding: 8px; border: 1px solid #ccc; border-radius: 4px; margin-bottom: 8px;",
+                placeholder: "{default_folder}",
+                value: current.export_path.clone().unwrap_or_default(),
+                disabled: running(),
+                onchange: move |e| {
+                    let folder = e.value().trim().to_string();
+                    let Ok(conn) = database::connection() else {
+                        return;
+                    };
+                    let Ok(Some(mut updated)) = sync_service::load_sync_settings(&conn) else {
+                        return;
+                    };
+                    updated.export_path = (!folder.is_empty()).then_some(folder);
+                    if let Err(e) = sync_service::save_sync_settings(&conn, &updated) {
+                        message.set(format!("\u{274c} {}: {}", t!("error-save-failed"), e));
+                        return;
+                    }
+                    settings.set(Some(updated));
+                },
+            }
+
+            PassphraseField { value: passphrase, disabled: running() }
+
+            button {
+                class: "btn-primary",
+                style: "width: 100%;",
+                disabled: running(),
+                onclick: upload,
+                {t!("cloud-export-button")}
+            }
+            if !message().is_empty() {
+                p { style: "margin: 8px 0 0 0; font-size: 13px; word-break: break-all;",
+                    "{message}"
+                }
+            }
+            if let Some(url) = link() {
+                a {
+                    href: "{url}",
+                    target: "_blank",
+                    style: "display: block; margin-top: 8px; font-size: 13px; color: #0066cc;",
+                    {t!("cloud-export-open")}
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,209 @@
+use crate::database;
+use crate::models::Coop;
+use crate::services::coop_service;
+use dioxus::prelude::*;
+use dioxus_i18n::t;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+const SELECT_STYLE: &str = "width: 100%; padding: 14px 16px; font-size: 16px; border: 2px solid #e0e0e0; border-radius: 8px; background: white;";
+const LABEL_STYLE: &str =
+    "display: block; margin-bottom: 8px; font-weight: 600; color: #333; font-size: 14px;";
+
+fn load_coops() -> Vec<Coop> {
+    database::with_db(coop_service::list_coops).unwrap_or_else(|e| {
+        log::error!("Fehler beim Laden der Ställe: {}", e);
+        Vec::new()
+    })
+}
+
+/// Auswahl eines Stalls; `none_label` steht für „kein Stall“
+///
+/// Ohne angelegte Ställe wird nichts angezeigt, die Auswahl bleibt `None`.
+#[component]
+pub fn CoopSelect(label: String, none_label: String, coop: Signal<Option<Uuid>>) -> Element {
+    let coops = use_signal(load_coops);
+
+    if coops().is_empty() {
+        return rsx! {};
+    }
+
+    rsx! {
+        div { style: "margin-bottom: 20px;",
+            if !label.is_empty() {
+                label { style: LABEL_STYLE, "{label}" }
+            }
+            select {
+                style: SELECT_STYLE,
+                value: coop().map(|id| id.to_string()).unwrap_or_default(),
+                onchange: move |e| coop.set(Uuid::parse_str(&e.value()).ok()),
+                option { value: "", "{none_label}" }
+                for c in coops() {
+                    option {
+                        key: "{c.uuid}",
+                        value: "{c.uuid}",
+                        selected: coop() == Some(c.uuid),
+                        "{c.name}"
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Namen aller Ställe für Anzeigen in Listen
+pub fn coop_names() -> HashMap<Uuid, String> {
+    load_coops().into_iter().map(|c| (c.uuid, c.name)).collect()
+}
+
+/// Ställe anlegen, umbenennen und löschen
+#[component]
+pub fn CoopsCard() -> Element {
+    let mut coops = use_signal(load_coops);
+    let mut members =
+        use_signal(|| database::with_db(coop_service::count_members).unwrap_or_default());
+    // Stall im Editor (neu oder in Bearbeitung)
+    let mut editing = use_signal(|| None::<Coop>);
+    let mut message = use_signal(String::new);
+
+    let mut reload = move || {
+        coops.set(load_coops());
+        members.set(database::with_db(coop_service::count_members).unwrap_or_default());
+    };
+
+    let save = move |_| {
+        let Some(coop) = editing() else {
+            return;
+        };
+        spawn(async move {
+            let result = match database::connection() {
+                Ok(conn) => coop_service::save_coop(&conn, &coop).await,
+                Err(e) => Err(e),
+            };
+            match result {
+                Ok(()) => {
+                    reload();
+                    editing.set(None);
+                    message.set(String::new());
+                }
+                Err(e) => message.set(format!("\u{274c} {}: {}", t!("error-save-failed"), e)),
+            }
+        });
+    };
+
+    let delete = move |uuid: Uuid| {
+        spawn(async move {
+            let result = match database::connection() {
+                Ok(conn) => coop_service::delete_coop(&conn, &uuid).await,
+                Err(e) => Err(e),
+            };
+            match result {
+                Ok(()) => {
+                    reload();
+                    message.set(String::new());
+                }
+                Err(e) => message.set(format!("\u{274c} {}", e)),
+            }
+        });
+    };
+
+    rsx! {
+        div { style: "margin-top: 16px; padding: 12px; background: #fff; border-radius: 8px; border: 1px solid #ddd;",
+            h3 { style: "margin: 0 0 8px 0; font-size: 16px;",
+                "🏠 "
+                {t!("coops-title")}
+            }
+            p { style: "margin: 0 0 12px 0; font-size: 13px; color: #666;",
+                {t!("coops-description")}
+            }
+
+            for c in coops() {
+                div {
+                    key: "{c.uuid}",
+                    style: "display: flex; align-items: center; gap: 8px; padding: 8px 0; border-bottom: 1px solid #eee;",
+                    div { style: "flex: 1; min-width: 0;",
+                        div { style: "font-size: 14px; font-weight: 600; color: #333;", "{c.name}" }
+                        div { style: "font-size: 12px; color: #666;",
+                            {t!("coops-members", count: members().get(&c.uuid).copied().unwrap_or_default())}
+                        }
+                    }
+                    button {
+                        class: "btn-secondary",
+                        style: "padding: 6px 10px;",
+                        onclick: {
+                            let c = c.clone();
+                            move |_| {
+                                editing.set(Some(c.clone()));
+                                message.set(String::new());
+                            }
+                        },
+                        {t!("action-edit")}
+                    }
+                    button {
+                        class: "btn-secondary",
+                        style: "padding: 6px 10px;",
+                        onclick: move |_| delete(c.uuid),
+                        {t!("action-delete")}
+                    }
+                }
+            }
+
+            if let Some(draft) = editing() {
+                div { style: "margin-top: 12px;",
+                    input {
+                        r#type: "text",
+                        style: "width: 100%; padding: 8px; border: 1px solid #ccc; border-radius: 4px; margin-bottom: 8px; box-sizing: border-box;",
+                        placeholder: t!("coops-name-placeholder"),
+                        value: "{draft.name}",
+                        oninput: move |e| {
+                            if let Some(draft) = editing.write().as_mut() {
+                                draft.name = e.value();
+                            }
+                        },
+                    }
+                    input {
+                        r#type: "text",
+                        style: "width: 100%; padding: 8px; border: 1px solid #ccc; border-radius: 4px; margin-bottom: 8px; box-sizing: border-box;",
+                        placeholder: t!("field-notes-placeholder"),
+                        value: draft.notes.clone().unwrap_or_default(),
+                        oninput: move |e| {
+                            if let Some(draft) = editing.write().as_mut() {
+                                let notes = e.value();
+                                draft.notes = (!notes.trim().is_empty()).then_some(notes);
+                            }
+                        },
+                    }
+                    div { style: "display: flex; gap: 8px;",
+                        button {
+                            class: "btn-primary",
+                            style: "flex: 1;",
+                            onclick: save,
+                            {t!("action-save")}
+                        }
+                        button {
+                            class: "btn-secondary",
+                            style: "flex: 1;",
+                            onclick: move |_| {
+                                editing.set(None);
+                                message.set(String::new());
+                            },
+                            {t!("action-cancel")}
+                        }
+                    }
+                }
+            } else {
+                button {
+                    class: "btn-primary",
+                    style: "width: 100%; margin-top: 12px;",
+                    onclick: move |_| editing.set(Some(Coop::new(String::new()))),
+                    "+ "
+                    {t!("coops-add")}
+                }
+            }
+
+            if !message().is_empty() {
+                p { style: "margin: 8px 0 0 0; font-size: 13px; color: #c33;", "{message}" }
+            }
+        }
+    }
+}
@@ -0,0 +1,141 @@
+use crate::components::transfer_stats::format_bytes;
+use crate::database::location;
+use dioxus::prelude::*;
+use dioxus_i18n::t;
+use std::path::PathBuf;
+
+/// Desktop: Ordner für Datenbank und Fotos wählen und die Daten umziehen
+#[component]
+pub fn DataLocationCard() -> Element {
+    let mut current = use_signal(location::current);
+    let mut target = use_signal(String::new);
+    let mut confirm = use_signal(|| false);
+    let mut busy = use_signal(|| false);
+    let mut message = use_signal(String::new);
+    let default_root = location::default_root();
+
+    let mut start_move = move |root: PathBuf| {
+        confirm.set(false);
+        busy.set(true);
+        message.set(String::new());
+        spawn(async move {
+            let result = tokio::task::spawn_blocking(move || location::move_to(&root))
+                .await
+                .map_err(|e| e.to_string())
+                .and_then(|r| r.map_err(|e| e.to_string()));
+            match result {
+                Ok(summary) => {
+                    message.set(t!(
+                        "data-location-moved",
+                        photos: summary.photos_copied,
+                        size: format_bytes(summary.bytes_copied),
+                        previous: current().root.display().to_string()
+                    ));
+                    current.set(summary.location);
+                    target.set(String::new());
+                }
+                Err(e) => message.set(t!("data-location-error", error: e)),
+            }
+            busy.set(false);
+        });
+    };
+
+    let is_default = default_root.as_ref() == Some(&current().root);
+
+    rsx! {
+        div { style: "margin-top: 16px; padding: 12px; background: #fff; border-radius: 8px; border: 1px solid #ddd;",
+            h3 { style: "margin: 0 0 8px 0; font-size: 16px;",
+                "📁 "
+                {t!("data-location-title")}
+            }
+            p { style: "margin: 0 0 8px 0; font-size: 13px; color: #666;",
+                {t!("data-location-description")}
+            }
+            p { style: "margin: 0 0 12px 0; font-size: 12px; color: #333; word-break: break-all;",
+                {t!("data-location-current", path: current().root.display().to_string())}
+            }
+            div { style: "display: flex; gap: 8px; margin-bottom: 8px;",
+                input {
+                    r#type: "text",
+                    style: "flex: 1; min-width: 0;",
+                    placeholder: t!("data-location-placeholder"),
+                    value: "{target}",
+                    disabled: busy(),
+                    oninput: move |e| {
+                        target.set(e.value());
+                        confirm.set(false);
+                    },
+                }
+                if cfg!(feature = "desktop") {
+                    button {
+                        style: "padding: 4px 10px; font-size: 13px; background: #e0e0e0; color: #333;",
+                        disabled: busy(),
+                        onclick: move |_| {
+                            if let Some(folder) = pick_folder() {
+                                target.set(folder.display().to_string());
+                                confirm.set(false);
+                            }
+                        },
+                        {t!("data-location-browse")}
+                    }
+                }
+            }
+            if confirm() {
+                p { style: "margin: 0 0 8px 0; padding: 8px; background: #fff3cd; border-radius: 4px; font-size: 12px; color: #856404;",
+                    "⚠️ "
+                    {t!("data-location-confirm", path: target())}
+                }
+                div { style: "display: flex; gap: 8px;",
+                    button {
+                        class: "btn-primary",
+                        style: "flex: 1;",
+                        onclick: move |_| start_move(PathBuf::from(target().trim())),
+                        {t!("data-location-move")}
+                    }
+                    button {
+                        style: "flex: 1; background: #e0e0e0; color: #666;",
+                        onclick: move |_| confirm.set(false),
+                        {t!("action-cancel")}
+                    }
+                }
+            } else {
+                button {
+                    class: "btn-primary",
+                    style: "width: 100%;",
+                    disabled: busy() || target().trim().is_empty(),
+                    onclick: move |_| confirm.set(true),
+                    {t!("data-location-move")}
+                }
+            }
+            if let Some(default) = default_root.filter(|_| !is_default) {
+                button {
+                    style: "width: 100%; margin-top: 8px; background: #e0e0e0; color: #333;",
+                    disabled: busy(),
+                    onclick: move |_| {
+                        target.set(default.display().to_string());
+                        confirm.set(true);
+                    },
+                    {t!("data-location-reset")}
+                }
+            }
+            if busy() {
+                p { style: "margin: 8px 0 0 0; font-size: 12px; color: #666;", {t!("data-location-busy")} }
+            }
+            if !message().is_empty() {
+                p { style: "margin: 8px 0 0 0; font-size: 12px; white-space: pre-line; word-break: break-all;",
+                    "{message}"
+                }
+            }
+        }
+    }
+}
+
+#[cfg(feature = "desktop")]
+fn pick_folder() -> Option<PathBuf> {
+    rfd::FileDialog::new().pick_folder()
+}
+
+#[cfg(not(feature = "desktop"))]
+fn pick_folder() -> Option<PathBuf> {
+    None
+}
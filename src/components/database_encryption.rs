@@ -0,0 +1,79 @@
+use crate::database::encryption::{self, EncryptionState};
+use dioxus::prelude::*;
+use dioxus_i18n::t;
+
+/// Verschlüsselung der lokalen Datenbank (Feature `sqlcipher`)
+///
+/// Ein- und Ausschalten werden erst beim nächsten Start wirksam, daher zeigt
+/// die Karte auch den ausstehenden Zustand an.
+#[component]
+pub fn DatabaseEncryptionCard() -> Element {
+    let mut state = use_signal(|| encryption::state().ok());
+    let mut confirm_disable = use_signal(|| false);
+    let mut message = use_signal(String::new);
+
+    let mut update = move |result: Result<(), crate::error::AppError>| {
+        match result {
+            Ok(()) => message.set(String::new()),
+            Err(e) => message.set(format!("❌ {}", e)),
+        }
+        state.set(encryption::state().ok());
+        confirm_disable.set(false);
+    };
+
+    let (hint, color) = match state() {
+        Some(EncryptionState::On) => (t!("db-encryption-on"), "#2e7d32"),
+        Some(EncryptionState::PendingEncrypt) => (t!("db-encryption-pending-on"), "#856404"),
+        Some(EncryptionState::PendingDecrypt) => (t!("db-encryption-pending-off"), "#856404"),
+        Some(EncryptionState::Off) | None => (t!("db-encryption-off"), "#666"),
+    };
+    let enabled = matches!(
+        state(),
+        Some(EncryptionState::On | EncryptionState::PendingEncrypt)
+    );
+
+    rsx! {
+        div { style: "margin-top: 16px; padding: 12px; background: #f5f5f5; border-radius: 8px;",
+            label { style: "display: block; margin-bottom: 4px; font-weight: 600; font-size: 14px;",
+                "🗄️ "
+                {t!("db-encryption-title")}
+            }
+            p { style: "margin: 0 0 8px 0; font-size: 12px; color: {color};", "{hint}" }
+            if !enabled {
+                button {
+                    class: "btn-primary",
+                    style: "width: 100%; padding: 10px; font-size: 14px;",
+                    onclick: move |_| update(encryption::enable_encryption()),
+                    {t!("db-encryption-enable")}
+                }
+            } else if confirm_disable() {
+                p { style: "margin: 0 0 8px 0; padding: 8px; background: #fff3cd; border-radius: 4px; font-size: 12px; color: #856404;",
+                    "⚠️ "
+                    {t!("db-encryption-disable-confirm")}
+                }
+                div { style: "display: flex; gap: 8px;",
+                    button {
+                        class: "btn-danger",
+                        style: "flex: 1; padding: 10px; font-size: 14px;",
+                        onclick: move |_| update(encryption::disable_encryption()),
+                        {t!("db-encryption-disable")}
+                    }
+                    button {
+                        style: "flex: 1; padding: 10px; background: #e0e0e0; color: #666; font-size: 14px;",
+                        onclick: move |_| confirm_disable.set(false),
+                        {t!("action-cancel")}
+                    }
+                }
+            } else {
+                button {
+                    style: "width: 100%; padding: 10px; background: #ffe6e6; color: #cc0000; font-size: 14px; border: 1px solid #ffcccc;",
+                    onclick: move |_| confirm_disable.set(true),
+                    {t!("db-encryption-disable")}
+                }
+            }
+            if !message().is_empty() {
+                p { style: "margin: 8px 0 0 0; font-size: 12px;", "{message}" }
+            }
+        }
+    }
+}
@@ -0,0 +1,131 @@
+use crate::database;
+use crate::models::{LogEntry, LogLevel};
+use crate::services::app_log;
+use crate::services::integrity::{self, IntegrityReport};
+use chrono::{Local, TimeZone};
+use dioxus::prelude::*;
+use dioxus_i18n::t;
+
+/// Angezeigte Einträge aus dem Protokoll
+const LOG_PREVIEW: usize = 5;
+
+fn load_log() -> Vec<LogEntry> {
+    database::with_db(|conn| app_log::list(conn, Some(integrity::LOG_CATEGORY), LOG_PREVIEW))
+        .unwrap_or_default()
+}
+
+/// Wartung: Integrität der Datenbank prüfen und reparieren
+#[component]
+pub fn IntegrityCheckCard() -> Element {
+    let mut report = use_signal(|| None::<IntegrityReport>);
+    let mut log_entries = use_signal(load_log);
+    let mut running = use_signal(|| false);
+    let mut message = use_signal(String::new);
+
+    let run_check = move |_| {
+        running.set(true);
+        message.set(String::new());
+        spawn(async move {
+            match database::with_db_async(integrity::check_and_log).await {
+                Ok(result) => report.set(Some(result)),
+                Err(e) => message.set(t!("integrity-error", error: e.to_string())),
+            }
+            log_entries.set(load_log());
+            running.set(false);
+        });
+    };
+
+    let run_repair = move |_| {
+        running.set(true);
+        spawn(async move {
+            match database::with_db_async(integrity::repair).await {
+                Ok(summary) => {
+                    message.set(t!(
+                        "integrity-repaired",
+                        cleared: summary.references_cleared,
+                        deleted: summary.rows_deleted
+                    ));
+                    report.set(Some(summary.remaining));
+                }
+                Err(e) => message.set(t!("integrity-error", error: e.to_string())),
+            }
+            log_entries.set(load_log());
+            running.set(false);
+        });
+    };
+
+    rsx! {
+        div { style: "margin-top: 16px; padding: 12px; background: #fff; border-radius: 8px; border: 1px solid #ddd;",
+            h3 { style: "margin: 0 0 8px 0; font-size: 16px;",
+                "🩺 "
+                {t!("integrity-title")}
+            }
+            p { style: "margin: 0 0 12px 0; font-size: 13px; color: #666;",
+                {t!("integrity-description")}
+            }
+            button {
+                class: "btn-primary",
+                style: "width: 100%;",
+                disabled: running(),
+                onclick: run_check,
+                {t!("integrity-check-button")}
+            }
+            if let Some(current) = report() {
+                if current.is_ok() {
+                    p { style: "margin: 8px 0 0 0; font-size: 13px; color: #2e7d32;",
+                        "✅ "
+                        {t!("integrity-ok")}
+                    }
+                } else {
+                    p { style: "margin: 8px 0 4px 0; font-size: 13px; color: #c62828;",
+                        "⚠️ "
+                        {t!("integrity-issues", count: current.issue_count())}
+                    }
+                    pre { style: "margin: 0 0 8px 0; padding: 8px; background: #f5f5f5; border-radius: 4px; font-size: 11px; white-space: pre-wrap; max-height: 160px; overflow-y: auto;",
+                        {integrity::describe(&current)}
+                    }
+                    button {
+                        class: "btn-danger",
+                        style: "width: 100%;",
+                        disabled: running(),
+                        onclick: run_repair,
+                        {t!("integrity-repair-button")}
+                    }
+                    p { style: "margin: 4px 0 0 0; font-size: 12px; color: #666;",
+                        {t!("integrity-repair-hint")}
+                    }
+                }
+            }
+            if !message().is_empty() {
+                p { style: "margin: 8px 0 0 0; font-size: 12px;", "{message}" }
+            }
+            if !log_entries().is_empty() {
+                div { style: "margin-top: 12px; display: flex; flex-direction: column; gap: 2px; font-size: 11px; color: #666;",
+                    for entry in log_entries() {
+                        IntegrityLogRow { key: "{entry.id}", entry: entry.clone() }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn IntegrityLogRow(entry: LogEntry) -> Element {
+    let time = Local
+        .timestamp_millis_opt(entry.created_at)
+        .single()
+        .map(|t| t.format("%d.%m.%Y %H:%M").to_string())
+        .unwrap_or_default();
+    let icon = match entry.level {
+        LogLevel::Info => "ℹ️",
+        LogLevel::Warning => "⚠️",
+        LogLevel::Error => "❌",
+    };
+    // Nur die Zusammenfassung, Details stehen im Protokoll
+    let summary = entry.message.lines().next().unwrap_or_default().to_string();
+
+    rsx! {
+        span { "{icon} {time} – {summary}" }
+    }
+}
@@ -1,3 +1,5 @@
+use crate::components::coops::{coop_names, CoopSelect};
+use crate::components::share_button::ShareButton;
 use crate::{database, models::EggRecord, services, Screen};
 use dioxus::prelude::*;
 use dioxus_i18n::t;
@@ -6,27 +8,59 @@ use dioxus_i18n::t;
 pub fn EggHistoryScreen(on_navigate: EventHandler<Screen>) -> Element {
     let mut records = use_signal(|| Vec::<EggRecord>::new());
     let mut status_message = use_signal(|| String::new());
+    let mut exported = use_signal(|| None::<std::path::PathBuf>);
+    let coops = use_signal(coop_names);
+    // Nur Einträge dieses Stalls, `None` zeigt alle
+    let coop_filter = use_signal(|| None::<uuid::Uuid>);
+    let visible = move || -> Vec<EggRecord> {
+        records()
+            .into_iter()
+            .filter(|r| coop_filter().is_none_or(|coop| r.coop_id == Some(coop)))
+            .collect()
+    };
 
     // Load records
-    let mut load_records = move || match database::init_database() {
-        Ok(conn) => match services::list_egg_records(&conn, None, None) {
-            Ok(list) => {
-                records.set(list);
-                status_message.set(format!(
-                    "✅ {}",
-                    t!("egg-history-loaded", count: records().len())
-                ));
+    let load_records = move || {
+        spawn(async move {
+            match database::with_db_async(|conn| services::list_egg_records(conn, None, None)).await
+            {
+                Ok(list) => {
+                    records.set(list);
+                    status_message.set(format!(
+                        "✅ {}",
+                        t!("egg-history-loaded", count: records().len())
+                    ));
+                }
+                Err(e) => {
+                    status_message.set(format!(
+                        "❌ {}",
+                        t!("error-load-failed", error: e.to_string())
+                    ));
+                }
             }
-            Err(e) => {
-                status_message.set(format!(
+        });
+    };
+
+    let export_csv = move || {
+        spawn(async move {
+            match database::with_db_async(|conn| {
+                services::export_import_service::export_eggs_csv(conn, None, None)
+            })
+            .await
+            {
+                Ok(path) => {
+                    status_message.set(format!(
+                        "✅ {}",
+                        t!("egg-history-export-success", path: path.display().to_string())
+                    ));
+                    exported.set(Some(path));
+                }
+                Err(e) => status_message.set(format!(
                     "❌ {}",
-                    t!("error-load-failed", error: e.to_string())
-                ));
+                    t!("egg-history-export-error", error: e.to_string())
+                )),
             }
-        },
-        Err(e) => {
-            status_message.set(format!("❌ {}", t!("error-database", error: e.to_string())));
-        }
+        });
     };
 
     // Load on mount
@@ -46,12 +80,26 @@ pub fn EggHistoryScreen(on_navigate: EventHandler<Screen>) -> Element {
                     "📋 ",
                     { t!("egg-history-title") }
                 }
-                button {
-                    class: "btn-success",
-                    style: "padding: 10px 20px; font-size: 16px; font-weight: 500;",
-                    onclick: move |_| on_navigate.call(Screen::EggTracking(None)),
-                    "+ ",
-                    { t!("action-new") }
+                div {
+                    style: "display: flex; gap: 8px;",
+                    button {
+                        class: "btn-secondary",
+                        style: "padding: 10px 14px; font-size: 16px;",
+                        disabled: records().is_empty(),
+                        onclick: move |_| export_csv(),
+                        "📄 ",
+                        { t!("egg-history-export-csv") }
+                    }
+                    button {
+                        class: "btn-success",
+                        style: "padding: 10px 20px; font-size: 16px; font-weight: 500;",
+                        onclick: move |_| on_navigate.call(Screen::EggTracking {
+                            date: None,
+                            coop_id: None,
+                        }),
+                        "+ ",
+                        { t!("action-new") }
+                    }
                 }
             }
 
@@ -62,18 +110,36 @@ pub fn EggHistoryScreen(on_navigate: EventHandler<Screen>) -> Element {
                     "{status_message}"
                 }
             }
+            if let Some(path) = exported() {
+                div { style: "margin-bottom: 12px;",
+                    ShareButton { path }
+                }
+            }
+
+            CoopSelect {
+                label: String::new(),
+                none_label: t!("coop-filter-all"),
+                coop: coop_filter,
+            }
 
             // Records List
-            if records().is_empty() {
+            if visible().is_empty() {
                 div {
                     style: "text-align: center; padding: 40px; color: #999;",
                     { t!("egg-history-empty") }
                 }
             } else {
-                for record in records() {
+                for record in visible() {
                     EggRecordCard {
                         record: record.clone(),
-                        on_edit: move |date| on_navigate.call(Screen::EggTracking(Some(date))),
+                        coop: record.coop_id.and_then(|id| coops().get(&id).cloned()),
+                        on_edit: move |_| {
+                            on_navigate
+                                .call(Screen::EggTracking {
+                                    date: Some(record.record_date.format("%Y-%m-%d").to_string()),
+                                    coop_id: record.coop_id.map(|id| id.to_string()),
+                                })
+                        },
                     }
                 }
             }
@@ -82,8 +148,7 @@ pub fn EggHistoryScreen(on_navigate: EventHandler<Screen>) -> Element {
 }
 
 #[component]
-fn EggRecordCard(record: EggRecord, on_edit: EventHandler<String>) -> Element {
-    let date_str = record.record_date.format("%Y-%m-%d").to_string();
+fn EggRecordCard(record: EggRecord, coop: Option<String>, on_edit: EventHandler<()>) -> Element {
     let display_date = record.record_date.format("%d.%m.%Y").to_string();
     use chrono::Datelike;
     let weekday_num = record.record_date.weekday().num_days_from_monday();
@@ -102,7 +167,7 @@ fn EggRecordCard(record: EggRecord, on_edit: EventHandler<String>) -> Element {
         div {
             class: "card",
             style: "padding: 16px; margin: 8px 0; border-left: 4px solid #ff8c00; cursor: pointer;",
-            onclick: move |_| on_edit.call(date_str.clone()),
+            onclick: move |_| on_edit.call(()),
 
             div {
                 style: "display: flex; justify-content: space-between; align-items: start;",
@@ -123,6 +188,12 @@ fn EggRecordCard(record: EggRecord, on_edit: EventHandler<String>) -> Element {
                             "🥚 ",
                             { t!("egg-history-eggs-count", count: record.total_eggs) }
                         }
+                        if let Some(coop) = coop {
+                            span {
+                                style: "display: inline-block; padding: 6px 14px; background: #f3e5f5; border-radius: 12px; font-size: 14px; color: #7b1fa2;",
+                                "🏠 {coop}"
+                            }
+                        }
                     }
                     if let Some(notes) = &record.notes {
                         if !notes.trim().is_empty() {
@@ -1,33 +1,48 @@
+use crate::components::coops::CoopSelect;
+use crate::components::entity_history::{EntityHistory, HistoryTabs};
+use crate::components::treatment::EggWithdrawalWarnings;
 use crate::{database, models::EggRecord, services, Screen};
 use chrono::Local;
 use dioxus::prelude::*;
 use dioxus_i18n::t;
 
 #[component]
-pub fn EggTrackingScreen(date: Option<String>, on_navigate: EventHandler<Screen>) -> Element {
+pub fn EggTrackingScreen(
+    date: Option<String>,
+    coop_id: Option<String>,
+    on_navigate: EventHandler<Screen>,
+) -> Element {
     let mut date_str = use_signal(|| {
         date.clone()
             .unwrap_or_else(|| Local::now().format("%Y-%m-%d").to_string())
     });
+    // Stall des Eintrags, `None` für den ganzen Bestand
+    let coop = use_signal(|| {
+        coop_id
+            .as_deref()
+            .and_then(|id| uuid::Uuid::parse_str(id).ok())
+    });
     let mut total_eggs = use_signal(|| String::new());
     let mut notes = use_signal(|| String::new());
     let mut error = use_signal(|| None::<String>);
     let mut success = use_signal(|| false);
     let mut existing_record = use_signal(|| None::<EggRecord>);
+    let show_history = use_signal(|| false);
 
     // Load existing record for selected date
     let mut load_record = move || {
         let date_value = date_str();
-        match database::init_database() {
+        let coop_value = coop();
+        match database::connection() {
             Ok(conn) => {
-                match services::get_egg_record(&conn, &date_value) {
+                match services::get_egg_record(&conn, &date_value, coop_value.as_ref()) {
                     Ok(record) => {
                         total_eggs.set(record.total_eggs.to_string());
                         notes.set(record.notes.clone().unwrap_or_default());
                         existing_record.set(Some(record));
                     }
                     Err(_) => {
-                        // Kein Eintrag für dieses Datum
+                        // Kein Eintrag für dieses Datum (und diesen Stall)
                         total_eggs.set(String::new());
                         notes.set(String::new());
                         existing_record.set(None);
@@ -92,13 +107,19 @@ pub fn EggTrackingScreen(date: Option<String>, on_navigate: EventHandler<Screen>
         // Save to database
         let date_trimmed_clone = date_trimmed.to_string();
         let existing = existing_record();
+        let coop_value = coop();
         spawn(async move {
-            match database::init_database() {
+            match database::connection() {
                 Ok(conn) => {
                     let result = if eggs_count == 0 {
                         // Delete record if eggs count is 0
                         if existing.is_some() {
-                            services::delete_egg_record(&conn, &date_trimmed_clone).await
+                            services::delete_egg_record(
+                                &conn,
+                                &date_trimmed_clone,
+                                coop_value.as_ref(),
+                            )
+                            .await
                         } else {
                             // Nothing to delete
                             Ok(())
@@ -114,6 +135,7 @@ pub fn EggTrackingScreen(date: Option<String>, on_navigate: EventHandler<Screen>
                         let record = EggRecord::new(record_date, eggs_count);
                         let mut record = record;
                         record.notes = notes_opt;
+                        record.coop_id = coop_value;
                         services::add_egg_record(&conn, &record).await.map(|_| ())
                     };
 
@@ -168,6 +190,9 @@ pub fn EggTrackingScreen(date: Option<String>, on_navigate: EventHandler<Screen>
                 }
             }
 
+            // Laufende Wartezeiten nach Behandlungen
+            EggWithdrawalWarnings { date: date_str() }
+
             // Status
             if existing_record().is_some() {
                 div {
@@ -177,78 +202,92 @@ pub fn EggTrackingScreen(date: Option<String>, on_navigate: EventHandler<Screen>
                 }
             }
 
-            // Form
-            div {
-                class: "card",
+            if existing_record().is_some() {
+                HistoryTabs { show_history, content_label: format!("✏️ {}", t!("action-edit")) }
+            }
 
-                // Date Field
+            if let Some(record) = existing_record().filter(|_| show_history()) {
+                EntityHistory { entity_type: "egg", entity_id: record.uuid.to_string() }
+            } else {
+                // Form
                 div {
-                    style: "margin-bottom: 20px;",
-                    label {
-                        style: "display: block; margin-bottom: 6px; font-weight: 600; color: #333; font-size: 14px;",
-                        { t!("field-date-required") }
-                    }
-                    input {
-                        r#type: "date",
-                        class: "input",
-                        value: "{date_str}",
-                        oninput: move |e| {
-                            date_str.set(e.value());
-                            load_record();
-                        },
-                        autofocus: true,
-                    }
-                    p {
-                        style: "margin: 4px 0 0 0; font-size: 12px; color: #666;",
-                        { t!("field-date-format-hint") }
-                    }
-                }
+                    class: "card",
 
-                // Total Eggs Field
-                div {
-                    style: "margin-bottom: 20px;",
-                    label {
-                        style: "display: block; margin-bottom: 6px; font-weight: 600; color: #333; font-size: 14px;",
-                        { t!("field-eggs-count-required") }
+                    // Date Field
+                    div {
+                        style: "margin-bottom: 20px;",
+                        label {
+                            style: "display: block; margin-bottom: 6px; font-weight: 600; color: #333; font-size: 14px;",
+                            { t!("field-date-required") }
+                        }
+                        input {
+                            r#type: "date",
+                            class: "input",
+                            value: "{date_str}",
+                            oninput: move |e| {
+                                date_str.set(e.value());
+                                load_record();
+                            },
+                            autofocus: true,
+                        }
+                        p {
+                            style: "margin: 4px 0 0 0; font-size: 12px; color: #666;",
+                            { t!("field-date-format-hint") }
+                        }
                     }
-                    input {
-                        r#type: "number",
-                        class: "input",
-                        placeholder: t!("field-eggs-count-placeholder"),
-                        min: "0",
-                        value: "{total_eggs}",
-                        oninput: move |e| total_eggs.set(e.value()),
+
+                    CoopSelect {
+                        label: t!("field-coop"),
+                        none_label: t!("coop-whole-flock"),
+                        coop,
                     }
-                }
 
-                // Notes Field
-                div {
-                    style: "margin-bottom: 20px;",
-                    label {
-                        style: "display: block; margin-bottom: 6px; font-weight: 600; color: #333; font-size: 14px;",
-                        { t!("field-notes") }
+                    // Total Eggs Field
+                    div {
+                        style: "margin-bottom: 20px;",
+                        label {
+                            style: "display: block; margin-bottom: 6px; font-weight: 600; color: #333; font-size: 14px;",
+                            { t!("field-eggs-count-required") }
+                        }
+                        input {
+                            r#type: "number",
+                            class: "input",
+                            placeholder: t!("field-eggs-count-placeholder"),
+                            min: "0",
+                            value: "{total_eggs}",
+                            oninput: move |e| total_eggs.set(e.value()),
+                        }
                     }
-                    textarea {
-                        class: "input",
-                        style: "min-height: 80px; resize: vertical; font-family: inherit;",
-                        placeholder: t!("field-notes-placeholder"),
-                        value: "{notes}",
-                        oninput: move |e| notes.set(e.value()),
+
+                    // Notes Field
+                    div {
+                        style: "margin-bottom: 20px;",
+                        label {
+                            style: "display: block; margin-bottom: 6px; font-weight: 600; color: #333; font-size: 14px;",
+                            { t!("field-notes") }
+                        }
+                        textarea {
+                            class: "input",
+                            style: "min-height: 80px; resize: vertical; font-family: inherit;",
+                            placeholder: t!("field-notes-placeholder"),
+                            value: "{notes}",
+                            oninput: move |e| notes.set(e.value()),
+                        }
                     }
-                }
 
-                // Action Buttons
-                div {
-                    style: "display: flex; gap: 12px; margin-top: 24px;",
-                    button {
-                        class: "btn-success",
-                        style: "flex: 1; padding: 14px;",
-                        onclick: move |_| handle_submit(),
-                        "💾 ",
-                        if existing_record().is_some() {
-                            { t!("action-update") }
-                        } else {
-                            { t!("action-save") }
+                    // Action Buttons
+                    div {
+                        style: "display: flex; gap: 12px; margin-top: 24px;",
+                        button {
+                            class: "btn-success",
+                            style: "flex: 1; padding: 14px;",
+                            onclick: move |_| handle_submit(),
+                            "💾 ",
+                            if existing_record().is_some() {
+                                { t!("action-update") }
+                            } else {
+                                { t!("action-save") }
+                            }
                         }
                     }
                 }
@@ -0,0 +1,161 @@
+use crate::models::{EventType, Gender, RingColor};
+use crate::services::entity_history::{self, FieldChange, HistoryEntry};
+use chrono::{Local, NaiveDate, TimeZone};
+use dioxus::prelude::*;
+use dioxus_i18n::t;
+
+/// Reiter einer Detailansicht: Inhalt oder Verlauf
+#[component]
+pub fn HistoryTabs(show_history: Signal<bool>, content_label: String) -> Element {
+    let tab_style = |active: bool| {
+        if active {
+            "flex:1; padding:10px; background:#0066cc; color:white; border-radius:8px; font-weight:600;"
+        } else {
+            "flex:1; padding:10px; background:#e0e0e0; color:#333; border-radius:8px;"
+        }
+    };
+
+    rsx! {
+        div { style: "display:flex; gap:8px; margin-bottom:16px;",
+            button {
+                style: tab_style(!show_history()),
+                onclick: move |_| show_history.set(false),
+                "{content_label}"
+            }
+            button {
+                style: tab_style(show_history()),
+                onclick: move |_| show_history.set(true),
+                "🕘 "
+                {t!("history-tab")}
+            }
+        }
+    }
+}
+
+/// Änderungsverlauf eines Eintrags aus dem Op-Log, neueste Änderung oben
+///
+/// `entity_type` wie im Op-Log (`quail`, `event`, `egg`).
+#[component]
+pub fn EntityHistory(entity_type: &'static str, entity_id: String) -> Element {
+    let mut entries = use_signal(|| None::<Vec<HistoryEntry>>);
+    let mut error = use_signal(String::new);
+
+    use_effect(use_reactive!(|entity_id| {
+        spawn(async move {
+            match entity_history::load_history_async(entity_type, entity_id).await {
+                Ok(list) => entries.set(Some(list)),
+                Err(e) => error.set(t!("error-load-failed", error: e.to_string())),
+            }
+        });
+    }));
+
+    rsx! {
+        if !error().is_empty() {
+            div { style: "background:#fee; color:#c33; padding:12px; border-radius:8px; font-size:14px;",
+                "⚠️ "
+                {error}
+            }
+        }
+        match entries() {
+            None => rsx! {
+                div { style: "padding:24px; text-align:center; color:#999;", "⏳" }
+            },
+            Some(list) if list.is_empty() => rsx! {
+                div { style: "padding:24px; text-align:center; background:#f5f5f5; border-radius:8px; color:#999;",
+                    {t!("history-empty")}
+                }
+            },
+            Some(list) => rsx! {
+                div { style: "display:flex; flex-direction:column; gap:8px;",
+                    for entry in list {
+                        HistoryRow { key: "{entry.ts}-{entry.device_id}", entry }
+                    }
+                }
+            },
+        }
+    }
+}
+
+#[component]
+fn HistoryRow(entry: HistoryEntry) -> Element {
+    let time = Local
+        .timestamp_millis_opt(entry.ts)
+        .single()
+        .map(|t| t.format("%d.%m.%Y %H:%M").to_string())
+        .unwrap_or_default();
+    let device = if entry.own_device {
+        t!("history-this-device")
+    } else {
+        t!(
+            "history-other-device",
+            device: entry.device_id.chars().take(8).collect::<String>()
+        )
+    };
+    let (icon, action) = if entry.deleted {
+        ("🗑️", t!("history-deleted"))
+    } else if entry.created {
+        ("🆕", t!("history-created"))
+    } else {
+        ("✏️", t!("history-changed"))
+    };
+
+    rsx! {
+        div { style: "padding:12px; background:white; border:1px solid #e0e0e0; border-radius:8px;",
+            div { style: "display:flex; justify-content:space-between; gap:8px; font-size:13px; margin-bottom:4px;",
+                span { style: "font-weight:600; color:#333;", "{icon} {action}" }
+                span { style: "color:#666;", "{time}" }
+            }
+            div { style: "font-size:12px; color:#999; margin-bottom:6px;", "📱 {device}" }
+            for change in entry.changes.iter() {
+                div {
+                    key: "{change.field}",
+                    style: "font-size:13px; color:#555; white-space:pre-wrap;",
+                    span { style: "color:#333;", "{field_label(&change.field)}: " }
+                    "{value_label(change)}"
+                }
+            }
+        }
+    }
+}
+
+fn field_label(field: &str) -> String {
+    match field {
+        "name" => t!("history-field-name"),
+        "gender" => t!("field-gender"),
+        "ring_color" => t!("field-ring-color"),
+        "profile_photo" => t!("history-field-profile-photo"),
+        "mother_id" => t!("field-mother"),
+        "father_id" => t!("field-father"),
+        "coop_id" => t!("field-coop"),
+        "quail_id" => t!("history-field-quail"),
+        "event_type" => t!("field-event-type"),
+        "event_date" | "record_date" => t!("field-date"),
+        "notes" => t!("field-notes"),
+        "total_eggs" => t!("history-field-eggs"),
+        "medication" => t!("field-medication"),
+        "dose" => t!("field-dose"),
+        "duration_days" => t!("field-treatment-days"),
+        "egg_withdrawal_days" => t!("field-egg-withdrawal-days"),
+        "meat_withdrawal_days" => t!("field-meat-withdrawal-days"),
+        other => other.to_string(),
+    }
+}
+
+fn value_label(change: &FieldChange) -> String {
+    let Some(value) = change.value.as_deref() else {
+        return "–".to_string();
+    };
+    match change.field.as_str() {
+        "event_type" => EventType::from_str(value).display_name().to_string(),
+        "gender" => Gender::from_str(value).display_name().to_string(),
+        "ring_color" => RingColor::from_str(value).display_name().to_string(),
+        "event_date" | "record_date" => NaiveDate::parse_from_str(value, "%Y-%m-%d")
+            .map(|d| d.format("%d.%m.%Y").to_string())
+            .unwrap_or_else(|_| value.to_string()),
+        // Verweise auf andere Einträge nur gekürzt
+        "profile_photo" | "quail_id" | "mother_id" | "father_id" | "coop_id" => {
+            value.chars().take(8).collect()
+        }
+        _ => value.to_string(),
+    }
+}
@@ -1,6 +1,7 @@
+use crate::components::treatment::TreatmentFields;
 use crate::database;
-use crate::models::quail_event::EventType;
-use crate::services::event_service;
+use crate::models::quail_event::{EventType, Treatment};
+use crate::services::{event_service, treatment_service};
 use crate::Screen;
 use chrono::NaiveDate;
 use dioxus::prelude::*;
@@ -20,6 +21,7 @@ pub fn EventAdd(
             .to_string()
     });
     let mut notes = use_signal(|| String::new());
+    let treatment = use_signal(Treatment::default);
     let photos = use_signal(|| Vec::<String>::new());
     let error_message = use_signal(|| None::<String>);
     let saving = use_signal(|| false);
@@ -41,7 +43,7 @@ pub fn EventAdd(
         let photos = photos_signal.clone();
         let mut saving_signal = saving_signal.clone();
         spawn(async move {
-            match database::init_database() {
+            match database::connection() {
                 Ok(conn) => {
                     // Parse date
                     let parsed_date = match NaiveDate::parse_from_str(&event_date(), "%Y-%m-%d") {
@@ -60,22 +62,31 @@ pub fn EventAdd(
                     };
 
                     if let Ok(q_uuid) = uuid::Uuid::parse_str(&quail_id) {
-                        match event_service::create_event(
-                            &conn,
-                            q_uuid,
-                            event_type(),
-                            parsed_date,
-                            notes_opt,
-                        )
-                        .await
-                        {
+                        let result = if event_type() == EventType::Treatment {
+                            treatment_service::record_treatment(
+                                &conn,
+                                q_uuid,
+                                parsed_date,
+                                treatment(),
+                                notes_opt,
+                            )
+                            .await
+                        } else {
+                            event_service::create_event(
+                                &conn,
+                                q_uuid,
+                                event_type(),
+                                parsed_date,
+                                notes_opt,
+                            )
+                            .await
+                        };
+                        match result {
                             Ok(event_id) => {
                                 // Save photos for this event
                                 for photo_path in photos() {
                                     let _ = crate::services::photo_service::add_event_photo(
-                                        &conn,
-                                        event_id,
-                                        photo_path,
+                                        &conn, event_id, photo_path,
                                         None, // Thumbnails werden im Service erstellt
                                     )
                                     .await;
@@ -133,9 +144,14 @@ pub fn EventAdd(
                     option { value: "marked_for_slaughter", { t!("event-type-marked") } }
                     option { value: "slaughtered", { t!("event-type-slaughtered") } }
                     option { value: "died", { t!("event-type-died") } }
+                    option { value: "treatment", { t!("event-type-treatment") } }
                 }
             }
 
+            if event_type() == EventType::Treatment {
+                TreatmentFields { treatment }
+            }
+
             div { class: "form-group", style: "margin-bottom: 20px;",
 
                 label { style: "display: block; margin-bottom: 8px; font-weight: bold;",
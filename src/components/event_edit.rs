@@ -1,77 +1,81 @@
 use crate::{
+    components::{
+        entity_history::{EntityHistory, HistoryTabs},
+        photo_paste::paste_image_button,
+        photo_thumbnails::thumbnail_state,
+        treatment::TreatmentFields,
+        TrashUndoBanner,
+    },
     database,
-    models::{EventType, QuailEvent},
-    services::{event_service, photo_service},
+    models::{photo::PhotoCollection, EventType, QuailEvent, Treatment},
+    services::{
+        download_queue::{DownloadRequest, DownloadScope},
+        event_service, photo_service,
+    },
     Screen,
 };
-use base64::Engine;
 use chrono::NaiveDate;
 use dioxus::prelude::*;
-use dioxus_gallery_components::{Gallery, GalleryConfig, GalleryItem};
+use dioxus_gallery_components::{
+    Gallery, GalleryConfig, GalleryItem, GalleryItemState, GalleryLayout,
+};
 use dioxus_i18n::t;
+use std::collections::HashMap;
+
+/// Abfragen, bis laufende Downloads der Galerie fertig sind (je Intervall)
+const DOWNLOAD_POLL_ROUNDS: usize = 30;
+const DOWNLOAD_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_secs(2);
 
 /// Helper component to load and display event photos using Gallery
 #[component]
 fn EventPhotoGallery(event_id: String, photos: Signal<Vec<crate::models::Photo>>) -> Element {
     // Load all photo data asynchronously
     let photo_list = photos();
-    let mut loaded_photos = use_signal(|| Vec::<(String, String)>::new());
+    let mut photo_states = use_signal(HashMap::<String, GalleryItemState>::new);
+    let mut last_trashed = use_signal(|| None::<uuid::Uuid>);
+    let event_id_for_restore = event_id.clone();
+    let download_scope = use_hook(DownloadScope::new);
+    use_drop(move || download_scope.cancel());
 
     // Trigger loading for all photos
     use_effect(move || {
-        let photo_list = photos();
+        let photo_ids: Vec<String> = photos().iter().map(|p| p.uuid.to_string()).collect();
         spawn(async move {
-            let mut loaded = Vec::new();
-            for photo in photo_list {
-                let photo_uuid = photo.uuid.to_string();
-                if let Ok(conn) = database::init_database() {
-                    if let Ok(uuid) = uuid::Uuid::parse_str(&photo_uuid) {
-                        match photo_service::get_photo_with_download(
-                            &conn,
-                            &uuid,
-                            crate::models::photo::PhotoSize::Small,
-                        )
-                        .await
-                        {
-                            Ok(crate::models::photo::PhotoResult::Available(bytes)) => {
-                                let data_url = format!(
-                                    "data:image/webp;base64,{}",
-                                    base64::engine::general_purpose::STANDARD.encode(&bytes)
-                                );
-                                loaded.push((photo_uuid, data_url));
-                            }
-                            Ok(crate::models::photo::PhotoResult::Downloading) => {
-                                log::debug!("Photo {} still downloading", photo_uuid);
-                            }
-                            Ok(crate::models::photo::PhotoResult::Failed(err, retry_count)) => {
-                                log::warn!(
-                                    "Photo {} download failed: {} (retry count: {})",
-                                    photo_uuid,
-                                    err,
-                                    retry_count
-                                );
-                            }
-                            Err(e) => {
-                                log::error!("Failed to load photo {}: {}", photo_uuid, e);
-                            }
-                        }
-                    } else {
-                        log::error!("Invalid photo UUID: {}", photo_uuid);
-                    }
-                } else {
-                    log::error!("Failed to initialize database");
+            for photo_id in &photo_ids {
+                let state =
+                    thumbnail_state(photo_id, DownloadRequest::visible(download_scope), false)
+                        .await;
+                photo_states.write().insert(photo_id.clone(), state);
+            }
+            // Laufende Downloads abwarten, damit die Platzhalter verschwinden
+            for _ in 0..DOWNLOAD_POLL_ROUNDS {
+                let loading: Vec<&String> = photo_ids
+                    .iter()
+                    .filter(|id| photo_states.peek().get(*id) == Some(&GalleryItemState::Loading))
+                    .collect();
+                if loading.is_empty() {
+                    break;
+                }
+                tokio::time::sleep(DOWNLOAD_POLL_INTERVAL).await;
+                for photo_id in loading {
+                    let state =
+                        thumbnail_state(photo_id, DownloadRequest::visible(download_scope), false)
+                            .await;
+                    photo_states.write().insert(photo_id.clone(), state);
                 }
             }
-            loaded_photos.set(loaded);
         });
     });
 
-    let gallery_items: Vec<GalleryItem> = loaded_photos()
+    let gallery_items: Vec<GalleryItem> = photo_list
         .iter()
-        .map(|(id, data_url)| GalleryItem {
-            id: id.clone(),
-            data_url: data_url.clone(),
-            caption: None,
+        .map(|photo| {
+            let id = photo.uuid.to_string();
+            GalleryItem {
+                state: photo_states().get(&id).cloned().unwrap_or_default(),
+                id,
+                caption: photo.caption.clone(),
+            }
         })
         .collect();
 
@@ -79,39 +83,111 @@ fn EventPhotoGallery(event_id: String, photos: Signal<Vec<crate::models::Photo>>
         allow_delete: true,
         allow_select: false,
         selected_id: None,
+        allow_caption_edit: true,
+        // Nicht quadratisch zuschneiden, sonst fehlt oft die Wachtel
+        layout: GalleryLayout::Justified,
+        allow_reorder: true,
     };
+    let event_id_for_reorder = event_id.clone();
 
     rsx! {
-        if gallery_items.is_empty() && !photo_list.is_empty() {
-            // Loading state
-            div { style: "padding: 24px; text-align: center; color: #666;", "⏳ Loading photos..." }
-        } else {
-            Gallery {
-                items: gallery_items,
-                config: gallery_config,
-                on_delete: move |photo_id: String| {
-                    let event_id_clone = event_id.clone();
-                    spawn(async move {
-                        if let Ok(conn) = database::init_database() {
-                            if let Ok(uuid) = uuid::Uuid::parse_str(&photo_id) {
-                                match photo_service::delete_photo(&conn, &uuid).await {
-                                    Ok(_) => {
-                                        log::info!("Successfully deleted photo {}", photo_id);
-                                    }
-                                    Err(e) => {
-                                        log::error!("Failed to delete photo {}: {}", photo_id, e);
-                                    }
+        Gallery {
+            items: gallery_items,
+            config: gallery_config,
+            on_delete: move |photo_id: String| {
+                let event_id_clone = event_id.clone();
+                spawn(async move {
+                    if let Ok(conn) = database::connection() {
+                        if let Ok(uuid) = uuid::Uuid::parse_str(&photo_id) {
+                            match photo_service::delete_photo(&conn, &uuid).await {
+                                Ok(_) => {
+                                    log::info!("Moved photo {} to the trash", photo_id);
+                                    last_trashed.set(Some(uuid));
+                                }
+                                Err(e) => {
+                                    log::error!("Failed to delete photo {}: {}", photo_id, e);
                                 }
                             }
-                            if let Ok(e_uuid) = uuid::Uuid::parse_str(&event_id_clone) {
-                                if let Ok(list) = photo_service::list_event_photos(&conn, &e_uuid) {
-                                    photos.set(list);
+                        }
+                        if let Ok(e_uuid) = uuid::Uuid::parse_str(&event_id_clone) {
+                            if let Ok(list) = photo_service::list_event_photos(&conn, &e_uuid) {
+                                photos.set(list);
+                            }
+                        }
+                    }
+                });
+            },
+            on_reorder: move |(from, to): (usize, usize)| {
+                let (Some(photo_uuid), Ok(e_uuid)) = (
+                    photos.peek().get(from).map(|photo| photo.uuid),
+                    uuid::Uuid::parse_str(&event_id_for_reorder),
+                ) else {
+                    return;
+                };
+                spawn(async move {
+                    let Ok(conn) = database::connection() else {
+                        return;
+                    };
+                    match photo_service::reorder_photo(
+                            &conn,
+                            PhotoCollection::Event(e_uuid),
+                            &photo_uuid,
+                            to,
+                        )
+                        .await
+                    {
+                        Ok(list) => photos.set(list),
+                        Err(e) => log::error!("Failed to reorder photo {}: {}", photo_uuid, e),
+                    }
+                });
+            },
+            on_retry: move |photo_id: String| {
+                photo_states.write().insert(photo_id.clone(), GalleryItemState::Loading);
+                spawn(async move {
+                    let state =
+                        thumbnail_state(&photo_id, DownloadRequest::visible(download_scope), true)
+                            .await;
+                    photo_states.write().insert(photo_id, state);
+                });
+            },
+            on_caption_change: move |(photo_id, caption): (String, String)| {
+                spawn(async move {
+                    let Ok(photo_uuid) = uuid::Uuid::parse_str(&photo_id) else {
+                        return;
+                    };
+                    let result = match database::connection() {
+                        Ok(conn) => {
+                            photo_service::set_photo_caption(&conn, &photo_uuid, Some(&caption))
+                                .await
+                        }
+                        Err(e) => Err(e),
+                    };
+                    match result {
+                        Ok(()) => {
+                            let caption = Some(caption).filter(|c| !c.is_empty());
+                            for photo in photos.write().iter_mut() {
+                                if photo.uuid == photo_uuid {
+                                    photo.caption = caption.clone();
                                 }
                             }
                         }
-                    });
-                },
-            }
+                        Err(e) => log::error!("Failed to save caption of photo {}: {}", photo_id, e),
+                    }
+                });
+            },
+        }
+        TrashUndoBanner {
+            trashed: last_trashed,
+            on_restored: move |_| {
+                if let (Ok(conn), Ok(e_uuid)) = (
+                    database::connection(),
+                    uuid::Uuid::parse_str(&event_id_for_restore),
+                ) {
+                    if let Ok(list) = photo_service::list_event_photos(&conn, &e_uuid) {
+                        photos.set(list);
+                    }
+                }
+            },
         }
     }
 }
@@ -131,22 +207,32 @@ pub fn EventEditScreen(
             .to_string()
     });
     let mut notes = use_signal(|| String::new());
+    let mut treatment = use_signal(Treatment::default);
     let mut photos = use_signal(|| Vec::<crate::models::Photo>::new());
     let mut error = use_signal(|| String::new());
     let mut success = use_signal(|| false);
     let mut uploading = use_signal(|| false);
     let saving = use_signal(|| false);
+    let show_history = use_signal(|| false);
 
-    #[cfg(target_os = "android")]
+    #[cfg(any(target_os = "android", feature = "desktop"))]
     let event_id_for_gallery = event_id.clone();
     #[cfg(target_os = "android")]
     let event_id_for_camera = event_id.clone();
 
+    // Downloads dieses Bildschirms enden beim Verlassen
+    let download_scope = use_hook(DownloadScope::new);
+    use_drop(move || download_scope.cancel());
+
     // Retry failed downloads beim Mount
     use_effect(move || {
         spawn(async move {
-            if let Ok(conn) = database::init_database() {
-                if let Err(e) = crate::services::photo_service::retry_failed_downloads(&conn).await
+            if let Ok(conn) = database::connection() {
+                if let Err(e) = crate::services::photo_service::retry_failed_downloads(
+                    &conn,
+                    DownloadRequest::background(download_scope),
+                )
+                .await
                 {
                     log::warn!("Failed to retry photo downloads: {}", e);
                 }
@@ -157,7 +243,7 @@ pub fn EventEditScreen(
     // Load event + photos
     let event_id_for_load = event_id.clone();
     use_effect(move || {
-        if let Ok(conn) = database::init_database() {
+        if let Ok(conn) = database::connection() {
             if let Ok(e_uuid) = uuid::Uuid::parse_str(&event_id_for_load) {
                 match event_service::get_event_by_id(&conn, &e_uuid) {
                     Ok(Some(e)) => {
@@ -165,14 +251,17 @@ pub fn EventEditScreen(
                         event_type.set(e.event_type.clone());
                         event_date_str.set(e.event_date.format("%Y-%m-%d").to_string());
                         notes.set(e.notes.unwrap_or_default());
+                        treatment.set(e.treatment.unwrap_or_default());
                     }
                     Ok(None) => error.set(t!("event-not-found")),
                     Err(e) => error.set(t!("error-loading", error: e.to_string())),
                 }
-                match photo_service::list_event_photos(&conn, &e_uuid) {
-                    Ok(list) => photos.set(list),
-                    Err(e) => log::error!("Fehler beim Laden der Event-Fotos: {}", e),
-                }
+                spawn(async move {
+                    match photo_service::load_event_photos(e_uuid).await {
+                        Ok(list) => photos.set(list),
+                        Err(e) => log::error!("Fehler beim Laden der Event-Fotos: {}", e),
+                    }
+                });
             }
         }
     });
@@ -199,6 +288,8 @@ pub fn EventEditScreen(
         let event_id_clone = event_id_for_save.clone();
         let quail_id_clone = quail_id_for_save.clone();
         let event_type_val = event_type();
+        // Angaben zur Behandlung nur für Behandlungen speichern
+        let treatment_val = (event_type_val == EventType::Treatment).then(|| treatment.cloned());
         let notes_val = if notes().is_empty() {
             None
         } else {
@@ -206,7 +297,7 @@ pub fn EventEditScreen(
         };
         let mut saving_signal = saving_signal.clone();
         spawn(async move {
-            if let Ok(conn) = database::init_database() {
+            if let Ok(conn) = database::connection() {
                 if let Ok(e_uuid) = uuid::Uuid::parse_str(&event_id_clone) {
                     match event_service::update_event_full(
                         &conn,
@@ -214,6 +305,7 @@ pub fn EventEditScreen(
                         event_type_val,
                         parsed_date,
                         notes_val,
+                        treatment_val,
                     )
                     .await
                     {
@@ -242,7 +334,7 @@ pub fn EventEditScreen(
         let event_id_clone = event_id_for_delete.clone();
         let quail_id_clone = quail_id_for_delete.clone();
         spawn(async move {
-            if let Ok(conn) = database::init_database() {
+            if let Ok(conn) = database::connection() {
                 if let Ok(e_uuid) = uuid::Uuid::parse_str(&event_id_clone) {
                     match event_service::delete_event(&conn, &e_uuid).await {
                         Ok(_) => on_navigate.call(Screen::ProfileDetail(quail_id_clone.clone())),
@@ -276,7 +368,10 @@ pub fn EventEditScreen(
                     {t!("updated")}
                 }
             }
-            if let Some(_) = event() {
+            HistoryTabs { show_history, content_label: format!("✏️ {}", t!("action-edit")) }
+            if show_history() {
+                EntityHistory { entity_type: "event", entity_id: event_id.clone() }
+            } else if let Some(_) = event() {
                 // Event type
                 div { style: "margin-bottom:16px;",
                     label { style: "display:block; font-weight:600; margin-bottom:6px;",
@@ -296,8 +391,12 @@ pub fn EventEditScreen(
                         option { value: "marked_for_slaughter", {t!("event-type-marked")} }
                         option { value: "slaughtered", {t!("event-type-slaughtered")} }
                         option { value: "died", {t!("event-type-died")} }
+                        option { value: "treatment", {t!("event-type-treatment")} }
                     }
                 }
+                if event_type() == EventType::Treatment {
+                    TreatmentFields { treatment }
+                }
                 // Date
                 div { style: "margin-bottom:16px;",
                     label { style: "display:block; font-weight:600; margin-bottom:6px;",
@@ -336,14 +435,14 @@ pub fn EventEditScreen(
                                 move |_| {
                                     uploading.set(true);
                                     error.set(String::new());
-                                    #[cfg(target_os = "android")]
+                                    #[cfg(any(target_os = "android", feature = "desktop"))]
                                     let event_id_clone = event_id_for_gallery.clone();
                                     spawn(async move {
-                                        #[cfg(target_os = "android")]
+                                        #[cfg(any(target_os = "android", feature = "desktop"))]
                                         {
                                             match crate::camera::pick_images() {
                                                 Ok(paths) => {
-                                                    if let Ok(conn) = database::init_database() {
+                                                    if let Ok(conn) = database::connection() {
                                                         if let Ok(e_uuid) = uuid::Uuid::parse_str(&event_id_clone) {
                                                             for p in paths {
                                                                 let ps = p.to_string_lossy().to_string();
@@ -369,7 +468,7 @@ pub fn EventEditScreen(
                                                 }
                                             }
                                         }
-                                        #[cfg(not(target_os = "android"))]
+                                        #[cfg(not(any(target_os = "android", feature = "desktop")))]
                                         {
                                             error.set(t!("error-android-only-gallery"));
                                         }
@@ -398,7 +497,7 @@ pub fn EventEditScreen(
                                         {
                                             match crate::camera::capture_photo() {
                                                 Ok(p) => {
-                                                    if let Ok(conn) = database::init_database() {
+                                                    if let Ok(conn) = database::connection() {
                                                         if let Ok(e_uuid) = uuid::Uuid::parse_str(&event_id_clone) {
                                                             let ps = p.to_string_lossy().to_string();
                                                             let _ = photo_service::add_event_photo(
@@ -437,6 +536,22 @@ pub fn EventEditScreen(
                                 {t!("action-photo")}
                             }
                         }
+                        if let Ok(e_uuid) = uuid::Uuid::parse_str(&event_id) {
+                            {
+                                paste_image_button(
+                                    PhotoCollection::Event(e_uuid),
+                                    EventHandler::new(move |_| {
+                                        error.set(String::new());
+                                        if let Ok(conn) = database::connection() {
+                                            if let Ok(list) = photo_service::list_event_photos(&conn, &e_uuid) {
+                                                photos.set(list);
+                                            }
+                                        }
+                                    }),
+                                    EventHandler::new(move |e| error.set(e)),
+                                )
+                            }
+                        }
                     }
                 }
                 // Action buttons
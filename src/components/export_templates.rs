@@ -0,0 +1,286 @@
+use crate::components::share_button::ShareButton;
+use crate::database;
+use crate::models::{EventType, ExportTemplate, TemplateEntity};
+use crate::services::export_templates;
+use dioxus::prelude::*;
+use dioxus_i18n::t;
+use std::path::PathBuf;
+
+fn entity_label(entity: TemplateEntity) -> String {
+    match entity {
+        TemplateEntity::Quails => t!("export-template-entity-quails"),
+        TemplateEntity::Events => t!("export-template-entity-events"),
+        TemplateEntity::EggRecords => t!("export-template-entity-eggs"),
+    }
+}
+
+fn column_label(column: &str) -> String {
+    match column {
+        "name" => t!("export-template-column-name"),
+        "gender" => t!("export-template-column-gender"),
+        "ring_color" => t!("export-template-column-ring-color"),
+        "date" => t!("export-template-column-date"),
+        "quail" => t!("export-template-column-quail"),
+        "event_type" => t!("export-template-column-event-type"),
+        "total_eggs" => t!("export-template-column-total-eggs"),
+        "notes" => t!("export-template-column-notes"),
+        other => other.to_string(),
+    }
+}
+
+fn event_type_label(event_type: &EventType) -> String {
+    match event_type {
+        EventType::Born => t!("event-type-born"),
+        EventType::Alive => t!("event-type-alive"),
+        EventType::Sick => t!("event-type-sick"),
+        EventType::Healthy => t!("event-type-healthy"),
+        EventType::MarkedForSlaughter => t!("event-type-marked"),
+        EventType::Slaughtered => t!("event-type-slaughtered"),
+        EventType::Died => t!("event-type-died"),
+        EventType::Treatment => t!("event-type-treatment"),
+    }
+}
+
+fn load_templates() -> Vec<ExportTemplate> {
+    database::with_db(export_templates::list_templates).unwrap_or_default()
+}
+
+/// CSV-Export nach gespeicherten Vorlagen (Einträge, Spalten, Ereignistypen)
+#[component]
+pub fn ExportTemplatesCard() -> Element {
+    let mut templates = use_signal(load_templates);
+    let mut selected = use_signal(|| templates.peek().first().and_then(|t| t.id));
+    // Vorlage im Editor (neu oder in Bearbeitung)
+    let mut editing = use_signal(|| None::<ExportTemplate>);
+    let mut message = use_signal(String::new);
+    let mut exported = use_signal(|| None::<PathBuf>);
+
+    let current = move || {
+        templates()
+            .into_iter()
+            .find(|template| template.id == selected())
+    };
+
+    let export = move |_| {
+        let Some(template) = current() else {
+            return;
+        };
+        match database::with_db(|conn| export_templates::export_template_csv(conn, &template)) {
+            Ok(path) => {
+                message.set(t!("export-template-success", path: path.display().to_string()));
+                exported.set(Some(path));
+            }
+            Err(e) => {
+                exported.set(None);
+                message.set(t!("export-template-error", error: e.to_string()));
+            }
+        }
+    };
+
+    let save = move |_| {
+        let Some(template) = editing() else {
+            return;
+        };
+        match database::with_db(|conn| export_templates::save_template(conn, &template)) {
+            Ok(id) => {
+                templates.set(load_templates());
+                selected.set(Some(id));
+                editing.set(None);
+                message.set(String::new());
+            }
+            Err(e) => message.set(format!("\u{274c} {}: {}", t!("error-save-failed"), e)),
+        }
+    };
+
+    let delete = move |_| {
+        let Some(id) = selected() else {
+            return;
+        };
+        match database::with_db(|conn| export_templates::delete_template(conn, id)) {
+            Ok(()) => {
+                let remaining = load_templates();
+                selected.set(remaining.first().and_then(|t| t.id));
+                templates.set(remaining);
+                exported.set(None);
+                message.set(String::new());
+            }
+            Err(e) => message.set(t!("export-template-error", error: e.to_string())),
+        }
+    };
+
+    rsx! {
+        div { style: "margin-top: 16px; padding: 12px; background: #fff; border-radius: 8px; border: 1px solid #ddd;",
+            h3 { style: "margin: 0 0 8px 0; font-size: 16px;",
+                "🧾 "
+                {t!("export-template-title")}
+            }
+            p { style: "margin: 0 0 12px 0; font-size: 13px; color: #666;",
+                {t!("export-template-description")}
+            }
+
+            if let Some(draft) = editing() {
+                input {
+                    r#type: "text",
+                    style: "width: 100%; padding: 8px; border: 1px solid #ccc; border-radius: 4px; margin-bottom: 8px; box-sizing: border-box;",
+                    placeholder: t!("export-template-name-placeholder"),
+                    value: "{draft.name}",
+                    oninput: move |e| {
+                        if let Some(draft) = editing.write().as_mut() {
+                            draft.name = e.value();
+                        }
+                    },
+                }
+                select {
+                    style: "width: 100%; padding: 8px; border: 1px solid #ccc; border-radius: 4px; margin-bottom: 8px;",
+                    value: draft.entity.as_str(),
+                    onchange: move |e| {
+                        let entity = TemplateEntity::parse(&e.value()).unwrap_or_default();
+                        if let Some(draft) = editing.write().as_mut() {
+                            // Andere Einträge haben andere Spalten
+                            *draft = ExportTemplate {
+                                id: draft.id,
+                                ..ExportTemplate::new(draft.name.clone(), entity)
+                            };
+                        }
+                    },
+                    for entity in TemplateEntity::ALL {
+                        option { value: entity.as_str(), {entity_label(entity)} }
+                    }
+                }
+
+                p { style: "margin: 0 0 4px 0; font-size: 13px; font-weight: 600;",
+                    {t!("export-template-columns")}
+                }
+                for column in draft.entity.columns().iter().copied() {
+                    label { style: "display: flex; align-items: center; gap: 8px; font-size: 14px; margin-bottom: 4px;",
+                        input {
+                            r#type: "checkbox",
+                            checked: draft.columns.iter().any(|c| c == column),
+                            onchange: move |e| {
+                                if let Some(draft) = editing.write().as_mut() {
+                                    draft.columns.retain(|c| c != column);
+                                    if e.checked() {
+                                        draft.columns.push(column.to_string());
+                                        draft.normalize();
+                                    }
+                                }
+                            },
+                        }
+                        {column_label(column)}
+                    }
+                }
+
+                if draft.entity == TemplateEntity::Events {
+                    p { style: "margin: 8px 0 4px 0; font-size: 13px; font-weight: 600;",
+                        {t!("export-template-event-types")}
+                    }
+                    for event_type in EventType::ALL {
+                        label { style: "display: flex; align-items: center; gap: 8px; font-size: 14px; margin-bottom: 4px;",
+                            input {
+                                r#type: "checkbox",
+                                checked: draft.event_types.contains(&event_type),
+                                onchange: {
+                                    let event_type = event_type.clone();
+                                    move |e: FormEvent| {
+                                        if let Some(draft) = editing.write().as_mut() {
+                                            draft.event_types.retain(|t| *t != event_type);
+                                            if e.checked() {
+                                                draft.event_types.push(event_type.clone());
+                                            }
+                                        }
+                                    }
+                                },
+                            }
+                            {event_type_label(&event_type)}
+                        }
+                    }
+                    p { style: "margin: 0 0 8px 0; font-size: 12px; color: #666;",
+                        {t!("export-template-event-types-hint")}
+                    }
+                }
+
+                div { style: "display: flex; gap: 8px; margin-top: 8px;",
+                    button {
+                        class: "btn-primary",
+                        style: "flex: 1;",
+                        onclick: save,
+                        {t!("action-save")}
+                    }
+                    button {
+                        class: "btn-secondary",
+                        style: "flex: 1;",
+                        onclick: move |_| {
+                            editing.set(None);
+                            message.set(String::new());
+                        },
+                        {t!("action-cancel")}
+                    }
+                }
+            } else {
+                if !templates().is_empty() {
+                    select {
+                        style: "width: 100%; padding: 8px; border: 1px solid #ccc; border-radius: 4px; margin-bottom: 8px;",
+                        value: selected().map(|id| id.to_string()).unwrap_or_default(),
+                        onchange: move |e| {
+                            selected.set(e.value().parse().ok());
+                            exported.set(None);
+                            message.set(String::new());
+                        },
+                        for template in templates() {
+                            option {
+                                value: template.id.map(|id| id.to_string()).unwrap_or_default(),
+                                "{template.name}"
+                            }
+                        }
+                    }
+                }
+
+                div { style: "display: flex; flex-direction: column; gap: 8px;",
+                    if current().is_some() {
+                        button {
+                            class: "btn-primary",
+                            style: "width: 100%;",
+                            onclick: export,
+                            {t!("export-template-export-button")}
+                        }
+                        div { style: "display: flex; gap: 8px;",
+                            button {
+                                class: "btn-secondary",
+                                style: "flex: 1;",
+                                onclick: move |_| {
+                                    editing.set(current());
+                                    message.set(String::new());
+                                },
+                                {t!("action-edit")}
+                            }
+                            button {
+                                class: "btn-secondary",
+                                style: "flex: 1;",
+                                onclick: delete,
+                                {t!("action-delete")}
+                            }
+                        }
+                    }
+                    button {
+                        class: "btn-secondary",
+                        style: "width: 100%;",
+                        onclick: move |_| {
+                            editing.set(Some(ExportTemplate::new(String::new(), TemplateEntity::default())));
+                            message.set(String::new());
+                        },
+                        {t!("export-template-new-button")}
+                    }
+                }
+            }
+
+            if !message().is_empty() {
+                p { style: "margin: 8px 0 0 0; font-size: 13px; word-break: break-all;",
+                    "{message}"
+                }
+            }
+            if let Some(path) = exported() {
+                ShareButton { path }
+            }
+        }
+    }
+}
@@ -10,7 +10,7 @@ pub fn HomeScreen(on_navigate: EventHandler<Screen>) -> Element {
     let mut profile_count = use_signal(|| 0i32);
 
     // Initialize database on mount
-    use_effect(move || match database::init_database() {
+    use_effect(move || match database::connection() {
         Ok(conn) => match services::count_profiles(&conn) {
             Ok(count) => {
                 profile_count.set(count);
@@ -55,7 +55,10 @@ pub fn HomeScreen(on_navigate: EventHandler<Screen>) -> Element {
                     button {
                         class: "btn-success",
                         style: "padding: 16px; font-size: 16px; display: flex; align-items: center; justify-content: center;",
-                        onclick: move |_| on_navigate.call(Screen::EggTracking(None)),
+                        onclick: move |_| on_navigate.call(Screen::EggTracking {
+                            date: None,
+                            coop_id: None,
+                        }),
                         {format!("🥚 {}", t!("egg-tracking-title"))}
                     }
                     button {
@@ -0,0 +1,122 @@
+use crate::components::transfer_stats::format_bytes;
+use crate::database;
+use crate::services::backup_service::{self, Snapshot};
+use dioxus::prelude::*;
+use dioxus_i18n::t;
+use std::path::PathBuf;
+
+fn load_snapshots() -> Vec<Snapshot> {
+    backup_service::list_snapshots(&backup_service::backup_dir()).unwrap_or_default()
+}
+
+/// Tägliche lokale Sicherungen: Liste, manuelle Sicherung und Wiederherstellung
+#[component]
+pub fn LocalBackupCard() -> Element {
+    let mut snapshots = use_signal(load_snapshots);
+    // Sicherung, deren Wiederherstellung bestätigt werden muss
+    let mut confirm_restore = use_signal(|| None::<PathBuf>);
+    let mut busy = use_signal(|| false);
+    let mut message = use_signal(String::new);
+
+    let create = move |_| {
+        busy.set(true);
+        spawn(async move {
+            let dir = backup_service::backup_dir();
+            let result = database::with_db_async(move |conn| {
+                let snapshot = backup_service::create_snapshot(conn, &dir)?;
+                backup_service::rotate(&dir, backup_service::KEEP_SNAPSHOTS)?;
+                Ok(snapshot)
+            })
+            .await;
+            match result {
+                Ok(_) => message.set(t!("local-backup-created")),
+                Err(e) => message.set(t!("local-backup-error", error: e.to_string())),
+            }
+            snapshots.set(load_snapshots());
+            busy.set(false);
+        });
+    };
+
+    let restore = move |_| {
+        let Some(path) = confirm_restore.take() else {
+            return;
+        };
+        match backup_service::restore_snapshot(&path) {
+            Ok(previous) => message.set(t!(
+                "local-backup-restored",
+                previous: previous.display().to_string()
+            )),
+            Err(e) => message.set(t!("local-backup-error", error: e.to_string())),
+        }
+        snapshots.set(load_snapshots());
+    };
+
+    rsx! {
+        div { style: "margin-top: 16px; padding: 12px; background: #fff; border-radius: 8px; border: 1px solid #ddd;",
+            h3 { style: "margin: 0 0 8px 0; font-size: 16px;",
+                "🗂️ "
+                {t!("local-backup-title")}
+            }
+            p { style: "margin: 0 0 12px 0; font-size: 13px; color: #666;",
+                {t!("local-backup-description", keep: backup_service::KEEP_SNAPSHOTS)}
+            }
+            if snapshots().is_empty() {
+                p { style: "margin: 0 0 8px 0; font-size: 12px; color: #666;", {t!("local-backup-empty")} }
+            } else {
+                div { style: "display: flex; flex-direction: column; gap: 4px; margin-bottom: 8px;",
+                    for snapshot in snapshots() {
+                        div {
+                            key: "{snapshot.path.display()}",
+                            style: "display: flex; justify-content: space-between; align-items: center; gap: 8px; font-size: 13px;",
+                            span { style: "color: #333;",
+                                {snapshot.created_at.format("%d.%m.%Y %H:%M").to_string()}
+                                span { style: "color: #999; margin-left: 8px;", "{format_bytes(snapshot.size_bytes)}" }
+                            }
+                            button {
+                                style: "padding: 4px 10px; font-size: 13px; background: #e0e0e0; color: #333;",
+                                disabled: busy(),
+                                onclick: {
+                                    let path = snapshot.path.clone();
+                                    move |_| confirm_restore.set(Some(path.clone()))
+                                },
+                                {t!("local-backup-restore")}
+                            }
+                        }
+                    }
+                }
+            }
+            if confirm_restore().is_some() {
+                p { style: "margin: 0 0 8px 0; padding: 8px; background: #fff3cd; border-radius: 4px; font-size: 12px; color: #856404;",
+                    "⚠️ "
+                    {t!("local-backup-restore-confirm")}
+                }
+                div { style: "display: flex; gap: 8px;",
+                    button {
+                        class: "btn-danger",
+                        style: "flex: 1;",
+                        onclick: restore,
+                        {t!("local-backup-restore")}
+                    }
+                    button {
+                        style: "flex: 1; background: #e0e0e0; color: #666;",
+                        onclick: move |_| confirm_restore.set(None),
+                        {t!("action-cancel")}
+                    }
+                }
+            } else {
+                button {
+                    class: "btn-primary",
+                    style: "width: 100%;",
+                    disabled: busy(),
+                    onclick: create,
+                    {t!("local-backup-create")}
+                }
+            }
+            if !message().is_empty() {
+                p { style: "margin: 8px 0 0 0; font-size: 12px; white-space: pre-line;",
+                    "{message}"
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,141 @@
+use crate::services::nextcloud_auth::{
+    AuthError, CertificatePin, NextcloudAuthService, NextcloudCredentials, ProxyConfig,
+};
+use dioxus::prelude::*;
+use dioxus_i18n::t;
+
+/// Fallback-Anmeldung mit Benutzername und App-Passwort, falls der
+/// Login Flow v2 (z.B. hinter SSO-Proxies) nicht funktioniert.
+/// Mit `pin_certificate` werden auch selbstsignierte Zertifikate akzeptiert
+/// und gemerkt.
+#[component]
+pub fn ManualCredentialsComponent(
+    server_url: String,
+    proxy_url: String,
+    pin_certificate: bool,
+    on_success: EventHandler<NextcloudCredentials>,
+) -> Element {
+    let mut username = use_signal(String::new);
+    let mut app_password = use_signal(String::new);
+    let mut validating = use_signal(|| false);
+    let mut error = use_signal(|| None::<String>);
+
+    let server_for_submit = server_url.clone();
+    let handle_submit = move |_| {
+        let server = server_for_submit.clone();
+        let proxy_value = proxy_url.clone();
+        let user = username();
+        let password = app_password();
+        if user.trim().is_empty() || password.is_empty() {
+            error.set(Some(t!("sync-manual-fields-required")));
+            return;
+        }
+
+        error.set(None);
+        validating.set(true);
+        spawn(async move {
+            let result = async {
+                let proxy = ProxyConfig::parse(&proxy_value)?;
+                let pin = if pin_certificate {
+                    CertificatePin::trust_on_first_use()
+                } else {
+                    CertificatePin::default()
+                };
+                let auth = NextcloudAuthService::with_pin(proxy.as_ref(), pin)?;
+                let base = auth.discover_server(&server).await?;
+                auth.server_status(&base).await?.ensure_available(&base)?;
+                auth.validate_credentials(&base, &user, &password).await
+            }
+            .await;
+            match result {
+                Ok(credentials) => on_success.call(credentials),
+                Err(e) if e.is_unauthorized() => {
+                    error.set(Some(t!("sync-manual-invalid")));
+                }
+                Err(e) => error.set(Some(auth_error_message(&e))),
+            }
+            validating.set(false);
+        });
+    };
+
+    rsx! {
+        div { style: "padding: 12px; background: #f8f9fa; border-radius: 4px; border: 1px solid #ddd;",
+            p { style: "margin: 0 0 4px 0; font-weight: 600; font-size: 14px;",
+                "🔑 "
+                {t!("sync-manual-title")}
+            }
+            p { style: "margin: 0 0 12px 0; font-size: 12px; color: #666;",
+                {t!("sync-manual-hint")}
+            }
+
+            if let Some(err) = error() {
+                div { style: "padding: 8px; margin-bottom: 12px; background: #f8d7da; border-radius: 4px; color: #721c24; font-size: 13px;",
+                    "⚠️ {err}"
+                }
+            }
+
+            div { style: "margin-bottom: 12px;",
+                label { style: "display: block; margin-bottom: 4px; font-weight: 600; font-size: 14px;",
+                    {t!("sync-manual-username")}
+                }
+                input {
+                    r#type: "text",
+                    value: "{username}",
+                    autocomplete: "username",
+                    oninput: move |e| username.set(e.value()),
+                    style: "width: 100%; padding: 10px; font-size: 16px; border: 1px solid #ccc; border-radius: 4px;",
+                }
+            }
+
+            div { style: "margin-bottom: 12px;",
+                label { style: "display: block; margin-bottom: 4px; font-weight: 600; font-size: 14px;",
+                    {t!("sync-manual-app-password")}
+                }
+                input {
+                    r#type: "password",
+                    value: "{app_password}",
+                    autocomplete: "current-password",
+                    oninput: move |e| app_password.set(e.value()),
+                    style: "width: 100%; padding: 10px; font-size: 16px; border: 1px solid #ccc; border-radius: 4px;",
+                }
+                p { style: "margin: 4px 0 0 0; font-size: 12px; color: #666;",
+                    {t!("sync-manual-app-password-hint")}
+                }
+            }
+
+            button {
+                class: "btn-primary",
+                style: "width: 100%;",
+                disabled: validating() || server_url.trim().is_empty(),
+                onclick: handle_submit,
+                if validating() {
+                    "⏳ "
+                    {t!("sync-manual-validating")}
+                } else {
+                    "✓ "
+                    {t!("sync-manual-submit")}
+                }
+            }
+        }
+    }
+}
+
+/// Lokalisierte Meldung für Anmeldefehler (Login Flow v2 und manuelle Anmeldung)
+pub fn auth_error_message(error: &AuthError) -> String {
+    match error {
+        AuthError::DnsFailure(_) => t!("error-auth-dns"),
+        AuthError::TlsError(_) => t!("error-auth-tls"),
+        AuthError::Timeout => t!("error-auth-timeout"),
+        AuthError::ServerStatus(status) => t!("error-auth-status", status: *status),
+        AuthError::JsonParse(_) => t!("error-auth-parse"),
+        AuthError::Cancelled => t!("error-auth-cancelled"),
+        AuthError::Network(e) => format!("{}: {}", t!("error-connection"), e),
+        AuthError::InvalidInput(_) => t!("sync-manual-fields-required"),
+        AuthError::NotNextcloud(url) => t!("error-auth-not-nextcloud", url: url.clone()),
+        AuthError::Maintenance => t!("error-auth-maintenance"),
+        AuthError::CertificateChanged(fingerprint) => {
+            t!("error-auth-certificate-changed", fingerprint: fingerprint.clone())
+        }
+        AuthError::Client(e) => format!("{}: {}", t!("error-client"), e),
+    }
+}
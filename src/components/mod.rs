@@ -1,25 +1,63 @@
+pub mod backup_import;
+pub mod batch_actions;
+pub mod calendar_export;
+pub mod cloud_export;
+pub mod coops;
+#[cfg(not(target_os = "android"))]
+pub mod data_location;
+#[cfg(feature = "sqlcipher")]
+pub mod database_encryption;
+pub mod database_integrity;
 pub mod egg_history;
 pub mod egg_tracking;
+pub mod entity_history;
 pub mod event_add;
 pub mod event_edit;
+pub mod export_templates;
 pub mod home;
+pub mod local_backup;
+pub mod manual_credentials;
 pub mod navigation;
+pub mod pedigree;
+#[cfg(feature = "encryption")]
+pub mod photo_encryption;
+pub mod photo_paste;
+pub mod photo_thumbnails;
+pub mod photo_trash;
 pub mod profile_add;
 pub mod profile_detail;
 pub mod profile_edit;
 pub mod profile_list;
+pub mod recovery;
+pub mod scheduled_export;
+pub mod search;
+pub mod selective_export;
 pub mod settings;
+pub mod share_button;
+pub mod spreadsheet_import;
+pub mod state_rebuild;
 pub mod statistics;
+pub mod sync_history;
+pub mod sync_progress;
+pub mod transfer_stats;
+pub mod treatment;
 
+pub use backup_import::BackupImportScreen;
 pub use egg_history::EggHistoryScreen;
 pub use egg_tracking::EggTrackingScreen;
 pub use event_add::EventAdd;
 pub use event_edit::EventEditScreen;
 pub use home::HomeScreen;
+pub use manual_credentials::ManualCredentialsComponent;
 pub use navigation::NavigationBar;
+pub use photo_trash::TrashUndoBanner;
 pub use profile_add::AddProfileScreen;
 pub use profile_detail::ProfileDetailScreen;
 pub use profile_edit::ProfileEditScreen;
 pub use profile_list::ProfileListScreen;
+pub use recovery::RecoveryScreen;
+pub use search::SearchScreen;
 pub use settings::SettingsScreen;
+pub use spreadsheet_import::SpreadsheetImportScreen;
 pub use statistics::StatisticsScreen;
+pub use sync_history::SyncHistoryScreen;
@@ -49,6 +49,16 @@ pub fn NavigationBar(current_screen: Screen, on_navigate: EventHandler<Screen>)
                 onclick: move |_| on_navigate.call(Screen::Statistics),
                 {format!("📊 {}", t!("nav-statistics"))} // Statistik
             }
+
+            button {
+                style: if matches!(current_screen, Screen::Search) {
+                    "flex: 1; padding: 12px; margin: 0 5px; border: none; border-radius: 8px; cursor: pointer; font-size: 14px; text-align: center; background: #0066cc; color: #ffffff;"
+                } else {
+                    "flex: 1; padding: 12px; margin: 0 5px; border: none; border-radius: 8px; cursor: pointer; font-size: 14px; text-align: center; background: #ffffff; color: #333;"
+                },
+                onclick: move |_| on_navigate.call(Screen::Search),
+                {format!("🔍 {}", t!("nav-search"))} // Suche
+            }
         }
     }
 }
@@ -0,0 +1,205 @@
+use crate::database;
+use crate::models::{Gender, Quail};
+use crate::services::{pedigree, profile_service};
+use crate::Screen;
+use dioxus::prelude::*;
+use dioxus_i18n::t;
+use uuid::Uuid;
+
+const SELECT_STYLE: &str = "width: 100%; padding: 14px 16px; font-size: 16px; border: 2px solid #e0e0e0; border-radius: 8px; background: white;";
+const LABEL_STYLE: &str =
+    "display: block; margin-bottom: 8px; font-weight: 600; color: #333; font-size: 14px;";
+
+/// Auswahl von Mutter und Vater für das Hinzufügen und Bearbeiten
+///
+/// Zur Wahl stehen alle anderen Wachteln; Hähne nicht als Mutter, Hennen
+/// nicht als Vater. Sind die gewählten Eltern verwandt, erscheint eine
+/// Inzucht-Warnung mit den gemeinsamen Vorfahren.
+#[component]
+pub fn ParentSelect(
+    quail_id: Option<Uuid>,
+    mother: Signal<Option<Uuid>>,
+    father: Signal<Option<Uuid>>,
+) -> Element {
+    let quails = use_signal(|| {
+        database::with_db(|conn| profile_service::list_profiles(conn, None))
+            .unwrap_or_else(|e| {
+                log::error!("Fehler beim Laden der Wachteln: {}", e);
+                Vec::new()
+            })
+            .into_iter()
+            .filter(|q| Some(q.uuid) != quail_id)
+            .collect::<Vec<Quail>>()
+    });
+
+    // Namen der gemeinsamen Vorfahren, leer wenn nicht verwandt
+    let shared = use_memo(move || match (mother(), father()) {
+        (Some(m), Some(f)) => database::with_db(|conn| pedigree::common_ancestors(conn, &m, &f))
+            .unwrap_or_default()
+            .into_iter()
+            .map(|q| q.name)
+            .collect::<Vec<_>>()
+            .join(", "),
+        _ => String::new(),
+    });
+
+    let candidates = move |excluded: Gender| -> Vec<Quail> {
+        quails()
+            .into_iter()
+            .filter(|q| q.gender != excluded)
+            .collect()
+    };
+    let as_value = |id: Option<Uuid>| id.map(|id| id.to_string()).unwrap_or_default();
+
+    rsx! {
+        div { style: "margin-bottom: 20px;",
+            label { style: LABEL_STYLE, {t!("field-mother")} }
+            select {
+                style: SELECT_STYLE,
+                value: as_value(mother()),
+                onchange: move |e| mother.set(Uuid::parse_str(&e.value()).ok()),
+                option { value: "", {t!("parent-unknown")} }
+                for q in candidates(Gender::Male) {
+                    option {
+                        key: "{q.uuid}",
+                        value: "{q.uuid}",
+                        selected: mother() == Some(q.uuid),
+                        "{q.name}"
+                    }
+                }
+            }
+        }
+        div { style: "margin-bottom: 20px;",
+            label { style: LABEL_STYLE, {t!("field-father")} }
+            select {
+                style: SELECT_STYLE,
+                value: as_value(father()),
+                onchange: move |e| father.set(Uuid::parse_str(&e.value()).ok()),
+                option { value: "", {t!("parent-unknown")} }
+                for q in candidates(Gender::Female) {
+                    option {
+                        key: "{q.uuid}",
+                        value: "{q.uuid}",
+                        selected: father() == Some(q.uuid),
+                        "{q.name}"
+                    }
+                }
+            }
+        }
+        if !shared().is_empty() {
+            div { style: "padding: 12px; background: #fff3e0; border: 1px solid #ffcc80; border-radius: 8px; color: #e65100; font-size: 13px; margin-bottom: 20px;",
+                "⚠️ "
+                {t!("pedigree-inbreeding-warning", ancestors: shared())}
+            }
+        }
+    }
+}
+
+/// Tiere einer Generation im Stammbaum, `None` für unbekannte Eltern
+fn generations(tree: &pedigree::PedigreeNode) -> Vec<Vec<Option<Quail>>> {
+    let mut result = vec![vec![Some(tree.quail.clone())]];
+    let mut current = vec![Some(tree)];
+    for _ in 1..pedigree::TREE_GENERATIONS {
+        current = current
+            .iter()
+            .flat_map(|node| match node {
+                Some(node) => [node.mother.as_deref(), node.father.as_deref()],
+                None => [None, None],
+            })
+            .collect();
+        result.push(
+            current
+                .iter()
+                .map(|node| node.map(|n| n.quail.clone()))
+                .collect(),
+        );
+    }
+    result
+}
+
+#[component]
+fn TreeNode(quail: Option<Quail>, on_navigate: EventHandler<Screen>) -> Element {
+    match quail {
+        Some(q) => {
+            let id = q.uuid.to_string();
+            let symbol = match q.gender {
+                Gender::Female => "♀",
+                Gender::Male => "♂",
+                Gender::Unknown => "",
+            };
+            rsx! {
+                div {
+                    style: "flex: 1; padding: 8px; background: white; border: 1px solid #cce0f5; border-radius: 8px; font-size: 13px; color: #0066cc; cursor: pointer; text-align: center; word-break: break-word;",
+                    onclick: move |_| on_navigate.call(Screen::ProfileDetail(id.clone())),
+                    "{q.name} {symbol}"
+                }
+            }
+        }
+        None => rsx! {
+            div { style: "flex: 1; padding: 8px; background: #f5f5f5; border: 1px dashed #ccc; border-radius: 8px; font-size: 13px; color: #999; text-align: center;",
+                "?"
+            }
+        },
+    }
+}
+
+/// Stammbaum (Tier, Eltern, Großeltern) und Nachkommen auf der Detailseite
+#[component]
+pub fn FamilyTree(quail_id: Uuid, on_navigate: EventHandler<Screen>) -> Element {
+    let tree = use_signal(|| {
+        database::with_db(|conn| pedigree::family_tree(conn, &quail_id, pedigree::TREE_GENERATIONS))
+            .map_err(|e| log::error!("Fehler beim Laden des Stammbaums: {}", e))
+            .ok()
+    });
+    let children = use_signal(|| {
+        database::with_db(|conn| pedigree::offspring(conn, &quail_id)).unwrap_or_default()
+    });
+
+    let Some(tree) = tree() else {
+        return rsx! {};
+    };
+
+    rsx! {
+        div { style: "margin-top: 24px;",
+            h3 { style: "margin: 0 0 12px 0; font-size: 18px; color: #333; font-weight: 600;",
+                "🌳 "
+                {t!("pedigree-title")}
+            }
+            div { style: "display: flex; gap: 8px; align-items: stretch;",
+                for (generation , column) in generations(&tree).into_iter().enumerate() {
+                    div {
+                        key: "{generation}",
+                        style: "flex: 1; display: flex; flex-direction: column; justify-content: space-around; gap: 8px; min-width: 0;",
+                        for (index , quail) in column.into_iter().enumerate() {
+                            TreeNode { key: "{index}", quail, on_navigate }
+                        }
+                    }
+                }
+            }
+
+            h3 { style: "margin: 20px 0 12px 0; font-size: 18px; color: #333; font-weight: 600;",
+                "🐣 "
+                {t!("pedigree-offspring-title")}
+            }
+            if children().is_empty() {
+                div { style: "padding: 16px; text-align: center; background: #f5f5f5; border-radius: 8px; color: #999; font-size: 14px;",
+                    {t!("pedigree-offspring-empty")}
+                }
+            } else {
+                div { style: "display: flex; flex-wrap: wrap; gap: 8px;",
+                    for child in children() {
+                        span {
+                            key: "{child.uuid}",
+                            style: "padding: 6px 14px; background: #e8f4f8; border-radius: 16px; font-size: 13px; color: #0066cc; cursor: pointer;",
+                            onclick: {
+                                let id = child.uuid.to_string();
+                                move |_| on_navigate.call(Screen::ProfileDetail(id.clone()))
+                            },
+                            "{child.name}"
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
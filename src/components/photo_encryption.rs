@@ -0,0 +1,217 @@
+use crate::database;
+use crate::services::photo_crypto::{self, PhotoKey};
+use crate::services::sync_backend::ConfiguredBackend;
+use crate::services::sync_service;
+use dioxus::prelude::*;
+use dioxus_i18n::t;
+
+/// Einstellungen zur clientseitigen Verschlüsselung (Feature `encryption`)
+///
+/// Erzeugt, importiert, leitet aus einer Passphrase ab, zeigt (Export) und
+/// entfernt den lokalen Schlüssel für Fotos, Op-Dateien und Snapshots. Die
+/// Passphrase braucht einen eingerichteten Sync, weil Salt und
+/// Schlüsselprüfung im Sync-Ordner liegen.
+#[component]
+pub fn PhotoEncryptionCard() -> Element {
+    let mut key_present = use_signal(|| {
+        database::with_db(photo_crypto::load_key)
+            .ok()
+            .flatten()
+            .is_some()
+    });
+    let mut accept_plaintext =
+        use_signal(|| database::with_db(photo_crypto::accepts_plaintext).unwrap_or(false));
+    let mut deriving = use_signal(|| false);
+    let mut exported = use_signal(|| None::<String>);
+    let mut import_text = use_signal(String::new);
+    let mut passphrase = use_signal(String::new);
+    let mut confirm_remove = use_signal(|| false);
+    let mut message = use_signal(String::new);
+
+    let mut store = move |key: PhotoKey| {
+        let result = database::with_db(|conn| photo_crypto::store_key(conn, &key));
+        match result {
+            Ok(()) => {
+                key_present.set(true);
+                accept_plaintext.set(false);
+                import_text.set(String::new());
+                passphrase.set(String::new());
+                message.set(format!("✅ {}", t!("encryption-enabled")));
+            }
+            Err(e) => message.set(format!("❌ {}: {}", t!("error-save-failed"), e)),
+        }
+    };
+
+    rsx! {
+        div { style: "margin-top: 16px; padding: 12px; background: #f5f5f5; border-radius: 8px;",
+            label { style: "display: block; margin-bottom: 4px; font-weight: 600; font-size: 14px;",
+                "🔐 "
+                {t!("encryption-title")}
+            }
+            if key_present() {
+                p { style: "margin: 0 0 8px 0; font-size: 12px; color: #2e7d32;",
+                    {t!("encryption-on-hint")}
+                }
+                if let Some(text) = exported() {
+                    textarea {
+                        readonly: true,
+                        value: "{text}",
+                        style: "width: 100%; padding: 8px; font-family: monospace; font-size: 12px; border: 1px solid #ccc; border-radius: 4px;",
+                    }
+                    p { style: "margin: 4px 0 8px 0; font-size: 12px; color: #856404;",
+                        {t!("encryption-export-hint")}
+                    }
+                } else {
+                    button {
+                        style: "width: 100%; padding: 10px; margin-bottom: 8px; font-size: 14px;",
+                        onclick: move |_| {
+                            let key = database::with_db(photo_crypto::load_key);
+                            match key {
+                                Ok(Some(key)) => exported.set(Some(key.export())),
+                                Ok(None) => key_present.set(false),
+                                Err(e) => message.set(format!("❌ {}", e)),
+                            }
+                        },
+                        {t!("encryption-show-key")}
+                    }
+                }
+                label { style: "display: flex; align-items: center; gap: 8px; margin-bottom: 4px; font-size: 14px;",
+                    input {
+                        r#type: "checkbox",
+                        checked: accept_plaintext(),
+                        onchange: move |e| {
+                            let accept = e.checked();
+                            match database::with_db(|conn| photo_crypto::set_accept_plaintext(conn, accept)) {
+                                Ok(()) => accept_plaintext.set(accept),
+                                Err(e) => message.set(format!("❌ {}: {}", t!("error-save-failed"), e)),
+                            }
+                        },
+                    }
+                    {t!("encryption-accept-plaintext")}
+                }
+                p { style: "margin: 0 0 8px 0; font-size: 12px; color: #666;",
+                    {t!("encryption-accept-plaintext-hint")}
+                }
+                if confirm_remove() {
+                    p { style: "margin: 0 0 8px 0; padding: 8px; background: #fff3cd; border-radius: 4px; font-size: 12px; color: #856404;",
+                        "⚠️ "
+                        {t!("encryption-disable-confirm")}
+                    }
+                    div { style: "display: flex; gap: 8px;",
+                        button {
+                            class: "btn-danger",
+                            style: "flex: 1; padding: 10px; font-size: 14px;",
+                            onclick: move |_| {
+                                match database::with_db(photo_crypto::remove_key) {
+                                    Ok(()) => {
+                                        key_present.set(false);
+                                        exported.set(None);
+                                        message.set(String::new());
+                                    }
+                                    Err(e) => message.set(format!("❌ {}", e)),
+                                }
+                                confirm_remove.set(false);
+                            },
+                            {t!("encryption-disable")}
+                        }
+                        button {
+                            style: "flex: 1; padding: 10px; background: #e0e0e0; color: #666; font-size: 14px;",
+                            onclick: move |_| confirm_remove.set(false),
+                            {t!("action-cancel")}
+                        }
+                    }
+                } else {
+                    button {
+                        style: "width: 100%; padding: 10px; background: #ffe6e6; color: #cc0000; font-size: 14px; border: 1px solid #ffcccc;",
+                        onclick: move |_| confirm_remove.set(true),
+                        {t!("encryption-disable")}
+                    }
+                }
+            } else {
+                p { style: "margin: 0 0 8px 0; font-size: 12px; color: #666;",
+                    {t!("encryption-off-hint")}
+                }
+                button {
+                    class: "btn-primary",
+                    style: "width: 100%; padding: 10px; margin-bottom: 8px; font-size: 14px;",
+                    onclick: move |_| match PhotoKey::generate() {
+                        Ok(key) => store(key),
+                        Err(e) => message.set(format!("❌ {}", e)),
+                    },
+                    {t!("encryption-generate")}
+                }
+                div { style: "display: flex; gap: 8px;",
+                    input {
+                        r#type: "text",
+                        value: "{import_text}",
+                        placeholder: "stb-photo-key-1:…",
+                        style: "flex: 1; padding: 10px; font-size: 14px; border: 1px solid #ccc; border-radius: 4px;",
+                        oninput: move |e| import_text.set(e.value()),
+                    }
+                    button {
+                        style: "padding: 10px; font-size: 14px;",
+                        disabled: import_text().trim().is_empty(),
+                        onclick: move |_| match PhotoKey::import(&import_text()) {
+                            Ok(key) => store(key),
+                            Err(e) => message.set(format!("❌ {}", e)),
+                        },
+                        {t!("encryption-import")}
+                    }
+                }
+                div { style: "display: flex; gap: 8px; margin-top: 8px;",
+                    input {
+                        r#type: "password",
+                        value: "{passphrase}",
+                        placeholder: t!("encryption-passphrase"),
+                        style: "flex: 1; padding: 10px; font-size: 14px; border: 1px solid #ccc; border-radius: 4px;",
+                        oninput: move |e| passphrase.set(e.value()),
+                    }
+                    button {
+                        style: "padding: 10px; font-size: 14px;",
+                        disabled: deriving() || passphrase().trim().chars().count() < photo_crypto::MIN_PASSPHRASE_LEN,
+                        onclick: move |_| {
+                            let settings = match database::with_db(sync_service::load_sync_settings) {
+                                Ok(Some(settings)) => settings,
+                                Ok(None) => {
+                                    message.set(format!("❌ {}", t!("encryption-passphrase-needs-sync")));
+                                    return;
+                                }
+                                Err(e) => {
+                                    message.set(format!("❌ {}", e));
+                                    return;
+                                }
+                            };
+                            let passphrase = passphrase();
+                            deriving.set(true);
+                            spawn(async move {
+                                let result = match ConfiguredBackend::from_settings(&settings) {
+                                    Ok(client) => {
+                                        photo_crypto::key_for_sync_folder(
+                                                &client,
+                                                settings.remote_path.trim_end_matches('/'),
+                                                &passphrase,
+                                            )
+                                            .await
+                                    }
+                                    Err(e) => Err(e),
+                                };
+                                match result {
+                                    Ok(key) => store(key),
+                                    Err(e) => message.set(format!("❌ {}", e)),
+                                }
+                                deriving.set(false);
+                            });
+                        },
+                        {t!("encryption-from-passphrase")}
+                    }
+                }
+                p { style: "margin: 4px 0 0 0; font-size: 12px; color: #666;",
+                    {t!("encryption-passphrase-hint", min: photo_crypto::MIN_PASSPHRASE_LEN)}
+                }
+            }
+            if !message().is_empty() {
+                p { style: "margin: 8px 0 0 0; font-size: 12px;", "{message}" }
+            }
+        }
+    }
+}
@@ -0,0 +1,89 @@
+use crate::models::photo::PhotoCollection;
+#[cfg(feature = "desktop")]
+use crate::{database, services::photo_service};
+use dioxus::prelude::*;
+#[cfg(feature = "desktop")]
+use dioxus_i18n::t;
+#[cfg(feature = "desktop")]
+use stalltagebuch_services::image_processing;
+
+/// Einfügen-Knopf für die Fotogalerien; ohne Feature `desktop` leer
+pub fn paste_image_button(
+    collection: PhotoCollection,
+    on_added: EventHandler<()>,
+    on_error: EventHandler<String>,
+) -> Element {
+    #[cfg(feature = "desktop")]
+    {
+        rsx! {
+            PasteImageButton { collection, on_added, on_error }
+        }
+    }
+    #[cfg(not(feature = "desktop"))]
+    {
+        let _ = (collection, on_added, on_error);
+        rsx! {}
+    }
+}
+
+/// Knopf zum Einfügen eines Bildes aus der Zwischenablage (Feature `desktop`)
+///
+/// Praktisch für Screenshots (z.B. Tierarztbefunde) oder Bilder aus
+/// Messengern. Nach dem Import wird `on_added` aufgerufen, damit die
+/// Galerie neu geladen werden kann.
+#[cfg(feature = "desktop")]
+#[component]
+pub fn PasteImageButton(
+    collection: PhotoCollection,
+    on_added: EventHandler<()>,
+    on_error: EventHandler<String>,
+) -> Element {
+    let mut pasting = use_signal(|| false);
+
+    rsx! {
+        button {
+            style: "flex:1; padding:10px; background:rgba(0,0,0,0.6); color:white; border-radius:8px;",
+            disabled: pasting(),
+            onclick: move |e| {
+                e.stop_propagation();
+                pasting.set(true);
+                spawn(async move {
+                    let result = match read_clipboard_image() {
+                        Ok(bytes) => match database::connection() {
+                            Ok(conn) => photo_service::add_photo_to_collection(&conn, collection, bytes)
+                                .await
+                                .map_err(|e| e.to_string()),
+                            Err(e) => Err(e.to_string()),
+                        },
+                        Err(e) => Err(e),
+                    };
+                    match result {
+                        Ok(_) => on_added.call(()),
+                        Err(e) => on_error.call(format!("{}: {}", t!("error-paste-failed"), e)),
+                    }
+                    pasting.set(false);
+                });
+            },
+            if pasting() {
+                "⏳"
+            } else {
+                "📋 "
+                {t!("action-paste-image")}
+            }
+        }
+    }
+}
+
+/// Liest das Bild aus der Zwischenablage als JPEG
+#[cfg(feature = "desktop")]
+fn read_clipboard_image() -> Result<Vec<u8>, String> {
+    let image = arboard::Clipboard::new()
+        .and_then(|mut clipboard| clipboard.get_image())
+        .map_err(|_| t!("error-clipboard-no-image"))?;
+    image_processing::encode_rgba_jpeg(
+        image.width as u32,
+        image.height as u32,
+        image.bytes.into_owned(),
+    )
+    .map_err(|e| e.to_string())
+}
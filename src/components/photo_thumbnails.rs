@@ -0,0 +1,50 @@
+use crate::{
+    database,
+    models::photo::{PhotoResult, PhotoSize},
+    services::{download_queue::DownloadRequest, photo_service},
+};
+use dioxus_gallery_components::GalleryItemState;
+
+/// Galerie-Zustand des Vorschaubilds eines Fotos; fehlt die Datei, wird
+/// der Download angestoßen und `Loading` geliefert
+///
+/// Mit `retry` bekommt ein zu oft fehlgeschlagener Download neue Versuche
+/// (Wiederholen-Knopf in der Galerie).
+pub async fn thumbnail_state(
+    photo_id: &str,
+    request: DownloadRequest,
+    retry: bool,
+) -> GalleryItemState {
+    let Ok(photo_uuid) = uuid::Uuid::parse_str(photo_id) else {
+        log::error!("Invalid photo UUID: {}", photo_id);
+        return GalleryItemState::Error(format!("Invalid photo UUID: {}", photo_id));
+    };
+    let conn = match database::connection() {
+        Ok(conn) => conn,
+        Err(e) => return GalleryItemState::Error(e.to_string()),
+    };
+    if retry {
+        if let Err(e) = photo_service::reset_download_retries(&conn, &photo_uuid) {
+            log::warn!("Failed to reset retries of photo {}: {}", photo_id, e);
+        }
+    }
+    match photo_service::get_photo_url_with_download(&conn, &photo_uuid, PhotoSize::SMALL, request)
+        .await
+    {
+        Ok(PhotoResult::Available(url)) => GalleryItemState::Ready(url),
+        Ok(PhotoResult::Downloading) => GalleryItemState::Loading,
+        Ok(PhotoResult::Failed(err, retry_count)) => {
+            log::warn!(
+                "Photo {} download failed: {} (retry count: {})",
+                photo_id,
+                err,
+                retry_count
+            );
+            GalleryItemState::Error(err)
+        }
+        Err(e) => {
+            log::error!("Failed to load photo {}: {}", photo_id, e);
+            GalleryItemState::Error(e.to_string())
+        }
+    }
+}
@@ -0,0 +1,44 @@
+use crate::{database, services::photo_service};
+use dioxus::prelude::*;
+use dioxus_i18n::t;
+
+/// Hinweis nach dem Löschen eines Fotos mit Rückgängig-Knopf
+///
+/// Gelöschte Fotos landen im Papierkorb; `trashed` enthält das zuletzt
+/// gelöschte Foto. Nach dem Wiederherstellen wird `on_restored` aufgerufen,
+/// damit die Galerie neu geladen werden kann.
+#[component]
+pub fn TrashUndoBanner(
+    trashed: Signal<Option<uuid::Uuid>>,
+    on_restored: EventHandler<()>,
+) -> Element {
+    let Some(photo_uuid) = trashed() else {
+        return rsx! {};
+    };
+
+    rsx! {
+        div { style: "display: flex; align-items: center; gap: 12px; margin-top: 12px; padding: 10px 12px; background: #333; color: white; border-radius: 8px; font-size: 14px;",
+            span { style: "flex: 1;",
+                "🗑️ "
+                {t!("photo-trashed", days : photo_service::TRASH_RETENTION_DAYS)}
+            }
+            button {
+                style: "padding: 6px 12px; background: transparent; color: #8cc8ff; font-size: 14px; font-weight: 600; border: none;",
+                onclick: move |_| {
+                    spawn(async move {
+                        if let Ok(conn) = database::connection() {
+                            match photo_service::restore_photo(&conn, &photo_uuid).await {
+                                Ok(_) => {
+                                    trashed.set(None);
+                                    on_restored.call(());
+                                }
+                                Err(e) => log::error!("Failed to restore photo {}: {}", photo_uuid, e),
+                            }
+                        }
+                    });
+                },
+                {t!("action-restore")}
+            }
+        }
+    }
+}
@@ -1,4 +1,5 @@
 use crate::{
+    components::{coops::CoopSelect, pedigree::ParentSelect},
     database,
     models::{Gender, Quail, RingColor},
     services, Screen,
@@ -12,6 +13,9 @@ pub fn AddProfileScreen(on_navigate: EventHandler<Screen>) -> Element {
     let mut name = use_signal(|| String::new());
     let mut gender = use_signal(|| "unknown".to_string());
     let mut ring_color = use_signal(|| String::new());
+    let mother = use_signal(|| None::<uuid::Uuid>);
+    let father = use_signal(|| None::<uuid::Uuid>);
+    let coop = use_signal(|| None::<uuid::Uuid>);
     let mut photo_path = use_signal(|| None::<PathBuf>);
     let mut uploading = use_signal(|| false);
     let mut error = use_signal(|| None::<String>);
@@ -46,9 +50,12 @@ pub fn AddProfileScreen(on_navigate: EventHandler<Screen>) -> Element {
         } else {
             Some(RingColor::from_str(ring_color_trimmed))
         };
+        quail.mother_id = mother();
+        quail.father_id = father();
+        quail.coop_id = coop();
 
         spawn(async move {
-            match database::init_database() {
+            match database::connection() {
                 Ok(conn) => {
                     match services::create_profile(&conn, &quail).await {
                         Ok(quail_id) => {
@@ -177,6 +184,14 @@ pub fn AddProfileScreen(on_navigate: EventHandler<Screen>) -> Element {
                     }
                 }
 
+                ParentSelect { quail_id: None, mother, father }
+
+                CoopSelect {
+                    label: t!("field-coop"),
+                    none_label: t!("coop-none"),
+                    coop,
+                }
+
                 div { style: "padding: 12px; background: #e3f2fd; border-radius: 8px; color: #0066cc; font-size: 13px; margin-bottom: 20px;",
                     "ℹ️ "
                     {t!("profile-add-info")}
@@ -224,14 +239,14 @@ pub fn AddProfileScreen(on_navigate: EventHandler<Screen>) -> Element {
                                 uploading.set(true);
                                 error.set(None);
                                 spawn(async move {
-                                    #[cfg(target_os = "android")]
+                                    #[cfg(any(target_os = "android", feature = "desktop"))]
                                     {
                                         match crate::camera::pick_image() {
                                             Ok(path) => photo_path.set(Some(path)),
                                             Err(e) => error.set(Some(format!("{}: {}", t!("error"), e))),
                                         }
                                     }
-                                    #[cfg(not(target_os = "android"))]
+                                    #[cfg(not(any(target_os = "android", feature = "desktop")))]
                                     {
                                         error.set(Some(t!("error-android-only")));
                                     }
@@ -1,9 +1,19 @@
+use crate::components::coops::coop_names;
+use crate::components::entity_history::{EntityHistory, HistoryTabs};
+use crate::components::pedigree::FamilyTree;
+use crate::components::photo_paste::paste_image_button;
+use crate::components::treatment::treatment_summary;
 use crate::database;
-use crate::image_processing;
+use crate::models::photo::{PhotoCollection, PhotoSize};
 use crate::models::{Quail, QuailEvent};
-use crate::services::{event_service, profile_service};
+use crate::services::download_queue::{DownloadRequest, DownloadScope};
+use crate::services::photo_edit::{CropRect, EditOps};
+use crate::services::{event_service, photo_assets, photo_edit, profile_service};
 use crate::Screen;
 use dioxus::prelude::*;
+use dioxus_gallery_components::{
+    CropRotate, CropRotateDialog, FullscreenViewer, GalleryItem, GalleryItemState, ViewerAction,
+};
 use dioxus_i18n::t;
 
 #[component]
@@ -14,19 +24,83 @@ pub fn ProfileDetailScreen(quail_id: String, on_navigate: EventHandler<Screen>)
     let mut photos = use_signal(|| Vec::<crate::models::Photo>::new());
     let mut current_photo_index = use_signal(|| 0usize);
     let mut show_fullscreen = use_signal(|| false);
+    let mut editing_photo = use_signal(|| false);
+    let mut saving_edit = use_signal(|| false);
+    let mut edit_error = use_signal(String::new);
     let mut uploading = use_signal(|| false);
     let mut upload_error = use_signal(|| String::new());
+    // (fertig, gesamt) während eines Galerie-Imports
+    let mut import_progress = use_signal(|| None::<(usize, usize)>);
+    let show_history = use_signal(|| false);
+    let coops = use_signal(coop_names);
+    // Letzter Tag der Fleisch-Wartezeit, solange sie läuft
+    let meat_withheld_until = use_memo(move || {
+        let today = chrono::Local::now().date_naive();
+        events()
+            .iter()
+            .filter_map(|e| Some(e.treatment.as_ref()?.meat_withheld_until(e.event_date)))
+            .max()
+            .filter(|until| *until >= today)
+    });
 
-    #[cfg(target_os = "android")]
+    #[cfg(any(target_os = "android", feature = "desktop"))]
     let quail_id_for_gallery = quail_id.clone();
     #[cfg(target_os = "android")]
     let quail_id_for_camera = quail_id.clone();
 
+    // Aktionen der Vollbild-Galerie (bekommen die ID des gezeigten Fotos)
+    let start_photo_edit = use_callback(move |_photo_id: String| {
+        edit_error.set(String::new());
+        editing_photo.set(true);
+    });
+    let quail_id_for_profile_photo = quail_id.clone();
+    let make_profile_photo = use_callback(move |photo_id: String| {
+        let (Ok(quail_uuid), Ok(photo_uuid)) = (
+            uuid::Uuid::parse_str(&quail_id_for_profile_photo),
+            uuid::Uuid::parse_str(&photo_id),
+        ) else {
+            return;
+        };
+        spawn(async move {
+            let result = match database::connection() {
+                Ok(conn) => {
+                    crate::services::photo_service::set_profile_photo(
+                        &conn,
+                        &quail_uuid,
+                        &photo_uuid,
+                    )
+                    .await
+                }
+                Err(e) => Err(e),
+            };
+            match result {
+                Ok(()) => {
+                    edit_error.set(String::new());
+                    if let Some(p) = profile.write().as_mut() {
+                        p.profile_photo = Some(photo_uuid);
+                    }
+                }
+                Err(e) => {
+                    log::error!("Failed to set profile photo: {}", e);
+                    edit_error.set(t!("error-profile-photo-failed", error: e.to_string()));
+                }
+            }
+        });
+    });
+
+    // Downloads dieses Bildschirms enden beim Verlassen
+    let download_scope = use_hook(DownloadScope::new);
+    use_drop(move || download_scope.cancel());
+
     // Retry failed downloads beim Mount
     use_effect(move || {
         spawn(async move {
-            if let Ok(conn) = database::init_database() {
-                if let Err(e) = crate::services::photo_service::retry_failed_downloads(&conn).await
+            if let Ok(conn) = database::connection() {
+                if let Err(e) = crate::services::photo_service::retry_failed_downloads(
+                    &conn,
+                    DownloadRequest::background(download_scope),
+                )
+                .await
                 {
                     log::warn!("Failed to retry photo downloads: {}", e);
                 }
@@ -37,21 +111,21 @@ pub fn ProfileDetailScreen(quail_id: String, on_navigate: EventHandler<Screen>)
     // Alle Bilder der Wachtel laden
     let quail_id_for_photos = quail_id.clone();
     use_effect(move || {
-        if let Ok(conn) = database::init_database() {
-            if let Ok(uuid) = uuid::Uuid::parse_str(&quail_id_for_photos) {
+        if let Ok(uuid) = uuid::Uuid::parse_str(&quail_id_for_photos) {
+            spawn(async move {
                 if let Ok(photo_list) =
-                    crate::services::photo_service::list_quail_photos(&conn, &uuid)
+                    crate::services::photo_service::load_quail_photos(uuid).await
                 {
                     photos.set(photo_list);
                 }
-            }
+            });
         }
     });
 
     // Profil und Events laden
     let quail_id_for_profile = quail_id.clone();
     use_effect(move || {
-        if let Ok(conn) = database::init_database() {
+        if let Ok(conn) = database::connection() {
             if let Ok(uuid) = uuid::Uuid::parse_str(&quail_id_for_profile) {
                 match profile_service::get_profile(&conn, &uuid) {
                     Ok(p) => {
@@ -112,49 +186,39 @@ pub fn ProfileDetailScreen(quail_id: String, on_navigate: EventHandler<Screen>)
                                     None
                                 };
                                 if let Some(profile_photo) = profile_photo_opt {
-                                    let path_to_use = profile_photo
-                                        .thumbnail_path
-                                        .clone()
-                                        .unwrap_or(profile_photo.path.clone());
-                                    match image_processing::image_path_to_data_url(&path_to_use) {
-                                        Ok(data_url) => rsx! {
-                                            img {
-                                                src: data_url,
-                                                alt: p.name.clone(),
-                                                style: "width:100%; height:100%; object-fit: cover;",
-                                            }
-                                            if photos().len() > 1 {
-                                                div { style: "position:absolute; bottom:8px; right:8px; background:rgba(0,0,0,0.7); color:white; padding:6px 12px; border-radius:16px; font-size:12px;",
-                                                    "📷 {photos().len()}"
-                                                }
+                                    let url = photo_assets::photo_asset_url(
+                                        &profile_photo.uuid,
+                                        PhotoSize::MEDIUM,
+                                    );
+                                    rsx! {
+                                        img {
+                                            src: url,
+                                            alt: p.name.clone(),
+                                            style: "width:100%; height:100%; object-fit: cover;",
+                                        }
+                                        if photos().len() > 1 {
+                                            div { style: "position:absolute; bottom:8px; right:8px; background:rgba(0,0,0,0.7); color:white; padding:6px 12px; border-radius:16px; font-size:12px;",
+                                                "📷 {photos().len()}"
                                             }
-                                        },
-                                        Err(_) => rsx! {
-                                            div { style: "font-size: 48px; color:#999;", "🐦" }
-                                        },
+                                        }
                                     }
                                 } else if !photos().is_empty() {
                                     let first_photo = &photos()[0];
-                                    let path_to_use = first_photo
-                                        .thumbnail_path
-                                        .clone()
-                                        .unwrap_or(first_photo.path.clone());
-                                    match image_processing::image_path_to_data_url(&path_to_use) {
-                                        Ok(data_url) => rsx! {
-                                            img {
-                                                src: data_url,
-                                                alt: p.name.clone(),
-                                                style: "width:100%; height:100%; object-fit: cover;",
-                                            }
-                                            if photos().len() > 1 {
-                                                div { style: "position:absolute; bottom:8px; right:8px; background:rgba(0,0,0,0.7); color:white; padding:6px 12px; border-radius:16px; font-size:12px;",
-                                                    "📷 {photos().len()}"
-                                                }
+                                    let url = photo_assets::photo_asset_url(
+                                        &first_photo.uuid,
+                                        PhotoSize::MEDIUM,
+                                    );
+                                    rsx! {
+                                        img {
+                                            src: url,
+                                            alt: p.name.clone(),
+                                            style: "width:100%; height:100%; object-fit: cover;",
+                                        }
+                                        if photos().len() > 1 {
+                                            div { style: "position:absolute; bottom:8px; right:8px; background:rgba(0,0,0,0.7); color:white; padding:6px 12px; border-radius:16px; font-size:12px;",
+                                                "📷 {photos().len()}"
                                             }
-                                        },
-                                        Err(_) => rsx! {
-                                            div { style: "font-size: 48px; color:#999;", "🐦" }
-                                        },
+                                        }
                                     }
                                 } else {
                                     rsx! {
@@ -173,41 +237,50 @@ pub fn ProfileDetailScreen(quail_id: String, on_navigate: EventHandler<Screen>)
                                     e.stop_propagation();
                                     uploading.set(true);
                                     upload_error.set(String::new());
-                                    #[cfg(target_os = "android")]
+                                    #[cfg(any(target_os = "android", feature = "desktop"))]
                                     let quail_id_clone = quail_id_for_gallery.clone();
                                 spawn(async move {
-                                    #[cfg(target_os = "android")]
+                                    #[cfg(any(target_os = "android", feature = "desktop"))]
                                     {
                                         match crate::camera::pick_images() {
                                             Ok(paths) => {
-                                                if let Ok(conn) = database::init_database() {
-                                                    let mut first = true;
-                                                    for pth in paths {
-                                                        let path_str = pth.to_string_lossy().to_string();
-                                                        let _is_profile = first && photos().is_empty();
-                                                        if let Ok(uuid) = uuid::Uuid::parse_str(&quail_id_clone) {
-                                                            match crate::services::photo_service::add_quail_photo(
-                                                                &conn,
-                                                                uuid,
-                                                                path_str,
-                                                                None, // Thumbnails werden im Service erstellt
-                                                            ).await {
-                                                                Ok(_) => {}
-                                                                Err(e) => {
-                                                                    upload_error.set(format!("Fehler beim Speichern: {}", e));
-                                                                    break;
+                                                if let (Ok(conn), Ok(uuid)) = (
+                                                    database::connection(),
+                                                    uuid::Uuid::parse_str(&quail_id_clone),
+                                                ) {
+                                                    let paths: Vec<String> = paths
+                                                        .iter()
+                                                        .map(|p| p.to_string_lossy().to_string())
+                                                        .collect();
+                                                    import_progress.set(Some((0, paths.len())));
+                                                    let results = crate::services::photo_service::import_quail_photos(
+                                                        &conn,
+                                                        uuid,
+                                                        paths,
+                                                        |progress| {
+                                                            import_progress.set(Some((progress.done, progress.total)));
+                                                            // Fertige Fotos sofort im Raster anzeigen
+                                                            if progress.result.is_ok() {
+                                                                if let Ok(photo_list) = crate::services::photo_service::list_quail_photos(
+                                                                    &conn,
+                                                                    &uuid,
+                                                                ) {
+                                                                    photos.set(photo_list);
                                                                 }
                                                             }
-                                                        }
-                                                        first = false;
-                                                    }
-                                                    if let Ok(uuid) = uuid::Uuid::parse_str(&quail_id_clone) {
-                                                        if let Ok(photo_list) = crate::services::photo_service::list_quail_photos(
-                                                            &conn,
-                                                            &uuid,
-                                                        ) {
-                                                            photos.set(photo_list);
-                                                        }
+                                                        },
+                                                    )
+                                                    .await;
+                                                    let failed: Vec<_> = results
+                                                        .iter()
+                                                        .filter_map(|p| p.result.as_ref().err())
+                                                        .collect();
+                                                    if let Some(first_error) = failed.first() {
+                                                        upload_error.set(format!(
+                                                            "{}: {}",
+                                                            t!("error-import-failed", failed: failed.len(), total: results.len()),
+                                                            first_error
+                                                        ));
                                                     }
                                                 }
                                             }
@@ -217,15 +290,18 @@ pub fn ProfileDetailScreen(quail_id: String, on_navigate: EventHandler<Screen>)
                                             }
                                         }
                                     }
-                                    #[cfg(not(target_os = "android"))]
+                                    #[cfg(not(any(target_os = "android", feature = "desktop")))]
                                     {
                                         upload_error.set(t!("error-multiselect-android-only"));
                                     }
+                                    import_progress.set(None);
                                     uploading.set(false);
                                 });
                                 }
                             },
-                            if uploading() {
+                            if let Some((done, total)) = import_progress() {
+                                "⏳ {done}/{total}"
+                            } else if uploading() {
                                 "⏳"
                             } else {
                                 "🖼️ "
@@ -248,7 +324,7 @@ pub fn ProfileDetailScreen(quail_id: String, on_navigate: EventHandler<Screen>)
                                     {
                                         match crate::camera::capture_photo() {
                                             Ok(path) => {
-                                                if let Ok(conn) = database::init_database() {
+                                                if let Ok(conn) = database::connection() {
                                                     let path_str = path.to_string_lossy().to_string();
                                                     if let Ok(uuid) = uuid::Uuid::parse_str(&quail_id_clone) {
                                                         match crate::services::photo_service::add_quail_photo(
@@ -296,6 +372,30 @@ pub fn ProfileDetailScreen(quail_id: String, on_navigate: EventHandler<Screen>)
                         }
                     }
 
+                    // Bild aus der Zwischenablage (Desktop)
+                    if cfg!(feature = "desktop") {
+                        div { style: "display:flex; margin-top:12px;",
+                            {
+                                let quail_uuid = p.uuid;
+                                paste_image_button(
+                                    PhotoCollection::Quail(quail_uuid),
+                                    EventHandler::new(move |_| {
+                                        upload_error.set(String::new());
+                                        if let Ok(conn) = database::connection() {
+                                            if let Ok(photo_list) = crate::services::photo_service::list_quail_photos(
+                                                &conn,
+                                                &quail_uuid,
+                                            ) {
+                                                photos.set(photo_list);
+                                            }
+                                        }
+                                    }),
+                                    EventHandler::new(move |e| upload_error.set(e)),
+                                )
+                            }
+                        }
+                    }
+
                     // Upload Error anzeigen falls vorhanden
                     if !upload_error().is_empty() {
                         div { style: "padding: 12px; background: #ffe6e6; border-radius: 8px; color: #cc0000; font-size: 14px; margin-top: 12px;",
@@ -316,6 +416,17 @@ pub fn ProfileDetailScreen(quail_id: String, on_navigate: EventHandler<Screen>)
                             span { style: "padding:6px 14px; background:#fff3e0; border-radius:16px; font-size:13px; color:#ff8c00;",
                                 "{p.gender.display_name()}"
                             }
+                            if let Some(coop) = p.coop_id.and_then(|id| coops().get(&id).cloned()) {
+                                span { style: "padding:6px 14px; background:#f3e5f5; border-radius:16px; font-size:13px; color:#7b1fa2;",
+                                    "🏠 {coop}"
+                                }
+                            }
+                            if let Some(until) = meat_withheld_until() {
+                                span { style: "padding:6px 14px; background:#fff3cd; border-radius:16px; font-size:13px; color:#856404;",
+                                    "💊 "
+                                    {t!("withdrawal-meat", date: until.format("%d.%m.").to_string())}
+                                }
+                            }
                             // Status Badge basierend auf letztem Event
                             if let Some(latest_event) = events().first() {
                                 match latest_event.event_type {
@@ -363,6 +474,12 @@ pub fn ProfileDetailScreen(quail_id: String, on_navigate: EventHandler<Screen>)
                                             {t!("status-died")}
                                         }
                                     },
+                                    crate::models::EventType::Treatment => rsx! {
+                                        span { style: "padding:6px 14px; background:#e3f2fd; border-radius:16px; font-size:13px; color:#1565c0;",
+                                            "💊 "
+                                            {t!("status-treatment")}
+                                        }
+                                    },
                                 }
                             }
                         }
@@ -379,73 +496,90 @@ pub fn ProfileDetailScreen(quail_id: String, on_navigate: EventHandler<Screen>)
                         }
                     }
 
-                    // Events Timeline
+                    FamilyTree { quail_id: p.uuid, on_navigate }
+
                     div { style: "margin-top:24px;",
-                        div { style: "display:flex; justify-content:space-between; align-items:center; margin-bottom:12px;",
-                            h3 { style: "margin:0; font-size:18px; color:#333; font-weight:600;",
-                                "📅 "
-                                {t!("events-timeline-title")}
-                            }
-                            button {
-                                style: "padding:8px 16px; background:#0066cc; color:white; border-radius:8px; font-size:14px; font-weight:500;",
-                                onclick: move |_| {
-                                    if let Some(p) = profile() {
-                                        on_navigate
-                                            .call(Screen::EventAdd {
-                                                quail_id: p.uuid.to_string(),
-                                                quail_name: p.name.clone(),
-                                            });
-                                    }
-                                },
-                                "+ "
-                                {t!("action-add-event")} // Add event
-                            }
-                        }
+                        HistoryTabs { show_history, content_label: format!("📅 {}", t!("events-timeline-title")) }
+                    }
 
-                        if events().is_empty() {
-                            div { style: "padding:24px; text-align:center; background:#f5f5f5; border-radius:8px; color:#999;",
-                                {t!("events-empty")} // No events available
+                    if show_history() {
+                        EntityHistory { entity_type: "quail", entity_id: quail_id.clone() }
+                    } else {
+                        // Events Timeline
+                        div {
+                            div { style: "display:flex; justify-content:space-between; align-items:center; margin-bottom:12px;",
+                                h3 { style: "margin:0; font-size:18px; color:#333; font-weight:600;",
+                                    "📅 "
+                                    {t!("events-timeline-title")}
+                                }
+                                button {
+                                    style: "padding:8px 16px; background:#0066cc; color:white; border-radius:8px; font-size:14px; font-weight:500;",
+                                    onclick: move |_| {
+                                        if let Some(p) = profile() {
+                                            on_navigate
+                                                .call(Screen::EventAdd {
+                                                    quail_id: p.uuid.to_string(),
+                                                    quail_name: p.name.clone(),
+                                                });
+                                        }
+                                    },
+                                    "+ "
+                                    {t!("action-add-event")} // Add event
+                                }
                             }
-                        } else {
-                            div { style: "display:flex; flex-direction:column; gap:12px;",
-                                for event in events() {
-                                    div {
-                                        key: "{event.uuid}",
-                                        style: "padding:14px; background:white; border:1px solid #e0e0e0; border-radius:8px; cursor:pointer;",
-                                        onclick: {
-                                            let quail_id_for_event = quail_id.clone();
-                                            move |_| {
-                                                on_navigate
-                                                    .call(Screen::EventEdit {
-                                                        event_id: event.uuid.to_string(),
-                                                        quail_id: quail_id_for_event.clone(),
-                                                    });
-                                            }
-                                        },
-                                        div { style: "display:flex; gap:10px; align-items:center; margin-bottom:8px;",
-                                            span { style: "font-size:20px;",
-                                                match event.event_type {
-                                                    crate::models::EventType::Born => "🐣",
-                                                    crate::models::EventType::Alive => "✅",
-                                                    crate::models::EventType::Sick => "🤒",
-                                                    crate::models::EventType::Healthy => "💪",
-                                                    crate::models::EventType::MarkedForSlaughter => "🥩",
-                                                    crate::models::EventType::Slaughtered => "🥩",
-                                                    crate::models::EventType::Died => "🪦",
+
+                            if events().is_empty() {
+                                div { style: "padding:24px; text-align:center; background:#f5f5f5; border-radius:8px; color:#999;",
+                                    {t!("events-empty")} // No events available
+                                }
+                            } else {
+                                div { style: "display:flex; flex-direction:column; gap:12px;",
+                                    for event in events() {
+                                        div {
+                                            key: "{event.uuid}",
+                                            style: "padding:14px; background:white; border:1px solid #e0e0e0; border-radius:8px; cursor:pointer;",
+                                            onclick: {
+                                                let quail_id_for_event = quail_id.clone();
+                                                move |_| {
+                                                    on_navigate
+                                                        .call(Screen::EventEdit {
+                                                            event_id: event.uuid.to_string(),
+                                                            quail_id: quail_id_for_event.clone(),
+                                                        });
                                                 }
-                                            }
-                                            div {
-                                                div { style: "font-size:14px; font-weight:600; color:#333;",
-                                                    "{event.event_type.display_name()}"
+                                            },
+                                            div { style: "display:flex; gap:10px; align-items:center; margin-bottom:8px;",
+                                                span { style: "font-size:20px;",
+                                                    match event.event_type {
+                                                        crate::models::EventType::Born => "🐣",
+                                                        crate::models::EventType::Alive => "✅",
+                                                        crate::models::EventType::Sick => "🤒",
+                                                        crate::models::EventType::Healthy => "💪",
+                                                        crate::models::EventType::MarkedForSlaughter => "🥩",
+                                                        crate::models::EventType::Slaughtered => "🥩",
+                                                        crate::models::EventType::Died => "🪦",
+                                                        crate::models::EventType::Treatment => "💊",
+                                                    }
                                                 }
-                                                div { style: "font-size:12px; color:#666;",
-                                                    {event.event_date.format("%d.%m.%Y").to_string()}
+                                                div {
+                                                    div { style: "font-size:14px; font-weight:600; color:#333;",
+                                                        "{event.event_type.display_name()}"
+                                                    }
+                                                    div { style: "font-size:12px; color:#666;",
+                                                        {event.event_date.format("%d.%m.%Y").to_string()}
+                                                    }
                                                 }
                                             }
-                                        }
-                                        if let Some(notes) = &event.notes {
-                                            div { style: "font-size:13px; color:#555; line-height:1.4; white-space:pre-wrap;",
-                                                "{notes}"
+                                            if let Some(treatment) = &event.treatment {
+                                                div { style: "font-size:13px; color:#555; margin-bottom:4px;",
+                                                    "💊 "
+                                                    {treatment_summary(treatment, event.event_date)}
+                                                }
+                                            }
+                                            if let Some(notes) = &event.notes {
+                                                div { style: "font-size:13px; color:#555; line-height:1.4; white-space:pre-wrap;",
+                                                    "{notes}"
+                                                }
                                             }
                                         }
                                     }
@@ -473,70 +607,114 @@ pub fn ProfileDetailScreen(quail_id: String, on_navigate: EventHandler<Screen>)
                 }
             }
 
-            // Vollbild-Galerie Overlay
-            if show_fullscreen() && !photos().is_empty() {
-                div {
-                    style: "position:fixed; top:0; left:0; right:0; bottom:0; background:rgba(0,0,0,0.95); z-index:9999; display:flex; flex-direction:column;",
-                    onclick: move |_| show_fullscreen.set(false),
-                    // Header
-                    div {
-                        style: "padding:16px; display:flex; justify-content:space-between; align-items:center;",
-                        onclick: move |e| e.stop_propagation(),
-                        div { style: "color:white; font-size:18px; font-weight:600;",
-                            "{current_photo_index() + 1} / {photos().len()}"
-                        }
-                        button {
-                            style: "background:rgba(255,255,255,0.2); color:white; padding:8px 16px; border-radius:8px; font-size:16px;",
-                            onclick: move |_| show_fullscreen.set(false),
-                            "✕ "
-                            {t!("action-close")}
-                        }
+            // Vollbild-Galerie
+            if show_fullscreen() && current_photo_index() < photos().len() {
+                {
+                    let items: Vec<GalleryItem> = photos()
+                        .iter()
+                        .map(|photo| GalleryItem {
+                            id: photo.uuid.to_string(),
+                            state: GalleryItemState::Ready(photo_assets::photo_asset_url(
+                                &photo.uuid,
+                                PhotoSize::LARGE,
+                            )),
+                            caption: photo.caption.clone(),
+                        })
+                        .collect();
+                    let current_item = items[current_photo_index()].clone();
+                    let is_profile_photo = profile()
+                        .and_then(|p| p.profile_photo)
+                        .is_some_and(|uuid| uuid.to_string() == current_item.id);
+                    let mut actions = vec![ViewerAction {
+                        label: format!("✂️ {}", t!("action-crop-rotate")),
+                        on_click: start_photo_edit,
+                    }];
+                    if !is_profile_photo {
+                        actions.push(ViewerAction {
+                            label: format!("⭐ {}", t!("action-set-profile-photo")),
+                            on_click: make_profile_photo,
+                        });
                     }
-                    // Hauptbild
-                    div {
-                        style: "flex:1; display:flex; align-items:center; justify-content:center; padding:16px;",
-                        onclick: move |e| e.stop_propagation(),
-                        {
-                            let current_photo = &photos()[current_photo_index()];
-                            let full_path = current_photo.path.clone();
-                            match image_processing::image_path_to_data_url(&full_path) {
-                                Ok(data_url) => rsx! {
-                                    img {
-                                        src: data_url,
-                                        style: "max-width:100%; max-height:100%; object-fit:contain;",
-                                    }
-                                },
-                                Err(_) => rsx! {
-                                    div { style: "color:white; font-size:48px;", "⚠️" }
-                                },
+                    rsx! {
+                        FullscreenViewer {
+                            current_item,
+                            all_items: items,
+                            allow_delete: false,
+                            actions,
+                            on_close: move |_| show_fullscreen.set(false),
+                            on_navigate_prev: move |_| {
+                                current_photo_index.set(current_photo_index().saturating_sub(1));
+                            },
+                            on_navigate_next: move |_| {
+                                if current_photo_index() + 1 < photos().len() {
+                                    current_photo_index.set(current_photo_index() + 1);
+                                }
+                            },
+                        }
+                        if !edit_error().is_empty() {
+                            div { style: "position:fixed; top:80px; left:16px; right:16px; z-index:1001; background:#fee; color:#c33; padding:8px 12px; border-radius:8px; font-size:14px;",
+                                "⚠️ "
+                                {edit_error}
                             }
                         }
                     }
-                    // Navigation
-                    if photos().len() > 1 {
-                        div {
-                            style: "padding:16px; display:flex; gap:12px; justify-content:center;",
-                            onclick: move |e| e.stop_propagation(),
-                            button {
-                                style: "background:rgba(255,255,255,0.3); color:white; padding:12px 24px; border-radius:8px; font-size:18px; font-weight:600;",
-                                disabled: current_photo_index() == 0,
-                                onclick: move |_| {
-                                    if current_photo_index() > 0 {
-                                        current_photo_index.set(current_photo_index() - 1);
-                                    }
-                                },
-                                "◀"
-                            }
-                            button {
-                                style: "background:rgba(255,255,255,0.3); color:white; padding:12px 24px; border-radius:8px; font-size:18px; font-weight:600;",
-                                disabled: current_photo_index() >= photos().len() - 1,
-                                onclick: move |_| {
-                                    if current_photo_index() < photos().len() - 1 {
-                                        current_photo_index.set(current_photo_index() + 1);
+                }
+            }
+
+            // Zuschneiden/Drehen des aktuellen Fotos
+            if editing_photo() && current_photo_index() < photos().len() {
+                {
+                    let photo = photos()[current_photo_index()].clone();
+                    let photo_uuid = photo.uuid;
+                    let quail_uuid = photo.quail_id.unwrap_or_default();
+                    rsx! {
+                        CropRotateDialog {
+                            src: photo_assets::photo_asset_url(&photo_uuid, PhotoSize::LARGE),
+                            apply_label: t!("action-save"),
+                            cancel_label: t!("action-cancel"),
+                            busy: saving_edit(),
+                            on_cancel: move |_| editing_photo.set(false),
+                            on_apply: move |edit: CropRotate| {
+                                let ops = EditOps {
+                                    rotate: edit.rotate,
+                                    crop: edit.is_cropped().then_some(CropRect {
+                                        left: edit.left,
+                                        top: edit.top,
+                                        width: 1.0 - edit.left - edit.right,
+                                        height: 1.0 - edit.top - edit.bottom,
+                                    }),
+                                };
+                                saving_edit.set(true);
+                                spawn(async move {
+                                    let result = match database::connection() {
+                                        Ok(conn) => photo_edit::edit_photo(&conn, &photo_uuid, ops)
+                                            .await
+                                            .and_then(|_| {
+                                                crate::services::photo_service::list_quail_photos(
+                                                    &conn,
+                                                    &quail_uuid,
+                                                )
+                                            }),
+                                        Err(e) => Err(e),
+                                    };
+                                    match result {
+                                        Ok(photo_list) => {
+                                            // Die neue Fassung übernimmt die Position der alten
+                                            if current_photo_index() >= photo_list.len() {
+                                                current_photo_index.set(photo_list.len().saturating_sub(1));
+                                            }
+                                            photos.set(photo_list);
+                                            editing_photo.set(false);
+                                        }
+                                        Err(e) => {
+                                            log::error!("Failed to edit photo: {}", e);
+                                            edit_error.set(t!("error-photo-edit-failed", error: e.to_string()));
+                                            editing_photo.set(false);
+                                        }
                                     }
-                                },
-                                "▶"
-                            }
+                                    saving_edit.set(false);
+                                });
+                            },
                         }
                     }
                 }
@@ -1,20 +1,60 @@
 use crate::{
+    components::{
+        coops::CoopSelect, pedigree::ParentSelect, photo_thumbnails::thumbnail_state,
+        TrashUndoBanner,
+    },
     database,
-    models::{Gender, Quail, RingColor},
-    services, Screen,
+    models::{photo::PhotoCollection, Gender, Quail, RingColor},
+    services::{
+        self,
+        download_queue::{DownloadRequest, DownloadScope},
+    },
+    Screen,
 };
 use dioxus::prelude::*;
-use dioxus_gallery_components::{Gallery, GalleryConfig, GalleryItem};
+use dioxus_gallery_components::{GalleryConfig, GalleryItem, GalleryItemState, VirtualGallery};
 use dioxus_i18n::t;
 
+/// Fotos pro nachgeladener Seite der Galerie
+const PHOTO_PAGE_SIZE: usize = 60;
+
+/// Lädt mindestens die ersten `count` Fotos (aber wenigstens eine Seite) und
+/// die Gesamtzahl der Fotos der Wachtel
+fn load_photo_pages(
+    quail_uuid: &uuid::Uuid,
+    count: usize,
+) -> Result<(Vec<crate::models::Photo>, usize), crate::error::AppError> {
+    let conn = database::connection()?;
+    let collection = PhotoCollection::Quail(*quail_uuid);
+    let photos = services::photo_service::list_collection_photos_page(
+        &conn,
+        collection,
+        0,
+        count.max(PHOTO_PAGE_SIZE),
+    )?;
+    let total = services::photo_service::count_collection_photos(&conn, collection)?;
+    Ok((photos, total))
+}
+
 #[component]
 pub fn ProfileEditScreen(quail_id: String, on_navigate: EventHandler<Screen>) -> Element {
     let mut profile = use_signal(|| None::<Quail>);
     let mut name = use_signal(|| String::new());
     let mut gender = use_signal(|| "unknown".to_string());
     let mut ring_color = use_signal(|| String::new());
+    let mut mother = use_signal(|| None::<uuid::Uuid>);
+    let mut father = use_signal(|| None::<uuid::Uuid>);
+    let mut coop = use_signal(|| None::<uuid::Uuid>);
+    // Bisher geladene Seiten der Fotos und die Gesamtzahl
     let mut photos = use_signal(|| Vec::<crate::models::Photo>::new());
+    let mut photo_total = use_signal(|| 0usize);
+    let mut loading_more_photos = use_signal(|| false);
+    // Vorschaubilder der bereits sichtbar gewordenen Fotos
+    let mut photo_states = use_signal(std::collections::HashMap::<String, GalleryItemState>::new);
+    let download_scope = use_hook(DownloadScope::new);
+    use_drop(move || download_scope.cancel());
     let mut selected_profile_photo_id = use_signal(|| None::<String>);
+    let mut last_trashed = use_signal(|| None::<uuid::Uuid>);
     let mut show_delete_confirm = use_signal(|| false);
     let mut error = use_signal(|| String::new());
     let mut success = use_signal(|| false);
@@ -23,7 +63,7 @@ pub fn ProfileEditScreen(quail_id: String, on_navigate: EventHandler<Screen>) ->
     // Load profile and photos
     let quail_id_for_load = quail_id.clone();
     use_effect(move || {
-        match database::init_database() {
+        match database::connection() {
             Ok(conn) => {
                 if let Ok(uuid) = uuid::Uuid::parse_str(&quail_id_for_load) {
                     match services::profile_service::get_profile(&conn, &uuid) {
@@ -33,6 +73,9 @@ pub fn ProfileEditScreen(quail_id: String, on_navigate: EventHandler<Screen>) ->
                             if let Some(rc) = &p.ring_color {
                                 ring_color.set(rc.as_str().to_string());
                             }
+                            mother.set(p.mother_id);
+                            father.set(p.father_id);
+                            coop.set(p.coop_id);
                             profile.set(Some(p));
                         }
                         Err(e) => {
@@ -40,9 +83,9 @@ pub fn ProfileEditScreen(quail_id: String, on_navigate: EventHandler<Screen>) ->
                             // Failed to load
                         }
                     }
-                    // Lade alle Fotos
-                    match crate::services::photo_service::list_quail_photos(&conn, &uuid) {
-                        Ok(photo_list) => {
+                    // Lade die erste Seite der Fotos
+                    match load_photo_pages(&uuid, 0) {
+                        Ok((photo_list, total)) => {
                             // Finde aktuelles Profilbild
                             if let Ok(Some(profile_photo)) =
                                 crate::services::photo_service::get_profile_photo(&conn, &uuid)
@@ -50,6 +93,7 @@ pub fn ProfileEditScreen(quail_id: String, on_navigate: EventHandler<Screen>) ->
                                 selected_profile_photo_id.set(Some(profile_photo.uuid.to_string()));
                             }
                             photos.set(photo_list);
+                            photo_total.set(total);
                         }
                         Err(e) => {
                             log::error!("{}: {}", t!("error-load-photos-failed"), e);
@@ -89,12 +133,15 @@ pub fn ProfileEditScreen(quail_id: String, on_navigate: EventHandler<Screen>) ->
             } else {
                 Some(RingColor::from_str(ring_color_trimmed))
             };
+            updated_profile.mother_id = mother();
+            updated_profile.father_id = father();
+            updated_profile.coop_id = coop();
 
             let updated_profile_clone = updated_profile.clone();
             let quail_id_clone = quail_id_for_submit.clone();
             let selected_photo = selected_profile_photo_id();
             spawn(async move {
-                match database::init_database() {
+                match database::connection() {
                     Ok(conn) => {
                         match services::profile_service::update_profile(
                             &conn,
@@ -141,7 +188,7 @@ pub fn ProfileEditScreen(quail_id: String, on_navigate: EventHandler<Screen>) ->
     let handle_delete = move || {
         let quail_id_clone = quail_id_for_delete.clone();
         spawn(async move {
-            match database::init_database() {
+            match database::connection() {
                 Ok(conn) => {
                     if let Ok(uuid) = uuid::Uuid::parse_str(&quail_id_clone) {
                         match services::profile_service::delete_profile(&conn, &uuid).await {
@@ -164,6 +211,7 @@ pub fn ProfileEditScreen(quail_id: String, on_navigate: EventHandler<Screen>) ->
 
     let quail_id_for_back = quail_id.clone();
     let quail_id_for_photo_delete = quail_id.clone();
+    let quail_id_for_photo_restore = quail_id.clone();
     let quail_id_for_cancel = quail_id.clone();
 
     rsx! {
@@ -254,6 +302,15 @@ pub fn ProfileEditScreen(quail_id: String, on_navigate: EventHandler<Screen>) ->
                     }
                 }
 
+                // Eltern
+                ParentSelect { quail_id: uuid::Uuid::parse_str(&quail_id).ok(), mother, father }
+
+                CoopSelect {
+                    label: t!("field-coop"),
+                    none_label: t!("coop-none"),
+                    coop,
+                }
+
                 div { style: "padding: 12px; background: #e3f2fd; border-radius: 8px; color: #0066cc; font-size: 13px; margin-bottom: 20px;",
                     "ℹ️ "
                     {t!("info-photos-detail-view")}
@@ -262,26 +319,18 @@ pub fn ProfileEditScreen(quail_id: String, on_navigate: EventHandler<Screen>) ->
                 // Photo Gallery with Profile Selection
                 div { style: "margin-bottom: 24px;",
                     label { style: "display: block; margin-bottom: 8px; font-weight: 600; color: #333; font-size: 14px;",
-                        {format!("{} ({})", t!("field-photos"), photos().len())} // Photos count
+                        {format!("{} ({})", t!("field-photos"), photo_total())} // Photos count
                     }
 
                     {
                         let gallery_items: Vec<GalleryItem> = photos()
                             .iter()
-                            .filter_map(|photo| {
-                                let thumb_path = photo
-                                    .thumbnail_path
-                                    .clone()
-                                    .unwrap_or(photo.path.clone());
-                                match crate::image_processing::image_path_to_data_url(&thumb_path) {
-                                    Ok(data_url) => {
-                                        Some(GalleryItem {
-                                            id: photo.uuid.to_string(),
-                                            data_url,
-                                            caption: None,
-                                        })
-                                    }
-                                    Err(_) => None,
+                            .map(|photo| {
+                                let id = photo.uuid.to_string();
+                                GalleryItem {
+                                    state: photo_states().get(&id).cloned().unwrap_or_default(),
+                                    id,
+                                    caption: photo.caption.clone(),
                                 }
                             })
                             .collect();
@@ -289,15 +338,66 @@ pub fn ProfileEditScreen(quail_id: String, on_navigate: EventHandler<Screen>) ->
                             allow_delete: true,
                             allow_select: true,
                             selected_id: selected_profile_photo_id(),
+                            allow_caption_edit: true,
+                            ..Default::default()
                         };
                         rsx! {
-                            Gallery {
+                            VirtualGallery {
                                 items: gallery_items,
                                 config: gallery_config,
+                                on_request_item_data: move |ids: Vec<String>| {
+                                    spawn(async move {
+                                        for id in ids {
+                                            // Noch ladende Fotos werden beim nächsten Scrollen erneut angefragt
+                                            let state = thumbnail_state(
+                                                    &id,
+                                                    DownloadRequest::visible(download_scope),
+                                                    false,
+                                                )
+                                                .await;
+                                            photo_states.write().insert(id, state);
+                                        }
+                                    });
+                                },
+                                has_more: photos().len() < photo_total(),
+                                loading_more: loading_more_photos(),
+                                on_load_more: {
+                                    let quail_id = quail_id.clone();
+                                    move |_| {
+                                        let Ok(q_uuid) = uuid::Uuid::parse_str(&quail_id) else {
+                                            return;
+                                        };
+                                        loading_more_photos.set(true);
+                                        match database::with_db(|conn| {
+                                            services::photo_service::list_collection_photos_page(
+                                                conn,
+                                                PhotoCollection::Quail(q_uuid),
+                                                photos.peek().len(),
+                                                PHOTO_PAGE_SIZE,
+                                            )
+                                        }) {
+                                            Ok(page) => photos.write().extend(page),
+                                            Err(e) => log::error!("{}: {}", t!("error-load-photos-failed"), e),
+                                        }
+                                        loading_more_photos.set(false);
+                                    }
+                                },
+                                on_retry: move |id: String| {
+                                    photo_states.write().insert(id.clone(), GalleryItemState::Loading);
+                                    spawn(async move {
+                                        let state = thumbnail_state(
+                                                &id,
+                                                DownloadRequest::visible(download_scope),
+                                                true,
+                                            )
+                                            .await;
+                                        photo_states.write().insert(id, state);
+                                    });
+                                },
                                 on_delete: move |photo_id: String| {
                                     let qid = quail_id_for_photo_delete.clone();
                                     spawn(async move {
-                                        if let Ok(conn) = database::init_database() {
+                                        if let Ok(conn) = database::connection() {
                                             if let Ok(photo_uuid) = uuid::Uuid::parse_str(&photo_id) {
                                                 match crate::services::photo_service::delete_photo(
                                                         &conn,
@@ -306,12 +406,15 @@ pub fn ProfileEditScreen(quail_id: String, on_navigate: EventHandler<Screen>) ->
                                                     .await
                                                 {
                                                     Ok(_) => {
+                                                        last_trashed.set(Some(photo_uuid));
                                                         if let Ok(q_uuid) = uuid::Uuid::parse_str(&qid) {
-                                                            if let Ok(photo_list) = crate::services::photo_service::list_quail_photos(
-                                                                &conn,
+                                                            let loaded = photos.peek().len();
+                                                            if let Ok((photo_list, total)) = load_photo_pages(
                                                                 &q_uuid,
+                                                                loaded,
                                                             ) {
                                                                 photos.set(photo_list);
+                                                                photo_total.set(total);
                                                                 if selected_profile_photo_id().as_ref().map(|s| s.as_str())
                                                                     == Some(&photo_id)
                                                                 {
@@ -331,10 +434,57 @@ pub fn ProfileEditScreen(quail_id: String, on_navigate: EventHandler<Screen>) ->
                                 on_select: move |photo_id: String| {
                                     selected_profile_photo_id.set(Some(photo_id));
                                 },
+                                on_caption_change: move |(photo_id, caption): (String, String)| {
+                                    spawn(async move {
+                                        let Ok(photo_uuid) = uuid::Uuid::parse_str(&photo_id) else {
+                                            return;
+                                        };
+                                        let result = match database::connection() {
+                                            Ok(conn) => {
+                                                services::photo_service::set_photo_caption(
+                                                        &conn,
+                                                        &photo_uuid,
+                                                        Some(&caption),
+                                                    )
+                                                    .await
+                                            }
+                                            Err(e) => Err(e),
+                                        };
+                                        match result {
+                                            Ok(()) => {
+                                                let caption = Some(caption).filter(|c| !c.is_empty());
+                                                for photo in photos.write().iter_mut() {
+                                                    if photo.uuid == photo_uuid {
+                                                        photo.caption = caption.clone();
+                                                    }
+                                                }
+                                            }
+                                            Err(e) => {
+                                                error.set(t!("error-caption-save-failed", error: e.to_string()))
+                                            }
+                                        }
+                                    });
+                                },
                             }
                         }
                     }
 
+                    TrashUndoBanner {
+                        trashed: last_trashed,
+                        on_restored: move |_| {
+                            if let Ok(q_uuid) = uuid::Uuid::parse_str(&quail_id_for_photo_restore) {
+                                let loaded = photos.peek().len();
+                                if let Ok((photo_list, total)) = load_photo_pages(
+                                    &q_uuid,
+                                    loaded,
+                                ) {
+                                    photos.set(photo_list);
+                                    photo_total.set(total);
+                                }
+                            }
+                        },
+                    }
+
                     if !photos().is_empty() {
                         div { style: "margin-top: 12px; padding: 10px; background: #f9f9f9; border-radius: 6px; font-size: 12px; color: #666;",
                             {t!("info-tap-photo-to-mark")} // Tap a photo to mark it as profile photo.
@@ -1,8 +1,10 @@
+use crate::components::batch_actions::BatchActions;
+use crate::components::coops::CoopSelect;
 use crate::database;
 use crate::models::{Quail, RingColor};
 use crate::services;
+use crate::services::download_queue::{DownloadRequest, DownloadScope};
 use crate::Screen;
-use base64::Engine;
 use dioxus::prelude::*;
 use dioxus_i18n::t;
 
@@ -12,24 +14,63 @@ pub fn ProfileListScreen(on_navigate: EventHandler<Screen>) -> Element {
     let mut search_filter = use_signal(|| String::new());
     // Toggle zeigt "nur Tote" an; Standard (false) zeigt Lebende + Markierte
     let mut show_dead = use_signal(|| false);
+    // Nur Wachteln dieses Stalls, `None` zeigt alle
+    let coop_filter = use_signal(|| None::<uuid::Uuid>);
+    // Auswahlmodus für Sammelaktionen
+    let mut selecting = use_signal(|| false);
+    let mut selected = use_signal(Vec::<uuid::Uuid>::new);
+    let mut batch_message = use_signal(String::new);
+    let visible = move || -> Vec<Quail> {
+        profiles()
+            .into_iter()
+            .filter(|q| coop_filter().is_none_or(|coop| q.coop_id == Some(coop)))
+            .collect()
+    };
+
+    // Gewählte Wachteln, die Suche und Filter gerade anzeigen
+    let chosen = move || -> Vec<uuid::Uuid> {
+        visible()
+            .iter()
+            .map(|q| q.uuid)
+            .filter(|id| selected().contains(id))
+            .collect()
+    };
 
     // Load profiles
-    let mut load_profiles = move || match database::init_database() {
-        Ok(conn) => {
-            let search_value = search_filter();
-            let filter = if search_value.is_empty() {
-                None
-            } else {
-                Some(search_value.as_str())
-            };
+    let load_profiles = move || {
+        let search_value = search_filter();
+        let alive_only = !show_dead();
+        spawn(async move {
+            let filter = (!search_value.is_empty()).then(|| search_value.clone());
+            let result = database::with_db_async(move |conn| {
+                let list = services::profile_service::list_profiles_with_status(
+                    conn,
+                    filter.as_deref(),
+                    alive_only,
+                )?;
+                // Thumbnails der Detailansichten schon vorab laden
+                let photo_uuids: Vec<uuid::Uuid> = list
+                    .iter()
+                    .filter_map(|q| services::photo_service::list_quail_photos(conn, &q.uuid).ok())
+                    .flatten()
+                    .map(|photo| photo.uuid)
+                    .collect();
+                if let Err(e) = services::photo_prefetch::prefetch_thumbnails(conn, &photo_uuids) {
+                    log::warn!("Vorabladen der Thumbnails fehlgeschlagen: {}", e);
+                }
+                Ok(list)
+            })
+            .await;
 
-            match services::profile_service::list_profiles_with_status(&conn, filter, !show_dead())
-            {
+            // Inzwischen weitergetippt: neuere Suche gewinnt
+            if search_filter() != search_value || show_dead() == alive_only {
+                return;
+            }
+            match result {
                 Ok(list) => profiles.set(list),
                 Err(e) => log::error!("{}: {}", t!("error-load-profiles-failed"), e), // Failed to load profiles
             }
-        }
-        Err(e) => log::error!("DB-Fehler: {}", e),
+        });
     };
 
     // Load on mount
@@ -47,6 +88,23 @@ pub fn ProfileListScreen(on_navigate: EventHandler<Screen>) -> Element {
                     {t!("profile-list-title")}
                 }
                 div { style: "display: flex; gap: 8px; align-items: center;",
+                    // Toggle: mehrere Wachteln auswählen
+                    button {
+                        style: format!(
+                            "padding: 8px 10px; font-size: 16px; border-radius: 8px; {}",
+                            if selecting() {
+                                "background:#e3f2fd; color:#0066cc; border:1px solid #90caf9;"
+                            } else {
+                                "background:#f0f0f0; color:#666; border:1px solid #ddd;"
+                            },
+                        ),
+                        onclick: move |_| {
+                            selecting.set(!selecting());
+                            selected.set(Vec::new());
+                            batch_message.set(String::new());
+                        },
+                        "☑️"
+                    }
                     // Toggle: nur tote anzeigen
                     button {
                         style: format!(
@@ -86,20 +144,72 @@ pub fn ProfileListScreen(on_navigate: EventHandler<Screen>) -> Element {
                         load_profiles();
                     },
                 }
+                CoopSelect {
+                    label: String::new(),
+                    none_label: t!("coop-filter-all"),
+                    coop: coop_filter,
+                }
+            }
+
+            if !batch_message().is_empty() {
+                div { style: "padding: 12px 16px; background: #e8f5e9; border-radius: 8px; color: #2e7d32; font-size: 14px; margin-bottom: 12px;",
+                    "✅ {batch_message}"
+                }
+            }
+
+            if selecting() {
+                div { style: "display: flex; justify-content: space-between; align-items: center; margin-bottom: 12px; font-size: 14px; color: #333;",
+                    span { {t!("batch-selected", count: chosen().len())} }
+                    button {
+                        class: "btn-secondary",
+                        style: "padding: 8px 12px; font-size: 14px;",
+                        onclick: move |_| {
+                            let all: Vec<uuid::Uuid> = visible().iter().map(|q| q.uuid).collect();
+                            if chosen().len() == all.len() {
+                                selected.set(Vec::new());
+                            } else {
+                                selected.set(all);
+                            }
+                        },
+                        if chosen().len() == visible().len() {
+                            {t!("batch-select-none")}
+                        } else {
+                            {t!("batch-select-all")}
+                        }
+                    }
+                }
+                if !chosen().is_empty() {
+                    BatchActions {
+                        quail_ids: chosen(),
+                        on_done: move |message| {
+                            batch_message.set(message);
+                            selected.set(Vec::new());
+                            selecting.set(false);
+                            load_profiles();
+                        },
+                    }
+                }
             }
 
             // Profile Grid
-            if profiles().is_empty() {
+            if visible().is_empty() {
                 div { style: "text-align: center; padding: 40px; color: #999;",
                     {t!("profile-list-empty")} // No profiles available
                 }
             } else {
                 div { class: "profile-grid",
-                    for profile in profiles() {
+                    for profile in visible() {
                         ProfileCard {
                             profile: profile.clone(),
+                            selected: selecting() && selected().contains(&profile.uuid),
                             on_click: move |_| {
-                                on_navigate.call(Screen::ProfileDetail(profile.uuid.to_string()));
+                                if !selecting() {
+                                    on_navigate.call(Screen::ProfileDetail(profile.uuid.to_string()));
+                                } else if selected().contains(&profile.uuid) {
+                                    selected.write().retain(|id| *id != profile.uuid);
+                                } else {
+                                    selected.write().push(profile.uuid);
+                                }
                             },
                         }
                     }
@@ -110,32 +220,43 @@ pub fn ProfileListScreen(on_navigate: EventHandler<Screen>) -> Element {
 }
 
 #[component]
-pub fn ProfileCard(profile: Quail, on_click: EventHandler<()>) -> Element {
+pub fn ProfileCard(profile: Quail, selected: bool, on_click: EventHandler<()>) -> Element {
     let profile_uuid = profile.uuid;
+    // Verlässt die Karte den Bildschirm, wird ihr Download abgebrochen
+    let download_scope = use_hook(DownloadScope::new);
+    use_drop(move || download_scope.cancel());
 
-#[derive(Clone)]
-enum ImageState {
-    Loading,
-    Available(String),
-    Failed,
-}
+    #[derive(Clone)]
+    enum ImageState {
+        Loading,
+        Available(String),
+        Failed,
+    }
 
     // Lade Profilfoto über photo_service
     let image_data = use_resource(move || async move {
         log::debug!("##### Lade Profilbild für UUID: {:?}", profile_uuid);
-        if let Ok(conn) = database::init_database() {
+        if let Ok(conn) = database::connection() {
             match services::photo_service::get_profile_photo(&conn, &profile_uuid) {
                 Ok(Some(photo)) => {
                     // Use get_photo_with_download to handle downloading
-                    match services::photo_service::get_photo_with_download(&conn, &photo.uuid, crate::models::photo::PhotoSize::Small).await {
-                        Ok(crate::models::photo::PhotoResult::Available(bytes)) => {
-                            // Convert bytes to data URL
-                            let data_url = format!("data:image/webp;base64,{}", base64::engine::general_purpose::STANDARD.encode(&bytes));
+                    match services::photo_service::get_photo_url_with_download(
+                        &conn,
+                        &photo.uuid,
+                        crate::models::photo::PhotoSize::TINY,
+                        DownloadRequest::visible(download_scope),
+                    )
+                    .await
+                    {
+                        Ok(crate::models::photo::PhotoResult::Available(url)) => {
                             log::debug!("Profilbild geladen für UUID: {}", profile_uuid);
-                            ImageState::Available(data_url)
+                            ImageState::Available(url)
                         }
                         Ok(crate::models::photo::PhotoResult::Downloading) => {
-                            log::debug!("Profilbild wird heruntergeladen für UUID: {}", profile_uuid);
+                            log::debug!(
+                                "Profilbild wird heruntergeladen für UUID: {}",
+                                profile_uuid
+                            );
                             ImageState::Loading
                         }
                         Ok(crate::models::photo::PhotoResult::Failed(error, retry_count)) => {
@@ -166,7 +287,7 @@ enum ImageState {
     let mut current_status = use_signal(|| None::<crate::models::EventType>);
     let profile_uuid_for_effect = profile.uuid;
     use_effect(move || {
-        if let Ok(conn) = database::init_database() {
+        if let Ok(conn) = database::connection() {
             if let Ok(status) = services::profile_service::get_profile_current_status(
                 &conn,
                 &profile_uuid_for_effect,
@@ -183,20 +304,20 @@ enum ImageState {
         "rgba(255, 255, 255, 0.9)".to_string()
     };
 
-    // Image Data URL (Base64)
-    // let has_image = image_data().is_some(); // aktuell nicht genutzt
-
     rsx! {
-        div { class: "profile-card", onclick: move |_| on_click.call(()),
+        div {
+            class: "profile-card",
+            style: if selected { "outline: 3px solid #0066cc; outline-offset: -3px;" } else { "" },
+            onclick: move |_| on_click.call(()),
             // Square Image Container
             div { class: "profile-image",
                 match image_data() {
                     Some(ImageState::Loading) => rsx! {
                         div { class: "profile-image-placeholder", style: "display: flex; align-items: center; justify-content: center; font-size: 24px;", "⏳" }
                     },
-                    Some(ImageState::Available(data_url)) => rsx! {
+                    Some(ImageState::Available(url)) => rsx! {
                         img {
-                            src: data_url,
+                            src: url,
                             alt: profile.name.clone(),
                             style: "width: 100%; height: 100%; object-fit: cover;",
                         }
@@ -209,6 +330,13 @@ enum ImageState {
                     },
                 }
 
+                // Auswahl-Haken (oben links)
+                if selected {
+                    div { style: "position: absolute; top: 8px; left: 8px; font-size: 20px; background: #0066cc; color: white; border-radius: 50%; width: 32px; height: 32px; display: flex; align-items: center; justify-content: center; box-shadow: 0 2px 8px rgba(0,0,0,0.3);",
+                        "✓"
+                    }
+                }
+
                 // Overlay with name and gender
                 div {
                     class: "profile-overlay",
@@ -0,0 +1,144 @@
+use crate::database;
+use crate::services::health_check::{self, HealthIssue, HealthReport};
+use dioxus::prelude::*;
+use dioxus_i18n::t;
+
+/// Shown instead of the app when the startup health check fails
+#[component]
+pub fn RecoveryScreen(report: HealthReport, on_resolved: EventHandler<()>) -> Element {
+    let mut report = use_signal(|| report);
+    let mut status_message = use_signal(String::new);
+    let mut busy = use_signal(|| false);
+
+    let mut recheck = move || {
+        let new_report = health_check::run_startup_checks();
+        if new_report.is_healthy() {
+            on_resolved.call(());
+        }
+        report.set(new_report);
+    };
+
+    let restore_backup = move |_| {
+        busy.set(true);
+        spawn(async move {
+            let backup_path = health_check::default_backup_path();
+            match health_check::restore_from_backup(&backup_path).await {
+                Ok(()) => {
+                    status_message.set(format!("✅ {}", t!("recovery-restore-success")));
+                    recheck();
+                }
+                Err(crate::error::AppError::NotFound(path)) => {
+                    status_message.set(format!("⚠️ {}", t!("backup-import-missing", path: path)));
+                }
+                Err(e) => {
+                    status_message.set(format!(
+                        "❌ {}",
+                        t!("backup-import-error", error: e.to_string())
+                    ));
+                }
+            }
+            busy.set(false);
+        });
+    };
+
+    let reset_sync = move |_| match database::connection() {
+        Ok(conn) => match health_check::reset_sync_state(&conn) {
+            Ok(()) => {
+                status_message.set(format!("✅ {}", t!("recovery-reset-sync-success")));
+                recheck();
+            }
+            Err(e) => status_message.set(format!("❌ {}", e)),
+        },
+        Err(e) => status_message.set(format!("❌ {}: {}", t!("error-database"), e)),
+    };
+
+    let current = report();
+    let has_credentials_issue = current
+        .issues
+        .iter()
+        .any(|i| matches!(i, HealthIssue::CredentialsUnreadable(_)));
+
+    rsx! {
+        div { style: "padding: 16px; max-width: 600px; margin: 0 auto; min-height: 100vh; background: #f5f5f5;",
+            h1 { style: "color: #c62828; text-align: center; margin: 48px 0 16px 0; font-size: 24px; font-weight: 700;",
+                "🛠️ "
+                {t!("recovery-title")}
+            }
+            p { style: "font-size: 14px; color: #555; text-align: center; margin: 0 0 24px 0;",
+                {t!("recovery-description")}
+            }
+
+            if !status_message().is_empty() {
+                div { style: "padding: 12px; margin-bottom: 16px; background: #f0f0f0; border-radius: 8px; border-left: 4px solid #0066cc;",
+                    "{status_message}"
+                }
+            }
+
+            // Gefundene Probleme
+            div { class: "card", style: "margin-bottom: 16px;",
+                h2 { style: "margin: 0 0 12px 0; font-size: 18px; color: #333;",
+                    {t!("recovery-issues-title")}
+                }
+                for issue in current.issues.iter() {
+                    div { style: "padding: 8px 12px; margin-bottom: 8px; background: #fdecea; border-radius: 6px; border-left: 3px solid #c62828; font-size: 13px; color: #333;",
+                        {issue_message(issue)}
+                    }
+                }
+            }
+
+            // Aktionen
+            div { class: "card", style: "display: flex; flex-direction: column; gap: 12px;",
+                if current.database_broken() {
+                    button {
+                        class: "btn-primary",
+                        disabled: busy(),
+                        onclick: restore_backup,
+                        "📦 "
+                        {t!("recovery-restore-backup")}
+                    }
+                    p { style: "margin: 0; font-size: 12px; color: #666; word-break: break-all;",
+                        {t!("recovery-restore-hint", path: health_check::default_backup_path().display().to_string())}
+                    }
+                }
+                if has_credentials_issue {
+                    button { class: "btn-danger", disabled: busy(), onclick: reset_sync,
+                        "🔄 "
+                        {t!("recovery-reset-sync")}
+                    }
+                }
+                button {
+                    class: "btn-secondary",
+                    disabled: busy(),
+                    onclick: move |_| recheck(),
+                    "🔍 "
+                    {t!("recovery-recheck")}
+                }
+                if !current.database_broken() {
+                    button {
+                        class: "btn-secondary",
+                        disabled: busy(),
+                        onclick: move |_| on_resolved.call(()),
+                        {t!("recovery-continue")}
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn issue_message(issue: &HealthIssue) -> String {
+    match issue {
+        HealthIssue::DatabaseUnavailable(error) => {
+            t!("recovery-issue-database", error: error.clone())
+        }
+        HealthIssue::SchemaVersion { found, expected } => {
+            t!("recovery-issue-schema", found: *found, expected: *expected)
+        }
+        HealthIssue::StorageNotWritable { path, error } => {
+            t!("recovery-issue-storage", path: path.clone(), error: error.clone())
+        }
+        HealthIssue::CredentialsUnreadable(error) => {
+            t!("recovery-issue-credentials", error: error.clone())
+        }
+    }
+}
@@ -0,0 +1,139 @@
+use crate::database;
+use crate::models::{ExportDestination, ExportInterval, ExportSchedule, LogEntry, LogLevel};
+use crate::services::{app_log, scheduled_export, sync_service};
+use chrono::{DateTime, Local, Utc};
+use dioxus::prelude::*;
+use dioxus_i18n::t;
+
+fn format_time(millis: i64) -> String {
+    DateTime::from_timestamp_millis(millis)
+        .map(|time| {
+            time.with_timezone(&Local)
+                .format("%d.%m.%Y %H:%M")
+                .to_string()
+        })
+        .unwrap_or_default()
+}
+
+fn level_color(level: LogLevel) -> &'static str {
+    match level {
+        LogLevel::Info => "#2e7d32",
+        LogLevel::Warning => "#856404",
+        LogLevel::Error => "#cc0000",
+    }
+}
+
+fn last_result() -> Option<LogEntry> {
+    database::with_db(|conn| app_log::list(conn, Some(scheduled_export::LOG_CATEGORY), 1))
+        .ok()
+        .and_then(|entries| entries.into_iter().next())
+}
+
+/// Wöchentliche/monatliche Exporte: Intervall, Ziel, Anzahl und letztes Ergebnis
+#[component]
+pub fn ScheduledExportCard() -> Element {
+    let mut schedule =
+        use_signal(|| database::with_db(scheduled_export::load_schedule).unwrap_or_default());
+    let has_nextcloud = use_hook(|| {
+        database::with_db(sync_service::load_sync_settings)
+            .ok()
+            .flatten()
+            .is_some()
+    });
+    let last = use_signal(last_result);
+    let mut message = use_signal(String::new);
+
+    let mut save = move |updated: ExportSchedule| match database::with_db(|conn| {
+        scheduled_export::save_schedule(conn, &updated)
+    }) {
+        Ok(()) => {
+            schedule.set(updated);
+            message.set(String::new());
+        }
+        Err(e) => message.set(format!("\u{274c} {}: {}", t!("error-save-failed"), e)),
+    };
+
+    let current = schedule();
+
+    rsx! {
+        div { style: "margin-top: 16px; padding: 12px; background: #fff; border-radius: 8px; border: 1px solid #ddd;",
+            h3 { style: "margin: 0 0 8px 0; font-size: 16px;",
+                "⏰ "
+                {t!("scheduled-export-title")}
+            }
+            p { style: "margin: 0 0 12px 0; font-size: 13px; color: #666;",
+                {t!("scheduled-export-description")}
+            }
+
+            select {
+                style: "width: 100%; padding: 8px; border: 1px solid #ccc; border-radius: 4px; margin-bottom: 8px;",
+                value: current.interval.as_str(),
+                onchange: move |e| {
+                    let mut updated = schedule();
+                    updated.interval = ExportInterval::parse(&e.value()).unwrap_or_default();
+                    save(updated);
+                },
+                option { value: "off", {t!("scheduled-export-interval-off")} }
+                option { value: "weekly", {t!("scheduled-export-interval-weekly")} }
+                option { value: "monthly", {t!("scheduled-export-interval-monthly")} }
+            }
+
+            if current.interval != ExportInterval::Off {
+                select {
+                    style: "width: 100%; padding: 8px; border: 1px solid #ccc; border-radius: 4px; margin-bottom: 8px;",
+                    value: current.destination.as_str(),
+                    onchange: move |e| {
+                        let mut updated = schedule();
+                        updated.destination = ExportDestination::parse(&e.value()).unwrap_or_default();
+                        save(updated);
+                    },
+                    option { value: "local", {t!("scheduled-export-destination-local")} }
+                    option {
+                        value: "nextcloud",
+                        disabled: !has_nextcloud,
+                        {t!("scheduled-export-destination-nextcloud")}
+                    }
+                    option { value: "both", disabled: !has_nextcloud,
+                        {t!("scheduled-export-destination-both")}
+                    }
+                }
+
+                label { style: "display: flex; align-items: center; gap: 8px; font-size: 14px; margin-bottom: 8px;",
+                    {t!("scheduled-export-keep")}
+                    input {
+                        r#type: "number",
+                        min: "1",
+                        style: "width: 80px; padding: 6px; border: 1px solid #ccc; border-radius: 4px;",
+                        value: "{current.keep}",
+                        onchange: move |e| {
+                            if let Ok(keep) = e.value().trim().parse::<u32>() {
+                                let mut updated = schedule();
+                                updated.keep = keep.max(1);
+                                save(updated);
+                            }
+                        },
+                    }
+                }
+
+                if let Some(next) = current.next_attempt() {
+                    p { style: "margin: 0 0 4px 0; font-size: 12px; color: #666;",
+                        if next <= Utc::now() {
+                            {t!("scheduled-export-soon")}
+                        } else {
+                            {t!("scheduled-export-next", time: format_time(next.timestamp_millis()))}
+                        }
+                    }
+                }
+            }
+
+            if let Some(entry) = last() {
+                p { style: "margin: 0; font-size: 12px; word-break: break-all; color: {level_color(entry.level)};",
+                    {t!("scheduled-export-last", time: format_time(entry.created_at), message: entry.message)}
+                }
+            }
+            if !message().is_empty() {
+                p { style: "margin: 8px 0 0 0; font-size: 12px;", "{message}" }
+            }
+        }
+    }
+}
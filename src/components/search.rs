@@ -0,0 +1,134 @@
+use crate::database;
+use crate::models::EggRecord;
+use crate::services::repository;
+use crate::services::search_service::{self, SearchHit, SearchHitKind};
+use crate::Screen;
+use dioxus::prelude::*;
+use dioxus_i18n::t;
+
+/// Globale Suche über Namen, Ereignis- und Eier-Notizen
+#[component]
+pub fn SearchScreen(on_navigate: EventHandler<Screen>) -> Element {
+    let mut query = use_signal(String::new);
+    let mut hits = use_signal(Vec::<SearchHit>::new);
+    let mut error = use_signal(String::new);
+
+    let mut run_search = move |value: String| {
+        query.set(value.clone());
+        spawn(async move {
+            let result = search_service::search_all_async(value.clone()).await;
+            // Inzwischen weitergetippt: neuere Suche gewinnt
+            if query() != value {
+                return;
+            }
+            match result {
+                Ok(list) => {
+                    hits.set(list);
+                    error.set(String::new());
+                }
+                Err(e) => error.set(t!("error-load-failed", error: e.to_string())),
+            }
+        });
+    };
+
+    rsx! {
+        div { style: "padding: 16px; max-width: 600px; margin: 0 auto; min-height: 100vh; background: #f5f5f5;",
+            h1 { style: "color: #0066cc; margin: 0 0 12px 0; padding-top: 8px; font-size: 24px; font-weight: 700;",
+                "🔍 "
+                {t!("search-title")}
+            }
+            input {
+                style: "width: 100%; padding: 14px 16px; font-size: 16px; border: 2px solid #e0e0e0; border-radius: 10px; background: white; margin-bottom: 12px;",
+                r#type: "search",
+                placeholder: "{t!(\"search-placeholder-all\")}",
+                value: "{query}",
+                autofocus: true,
+                oninput: move |e| run_search(e.value()),
+            }
+
+            if !error().is_empty() {
+                div { style: "padding: 12px; background: #ffebee; border-radius: 8px; color: #c62828; margin-bottom: 12px;",
+                    "{error}"
+                }
+            }
+
+            if query().trim().is_empty() {
+                p { style: "text-align: center; padding: 40px; color: #999;", {t!("search-hint")} }
+            } else if hits().is_empty() {
+                p { style: "text-align: center; padding: 40px; color: #999;", {t!("search-no-results")} }
+            } else {
+                div { style: "display: flex; flex-direction: column; gap: 8px;",
+                    for hit in hits() {
+                        SearchHitRow {
+                            key: "{hit.uuid}",
+                            hit: hit.clone(),
+                            on_navigate,
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Ziel eines Treffers: Profil, Ereignis oder Eier-Eintrag
+fn target(hit: &SearchHit) -> Option<Screen> {
+    match hit.kind {
+        SearchHitKind::Quail => Some(Screen::ProfileDetail(hit.uuid.to_string())),
+        SearchHitKind::Event(_) => hit.quail_uuid.map(|quail| Screen::EventEdit {
+            event_id: hit.uuid.to_string(),
+            quail_id: quail.to_string(),
+        }),
+        SearchHitKind::EggRecord => Some(Screen::EggTracking {
+            date: hit.date.map(|d| d.format("%Y-%m-%d").to_string()),
+            // Stall des Eintrags, damit der richtige Eintrag des Tages öffnet
+            coop_id: database::with_db(|conn| repository::get::<EggRecord>(conn, &hit.uuid))
+                .ok()
+                .flatten()
+                .and_then(|record| record.coop_id)
+                .map(|id| id.to_string()),
+        }),
+    }
+}
+
+#[component]
+fn SearchHitRow(hit: SearchHit, on_navigate: EventHandler<Screen>) -> Element {
+    let (icon, title) = match &hit.kind {
+        SearchHitKind::Quail => ("🐦", hit.quail_name.clone().unwrap_or_default()),
+        SearchHitKind::Event(event_type) => (
+            "📅",
+            format!(
+                "{} – {}",
+                hit.quail_name.clone().unwrap_or_default(),
+                event_type.display_name()
+            ),
+        ),
+        SearchHitKind::EggRecord => ("🥚", t!("search-kind-egg-record")),
+    };
+    let date = hit
+        .date
+        .map(|d| d.format("%d.%m.%Y").to_string())
+        .unwrap_or_default();
+    let screen = target(&hit);
+    // Bei Wachteln ist der Treffer der Name selbst
+    let show_snippet = !matches!(hit.kind, SearchHitKind::Quail);
+
+    rsx! {
+        button {
+            style: "display: block; width: 100%; text-align: left; padding: 12px; background: white; border: 1px solid #e0e0e0; border-radius: 8px;",
+            disabled: screen.is_none(),
+            onclick: move |_| {
+                if let Some(screen) = screen.clone() {
+                    on_navigate.call(screen);
+                }
+            },
+            div { style: "display: flex; justify-content: space-between; gap: 8px;",
+                span { style: "font-weight: 600; color: #333;", "{icon} {title}" }
+                span { style: "font-size: 12px; color: #999;", "{date}" }
+            }
+            if show_snippet {
+                p { style: "margin: 4px 0 0 0; font-size: 13px; color: #666;", "{hit.snippet}" }
+            }
+        }
+    }
+}
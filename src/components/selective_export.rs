@@ -0,0 +1,210 @@
+use crate::components::share_button::ShareButton;
+use crate::database;
+use crate::models::Quail;
+use crate::services::backup_crypto::{self, Passphrase};
+use crate::services::export_import_service::{self, ExportFilter, PhotoExport};
+use crate::services::profile_service;
+use chrono::NaiveDate;
+use dioxus::prelude::*;
+use dioxus_i18n::t;
+use uuid::Uuid;
+
+fn parse_date(value: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d").ok()
+}
+
+/// Leere Eingabe = unverschlüsselt
+pub(crate) fn parse_passphrase(value: &str) -> Result<Option<Passphrase>, String> {
+    if value.is_empty() {
+        return Ok(None);
+    }
+    Passphrase::new(value).map(Some).map_err(|e| e.to_string())
+}
+
+/// Optionale Passphrase für verschlüsselte Exporte (nur mit Feature `encryption`)
+#[component]
+pub(crate) fn PassphraseField(value: Signal<String>, disabled: bool) -> Element {
+    if !backup_crypto::is_available() {
+        return rsx! {};
+    }
+    rsx! {
+        input {
+            r#type: "password",
+            style: "width: 100%; padding: 8px; border: 1px solid #ccc; border-radius: 4px; margin-bottom: 8px;",
+            placeholder: t!("backup-passphrase-placeholder", min: backup_crypto::MIN_PASSPHRASE_LEN),
+            autocomplete: "new-password",
+            value: "{value}",
+            disabled,
+            oninput: move |e| value.set(e.value()),
+        }
+    }
+}
+
+/// Teil-Export als ZIP: Zeitraum, einzelne Wachteln, Fotos ja/nein/nur
+/// Vorschaubilder (z.B. die Krankengeschichte einer Wachtel für den Tierarzt)
+#[component]
+pub fn SelectiveExportCard() -> Element {
+    let quails = use_signal(|| {
+        database::with_db(|conn| profile_service::list_profiles_with_status(conn, None, false))
+            .unwrap_or_default()
+    });
+    let mut start_date = use_signal(String::new);
+    let mut end_date = use_signal(String::new);
+    let selected = use_signal(Vec::<Uuid>::new);
+    let mut include_eggs = use_signal(|| true);
+    let mut photos = use_signal(PhotoExport::default);
+    let passphrase = use_signal(String::new);
+    let mut running = use_signal(|| false);
+    let mut message = use_signal(String::new);
+    let mut exported = use_signal(|| None::<std::path::PathBuf>);
+
+    let export = move |_| {
+        let passphrase = match parse_passphrase(&passphrase()) {
+            Ok(passphrase) => passphrase,
+            Err(e) => {
+                message.set(format!("❌ {}", e));
+                return;
+            }
+        };
+        let filter = ExportFilter {
+            start_date: parse_date(&start_date()),
+            end_date: parse_date(&end_date()),
+            quail_ids: selected(),
+            include_egg_records: include_eggs(),
+            photos: photos(),
+            passphrase,
+        };
+        running.set(true);
+        exported.set(None);
+        spawn(async move {
+            match database::connection() {
+                Ok(conn) => {
+                    match export_import_service::export_selection_to_zip(&conn, &filter).await {
+                        Ok(path) => {
+                            message.set(t!(
+                                "backup-export-success",
+                                path: path.display().to_string()
+                            ));
+                            exported.set(Some(path));
+                        }
+                        Err(e) => message.set(t!("backup-export-error", error: e.to_string())),
+                    }
+                }
+                Err(e) => message.set(t!("backup-db-error", error: e.to_string())),
+            }
+            running.set(false);
+        });
+    };
+
+    rsx! {
+        div { style: "margin-top: 16px; padding: 12px; background: #fff; border-radius: 8px; border: 1px solid #ddd;",
+            h3 { style: "margin: 0 0 8px 0; font-size: 16px;", {t!("selective-export-title")} }
+            p { style: "margin: 0 0 12px 0; font-size: 13px; color: #666;",
+                {t!("selective-export-description")}
+            }
+
+            div { style: "display: flex; gap: 8px; margin-bottom: 8px;",
+                label { style: "flex: 1; font-size: 13px;",
+                    {t!("selective-export-from")}
+                    input {
+                        r#type: "date",
+                        style: "width: 100%;",
+                        value: "{start_date}",
+                        oninput: move |e| start_date.set(e.value()),
+                    }
+                }
+                label { style: "flex: 1; font-size: 13px;",
+                    {t!("selective-export-to")}
+                    input {
+                        r#type: "date",
+                        style: "width: 100%;",
+                        value: "{end_date}",
+                        oninput: move |e| end_date.set(e.value()),
+                    }
+                }
+            }
+
+            p { style: "margin: 0 0 4px 0; font-size: 13px; font-weight: 600;",
+                {t!("selective-export-quails")}
+            }
+            div { style: "max-height: 160px; overflow-y: auto; margin-bottom: 8px; font-size: 14px;",
+                for quail in quails() {
+                    QuailCheckbox { key: "{quail.uuid}", quail: quail.clone(), selected }
+                }
+            }
+
+            label { style: "display: flex; align-items: center; gap: 8px; font-size: 14px; margin-bottom: 8px;",
+                input {
+                    r#type: "checkbox",
+                    checked: include_eggs(),
+                    onchange: move |e| include_eggs.set(e.checked()),
+                }
+                {t!("selective-export-include-eggs")}
+            }
+
+            select {
+                style: "width: 100%; padding: 8px; border: 1px solid #ccc; border-radius: 4px; margin-bottom: 8px;",
+                value: match photos() {
+                    PhotoExport::Originals => "originals",
+                    PhotoExport::ThumbnailsOnly => "thumbnails",
+                    PhotoExport::Reduced => "reduced",
+                    PhotoExport::None => "none",
+                },
+                onchange: move |e| {
+                    photos
+                        .set(
+                            match e.value().as_str() {
+                                "thumbnails" => PhotoExport::ThumbnailsOnly,
+                                "reduced" => PhotoExport::Reduced,
+                                "none" => PhotoExport::None,
+                                _ => PhotoExport::Originals,
+                            },
+                        )
+                },
+                option { value: "originals", {t!("selective-export-photos-originals")} }
+                option { value: "reduced", {t!("selective-export-photos-reduced")} }
+                option { value: "thumbnails", {t!("selective-export-photos-thumbnails")} }
+                option { value: "none", {t!("selective-export-photos-none")} }
+            }
+
+            PassphraseField { value: passphrase, disabled: running() }
+
+            button {
+                class: "btn-primary",
+                style: "width: 100%;",
+                disabled: running(),
+                onclick: export,
+                {t!("selective-export-button")}
+            }
+            if !message().is_empty() {
+                p { style: "margin: 8px 0 0 0; font-size: 13px; word-break: break-all;",
+                    "{message}"
+                }
+            }
+            if let Some(path) = exported() {
+                ShareButton { path }
+            }
+        }
+    }
+}
+
+#[component]
+fn QuailCheckbox(quail: Quail, selected: Signal<Vec<Uuid>>) -> Element {
+    let uuid = quail.uuid;
+    rsx! {
+        label { style: "display: flex; align-items: center; gap: 8px; padding: 2px 0;",
+            input {
+                r#type: "checkbox",
+                checked: selected().contains(&uuid),
+                onchange: move |e| {
+                    if e.checked() {
+                        selected.write().push(uuid);
+                    } else {
+                        selected.write().retain(|id| *id != uuid);
+                    }
+                },
+            }
+            "{quail.name}"
+        }
+    }
+}
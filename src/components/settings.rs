@@ -1,17 +1,43 @@
+use crate::components::backup_import::default_import_file;
+use crate::components::calendar_export::CalendarExportCard;
+use crate::components::cloud_export::CloudExportCard;
+use crate::components::coops::CoopsCard;
+use crate::components::database_integrity::IntegrityCheckCard;
+use crate::components::export_templates::ExportTemplatesCard;
+use crate::components::local_backup::LocalBackupCard;
+use crate::components::manual_credentials::auth_error_message;
+use crate::components::scheduled_export::ScheduledExportCard;
+use crate::components::selective_export::SelectiveExportCard;
+use crate::components::share_button::ShareButton;
+use crate::components::state_rebuild::StateRebuildCard;
+use crate::components::sync_history::SyncHistoryPreview;
+use crate::components::sync_progress::SyncProgressCard;
+use crate::components::transfer_stats::TransferStatsCard;
+use crate::components::ManualCredentialsComponent;
 use crate::database;
-use crate::models::SyncSettings;
-use crate::services::export_import_service::ImportMode;
+use crate::file_picker;
+use crate::login_view;
+use crate::models::{OriginalsPolicy, SyncSettings};
+use crate::services::export_import_service::{ExportFilter, PhotoExport};
+use crate::services::nextcloud_auth::{
+    AuthError, CertificatePin, LoginFlowSession, NextcloudAuthService, NextcloudCredentials,
+    PollProgress, ProxyConfig,
+};
+use crate::services::sync_backend::{ConfiguredBackend, SyncBackend};
 use crate::services::sync_service;
 use crate::Screen;
-use chrono::{Local, TimeZone};
 use dioxus::prelude::*;
 use dioxus_i18n::t;
-use serde::{Deserialize, Serialize};
 
-fn format_hms(ts_ms: i64) -> String {
-    match Local.timestamp_millis_opt(ts_ms).single() {
-        Some(dt) => dt.format(&t!("log-time-format")).to_string(), // the format string for the chrono format time.
-        None => String::from("--:--:--"),
+/// Label of an entity type that can be excluded from sync
+pub(crate) fn entity_type_label(entity_type: &str) -> String {
+    match entity_type {
+        "quail" => t!("sync-scope-quail"),
+        "event" => t!("sync-scope-event"),
+        "egg" => t!("sync-scope-egg"),
+        "photo" => t!("sync-scope-photo"),
+        "coop" => t!("sync-scope-coop"),
+        other => other.to_string(),
     }
 }
 
@@ -22,6 +48,36 @@ enum NetworkStatus {
     Offline(String),
 }
 
+/// Client-side photo encryption settings (only with feature `encryption`)
+fn photo_encryption_card() -> Element {
+    #[cfg(feature = "encryption")]
+    return rsx! {
+        crate::components::photo_encryption::PhotoEncryptionCard {}
+    };
+    #[cfg(not(feature = "encryption"))]
+    rsx! {}
+}
+
+/// Local database encryption settings (only with feature `sqlcipher`)
+fn database_encryption_card() -> Element {
+    #[cfg(feature = "sqlcipher")]
+    return rsx! {
+        crate::components::database_encryption::DatabaseEncryptionCard {}
+    };
+    #[cfg(not(feature = "sqlcipher"))]
+    rsx! {}
+}
+
+/// Storage location of database and photos (desktop only, Android uses the app directory)
+fn data_location_card() -> Element {
+    #[cfg(not(target_os = "android"))]
+    return rsx! {
+        crate::components::data_location::DataLocationCard {}
+    };
+    #[cfg(target_os = "android")]
+    rsx! {}
+}
+
 #[component]
 fn NetworkCheckCard() -> Element {
     let mut network_status = use_signal(|| NetworkStatus::Checking);
@@ -149,36 +205,11 @@ fn NetworkCheckCard() -> Element {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-struct LoginFlowInit {
-    poll: PollInfo,
-    login: String,
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-struct PollInfo {
-    token: String,
-    endpoint: String,
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-struct LoginFlowResult {
-    server: String,
-    #[serde(rename = "loginName")]
-    login_name: String,
-    #[serde(rename = "appPassword")]
-    app_password: String,
-}
-
 #[derive(Clone, PartialEq)]
 enum LoginState {
     NotStarted,
     InitiatingFlow,
-    WaitingForUser {
-        poll_url: String,
-        token: String,
-        login_url: String,
-    },
+    WaitingForUser(LoginFlowSession),
     Success,
     Error(String),
 }
@@ -190,29 +221,127 @@ enum ConnectionStatus {
     Failed(String),
 }
 
+/// Legt den Remote-Ordner an und speichert die Zugangsdaten als Sync-Settings.
+/// Gemeinsamer Abschluss für Login Flow v2 und manuelle Anmeldung.
+/// Mit `pin_certificate` wird der Zertifikats-Fingerabdruck der Verbindung
+/// gemerkt, über die die Zugangsdaten kamen.
+async fn store_credentials(
+    credentials: NextcloudCredentials,
+    remote_path: String,
+    proxy: Option<ProxyConfig>,
+    pin_certificate: bool,
+) -> Result<SyncSettings, String> {
+    let mut settings = SyncSettings::new(
+        credentials.server,
+        credentials.login_name,
+        credentials.app_password,
+        remote_path,
+    );
+    settings.proxy_url = proxy.as_ref().map(|p| p.url.clone());
+    if pin_certificate {
+        settings.cert_fingerprint = credentials.certificate;
+    }
+
+    // Create WebDAV client and folder
+    match ConfiguredBackend::from_settings(&settings) {
+        Ok(backend) => match backend.mkcol(&settings.remote_path).await {
+            Ok(_) => {
+                log::info!(
+                    "LoginFlow: Remote-Ordner erstellt: {}",
+                    settings.remote_path
+                );
+            }
+            Err(e) => {
+                // Folder might already exist (405)
+                log::debug!(
+                    "LoginFlow: Ordner-Erstellung Hinweis (evtl. bereits vorhanden): {}",
+                    e
+                );
+            }
+        },
+        Err(e) => {
+            log::error!("LoginFlow: WebDAV-Client Fehler: {}", e);
+            return Err(format!("{}: {}", t!("error-webdav-client"), e));
+        }
+    }
+
+    let conn = database::connection().map_err(|e| {
+        log::error!("LoginFlow: Datenbank-Init fehlgeschlagen: {}", e);
+        format!("{}: {}", t!("error-database"), e)
+    })?;
+
+    // Erneute Anmeldung am selben Konto: Geräte-ID und Sync-Stand behalten
+    if let Ok(Some(previous)) = sync_service::load_sync_settings(&conn) {
+        if previous.server_url == settings.server_url && previous.username == settings.username {
+            settings.device_id = previous.device_id;
+            settings.format_version = previous.format_version;
+            settings.initial_upload_done = previous.initial_upload_done;
+        }
+    }
+
+    sync_service::save_sync_settings(&conn, &settings).map_err(|e| {
+        log::error!(
+            "LoginFlow: Speichern der Sync-Settings fehlgeschlagen: {}",
+            e
+        );
+        format!("{}: {}", t!("error-save"), e)
+    })?;
+    log::info!("LoginFlow: Zugangsdaten gespeichert und Login abgeschlossen.");
+    Ok(settings)
+}
+
+/// Entfernt den gespeicherten Login Flow (abgeschlossen, fehlgeschlagen oder abgebrochen)
+fn clear_pending_login() {
+    match database::connection() {
+        Ok(conn) => {
+            if let Err(e) = sync_service::clear_login_session(&conn) {
+                log::warn!(
+                    "LoginFlow: Löschen der gespeicherten Session fehlgeschlagen: {}",
+                    e
+                );
+            }
+        }
+        Err(e) => log::warn!("LoginFlow: Datenbank-Init fehlgeschlagen: {}", e),
+    }
+}
+
 #[component]
 pub fn SettingsScreen(on_navigate: EventHandler<Screen>) -> Element {
     let mut server_url = use_signal(|| String::from("https://"));
     let mut remote_path = use_signal(|| String::from("/Stalltagebuch"));
+    // Optionaler HTTP-/SOCKS-Proxy (leer = direkte Verbindung)
+    let mut proxy_url = use_signal(String::new);
     let mut login_state = use_signal(|| LoginState::NotStarted);
+    // Laufender Login Flow, damit das Polling abgebrochen werden kann
+    let mut login_service = use_signal(|| None::<NextcloudAuthService>);
+    // Letzter Poll-Versuch (Versuch, Dauer, Status, nächster Versuch)
+    let mut poll_progress = use_signal(|| None::<PollProgress>);
+    let mut show_manual_login = use_signal(|| false);
+    // Erneute Anmeldung nach abgelehntem App-Passwort: Login-Formular statt Übersicht
+    let mut relogin = use_signal(|| false);
+    // Zertifikat beim Login merken und später prüfen (Trust on first use)
+    let mut pin_certificate = use_signal(|| false);
+    // Login-Seite im WebView-Dialog statt im externen Browser (nur Android)
+    let mut in_app_login = use_signal(|| false);
     let mut current_settings = use_signal(|| None::<SyncSettings>);
     let mut status_message = use_signal(|| String::new());
+    let mut exported = use_signal(|| None::<std::path::PathBuf>);
+    // Nur mittlere Vorschaubilder statt Originalen, klein genug für E-Mails
+    let mut reduced_photos = use_signal(|| false);
     // Separater bool für laufende Synchronisierung, damit Anzeige sicher zurückgesetzt wird
     let mut is_syncing = use_signal(|| false);
     let mut connection_status = use_signal(|| None::<ConnectionStatus>);
     let mut background_sync_running =
         use_signal(|| crate::services::background_sync::is_background_sync_running());
-    // Live Countdown & Log
+    // Live Countdown
     let mut sync_eta = use_signal(|| crate::services::background_sync::next_sync_eta_seconds());
-    let mut sync_log = use_signal(|| crate::services::background_sync::get_sync_log());
 
-    // Ticker Effekt (1s Interval) aktualisiert ETA und Log ohne User-Interaktion
+    // Ticker Effekt (1s Interval) aktualisiert ETA ohne User-Interaktion
     use_effect(move || {
         // Spawn ticker loop (kein Cleanup nötig für einfache 1s Timer)
         spawn(async move {
             loop {
                 sync_eta.set(crate::services::background_sync::next_sync_eta_seconds());
-                sync_log.set(crate::services::background_sync::get_sync_log());
                 background_sync_running
                     .set(crate::services::background_sync::is_background_sync_running());
                 tokio::time::sleep(std::time::Duration::from_secs(1)).await;
@@ -222,11 +351,12 @@ pub fn SettingsScreen(on_navigate: EventHandler<Screen>) -> Element {
 
     // Load existing settings on mount
     use_effect(move || {
-        match database::init_database() {
+        match database::connection() {
             Ok(conn) => match sync_service::load_sync_settings(&conn) {
                 Ok(Some(settings)) => {
                     server_url.set(settings.server_url.clone());
                     remote_path.set(settings.remote_path.clone());
+                    proxy_url.set(settings.proxy_url.clone().unwrap_or_default());
                     current_settings.set(Some(settings.clone()));
 
                     // Test connection in background
@@ -234,41 +364,22 @@ pub fn SettingsScreen(on_navigate: EventHandler<Screen>) -> Element {
                     spawn(async move {
                         connection_status.set(Some(ConnectionStatus::Checking));
 
-                        let webdav_url = format!(
-                            "{}/remote.php/dav/files/{}",
-                            settings_clone.server_url.trim_end_matches('/'),
-                            settings_clone.username
-                        );
-
-                        match reqwest_dav::ClientBuilder::new()
-                            .set_host(webdav_url)
-                            .set_auth(reqwest_dav::Auth::Basic(
-                                settings_clone.username.clone(),
-                                settings_clone.app_password.clone(),
-                            ))
-                            .build()
-                        {
-                            Ok(client) => {
-                                match client
-                                    .list(
-                                        &settings_clone.remote_path,
-                                        reqwest_dav::Depth::Number(0),
-                                    )
-                                    .await
-                                {
+                        match ConfiguredBackend::from_settings(&settings_clone) {
+                            Ok(backend) => {
+                                match backend.list(&settings_clone.remote_path).await {
                                     Ok(_) => {
                                         connection_status.set(Some(ConnectionStatus::Connected));
                                     }
                                     Err(e) => {
                                         connection_status.set(Some(ConnectionStatus::Failed(
-                                            format!("{}: {:?}", t!("error-access-failed"), e),
+                                            format!("{}: {}", t!("error-access-failed"), e),
                                         )));
                                     }
                                 }
                             }
                             Err(e) => {
                                 connection_status.set(Some(ConnectionStatus::Failed(format!(
-                                    "{}: {:?}",
+                                    "{}: {}",
                                     t!("error-client"),
                                     e
                                 ))));
@@ -292,313 +403,184 @@ pub fn SettingsScreen(on_navigate: EventHandler<Screen>) -> Element {
         }
     });
 
+    // Wartet auf die Bestätigung im Browser und speichert danach die Zugangsdaten.
+    // Die Session bleibt bis dahin in der DB, damit sie einen Neustart übersteht.
+    let mut poll_login =
+        move |auth: NextcloudAuthService, session: LoginFlowSession, remote_path_value: String| {
+            login_service.set(Some(auth.clone()));
+            poll_progress.set(None);
+            login_state.set(LoginState::WaitingForUser(session.clone()));
+
+            // Endet, sobald alle Klone des Services (und damit der Sender) weg sind
+            let mut progress_rx = auth.subscribe_progress();
+            spawn(async move {
+                while progress_rx.changed().await.is_ok() {
+                    let progress = progress_rx.borrow_and_update().clone();
+                    poll_progress.set(progress);
+                }
+            });
+            if in_app_login() {
+                if let Err(e) = login_view::open_login_view(&session.login_url) {
+                    log::warn!("LoginFlow: In-App-Anmeldung nicht möglich: {}", e);
+                }
+            }
+
+            spawn(async move {
+                let polled = auth.resume_polling(&session).await;
+                drop(auth);
+                login_service.set(None);
+                if let Err(e) = login_view::close_login_view() {
+                    log::warn!(
+                        "LoginFlow: Schließen der In-App-Anmeldung fehlgeschlagen: {}",
+                        e
+                    );
+                }
+                let result = match polled {
+                    Ok(result) => result,
+                    // Zustand wurde beim Abbrechen bereits zurückgesetzt
+                    Err(AuthError::Cancelled) => return,
+                    Err(e) => {
+                        clear_pending_login();
+                        login_state.set(LoginState::Error(auth_error_message(&e)));
+                        return;
+                    }
+                };
+                clear_pending_login();
+
+                let proxy = ProxyConfig::parse(&proxy_url()).ok().flatten();
+                match store_credentials(result.into(), remote_path_value, proxy, pin_certificate())
+                    .await
+                {
+                    Ok(settings) => {
+                        current_settings.set(Some(settings));
+                        relogin.set(false);
+                        login_state.set(LoginState::Success);
+                        status_message.set(format!("\u{2705} {}", t!("sync-login-success-folder")));
+                    }
+                    Err(e) => login_state.set(LoginState::Error(e)),
+                }
+            });
+        };
+
+    // Login Flow fortsetzen, falls die App während der Anmeldung im Browser beendet wurde
+    use_effect(move || {
+        let pending = match database::connection() {
+            Ok(conn) => sync_service::load_login_session(&conn).unwrap_or_else(|e| {
+                log::warn!(
+                    "LoginFlow: Laden der gespeicherten Session fehlgeschlagen: {}",
+                    e
+                );
+                None
+            }),
+            Err(_) => None,
+        };
+        let Some(pending) = pending else {
+            return;
+        };
+
+        if pending
+            .session
+            .is_expired(chrono::Utc::now().timestamp_millis())
+        {
+            log::info!("LoginFlow: gespeicherte Session abgelaufen, verworfen");
+            clear_pending_login();
+            return;
+        }
+
+        let proxy = ProxyConfig::parse(pending.proxy_url.as_deref().unwrap_or(""))
+            .ok()
+            .flatten();
+        match NextcloudAuthService::with_proxy(proxy.as_ref()) {
+            Ok(auth) => {
+                proxy_url.set(pending.proxy_url.clone().unwrap_or_default());
+                remote_path.set(pending.remote_path.clone());
+                poll_login(auth, pending.session, pending.remote_path);
+            }
+            Err(e) => login_state.set(LoginState::Error(auth_error_message(&e))),
+        }
+    });
+
     // Start Nextcloud Login Flow v2
-    let start_login = move |_| {
+    let mut begin_login = move || {
         let server = server_url();
         let remote_path_value = remote_path();
+        let proxy_value = proxy_url();
+        // Beim Merken des Zertifikats auch selbstsignierte Server zulassen
+        let pin = if pin_certificate() {
+            CertificatePin::trust_on_first_use()
+        } else {
+            CertificatePin::default()
+        };
         login_state.set(LoginState::InitiatingFlow);
 
         spawn(async move {
-            let url = format!("{}/index.php/login/v2", server.trim_end_matches('/'));
-
-            // Create a properly configured HTTP client
-            let client = match reqwest::Client::builder()
-                .timeout(std::time::Duration::from_secs(60))
-                .connect_timeout(std::time::Duration::from_secs(10))
-                .tcp_keepalive(std::time::Duration::from_secs(30))
-                .user_agent("Stalltagebuch/0.1.0")
-                .build()
-            {
-                Ok(client) => client,
+            let proxy = match ProxyConfig::parse(&proxy_value) {
+                Ok(proxy) => proxy,
                 Err(e) => {
-                    login_state.set(LoginState::Error(format!(
-                        "{}: {:?}",
-                        t!("error-client"),
-                        e
-                    )));
+                    login_state.set(LoginState::Error(auth_error_message(&e)));
+                    return;
+                }
+            };
+            let auth = match NextcloudAuthService::with_pin(proxy.as_ref(), pin) {
+                Ok(auth) => auth,
+                Err(e) => {
+                    login_state.set(LoginState::Error(auth_error_message(&e)));
                     return;
                 }
             };
 
-            match client.post(&url).send().await {
-                Ok(response) => {
-                    if response.status().is_success() {
-                        match response.json::<LoginFlowInit>().await {
-                            Ok(flow) => {
-                                let poll_url = flow.poll.endpoint.clone();
-                                let token = flow.poll.token.clone();
-                                let login_url = flow.login.clone();
-
-                                // Set state to show login URL
-                                login_state.set(LoginState::WaitingForUser {
-                                    poll_url: poll_url.clone(),
-                                    token: token.clone(),
-                                    login_url: login_url.clone(),
-                                });
-
-                                // Start polling immediately in background
-                                spawn(async move {
-                                    // Create a properly configured HTTP client for polling
-                                    let poll_client = match reqwest::Client::builder()
-                                        .timeout(std::time::Duration::from_secs(30))
-                                        .connect_timeout(std::time::Duration::from_secs(10))
-                                        .tcp_keepalive(std::time::Duration::from_secs(30))
-                                        .user_agent("Stalltagebuch/0.1.0")
-                                        .pool_idle_timeout(std::time::Duration::from_secs(90))
-                                        .pool_max_idle_per_host(4)
-                                        .build()
-                                    {
-                                        Ok(client) => client,
-                                        Err(e) => {
-                                            log::error!("LoginFlow: HTTP-Client für Polling konnte nicht erstellt werden: {:?}", e);
-                                            login_state.set(LoginState::Error(format!(
-                                                "{}: {:?}",
-                                                t!("error-client"),
-                                                e
-                                            )));
-                                            return;
-                                        }
-                                    };
-
-                                    // Small delay to ensure network is ready and user can open browser
-                                    #[cfg(not(target_arch = "wasm32"))]
-                                    {
-                                        log::debug!(
-                                            "LoginFlow: kurze Wartezeit vor Start des Pollings"
-                                        );
-                                        tokio::time::sleep(std::time::Duration::from_millis(500))
-                                            .await;
-                                    }
-                                    #[cfg(target_arch = "wasm32")]
-                                    gloo_timers::future::sleep(std::time::Duration::from_millis(
-                                        500,
-                                    ))
-                                    .await;
-
-                                    // Poll bis zu 60 Versuche (~5 Minuten bei 404, bei Netzfehlern mit Backoff)
-                                    let mut consecutive_errors: u32 = 0;
-                                    for attempt in 0..60 {
-                                        log::debug!("LoginFlow: Polling Versuch {}", attempt + 1);
-
-                                        // Standard-Wartezeit (wird in den Branches gesetzt)
-                                        #[allow(unused_assignments)]
-                                        let mut wait_after_secs: u64 = 5; // Startwert, wird in Branches überschrieben
-
-                                        match poll_client
-                                            .post(&poll_url)
-                                            .form(&[("token", &token)])
-                                            .header("User-Agent", "Stalltagebuch/0.1.0")
-                                            .header("Accept", "application/json")
-                                            .send()
-                                            .await
-                                        {
-                                            Ok(response) => {
-                                                if response.status().as_u16() == 200 {
-                                                    log::info!("LoginFlow: Polling erfolgreich (200). Verarbeite Zugangsdaten…");
-                                                    match response.json::<LoginFlowResult>().await {
-                                                        Ok(result) => {
-                                                            // Create WebDAV client and folder
-                                                            let webdav_url = format!(
-                                                                "{}/remote.php/dav/files/{}",
-                                                                result.server.trim_end_matches('/'),
-                                                                result.login_name
-                                                            );
-
-                                                            match reqwest_dav::ClientBuilder::new()
-                                                                .set_host(webdav_url)
-                                                                .set_auth(reqwest_dav::Auth::Basic(
-                                                                    result.login_name.clone(),
-                                                                    result.app_password.clone(),
-                                                                ))
-                                                                .build()
-                                                            {
-                                                                Ok(client) => {
-                                                                    // Try to create the folder
-                                                                    match client
-                                                                        .mkcol(&remote_path_value)
-                                                                        .await
-                                                                    {
-                                                                        Ok(_) => {
-                                                                            log::info!("LoginFlow: Remote-Ordner erstellt: {}", remote_path_value);
-                                                                        }
-                                                                        Err(e) => {
-                                                                            // Folder might already exist (405)
-                                                                            log::debug!("LoginFlow: Ordner-Erstellung Hinweis (evtl. bereits vorhanden): {}", e);
-                                                                        }
-                                                                    }
-                                                                }
-                                                                Err(e) => {
-                                                                    log::error!("LoginFlow: WebDAV-Client Fehler: {:?}", e);
-                                                                    login_state.set(LoginState::Error(
-                                                                        format!("{}: {:?}", t!("error-webdav-client"), e),
-                                                                    ));
-                                                                    return;
-                                                                }
-                                                            }
+            // Eingaben wie "cloud.example.com/index.php/apps/files" auf die Basis-URL bringen
+            let server = match auth.discover_server(&server).await {
+                Ok(base) => {
+                    server_url.set(base.clone());
+                    base
+                }
+                Err(e) => {
+                    login_state.set(LoginState::Error(auth_error_message(&e)));
+                    return;
+                }
+            };
 
-                                                            // Save credentials
-                                                            let settings = SyncSettings::new(
-                                                                result.server,
-                                                                result.login_name,
-                                                                result.app_password,
-                                                                remote_path_value.clone(),
-                                                            );
-
-                                                            match database::init_database() {
-                                                                Ok(conn) => {
-                                                                    match sync_service::save_sync_settings(
-                                                                        &conn, &settings,
-                                                                    ) {
-                                                                        Ok(_) => {
-                                                                            current_settings
-                                                                                .set(Some(settings));
-                                                                            login_state
-                                                                                .set(LoginState::Success);
-                                                                            status_message.set(
-                                                                                format!("\u{2705} {}", t!("sync-login-success-folder"))
-                                                                            );
-                                                                            log::info!("LoginFlow: Zugangsdaten gespeichert und Login abgeschlossen.");
-                                                                            return;
-                                                                        }
-                                                                        Err(e) => {
-                                                                            log::error!("LoginFlow: Speichern der Sync-Settings fehlgeschlagen: {}", e);
-                                                                            login_state.set(
-                                                                                LoginState::Error(format!(
-                                                                                    "{}: {}",
-                                                                                    t!("error-save"), e
-                                                                                )),
-                                                                            );
-                                                                            return;
-                                                                        }
-                                                                    }
-                                                                }
-                                                                Err(e) => {
-                                                                    log::error!("LoginFlow: Datenbank-Init fehlgeschlagen: {}", e);
-                                                                    login_state.set(LoginState::Error(
-                                                                        format!("{}: {}", t!("error-database"), e),
-                                                                    ));
-                                                                    return;
-                                                                }
-                                                            }
-                                                        }
-                                                        Err(e) => {
-                                                            log::error!("LoginFlow: JSON-Parse der Poll-Antwort fehlgeschlagen: {}", e);
-                                                            login_state.set(LoginState::Error(
-                                                                format!(
-                                                                    "{}: {}",
-                                                                    t!("error-json"),
-                                                                    e
-                                                                ),
-                                                            ));
-                                                            return;
-                                                        }
-                                                    }
-                                                } else if response.status().as_u16() != 404 {
-                                                    log::warn!(
-                                                        "LoginFlow: Unerwarteter HTTP-Status beim Polling: {}",
-                                                        response.status()
-                                                    );
-                                                    login_state.set(LoginState::Error(format!(
-                                                        "{}: {}",
-                                                        t!("error-unexpected-status"),
-                                                        response.status()
-                                                    )));
-                                                    return;
-                                                }
-                                                // 404 means waiting, continue polling
-                                                log::debug!("LoginFlow: Polling noch nicht bestätigt (404). Weiter warten…");
-                                                consecutive_errors = 0; // reset on valid response
-                                                wait_after_secs = 5;
-                                            }
-                                            Err(e) => {
-                                                // Netzfehler: mit Exponential-Backoff weiterprobieren statt früh abzubrechen
-                                                consecutive_errors =
-                                                    consecutive_errors.saturating_add(1);
-
-                                                let kind = if e.is_timeout() {
-                                                    "timeout"
-                                                } else if e.is_connect() {
-                                                    "connect"
-                                                } else if e.is_request() {
-                                                    "request"
-                                                } else {
-                                                    "other"
-                                                };
-
-                                                // Backoff: 5s, 10s, 20s, dann Deckel 30s
-                                                let backoff = 5u64.saturating_mul(
-                                                    1u64 << (consecutive_errors
-                                                        .saturating_sub(1)
-                                                        .min(2))
-                                                        as u32,
-                                                );
-                                                wait_after_secs = backoff.min(30);
-
-                                                log::warn!(
-                                                    "LoginFlow: Netzfehler beim Polling ({} in Folge, Typ: {}): {} – Backoff {}s",
-                                                    consecutive_errors,
-                                                    kind,
-                                                    e,
-                                                    wait_after_secs
-                                                );
-                                            }
-                                        }
+            // Wartungsmodus nicht als allgemeinen Verbindungsfehler melden
+            if let Err(e) = auth
+                .server_status(&server)
+                .await
+                .and_then(|status| status.ensure_available(&server))
+            {
+                login_state.set(LoginState::Error(auth_error_message(&e)));
+                return;
+            }
 
-                                        // Warten vor nächstem Poll (404: 5s, Netzfehler: Backoff)
-                                        #[cfg(not(target_arch = "wasm32"))]
-                                        {
-                                            tokio::time::sleep(std::time::Duration::from_secs(
-                                                wait_after_secs,
-                                            ))
-                                            .await;
-                                        }
-                                        #[cfg(target_arch = "wasm32")]
-                                        gloo_timers::future::sleep(std::time::Duration::from_secs(
-                                            wait_after_secs,
-                                        ))
-                                        .await;
-                                    }
+            let flow = match auth.start_login_flow(&server).await {
+                Ok(flow) => flow,
+                Err(e) => {
+                    login_state.set(LoginState::Error(auth_error_message(&e)));
+                    return;
+                }
+            };
 
-                                    log::error!("LoginFlow: Polling-Timeout nach 5 Minuten.");
-                                    login_state.set(LoginState::Error(
-                                        t!("error-login-timeout").to_string(),
-                                    ));
-                                });
-                            }
-                            Err(e) => {
-                                log::error!("LoginFlow: JSON-Parse der Flow-Initialisierung fehlgeschlagen: {}", e);
-                                login_state.set(LoginState::Error(format!(
-                                    "{}: {}",
-                                    t!("error-json"),
-                                    e
-                                )));
-                            }
-                        }
-                    } else {
-                        log::warn!(
-                            "LoginFlow: Server antwortete mit Status {} bei Flow-Start",
-                            response.status()
-                        );
-                        login_state.set(LoginState::Error(format!(
-                            "{}: {}",
-                            t!("error-server"),
-                            response.status()
-                        )));
+            let session = LoginFlowSession::new(&flow);
+            match database::connection() {
+                Ok(conn) => {
+                    if let Err(e) = sync_service::save_login_session(
+                        &conn,
+                        &session,
+                        &remote_path_value,
+                        proxy.as_ref().map(|p| p.url.as_str()),
+                    ) {
+                        log::warn!("LoginFlow: Session konnte nicht gespeichert werden: {}", e);
                     }
                 }
-                Err(e) => {
-                    log::error!("LoginFlow: Verbindungsfehler beim Flow-Start: {}", e);
-                    login_state.set(LoginState::Error(format!(
-                        "{}: {}",
-                        t!("error-connection"),
-                        e
-                    )));
-                }
+                Err(e) => log::warn!("LoginFlow: Datenbank-Init fehlgeschlagen: {}", e),
             }
+
+            // Show login URL and start polling in background
+            poll_login(auth, session, remote_path_value);
         });
     };
+    let start_login = move |_| begin_login();
 
-    let delete_settings = move |_| match database::init_database() {
+    let delete_settings = move |_| match database::connection() {
         Ok(conn) => match sync_service::delete_sync_settings(&conn) {
             Ok(_) => {
                 current_settings.set(None);
@@ -647,7 +629,34 @@ pub fn SettingsScreen(on_navigate: EventHandler<Screen>) -> Element {
             NetworkCheckCard {}
 
             // Current settings display
-            if let Some(settings) = current_settings() {
+            if let Some(settings) = current_settings().filter(|_| !relogin()) {
+                if settings.credentials_invalid {
+                    div {
+                        class: "card",
+                        style: "margin-bottom: 16px; background: #fdecea; color: #611a15;",
+                        p { style: "margin: 0 0 12px 0; font-weight: 600;",
+                            "\u{26a0}\u{fe0f} "
+                            {t!("sync-credentials-invalid")}
+                        }
+                        button {
+                            class: "btn-primary",
+                            style: "width: 100%;",
+                            onclick: {
+                                let server = settings.server_url.clone();
+                                let path = settings.remote_path.clone();
+                                move |_| {
+                                    // Server und Ordner bleiben, nur das App-Passwort wird erneuert
+                                    server_url.set(server.clone());
+                                    remote_path.set(path.clone());
+                                    relogin.set(true);
+                                    begin_login();
+                                }
+                            },
+                            "🔐 "
+                            {t!("sync-relogin")}
+                        }
+                    }
+                }
                 div {
                     class: "card",
                     style: "margin-bottom: 16px; background: #e8f5e9;",
@@ -691,7 +700,16 @@ pub fn SettingsScreen(on_navigate: EventHandler<Screen>) -> Element {
                             },
                         }
                     }
-                    if let Some(last_sync) = settings.last_sync {
+                    if let Some(fingerprint) = settings.cert_fingerprint.clone() {
+                        p { style: "margin: 4px 0; font-size: 12px; word-break: break-all;",
+                            strong {
+                                {t!("sync-certificate")}
+                                ": "
+                            }
+                            "{fingerprint}"
+                        }
+                    }
+                    if let Some(last_sync) = settings.last_sync.clone() {
                         p { style: "margin: 4px 0; font-size: 14px;",
                             strong {
                                 {t!("sync-last-sync")}
@@ -719,7 +737,7 @@ pub fn SettingsScreen(on_navigate: EventHandler<Screen>) -> Element {
                                                         stats.photos_uploaded,
                                                     ),
                                                 );
-                                            if let Ok(conn) = database::init_database() {
+                                            if let Ok(conn) = database::connection() {
                                                 if let Ok(Some(updated)) = crate::services::sync_service::load_sync_settings(
                                                     &conn,
                                                 ) {
@@ -789,76 +807,258 @@ pub fn SettingsScreen(on_navigate: EventHandler<Screen>) -> Element {
                         }
                     }
 
-                    // Photo Upload Progress
-                    {
-                        let mut upload_progress = use_signal(|| (0usize, 0usize));
-                        use_coroutine(move |_: UnboundedReceiver<()>| async move {
-                            let mut rx = crate::services::background_sync::subscribe_upload_progress();
-                            loop {
-                                match rx.changed().await {
-                                    Ok(_) => {
-                                        let progress = *rx.borrow_and_update();
-                                        upload_progress.set(progress);
-                                    }
-                                    Err(_) => break,
+                    // Local photo storage limit
+                    div { style: "margin-top: 16px; padding: 12px; background: #f5f5f5; border-radius: 8px;",
+                        label { style: "display: block; margin-bottom: 4px; font-weight: 600; font-size: 14px;",
+                            {t!("sync-storage-limit-label")}
+                        }
+                        input {
+                            r#type: "number",
+                            min: "0",
+                            value: settings.photo_storage_limit_mb.map(|mb| mb.to_string()).unwrap_or_default(),
+                            placeholder: "∞",
+                            style: "width: 100%; padding: 10px; font-size: 16px; border: 1px solid #ccc; border-radius: 4px;",
+                            onchange: move |e| {
+                                let limit = e.value().trim().parse::<u32>().ok().filter(|mb| *mb > 0);
+                                let Ok(conn) = database::connection() else {
+                                    return;
+                                };
+                                let Ok(Some(mut updated)) = sync_service::load_sync_settings(&conn) else {
+                                    return;
+                                };
+                                updated.photo_storage_limit_mb = limit;
+                                if let Err(e) = sync_service::save_sync_settings(&conn, &updated) {
+                                    status_message.set(format!("\u{274c} {}: {}", t!("error-save-failed"), e));
+                                    return;
                                 }
-                            }
-                        });
-                        let (current, total) = upload_progress();
-                        if total > 0 {
-                            let percent = if total > 0 {
-                                (current as f64 / total as f64 * 100.0) as usize
-                            } else {
-                                0
-                            };
-                            rsx! {
-                                div { style: "margin-top: 16px; padding: 12px; background: #fff3cd; border-radius: 8px; border-left: 4px solid #ffb300;",
-                                    div { style: "display: flex; align-items: center; gap: 12px; margin-bottom: 8px;",
-                                        span { style: "font-size: 24px;", "📤" }
-                                        div { style: "flex: 1;",
-                                            p { style: "margin: 0; font-weight: 600; font-size: 14px;", "Fotos werden hochgeladen..." }
-                                            p { style: "margin: 4px 0 0 0; font-size: 12px; color: #666;",
-                                                "{current} von {total} Fotos hochgeladen ({percent}%)"
-                                            }
-                                        }
+                                current_settings.set(Some(updated));
+                                match crate::services::storage_quota::enforce_configured_limit(&conn) {
+                                    Ok(Some(report)) if report.evicted > 0 => {
+                                        let mb = report.freed_bytes / (1024 * 1024);
+                                        status_message
+                                            .set(
+                                                format!(
+                                                    "\u{1f5d1}\u{fe0f} {}",
+                                                    t!("sync-storage-evicted", count: report.evicted, mb: mb),
+                                                ),
+                                            );
                                     }
-                                    div { style: "width: 100%; background: #e0e0e0; border-radius: 4px; height: 8px; overflow: hidden;",
-                                        div { style: "height: 100%; background: linear-gradient(90deg, #0066cc, #0088ff); transition: width 0.3s ease; width: {percent}%;" }
+                                    Ok(_) => {}
+                                    Err(e) => {
+                                        status_message.set(format!("\u{274c} {}", e));
                                     }
                                 }
+                            },
+                        }
+                        p { style: "margin: 4px 0 0 0; font-size: 12px; color: #666;",
+                            {t!("sync-storage-limit-hint")}
+                        }
+                    }
+
+                    // Parallel photo uploads
+                    div { style: "margin-top: 16px; padding: 12px; background: #f5f5f5; border-radius: 8px;",
+                        label { style: "display: block; margin-bottom: 4px; font-weight: 600; font-size: 14px;",
+                            {t!("sync-upload-concurrency-label")}
+                        }
+                        input {
+                            r#type: "number",
+                            min: "1",
+                            max: "10",
+                            value: settings.upload_concurrency.map(|n| n.to_string()).unwrap_or_default(),
+                            placeholder: "{crate::services::upload_service::DEFAULT_UPLOAD_CONCURRENCY}",
+                            style: "width: 100%; padding: 10px; font-size: 16px; border: 1px solid #ccc; border-radius: 4px;",
+                            onchange: move |e| {
+                                let concurrency = e
+                                    .value()
+                                    .trim()
+                                    .parse::<u32>()
+                                    .ok()
+                                    .filter(|n| *n > 0)
+                                    .map(|n| n.min(10));
+                                let Ok(conn) = database::connection() else {
+                                    return;
+                                };
+                                let Ok(Some(mut updated)) = sync_service::load_sync_settings(&conn) else {
+                                    return;
+                                };
+                                updated.upload_concurrency = concurrency;
+                                if let Err(e) = sync_service::save_sync_settings(&conn, &updated) {
+                                    status_message.set(format!("\u{274c} {}: {}", t!("error-save-failed"), e));
+                                    return;
+                                }
+                                current_settings.set(Some(updated));
+                            },
+                        }
+                        p { style: "margin: 4px 0 0 0; font-size: 12px; color: #666;",
+                            {
+                                t!(
+                                    "sync-upload-concurrency-hint", default :
+                                    crate::services::upload_service::DEFAULT_UPLOAD_CONCURRENCY
+                                )
                             }
-                        } else {
-                            rsx! {}
                         }
                     }
 
-                    // Session Sync Log Anzeige
-                    div { style: "margin-top: 16px; padding: 12px; background: #fff; border-radius: 8px; border: 1px solid #ddd;",
-                        h3 { style: "margin: 0 0 8px 0; font-size: 16px;",
-                            "📝 Sync Sitzung (flüchtig)"
+                    // When original photos are transferred
+                    div { style: "margin-top: 16px; padding: 12px; background: #f5f5f5; border-radius: 8px;",
+                        label { style: "display: block; margin-bottom: 4px; font-weight: 600; font-size: 14px;",
+                            {t!("sync-originals-policy-label")}
                         }
-                        {
-                            let log_entries = sync_log();
-                            if log_entries.is_empty() {
-                                rsx! {
-                                    p { style: "margin: 0; font-size: 12px; color: #666;", "Noch keine Einträge" }
+                        select {
+                            value: "{settings.originals_policy.as_str()}",
+                            style: "width: 100%; padding: 10px; font-size: 16px; border: 1px solid #ccc; border-radius: 4px;",
+                            onchange: move |e| {
+                                let Some(policy) = OriginalsPolicy::parse(&e.value()) else {
+                                    return;
+                                };
+                                let Ok(conn) = database::connection() else {
+                                    return;
+                                };
+                                let Ok(Some(mut updated)) = sync_service::load_sync_settings(&conn) else {
+                                    return;
+                                };
+                                updated.originals_policy = policy;
+                                if let Err(e) = sync_service::save_sync_settings(&conn, &updated) {
+                                    status_message.set(format!("\u{274c} {}: {}", t!("error-save-failed"), e));
+                                    return;
                                 }
-                            } else {
-                                rsx! {
-                                    div { style: "display: flex; flex-direction: column; gap: 4px; max-height: 180px; overflow-y: auto;",
-                                        for entry in log_entries {
-                                            div { style: "font-size: 12px; padding: 4px 6px; background: #f8f9fa; border-radius: 4px; border-left: 3px solid #0066cc;",
-                                                span { style: "color: #333;",
-                                                    "{format_hms(entry.ts_ms)}: Ops {entry.operations_downloaded} · Fotos {entry.photos_uploaded}"
-                                                }
-                                            }
-                                        }
+                                current_settings.set(Some(updated));
+                            },
+                            option { value: "always", {t!("sync-originals-policy-always")} }
+                            option { value: "wifi_only", {t!("sync-originals-policy-wifi-only")} }
+                            option { value: "wifi_and_charging", {t!("sync-originals-policy-wifi-and-charging")} }
+                            option { value: "on_demand", {t!("sync-originals-policy-on-demand")} }
+                        }
+                        p { style: "margin: 4px 0 0 0; font-size: 12px; color: #666;",
+                            {t!("sync-originals-policy-hint")}
+                        }
+                    }
+
+                    // Pause background sync on metered connections / low battery
+                    div { style: "margin-top: 16px; padding: 12px; background: #f5f5f5; border-radius: 8px;",
+                        label { style: "display: block; margin-bottom: 4px; font-weight: 600; font-size: 14px;",
+                            {t!("sync-pause-label")}
+                        }
+                        label { style: "display: flex; align-items: center; gap: 8px; margin-bottom: 4px; font-size: 14px;",
+                            input {
+                                r#type: "checkbox",
+                                checked: settings.pause_on_metered,
+                                onchange: move |e| {
+                                    let Ok(conn) = database::connection() else {
+                                        return;
+                                    };
+                                    let Ok(Some(mut updated)) = sync_service::load_sync_settings(&conn) else {
+                                        return;
+                                    };
+                                    updated.pause_on_metered = e.checked();
+                                    if let Err(e) = sync_service::save_sync_settings(&conn, &updated) {
+                                        status_message.set(format!("\u{274c} {}: {}", t!("error-save-failed"), e));
+                                        return;
                                     }
+                                    crate::services::background_sync::reschedule_background_sync();
+                                    current_settings.set(Some(updated));
+                                },
+                            }
+                            {t!("sync-pause-on-metered")}
+                        }
+                        label { style: "display: flex; align-items: center; gap: 8px; margin-bottom: 4px; font-size: 14px;",
+                            input {
+                                r#type: "checkbox",
+                                checked: settings.pause_on_low_battery,
+                                onchange: move |e| {
+                                    let Ok(conn) = database::connection() else {
+                                        return;
+                                    };
+                                    let Ok(Some(mut updated)) = sync_service::load_sync_settings(&conn) else {
+                                        return;
+                                    };
+                                    updated.pause_on_low_battery = e.checked();
+                                    if let Err(e) = sync_service::save_sync_settings(&conn, &updated) {
+                                        status_message.set(format!("\u{274c} {}: {}", t!("error-save-failed"), e));
+                                        return;
+                                    }
+                                    crate::services::background_sync::reschedule_background_sync();
+                                    current_settings.set(Some(updated));
+                                },
+                            }
+                            {t!("sync-pause-on-low-battery")}
+                        }
+                        p { style: "margin: 4px 0 0 0; font-size: 12px; color: #666;",
+                            {
+                                t!(
+                                    "sync-pause-hint", percent :
+                                    crate::services::connectivity::LOW_BATTERY_PERCENT
+                                )
+                            }
+                        }
+                    }
+
+                    // Pause sync / choose what is synchronized
+                    div { style: "margin-top: 16px; padding: 12px; background: #f5f5f5; border-radius: 8px;",
+                        label { style: "display: block; margin-bottom: 4px; font-weight: 600; font-size: 14px;",
+                            {t!("sync-scope-label")}
+                        }
+                        label { style: "display: flex; align-items: center; gap: 8px; margin-bottom: 8px; font-size: 14px;",
+                            input {
+                                r#type: "checkbox",
+                                checked: settings.paused,
+                                onchange: move |e| {
+                                    let Ok(conn) = database::connection() else {
+                                        return;
+                                    };
+                                    if let Err(e) = sync_service::set_sync_paused(&conn, e.checked()) {
+                                        status_message.set(format!("\u{274c} {}: {}", t!("error-save-failed"), e));
+                                        return;
+                                    }
+                                    current_settings.set(sync_service::load_sync_settings(&conn).ok().flatten());
+                                },
+                            }
+                            {t!("sync-paused")}
+                        }
+                        for entity_type in SyncSettings::ENTITY_TYPES {
+                            label {
+                                key: "{entity_type}",
+                                style: "display: flex; align-items: center; gap: 8px; margin-bottom: 4px; font-size: 14px;",
+                                input {
+                                    r#type: "checkbox",
+                                    checked: settings.syncs(entity_type),
+                                    disabled: settings.paused,
+                                    onchange: move |e| {
+                                        let Ok(conn) = database::connection() else {
+                                            return;
+                                        };
+                                        if let Err(e) = sync_service::set_entity_type_synced(
+                                            &conn,
+                                            entity_type,
+                                            e.checked(),
+                                        ) {
+                                            status_message.set(format!("\u{274c} {}: {}", t!("error-save-failed"), e));
+                                            return;
+                                        }
+                                        current_settings.set(sync_service::load_sync_settings(&conn).ok().flatten());
+                                    },
                                 }
+                                {entity_type_label(entity_type)}
                             }
                         }
+                        p { style: "margin: 4px 0 0 0; font-size: 12px; color: #666;",
+                            {t!("sync-scope-hint")}
+                        }
                     }
 
+                    {photo_encryption_card()}
+
+                    {database_encryption_card()}
+
+                    // Progress of the running sync cycle
+                    SyncProgressCard {}
+
+                    // Persistent sync history (latest runs)
+                    SyncHistoryPreview { on_navigate }
+
+                    // Data usage of the sync (per day)
+                    TransferStatsCard {}
+
                     // Cleanup orphaned photos
                     div { style: "margin-top: 16px; padding: 12px; background: #fff; border-radius: 8px; border: 1px solid #ddd;",
                         h3 { style: "margin: 0 0 8px 0; font-size: 16px;", {t!("backup-cleanup-title")} }
@@ -874,11 +1074,33 @@ pub fn SettingsScreen(on_navigate: EventHandler<Screen>) -> Element {
                                     let confirmed = if cfg!(target_os = "android") { true } else { true };
                                     if confirmed {
                                         spawn(async move {
-                                            match database::init_database() {
+                                            match database::connection() {
                                                 Ok(conn) => {
                                                     match crate::services::photo_service::cleanup_orphaned_photos(&conn).await {
                                                         Ok(count) => {
-                                                            status_message.set(t!("backup-cleanup-success", count: count));
+                                                            let mut message = t!("backup-cleanup-success", count: count);
+                                                            // Stray files left by crashes (and photos without their file)
+                                                            match crate::services::photo_sweep::sweep_orphan_files(&conn, true).await {
+                                                                Ok(report) => {
+                                                                    message = format!(
+                                                                        "{}\n{}",
+                                                                        message,
+                                                                        t!(
+                                                                            "backup-sweep-success", files : report.stray_files.len(), mb :
+                                                                            report.freed_bytes / (1024 * 1024), missing : report
+                                                                            .missing_originals.len()
+                                                                        ),
+                                                                    );
+                                                                }
+                                                                Err(e) => {
+                                                                    message = format!(
+                                                                        "{}\n{}",
+                                                                        message,
+                                                                        t!("backup-cleanup-error", error: e.to_string()),
+                                                                    );
+                                                                }
+                                                            }
+                                                            status_message.set(message);
                                                         }
                                                         Err(e) => {
                                                             status_message.set(t!("backup-cleanup-error", error: e.to_string()));
@@ -897,6 +1119,9 @@ pub fn SettingsScreen(on_navigate: EventHandler<Screen>) -> Element {
                         }
                     }
 
+                    // Rebuild local data from the op log
+                    StateRebuildCard {}
+
                     // Daten-Export / -Import
                     div { style: "margin-top: 16px; padding: 12px; background: #fff; border-radius: 8px; border: 1px solid #ddd;",
                         h3 { style: "margin: 0 0 8px 0; font-size: 16px;", {t!("backup-export-title")} }
@@ -911,10 +1136,19 @@ pub fn SettingsScreen(on_navigate: EventHandler<Screen>) -> Element {
                                     let mut status_message = status_message.clone();
                                     move |_| {
                                         spawn(async move {
-                                            match database::init_database() {
-                                                Ok(conn) => match crate::services::export_import_service::export_to_zip(&conn).await {
+                                            let filter = ExportFilter {
+                                                photos: if reduced_photos() {
+                                                    PhotoExport::Reduced
+                                                } else {
+                                                    PhotoExport::Originals
+                                                },
+                                                ..ExportFilter::default()
+                                            };
+                                            match database::connection() {
+                                                Ok(conn) => match crate::services::export_import_service::export_selection_to_zip(&conn, &filter).await {
                                                     Ok(path) => {
                                                         status_message.set(t!("backup-export-success", path: path.display().to_string()));
+                                                        exported.set(Some(path));
                                                     }
                                                     Err(e) => {
                                                         status_message.set(t!("backup-export-error", error: e.to_string()));
@@ -929,43 +1163,60 @@ pub fn SettingsScreen(on_navigate: EventHandler<Screen>) -> Element {
                                 },
                                 {t!("backup-export-button")}
                             }
+                            label { style: "display: flex; align-items: center; gap: 8px; font-size: 13px; color: #555;",
+                                input {
+                                    r#type: "checkbox",
+                                    checked: reduced_photos(),
+                                    onchange: move |e| reduced_photos.set(e.checked()),
+                                }
+                                {t!("backup-export-reduced-photos")}
+                            }
+                            button {
+                                class: "btn-secondary",
+                                style: "width: 100%;",
+                                onclick: move |_| match database::with_db(crate::services::json_export::export_json) {
+                                    Ok(path) => {
+                                        status_message.set(t!("backup-export-success", path: path.display().to_string()));
+                                        exported.set(Some(path));
+                                    }
+                                    Err(e) => status_message.set(t!("backup-export-error", error: e.to_string())),
+                                },
+                                {t!("backup-export-json-button")}
+                            }
+                            if let Some(path) = exported() {
+                                ShareButton { path }
+                            }
                             button {
                                 class: "btn-danger",
                                 style: "width: 100%;",
-                                onclick: {
-                                    let mut status_message = status_message.clone();
-                                    move |_| {
-                                        spawn(async move {
-                                            let base_dir = if cfg!(target_os = "android") {
-                                                std::path::PathBuf::from("/storage/emulated/0/Android/data/de.teilgedanken.stalltagebuch/files/exports")
-                                            } else {
-                                                std::path::PathBuf::from("./exports")
-                                            };
-                                            let import_path = base_dir.join("import.zip");
-                                            if !import_path.exists() {
-                                                status_message.set(t!("backup-import-missing", path: import_path.display().to_string()));
-                                                return;
-                                            }
-                                            match database::init_database() {
-                                                Ok(conn) => match crate::services::export_import_service::import_from_zip(&conn, &import_path, ImportMode::MergePreferImport).await {
-                                                    Ok(()) => {
-                                                        status_message.set(t!("backup-import-success", path: import_path.display().to_string()));
-                                                    }
-                                                    Err(e) => {
-                                                        status_message.set(t!("backup-import-error", error: e.to_string()));
-                                                    }
-                                                },
-                                                Err(e) => {
-                                                    status_message.set(t!("backup-db-error", error: e.to_string()));
-                                                }
-                                            }
-                                        });
+                                onclick: move |_| {
+                                    if !file_picker::FILE_PICKER_AVAILABLE {
+                                        on_navigate.call(Screen::BackupImport(default_import_file()));
+                                        return;
                                     }
+                                    spawn(async move {
+                                        match file_picker::pick_import_archive() {
+                                            Ok(path) => on_navigate.call(Screen::BackupImport(path)),
+                                            Err(e) => status_message.set(t!("backup-import-pick-error", error: e.to_string())),
+                                        }
+                                    });
                                 },
                                 {t!("backup-import-button")}
                             }
+                            button {
+                                class: "btn-secondary",
+                                style: "width: 100%;",
+                                onclick: move |_| on_navigate.call(Screen::SpreadsheetImport),
+                                {t!("spreadsheet-import-open")}
+                            }
                         }
                     }
+
+                    // Export in die Nextcloud hochladen
+                    CloudExportCard {}
+
+                    // Teil-Export (Zeitraum, Wachteln, Fotos)
+                    SelectiveExportCard {}
                 }
             } else {
                 // Setup form
@@ -1008,13 +1259,54 @@ pub fn SettingsScreen(on_navigate: EventHandler<Screen>) -> Element {
                         }
                     }
 
+                    // Optional proxy
+                    div { style: "margin-bottom: 16px;",
+                        label { style: "display: block; margin-bottom: 4px; font-weight: 600; font-size: 14px;",
+                            {t!("sync-proxy-label")}
+                        }
+                        input {
+                            r#type: "text",
+                            value: "{proxy_url}",
+                            oninput: move |e| proxy_url.set(e.value()),
+                            placeholder: "socks5://127.0.0.1:1080",
+                            style: "width: 100%; padding: 10px; font-size: 16px; border: 1px solid #ccc; border-radius: 4px;",
+                        }
+                        p { style: "margin: 4px 0 0 0; font-size: 12px; color: #666;",
+                            {t!("sync-proxy-hint")}
+                        }
+                    }
+
                     // Login button and status
+                    if login_state() == LoginState::NotStarted {
+                        label { style: "display: flex; align-items: center; gap: 8px; margin-bottom: 4px; font-size: 14px;",
+                            input {
+                                r#type: "checkbox",
+                                checked: pin_certificate(),
+                                onchange: move |e| pin_certificate.set(e.checked()),
+                            }
+                            {t!("sync-pin-certificate")}
+                        }
+                        p { style: "margin: 0 0 16px 0; font-size: 12px; color: #666;",
+                            {t!("sync-pin-certificate-hint")}
+                        }
+                    }
+                    if login_view::IN_APP_LOGIN_AVAILABLE && login_state() == LoginState::NotStarted {
+                        label { style: "display: flex; align-items: center; gap: 8px; margin-bottom: 16px; font-size: 14px;",
+                            input {
+                                r#type: "checkbox",
+                                checked: in_app_login(),
+                                onchange: move |e| in_app_login.set(e.checked()),
+                            }
+                            {t!("sync-login-in-app")}
+                        }
+                    }
+
                     match login_state() {
                         LoginState::NotStarted => rsx! {
                             button {
                                 class: "btn-primary",
                                 onclick: start_login,
-                                disabled: server_url().trim().is_empty() || !server_url().starts_with("http"),
+                                disabled: server_url().trim().is_empty() || server_url().trim() == "https://",
                                 "🔐 "
                                 {t!("sync-login")}
                             }
@@ -1025,7 +1317,10 @@ pub fn SettingsScreen(on_navigate: EventHandler<Screen>) -> Element {
                                 {t!("sync-connecting")}
                             }
                         },
-                        LoginState::WaitingForUser { login_url, poll_url: _, token: _ } => {
+                        LoginState::WaitingForUser(session) => {
+                            let login_url = session.login_url.clone();
+                            let in_app_url = login_url.clone();
+                            let minutes_left = session.remaining_minutes(chrono::Utc::now().timestamp_millis());
                             rsx! {
                                 div { style: "padding: 12px; background: #d1ecf1; border-radius: 4px;",
                                     div { style: "display: flex; align-items: center; gap: 12px; margin-bottom: 12px;",
@@ -1038,6 +1333,25 @@ pub fn SettingsScreen(on_navigate: EventHandler<Screen>) -> Element {
                                         }
                                     }
                                     p { style: "margin: 0 0 12px 0; font-size: 14px;", {t!("sync-login-instructions")} } // Login instructions
+                                    p { style: "margin: 0 0 12px 0; font-size: 12px; color: #666;",
+                                        {t!("sync-login-expires", minutes: minutes_left)}
+                                    }
+                                    if let Some(progress) = poll_progress() {
+                                        p { style: "margin: 0 0 12px 0; font-size: 12px; color: #666;",
+                                            {
+                                                t!(
+                                                    "sync-poll-progress",
+                                                    attempt: progress.attempt,
+                                                    elapsed: progress.elapsed.as_secs(),
+                                                    next: progress.next_retry_in.as_secs()
+                                                )
+                                            }
+                                            if let Some(status) = progress.last_status.filter(|s| *s != 404) {
+                                                " · "
+                                                {t!("sync-poll-last-status", status: status)}
+                                            }
+                                        }
+                                    }
                                     a {
                                         href: "{login_url}",
                                         target: "_blank",
@@ -1045,6 +1359,32 @@ pub fn SettingsScreen(on_navigate: EventHandler<Screen>) -> Element {
                                         "🌐 "
                                         {t!("sync-login-browser")}
                                     }
+                                    if in_app_login() {
+                                        button {
+                                            class: "btn-secondary",
+                                            style: "width: 100%; margin-top: 12px;",
+                                            onclick: move |_| {
+                                                if let Err(e) = login_view::open_login_view(&in_app_url) {
+                                                    log::warn!("LoginFlow: In-App-Anmeldung nicht möglich: {}", e);
+                                                }
+                                            },
+                                            "📱 "
+                                            {t!("sync-login-in-app-open")}
+                                        }
+                                    }
+                                    button {
+                                        class: "btn-secondary",
+                                        style: "width: 100%; margin-top: 12px;",
+                                        onclick: move |_| {
+                                            if let Some(auth) = login_service() {
+                                                auth.cancel();
+                                            }
+                                            clear_pending_login();
+                                            relogin.set(false);
+                                            login_state.set(LoginState::NotStarted);
+                                        },
+                                        {t!("action-cancel")}
+                                    }
                                 }
                             }
                         }
@@ -1071,6 +1411,50 @@ pub fn SettingsScreen(on_navigate: EventHandler<Screen>) -> Element {
                         },
                     }
 
+                    // Manual login fallback (e.g. SSO proxies breaking Login Flow v2)
+                    div { style: "margin-top: 12px;",
+                        button {
+                            class: "btn-secondary",
+                            style: "width: 100%; margin-bottom: 12px;",
+                            onclick: move |_| show_manual_login.set(!show_manual_login()),
+                            if show_manual_login() {
+                                {t!("sync-manual-hide")}
+                            } else {
+                                {t!("sync-manual-show")}
+                            }
+                        }
+                        if show_manual_login() {
+                            ManualCredentialsComponent {
+                                server_url: server_url(),
+                                proxy_url: proxy_url(),
+                                pin_certificate: pin_certificate(),
+                                on_success: move |credentials: NextcloudCredentials| {
+                                    let remote_path_value = remote_path();
+                                    spawn(async move {
+                                        let proxy = ProxyConfig::parse(&proxy_url()).ok().flatten();
+                                        match store_credentials(
+                                                credentials,
+                                                remote_path_value,
+                                                proxy,
+                                                pin_certificate(),
+                                            )
+                                            .await
+                                        {
+                                            Ok(settings) => {
+                                                current_settings.set(Some(settings));
+                                                login_state.set(LoginState::Success);
+                                                show_manual_login.set(false);
+                                                status_message
+                                                    .set(format!("\u{2705} {}", t!("sync-login-success-folder")));
+                                            }
+                                            Err(e) => login_state.set(LoginState::Error(e)),
+                                        }
+                                    });
+                                },
+                            }
+                        }
+                    }
+
                     // Info box
                     div { style: "margin-top: 16px; padding: 12px; background: #f8f9fa; border-radius: 4px; border-left: 4px solid #0066cc;",
                         p { style: "margin: 0 0 8px 0; font-size: 14px; font-weight: 600;",
@@ -1087,6 +1471,26 @@ pub fn SettingsScreen(on_navigate: EventHandler<Screen>) -> Element {
                     }
                 }
             }
+
+            // Coops that quails and egg records are assigned to
+            CoopsCard {}
+
+            // Daily local snapshots, independent of sync
+            LocalBackupCard {}
+
+            // Weekly/monthly export ZIPs, locally and/or to Nextcloud
+            ScheduledExportCard {}
+
+            // Events as an .ics calendar, optionally uploaded for subscription
+            CalendarExportCard {}
+
+            // CSV exports from saved templates (entities, columns, event types)
+            ExportTemplatesCard {}
+
+            // Database integrity check and repair
+            IntegrityCheckCard {}
+
+            {data_location_card()}
         }
     }
 }
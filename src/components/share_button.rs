@@ -0,0 +1,31 @@
+use crate::share;
+use dioxus::prelude::*;
+use dioxus_i18n::t;
+use std::path::PathBuf;
+
+/// „Teilen“-Knopf nach einem Export; öffnet auf Android das Teilen-Menü,
+/// auf anderen Plattformen wird nichts angezeigt
+#[component]
+pub fn ShareButton(path: PathBuf) -> Element {
+    let mut error = use_signal(String::new);
+
+    if !share::SHARE_AVAILABLE {
+        return rsx! {};
+    }
+
+    rsx! {
+        button {
+            class: "btn-secondary",
+            style: "width: 100%; margin-top: 8px;",
+            onclick: move |_| match share::share_file(&path, &t!("share-chooser-title")) {
+                Ok(()) => error.set(String::new()),
+                Err(e) => error.set(format!("❌ {}", t!("share-error", error: e.to_string()))),
+            },
+            "📤 "
+            {t!("share-button")}
+        }
+        if !error().is_empty() {
+            p { style: "margin: 4px 0 0 0; font-size: 12px; color: #cc0000;", "{error}" }
+        }
+    }
+}
@@ -0,0 +1,295 @@
+use crate::database;
+use crate::services::export_import_service::{
+    self, apply_import, guess_mapping, preview_import, ColumnMapping, ImportField, ImportKind,
+};
+use crate::services::import_adapters::{self, ImportAdapter};
+use crate::Screen;
+use dioxus::prelude::*;
+use dioxus_i18n::t;
+use stalltagebuch_services::spreadsheet::{self, Table};
+use std::path::PathBuf;
+
+/// Angezeigte Zeilenfehler, der Rest wird nur gezählt
+const MAX_SHOWN_ERRORS: usize = 20;
+/// Beispielzeilen in der Vorschau
+const SAMPLE_ROWS: usize = 5;
+
+fn field_key(field: ImportField) -> &'static str {
+    match field {
+        ImportField::Name => "name",
+        ImportField::Gender => "gender",
+        ImportField::RingColor => "ring_color",
+        ImportField::Date => "date",
+        ImportField::TotalEggs => "total_eggs",
+        ImportField::Notes => "notes",
+    }
+}
+
+/// Wachteln oder Eier-Einträge aus CSV/XLSX: Datei laden, Spalten
+/// zuordnen, Vorschau prüfen, importieren
+#[component]
+pub fn SpreadsheetImportScreen(on_navigate: EventHandler<Screen>) -> Element {
+    let mut kind = use_signal(|| ImportKind::EggRecords);
+    let mut path = use_signal(|| {
+        export_import_service::get_export_base_dir()
+            .join("import.csv")
+            .display()
+            .to_string()
+    });
+    let mut table = use_signal(|| None::<Table>);
+    let mut mapping = use_signal(ColumnMapping::new);
+    // Export einer anderen App: ersetzt die Spaltenzuordnung
+    let mut adapter = use_signal(|| None::<&'static dyn ImportAdapter>);
+    let mut busy = use_signal(|| false);
+    let mut message = use_signal(String::new);
+
+    let mut load = move || match spreadsheet::read_table(&PathBuf::from(path())) {
+        Ok(loaded) => {
+            mapping.set(guess_mapping(kind(), &loaded.headers));
+            adapter.set(import_adapters::detect(&loaded.headers));
+            table.set(Some(loaded));
+            message.set(String::new());
+        }
+        Err(e) => {
+            table.set(None);
+            message.set(t!("spreadsheet-import-load-error", error: e.to_string()));
+        }
+    };
+
+    let preview = table().map(|table| {
+        match adapter() {
+            Some(adapter) => adapter.preview(&table),
+            None => preview_import(kind(), &table, &mapping()),
+        }
+        .map_err(|e| e.to_string())
+    });
+    let total_rows = table().map_or(0, |table| table.rows.len());
+
+    rsx! {
+        div { style: "padding: 16px; max-width: 600px; margin: 0 auto;",
+            div { style: "display: flex; align-items: center; margin-bottom: 16px;",
+                button {
+                    class: "btn-back",
+                    onclick: move |_| on_navigate.call(Screen::Settings),
+                    "← "
+                }
+                h1 { style: "margin: 0; font-size: 24px; color: #0066cc;",
+                    {t!("spreadsheet-import-title")}
+                }
+            }
+
+            p { style: "margin: 0 0 12px 0; font-size: 13px; color: #666;",
+                {t!("spreadsheet-import-description")}
+            }
+
+            div { class: "card", style: "margin-bottom: 16px;",
+                select {
+                    style: "width: 100%; padding: 8px; border: 1px solid #ccc; border-radius: 4px; margin-bottom: 8px;",
+                    value: if kind() == ImportKind::Quails { "quails" } else { "eggs" },
+                    disabled: busy(),
+                    onchange: move |e| {
+                        let selected = if e.value() == "quails" {
+                            ImportKind::Quails
+                        } else {
+                            ImportKind::EggRecords
+                        };
+                        kind.set(selected);
+                        if let Some(table) = table() {
+                            mapping.set(guess_mapping(selected, &table.headers));
+                        }
+                    },
+                    option { value: "eggs", {t!("spreadsheet-import-kind-eggs")} }
+                    option { value: "quails", {t!("spreadsheet-import-kind-quails")} }
+                }
+                div { style: "display: flex; gap: 8px;",
+                    input {
+                        r#type: "text",
+                        style: "flex: 1; min-width: 0;",
+                        placeholder: t!("spreadsheet-import-path-placeholder"),
+                        value: "{path}",
+                        disabled: busy(),
+                        oninput: move |e| path.set(e.value()),
+                    }
+                    if cfg!(feature = "desktop") {
+                        button {
+                            style: "padding: 4px 10px; font-size: 13px; background: #e0e0e0; color: #333;",
+                            disabled: busy(),
+                            onclick: move |_| {
+                                if let Some(file) = pick_file() {
+                                    path.set(file.display().to_string());
+                                    load();
+                                }
+                            },
+                            {t!("spreadsheet-import-browse")}
+                        }
+                    }
+                }
+                button {
+                    class: "btn-secondary",
+                    style: "width: 100%; margin-top: 8px;",
+                    disabled: busy() || path().trim().is_empty(),
+                    onclick: move |_| load(),
+                    {t!("spreadsheet-import-load")}
+                }
+            }
+
+            if let (Some(_), Some(detected)) = (table(), adapter()) {
+                div { class: "card", style: "margin-bottom: 16px;",
+                    p { style: "margin: 0 0 8px 0; font-size: 14px;",
+                        {t!("spreadsheet-import-adapter-detected", app: detected.name())}
+                    }
+                    button {
+                        style: "padding: 4px 10px; font-size: 13px; background: #e0e0e0; color: #333;",
+                        disabled: busy(),
+                        onclick: move |_| adapter.set(None),
+                        {t!("spreadsheet-import-adapter-manual")}
+                    }
+                }
+            } else if let Some(loaded) = table() {
+                div { class: "card", style: "margin-bottom: 16px;",
+                    h3 { style: "margin: 0 0 8px 0; font-size: 16px;",
+                        {t!("spreadsheet-import-mapping-title")}
+                    }
+                    for (column , header) in loaded.headers.iter().enumerate() {
+                        div {
+                            key: "{column}",
+                            style: "display: flex; align-items: center; gap: 8px; margin-bottom: 6px;",
+                            span { style: "flex: 1; font-size: 14px; overflow: hidden; text-overflow: ellipsis;",
+                                "{header}"
+                            }
+                            select {
+                                style: "flex: 1; padding: 6px; border: 1px solid #ccc; border-radius: 4px;",
+                                value: mapping().get(column).copied().flatten().map_or("", field_key),
+                                disabled: busy(),
+                                onchange: move |e| {
+                                    let selected = kind()
+                                        .fields()
+                                        .iter()
+                                        .copied()
+                                        .find(|field| field_key(*field) == e.value());
+                                    let mut current = mapping();
+                                    if current.len() <= column {
+                                        current.resize(column + 1, None);
+                                    }
+                                    // Jedes Feld nur einer Spalte zuordnen
+                                    for slot in current.iter_mut() {
+                                        if selected.is_some() && *slot == selected {
+                                            *slot = None;
+                                        }
+                                    }
+                                    current[column] = selected;
+                                    mapping.set(current);
+                                },
+                                option { value: "", {t!("spreadsheet-import-ignore")} }
+                                for field in kind().fields().iter().copied() {
+                                    option { value: field_key(field),
+                                        if field.is_required() {
+                                            "{field.display_name()} *"
+                                        } else {
+                                            "{field.display_name()}"
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+
+                match preview.clone() {
+                    Some(Err(e)) => rsx! {
+                        p { style: "color: #cc0000; font-size: 14px;", "❌ {e}" }
+                    },
+                    Some(Ok(preview)) => rsx! {
+                        div { class: "card", style: "margin-bottom: 16px;",
+                            p { style: "margin: 0 0 8px 0; font-weight: 600;",
+                                {t!("spreadsheet-import-preview-valid", valid : preview.valid_count(), total : total_rows)}
+                            }
+                            ul { style: "margin: 0 0 8px 0; padding-left: 20px; font-size: 13px; color: #333;",
+                                for quail in preview.quails.iter().take(SAMPLE_ROWS) {
+                                    li { key: "{quail.uuid}",
+                                        "{quail.name} · {quail.gender.display_name()}"
+                                        if let Some(color) = &quail.ring_color {
+                                            " · {color.display_name()}"
+                                        }
+                                    }
+                                }
+                                for record in preview.egg_records.iter().take(SAMPLE_ROWS) {
+                                    li { key: "{record.uuid}",
+                                        "{record.record_date.format(\"%d.%m.%Y\")}: {record.total_eggs}"
+                                        if let Some(notes) = &record.notes {
+                                            " · {notes}"
+                                        }
+                                    }
+                                }
+                            }
+                            if !preview.errors.is_empty() {
+                                div { style: "padding: 8px; background: #fff3f3; border-radius: 4px; font-size: 13px; color: #cc0000;",
+                                    for error in preview.errors.iter().take(MAX_SHOWN_ERRORS) {
+                                        div { key: "{error.row}",
+                                            {t!("spreadsheet-import-row-error", row : error.row, message : error.message.clone())}
+                                        }
+                                    }
+                                    if preview.errors.len() > MAX_SHOWN_ERRORS {
+                                        div {
+                                            {t!("spreadsheet-import-more-errors", count : preview.errors.len() - MAX_SHOWN_ERRORS)}
+                                        }
+                                    }
+                                }
+                            }
+                            button {
+                                class: "btn-primary",
+                                style: "width: 100%; margin-top: 12px;",
+                                disabled: busy() || preview.valid_count() == 0,
+                                onclick: move |_| {
+                                    let preview = preview.clone();
+                                    busy.set(true);
+                                    spawn(async move {
+                                        match database::connection() {
+                                            Ok(conn) => match apply_import(&conn, &preview).await {
+                                                Ok(summary) => {
+                                                    message
+                                                        .set(
+                                                            t!(
+                                                                "spreadsheet-import-success", created : summary.created, updated :
+                                                                summary.updated, skipped : summary.skipped
+                                                            ),
+                                                        );
+                                                    table.set(None);
+                                                }
+                                                Err(e) => {
+                                                    message.set(t!("spreadsheet-import-error", error : e.to_string()));
+                                                }
+                                            },
+                                            Err(e) => {
+                                                message.set(t!("backup-db-error", error : e.to_string()));
+                                            }
+                                        }
+                                        busy.set(false);
+                                    });
+                                },
+                                {t!("spreadsheet-import-button", count : preview.valid_count())}
+                            }
+                        }
+                    },
+                    None => rsx! {},
+                }
+            }
+
+            if !message().is_empty() {
+                p { style: "margin: 0; font-size: 14px;", "{message}" }
+            }
+        }
+    }
+}
+
+#[cfg(feature = "desktop")]
+fn pick_file() -> Option<PathBuf> {
+    rfd::FileDialog::new()
+        .add_filter("CSV / Excel", &["csv", "xlsx"])
+        .pick_file()
+}
+
+#[cfg(not(feature = "desktop"))]
+fn pick_file() -> Option<PathBuf> {
+    None
+}
@@ -0,0 +1,109 @@
+use crate::components::settings::entity_type_label;
+use crate::database;
+use crate::services::{export_import_service, state_rebuild};
+use dioxus::prelude::*;
+use dioxus_i18n::t;
+
+/// Wartung: lokale Daten aus dem Op-Log neu aufbauen
+///
+/// Legt vorher ein Backup (ZIP-Export) an und zeigt danach, wie sich die
+/// Anzahl der Einträge je Typ verändert hat.
+#[component]
+pub fn StateRebuildCard() -> Element {
+    let mut confirm = use_signal(|| false);
+    let mut running = use_signal(|| false);
+    let mut message = use_signal(String::new);
+
+    let rebuild = move |_| {
+        confirm.set(false);
+        running.set(true);
+        message.set(t!("rebuild-running"));
+        spawn(async move {
+            let conn = match database::connection() {
+                Ok(conn) => conn,
+                Err(e) => {
+                    message.set(t!("rebuild-error", error: e.to_string()));
+                    running.set(false);
+                    return;
+                }
+            };
+            let backup = match export_import_service::export_to_zip(&conn).await {
+                Ok(path) => path,
+                Err(e) => {
+                    message.set(t!("rebuild-error", error: e.to_string()));
+                    running.set(false);
+                    return;
+                }
+            };
+
+            let mut lines = vec![t!("rebuild-backup", path: backup.display().to_string())];
+            match state_rebuild::rebuild_from_op_log(&conn).await {
+                Ok(report) => {
+                    lines.push(t!(
+                        "rebuild-success",
+                        ops: report.replayed_ops,
+                        remote: report.merged_remote
+                    ));
+                    if report.is_consistent() {
+                        lines.push(t!("rebuild-consistent"));
+                    }
+                    for count in report.mismatches() {
+                        lines.push(format!(
+                            "{}: {} → {}",
+                            entity_type_label(count.entity_type),
+                            count.before,
+                            count.after
+                        ));
+                    }
+                    if let Some(error) = report.remote_error {
+                        lines.push(t!("rebuild-remote-error", error: error));
+                    }
+                }
+                Err(e) => lines.push(t!("rebuild-error", error: e.to_string())),
+            }
+            message.set(lines.join("\n"));
+            running.set(false);
+        });
+    };
+
+    rsx! {
+        div { style: "margin-top: 16px; padding: 12px; background: #fff; border-radius: 8px; border: 1px solid #ddd;",
+            h3 { style: "margin: 0 0 8px 0; font-size: 16px;", {t!("rebuild-title")} }
+            p { style: "margin: 0 0 12px 0; font-size: 13px; color: #666;",
+                {t!("rebuild-description")}
+            }
+            if confirm() {
+                p { style: "margin: 0 0 8px 0; padding: 8px; background: #fff3cd; border-radius: 4px; font-size: 12px; color: #856404;",
+                    "⚠️ "
+                    {t!("rebuild-confirm")}
+                }
+                div { style: "display: flex; gap: 8px;",
+                    button {
+                        class: "btn-danger",
+                        style: "flex: 1;",
+                        onclick: rebuild,
+                        {t!("rebuild-button")}
+                    }
+                    button {
+                        style: "flex: 1; background: #e0e0e0; color: #666;",
+                        onclick: move |_| confirm.set(false),
+                        {t!("action-cancel")}
+                    }
+                }
+            } else {
+                button {
+                    class: "btn-danger",
+                    style: "width: 100%;",
+                    disabled: running(),
+                    onclick: move |_| confirm.set(true),
+                    {t!("rebuild-button")}
+                }
+            }
+            if !message().is_empty() {
+                p { style: "margin: 8px 0 0 0; font-size: 12px; white-space: pre-line;",
+                    "{message}"
+                }
+            }
+        }
+    }
+}
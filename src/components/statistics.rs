@@ -1,4 +1,5 @@
-use crate::{database, services, Screen};
+use crate::components::share_button::ShareButton;
+use crate::{services, Screen};
 use dioxus::prelude::*;
 use dioxus_i18n::t;
 
@@ -8,64 +9,68 @@ pub fn StatisticsScreen(on_navigate: EventHandler<Screen>) -> Element {
     let mut trend = use_signal(|| Vec::<(String, i32)>::new());
     let mut error = use_signal(|| String::new());
     let mut selected_period = use_signal(|| "all".to_string());
+    let mut include_photos = use_signal(|| false);
+    let mut report_status = use_signal(String::new);
+    let mut report_file = use_signal(|| None::<std::path::PathBuf>);
 
-    let mut load_statistics = move || {
-        match database::init_database() {
-            Ok(conn) => {
-                // Berechne Zeitraum basierend auf Auswahl
-                let (start_date, end_date) = match selected_period().as_str() {
-                    "week" => {
-                        let today = chrono::Local::now().date_naive();
-                        let week_ago = today - chrono::Duration::days(7);
-                        (
-                            Some(week_ago.format("%Y-%m-%d").to_string()),
-                            Some(today.format("%Y-%m-%d").to_string()),
-                        )
-                    }
-                    "month" => {
-                        let today = chrono::Local::now().date_naive();
-                        let month_ago = today - chrono::Duration::days(30);
-                        (
-                            Some(month_ago.format("%Y-%m-%d").to_string()),
-                            Some(today.format("%Y-%m-%d").to_string()),
-                        )
-                    }
-                    "year" => {
-                        let today = chrono::Local::now().date_naive();
-                        let year_ago = today - chrono::Duration::days(365);
-                        (
-                            Some(year_ago.format("%Y-%m-%d").to_string()),
-                            Some(today.format("%Y-%m-%d").to_string()),
-                        )
-                    }
-                    _ => (None, None),
-                };
-
-                match services::analytics_service::calculate_statistics(
-                    &conn,
-                    start_date.as_deref(),
-                    end_date.as_deref(),
-                ) {
-                    Ok(statistics) => {
-                        stats.set(Some(statistics));
-                        error.set(String::new());
-                    }
-                    Err(e) => {
-                        error.set(format!("{}: {}", t!("error-calculation"), e));
-                        // Error message when statistics calculation fails
-                    }
-                }
+    let load_statistics = move || {
+        // Berechne Zeitraum basierend auf Auswahl
+        let (start_date, end_date) = match period_start(&selected_period()) {
+            Some(start) => (
+                Some(start.format("%Y-%m-%d").to_string()),
+                Some(
+                    chrono::Local::now()
+                        .date_naive()
+                        .format("%Y-%m-%d")
+                        .to_string(),
+                ),
+            ),
+            None => (None, None),
+        };
 
-                // Load trend data (last 30 days)
-                match services::analytics_service::get_recent_trend(&conn, 30) {
-                    Ok(data) => trend.set(data),
-                    Err(e) => error.set(format!("{}: {}", t!("error-trend-load"), e)), // Error message when loading trend data fails
+        // Abfragen laufen im Hintergrund, die Ansicht bleibt flüssig
+        spawn(async move {
+            match services::analytics_service::load_statistics(start_date, end_date).await {
+                Ok(statistics) => {
+                    stats.set(Some(statistics));
+                    error.set(String::new());
+                }
+                Err(e) => {
+                    error.set(format!("{}: {}", t!("error-calculation"), e));
+                    // Error message when statistics calculation fails
                 }
             }
-            Err(e) => {
-                error.set(t!("error-database", error: e.to_string())); // Database connection error
+
+            // Load trend data (last 30 days)
+            match services::analytics_service::load_recent_trend(30).await {
+                Ok(data) => trend.set(data),
+                Err(e) => error.set(format!("{}: {}", t!("error-trend-load"), e)), // Error message when loading trend data fails
             }
-        }
+        });
+    };
+
+    let mut export_report = move || {
+        let options = services::report_service::ReportOptions {
+            start: period_start(&selected_period()),
+            end: chrono::Local::now().date_naive(),
+            include_photos: include_photos(),
+        };
+        report_status.set(t!("report-creating"));
+        report_file.set(None);
+        spawn(async move {
+            match services::report_service::export_flock_report_async(options).await {
+                Ok(path) => {
+                    report_status.set(format!(
+                        "✅ {}",
+                        t!("report-success", path: path.display().to_string())
+                    ));
+                    report_file.set(Some(path));
+                }
+                Err(e) => {
+                    report_status.set(format!("❌ {}", t!("report-error", error: e.to_string())))
+                }
+            }
+        });
     };
 
     // Load on mount and when period changes
@@ -197,13 +202,55 @@ pub fn StatisticsScreen(on_navigate: EventHandler<Screen>) -> Element {
                 }
             }
 
+            // PDF-Bericht für den gewählten Zeitraum
+            div {
+                class: "card",
+                style: "margin-top: 12px;",
+                h2 {
+                    style: "margin: 0 0 8px 0; font-size: 18px; color: #333;",
+                    "📄 "
+                    {t!("report-title")}
+                }
+                p {
+                    style: "margin: 0 0 12px 0; font-size: 13px; color: #666;",
+                    {t!("report-description")}
+                }
+                label {
+                    style: "display: flex; align-items: center; gap: 8px; font-size: 14px; margin-bottom: 12px;",
+                    input {
+                        r#type: "checkbox",
+                        checked: include_photos(),
+                        onchange: move |e| include_photos.set(e.checked()),
+                    }
+                    {t!("report-include-photos")}
+                }
+                button {
+                    class: "btn-secondary",
+                    style: "width: 100%;",
+                    onclick: move |_| export_report(),
+                    {t!("report-button")}
+                }
+                if !report_status().is_empty() {
+                    p {
+                        style: "margin: 12px 0 0 0; font-size: 13px; color: #333; word-break: break-all;",
+                        "{report_status}"
+                    }
+                }
+                if let Some(path) = report_file() {
+                    ShareButton { path }
+                }
+            }
+
             // Navigation
             div {
                 style: "margin-top: 20px;",
                 button {
                     class: "btn-primary",
                     style: "width: 100%;",
-                    onclick: move |_| on_navigate.call(Screen::EggTracking(None)),
+                    onclick: move |_| on_navigate.call(Screen::EggTracking {
+                            date: None,
+                            coop_id: None,
+                        }),
                     "➕ " // Button to navigate to egg entry form
                     {t!("stats-add-entry")}
                 }
@@ -212,6 +259,17 @@ pub fn StatisticsScreen(on_navigate: EventHandler<Screen>) -> Element {
     }
 }
 
+/// Beginn des gewählten Zeitraums, `None` für alles
+fn period_start(period: &str) -> Option<chrono::NaiveDate> {
+    let days = match period {
+        "week" => 7,
+        "month" => 30,
+        "year" => 365,
+        _ => return None,
+    };
+    Some(chrono::Local::now().date_naive() - chrono::Duration::days(days))
+}
+
 #[component]
 fn StatCard(label: String, value: String, icon: String) -> Element {
     rsx! {
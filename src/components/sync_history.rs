@@ -0,0 +1,189 @@
+use crate::components::transfer_stats::format_bytes;
+use crate::database;
+use crate::models::SyncRun;
+use crate::services::sync_history;
+use crate::Screen;
+use chrono::{Local, TimeZone};
+use dioxus::prelude::*;
+use dioxus_i18n::t;
+
+/// Maximale Anzahl angezeigter Durchläufe
+const HISTORY_LIMIT: usize = 200;
+
+fn load_runs(failures_only: bool, limit: usize) -> Result<Vec<SyncRun>, String> {
+    database::with_db(|conn| sync_history::list_runs(conn, failures_only, limit))
+        .map_err(|e| e.to_string())
+}
+
+/// Gespeicherte Sync-Durchläufe, optional nur die fehlgeschlagenen
+#[component]
+pub fn SyncHistoryScreen(on_navigate: EventHandler<Screen>) -> Element {
+    let mut failures_only = use_signal(|| false);
+    let mut runs = use_signal(Vec::<SyncRun>::new);
+    let mut status_message = use_signal(String::new);
+
+    let mut reload = move || match load_runs(failures_only(), HISTORY_LIMIT) {
+        Ok(list) => {
+            runs.set(list);
+            status_message.set(String::new());
+        }
+        Err(e) => status_message.set(format!("❌ {}", t!("error-load-failed", error: e))),
+    };
+
+    use_effect(move || {
+        reload();
+    });
+
+    rsx! {
+        div { style: "padding: 16px; max-width: 600px; margin: 0 auto;",
+            // Header
+            div { style: "display: flex; align-items: center; margin-bottom: 16px;",
+                button {
+                    class: "btn-back",
+                    onclick: move |_| on_navigate.call(Screen::Settings),
+                    "← "
+                }
+                h1 { style: "margin: 0; font-size: 24px; color: #0066cc;",
+                    "📝 "
+                    {t!("sync-history-title")}
+                }
+            }
+
+            div { style: "display: flex; justify-content: space-between; align-items: center; gap: 8px; margin-bottom: 12px;",
+                label { style: "display: flex; align-items: center; gap: 8px; font-size: 14px;",
+                    input {
+                        r#type: "checkbox",
+                        checked: failures_only(),
+                        onchange: move |e| failures_only.set(e.checked()),
+                    }
+                    {t!("sync-history-failures-only")}
+                }
+                button {
+                    style: "padding: 8px 12px; background: #ffe6e6; color: #cc0000; font-size: 14px; border: 1px solid #ffcccc;",
+                    disabled: runs().is_empty(),
+                    onclick: move |_| {
+                        match database::with_db(sync_history::clear_history) {
+                            Ok(()) => runs.set(Vec::new()),
+                            Err(e) => status_message.set(format!("❌ {}", e)),
+                        }
+                    },
+                    {t!("sync-history-clear")}
+                }
+            }
+
+            if !status_message().is_empty() {
+                p { style: "margin: 0 0 12px 0; font-size: 14px;", "{status_message}" }
+            }
+
+            if runs().is_empty() {
+                p { style: "text-align: center; padding: 40px; color: #999;",
+                    {t!("sync-history-empty")}
+                }
+            } else {
+                div { style: "display: flex; flex-direction: column; gap: 6px;",
+                    for run in runs() {
+                        SyncRunRow { key: "{run.id}", run: run.clone() }
+                    }
+                }
+            }
+
+            p { style: "margin: 12px 0 0 0; font-size: 12px; color: #666;",
+                {t!("sync-history-retention", days: sync_history::HISTORY_RETENTION_DAYS)}
+            }
+        }
+    }
+}
+
+/// Neueste Durchläufe als Vorschau in den Einstellungen
+#[component]
+pub fn SyncHistoryPreview(on_navigate: EventHandler<Screen>) -> Element {
+    let mut runs = use_signal(|| load_runs(false, 5).unwrap_or_default());
+
+    // Hintergrund-Sync schreibt laufend neue Einträge
+    use_effect(move || {
+        spawn(async move {
+            loop {
+                tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+                runs.set(load_runs(false, 5).unwrap_or_default());
+            }
+        });
+    });
+
+    rsx! {
+        div { style: "margin-top: 16px; padding: 12px; background: #fff; border-radius: 8px; border: 1px solid #ddd;",
+            h3 { style: "margin: 0 0 8px 0; font-size: 16px;",
+                "📝 "
+                {t!("sync-history-title")}
+            }
+            if runs().is_empty() {
+                p { style: "margin: 0; font-size: 12px; color: #666;", {t!("sync-history-empty")} }
+            } else {
+                div { style: "display: flex; flex-direction: column; gap: 4px;",
+                    for run in runs() {
+                        SyncRunRow { key: "{run.id}", run: run.clone() }
+                    }
+                }
+            }
+            button {
+                style: "width: 100%; margin-top: 8px; padding: 8px; font-size: 14px;",
+                onclick: move |_| on_navigate.call(Screen::SyncHistory),
+                {t!("sync-history-show-all")}
+            }
+        }
+    }
+}
+
+#[component]
+fn SyncRunRow(run: SyncRun) -> Element {
+    let started = Local
+        .timestamp_millis_opt(run.started_at)
+        .single()
+        .map(|dt| {
+            dt.format(&format!("%d.%m.%Y {}", t!("log-time-format")))
+                .to_string()
+        })
+        .unwrap_or_default();
+    let seconds = run.duration_ms as f64 / 1000.0;
+    let summary = t!(
+        "sync-history-summary",
+        ops_up: run.operations_uploaded,
+        ops_down: run.operations_downloaded,
+        photos_up: run.photos_uploaded,
+        photos_down: run.photos_downloaded
+    );
+    let transfer = t!(
+        "sync-history-transfer",
+        up: format_bytes(run.bytes_uploaded),
+        down: format_bytes(run.bytes_downloaded),
+        requests: run.request_count
+    );
+    let color = if run.is_failure() {
+        "#cc0000"
+    } else {
+        "#0066cc"
+    };
+
+    rsx! {
+        div { style: "font-size: 12px; padding: 4px 6px; background: #f8f9fa; border-radius: 4px; border-left: 3px solid {color};",
+            div { style: "display: flex; justify-content: space-between; color: #333;",
+                span {
+                    if run.is_failure() { "❌ " } else { "✅ " }
+                    "{started}"
+                    if run.on_demand {
+                        " · "
+                        {t!("sync-history-manual")}
+                    }
+                }
+                span { style: "color: #666;", "{seconds:.1} s" }
+            }
+            if let Some(error) = &run.error {
+                div { style: "color: #cc0000; margin-top: 2px;", "{error}" }
+            } else {
+                div { style: "color: #666; margin-top: 2px;", "{summary}" }
+            }
+            if run.request_count > 0 {
+                div { style: "color: #999; margin-top: 2px;", "{transfer}" }
+            }
+        }
+    }
+}
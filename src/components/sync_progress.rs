@@ -0,0 +1,63 @@
+use crate::services::sync_progress::{self, SyncPhase};
+use dioxus::prelude::*;
+use dioxus_i18n::t;
+
+fn phase_label(phase: &SyncPhase) -> String {
+    match *phase {
+        SyncPhase::Listing => t!("sync-phase-listing"),
+        SyncPhase::DownloadingOps { done, total } => {
+            t!("sync-phase-downloading-ops", done: done, total: total)
+        }
+        SyncPhase::Applying { ops } => t!("sync-phase-applying", ops: ops),
+        SyncPhase::DownloadingPhotos { done, total } => {
+            t!("sync-phase-downloading-photos", done: done, total: total)
+        }
+        SyncPhase::UploadingOps => t!("sync-phase-uploading-ops"),
+        SyncPhase::UploadingPhotos { done, total } => {
+            t!("sync-phase-uploading-photos", done: done, total: total)
+        }
+        SyncPhase::Idle | SyncPhase::Done(_) | SyncPhase::Failed(_) => String::new(),
+    }
+}
+
+/// Fortschritt des laufenden Sync-Zyklus (nur sichtbar, solange er läuft)
+#[component]
+pub fn SyncProgressCard() -> Element {
+    let mut phase = use_signal(sync_progress::current);
+
+    use_coroutine(move |_: UnboundedReceiver<()>| async move {
+        let mut rx = sync_progress::subscribe();
+        while rx.changed().await.is_ok() {
+            let current = rx.borrow_and_update().clone();
+            phase.set(current);
+        }
+    });
+
+    let current = phase();
+    if !current.is_running() {
+        return rsx! {};
+    }
+    let label = phase_label(&current);
+    // Phasen ohne Zähler: Balken läuft durch
+    let (percent, opacity) = match current.fraction() {
+        Some(fraction) => ((fraction * 100.0) as usize, "1"),
+        None => (100, "0.4"),
+    };
+
+    rsx! {
+        div { style: "margin-top: 16px; padding: 12px; background: #fff3cd; border-radius: 8px; border-left: 4px solid #ffb300;",
+            div { style: "display: flex; align-items: center; gap: 12px; margin-bottom: 8px;",
+                span { style: "font-size: 24px;", "🔄" }
+                div { style: "flex: 1;",
+                    p { style: "margin: 0; font-weight: 600; font-size: 14px;",
+                        {t!("sync-phase-title")}
+                    }
+                    p { style: "margin: 4px 0 0 0; font-size: 12px; color: #666;", "{label}" }
+                }
+            }
+            div { style: "width: 100%; background: #e0e0e0; border-radius: 4px; height: 8px; overflow: hidden;",
+                div { style: "height: 100%; background: linear-gradient(90deg, #0066cc, #0088ff); transition: width 0.3s ease; width: {percent}%; opacity: {opacity};" }
+            }
+        }
+    }
+}
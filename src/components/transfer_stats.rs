@@ -0,0 +1,138 @@
+use crate::database;
+use crate::models::TransferDay;
+use crate::services::transfer_stats;
+use chrono::{Duration, Local};
+use dioxus::prelude::*;
+use dioxus_i18n::t;
+
+/// Angezeigte Tage im Balkendiagramm
+const CHART_DAYS: usize = 7;
+
+/// Byte-Anzahl lesbar (B, KB, MB, GB)
+pub(crate) fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KB", "MB", "GB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} B", bytes)
+    } else {
+        format!("{:.1} {}", value, UNITS[unit])
+    }
+}
+
+/// Summen für heute, 7 und 30 Tage plus die letzten Tage einzeln
+#[derive(Clone, PartialEq, Default)]
+struct Dashboard {
+    today: Option<TransferDay>,
+    week: Option<TransferDay>,
+    month: Option<TransferDay>,
+    days: Vec<TransferDay>,
+}
+
+fn load_dashboard() -> Dashboard {
+    let Ok(conn) = database::connection() else {
+        return Dashboard::default();
+    };
+    let since = |days: i64| {
+        (Local::now() - Duration::days(days))
+            .format("%Y-%m-%d")
+            .to_string()
+    };
+    Dashboard {
+        today: transfer_stats::total_since(&conn, &since(0)).ok(),
+        week: transfer_stats::total_since(&conn, &since(6)).ok(),
+        month: transfer_stats::total_since(&conn, &since(29)).ok(),
+        days: transfer_stats::list_days(&conn, CHART_DAYS).unwrap_or_default(),
+    }
+}
+
+/// Datenverbrauch des Syncs (für getaktete Verbindungen)
+#[component]
+pub fn TransferStatsCard() -> Element {
+    let mut dashboard = use_signal(load_dashboard);
+
+    // Hintergrund-Sync addiert laufend neuen Verkehr
+    use_effect(move || {
+        spawn(async move {
+            loop {
+                tokio::time::sleep(std::time::Duration::from_secs(15)).await;
+                dashboard.set(load_dashboard());
+            }
+        });
+    });
+
+    let current = dashboard();
+    let periods = [
+        (t!("transfer-today"), current.today.clone()),
+        (t!("transfer-week"), current.week.clone()),
+        (t!("transfer-month"), current.month.clone()),
+    ];
+    let max_bytes = current
+        .days
+        .iter()
+        .map(TransferDay::total_bytes)
+        .max()
+        .unwrap_or(0)
+        .max(1);
+
+    rsx! {
+        div { style: "margin-top: 16px; padding: 12px; background: #fff; border-radius: 8px; border: 1px solid #ddd;",
+            h3 { style: "margin: 0 0 8px 0; font-size: 16px;",
+                "📶 "
+                {t!("transfer-title")}
+            }
+            if current.days.is_empty() {
+                p { style: "margin: 0; font-size: 12px; color: #666;", {t!("transfer-empty")} }
+            } else {
+                div { style: "display: flex; flex-direction: column; gap: 4px; font-size: 12px;",
+                    for (label , total) in periods {
+                        if let Some(total) = total {
+                            div { style: "display: flex; justify-content: space-between; gap: 8px;",
+                                span { style: "color: #333; font-weight: 600;", "{label}" }
+                                span { style: "color: #666;",
+                                    {
+                                        t!(
+                                            "transfer-summary", up : format_bytes(total.bytes_uploaded), down :
+                                            format_bytes(total.bytes_downloaded), requests : total.request_count, runs :
+                                            total.runs
+                                        )
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+                // Letzte Tage als Balken (Höhe relativ zum stärksten Tag)
+                div { style: "display: flex; align-items: flex-end; gap: 4px; height: 60px; margin-top: 12px;",
+                    for day in current.days.iter().rev() {
+                        div {
+                            key: "{day.day}",
+                            style: "flex: 1; display: flex; flex-direction: column; align-items: center; gap: 2px;",
+                            title: "{format_bytes(day.total_bytes())}",
+                            div { style: "width: 100%; background: #0066cc; border-radius: 2px 2px 0 0; height: {day.total_bytes() * 44 / max_bytes}px; min-height: 1px;" }
+                            span { style: "font-size: 10px; color: #999;", "{&day.day[8..]}" }
+                        }
+                    }
+                }
+                button {
+                    style: "width: 100%; margin-top: 8px; padding: 8px; font-size: 14px; background: #e0e0e0; color: #666;",
+                    onclick: move |_| {
+                        if database::with_db(transfer_stats::clear)
+                            .is_ok()
+                        {
+                            dashboard.set(Dashboard::default());
+                        }
+                    },
+                    {t!("transfer-reset")}
+                }
+            }
+            p { style: "margin: 8px 0 0 0; font-size: 12px; color: #666;",
+                {t!("transfer-hint", days: transfer_stats::DAILY_RETENTION_DAYS)}
+            }
+        }
+    }
+}
@@ -0,0 +1,122 @@
+use crate::components::coops::coop_names;
+use crate::database;
+use crate::models::Treatment;
+use crate::services::treatment_service;
+use chrono::NaiveDate;
+use dioxus::prelude::*;
+use dioxus_i18n::t;
+
+const LABEL_STYLE: &str = "display: block; margin-bottom: 6px; font-weight: 600; font-size: 14px;";
+const INPUT_STYLE: &str = "width: 100%; padding: 8px; border: 1px solid #ccc; border-radius: 4px; box-sizing: border-box;";
+
+/// Tage aus einem Zahlenfeld; leer oder ungültig zählt als 0
+fn parse_days(value: &str) -> u32 {
+    value.trim().parse().unwrap_or_default()
+}
+
+/// Kurzbeschreibung für die Ereignisliste, z.B. „Baytril, 0,5 ml/l – 5 Tage,
+/// Eier bis 12.05.2025, Fleisch bis 19.05.2025“
+pub fn treatment_summary(treatment: &Treatment, start: NaiveDate) -> String {
+    let medication = match &treatment.dose {
+        Some(dose) => format!("{}, {}", treatment.medication, dose),
+        None => treatment.medication.clone(),
+    };
+    t!(
+        "treatment-summary",
+        medication: medication,
+        days: treatment.duration_days,
+        eggs: treatment.eggs_withheld_until(start).format("%d.%m.%Y").to_string(),
+        meat: treatment.meat_withheld_until(start).format("%d.%m.%Y").to_string()
+    )
+}
+
+/// Angaben zu einer Behandlung: Medikament, Dosis, Dauer und Wartezeiten
+#[component]
+pub fn TreatmentFields(treatment: Signal<Treatment>) -> Element {
+    let current = treatment();
+    let days_field = |label: String, value: u32, set: fn(&mut Treatment, u32)| {
+        rsx! {
+            div { style: "flex: 1; min-width: 0;",
+                label { style: LABEL_STYLE, "{label}" }
+                input {
+                    r#type: "number",
+                    min: "0",
+                    max: "365",
+                    style: INPUT_STYLE,
+                    value: "{value}",
+                    oninput: move |e| set(&mut treatment.write(), parse_days(&e.value())),
+                }
+            }
+        }
+    };
+
+    rsx! {
+        div { style: "margin-bottom: 16px; padding: 12px; background: #f3f8ff; border-radius: 8px; border: 1px solid #cce0f5;",
+            div { style: "margin-bottom: 12px;",
+                label { style: LABEL_STYLE, {t!("field-medication")} }
+                input {
+                    r#type: "text",
+                    style: INPUT_STYLE,
+                    value: "{current.medication}",
+                    oninput: move |e| treatment.write().medication = e.value(),
+                }
+            }
+            div { style: "margin-bottom: 12px;",
+                label { style: LABEL_STYLE, {t!("field-dose")} }
+                input {
+                    r#type: "text",
+                    style: INPUT_STYLE,
+                    placeholder: t!("placeholder-dose"),
+                    value: current.dose.clone().unwrap_or_default(),
+                    oninput: move |e| {
+                        let dose = e.value();
+                        treatment.write().dose = (!dose.trim().is_empty()).then_some(dose);
+                    },
+                }
+            }
+            div { style: "display: flex; gap: 8px;",
+                {days_field(t!("field-treatment-days"), current.duration_days, |t, days| t.duration_days = days)}
+                {days_field(t!("field-egg-withdrawal-days"), current.egg_withdrawal_days, |t, days| t.egg_withdrawal_days = days)}
+                {days_field(t!("field-meat-withdrawal-days"), current.meat_withdrawal_days, |t, days| t.meat_withdrawal_days = days)}
+            }
+        }
+    }
+}
+
+/// Hinweise auf laufende Eier-Wartezeiten am gewählten Tag, je Stall
+///
+/// Ohne laufende Wartezeit wird nichts angezeigt.
+#[component]
+pub fn EggWithdrawalWarnings(date: String) -> Element {
+    let Ok(day) = NaiveDate::parse_from_str(&date, "%Y-%m-%d") else {
+        return rsx! {};
+    };
+    let withdrawals =
+        database::with_db(|conn| treatment_service::egg_withdrawals_by_coop(conn, day))
+            .unwrap_or_else(|e| {
+                log::error!("Fehler beim Laden der Wartezeiten: {}", e);
+                Default::default()
+            });
+    if withdrawals.is_empty() {
+        return rsx! {};
+    }
+    let coops = coop_names();
+    let lines: Vec<String> = withdrawals
+        .into_iter()
+        .map(|(coop_id, until)| {
+            let until = until.format("%d.%m.").to_string();
+            match coop_id.and_then(|id| coops.get(&id).cloned()) {
+                Some(coop) => t!("withdrawal-eggs-coop", coop: coop, date: until),
+                None => t!("withdrawal-eggs-without-coop", date: until),
+            }
+        })
+        .collect();
+
+    rsx! {
+        div { style: "background: #fff3cd; border: 1px solid #ffe08a; color: #856404; padding: 12px; margin-bottom: 16px; border-radius: 8px; font-size: 14px;",
+            for line in lines {
+                div { "💊 {line}" }
+            }
+        }
+    }
+}
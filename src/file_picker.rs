@@ -0,0 +1,94 @@
+//! Dateiauswahl für Import-Archive
+//!
+//! Auf Android öffnet die `MainActivity` den System-Dateiauswahldialog (SAF)
+//! und kopiert das gewählte Archiv in den Cache, auf dem Desktop übernimmt
+//! das `rfd`. Der zurückgegebene Pfad kann direkt an
+//! `export_import_service::import_from_zip` gegeben werden.
+
+use crate::error::AppError;
+use std::path::PathBuf;
+
+#[cfg(target_os = "android")]
+use crate::camera::get_activity_instance;
+#[cfg(target_os = "android")]
+use ndk_context::android_context;
+
+/// Gibt es auf dieser Plattform einen Dateiauswahldialog?
+pub const FILE_PICKER_AVAILABLE: bool = cfg!(any(target_os = "android", feature = "desktop"));
+
+/// Wie lange auf die Auswahl gewartet wird (das Durchsuchen von Cloud-Ordnern
+/// dauert länger als die Auswahl eines Fotos)
+#[cfg(target_os = "android")]
+const PICK_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(300);
+
+#[cfg(target_os = "android")]
+fn read_static_string(
+    env: &mut jni::JNIEnv,
+    cls: &jni::objects::JClass,
+    method: &str,
+) -> Result<Option<String>, AppError> {
+    let Ok(obj) = env
+        .call_static_method(cls, method, "()Ljava/lang/String;", &[])
+        .and_then(|result| result.l())
+    else {
+        return Ok(None);
+    };
+    if obj.is_null() {
+        return Ok(None);
+    }
+    let value: String = env
+        .get_string((&obj).into())
+        .map_err(|e| AppError::Other(format!("String conversion failed: {}", e)))?
+        .into();
+    Ok(Some(value))
+}
+
+#[cfg(target_os = "android")]
+pub fn pick_import_archive() -> Result<PathBuf, AppError> {
+    let vm_ptr = android_context().vm() as *mut *const jni::sys::JNIInvokeInterface_;
+    let vm = unsafe { jni::JavaVM::from_raw(vm_ptr) }
+        .map_err(|e| AppError::Other(format!("JavaVM failed: {}", e)))?;
+    let mut env = vm
+        .attach_current_thread()
+        .map_err(|e| AppError::Other(format!("JNI attach failed: {}", e)))?;
+
+    let (activity, main_cls) = get_activity_instance(&mut env)?;
+    env.call_method(&activity, "launchDocumentPicker", "()V", &[])
+        .map_err(|e| AppError::Other(format!("launchDocumentPicker failed: {}", e)))?;
+
+    let started = std::time::Instant::now();
+    while started.elapsed() < PICK_TIMEOUT {
+        std::thread::sleep(std::time::Duration::from_millis(100));
+
+        if let Some(path) = read_static_string(&mut env, &main_cls, "getLastDocumentPath")? {
+            return Ok(PathBuf::from(path));
+        }
+        if let Some(err) = read_static_string(&mut env, &main_cls, "getLastError")? {
+            return Err(AppError::Other(err));
+        }
+    }
+
+    Err(AppError::Other(
+        "File picker timeout - no selection made".to_string(),
+    ))
+}
+
+#[cfg(all(not(target_os = "android"), feature = "desktop"))]
+pub fn pick_import_archive() -> Result<PathBuf, AppError> {
+    rfd::FileDialog::new()
+        .add_filter(
+            "Backup",
+            &["zip", crate::services::backup_crypto::ENCRYPTED_EXTENSION],
+        )
+        .set_directory(crate::services::export_import_service::get_export_base_dir())
+        .pick_file()
+        .ok_or_else(|| AppError::Other("No file selected".to_string()))
+}
+
+#[allow(dead_code)]
+#[cfg(all(not(target_os = "android"), not(feature = "desktop")))]
+pub fn pick_import_archive() -> Result<PathBuf, AppError> {
+    Err(AppError::Other(
+        "File picker not available on this platform".to_string(),
+    ))
+}
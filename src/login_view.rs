@@ -0,0 +1,69 @@
+//! In-App-Anmeldung für den Nextcloud Login Flow v2
+//!
+//! Auf Android geht beim Wechsel in den externen Browser oft der App-Kontext
+//! verloren. Optional wird die Login-URL deshalb in einem WebView-Dialog der
+//! `MainActivity` angezeigt; das Polling läuft unverändert im Hintergrund.
+
+use crate::error::AppError;
+
+#[cfg(target_os = "android")]
+use crate::camera::get_activity_instance;
+#[cfg(target_os = "android")]
+use jni::objects::{JObject, JValue};
+#[cfg(target_os = "android")]
+use ndk_context::android_context;
+
+/// In-App-Anmeldung wird nur auf Android angeboten
+pub const IN_APP_LOGIN_AVAILABLE: bool = cfg!(target_os = "android");
+
+#[cfg(target_os = "android")]
+pub fn open_login_view(url: &str) -> Result<(), AppError> {
+    let vm_ptr = android_context().vm() as *mut *const jni::sys::JNIInvokeInterface_;
+    let vm = unsafe { jni::JavaVM::from_raw(vm_ptr) }
+        .map_err(|e| AppError::Other(format!("JavaVM failed: {}", e)))?;
+    let mut env = vm
+        .attach_current_thread()
+        .map_err(|e| AppError::Other(format!("JNI attach failed: {}", e)))?;
+
+    let (activity, _cls) = get_activity_instance(&mut env)?;
+    let url = env
+        .new_string(url)
+        .map_err(|e| AppError::Other(format!("new_string failed: {}", e)))?;
+
+    env.call_method(
+        &activity,
+        "openLoginView",
+        "(Ljava/lang/String;)V",
+        &[JValue::Object(&JObject::from(url))],
+    )
+    .map_err(|e| AppError::Other(format!("openLoginView failed: {}", e)))?;
+    Ok(())
+}
+
+/// Schließt den Anmelde-Dialog und holt die App-Oberfläche zurück in den Vordergrund
+#[cfg(target_os = "android")]
+pub fn close_login_view() -> Result<(), AppError> {
+    let vm_ptr = android_context().vm() as *mut *const jni::sys::JNIInvokeInterface_;
+    let vm = unsafe { jni::JavaVM::from_raw(vm_ptr) }
+        .map_err(|e| AppError::Other(format!("JavaVM failed: {}", e)))?;
+    let mut env = vm
+        .attach_current_thread()
+        .map_err(|e| AppError::Other(format!("JNI attach failed: {}", e)))?;
+
+    let (activity, _cls) = get_activity_instance(&mut env)?;
+    env.call_method(&activity, "closeLoginView", "()V", &[])
+        .map_err(|e| AppError::Other(format!("closeLoginView failed: {}", e)))?;
+    Ok(())
+}
+
+#[cfg(not(target_os = "android"))]
+pub fn open_login_view(_url: &str) -> Result<(), AppError> {
+    Err(AppError::Other(
+        "In-app login not available on this platform".to_string(),
+    ))
+}
+
+#[cfg(not(target_os = "android"))]
+pub fn close_login_view() -> Result<(), AppError> {
+    Ok(())
+}
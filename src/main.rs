@@ -3,17 +3,20 @@ use dioxus_i18n::prelude::*;
 
 mod camera;
 mod components;
-mod database;
-mod error;
+mod file_picker;
 mod i18n;
-mod image_processing;
-mod models;
-mod services;
+mod login_view;
+mod photo_assets;
+mod share;
+mod sync_worker;
+
+use stalltagebuch_services::{database, error, models, services};
 
 use components::{
-    AddProfileScreen, EggHistoryScreen, EggTrackingScreen, EventAdd, EventEditScreen, HomeScreen,
-    NavigationBar, ProfileDetailScreen, ProfileEditScreen, ProfileListScreen, SettingsScreen,
-    StatisticsScreen,
+    AddProfileScreen, BackupImportScreen, EggHistoryScreen, EggTrackingScreen, EventAdd,
+    EventEditScreen, HomeScreen, NavigationBar, ProfileDetailScreen, ProfileEditScreen,
+    ProfileListScreen, RecoveryScreen, SearchScreen, SettingsScreen, SpreadsheetImportScreen,
+    StatisticsScreen, SyncHistoryScreen,
 };
 
 const FAVICON: Asset = asset!("/assets/favicon.ico");
@@ -64,31 +67,91 @@ pub enum Screen {
         event_id: String,
         quail_id: String,
     },
-    EggTracking(Option<String>), // Date in YYYY-MM-DD format
+    EggTracking {
+        date: Option<String>, // Date in YYYY-MM-DD format
+        coop_id: Option<String>,
+    },
     EggHistory,
     Statistics,
+    Search,
     Settings,
+    SyncHistory,
+    SpreadsheetImport,
+    BackupImport(std::path::PathBuf),
 }
 
 #[component]
 fn App() -> Element {
     let mut current_screen = use_signal(|| Screen::Home);
     use_init_i18n(i18n::init_i18n);
+    photo_assets::use_photo_asset_handler();
+
+    // Startup health check (DB, schema, storage, credentials)
+    let mut health_report = use_signal(services::health_check::run_startup_checks);
+
+    // Daily local database snapshot and scheduled exports (checked hourly
+    // while the app runs)
+    use_effect(move || {
+        if !health_report().is_healthy() {
+            return;
+        }
+        spawn(async move {
+            loop {
+                match database::with_db_async(services::backup_service::snapshot_if_due).await {
+                    Ok(Some(snapshot)) => log::info!("Backup created: {}", snapshot.path.display()),
+                    Ok(None) => {}
+                    Err(e) => log::warn!("Failed to create backup: {}", e),
+                }
+                match database::connection() {
+                    Ok(conn) => match services::scheduled_export::run_if_due(&conn).await {
+                        Ok(Some(report)) => log::info!("Scheduled export: {}", report.file_name),
+                        Ok(None) => {}
+                        Err(e) => log::warn!("Scheduled export failed: {}", e),
+                    },
+                    Err(e) => log::warn!("Failed to check scheduled export: {}", e),
+                }
+                tokio::time::sleep(std::time::Duration::from_secs(60 * 60)).await;
+            }
+        });
+    });
 
     // Auto-start background sync if configured
-    use_effect(move || match database::init_database() {
-        Ok(conn) => match services::sync_service::load_sync_settings(&conn) {
-            Ok(Some(settings)) if settings.enabled => {
-                log::info!("Auto-starting background sync");
-                services::background_sync::start_background_sync();
+    use_effect(move || {
+        if !health_report().is_healthy() {
+            return;
+        }
+        match database::connection() {
+            Ok(conn) => {
+                // Thumbnails interrupted by a previous app exit
+                if let Err(e) = services::thumbnail_queue::requeue_missing(&conn) {
+                    log::warn!("Failed to requeue thumbnails: {}", e);
+                }
+                match services::sync_service::load_sync_settings(&conn) {
+                    Ok(Some(settings)) if settings.enabled => {
+                        log::info!("Auto-starting background sync");
+                        services::background_sync::start_background_sync();
+                    }
+                    _ => {}
+                }
+            }
+            Err(e) => {
+                log::error!("Failed to check sync settings: {}", e);
             }
-            _ => {}
-        },
-        Err(e) => {
-            log::error!("Failed to check sync settings: {}", e);
         }
     });
 
+    if !health_report().is_healthy() {
+        return rsx! {
+            document::Link { rel: "icon", href: FAVICON }
+            document::Link { rel: "stylesheet", href: MAIN_CSS }
+
+            RecoveryScreen {
+                report: health_report(),
+                on_resolved: move |_| health_report.set(Default::default()),
+            }
+        };
+    }
+
     rsx! {
         document::Link { rel: "icon", href: FAVICON }
         document::Link { rel: "stylesheet", href: MAIN_CSS }
@@ -105,7 +168,8 @@ fn App() -> Element {
                         ProfileListScreen { on_navigate: move |s| current_screen.set(s) }
                     },
                     Screen::ProfileDetail(id) => rsx! {
-                        ProfileDetailScreen { quail_id: id, on_navigate: move |s| current_screen.set(s) }
+                        // Neu aufbauen, wenn im Stammbaum zu einer anderen Wachtel gewechselt wird
+                        ProfileDetailScreen { key: "{id}", quail_id: id, on_navigate: move |s| current_screen.set(s) }
                     },
                     Screen::ProfileEdit(id) => rsx! {
                         ProfileEditScreen { quail_id: id, on_navigate: move |s| current_screen.set(s) }
@@ -125,8 +189,8 @@ fn App() -> Element {
                     Screen::EventEdit { event_id, quail_id } => rsx! {
                         EventEditScreen { event_id, quail_id, on_navigate: move |s| current_screen.set(s) }
                     },
-                    Screen::EggTracking(date_opt) => rsx! {
-                        EggTrackingScreen { date: date_opt, on_navigate: move |s| current_screen.set(s) }
+                    Screen::EggTracking { date, coop_id } => rsx! {
+                        EggTrackingScreen { date, coop_id, on_navigate: move |s| current_screen.set(s) }
                     },
                     Screen::EggHistory => rsx! {
                         EggHistoryScreen { on_navigate: move |s| current_screen.set(s) }
@@ -134,9 +198,21 @@ fn App() -> Element {
                     Screen::Statistics => rsx! {
                         StatisticsScreen { on_navigate: move |s| current_screen.set(s) }
                     },
+                    Screen::Search => rsx! {
+                        SearchScreen { on_navigate: move |s| current_screen.set(s) }
+                    },
                     Screen::Settings => rsx! {
                         SettingsScreen { on_navigate: move |s| current_screen.set(s) }
                     },
+                    Screen::SyncHistory => rsx! {
+                        SyncHistoryScreen { on_navigate: move |s| current_screen.set(s) }
+                    },
+                    Screen::SpreadsheetImport => rsx! {
+                        SpreadsheetImportScreen { on_navigate: move |s| current_screen.set(s) }
+                    },
+                    Screen::BackupImport(import_path) => rsx! {
+                        BackupImportScreen { import_path, on_navigate: move |s| current_screen.set(s) }
+                    },
                 }
             }
 
@@ -1,11 +0,0 @@
-pub mod egg_record;
-pub mod photo;
-pub mod quail;
-pub mod quail_event;
-pub mod sync_settings;
-
-pub use egg_record::EggRecord;
-pub use photo::Photo;
-pub use quail::{Gender, Quail, RingColor};
-pub use quail_event::{EventType, QuailEvent};
-pub use sync_settings::SyncSettings;
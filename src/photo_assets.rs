@@ -0,0 +1,49 @@
+//! Asset handler that streams photos to the WebView by UUID and size
+//!
+//! Images reference `services::photo_assets::photo_asset_url` instead of
+//! base64 data URLs, so big galleries no longer keep every file in memory
+//! twice (bytes plus encoded string) inside the DOM.
+
+#[cfg(feature = "desktop")]
+use dioxus::desktop as webview;
+#[cfg(all(feature = "mobile", not(feature = "desktop")))]
+use dioxus::mobile as webview;
+
+/// Registers the photo handler for the lifetime of the calling component
+#[cfg(any(feature = "desktop", feature = "mobile"))]
+pub fn use_photo_asset_handler() {
+    use crate::{database, services::photo_assets};
+    use webview::wry::http::{Response, StatusCode};
+
+    webview::use_asset_handler(photo_assets::PHOTO_ASSET_HANDLER, |request, responder| {
+        let photo = photo_assets::parse_photo_asset_path(request.uri().path()).and_then(
+            |(photo_uuid, size)| {
+                let conn = database::connection().ok()?;
+                match photo_assets::read_photo_asset(&conn, &photo_uuid, size) {
+                    Ok(photo) => photo,
+                    Err(e) => {
+                        log::warn!("Failed to serve photo {}: {}", photo_uuid, e);
+                        None
+                    }
+                }
+            },
+        );
+        let response = match photo {
+            Some((bytes, mime)) => Response::builder()
+                .header("Content-Type", mime)
+                .header("Cache-Control", "no-cache")
+                .body(bytes),
+            None => Response::builder()
+                .status(StatusCode::NOT_FOUND)
+                .body(Vec::new()),
+        };
+        match response {
+            Ok(response) => responder.respond(response),
+            Err(e) => log::error!("Failed to build photo response: {}", e),
+        }
+    });
+}
+
+/// The web build has no custom protocols; photo URLs are not served there
+#[cfg(not(any(feature = "desktop", feature = "mobile")))]
+pub fn use_photo_asset_handler() {}
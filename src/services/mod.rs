@@ -1,16 +0,0 @@
-pub mod analytics_service;
-pub mod background_sync;
-pub mod crdt_service;
-pub mod download_service;
-pub mod egg_service;
-pub mod event_service;
-pub mod export_import_service;
-pub mod operation_capture;
-pub mod photo_service;
-pub mod profile_service;
-pub mod sync_paths;
-pub mod sync_service;
-pub mod upload_service;
-
-pub use egg_service::*;
-pub use profile_service::*;
@@ -0,0 +1,75 @@
+//! Exporte über das Android-Teilen-Menü weitergeben
+//!
+//! Der Export-Ordner liegt auf Android im App-Verzeichnis und ist für
+//! Nutzer kaum zu finden. Nach einem Export kann die Datei deshalb per
+//! `ACTION_SEND` an E-Mail, Messenger oder Drive übergeben werden; die
+//! `MainActivity` stellt sie dazu über den `FileProvider` bereit.
+
+use crate::error::AppError;
+use std::path::Path;
+
+#[cfg(target_os = "android")]
+use crate::camera::get_activity_instance;
+#[cfg(target_os = "android")]
+use jni::objects::{JObject, JValue};
+#[cfg(target_os = "android")]
+use ndk_context::android_context;
+
+/// Teilen wird nur auf Android angeboten
+pub const SHARE_AVAILABLE: bool = cfg!(target_os = "android");
+
+/// MIME-Typ für das Teilen-Menü anhand der Dateiendung
+#[cfg(target_os = "android")]
+fn mime_type(path: &Path) -> &'static str {
+    let extension = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(str::to_ascii_lowercase);
+    match extension.as_deref() {
+        Some("zip") => "application/zip",
+        Some("csv") => "text/csv",
+        Some("pdf") => "application/pdf",
+        _ => "application/octet-stream",
+    }
+}
+
+#[cfg(target_os = "android")]
+pub fn share_file(path: &Path, title: &str) -> Result<(), AppError> {
+    let vm_ptr = android_context().vm() as *mut *const jni::sys::JNIInvokeInterface_;
+    let vm = unsafe { jni::JavaVM::from_raw(vm_ptr) }
+        .map_err(|e| AppError::Other(format!("JavaVM failed: {}", e)))?;
+    let mut env = vm
+        .attach_current_thread()
+        .map_err(|e| AppError::Other(format!("JNI attach failed: {}", e)))?;
+
+    let (activity, _cls) = get_activity_instance(&mut env)?;
+    let path_str = env
+        .new_string(path.to_string_lossy())
+        .map_err(|e| AppError::Other(format!("new_string failed: {}", e)))?;
+    let mime = env
+        .new_string(mime_type(path))
+        .map_err(|e| AppError::Other(format!("new_string failed: {}", e)))?;
+    let title = env
+        .new_string(title)
+        .map_err(|e| AppError::Other(format!("new_string failed: {}", e)))?;
+
+    env.call_method(
+        &activity,
+        "shareFile",
+        "(Ljava/lang/String;Ljava/lang/String;Ljava/lang/String;)V",
+        &[
+            JValue::Object(&JObject::from(path_str)),
+            JValue::Object(&JObject::from(mime)),
+            JValue::Object(&JObject::from(title)),
+        ],
+    )
+    .map_err(|e| AppError::Other(format!("shareFile failed: {}", e)))?;
+    Ok(())
+}
+
+#[cfg(not(target_os = "android"))]
+pub fn share_file(_path: &Path, _title: &str) -> Result<(), AppError> {
+    Err(AppError::Other(
+        "Sharing not available on this platform".to_string(),
+    ))
+}
@@ -0,0 +1,76 @@
+//! JNI-Einstieg für den `SyncWorker` (WorkManager, siehe `MainActivity.kt`)
+//!
+//! WorkManager startet den App-Prozess für die Hintergrund-Synchronisation
+//! gegebenenfalls ohne Activity. Dann fehlt der `ndk_context`, den die
+//! Datenbank für das App-Verzeichnis braucht; er wird hier aus dem
+//! Application-Context des Workers gesetzt.
+
+#[cfg(target_os = "android")]
+use crate::services::background_sync::{self, ScheduledSyncOutcome};
+#[cfg(target_os = "android")]
+use jni::objects::{JClass, JObject};
+#[cfg(target_os = "android")]
+use jni::sys::jstring;
+#[cfg(target_os = "android")]
+use jni::JNIEnv;
+
+/// Setzt den `ndk_context`, falls noch keine Activity ihn gesetzt hat
+#[cfg(target_os = "android")]
+fn ensure_android_context(env: &mut JNIEnv, context: &JObject) -> Result<(), String> {
+    // `android_context()` panikt ohne Initialisierung, eine Abfrage gibt es nicht
+    let initialized = std::panic::catch_unwind(ndk_context::android_context).is_ok();
+    if initialized {
+        return Ok(());
+    }
+
+    let vm = env
+        .get_java_vm()
+        .map_err(|e| format!("JavaVM failed: {}", e))?;
+    // Der Context muss den Aufruf überleben: globale Referenz, die nie freigegeben wird
+    let global = env
+        .new_global_ref(context)
+        .map_err(|e| format!("new_global_ref failed: {}", e))?;
+    unsafe {
+        ndk_context::initialize_android_context(
+            vm.get_java_vm_pointer() as *mut std::ffi::c_void,
+            global.as_obj().as_raw() as *mut std::ffi::c_void,
+        );
+    }
+    std::mem::forget(global);
+    Ok(())
+}
+
+/// `SyncWorker.runSync(context)`: ein Sync-Zyklus, Ergebnis als `status|...`
+#[cfg(target_os = "android")]
+#[no_mangle]
+pub extern "system" fn Java_dev_dioxus_main_SyncWorker_runSync<'local>(
+    mut env: JNIEnv<'local>,
+    _class: JClass<'local>,
+    context: JObject<'local>,
+) -> jstring {
+    crate::init_logger();
+
+    let result = match ensure_android_context(&mut env, &context) {
+        Ok(()) => match background_sync::run_scheduled_sync() {
+            ScheduledSyncOutcome::Synced(stats) => format!(
+                "ok|{}|{}",
+                stats.operations_downloaded, stats.photos_uploaded
+            ),
+            ScheduledSyncOutcome::Skipped => "skip".to_string(),
+            ScheduledSyncOutcome::Retry(msg) => format!("retry|{}", msg),
+            ScheduledSyncOutcome::Failed(msg) => format!("fail|{}", msg),
+        },
+        Err(msg) => {
+            log::error!("SyncWorker: Android-Kontext fehlt: {}", msg);
+            format!("retry|{}", msg)
+        }
+    };
+
+    match env.new_string(result) {
+        Ok(s) => s.into_raw(),
+        Err(e) => {
+            log::error!("SyncWorker: Ergebnis konnte nicht übergeben werden: {}", e);
+            std::ptr::null_mut()
+        }
+    }
+}
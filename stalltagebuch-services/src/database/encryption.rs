@@ -0,0 +1,652 @@
+//! Verschlüsselte Datenbank mit SQLCipher (Feature `sqlcipher`)
+//!
+//! Gesundheitsnotizen und Sync-Zugangsdaten liegen sonst im Klartext im
+//! App-Speicher. Ist ein Datenbankschlüssel hinterlegt, öffnet
+//! [`init_database`](super::init_database) die Datei mit `PRAGMA key`.
+//!
+//! Der Schlüssel (32 Zufallsbytes) liegt im [`KeyStore`]: auf Android mit
+//! einem Schlüssel aus dem Android Keystore verschlüsselt, sonst in einer
+//! Datei neben der Datenbank, die nur der Benutzer lesen darf.
+//!
+//! Umschlüsseln geht nicht im laufenden Betrieb, weil andere Verbindungen
+//! (Hintergrund-Sync) noch auf die alte Datei schreiben könnten.
+//! [`enable_encryption`] hinterlegt daher nur den Schlüssel,
+//! [`disable_encryption`] setzt eine Markierung; [`apply_pending`] erledigt
+//! beim nächsten Start vor allen anderen Zugriffen den Export per
+//! `sqlcipher_export` in eine neue Datei und ersetzt die alte. Bis dahin
+//! bleibt die Datei lesbar wie bisher.
+
+use crate::error::AppError;
+use rusqlite::Connection;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+
+/// Wurde die App mit SQLCipher gebaut?
+pub const AVAILABLE: bool = cfg!(feature = "sqlcipher");
+
+const KEY_LEN: usize = 32;
+/// Die ersten 16 Bytes jeder unverschlüsselten SQLite-Datei
+const PLAINTEXT_HEADER: &[u8] = b"SQLite format 3\0";
+
+/// Schlüssel der Datenbankdatei
+#[derive(Clone, PartialEq, Eq)]
+pub struct DatabaseKey([u8; KEY_LEN]);
+
+impl std::fmt::Debug for DatabaseKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("DatabaseKey(..)")
+    }
+}
+
+impl DatabaseKey {
+    pub fn generate() -> Self {
+        use rand::Rng;
+        let mut bytes = [0u8; KEY_LEN];
+        rand::rng().fill(&mut bytes);
+        Self(bytes)
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, AppError> {
+        let bytes: [u8; KEY_LEN] = bytes
+            .try_into()
+            .map_err(|_| AppError::Validation("Datenbankschlüssel ungültig".to_string()))?;
+        Ok(Self(bytes))
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    /// Rohschlüssel in SQLCipher-Schreibweise (`x'…'`, ohne Ableitung)
+    fn sql_literal(&self) -> String {
+        let hex: String = self.0.iter().map(|b| format!("{:02x}", b)).collect();
+        format!("x'{}'", hex)
+    }
+}
+
+/// Ablage des Datenbankschlüssels
+pub trait KeyStore: Send + Sync {
+    fn load(&self) -> Result<Option<DatabaseKey>, AppError>;
+    fn store(&self, key: &DatabaseKey) -> Result<(), AppError>;
+    fn delete(&self) -> Result<(), AppError>;
+}
+
+/// Schlüssel als Datei mit Rechten 0600
+///
+/// Schützt nur gegen andere Benutzer und Backups der Datenbank allein, nicht
+/// gegen jemanden mit Zugriff auf das Benutzerkonto.
+pub struct FileKeyStore {
+    path: PathBuf,
+}
+
+impl FileKeyStore {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+impl KeyStore for FileKeyStore {
+    fn load(&self) -> Result<Option<DatabaseKey>, AppError> {
+        match std::fs::read(&self.path) {
+            Ok(bytes) => DatabaseKey::from_bytes(&bytes).map(Some),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn store(&self, key: &DatabaseKey) -> Result<(), AppError> {
+        write_private(&self.path, key.as_bytes())
+    }
+
+    fn delete(&self) -> Result<(), AppError> {
+        remove_if_exists(&self.path)
+    }
+}
+
+/// Schreibt eine Datei, die nur der Benutzer lesen darf
+fn write_private(path: &Path, data: &[u8]) -> Result<(), AppError> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let tmp = path.with_extension("tmp");
+    std::fs::write(&tmp, data)?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(&tmp, std::fs::Permissions::from_mode(0o600))?;
+    }
+    std::fs::rename(&tmp, path)?;
+    Ok(())
+}
+
+fn remove_if_exists(path: &Path) -> Result<(), AppError> {
+    match std::fs::remove_file(path) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+        _ => Ok(()),
+    }
+}
+
+/// Ablage dieses Systems: Android Keystore oder [`FileKeyStore`]
+pub fn system_key_store() -> Arc<dyn KeyStore> {
+    let path = key_file_path(&super::get_database_path());
+    #[cfg(target_os = "android")]
+    {
+        Arc::new(android::KeystoreWrapped::new(path))
+    }
+    #[cfg(not(target_os = "android"))]
+    {
+        Arc::new(FileKeyStore::new(path))
+    }
+}
+
+static KEY_STORE: RwLock<Option<Arc<dyn KeyStore>>> = RwLock::new(None);
+
+/// Ersetzt die Ablage des Systems (Tests)
+pub fn set_key_store(store: Arc<dyn KeyStore>) {
+    *KEY_STORE.write().unwrap_or_else(|e| e.into_inner()) = Some(store);
+}
+
+fn key_store() -> Arc<dyn KeyStore> {
+    KEY_STORE
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone()
+        .unwrap_or_else(system_key_store)
+}
+
+pub(crate) fn key_file_path(db_path: &Path) -> PathBuf {
+    db_path.with_extension("db.key")
+}
+
+/// Markierung: beim nächsten Start entschlüsseln
+pub(crate) fn decrypt_marker_path(db_path: &Path) -> PathBuf {
+    db_path.with_extension("db.decrypt-pending")
+}
+
+/// Beginnt die Datei mit dem SQLite-Header (also unverschlüsselt)?
+pub fn is_plaintext(path: &Path) -> bool {
+    use std::io::Read;
+    let mut header = [0u8; 16];
+    std::fs::File::open(path)
+        .and_then(|mut file| file.read_exact(&mut header))
+        .map(|()| header == PLAINTEXT_HEADER)
+        .unwrap_or(false)
+}
+
+/// Zustand der Verschlüsselung
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EncryptionState {
+    Off,
+    /// Schlüssel hinterlegt, Datei wird beim nächsten Start verschlüsselt
+    PendingEncrypt,
+    On,
+    /// Datei wird beim nächsten Start entschlüsselt
+    PendingDecrypt,
+}
+
+pub fn state() -> Result<EncryptionState, AppError> {
+    state_of(&super::get_database_path(), key_store().as_ref())
+}
+
+fn state_of(db_path: &Path, store: &dyn KeyStore) -> Result<EncryptionState, AppError> {
+    if store.load()?.is_none() {
+        return Ok(EncryptionState::Off);
+    }
+    if decrypt_marker_path(db_path).exists() {
+        Ok(EncryptionState::PendingDecrypt)
+    } else if is_plaintext(db_path) {
+        Ok(EncryptionState::PendingEncrypt)
+    } else {
+        Ok(EncryptionState::On)
+    }
+}
+
+/// Öffnet die Datenbankdatei, mit Schlüssel sofern sie verschlüsselt ist
+pub(crate) fn open(db_path: &Path) -> Result<Connection, AppError> {
+    open_with(db_path, key_store().as_ref())
+}
+
+fn open_with(db_path: &Path, store: &dyn KeyStore) -> Result<Connection, AppError> {
+    let key = store.load()?;
+    let conn = Connection::open(db_path)?;
+    match key {
+        // Noch nicht umgestellt: bis zum nächsten Start im Klartext
+        Some(_) if is_plaintext(db_path) => {}
+        Some(key) => apply_key(&conn, &key)?,
+        None => {}
+    }
+    Ok(conn)
+}
+
+fn apply_key(conn: &Connection, key: &DatabaseKey) -> Result<(), AppError> {
+    if !AVAILABLE {
+        return Err(AppError::Other(
+            "Datenbank ist verschlüsselt, aber die App wurde ohne SQLCipher gebaut".to_string(),
+        ));
+    }
+    conn.pragma_update(None, "key", key.sql_literal())?;
+    // Erst der erste Zugriff zeigt, ob der Schlüssel passt
+    conn.query_row("SELECT COUNT(*) FROM sqlite_master", [], |row| {
+        row.get::<_, i64>(0)
+    })
+    .map_err(|e| AppError::Other(format!("Datenbankschlüssel passt nicht: {}", e)))?;
+    Ok(())
+}
+
+/// Schaltet die Verschlüsselung ein; wirksam ab dem nächsten Start
+pub fn enable_encryption() -> Result<(), AppError> {
+    enable_with(&super::get_database_path(), key_store().as_ref())
+}
+
+fn enable_with(db_path: &Path, store: &dyn KeyStore) -> Result<(), AppError> {
+    if !AVAILABLE {
+        return Err(AppError::Validation(
+            "Diese App wurde ohne SQLCipher gebaut".to_string(),
+        ));
+    }
+    remove_if_exists(&decrypt_marker_path(db_path))?;
+    if store.load()?.is_none() {
+        store.store(&DatabaseKey::generate())?;
+        log::info!("Datenbankschlüssel erzeugt, Verschlüsselung beim nächsten Start");
+    }
+    Ok(())
+}
+
+/// Schaltet die Verschlüsselung aus; wirksam ab dem nächsten Start
+pub fn disable_encryption() -> Result<(), AppError> {
+    disable_with(&super::get_database_path(), key_store().as_ref())
+}
+
+fn disable_with(db_path: &Path, store: &dyn KeyStore) -> Result<(), AppError> {
+    if store.load()?.is_none() {
+        return Ok(());
+    }
+    if is_plaintext(db_path) || !db_path.exists() {
+        // Noch nicht verschlüsselt: Schlüssel einfach verwerfen
+        return store.delete();
+    }
+    write_private(&decrypt_marker_path(db_path), b"")
+}
+
+/// Verschlüsselt oder entschlüsselt die Datei, falls umgestellt wurde
+///
+/// Muss beim Start vor allen anderen Zugriffen auf die Datenbank laufen.
+pub fn apply_pending() -> Result<(), AppError> {
+    apply_pending_with(&super::get_database_path(), key_store().as_ref())?;
+    // Die Datei wurde eventuell ersetzt
+    super::reset_connections();
+    Ok(())
+}
+
+fn apply_pending_with(db_path: &Path, store: &dyn KeyStore) -> Result<(), AppError> {
+    let Some(key) = store.load()? else {
+        return Ok(());
+    };
+    let marker = decrypt_marker_path(db_path);
+    if marker.exists() {
+        if !is_plaintext(db_path) && db_path.exists() {
+            export_database(db_path, Some(&key), None)?;
+            log::info!("Datenbank entschlüsselt");
+        }
+        store.delete()?;
+        remove_if_exists(&marker)?;
+    } else if is_plaintext(db_path) {
+        export_database(db_path, None, Some(&key))?;
+        log::info!("Datenbank verschlüsselt");
+    }
+    Ok(())
+}
+
+/// Schreibt die Datenbank mit neuem Schlüssel (oder ohne) in eine neue Datei
+/// und ersetzt damit die alte
+fn export_database(
+    db_path: &Path,
+    from: Option<&DatabaseKey>,
+    to: Option<&DatabaseKey>,
+) -> Result<(), AppError> {
+    if !AVAILABLE {
+        return Err(AppError::Validation(
+            "Diese App wurde ohne SQLCipher gebaut".to_string(),
+        ));
+    }
+    let target = db_path.with_extension("db.rekey");
+    remove_if_exists(&target)?;
+
+    {
+        let conn = Connection::open(db_path)?;
+        if let Some(key) = from {
+            apply_key(&conn, key)?;
+        }
+        let target_key = to.map(DatabaseKey::sql_literal).unwrap_or_default();
+        conn.execute(
+            "ATTACH DATABASE ?1 AS rekeyed KEY ?2",
+            rusqlite::params![target.to_string_lossy(), target_key],
+        )?;
+        conn.query_row("SELECT sqlcipher_export('rekeyed')", [], |_| Ok(()))?;
+        conn.execute("DETACH DATABASE rekeyed", [])?;
+    }
+
+    std::fs::rename(&target, db_path)?;
+    Ok(())
+}
+
+/// Schlüssel in einer Datei, verschlüsselt mit einem AES-Schlüssel aus dem
+/// Android Keystore (verlässt die Hardware nie)
+#[cfg(target_os = "android")]
+mod android {
+    use super::{remove_if_exists, write_private, DatabaseKey, KeyStore};
+    use crate::error::AppError;
+    use jni::objects::{JByteArray, JObject, JValue};
+    use jni::JNIEnv;
+    use ndk_context::android_context;
+    use std::path::PathBuf;
+
+    const ALIAS: &str = "stalltagebuch-db-key";
+    const GCM_IV_LEN: usize = 12;
+    const GCM_TAG_BITS: i32 = 128;
+    /// `Cipher.ENCRYPT_MODE` / `Cipher.DECRYPT_MODE`
+    const ENCRYPT_MODE: i32 = 1;
+    const DECRYPT_MODE: i32 = 2;
+    /// `KeyProperties.PURPOSE_ENCRYPT | PURPOSE_DECRYPT`
+    const PURPOSES: i32 = 3;
+
+    pub(super) struct KeystoreWrapped {
+        path: PathBuf,
+    }
+
+    impl KeystoreWrapped {
+        pub(super) fn new(path: PathBuf) -> Self {
+            Self { path }
+        }
+    }
+
+    impl KeyStore for KeystoreWrapped {
+        fn load(&self) -> Result<Option<DatabaseKey>, AppError> {
+            let wrapped = match std::fs::read(&self.path) {
+                Ok(bytes) => bytes,
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+                Err(e) => return Err(e.into()),
+            };
+            if wrapped.len() <= GCM_IV_LEN {
+                return Err(AppError::Validation(
+                    "Datenbankschlüssel beschädigt".to_string(),
+                ));
+            }
+            let (iv, ciphertext) = wrapped.split_at(GCM_IV_LEN);
+            let plain = with_env(|env| cipher(env, DECRYPT_MODE, Some(iv), ciphertext))?;
+            DatabaseKey::from_bytes(&plain.1).map(Some)
+        }
+
+        fn store(&self, key: &DatabaseKey) -> Result<(), AppError> {
+            let (iv, ciphertext) = with_env(|env| cipher(env, ENCRYPT_MODE, None, key.as_bytes()))?;
+            let mut wrapped = iv;
+            wrapped.extend_from_slice(&ciphertext);
+            write_private(&self.path, &wrapped)
+        }
+
+        fn delete(&self) -> Result<(), AppError> {
+            remove_if_exists(&self.path)
+        }
+    }
+
+    fn with_env<T>(
+        f: impl FnOnce(&mut JNIEnv) -> Result<T, jni::errors::Error>,
+    ) -> Result<T, AppError> {
+        let vm_ptr = android_context().vm() as *mut jni::sys::JavaVM;
+        let vm = unsafe { jni::JavaVM::from_raw(vm_ptr) }
+            .map_err(|e| AppError::Other(format!("JavaVM creation failed: {}", e)))?;
+        let mut env = vm
+            .attach_current_thread()
+            .map_err(|e| AppError::Other(format!("Failed to attach thread: {}", e)))?;
+        let result = f(&mut env);
+        if result.is_err() {
+            // Java-Exception nicht in spätere JNI-Aufrufe durchreichen
+            let _ = env.exception_clear();
+        }
+        result.map_err(|e| AppError::Other(format!("Android Keystore: {}", e)))
+    }
+
+    /// AES-Schlüssel aus dem Keystore, wird beim ersten Mal erzeugt
+    fn secret_key<'local>(env: &mut JNIEnv<'local>) -> Result<JObject<'local>, jni::errors::Error> {
+        let provider = env.new_string("AndroidKeyStore")?;
+        let alias = env.new_string(ALIAS)?;
+        let key_store = env
+            .call_static_method(
+                "java/security/KeyStore",
+                "getInstance",
+                "(Ljava/lang/String;)Ljava/security/KeyStore;",
+                &[JValue::Object(&provider)],
+            )?
+            .l()?;
+        env.call_method(
+            &key_store,
+            "load",
+            "(Ljava/security/KeyStore$LoadStoreParameter;)V",
+            &[JValue::Object(&JObject::null())],
+        )?;
+
+        let exists = env
+            .call_method(
+                &key_store,
+                "containsAlias",
+                "(Ljava/lang/String;)Z",
+                &[JValue::Object(&alias)],
+            )?
+            .z()?;
+        if !exists {
+            let builder = env.new_object(
+                "android/security/keystore/KeyGenParameterSpec$Builder",
+                "(Ljava/lang/String;I)V",
+                &[JValue::Object(&alias), JValue::Int(PURPOSES)],
+            )?;
+            let string_array = |env: &mut JNIEnv<'local>, value: &str| {
+                let value = env.new_string(value)?;
+                env.new_object_array(1, "java/lang/String", &value)
+            };
+            let block_modes = string_array(env, "GCM")?;
+            env.call_method(
+                &builder,
+                "setBlockModes",
+                "([Ljava/lang/String;)Landroid/security/keystore/KeyGenParameterSpec$Builder;",
+                &[JValue::Object(&block_modes)],
+            )?;
+            let paddings = string_array(env, "NoPadding")?;
+            env.call_method(
+                &builder,
+                "setEncryptionPaddings",
+                "([Ljava/lang/String;)Landroid/security/keystore/KeyGenParameterSpec$Builder;",
+                &[JValue::Object(&paddings)],
+            )?;
+            let spec = env
+                .call_method(
+                    &builder,
+                    "build",
+                    "()Landroid/security/keystore/KeyGenParameterSpec;",
+                    &[],
+                )?
+                .l()?;
+
+            let algorithm = env.new_string("AES")?;
+            let generator = env
+                .call_static_method(
+                    "javax/crypto/KeyGenerator",
+                    "getInstance",
+                    "(Ljava/lang/String;Ljava/lang/String;)Ljavax/crypto/KeyGenerator;",
+                    &[JValue::Object(&algorithm), JValue::Object(&provider)],
+                )?
+                .l()?;
+            env.call_method(
+                &generator,
+                "init",
+                "(Ljava/security/spec/AlgorithmParameterSpec;)V",
+                &[JValue::Object(&spec)],
+            )?;
+            env.call_method(&generator, "generateKey", "()Ljavax/crypto/SecretKey;", &[])?;
+        }
+
+        env.call_method(
+            &key_store,
+            "getKey",
+            "(Ljava/lang/String;[C)Ljava/security/Key;",
+            &[JValue::Object(&alias), JValue::Object(&JObject::null())],
+        )?
+        .l()
+    }
+
+    /// AES-GCM mit dem Keystore-Schlüssel; liefert (IV, Ergebnis)
+    fn cipher(
+        env: &mut JNIEnv,
+        mode: i32,
+        iv: Option<&[u8]>,
+        input: &[u8],
+    ) -> Result<(Vec<u8>, Vec<u8>), jni::errors::Error> {
+        let key = secret_key(env)?;
+        let transformation = env.new_string("AES/GCM/NoPadding")?;
+        let cipher = env
+            .call_static_method(
+                "javax/crypto/Cipher",
+                "getInstance",
+                "(Ljava/lang/String;)Ljavax/crypto/Cipher;",
+                &[JValue::Object(&transformation)],
+            )?
+            .l()?;
+
+        match iv {
+            Some(iv) => {
+                let iv = env.byte_array_from_slice(iv)?;
+                let spec = env.new_object(
+                    "javax/crypto/spec/GCMParameterSpec",
+                    "(I[B)V",
+                    &[JValue::Int(GCM_TAG_BITS), JValue::Object(&iv)],
+                )?;
+                env.call_method(
+                    &cipher,
+                    "init",
+                    "(ILjava/security/Key;Ljava/security/spec/AlgorithmParameterSpec;)V",
+                    &[
+                        JValue::Int(mode),
+                        JValue::Object(&key),
+                        JValue::Object(&spec),
+                    ],
+                )?;
+            }
+            // Beim Verschlüsseln wählt der Keystore den IV selbst
+            None => {
+                env.call_method(
+                    &cipher,
+                    "init",
+                    "(ILjava/security/Key;)V",
+                    &[JValue::Int(mode), JValue::Object(&key)],
+                )?;
+            }
+        }
+
+        let input = env.byte_array_from_slice(input)?;
+        let output = env
+            .call_method(&cipher, "doFinal", "([B)[B", &[JValue::Object(&input)])?
+            .l()?;
+        let output = env.convert_byte_array(JByteArray::from(output))?;
+        let used_iv = env.call_method(&cipher, "getIV", "()[B", &[])?.l()?;
+        let used_iv = env.convert_byte_array(JByteArray::from(used_iv))?;
+        Ok((used_iv, output))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_db() -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("db-encryption-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir.join("stalltagebuch.db")
+    }
+
+    #[test]
+    fn test_file_key_store_roundtrip() {
+        let db = temp_db();
+        let store = FileKeyStore::new(key_file_path(&db));
+        assert_eq!(store.load().unwrap(), None);
+
+        let key = DatabaseKey::generate();
+        store.store(&key).unwrap();
+        assert_eq!(store.load().unwrap(), Some(key));
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(key_file_path(&db))
+                .unwrap()
+                .permissions()
+                .mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+
+        store.delete().unwrap();
+        assert_eq!(store.load().unwrap(), None);
+        assert!(format!("{:?}", DatabaseKey::generate()).ends_with("(..)"));
+    }
+
+    #[test]
+    fn test_plaintext_database_opens_without_key() {
+        let db = temp_db();
+        let store = FileKeyStore::new(key_file_path(&db));
+        {
+            let conn = open_with(&db, &store).unwrap();
+            conn.execute("CREATE TABLE t (x)", []).unwrap();
+        }
+        assert!(is_plaintext(&db));
+        assert_eq!(state_of(&db, &store).unwrap(), EncryptionState::Off);
+        if !AVAILABLE {
+            assert!(enable_with(&db, &store).is_err());
+        }
+    }
+
+    #[cfg(feature = "sqlcipher")]
+    #[test]
+    fn test_encrypt_and_decrypt_on_next_start() {
+        let db = temp_db();
+        let store = FileKeyStore::new(key_file_path(&db));
+        {
+            let conn = open_with(&db, &store).unwrap();
+            conn.execute("CREATE TABLE t (x)", []).unwrap();
+            conn.execute("INSERT INTO t VALUES ('Legenot')", [])
+                .unwrap();
+        }
+
+        enable_with(&db, &store).unwrap();
+        assert_eq!(
+            state_of(&db, &store).unwrap(),
+            EncryptionState::PendingEncrypt
+        );
+        // Bis zum Neustart bleibt die Datei lesbar
+        open_with(&db, &store).unwrap();
+
+        apply_pending_with(&db, &store).unwrap();
+        assert!(!is_plaintext(&db));
+        assert_eq!(state_of(&db, &store).unwrap(), EncryptionState::On);
+        assert!(Connection::open(&db)
+            .unwrap()
+            .query_row("SELECT x FROM t", [], |row| row.get::<_, String>(0))
+            .is_err());
+        let conn = open_with(&db, &store).unwrap();
+        let value: String = conn
+            .query_row("SELECT x FROM t", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(value, "Legenot");
+        drop(conn);
+
+        disable_with(&db, &store).unwrap();
+        assert_eq!(
+            state_of(&db, &store).unwrap(),
+            EncryptionState::PendingDecrypt
+        );
+        apply_pending_with(&db, &store).unwrap();
+        assert!(is_plaintext(&db));
+        assert_eq!(state_of(&db, &store).unwrap(), EncryptionState::Off);
+        let value: String = Connection::open(&db)
+            .unwrap()
+            .query_row("SELECT x FROM t", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(value, "Legenot");
+    }
+}
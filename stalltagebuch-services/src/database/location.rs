@@ -0,0 +1,435 @@
+//! Speicherort von Datenbank und Fotos auf dem Desktop
+//!
+//! Bisher lagen `data/stalltagebuch.db` und die Fotos relativ zum
+//! Arbeitsverzeichnis, je nach Startort also woanders. Jetzt gilt:
+//!
+//! 1. ein in den Einstellungen gewählter Ordner (`location.toml` im
+//!    Konfigurationsverzeichnis des Systems),
+//! 2. sonst das Arbeitsverzeichnis, falls dort schon eine Datenbank liegt
+//!    (alte Installationen, Fotos direkt im Arbeitsverzeichnis),
+//! 3. sonst das Datenverzeichnis des Systems, z.B.
+//!    `~/.local/share/stalltagebuch`.
+//!
+//! [`move_to`] kopiert alle Daten an einen neuen Ort (z.B. einen
+//! synchronisierten Ordner oder ein externes Laufwerk). Der alte Ort bleibt
+//! unangetastet und kann danach von Hand gelöscht werden.
+
+use super::encryption;
+use crate::error::AppError;
+use crate::models::photo::THUMBNAIL_TIERS;
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+
+/// Unterordner in den Verzeichnissen des Systems
+const APP_DIR_NAME: &str = "stalltagebuch";
+const CONFIG_FILE_NAME: &str = "location.toml";
+
+/// Ordner mit allen Daten der App
+#[derive(Debug, Clone, PartialEq)]
+pub struct DataLocation {
+    pub root: PathBuf,
+    /// Alte Ablage: Fotos direkt in `root` statt in `root/photos`
+    pub legacy: bool,
+}
+
+impl DataLocation {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self {
+            root: root.into(),
+            legacy: false,
+        }
+    }
+
+    fn legacy(root: impl Into<PathBuf>) -> Self {
+        Self {
+            root: root.into(),
+            legacy: true,
+        }
+    }
+
+    pub fn database_path(&self) -> PathBuf {
+        self.root.join("data").join("stalltagebuch.db")
+    }
+
+    pub fn photo_dir(&self) -> PathBuf {
+        if self.legacy {
+            self.root.clone()
+        } else {
+            self.root.join("photos")
+        }
+    }
+
+    pub fn export_dir(&self) -> PathBuf {
+        self.root.join("exports")
+    }
+}
+
+/// Inhalt von `location.toml`
+#[derive(Debug, Serialize, Deserialize)]
+struct LocationConfig {
+    data_dir: PathBuf,
+}
+
+/// Ergebnis von [`move_to`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct MoveSummary {
+    pub location: DataLocation,
+    pub photos_copied: usize,
+    pub bytes_copied: u64,
+}
+
+static LOCATION: RwLock<Option<DataLocation>> = RwLock::new(None);
+
+/// Aktueller Speicherort (beim ersten Aufruf ermittelt)
+pub fn current() -> DataLocation {
+    if let Some(location) = LOCATION.read().unwrap_or_else(|e| e.into_inner()).clone() {
+        return location;
+    }
+    let location = resolve_with(
+        config_path().as_deref(),
+        &std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")),
+        default_root().as_deref(),
+    );
+    // Fotos werden ohne weitere Prüfung in den Ordner geschrieben
+    if let Err(e) = std::fs::create_dir_all(location.photo_dir()) {
+        log::warn!(
+            "Fotoverzeichnis {} nicht anlegbar: {}",
+            location.photo_dir().display(),
+            e
+        );
+    }
+    log::info!("Datenverzeichnis: {}", location.root.display());
+    *LOCATION.write().unwrap_or_else(|e| e.into_inner()) = Some(location.clone());
+    location
+}
+
+/// Verwendet `location` statt des ermittelten Orts
+#[cfg(test)]
+pub(crate) fn set_current(location: DataLocation) {
+    *LOCATION.write().unwrap_or_else(|e| e.into_inner()) = Some(location);
+}
+
+/// Standardort im Datenverzeichnis des Systems
+pub fn default_root() -> Option<PathBuf> {
+    dirs::data_dir().map(|dir| dir.join(APP_DIR_NAME))
+}
+
+fn config_path() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join(APP_DIR_NAME).join(CONFIG_FILE_NAME))
+}
+
+fn read_config(path: &Path) -> Option<PathBuf> {
+    let content = std::fs::read_to_string(path).ok()?;
+    match toml::from_str::<LocationConfig>(&content) {
+        Ok(config) => Some(config.data_dir),
+        Err(e) => {
+            log::warn!("{} ist ungültig: {}", path.display(), e);
+            None
+        }
+    }
+}
+
+fn resolve_with(config: Option<&Path>, cwd: &Path, default: Option<&Path>) -> DataLocation {
+    if let Some(root) = config.and_then(read_config) {
+        return DataLocation::new(root);
+    }
+    let legacy = DataLocation::legacy(cwd);
+    if legacy.database_path().exists() {
+        return legacy;
+    }
+    default.map(DataLocation::new).unwrap_or(legacy)
+}
+
+/// Schreibt `location.toml`, beim Standardort wird sie entfernt
+fn write_config(path: &Path, root: &Path, default: Option<&Path>) -> Result<(), AppError> {
+    if default == Some(root) {
+        if path.exists() {
+            std::fs::remove_file(path)?;
+        }
+        return Ok(());
+    }
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let content = toml::to_string(&LocationConfig {
+        data_dir: root.to_path_buf(),
+    })
+    .map_err(|e| AppError::Other(format!("Konfiguration nicht speicherbar: {}", e)))?;
+    std::fs::write(path, content)?;
+    Ok(())
+}
+
+/// Kopiert alle Daten nach `new_root` und verwendet ab sofort diesen Ort
+///
+/// Währenddessen ist keine andere Verbindung zur Datenbank offen (siehe
+/// [`exclusive_access`](super::exclusive_access)): Synchronisation,
+/// Sicherungen und geplante Exporte schreiben sonst noch in den alten Ort,
+/// nachdem er schon kopiert wurde. Blockiert bis dahin, also nicht im
+/// UI-Thread aufrufen.
+pub fn move_to(new_root: &Path) -> Result<MoveSummary, AppError> {
+    let config = config_path()
+        .ok_or_else(|| AppError::Other("Kein Konfigurationsverzeichnis gefunden".to_string()))?;
+    let from = current();
+    let exclusive = super::exclusive_access()?;
+    let conn = encryption::open(&from.database_path())?;
+    let summary = copy_data(&conn, &from, new_root)?;
+    drop(conn);
+    write_config(&config, &summary.location.root, default_root().as_deref())?;
+
+    // Erst umschalten, dann freigeben: neue Verbindungen öffnen die Kopie
+    *LOCATION.write().unwrap_or_else(|e| e.into_inner()) = Some(summary.location.clone());
+    drop(exclusive);
+    log::warn!(
+        "Daten von {} nach {} umgezogen ({} Fotos)",
+        from.root.display(),
+        summary.location.root.display(),
+        summary.photos_copied
+    );
+    Ok(summary)
+}
+
+fn copy_data(
+    conn: &Connection,
+    from: &DataLocation,
+    new_root: &Path,
+) -> Result<MoveSummary, AppError> {
+    if new_root.as_os_str().is_empty() {
+        return Err(AppError::Validation("Kein Ordner angegeben".to_string()));
+    }
+    std::fs::create_dir_all(new_root)?;
+    let new_root = new_root.canonicalize()?;
+    if from.root.canonicalize().ok().as_deref() == Some(new_root.as_path()) {
+        return Err(AppError::Validation(
+            "Die Daten liegen bereits in diesem Ordner".to_string(),
+        ));
+    }
+    let to = DataLocation::new(new_root);
+    let db_path = to.database_path();
+    if db_path.exists() {
+        return Err(AppError::Validation(format!(
+            "Im Ziel liegt bereits eine Datenbank: {}",
+            db_path.display()
+        )));
+    }
+
+    if let Some(parent) = db_path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    // Konsistente Kopie samt WAL-Inhalt (verschlüsselt bleibt verschlüsselt)
+    conn.execute("VACUUM INTO ?1", [db_path.to_string_lossy()])?;
+
+    let mut bytes_copied = std::fs::metadata(&db_path)?.len();
+    let from_db = from.database_path();
+    for (source, target) in [
+        (
+            encryption::key_file_path(&from_db),
+            encryption::key_file_path(&db_path),
+        ),
+        (
+            encryption::decrypt_marker_path(&from_db),
+            encryption::decrypt_marker_path(&db_path),
+        ),
+    ] {
+        if source.exists() {
+            bytes_copied += std::fs::copy(source, target)?;
+        }
+    }
+    if let (Some(from_data), Some(to_data)) = (from_db.parent(), db_path.parent()) {
+        let (_, backup_bytes) =
+            copy_files(&from_data.join("backups"), &to_data.join("backups"), |_| {
+                true
+            })?;
+        bytes_copied += backup_bytes;
+    }
+    let (_, export_bytes) = copy_files(&from.export_dir(), &to.export_dir(), |_| true)?;
+    bytes_copied += export_bytes;
+
+    let photo_dir = to.photo_dir();
+    std::fs::create_dir_all(&photo_dir)?;
+    let (photos_copied, photo_bytes) = if from.legacy {
+        // Im Arbeitsverzeichnis liegt auch Fremdes, nur Fotos der App mitnehmen
+        let names = photo_file_names(conn)?;
+        copy_files(&from.photo_dir(), &photo_dir, |name| names.contains(name))?
+    } else {
+        copy_files(&from.photo_dir(), &photo_dir, |_| true)?
+    };
+
+    Ok(MoveSummary {
+        location: to,
+        photos_copied,
+        bytes_copied: bytes_copied + photo_bytes,
+    })
+}
+
+/// Dateinamen aller Fotos und Thumbnails aus der Datenbank
+fn photo_file_names(conn: &Connection) -> Result<std::collections::HashSet<String>, AppError> {
+    let mut stmt = conn.prepare(
+        "SELECT uuid, path, relative_path, thumbnail_path, thumbnail_small_path, thumbnail_medium_path
+         FROM photos",
+    )?;
+    let rows = stmt.query_map([], |row| {
+        let mut names = vec![row.get::<_, String>(0)?];
+        for column in 1..=5 {
+            if let Some(name) = row.get::<_, Option<String>>(column)? {
+                names.push(name);
+            }
+        }
+        Ok(names)
+    })?;
+
+    let mut names = std::collections::HashSet::new();
+    for row in rows {
+        let mut row = row?.into_iter();
+        let Some(uuid) = row.next() else {
+            continue;
+        };
+        names.extend(THUMBNAIL_TIERS.iter().map(|tier| tier.file_name(&uuid)));
+        // Absolute Pfade liegen außerhalb und bleiben, wo sie sind
+        names.extend(row.filter(|name| !name.is_empty() && Path::new(name).is_relative()));
+    }
+    Ok(names)
+}
+
+/// Kopiert die Dateien (ohne Unterordner) aus `from`, für die `keep` gilt
+fn copy_files(
+    from: &Path,
+    to: &Path,
+    keep: impl Fn(&str) -> bool,
+) -> Result<(usize, u64), AppError> {
+    if !from.is_dir() {
+        return Ok((0, 0));
+    }
+    std::fs::create_dir_all(to)?;
+    let mut count = 0;
+    let mut bytes = 0;
+    for entry in std::fs::read_dir(from)? {
+        let entry = entry?;
+        if !entry.file_type()?.is_file() {
+            continue;
+        }
+        let name = entry.file_name();
+        if !name.to_str().is_some_and(&keep) {
+            continue;
+        }
+        bytes += std::fs::copy(entry.path(), to.join(&name))?;
+        count += 1;
+    }
+    Ok((count, bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::schema;
+
+    fn test_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "stalltagebuch-location-{}-{}",
+            name,
+            uuid::Uuid::new_v4()
+        ));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_resolution_order() {
+        let dir = test_dir("resolve");
+        let cwd = dir.join("cwd");
+        let default = dir.join("default");
+        let config = dir.join("config").join(CONFIG_FILE_NAME);
+        std::fs::create_dir_all(&cwd).unwrap();
+
+        // Neue Installation: Standardort
+        let location = resolve_with(Some(&config), &cwd, Some(&default));
+        assert_eq!(location, DataLocation::new(&default));
+        assert_eq!(location.photo_dir(), default.join("photos"));
+
+        // Alte Installation im Arbeitsverzeichnis bleibt, wo sie ist
+        std::fs::create_dir_all(cwd.join("data")).unwrap();
+        std::fs::write(cwd.join("data").join("stalltagebuch.db"), b"").unwrap();
+        let location = resolve_with(Some(&config), &cwd, Some(&default));
+        assert!(location.legacy);
+        assert_eq!(location.photo_dir(), cwd);
+
+        // Gewählter Ordner geht vor
+        let chosen = dir.join("chosen");
+        write_config(&config, &chosen, Some(&default)).unwrap();
+        assert_eq!(
+            resolve_with(Some(&config), &cwd, Some(&default)),
+            DataLocation::new(&chosen)
+        );
+
+        // Zurück zum Standardort entfernt die Konfiguration
+        write_config(&config, &default, Some(&default)).unwrap();
+        assert!(!config.exists());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_copy_data_from_legacy_layout() {
+        let dir = test_dir("move");
+        let from = DataLocation::legacy(dir.join("old"));
+        std::fs::create_dir_all(from.database_path().parent().unwrap()).unwrap();
+        let conn = Connection::open(from.database_path()).unwrap();
+        schema::init_schema(&conn).unwrap();
+        conn.execute(
+            "INSERT INTO photos (uuid, path, relative_path) VALUES ('p1', '', 'p1.jpg')",
+            [],
+        )
+        .unwrap();
+        std::fs::write(from.photo_dir().join("p1.jpg"), b"jpeg").unwrap();
+        std::fs::write(
+            from.photo_dir().join(THUMBNAIL_TIERS[0].file_name("p1")),
+            b"webp",
+        )
+        .unwrap();
+        // Fremde Datei im Arbeitsverzeichnis
+        std::fs::write(from.photo_dir().join("Cargo.toml"), b"[package]").unwrap();
+        std::fs::write(encryption::key_file_path(&from.database_path()), b"key").unwrap();
+        let backups = from.database_path().parent().unwrap().join("backups");
+        std::fs::create_dir_all(&backups).unwrap();
+        std::fs::write(backups.join("stalltagebuch-20250101-120000.db"), b"old").unwrap();
+        std::fs::create_dir_all(from.export_dir()).unwrap();
+        std::fs::write(
+            from.export_dir()
+                .join("stalltagebuch-export-20250101-120000.zip"),
+            b"zip",
+        )
+        .unwrap();
+
+        let summary = copy_data(&conn, &from, &dir.join("new")).unwrap();
+        let to = summary.location;
+        assert!(!to.legacy);
+        assert_eq!(summary.photos_copied, 2);
+        assert!(to.photo_dir().join("p1.jpg").exists());
+        assert!(!to.photo_dir().join("Cargo.toml").exists());
+        assert!(encryption::key_file_path(&to.database_path()).exists());
+        assert!(to
+            .database_path()
+            .parent()
+            .unwrap()
+            .join("backups")
+            .join("stalltagebuch-20250101-120000.db")
+            .exists());
+        assert!(to
+            .export_dir()
+            .join("stalltagebuch-export-20250101-120000.zip")
+            .exists());
+
+        let copied = Connection::open(to.database_path()).unwrap();
+        let count: i64 = copied
+            .query_row("SELECT COUNT(*) FROM photos", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(count, 1);
+
+        // Kein zweites Mal in denselben Ordner und nicht auf sich selbst
+        assert!(copy_data(&conn, &from, &to.root).is_err());
+        assert!(copy_data(&conn, &from, &from.root).is_err());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
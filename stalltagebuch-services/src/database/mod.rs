@@ -0,0 +1,465 @@
+pub mod encryption;
+#[cfg(not(target_os = "android"))]
+pub mod location;
+pub mod schema;
+
+use crate::error::AppError;
+use rusqlite::Connection;
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+#[cfg(target_os = "android")]
+use jni::objects::JObject;
+#[cfg(target_os = "android")]
+use jni::JNIEnv;
+#[cfg(target_os = "android")]
+use ndk_context::android_context;
+
+/// Returns the app directory (for photos etc.)
+#[cfg(target_os = "android")]
+pub fn get_app_directory() -> Option<PathBuf> {
+    android_files_dir().ok()
+}
+
+#[cfg(not(target_os = "android"))]
+pub fn get_app_directory() -> Option<PathBuf> {
+    Some(location::current().root)
+}
+
+/// Returns the path to the database directory
+pub fn get_database_path() -> PathBuf {
+    #[cfg(target_os = "android")]
+    {
+        android_files_dir()
+            .unwrap_or_else(|_| PathBuf::from("/data/local/tmp/stalltagebuch"))
+            .join("stalltagebuch.db")
+    }
+
+    #[cfg(not(target_os = "android"))]
+    {
+        location::current().database_path()
+    }
+}
+
+#[cfg(target_os = "android")]
+fn android_files_dir() -> Result<PathBuf, AppError> {
+    use jni::JavaVM;
+
+    let vm_ptr = android_context().vm() as *mut jni::sys::JavaVM;
+
+    let vm = unsafe { JavaVM::from_raw(vm_ptr) }
+        .map_err(|e| AppError::Other(format!("JavaVM creation failed: {}", e)))?;
+
+    let mut env = vm
+        .attach_current_thread()
+        .map_err(|e| AppError::Other(format!("Failed to attach thread: {}", e)))?;
+
+    let context_ptr = android_context().context();
+    let context = unsafe { JObject::from_raw(context_ptr as jni::sys::jobject) };
+
+    get_files_dir(&mut env, &context)
+}
+
+#[cfg(target_os = "android")]
+fn get_files_dir(env: &mut JNIEnv, context: &JObject) -> Result<PathBuf, AppError> {
+    let file = env
+        .call_method(context, "getFilesDir", "()Ljava/io/File;", &[])
+        .map_err(|e| AppError::Other(format!("getFilesDir failed: {}", e)))?;
+
+    let file_obj = file
+        .l()
+        .map_err(|e| AppError::Other(format!("Failed to get file object: {}", e)))?;
+
+    let path_jstring = env
+        .call_method(file_obj, "getAbsolutePath", "()Ljava/lang/String;", &[])
+        .map_err(|e| AppError::Other(format!("getAbsolutePath failed: {}", e)))?;
+
+    let path_obj = path_jstring
+        .l()
+        .map_err(|e| AppError::Other(format!("Failed to get path object: {}", e)))?;
+
+    let path_str: String = env
+        .get_string(&path_obj.into())
+        .map_err(|e| AppError::Other(format!("Failed to get string: {}", e)))?
+        .into();
+
+    Ok(PathBuf::from(path_str))
+}
+
+/// Schema of the current database file is up to date (see [`reset_connections`])
+static SCHEMA_READY: AtomicBool = AtomicBool::new(false);
+/// Bumped whenever the database file is replaced; older connections are dropped
+static GENERATION: AtomicU64 = AtomicU64::new(0);
+/// Idle connections, reused by [`connection`]
+static POOL: Mutex<Vec<Connection>> = Mutex::new(Vec::new());
+/// More idle connections than this are closed
+const MAX_IDLE_CONNECTIONS: usize = 4;
+/// Borrowed connections ([`DbConnection`]) that are still open
+static BORROWED: AtomicUsize = AtomicUsize::new(0);
+/// Set while an [`ExclusiveAccess`] guard lives; no connections are handed out
+static EXCLUSIVE: AtomicBool = AtomicBool::new(false);
+/// How long [`exclusive_access`] waits for borrowed connections (a running
+/// sync) to come back
+const EXCLUSIVE_TIMEOUT: Duration = Duration::from_secs(60);
+const EXCLUSIVE_POLL_INTERVAL: Duration = Duration::from_millis(20);
+/// Wait for a concurrent writer (background sync) instead of failing with SQLITE_BUSY
+const BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Page cache per connection in KiB (negative `cache_size` = KiB, not pages)
+const CACHE_SIZE_KIB: i64 = 8 * 1024;
+/// Prepared statements kept per connection; the sync merge alone cycles
+/// through a few dozen (rusqlite keeps 16 by default)
+const STATEMENT_CACHE_CAPACITY: usize = 64;
+
+/// Pragmas for every connection to the database file
+///
+/// WAL lets the UI read while the background sync writes; with the default
+/// rollback journal a single open reader made the sync fail with "database
+/// is locked". `synchronous = NORMAL` is safe in WAL mode (a power loss can
+/// only drop the last transactions, never corrupt the file).
+pub(crate) fn configure_connection(conn: &Connection) -> Result<(), AppError> {
+    conn.busy_timeout(BUSY_TIMEOUT)?;
+    // Stored in the file, but cheap to repeat
+    let mode: String = conn.query_row("PRAGMA journal_mode = WAL", [], |row| row.get(0))?;
+    if !mode.eq_ignore_ascii_case("wal") {
+        log::warn!("WAL nicht verfügbar, Journal-Modus: {}", mode);
+    }
+    // Per connection, not stored in the file
+    conn.pragma_update(None, "synchronous", "NORMAL")?;
+    conn.pragma_update(None, "cache_size", -CACHE_SIZE_KIB)?;
+    conn.pragma_update(None, "foreign_keys", true)?;
+    conn.set_prepared_statement_cache_capacity(STATEMENT_CACHE_CAPACITY);
+    Ok(())
+}
+
+/// `-wal` and `-shm` files that belong to a database file in WAL mode
+pub fn sidecar_files(db_path: &Path) -> [PathBuf; 2] {
+    let name = db_path.as_os_str();
+    ["-wal", "-shm"].map(|suffix| {
+        let mut path = name.to_os_string();
+        path.push(suffix);
+        PathBuf::from(path)
+    })
+}
+
+/// Renames a database file together with its WAL files
+///
+/// A stale `-wal` next to a different database file would be replayed into
+/// it, so the files must always move together.
+pub fn rename_database(from: &Path, to: &Path) -> Result<(), AppError> {
+    std::fs::rename(from, to)?;
+    for (from_sidecar, to_sidecar) in sidecar_files(from).into_iter().zip(sidecar_files(to)) {
+        if from_sidecar.exists() {
+            std::fs::rename(from_sidecar, to_sidecar)?;
+        }
+    }
+    Ok(())
+}
+
+/// Opens a new connection to the database file
+///
+/// The schema is created/migrated on the first open only. Prefer
+/// [`connection`] or [`with_db`], which reuse open connections; this is for
+/// code that needs an owned `Connection`.
+pub fn init_database() -> Result<Connection, AppError> {
+    let db_path = get_database_path();
+
+    // Ensure directory exists
+    if let Some(parent) = db_path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    let conn = encryption::open(&db_path)?;
+    configure_connection(&conn)?;
+
+    // Initialize schema (triggers are created inside init_schema now)
+    if !SCHEMA_READY.load(Ordering::SeqCst) {
+        schema::init_schema(&conn)?;
+        SCHEMA_READY.store(true, Ordering::SeqCst);
+    }
+
+    Ok(conn)
+}
+
+/// Connection borrowed from the pool; goes back when dropped
+pub struct DbConnection {
+    conn: Option<Connection>,
+    generation: u64,
+}
+
+impl Deref for DbConnection {
+    type Target = Connection;
+
+    fn deref(&self) -> &Connection {
+        self.conn.as_ref().expect("connection present until drop")
+    }
+}
+
+impl Drop for DbConnection {
+    fn drop(&mut self) {
+        // Declared first so it drops last, after the connection is closed
+        let _returned = Returned;
+        let Some(conn) = self.conn.take() else {
+            return;
+        };
+        // Checked under the lock, reset_connections bumps the generation there
+        let mut pool = POOL.lock().unwrap_or_else(|e| e.into_inner());
+        // Abandoned transaction or replaced file: close instead of reusing
+        if !conn.is_autocommit() || self.generation != GENERATION.load(Ordering::SeqCst) {
+            return;
+        }
+        if pool.len() < MAX_IDLE_CONNECTIONS {
+            pool.push(conn);
+        }
+    }
+}
+
+/// Counts a borrowed connection as returned once dropped (after it was
+/// closed or put back into the pool)
+struct Returned;
+
+impl Drop for Returned {
+    fn drop(&mut self) {
+        BORROWED.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// Borrows an open connection (or opens one)
+///
+/// The guard may be held across `.await`; other callers get their own
+/// connection meanwhile. Fails while the file is being moved or replaced
+/// (see [`exclusive_access`]).
+pub fn connection() -> Result<DbConnection, AppError> {
+    // Count first, then check: exclusive_access sets the flag first, then
+    // waits for the count, so one of both sees the other
+    BORROWED.fetch_add(1, Ordering::SeqCst);
+    let returned = Returned;
+    if EXCLUSIVE.load(Ordering::SeqCst) {
+        return Err(AppError::Other(
+            "Datenbank wird gerade verschoben oder ersetzt".to_string(),
+        ));
+    }
+    let generation = GENERATION.load(Ordering::SeqCst);
+    let idle = POOL.lock().unwrap_or_else(|e| e.into_inner()).pop();
+    let conn = match idle {
+        Some(conn) => conn,
+        None => init_database()?,
+    };
+    // From now on DbConnection's drop gives the count back
+    std::mem::forget(returned);
+    Ok(DbConnection {
+        conn: Some(conn),
+        generation,
+    })
+}
+
+/// Runs `f` with a pooled connection
+pub fn with_db<T>(f: impl FnOnce(&Connection) -> Result<T, AppError>) -> Result<T, AppError> {
+    let conn = connection()?;
+    f(&conn)
+}
+
+/// Runs `f` with a pooled connection on tokio's blocking thread pool
+///
+/// For loads triggered from the UI (lists, statistics): the query runs
+/// while the render thread keeps drawing. Writes that must be visible to the
+/// very next statement can keep using [`with_db`].
+pub async fn with_db_async<T, F>(f: F) -> Result<T, AppError>
+where
+    T: Send + 'static,
+    F: FnOnce(&Connection) -> Result<T, AppError> + Send + 'static,
+{
+    tokio::task::spawn_blocking(move || with_db(f))
+        .await
+        .map_err(|e| AppError::Other(format!("Database task failed: {}", e)))?
+}
+
+/// Forgets all open connections and the schema state, e.g. after the
+/// database file was moved aside or replaced
+pub fn reset_connections() {
+    let mut pool = POOL.lock().unwrap_or_else(|e| e.into_inner());
+    GENERATION.fetch_add(1, Ordering::SeqCst);
+    pool.clear();
+    drop(pool);
+    SCHEMA_READY.store(false, Ordering::SeqCst);
+}
+
+/// Sole access to the database file, e.g. to move or replace it
+///
+/// While the guard lives no connection is open and [`connection`] fails.
+/// Dropping it forgets the schema state like [`reset_connections`], so the
+/// next connection opens (and migrates) whatever file is in place then.
+pub struct ExclusiveAccess(());
+
+impl Drop for ExclusiveAccess {
+    fn drop(&mut self) {
+        reset_connections();
+        EXCLUSIVE.store(false, Ordering::SeqCst);
+    }
+}
+
+impl ExclusiveAccess {
+    /// Sets the flag and closes idle connections; `None` while another
+    /// guard lives
+    fn try_acquire() -> Option<Self> {
+        EXCLUSIVE
+            .compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst)
+            .ok()?;
+        reset_connections();
+        Some(Self(()))
+    }
+
+    /// Waits for borrowed connections until `deadline`
+    fn in_use(deadline: Instant) -> Result<bool, AppError> {
+        if BORROWED.load(Ordering::SeqCst) == 0 {
+            return Ok(false);
+        }
+        if Instant::now() >= deadline {
+            return Err(AppError::Other(
+                "Datenbank ist noch in Benutzung (Synchronisation?), bitte später erneut versuchen"
+                    .to_string(),
+            ));
+        }
+        Ok(true)
+    }
+}
+
+/// Closes all connections and keeps new ones from opening until the guard
+/// is dropped
+///
+/// Connections borrowed elsewhere are closed when they come back; this waits
+/// for them (at most [`EXCLUSIVE_TIMEOUT`]). Blocks the thread, from async
+/// code use [`exclusive_access_async`].
+pub fn exclusive_access() -> Result<ExclusiveAccess, AppError> {
+    let deadline = Instant::now() + EXCLUSIVE_TIMEOUT;
+    let guard = loop {
+        if let Some(guard) = ExclusiveAccess::try_acquire() {
+            break guard;
+        }
+        if Instant::now() >= deadline {
+            return Err(AppError::Other(
+                "Datenbank wird bereits verschoben oder ersetzt".to_string(),
+            ));
+        }
+        std::thread::sleep(EXCLUSIVE_POLL_INTERVAL);
+    };
+    while ExclusiveAccess::in_use(deadline)? {
+        std::thread::sleep(EXCLUSIVE_POLL_INTERVAL);
+    }
+    Ok(guard)
+}
+
+/// Like [`exclusive_access`], but waits without blocking the runtime
+pub async fn exclusive_access_async() -> Result<ExclusiveAccess, AppError> {
+    let deadline = Instant::now() + EXCLUSIVE_TIMEOUT;
+    let guard = loop {
+        if let Some(guard) = ExclusiveAccess::try_acquire() {
+            break guard;
+        }
+        if Instant::now() >= deadline {
+            return Err(AppError::Other(
+                "Datenbank wird bereits verschoben oder ersetzt".to_string(),
+            ));
+        }
+        tokio::time::sleep(EXCLUSIVE_POLL_INTERVAL).await;
+    };
+    while ExclusiveAccess::in_use(deadline)? {
+        tokio::time::sleep(EXCLUSIVE_POLL_INTERVAL).await;
+    }
+    Ok(guard)
+}
+
+/// Tests the database connection
+#[allow(dead_code)]
+pub fn test_connection() -> Result<(), AppError> {
+    let conn = connection()?;
+
+    // Simple query to test
+    let count: i32 = conn.query_row(
+        "SELECT COUNT(*) FROM sqlite_master WHERE type='table'",
+        [],
+        |row| row.get(0),
+    )?;
+
+    if count < 3 {
+        return Err(AppError::Database(rusqlite::Error::QueryReturnedNoRows));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_readers_do_not_block_writer() {
+        let dir = std::env::temp_dir().join(format!("db-wal-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("test.db");
+
+        let writer = Connection::open(&path).unwrap();
+        configure_connection(&writer).unwrap();
+        writer
+            .execute_batch("CREATE TABLE counter (value INTEGER); INSERT INTO counter VALUES (0);")
+            .unwrap();
+        let reader = Connection::open(&path).unwrap();
+        configure_connection(&reader).unwrap();
+        let journal_mode: String = reader
+            .query_row("PRAGMA journal_mode", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(journal_mode, "wal");
+
+        // Reader holds a read transaction open (e.g. a long list query)
+        reader.execute_batch("BEGIN").unwrap();
+        let before: i64 = reader
+            .query_row("SELECT value FROM counter", [], |row| row.get(0))
+            .unwrap();
+
+        // Writer (background sync) commits on another thread without waiting
+        // (with a rollback journal this fails with SQLITE_BUSY)
+        let started = std::time::Instant::now();
+        std::thread::spawn(move || {
+            writer.busy_timeout(Duration::from_millis(100)).unwrap();
+            writer.execute("UPDATE counter SET value = 1", []).unwrap();
+        })
+        .join()
+        .unwrap();
+        assert!(started.elapsed() < BUSY_TIMEOUT);
+
+        // Reader keeps its snapshot until the transaction ends
+        let during: i64 = reader
+            .query_row("SELECT value FROM counter", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!((before, during), (0, 0));
+        reader.execute_batch("COMMIT").unwrap();
+        let after: i64 = reader
+            .query_row("SELECT value FROM counter", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(after, 1);
+
+        drop(reader);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_rename_moves_wal_files() {
+        let dir = std::env::temp_dir().join(format!("db-rename-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let from = dir.join("a.db");
+        let to = dir.join("b.db");
+        std::fs::write(&from, b"db").unwrap();
+        std::fs::write(dir.join("a.db-wal"), b"wal").unwrap();
+
+        rename_database(&from, &to).unwrap();
+        assert!(to.exists());
+        assert!(dir.join("b.db-wal").exists());
+        assert!(!dir.join("a.db-wal").exists());
+        assert!(!dir.join("b.db-shm").exists());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,1819 @@
+use rusqlite::{Connection, Result};
+
+/// Latest schema version known to this build
+pub const SCHEMA_VERSION: i32 = 37;
+
+/// Reads the highest applied schema version (0 if none)
+pub fn get_schema_version(conn: &Connection) -> Result<i32> {
+    conn.query_row(
+        "SELECT COALESCE(MAX(version), 0) FROM schema_version",
+        [],
+        |row| row.get(0),
+    )
+}
+
+/// Initialize complete database schema for the Quail Diary app
+pub fn init_schema(conn: &Connection) -> Result<()> {
+    // Enable foreign keys
+    conn.execute("PRAGMA foreign_keys = ON", [])?;
+
+    // Schema version table for future migrations
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS schema_version (
+            version INTEGER PRIMARY KEY,
+            applied_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+        )",
+        [],
+    )?;
+
+    // Check if schema already exists
+    let current_version: i32 = conn
+        .query_row(
+            "SELECT version FROM schema_version ORDER BY version DESC LIMIT 1",
+            [],
+            |row| row.get(0),
+        )
+        .unwrap_or(0);
+
+    if current_version < 2 {
+        create_schema(conn)?;
+        conn.execute("INSERT INTO schema_version (version) VALUES (2)", [])?;
+    }
+
+    // Migration to version 3: Add relative_path column
+    if current_version < 3 {
+        migrate_to_v3(conn)?;
+        conn.execute("INSERT INTO schema_version (version) VALUES (3)", [])?;
+    }
+
+    // Migration to version 4: Remove strict photo FK triggers to allow CRDT out-of-order merges
+    if current_version < 4 {
+        migrate_to_v4(conn)?;
+        conn.execute("INSERT INTO schema_version (version) VALUES (4)", [])?;
+    }
+
+    // Migration to version 5: Add initial_upload_done flag to sync_settings
+    if current_version < 5 {
+        migrate_to_v5(conn)?;
+        conn.execute("INSERT INTO schema_version (version) VALUES (5)", [])?;
+    }
+
+    // Migration to version 6: Persist pending Login Flow v2 across app restarts
+    if current_version < 6 {
+        migrate_to_v6(conn)?;
+        conn.execute("INSERT INTO schema_version (version) VALUES (6)", [])?;
+    }
+
+    // Migration to version 7: Flag for app passwords revoked on the server
+    if current_version < 7 {
+        migrate_to_v7(conn)?;
+        conn.execute("INSERT INTO schema_version (version) VALUES (7)", [])?;
+    }
+
+    // Migration to version 8: Pinned server certificate (trust on first use)
+    if current_version < 8 {
+        migrate_to_v8(conn)?;
+        conn.execute("INSERT INTO schema_version (version) VALUES (8)", [])?;
+    }
+
+    // Migration to version 9: Optional HTTP/SOCKS proxy for auth and sync
+    if current_version < 9 {
+        migrate_to_v9(conn)?;
+        conn.execute("INSERT INTO schema_version (version) VALUES (9)", [])?;
+    }
+
+    // Migration to version 10: EXIF metadata of imported photos
+    if current_version < 10 {
+        migrate_to_v10(conn)?;
+        conn.execute("INSERT INTO schema_version (version) VALUES (10)", [])?;
+    }
+
+    // Migration to version 11: Local photo storage limit with LRU eviction
+    if current_version < 11 {
+        migrate_to_v11(conn)?;
+        conn.execute("INSERT INTO schema_version (version) VALUES (11)", [])?;
+    }
+
+    // Migration to version 12: Photo captions and tags
+    if current_version < 12 {
+        migrate_to_v12(conn)?;
+        conn.execute("INSERT INTO schema_version (version) VALUES (12)", [])?;
+    }
+
+    // Migration to version 13: Manual photo order within a quail/event
+    if current_version < 13 {
+        migrate_to_v13(conn)?;
+        conn.execute("INSERT INTO schema_version (version) VALUES (13)", [])?;
+    }
+
+    // Migration to version 14: Photo trash with restore window
+    if current_version < 14 {
+        migrate_to_v14(conn)?;
+        conn.execute("INSERT INTO schema_version (version) VALUES (14)", [])?;
+    }
+
+    // Migration to version 15: Local key for client-side photo encryption
+    if current_version < 15 {
+        migrate_to_v15(conn)?;
+        conn.execute("INSERT INTO schema_version (version) VALUES (15)", [])?;
+    }
+
+    // Migration to version 16: Content checksum for sync integrity checks
+    if current_version < 16 {
+        migrate_to_v16(conn)?;
+        conn.execute("INSERT INTO schema_version (version) VALUES (16)", [])?;
+    }
+
+    // Migration to version 17: Configurable number of parallel photo uploads
+    if current_version < 17 {
+        migrate_to_v17(conn)?;
+        conn.execute("INSERT INTO schema_version (version) VALUES (17)", [])?;
+    }
+
+    // Migration to version 18: Remote photo files changed outside the app
+    if current_version < 18 {
+        migrate_to_v18(conn)?;
+        conn.execute("INSERT INTO schema_version (version) VALUES (18)", [])?;
+    }
+
+    // Migration to version 19: Full hybrid logical clock per synced row
+    if current_version < 19 {
+        migrate_to_v19(conn)?;
+        conn.execute("INSERT INTO schema_version (version) VALUES (19)", [])?;
+    }
+
+    // Migration to version 20: Outbox for batched op uploads
+    if current_version < 20 {
+        migrate_to_v20(conn)?;
+        conn.execute("INSERT INTO schema_version (version) VALUES (20)", [])?;
+    }
+
+    // Migration to version 21: When original photos are transferred
+    if current_version < 21 {
+        migrate_to_v21(conn)?;
+        conn.execute("INSERT INTO schema_version (version) VALUES (21)", [])?;
+    }
+
+    // Migration to version 22: Pause background sync on metered connections / low battery
+    if current_version < 22 {
+        migrate_to_v22(conn)?;
+        conn.execute("INSERT INTO schema_version (version) VALUES (22)", [])?;
+    }
+
+    // Migration to version 23: Pause sync, per-entity-type sync
+    if current_version < 23 {
+        migrate_to_v23(conn)?;
+        conn.execute("INSERT INTO schema_version (version) VALUES (23)", [])?;
+    }
+
+    // Migration to version 24: Persistent sync history
+    if current_version < 24 {
+        migrate_to_v24(conn)?;
+        conn.execute("INSERT INTO schema_version (version) VALUES (24)", [])?;
+    }
+
+    // Migration to version 25: Transfer statistics per run and per day
+    if current_version < 25 {
+        migrate_to_v25(conn)?;
+        conn.execute("INSERT INTO schema_version (version) VALUES (25)", [])?;
+    }
+
+    // Migration to version 26: Full-text search index
+    if current_version < 26 {
+        migrate_to_v26(conn)?;
+        conn.execute("INSERT INTO schema_version (version) VALUES (26)", [])?;
+    }
+
+    // Migration to version 27: Persistent app log
+    if current_version < 27 {
+        migrate_to_v27(conn)?;
+        conn.execute("INSERT INTO schema_version (version) VALUES (27)", [])?;
+    }
+
+    // Migration to version 28: Composite indexes for the list screens
+    if current_version < 28 {
+        migrate_to_v28(conn)?;
+        conn.execute("INSERT INTO schema_version (version) VALUES (28)", [])?;
+    }
+
+    // Migration to version 29: Foreign keys from photos to quails/events
+    if current_version < 29 {
+        migrate_to_v29(conn)?;
+        conn.execute("INSERT INTO schema_version (version) VALUES (29)", [])?;
+    }
+
+    // Migration to version 30: Nextcloud folder for uploaded exports
+    if current_version < 30 {
+        migrate_to_v30(conn)?;
+        conn.execute("INSERT INTO schema_version (version) VALUES (30)", [])?;
+    }
+
+    // Migration to version 31: schedule of automatic exports
+    if current_version < 31 {
+        migrate_to_v31(conn)?;
+        conn.execute("INSERT INTO schema_version (version) VALUES (31)", [])?;
+    }
+
+    // Migration to version 32: saved CSV export templates
+    if current_version < 32 {
+        migrate_to_v32(conn)?;
+        conn.execute("INSERT INTO schema_version (version) VALUES (32)", [])?;
+    }
+
+    // Migration to version 33: parents of a quail (pedigree)
+    if current_version < 33 {
+        migrate_to_v33(conn)?;
+        conn.execute("INSERT INTO schema_version (version) VALUES (33)", [])?;
+    }
+
+    // Migration to version 34: coops, with quails and egg records per coop
+    if current_version < 34 {
+        migrate_to_v34(conn)?;
+        conn.execute("INSERT INTO schema_version (version) VALUES (34)", [])?;
+    }
+
+    // Migration to version 35: treatment events with withdrawal periods
+    if current_version < 35 {
+        migrate_to_v35(conn)?;
+        conn.execute("INSERT INTO schema_version (version) VALUES (35)", [])?;
+    }
+
+    // Migration to version 36: explicit opt-in for plaintext sync files
+    if current_version < 36 {
+        migrate_to_v36(conn)?;
+        conn.execute("INSERT INTO schema_version (version) VALUES (36)", [])?;
+    }
+
+    // Migration to version 37: references parked until their target arrives
+    if current_version < 37 {
+        migrate_to_v37(conn)?;
+        conn.execute("INSERT INTO schema_version (version) VALUES (37)", [])?;
+    }
+
+    Ok(())
+}
+
+/// Create the complete schema (version 2) - multi-master sync ready (CRDT fields)
+fn create_schema(conn: &Connection) -> Result<()> {
+    // Table: photos (now with rev/logical_clock/deleted fields for CRDT sync + multi-size thumbnails + sync status)
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS photos (
+            uuid TEXT PRIMARY KEY,
+            quail_id TEXT,
+            event_id TEXT,
+            path TEXT NOT NULL,
+            relative_path TEXT,
+            thumbnail_path TEXT,
+            thumbnail_small_path TEXT,
+            thumbnail_medium_path TEXT,
+            sync_status TEXT DEFAULT 'local_only' CHECK(sync_status IN ('local_only', 'uploading', 'synced', 'download_pending', 'downloading', 'download_failed', 'corrupt')),
+            sync_error TEXT,
+            last_sync_attempt INTEGER,
+            retry_count INTEGER DEFAULT 0,
+            created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+            updated_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+            rev INTEGER NOT NULL DEFAULT 0,
+            logical_clock INTEGER NOT NULL DEFAULT 0,
+            deleted INTEGER NOT NULL DEFAULT 0 CHECK(deleted IN (0,1)),
+            CHECK( (quail_id IS NOT NULL AND event_id IS NOT NULL) OR (quail_id IS NULL OR event_id IS NULL) ),
+            FOREIGN KEY (quail_id) REFERENCES quails(uuid) ON DELETE SET NULL,
+            FOREIGN KEY (event_id) REFERENCES quail_events(uuid) ON DELETE SET NULL
+        )",
+        [],
+    )?;
+
+    // Indexes for photos
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_photos_quail ON photos(quail_id)",
+        [],
+    )?;
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_photos_event ON photos(event_id)",
+        [],
+    )?;
+
+    // Trigger for updated_at in photos
+    conn.execute(
+        "CREATE TRIGGER IF NOT EXISTS update_photos_timestamp 
+         AFTER UPDATE ON photos
+         BEGIN
+            UPDATE photos SET updated_at = CURRENT_TIMESTAMP WHERE uuid = NEW.uuid;
+         END",
+        [],
+    )?;
+
+    // Table: quails (Quail profiles)
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS quails (
+            uuid TEXT PRIMARY KEY,
+            name TEXT NOT NULL,
+            gender TEXT CHECK(gender IN ('male', 'female', 'unknown')) NOT NULL DEFAULT 'unknown',
+            ring_color TEXT,
+            profile_photo TEXT,
+            created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+            updated_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+            rev INTEGER NOT NULL DEFAULT 0,
+            logical_clock INTEGER NOT NULL DEFAULT 0,
+            deleted INTEGER NOT NULL DEFAULT 0 CHECK(deleted IN (0,1)),
+            FOREIGN KEY (profile_photo) REFERENCES photos(uuid) ON DELETE SET NULL
+        )",
+        [],
+    )?;
+
+    // Indexes for quails
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_quails_name ON quails(name)",
+        [],
+    )?;
+
+    // Trigger for updated_at in quails
+    conn.execute(
+        "CREATE TRIGGER IF NOT EXISTS update_quails_timestamp 
+         AFTER UPDATE ON quails
+         BEGIN
+            UPDATE quails SET updated_at = CURRENT_TIMESTAMP WHERE uuid = NEW.uuid;
+         END",
+        [],
+    )?;
+
+    // Table: quail_events (Life events for quails)
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS quail_events (
+            uuid TEXT PRIMARY KEY,
+            quail_id TEXT NOT NULL,
+            event_type TEXT CHECK(event_type IN ('born', 'alive', 'sick', 'healthy', 'marked_for_slaughter', 'slaughtered', 'died')) NOT NULL,
+            event_date TEXT NOT NULL,
+            notes TEXT,
+            created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+            updated_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+            rev INTEGER NOT NULL DEFAULT 0,
+            logical_clock INTEGER NOT NULL DEFAULT 0,
+            deleted INTEGER NOT NULL DEFAULT 0 CHECK(deleted IN (0,1)),
+            FOREIGN KEY (quail_id) REFERENCES quails(uuid) ON DELETE CASCADE
+        )",
+        [],
+    )?;
+
+    // Indexes for quail_events
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_quail_events_quail_id ON quail_events(quail_id)",
+        [],
+    )?;
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_quail_events_date ON quail_events(event_date DESC)",
+        [],
+    )?;
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_quail_events_type ON quail_events(event_type)",
+        [],
+    )?;
+
+    // Trigger for updated_at in quail_events
+    conn.execute(
+        "CREATE TRIGGER IF NOT EXISTS update_quail_events_timestamp 
+         AFTER UPDATE ON quail_events
+         BEGIN
+            UPDATE quail_events SET updated_at = CURRENT_TIMESTAMP WHERE uuid = NEW.uuid;
+         END",
+        [],
+    )?;
+
+    // Note: We no longer enforce strict FK on photos via triggers to support
+    // CRDT/out-of-order sync. Orphaned photos are allowed and handled in sync logic.
+
+    // Table: egg_records (Daily egg production tracking)
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS egg_records (
+            uuid TEXT PRIMARY KEY,
+            record_date TEXT NOT NULL UNIQUE,
+            total_eggs INTEGER NOT NULL DEFAULT 0 CHECK(total_eggs >= 0),
+            notes TEXT,
+            created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+            updated_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+            rev INTEGER NOT NULL DEFAULT 0,
+            logical_clock INTEGER NOT NULL DEFAULT 0,
+            deleted INTEGER NOT NULL DEFAULT 0 CHECK(deleted IN (0,1))
+        )",
+        [],
+    )?;
+
+    // Indexes for egg_records
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_egg_records_date ON egg_records(record_date DESC)",
+        [],
+    )?;
+
+    // Trigger for updated_at in egg_records
+    conn.execute(
+        "CREATE TRIGGER IF NOT EXISTS update_egg_records_timestamp 
+         AFTER UPDATE ON egg_records
+         BEGIN
+            UPDATE egg_records SET updated_at = CURRENT_TIMESTAMP WHERE uuid = NEW.uuid;
+         END",
+        [],
+    )?;
+
+    // Table: sync_settings (Nextcloud WebDAV synchronization settings)
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS sync_settings (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            server_url TEXT NOT NULL,
+            username TEXT NOT NULL,
+            app_password TEXT NOT NULL,
+            remote_path TEXT NOT NULL DEFAULT '/Stalltagebuch',
+            enabled INTEGER NOT NULL DEFAULT 1 CHECK(enabled IN (0,1)),
+            last_sync TEXT,
+            device_id TEXT,
+            format_version INTEGER NOT NULL DEFAULT 2,
+            created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+            updated_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+        )",
+        [],
+    )?;
+
+    // Trigger for updated_at in sync_settings
+    conn.execute(
+        "CREATE TRIGGER IF NOT EXISTS update_sync_settings_timestamp 
+         AFTER UPDATE ON sync_settings
+         BEGIN
+            UPDATE sync_settings SET updated_at = CURRENT_TIMESTAMP WHERE id = NEW.id;
+         END",
+        [],
+    )?;
+
+    // Table: sync_queue (Queue for pending photo uploads)
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS sync_queue (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            photo_id TEXT NOT NULL,
+            status TEXT CHECK(status IN ('pending', 'uploading', 'completed', 'failed')) NOT NULL DEFAULT 'pending',
+            retry_count INTEGER NOT NULL DEFAULT 0,
+            last_error TEXT,
+            created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+            updated_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+            FOREIGN KEY (photo_id) REFERENCES photos(uuid) ON DELETE CASCADE
+        )",
+        [],
+    )?;
+
+    // Operation Log for CRDT operations
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS op_log (
+            op_id TEXT PRIMARY KEY,
+            entity_type TEXT NOT NULL,
+            entity_id TEXT NOT NULL,
+            ts INTEGER NOT NULL,
+            logical_counter INTEGER NOT NULL,
+            device_id TEXT NOT NULL,
+            op_kind TEXT NOT NULL, -- upsert | delete | inc
+            payload TEXT,
+            created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+        )",
+        [],
+    )?;
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_op_log_entity ON op_log(entity_id, ts)",
+        [],
+    )?;
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_op_log_device ON op_log(device_id, ts)",
+        [],
+    )?;
+
+    // Sync checkpoint (tracks last applied clock/snapshot)
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS sync_checkpoint (
+            id INTEGER PRIMARY KEY CHECK(id = 1),
+            last_ts INTEGER NOT NULL DEFAULT 0,
+            last_logical_counter INTEGER NOT NULL DEFAULT 0,
+            last_device_id TEXT,
+            snapshot_rev INTEGER NOT NULL DEFAULT 0,
+            updated_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+        )",
+        [],
+    )?;
+
+    // Device state (stable device identifier)
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS device_state (
+            id INTEGER PRIMARY KEY CHECK(id = 1),
+            device_id TEXT NOT NULL,
+            created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+        )",
+        [],
+    )?;
+
+    // Indexes for sync_queue
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_sync_queue_status ON sync_queue(status)",
+        [],
+    )?;
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_sync_queue_photo ON sync_queue(photo_id)",
+        [],
+    )?;
+
+    // Trigger for updated_at in sync_queue
+    conn.execute(
+        "CREATE TRIGGER IF NOT EXISTS update_sync_queue_timestamp 
+         AFTER UPDATE ON sync_queue
+         BEGIN
+            UPDATE sync_queue SET updated_at = CURRENT_TIMESTAMP WHERE id = NEW.id;
+         END",
+        [],
+    )?;
+
+    Ok(())
+}
+
+/// Migration to version 3: Add relative_path column to photos
+fn migrate_to_v3(conn: &Connection) -> Result<()> {
+    // Check if column already exists
+    let has_column: bool = conn
+        .query_row(
+            "SELECT COUNT(*) FROM pragma_table_info('photos') WHERE name='relative_path'",
+            [],
+            |row| row.get::<_, i32>(0).map(|c| c > 0),
+        )
+        .unwrap_or(false);
+
+    if !has_column {
+        log::info!("Migrating to schema version 3: adding relative_path column");
+        conn.execute("ALTER TABLE photos ADD COLUMN relative_path TEXT", [])?;
+
+        // Migrate existing data: copy path to relative_path for existing photos
+        conn.execute(
+            "UPDATE photos SET relative_path = path WHERE relative_path IS NULL",
+            [],
+        )?;
+        log::info!("Migration to v3 complete");
+    }
+
+    Ok(())
+}
+
+/// Migration to version 4: Drop strict photo FK triggers to avoid aborts during CRDT merges
+fn migrate_to_v4(conn: &Connection) -> Result<()> {
+    // Best-effort drop if they exist in older databases
+    let _ = conn.execute("DROP TRIGGER IF EXISTS photos_quail_fk_check", []);
+    let _ = conn.execute("DROP TRIGGER IF EXISTS photos_event_fk_check", []);
+    Ok(())
+}
+
+/// Migration to version 5: Add initial_upload_done flag to sync_settings
+fn migrate_to_v5(conn: &Connection) -> Result<()> {
+    let has_column: bool = conn
+        .query_row(
+            "SELECT COUNT(*) FROM pragma_table_info('sync_settings') WHERE name='initial_upload_done'",
+            [],
+            |row| row.get::<_, i32>(0).map(|c| c > 0),
+        )
+        .unwrap_or(false);
+
+    if !has_column {
+        log::info!("Migrating to schema version 5: adding initial_upload_done column");
+        conn.execute(
+            "ALTER TABLE sync_settings ADD COLUMN initial_upload_done INTEGER NOT NULL DEFAULT 0 CHECK(initial_upload_done IN (0,1))",
+            [],
+        )?;
+        log::info!("Migration to v5 complete");
+    }
+
+    Ok(())
+}
+
+fn migrate_to_v6(conn: &Connection) -> Result<()> {
+    log::info!("Migrating to schema version 6: adding login_flow_session table");
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS login_flow_session (
+            id INTEGER PRIMARY KEY CHECK(id = 1),
+            poll_endpoint TEXT NOT NULL,
+            token TEXT NOT NULL,
+            login_url TEXT NOT NULL,
+            remote_path TEXT NOT NULL,
+            started_at INTEGER NOT NULL
+        )",
+        [],
+    )?;
+    log::info!("Migration to v6 complete");
+    Ok(())
+}
+
+/// Migration to version 7: Add credentials_invalid flag to sync_settings
+fn migrate_to_v7(conn: &Connection) -> Result<()> {
+    let has_column: bool = conn
+        .query_row(
+            "SELECT COUNT(*) FROM pragma_table_info('sync_settings') WHERE name='credentials_invalid'",
+            [],
+            |row| row.get::<_, i32>(0).map(|c| c > 0),
+        )
+        .unwrap_or(false);
+
+    if !has_column {
+        log::info!("Migrating to schema version 7: adding credentials_invalid column");
+        conn.execute(
+            "ALTER TABLE sync_settings ADD COLUMN credentials_invalid INTEGER NOT NULL DEFAULT 0 CHECK(credentials_invalid IN (0,1))",
+            [],
+        )?;
+        log::info!("Migration to v7 complete");
+    }
+
+    Ok(())
+}
+
+/// Migration to version 8: Add cert_fingerprint column to sync_settings
+fn migrate_to_v8(conn: &Connection) -> Result<()> {
+    let has_column: bool = conn
+        .query_row(
+            "SELECT COUNT(*) FROM pragma_table_info('sync_settings') WHERE name='cert_fingerprint'",
+            [],
+            |row| row.get::<_, i32>(0).map(|c| c > 0),
+        )
+        .unwrap_or(false);
+
+    if !has_column {
+        log::info!("Migrating to schema version 8: adding cert_fingerprint column");
+        conn.execute(
+            "ALTER TABLE sync_settings ADD COLUMN cert_fingerprint TEXT",
+            [],
+        )?;
+        log::info!("Migration to v8 complete");
+    }
+
+    Ok(())
+}
+
+/// Migration to version 9: Add proxy_url to sync_settings and login_flow_session
+fn migrate_to_v9(conn: &Connection) -> Result<()> {
+    for table in ["sync_settings", "login_flow_session"] {
+        let has_column: bool = conn
+            .query_row(
+                &format!(
+                    "SELECT COUNT(*) FROM pragma_table_info('{}') WHERE name='proxy_url'",
+                    table
+                ),
+                [],
+                |row| row.get::<_, i32>(0).map(|c| c > 0),
+            )
+            .unwrap_or(false);
+
+        if !has_column {
+            log::info!(
+                "Migrating to schema version 9: adding proxy_url to {}",
+                table
+            );
+            conn.execute(
+                &format!("ALTER TABLE {} ADD COLUMN proxy_url TEXT", table),
+                [],
+            )?;
+        }
+    }
+    log::info!("Migration to v9 complete");
+    Ok(())
+}
+
+/// Migration to version 10: Add EXIF columns to photos
+fn migrate_to_v10(conn: &Connection) -> Result<()> {
+    for (column, sql_type) in [
+        ("exif_orientation", "INTEGER"),
+        ("taken_at", "TEXT"),
+        ("camera_model", "TEXT"),
+        ("gps_latitude", "REAL"),
+        ("gps_longitude", "REAL"),
+    ] {
+        let has_column: bool = conn
+            .query_row(
+                &format!(
+                    "SELECT COUNT(*) FROM pragma_table_info('photos') WHERE name='{}'",
+                    column
+                ),
+                [],
+                |row| row.get::<_, i32>(0).map(|c| c > 0),
+            )
+            .unwrap_or(false);
+
+        if !has_column {
+            log::info!(
+                "Migrating to schema version 10: adding {} to photos",
+                column
+            );
+            conn.execute(
+                &format!("ALTER TABLE photos ADD COLUMN {} {}", column, sql_type),
+                [],
+            )?;
+        }
+    }
+    log::info!("Migration to v10 complete");
+    Ok(())
+}
+
+/// Migration to version 11: Add last_viewed_at/original_evicted to photos and
+/// photo_storage_limit_mb to sync_settings
+fn migrate_to_v11(conn: &Connection) -> Result<()> {
+    for (table, column, definition) in [
+        ("photos", "last_viewed_at", "INTEGER"),
+        ("photos", "original_evicted", "INTEGER NOT NULL DEFAULT 0"),
+        ("sync_settings", "photo_storage_limit_mb", "INTEGER"),
+    ] {
+        let has_column: bool = conn
+            .query_row(
+                &format!(
+                    "SELECT COUNT(*) FROM pragma_table_info('{}') WHERE name='{}'",
+                    table, column
+                ),
+                [],
+                |row| row.get::<_, i32>(0).map(|c| c > 0),
+            )
+            .unwrap_or(false);
+
+        if !has_column {
+            log::info!(
+                "Migrating to schema version 11: adding {} to {}",
+                column,
+                table
+            );
+            conn.execute(
+                &format!("ALTER TABLE {} ADD COLUMN {} {}", table, column, definition),
+                [],
+            )?;
+        }
+    }
+    log::info!("Migration to v11 complete");
+    Ok(())
+}
+
+/// Migration to version 12: Caption and tags (JSON array) for photos
+fn migrate_to_v12(conn: &Connection) -> Result<()> {
+    for (column, definition) in [("caption", "TEXT"), ("tags", "TEXT NOT NULL DEFAULT '[]'")] {
+        let has_column: bool = conn
+            .query_row(
+                &format!(
+                    "SELECT COUNT(*) FROM pragma_table_info('photos') WHERE name='{}'",
+                    column
+                ),
+                [],
+                |row| row.get::<_, i32>(0).map(|c| c > 0),
+            )
+            .unwrap_or(false);
+
+        if !has_column {
+            log::info!(
+                "Migrating to schema version 12: adding {} to photos",
+                column
+            );
+            conn.execute(
+                &format!("ALTER TABLE photos ADD COLUMN {} {}", column, definition),
+                [],
+            )?;
+        }
+    }
+    log::info!("Migration to v12 complete");
+    Ok(())
+}
+
+/// Migration to version 13: Position of a photo within its quail/event
+fn migrate_to_v13(conn: &Connection) -> Result<()> {
+    let has_column: bool = conn
+        .query_row(
+            "SELECT COUNT(*) FROM pragma_table_info('photos') WHERE name='position'",
+            [],
+            |row| row.get::<_, i32>(0).map(|c| c > 0),
+        )
+        .unwrap_or(false);
+
+    if !has_column {
+        log::info!("Migrating to schema version 13: adding position to photos");
+        conn.execute("ALTER TABLE photos ADD COLUMN position INTEGER", [])?;
+    }
+    log::info!("Migration to v13 complete");
+    Ok(())
+}
+
+/// Migration to version 14: Timestamp (ms) when a photo was moved to the trash
+fn migrate_to_v14(conn: &Connection) -> Result<()> {
+    let has_column: bool = conn
+        .query_row(
+            "SELECT COUNT(*) FROM pragma_table_info('photos') WHERE name='trashed_at'",
+            [],
+            |row| row.get::<_, i32>(0).map(|c| c > 0),
+        )
+        .unwrap_or(false);
+
+    if !has_column {
+        log::info!("Migrating to schema version 14: adding trashed_at to photos");
+        conn.execute("ALTER TABLE photos ADD COLUMN trashed_at INTEGER", [])?;
+    }
+    log::info!("Migration to v14 complete");
+    Ok(())
+}
+
+/// Migration to version 15: Table for the photo encryption key (never synced)
+fn migrate_to_v15(conn: &Connection) -> Result<()> {
+    log::info!("Migrating to schema version 15: adding photo_encryption table");
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS photo_encryption (
+            id INTEGER PRIMARY KEY CHECK(id = 1),
+            key TEXT NOT NULL,
+            created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+        )",
+        [],
+    )?;
+    log::info!("Migration to v15 complete");
+    Ok(())
+}
+
+/// Migration to version 16: SHA-256 (hex) of the original photo file and the
+/// `corrupt` sync status for checksum mismatches
+fn migrate_to_v16(conn: &Connection) -> Result<()> {
+    let has_column: bool = conn
+        .query_row(
+            "SELECT COUNT(*) FROM pragma_table_info('photos') WHERE name='checksum'",
+            [],
+            |row| row.get::<_, i32>(0).map(|c| c > 0),
+        )
+        .unwrap_or(false);
+
+    if !has_column {
+        log::info!("Migrating to schema version 16: adding checksum to photos");
+        conn.execute("ALTER TABLE photos ADD COLUMN checksum TEXT", [])?;
+    }
+
+    // SQLite cannot alter a CHECK constraint: rebuild the table from its own
+    // definition (keeps all columns added by earlier migrations)
+    let create_sql = table_sql(conn, "photos")?;
+    if !create_sql.contains("'corrupt'") {
+        log::info!("Migrating to schema version 16: allowing sync_status 'corrupt'");
+        let columns = &create_sql[create_sql.find('(').unwrap_or(0)..];
+        rebuild_table(
+            conn,
+            "photos",
+            &columns.replacen("'download_failed')", "'download_failed', 'corrupt')", 1),
+        )?;
+    }
+    log::info!("Migration to v16 complete");
+    Ok(())
+}
+
+/// `CREATE TABLE` statement of `table` as stored in the schema
+fn table_sql(conn: &Connection, table: &str) -> Result<String> {
+    conn.query_row(
+        "SELECT sql FROM sqlite_master WHERE type = 'table' AND name = ?1",
+        [table],
+        |row| row.get(0),
+    )
+}
+
+/// Recreates `table` with a new definition (the part after the table name),
+/// keeping its rows, indexes and triggers
+fn rebuild_table(conn: &Connection, table: &str, definition: &str) -> Result<()> {
+    let mut stmt = conn.prepare(
+        "SELECT sql FROM sqlite_master
+         WHERE tbl_name = ?1 AND type IN ('index', 'trigger') AND sql IS NOT NULL",
+    )?;
+    let dependents: Vec<String> = stmt
+        .query_map([table], |row| row.get(0))?
+        .collect::<Result<_>>()?;
+
+    // Otherwise dropping the old table would cascade (e.g. photos into
+    // quails/sync_queue)
+    conn.execute("PRAGMA foreign_keys = OFF", [])?;
+    conn.execute_batch(&format!(
+        "BEGIN;
+         CREATE TABLE {table}_rebuild {definition};
+         INSERT INTO {table}_rebuild SELECT * FROM {table};
+         DROP TABLE {table};
+         ALTER TABLE {table}_rebuild RENAME TO {table};
+         {};
+         COMMIT;",
+        dependents.join(";\n")
+    ))?;
+    conn.execute("PRAGMA foreign_keys = ON", [])?;
+    Ok(())
+}
+
+/// Migration to version 17: Number of photo files uploaded concurrently
+fn migrate_to_v17(conn: &Connection) -> Result<()> {
+    let has_column: bool = conn
+        .query_row(
+            "SELECT COUNT(*) FROM pragma_table_info('sync_settings') WHERE name='upload_concurrency'",
+            [],
+            |row| row.get::<_, i32>(0).map(|c| c > 0),
+        )
+        .unwrap_or(false);
+
+    if !has_column {
+        log::info!("Migrating to schema version 17: adding upload_concurrency to sync_settings");
+        conn.execute(
+            "ALTER TABLE sync_settings ADD COLUMN upload_concurrency INTEGER",
+            [],
+        )?;
+    }
+    log::info!("Migration to v17 complete");
+    Ok(())
+}
+
+/// Migration to version 18: Conflict with the remote copy of a photo
+/// (`modified`/`deleted` outside the app, see `RemoteConflict`)
+fn migrate_to_v18(conn: &Connection) -> Result<()> {
+    let has_column: bool = conn
+        .query_row(
+            "SELECT COUNT(*) FROM pragma_table_info('photos') WHERE name='remote_conflict'",
+            [],
+            |row| row.get::<_, i32>(0).map(|c| c > 0),
+        )
+        .unwrap_or(false);
+
+    if !has_column {
+        log::info!("Migrating to schema version 18: adding remote_conflict to photos");
+        conn.execute("ALTER TABLE photos ADD COLUMN remote_conflict TEXT", [])?;
+    }
+    log::info!("Migration to v18 complete");
+    Ok(())
+}
+
+/// Migration to version 19: Counter and device of the hybrid logical clock
+/// (`logical_clock` keeps the physical ms) for every synced table
+///
+/// Existing rows keep their timestamp with counter 0, and the local clock
+/// starts after the newest of them.
+fn migrate_to_v19(conn: &Connection) -> Result<()> {
+    const TABLES: [&str; 4] = ["quails", "quail_events", "photos", "egg_records"];
+    for table in TABLES {
+        for (column, definition) in [
+            ("clock_counter", "INTEGER NOT NULL DEFAULT 0"),
+            ("clock_device", "TEXT NOT NULL DEFAULT ''"),
+        ] {
+            let has_column: bool = conn
+                .query_row(
+                    &format!(
+                        "SELECT COUNT(*) FROM pragma_table_info('{}') WHERE name='{}'",
+                        table, column
+                    ),
+                    [],
+                    |row| row.get::<_, i32>(0).map(|c| c > 0),
+                )
+                .unwrap_or(false);
+
+            if !has_column {
+                log::info!(
+                    "Migrating to schema version 19: adding {} to {}",
+                    column,
+                    table
+                );
+                conn.execute(
+                    &format!("ALTER TABLE {} ADD COLUMN {} {}", table, column, definition),
+                    [],
+                )?;
+            }
+        }
+    }
+
+    let newest: i64 = conn.query_row(
+        &format!(
+            "SELECT MAX({}) ",
+            TABLES
+                .iter()
+                .map(|t| format!("(SELECT COALESCE(MAX(logical_clock), 0) FROM {})", t))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        [],
+        |row| row.get(0),
+    )?;
+    conn.execute(
+        "INSERT INTO sync_checkpoint (id, last_ts) VALUES (1, ?1)
+         ON CONFLICT(id) DO UPDATE SET last_ts = MAX(last_ts, ?1)",
+        [newest],
+    )?;
+    log::info!("Migration to v19 complete");
+    Ok(())
+}
+
+/// Migration to version 20: Local outbox of serialized operations that are
+/// collected until a batch is large or old enough to be uploaded
+fn migrate_to_v20(conn: &Connection) -> Result<()> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS op_outbox (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            line TEXT NOT NULL,
+            created_at INTEGER NOT NULL
+        )",
+        [],
+    )?;
+    log::info!("Migration to v20 complete");
+    Ok(())
+}
+
+/// Migration to version 21: `OriginalsPolicy` of the sync settings
+fn migrate_to_v21(conn: &Connection) -> Result<()> {
+    let has_column: bool = conn
+        .query_row(
+            "SELECT COUNT(*) FROM pragma_table_info('sync_settings') WHERE name='originals_policy'",
+            [],
+            |row| row.get::<_, i32>(0).map(|c| c > 0),
+        )
+        .unwrap_or(false);
+
+    if !has_column {
+        log::info!("Migrating to schema version 21: adding originals_policy to sync_settings");
+        conn.execute(
+            "ALTER TABLE sync_settings ADD COLUMN originals_policy TEXT NOT NULL DEFAULT 'always'",
+            [],
+        )?;
+    }
+    log::info!("Migration to v21 complete");
+    Ok(())
+}
+
+/// Migration to version 22: conditions under which the background sync pauses
+fn migrate_to_v22(conn: &Connection) -> Result<()> {
+    for column in ["pause_on_metered", "pause_on_low_battery"] {
+        let has_column: bool = conn
+            .query_row(
+                "SELECT COUNT(*) FROM pragma_table_info('sync_settings') WHERE name=?1",
+                [column],
+                |row| row.get::<_, i32>(0).map(|c| c > 0),
+            )
+            .unwrap_or(false);
+
+        if !has_column {
+            log::info!(
+                "Migrating to schema version 22: adding {} to sync_settings",
+                column
+            );
+            conn.execute(
+                &format!(
+                    "ALTER TABLE sync_settings ADD COLUMN {} INTEGER NOT NULL DEFAULT 0",
+                    column
+                ),
+                [],
+            )?;
+        }
+    }
+    log::info!("Migration to v22 complete");
+    Ok(())
+}
+
+/// Migration to version 23: global sync pause and excluded entity types
+fn migrate_to_v23(conn: &Connection) -> Result<()> {
+    for (column, definition) in [
+        ("paused", "INTEGER NOT NULL DEFAULT 0"),
+        ("excluded_entity_types", "TEXT NOT NULL DEFAULT ''"),
+    ] {
+        let has_column: bool = conn
+            .query_row(
+                "SELECT COUNT(*) FROM pragma_table_info('sync_settings') WHERE name=?1",
+                [column],
+                |row| row.get::<_, i32>(0).map(|c| c > 0),
+            )
+            .unwrap_or(false);
+
+        if !has_column {
+            log::info!(
+                "Migrating to schema version 23: adding {} to sync_settings",
+                column
+            );
+            conn.execute(
+                &format!(
+                    "ALTER TABLE sync_settings ADD COLUMN {} {}",
+                    column, definition
+                ),
+                [],
+            )?;
+        }
+    }
+    log::info!("Migration to v23 complete");
+    Ok(())
+}
+
+/// Migration to version 24: one row per sync run (see `sync_history`)
+fn migrate_to_v24(conn: &Connection) -> Result<()> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS sync_history (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            started_at INTEGER NOT NULL,
+            duration_ms INTEGER NOT NULL,
+            on_demand INTEGER NOT NULL DEFAULT 0,
+            operations_uploaded INTEGER NOT NULL DEFAULT 0,
+            operations_downloaded INTEGER NOT NULL DEFAULT 0,
+            photos_uploaded INTEGER NOT NULL DEFAULT 0,
+            photos_downloaded INTEGER NOT NULL DEFAULT 0,
+            error TEXT
+        )",
+        [],
+    )?;
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_sync_history_started_at ON sync_history(started_at)",
+        [],
+    )?;
+    log::info!("Migration to v24 complete");
+    Ok(())
+}
+
+/// Migration to version 25: transferred bytes and requests per sync run,
+/// plus a per-day summary that outlives the pruned history (see `transfer_stats`)
+fn migrate_to_v25(conn: &Connection) -> Result<()> {
+    for column in ["bytes_uploaded", "bytes_downloaded", "request_count"] {
+        let has_column: bool = conn
+            .query_row(
+                &format!(
+                    "SELECT COUNT(*) FROM pragma_table_info('sync_history') WHERE name='{}'",
+                    column
+                ),
+                [],
+                |row| row.get::<_, i32>(0).map(|c| c > 0),
+            )
+            .unwrap_or(false);
+
+        if !has_column {
+            conn.execute(
+                &format!(
+                    "ALTER TABLE sync_history ADD COLUMN {} INTEGER NOT NULL DEFAULT 0",
+                    column
+                ),
+                [],
+            )?;
+        }
+    }
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS sync_transfer_daily (
+            day TEXT PRIMARY KEY,
+            runs INTEGER NOT NULL DEFAULT 0,
+            bytes_uploaded INTEGER NOT NULL DEFAULT 0,
+            bytes_downloaded INTEGER NOT NULL DEFAULT 0,
+            request_count INTEGER NOT NULL DEFAULT 0,
+            duration_ms INTEGER NOT NULL DEFAULT 0
+        )",
+        [],
+    )?;
+    log::info!("Migration to v25 complete");
+    Ok(())
+}
+
+/// Migration to version 26: FTS5 index over quail names, event notes and
+/// egg-record notes (see `search_service`), kept current by triggers
+fn migrate_to_v26(conn: &Connection) -> Result<()> {
+    conn.execute(
+        "CREATE VIRTUAL TABLE IF NOT EXISTS search_fts USING fts5(
+            kind UNINDEXED,
+            uuid UNINDEXED,
+            content,
+            tokenize = 'unicode61 remove_diacritics 2'
+        )",
+        [],
+    )?;
+
+    // (table, kind, indexed column)
+    for (table, kind, column) in [
+        ("quails", "quail", "name"),
+        ("quail_events", "event", "notes"),
+        ("egg_records", "egg_record", "notes"),
+    ] {
+        conn.execute_batch(&format!(
+            "CREATE TRIGGER IF NOT EXISTS {table}_search_insert
+             AFTER INSERT ON {table}
+             WHEN NEW.deleted = 0 AND NEW.{column} IS NOT NULL AND NEW.{column} != ''
+             BEGIN
+                INSERT INTO search_fts (kind, uuid, content) VALUES ('{kind}', NEW.uuid, NEW.{column});
+             END;
+
+             CREATE TRIGGER IF NOT EXISTS {table}_search_update
+             AFTER UPDATE OF {column}, deleted ON {table}
+             BEGIN
+                DELETE FROM search_fts WHERE kind = '{kind}' AND uuid = OLD.uuid;
+                INSERT INTO search_fts (kind, uuid, content)
+                    SELECT '{kind}', NEW.uuid, NEW.{column}
+                    WHERE NEW.deleted = 0 AND NEW.{column} IS NOT NULL AND NEW.{column} != '';
+             END;
+
+             CREATE TRIGGER IF NOT EXISTS {table}_search_delete
+             AFTER DELETE ON {table}
+             BEGIN
+                DELETE FROM search_fts WHERE kind = '{kind}' AND uuid = OLD.uuid;
+             END;"
+        ))?;
+    }
+
+    // Index existing rows (rebuilt from scratch, so re-running is harmless)
+    conn.execute_batch(
+        "DELETE FROM search_fts;
+         INSERT INTO search_fts (kind, uuid, content)
+            SELECT 'quail', uuid, name FROM quails WHERE deleted = 0;
+         INSERT INTO search_fts (kind, uuid, content)
+            SELECT 'event', uuid, notes FROM quail_events
+            WHERE deleted = 0 AND notes IS NOT NULL AND notes != '';
+         INSERT INTO search_fts (kind, uuid, content)
+            SELECT 'egg_record', uuid, notes FROM egg_records
+            WHERE deleted = 0 AND notes IS NOT NULL AND notes != '';",
+    )?;
+    log::info!("Migration to v26 complete");
+    Ok(())
+}
+
+/// Migration to version 27: persistent log for maintenance results that
+/// should survive restarts (see `app_log`)
+fn migrate_to_v27(conn: &Connection) -> Result<()> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS app_log (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            created_at INTEGER NOT NULL,
+            category TEXT NOT NULL,
+            level TEXT NOT NULL DEFAULT 'info',
+            message TEXT NOT NULL
+        )",
+        [],
+    )?;
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_app_log_category ON app_log(category, created_at)",
+        [],
+    )?;
+    log::info!("Migration to v27 complete");
+    Ok(())
+}
+
+/// Migration to version 28: Composite indexes for the list screens
+///
+/// Photos are always loaded per quail/event without deleted rows and events
+/// per quail sorted by date; the composite indexes replace the single-column
+/// ones on `quail_id`/`event_id`. Egg records (`idx_egg_records_date`) and
+/// the op log (`idx_op_log_entity`) are already covered.
+fn migrate_to_v28(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        "CREATE INDEX IF NOT EXISTS idx_photos_quail_deleted ON photos(quail_id, deleted);
+         CREATE INDEX IF NOT EXISTS idx_photos_event_deleted ON photos(event_id, deleted);
+         DROP INDEX IF EXISTS idx_photos_quail;
+         DROP INDEX IF EXISTS idx_photos_event;
+         CREATE INDEX IF NOT EXISTS idx_quail_events_quail_date ON quail_events(quail_id, event_date DESC);
+         DROP INDEX IF EXISTS idx_quail_events_quail_id;",
+    )?;
+    log::info!("Migration to v28 complete");
+    Ok(())
+}
+
+/// Migration to version 29: Foreign keys from photos to their quail/event
+///
+/// Without them a photo could point to a quail or event that does not
+/// exist, which only showed up once a screen tried to load it. Existing
+/// orphans get placeholders or deferred references like in the sync merge
+/// (or lose the reference if the target was deleted), so sync can still
+/// fill them in later.
+fn migrate_to_v29(conn: &Connection) -> Result<()> {
+    let create_sql = table_sql(conn, "photos")?;
+    if !create_sql.contains("REFERENCES quails") {
+        log::info!("Migrating to schema version 29: foreign keys for photos");
+        let start = create_sql.find('(').unwrap_or(0);
+        let end = create_sql.rfind(')').unwrap_or(create_sql.len());
+        rebuild_table(
+            conn,
+            "photos",
+            &format!(
+                "{},
+            FOREIGN KEY (quail_id) REFERENCES quails(uuid) ON DELETE SET NULL,
+            FOREIGN KEY (event_id) REFERENCES quail_events(uuid) ON DELETE SET NULL
+        )",
+                create_sql[start..end].trim_end()
+            ),
+        )?;
+    }
+
+    // Also orphans of the tables that already had foreign keys (rows
+    // written while a connection had them switched off)
+    create_deferred_references(conn)?;
+    let repaired = crate::services::integrity::repair_orphans(conn).map_err(|e| match e {
+        crate::error::AppError::Database(e) => e,
+        other => rusqlite::Error::SqliteFailure(
+            rusqlite::ffi::Error::new(rusqlite::ffi::SQLITE_ERROR),
+            Some(other.to_string()),
+        ),
+    })?;
+    log::info!("Migration to v29 complete: {:?}", repaired);
+    Ok(())
+}
+
+/// Migration to version 30: Nextcloud folder for uploaded exports
+/// (`NULL` = `exports` below the sync folder)
+fn migrate_to_v30(conn: &Connection) -> Result<()> {
+    let has_column: bool = conn
+        .query_row(
+            "SELECT COUNT(*) FROM pragma_table_info('sync_settings') WHERE name='export_path'",
+            [],
+            |row| row.get::<_, i32>(0).map(|c| c > 0),
+        )
+        .unwrap_or(false);
+
+    if !has_column {
+        log::info!("Migrating to schema version 30: adding export_path to sync_settings");
+        conn.execute("ALTER TABLE sync_settings ADD COLUMN export_path TEXT", [])?;
+    }
+    log::info!("Migration to v30 complete");
+    Ok(())
+}
+
+/// Migration to version 31: Schedule of automatic exports (single row,
+/// never synced; timestamps in ms since epoch)
+fn migrate_to_v31(conn: &Connection) -> Result<()> {
+    log::info!("Migrating to schema version 31: adding export_schedule table");
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS export_schedule (
+            id INTEGER PRIMARY KEY CHECK(id = 1),
+            interval TEXT NOT NULL DEFAULT 'off',
+            destination TEXT NOT NULL DEFAULT 'local',
+            keep INTEGER NOT NULL DEFAULT 4,
+            last_success INTEGER,
+            last_attempt INTEGER
+        )",
+        [],
+    )?;
+    log::info!("Migration to v31 complete");
+    Ok(())
+}
+
+/// Migration to version 32: Saved templates for CSV exports (never synced;
+/// `columns` and `event_types` are JSON arrays of keys). Starts with a vet
+/// report of health events.
+fn migrate_to_v32(conn: &Connection) -> Result<()> {
+    log::info!("Migrating to schema version 32: adding export_templates table");
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS export_templates (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            name TEXT NOT NULL UNIQUE,
+            entity TEXT NOT NULL,
+            columns TEXT NOT NULL,
+            event_types TEXT NOT NULL DEFAULT '[]'
+        )",
+        [],
+    )?;
+    conn.execute(
+        "INSERT OR IGNORE INTO export_templates (name, entity, columns, event_types)
+         VALUES ('Tierarzt-Bericht', 'events', '[\"date\",\"quail\",\"event_type\",\"notes\"]',
+                 '[\"sick\",\"healthy\",\"died\"]')",
+        [],
+    )?;
+    log::info!("Migration to v32 complete");
+    Ok(())
+}
+
+/// Migration to version 33: Mother and father of a quail (synced like the
+/// other quail fields). Plain UUIDs without foreign keys: a parent may
+/// arrive later in a sync or be deleted while its offspring stays.
+fn migrate_to_v33(conn: &Connection) -> Result<()> {
+    log::info!("Migrating to schema version 33: adding mother_id/father_id to quails");
+    for column in ["mother_id", "father_id"] {
+        let has_column: bool = conn
+            .query_row(
+                "SELECT COUNT(*) FROM pragma_table_info('quails') WHERE name = ?1",
+                [column],
+                |row| row.get::<_, i32>(0).map(|c| c > 0),
+            )
+            .unwrap_or(false);
+        if !has_column {
+            conn.execute(
+                &format!("ALTER TABLE quails ADD COLUMN {} TEXT", column),
+                [],
+            )?;
+        }
+    }
+    conn.execute_batch(
+        "CREATE INDEX IF NOT EXISTS idx_quails_mother ON quails(mother_id);
+         CREATE INDEX IF NOT EXISTS idx_quails_father ON quails(father_id);",
+    )?;
+    log::info!("Migration to v33 complete");
+    Ok(())
+}
+
+/// Migration to version 34: Coops (synced like the other entities) and the
+/// coop of a quail and of an egg record, again without foreign keys
+///
+/// Egg records were unique per day; now there is one per day and coop
+/// (`coop_id` NULL = whole flock). A unique index over both would reject
+/// records whose `coop_id` op is merged after their `record_date` op, so
+/// `egg_service` checks uniqueness instead and the table is rebuilt
+/// without the `UNIQUE` on `record_date`.
+fn migrate_to_v34(conn: &Connection) -> Result<()> {
+    log::info!("Migrating to schema version 34: adding coops");
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS coops (
+            uuid TEXT PRIMARY KEY,
+            name TEXT NOT NULL,
+            notes TEXT,
+            created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+            updated_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+            rev INTEGER NOT NULL DEFAULT 0,
+            logical_clock INTEGER NOT NULL DEFAULT 0,
+            deleted INTEGER NOT NULL DEFAULT 0 CHECK(deleted IN (0,1)),
+            clock_counter INTEGER NOT NULL DEFAULT 0,
+            clock_device TEXT NOT NULL DEFAULT ''
+        );
+
+        CREATE TRIGGER IF NOT EXISTS update_coops_timestamp
+         AFTER UPDATE ON coops
+         BEGIN
+            UPDATE coops SET updated_at = CURRENT_TIMESTAMP WHERE uuid = NEW.uuid;
+         END;",
+    )?;
+
+    for table in ["quails", "egg_records"] {
+        let has_column: bool = conn
+            .query_row(
+                "SELECT COUNT(*) FROM pragma_table_info(?1) WHERE name = 'coop_id'",
+                [table],
+                |row| row.get::<_, i32>(0).map(|c| c > 0),
+            )
+            .unwrap_or(false);
+        if !has_column {
+            conn.execute(
+                &format!("ALTER TABLE {} ADD COLUMN coop_id TEXT", table),
+                [],
+            )?;
+        }
+    }
+
+    let create_sql = table_sql(conn, "egg_records")?;
+    if create_sql.contains("record_date TEXT NOT NULL UNIQUE") {
+        log::info!("Migrating to schema version 34: egg records per day and coop");
+        let definition = &create_sql[create_sql.find('(').unwrap_or(0)..];
+        rebuild_table(
+            conn,
+            "egg_records",
+            &definition.replacen(
+                "record_date TEXT NOT NULL UNIQUE",
+                "record_date TEXT NOT NULL",
+                1,
+            ),
+        )?;
+    }
+
+    conn.execute_batch(
+        "CREATE INDEX IF NOT EXISTS idx_quails_coop ON quails(coop_id);
+         CREATE INDEX IF NOT EXISTS idx_egg_records_coop ON egg_records(coop_id, record_date);",
+    )?;
+    log::info!("Migration to v34 complete");
+    Ok(())
+}
+
+/// Migration to version 35: Treatment events
+///
+/// Medication, dose and the treatment and withdrawal periods are stored on
+/// the event itself, so they sync field by field like the other event
+/// columns. The `event_type` CHECK constraint only changes by rebuilding the
+/// table.
+fn migrate_to_v35(conn: &Connection) -> Result<()> {
+    log::info!("Migrating to schema version 35: treatment events");
+    for (column, column_type) in [
+        ("medication", "TEXT"),
+        ("dose", "TEXT"),
+        ("duration_days", "INTEGER"),
+        ("egg_withdrawal_days", "INTEGER"),
+        ("meat_withdrawal_days", "INTEGER"),
+    ] {
+        let has_column: bool = conn
+            .query_row(
+                "SELECT COUNT(*) FROM pragma_table_info('quail_events') WHERE name = ?1",
+                [column],
+                |row| row.get::<_, i32>(0).map(|c| c > 0),
+            )
+            .unwrap_or(false);
+        if !has_column {
+            conn.execute(
+                &format!(
+                    "ALTER TABLE quail_events ADD COLUMN {} {}",
+                    column, column_type
+                ),
+                [],
+            )?;
+        }
+    }
+
+    let create_sql = table_sql(conn, "quail_events")?;
+    if !create_sql.contains("'treatment'") {
+        let definition = &create_sql[create_sql.find('(').unwrap_or(0)..];
+        rebuild_table(
+            conn,
+            "quail_events",
+            &definition.replacen("'died')", "'died', 'treatment')", 1),
+        )?;
+    }
+    log::info!("Migration to v35 complete");
+    Ok(())
+}
+
+/// Migration to version 36: Plaintext sync files with a key set
+///
+/// Once a key is stored, downloads reject unencrypted op files and snapshots
+/// unless this flag is set to migrate files uploaded before encryption.
+fn migrate_to_v36(conn: &Connection) -> Result<()> {
+    let has_column: bool = conn
+        .query_row(
+            "SELECT COUNT(*) FROM pragma_table_info('photo_encryption') WHERE name='accept_plaintext'",
+            [],
+            |row| row.get::<_, i32>(0).map(|c| c > 0),
+        )
+        .unwrap_or(false);
+    if !has_column {
+        log::info!("Migrating to schema version 36: adding accept_plaintext to photo_encryption");
+        conn.execute(
+            "ALTER TABLE photo_encryption ADD COLUMN accept_plaintext INTEGER NOT NULL DEFAULT 0",
+            [],
+        )?;
+    }
+    log::info!("Migration to v36 complete");
+    Ok(())
+}
+
+/// Migration to version 37: Deferred references
+///
+/// A photo can reference an event that has not arrived yet. Instead of a
+/// visible placeholder event, the reference waits here and is filled in
+/// once the event exists.
+fn migrate_to_v37(conn: &Connection) -> Result<()> {
+    log::info!("Migrating to schema version 37: adding deferred_references table");
+    create_deferred_references(conn)?;
+    log::info!("Migration to v37 complete");
+    Ok(())
+}
+
+/// Table of [`migrate_to_v37`]; the orphan repair of v29 already needs it
+fn create_deferred_references(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS deferred_references (
+            entity_table TEXT NOT NULL,
+            entity_id TEXT NOT NULL,
+            column_name TEXT NOT NULL,
+            target_id TEXT NOT NULL,
+            PRIMARY KEY (entity_table, entity_id, column_name)
+        );
+        CREATE INDEX IF NOT EXISTS idx_deferred_references_target
+            ON deferred_references(target_id);",
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Detail column of `EXPLAIN QUERY PLAN`, one line per step
+    fn query_plan(conn: &Connection, sql: &str) -> String {
+        let mut stmt = conn
+            .prepare(&format!("EXPLAIN QUERY PLAN {}", sql))
+            .unwrap();
+        let rows = stmt
+            .query_map([], |row| row.get::<_, String>(3))
+            .unwrap()
+            .collect::<Result<Vec<_>>>()
+            .unwrap();
+        rows.join("\n")
+    }
+
+    fn assert_uses_index(conn: &Connection, sql: &str, index: &str) {
+        let plan = query_plan(conn, sql);
+        assert!(
+            plan.contains(&format!("INDEX {}", index)),
+            "{} not used:\n{}\n{}",
+            index,
+            sql,
+            plan
+        );
+    }
+
+    #[test]
+    fn test_hot_queries_use_indexes() {
+        let conn = Connection::open_in_memory().unwrap();
+        init_schema(&conn).unwrap();
+
+        // Photo lists of a quail/event (photo_service::list_*_photos)
+        assert_uses_index(
+            &conn,
+            "SELECT uuid FROM photos WHERE quail_id = 'q' AND deleted = 0 AND trashed_at IS NULL
+             ORDER BY position IS NULL, position, created_at, rowid",
+            "idx_photos_quail_deleted",
+        );
+        assert_uses_index(
+            &conn,
+            "SELECT COUNT(*) FROM photos WHERE event_id = 'e' AND deleted = 0 AND trashed_at IS NULL",
+            "idx_photos_event_deleted",
+        );
+
+        // Events of a quail, newest first, without sorting afterwards
+        let events = "SELECT uuid, event_type, event_date FROM quail_events
+                      WHERE quail_id = 'q' ORDER BY event_date DESC";
+        assert_uses_index(&conn, events, "idx_quail_events_quail_date");
+        assert!(!query_plan(&conn, events).contains("TEMP B-TREE"));
+
+        // Egg records of a date range (egg_service::list_egg_records)
+        let eggs = "SELECT uuid FROM egg_records
+                    WHERE record_date BETWEEN '2025-01-01' AND '2025-01-31' AND deleted = 0
+                    ORDER BY record_date DESC";
+        let plan = query_plan(&conn, eggs);
+        assert!(plan.contains("SEARCH egg_records USING"), "{}", plan);
+
+        // Change history of an entity (entity_history::load_history)
+        assert_uses_index(
+            &conn,
+            "SELECT ts, device_id, op_kind FROM op_log
+             WHERE entity_id = 'q' AND entity_type = 'quail'
+             ORDER BY ts, logical_counter, device_id, op_id",
+            "idx_op_log_entity",
+        );
+    }
+
+    #[test]
+    fn test_v28_replaces_single_column_indexes() {
+        let conn = Connection::open_in_memory().unwrap();
+        init_schema(&conn).unwrap();
+        // Database as of v27
+        conn.execute_batch(
+            "DROP INDEX idx_photos_quail_deleted;
+             DROP INDEX idx_photos_event_deleted;
+             DROP INDEX idx_quail_events_quail_date;
+             CREATE INDEX idx_photos_quail ON photos(quail_id);
+             CREATE INDEX idx_quail_events_quail_id ON quail_events(quail_id);
+             DELETE FROM schema_version WHERE version >= 28;",
+        )
+        .unwrap();
+
+        init_schema(&conn).unwrap();
+        assert_eq!(get_schema_version(&conn).unwrap(), SCHEMA_VERSION);
+        let indexes: Vec<String> = conn
+            .prepare("SELECT name FROM sqlite_master WHERE type = 'index' AND name LIKE 'idx_%'")
+            .unwrap()
+            .query_map([], |row| row.get(0))
+            .unwrap()
+            .collect::<Result<_>>()
+            .unwrap();
+        assert!(indexes.contains(&"idx_photos_quail_deleted".to_string()));
+        assert!(indexes.contains(&"idx_quail_events_quail_date".to_string()));
+        assert!(!indexes.contains(&"idx_photos_quail".to_string()));
+        assert!(!indexes.contains(&"idx_quail_events_quail_id".to_string()));
+    }
+
+    #[test]
+    fn test_v29_adds_photo_foreign_keys_and_repairs_orphans() {
+        let conn = Connection::open_in_memory().unwrap();
+        init_schema(&conn).unwrap();
+        // Database as of v28: photos without foreign keys, with orphans
+        let create_sql = table_sql(&conn, "photos").unwrap();
+        let old_definition = create_sql[create_sql.find('(').unwrap()..]
+            .replace(
+                ",\n            FOREIGN KEY (quail_id) REFERENCES quails(uuid) ON DELETE SET NULL,\n            FOREIGN KEY (event_id) REFERENCES quail_events(uuid) ON DELETE SET NULL",
+                "",
+            );
+        rebuild_table(&conn, "photos", &old_definition).unwrap();
+        assert!(!table_sql(&conn, "photos").unwrap().contains("REFERENCES"));
+        conn.execute_batch(
+            "INSERT INTO op_log (op_id, entity_type, entity_id, ts, logical_counter, device_id, op_kind)
+                VALUES ('op1', 'quail', 'deleted-quail', 1, 0, 'd', '{\"type\":\"delete\"}');
+             INSERT INTO photos (uuid, quail_id, path) VALUES ('p1', 'missing-quail', '');
+             INSERT INTO photos (uuid, event_id, path) VALUES ('p2', 'missing-event', '');
+             INSERT INTO photos (uuid, quail_id, path) VALUES ('p3', 'deleted-quail', '');
+             DELETE FROM schema_version WHERE version >= 29;",
+        )
+        .unwrap();
+
+        init_schema(&conn).unwrap();
+        assert_eq!(get_schema_version(&conn).unwrap(), SCHEMA_VERSION);
+        let parents: Vec<String> = conn
+            .prepare("SELECT \"table\" FROM pragma_foreign_key_list('photos') ORDER BY \"table\"")
+            .unwrap()
+            .query_map([], |row| row.get(0))
+            .unwrap()
+            .collect::<Result<_>>()
+            .unwrap();
+        assert_eq!(parents, vec!["quail_events", "quails"]);
+        let violations: i64 = conn
+            .query_row("SELECT COUNT(*) FROM pragma_foreign_key_check", [], |row| {
+                row.get(0)
+            })
+            .unwrap();
+        assert_eq!(violations, 0);
+
+        // Placeholders like in the sync merge, with the zero clock
+        let (name, clock): (String, i64) = conn
+            .query_row(
+                "SELECT name, logical_clock FROM quails WHERE uuid = 'missing-quail'",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .unwrap();
+        assert_eq!((name.as_str(), clock), ("", 0));
+        // No placeholder event, the reference waits for the event
+        let deferred: (String, Option<String>) = conn
+            .query_row(
+                "SELECT d.target_id, p.event_id
+                 FROM deferred_references d JOIN photos p ON p.uuid = d.entity_id
+                 WHERE d.entity_table = 'photos' AND d.column_name = 'event_id'",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .unwrap();
+        assert_eq!(deferred, ("missing-event".to_string(), None));
+        let events: i64 = conn
+            .query_row("SELECT COUNT(*) FROM quail_events", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(events, 0);
+        // Deleted quail: reference dropped like ON DELETE SET NULL
+        let quail_id: Option<String> = conn
+            .query_row("SELECT quail_id FROM photos WHERE uuid = 'p3'", [], |row| {
+                row.get(0)
+            })
+            .unwrap();
+        assert_eq!(quail_id, None);
+        // Indexes survive the rebuild
+        assert_uses_index(
+            &conn,
+            "SELECT uuid FROM photos WHERE quail_id = 'x' AND deleted = 0",
+            "idx_photos_quail_deleted",
+        );
+
+        // A photo can no longer point to a missing quail
+        assert!(conn
+            .execute(
+                "INSERT INTO photos (uuid, quail_id, path) VALUES ('p4', 'nope', '')",
+                [],
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn test_v35_allows_treatment_events() {
+        let conn = Connection::open_in_memory().unwrap();
+        init_schema(&conn).unwrap();
+        assert!(table_sql(&conn, "quail_events")
+            .unwrap()
+            .contains("'treatment'"));
+        conn.execute_batch(
+            "INSERT INTO quails (uuid, name) VALUES ('q1', 'Greta');
+             INSERT INTO quail_events (uuid, quail_id, event_type, event_date, medication, duration_days)
+                VALUES ('e1', 'q1', 'treatment', '2025-04-28', 'Baytril', 5);
+             INSERT INTO photos (uuid, quail_id, event_id, path) VALUES ('p1', 'q1', 'e1', '');",
+        )
+        .unwrap();
+        // Photos still point at their event after the rebuild
+        let parents: Vec<String> = conn
+            .prepare("SELECT \"table\" FROM pragma_foreign_key_list('photos') ORDER BY \"table\"")
+            .unwrap()
+            .query_map([], |row| row.get(0))
+            .unwrap()
+            .collect::<Result<_>>()
+            .unwrap();
+        assert_eq!(parents, vec!["quail_events", "quails"]);
+        assert!(conn
+            .execute(
+                "INSERT INTO quail_events (uuid, quail_id, event_type, event_date)
+                 VALUES ('e2', 'q1', 'vaccinated', '2025-04-28')",
+                [],
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn test_v34_allows_egg_records_per_coop() {
+        let conn = Connection::open_in_memory().unwrap();
+        init_schema(&conn).unwrap();
+        // Database as of v33: one egg record per day
+        let create_sql = table_sql(&conn, "egg_records").unwrap();
+        let old_definition = create_sql[create_sql.find('(').unwrap()..].replacen(
+            "record_date TEXT NOT NULL",
+            "record_date TEXT NOT NULL UNIQUE",
+            1,
+        );
+        rebuild_table(&conn, "egg_records", &old_definition).unwrap();
+        conn.execute_batch(
+            "INSERT INTO egg_records (uuid, record_date, total_eggs, notes)
+                VALUES ('e1', '2025-03-01', 4, 'Legenot');
+             DELETE FROM schema_version WHERE version >= 34;",
+        )
+        .unwrap();
+
+        init_schema(&conn).unwrap();
+        assert_eq!(get_schema_version(&conn).unwrap(), SCHEMA_VERSION);
+        assert!(!table_sql(&conn, "egg_records").unwrap().contains("UNIQUE"));
+        conn.execute(
+            "INSERT INTO egg_records (uuid, record_date, total_eggs, coop_id)
+             VALUES ('e2', '2025-03-01', 2, 'c1')",
+            [],
+        )
+        .unwrap();
+        // Rows and search triggers survive the rebuild
+        let total: i64 = conn
+            .query_row(
+                "SELECT SUM(total_eggs) FROM egg_records WHERE record_date = '2025-03-01'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(total, 6);
+        conn.execute(
+            "UPDATE egg_records SET notes = 'Windei' WHERE uuid = 'e1'",
+            [],
+        )
+        .unwrap();
+        let indexed: String = conn
+            .query_row(
+                "SELECT content FROM search_fts WHERE uuid = 'e1'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(indexed, "Windei");
+    }
+}
@@ -0,0 +1,562 @@
+use crate::error::AppError;
+use crate::models::photo::PhotoMetadata;
+use base64::Engine;
+use chrono::NaiveDateTime;
+use exif::{Exif, In, Tag, Value};
+use image::metadata::Orientation;
+use image::DynamicImage;
+use std::path::{Path, PathBuf};
+
+// Note: Image processing dependencies will be added in Phase 2.3
+// For now, this is a placeholder implementation
+
+/// Process a photo: resize and create thumbnail
+/// Returns (main_photo_path, thumbnail_path)
+#[allow(dead_code)]
+pub fn process_photo(
+    input_path: &Path,
+    output_dir: &Path,
+    filename: &str,
+) -> Result<(PathBuf, PathBuf), AppError> {
+    // TODO: Implement with `image` crate
+    // 1. Load image from input_path
+    // 2. Resize to max 1024x1024 (maintain aspect ratio)
+    // 3. Save to output_dir/photos/filename
+    // 4. Create thumbnail (256x256)
+    // 5. Save to output_dir/thumbnails/filename
+
+    let main_path = output_dir.join("photos").join(filename);
+    let thumb_path = output_dir.join("thumbnails").join(filename);
+
+    // Placeholder: just copy the file
+    std::fs::create_dir_all(main_path.parent().unwrap())?;
+    std::fs::create_dir_all(thumb_path.parent().unwrap())?;
+
+    std::fs::copy(input_path, &main_path)?;
+    std::fs::copy(input_path, &thumb_path)?;
+
+    Ok((main_path, thumb_path))
+}
+
+/// Determines a simple MIME type based on file extension
+fn guess_mime_from_ext(path: &Path) -> &'static str {
+    match path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|s| s.to_ascii_lowercase())
+        .as_deref()
+    {
+        Some("jpg") | Some("jpeg") => "image/jpeg",
+        Some("png") => "image/png",
+        Some("webp") => "image/webp",
+        Some("gif") => "image/gif",
+        Some("bmp") => "image/bmp",
+        Some("heic") | Some("heif") => "image/heic",
+        _ => "image/jpeg",
+    }
+}
+
+/// Image format recognized by the first bytes of a file (not its extension)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputFormat {
+    Jpeg,
+    Png,
+    WebP,
+    Gif,
+    /// HEIF container with HEVC image (Android/iOS camera default)
+    Heic,
+    /// HEIF container with AV1 image
+    Avif,
+    Unknown,
+}
+
+impl InputFormat {
+    /// Detects the format from the file header (at least 32 bytes recommended)
+    pub fn detect(header: &[u8]) -> Self {
+        if header.starts_with(&[0xFF, 0xD8, 0xFF]) {
+            return InputFormat::Jpeg;
+        }
+        if header.starts_with(b"\x89PNG\r\n\x1a\n") {
+            return InputFormat::Png;
+        }
+        if header.len() >= 12 && &header[0..4] == b"RIFF" && &header[8..12] == b"WEBP" {
+            return InputFormat::WebP;
+        }
+        if header.starts_with(b"GIF87a") || header.starts_with(b"GIF89a") {
+            return InputFormat::Gif;
+        }
+        if header.len() >= 12 && &header[4..8] == b"ftyp" {
+            // ISO-BMFF: major brand at 8..12, compatible brands after the minor version
+            let box_len = u32::from_be_bytes([header[0], header[1], header[2], header[3]]) as usize;
+            let end = box_len.clamp(12, header.len());
+            let brands: Vec<&[u8]> = std::iter::once(&header[8..12])
+                .chain(header.get(16..end).unwrap_or(&[]).chunks_exact(4))
+                .collect();
+            if brands.iter().any(|b| matches!(*b, b"avif" | b"avis")) {
+                return InputFormat::Avif;
+            }
+            if brands.iter().any(|b| {
+                matches!(
+                    *b,
+                    b"heic" | b"heix" | b"hevc" | b"hevx" | b"heim" | b"heis" | b"mif1" | b"msf1"
+                )
+            }) {
+                return InputFormat::Heic;
+            }
+        }
+        InputFormat::Unknown
+    }
+
+    /// Detects the format of a file by reading its first bytes
+    pub fn of_file(path: &Path) -> Result<Self, AppError> {
+        use std::io::Read;
+        let mut header = Vec::with_capacity(64);
+        std::fs::File::open(path)?
+            .take(64)
+            .read_to_end(&mut header)?;
+        Ok(Self::detect(&header))
+    }
+
+    pub fn mime(self) -> Option<&'static str> {
+        match self {
+            InputFormat::Jpeg => Some("image/jpeg"),
+            InputFormat::Png => Some("image/png"),
+            InputFormat::WebP => Some("image/webp"),
+            InputFormat::Gif => Some("image/gif"),
+            InputFormat::Heic => Some("image/heic"),
+            InputFormat::Avif => Some("image/avif"),
+            InputFormat::Unknown => None,
+        }
+    }
+
+    /// HEIC/AVIF are converted to JPEG on import so the rest of the app
+    /// (thumbnails, WebView) only sees formats it can display
+    pub fn needs_transcoding(self) -> bool {
+        matches!(self, InputFormat::Heic | InputFormat::Avif)
+    }
+}
+
+/// Decodes an image regardless of its file extension
+///
+/// HEIC/AVIF need the `heif` feature (libheif); without it they yield an
+/// [`AppError::ImageProcessing`] instead of a misleading decoder error.
+pub fn open_image(path: &Path) -> Result<DynamicImage, AppError> {
+    let format = InputFormat::of_file(path)?;
+    if format.needs_transcoding() {
+        return decode_heif(&std::fs::read(path)?, format);
+    }
+    image::ImageReader::open(path)?
+        .with_guessed_format()?
+        .decode()
+        .map_err(|e| AppError::ImageProcessing(format!("Fehler beim Laden des Bildes: {}", e)))
+}
+
+#[cfg(feature = "heif")]
+fn decode_heif(data: &[u8], _format: InputFormat) -> Result<DynamicImage, AppError> {
+    use libheif_rs::{ColorSpace, HeifContext, LibHeif, RgbChroma};
+
+    let heif_error =
+        |e: libheif_rs::HeifError| AppError::ImageProcessing(format!("HEIF decode failed: {}", e));
+    let context = HeifContext::read_from_bytes(data).map_err(heif_error)?;
+    let handle = context.primary_image_handle().map_err(heif_error)?;
+    // libheif applies the container rotation/mirroring (irot/imir) itself
+    let image = LibHeif::new()
+        .decode(&handle, ColorSpace::Rgb(RgbChroma::Rgb), None)
+        .map_err(heif_error)?;
+    let plane = image
+        .planes()
+        .interleaved
+        .ok_or_else(|| AppError::ImageProcessing("HEIF: no RGB plane".to_string()))?;
+
+    let row_len = plane.width as usize * 3;
+    let mut pixels = Vec::with_capacity(row_len * plane.height as usize);
+    for row in plane.data.chunks(plane.stride).take(plane.height as usize) {
+        pixels.extend_from_slice(&row[..row_len]);
+    }
+    image::RgbImage::from_raw(plane.width, plane.height, pixels)
+        .map(DynamicImage::ImageRgb8)
+        .ok_or_else(|| AppError::ImageProcessing("HEIF: invalid image size".to_string()))
+}
+
+#[cfg(not(feature = "heif"))]
+fn decode_heif(_data: &[u8], format: InputFormat) -> Result<DynamicImage, AppError> {
+    Err(AppError::ImageProcessing(format!(
+        "{:?} wird in diesem Build nicht unterstützt (Feature `heif`)",
+        format
+    )))
+}
+
+/// Converts an image (e.g. HEIC/AVIF) to a JPEG file at `dest`
+pub fn transcode_to_jpeg(src: &Path, dest: &Path) -> Result<(), AppError> {
+    let img = open_image(src)?;
+    let file = std::io::BufWriter::new(std::fs::File::create(dest)?);
+    image::codecs::jpeg::JpegEncoder::new_with_quality(file, 90)
+        .encode_image(&img.to_rgb8())
+        .map_err(|e| AppError::ImageProcessing(format!("JPEG encode failed: {}", e)))
+}
+
+/// Encodes raw RGBA pixels (e.g. a clipboard image) as JPEG; alpha is dropped
+pub fn encode_rgba_jpeg(width: u32, height: u32, rgba: Vec<u8>) -> Result<Vec<u8>, AppError> {
+    let img = image::RgbaImage::from_raw(width, height, rgba)
+        .map(DynamicImage::ImageRgba8)
+        .ok_or_else(|| AppError::ImageProcessing("Ungültige Bildgröße".to_string()))?;
+    let mut jpeg = Vec::new();
+    image::codecs::jpeg::JpegEncoder::new_with_quality(&mut jpeg, 90)
+        .encode_image(&img.to_rgb8())
+        .map_err(|e| AppError::ImageProcessing(format!("JPEG encode failed: {}", e)))?;
+    Ok(jpeg)
+}
+
+/// Reads an image from `path` and returns a data URL (Base64)
+pub fn image_path_to_data_url(path: &str) -> Result<String, AppError> {
+    let p = Path::new(path);
+    let data = std::fs::read(p)
+        .map_err(|e| AppError::ImageProcessing(format!("Reading image failed: {}", e)))?;
+    let mime = InputFormat::detect(&data)
+        .mime()
+        .unwrap_or_else(|| guess_mime_from_ext(p));
+    let b64 = base64::engine::general_purpose::STANDARD.encode(data);
+    Ok(format!("data:{};base64,{}", mime, b64))
+}
+
+/// Creates a thumbnail for an image (placeholder implementation)
+/// Currently only copies the file with _thumb.jpg suffix
+pub fn create_thumbnail(path: &str) -> Result<String, AppError> {
+    let p = Path::new(path);
+    let parent = p.parent().unwrap_or(Path::new("/"));
+    let stem = p.file_stem().and_then(|s| s.to_str()).unwrap_or("img");
+    let ext = p.extension().and_then(|e| e.to_str()).unwrap_or("jpg");
+    let thumb_path = parent.join(format!("{}_thumb.{}", stem, ext));
+
+    // Copy file as thumbnail (TODO: real resizing)
+    std::fs::copy(p, &thumb_path)
+        .map_err(|e| AppError::ImageProcessing(format!("Thumbnail creation failed: {}", e)))?;
+
+    Ok(thumb_path.to_string_lossy().to_string())
+}
+
+/// Reads orientation, capture time, camera and GPS position from the EXIF
+/// block of an image. Missing or unreadable EXIF data yields empty metadata.
+pub fn read_exif(path: &Path) -> PhotoMetadata {
+    let file = match std::fs::File::open(path) {
+        Ok(file) => file,
+        Err(e) => {
+            log::warn!("EXIF: Could not open {:?}: {}", path, e);
+            return PhotoMetadata::default();
+        }
+    };
+    match exif::Reader::new().read_from_container(&mut std::io::BufReader::new(file)) {
+        Ok(exif) => metadata_from_exif(&exif),
+        Err(e) => {
+            log::debug!("EXIF: No metadata in {:?}: {}", path, e);
+            PhotoMetadata::default()
+        }
+    }
+}
+
+fn metadata_from_exif(exif: &Exif) -> PhotoMetadata {
+    let orientation = exif
+        .get_field(Tag::Orientation, In::PRIMARY)
+        .and_then(|f| f.value.get_uint(0))
+        .and_then(|o| u16::try_from(o).ok())
+        .filter(|o| (1..=8).contains(o));
+
+    let taken_at = ascii_field(exif, Tag::DateTimeOriginal)
+        .or_else(|| ascii_field(exif, Tag::DateTime))
+        .and_then(|s| NaiveDateTime::parse_from_str(&s, "%Y:%m:%d %H:%M:%S").ok());
+
+    // Many vendors repeat the make in the model name ("Canon" / "Canon EOS 80D")
+    let camera_model = match (ascii_field(exif, Tag::Make), ascii_field(exif, Tag::Model)) {
+        (Some(make), Some(model)) if !model.starts_with(&make) => {
+            Some(format!("{} {}", make, model))
+        }
+        (_, Some(model)) => Some(model),
+        (make, None) => make,
+    };
+
+    PhotoMetadata {
+        orientation,
+        taken_at,
+        camera_model,
+        gps_latitude: gps_coordinate(exif, Tag::GPSLatitude, Tag::GPSLatitudeRef, "S"),
+        gps_longitude: gps_coordinate(exif, Tag::GPSLongitude, Tag::GPSLongitudeRef, "W"),
+    }
+}
+
+fn ascii_field(exif: &Exif, tag: Tag) -> Option<String> {
+    match &exif.get_field(tag, In::PRIMARY)?.value {
+        Value::Ascii(values) => values
+            .first()
+            .map(|v| String::from_utf8_lossy(v).trim().to_string())
+            .filter(|s| !s.is_empty()),
+        _ => None,
+    }
+}
+
+/// Converts degrees/minutes/seconds into signed decimal degrees
+fn gps_coordinate(exif: &Exif, tag: Tag, ref_tag: Tag, negative_ref: &str) -> Option<f64> {
+    let Value::Rational(parts) = &exif.get_field(tag, In::PRIMARY)?.value else {
+        return None;
+    };
+    if parts.len() < 3 {
+        return None;
+    }
+    let degrees = parts[0].to_f64() + parts[1].to_f64() / 60.0 + parts[2].to_f64() / 3600.0;
+    if !degrees.is_finite() {
+        return None;
+    }
+    if ascii_field(exif, ref_tag).as_deref() == Some(negative_ref) {
+        Some(-degrees)
+    } else {
+        Some(degrees)
+    }
+}
+
+/// Rotates/mirrors an image according to its EXIF orientation so that
+/// thumbnails are shown upright (the WebP thumbnails carry no EXIF data)
+pub fn apply_orientation(mut img: DynamicImage, orientation: Option<u16>) -> DynamicImage {
+    if let Some(orientation) = orientation
+        .and_then(|o| u8::try_from(o).ok())
+        .and_then(Orientation::from_exif)
+    {
+        img.apply_orientation(orientation);
+    }
+    img
+}
+
+/// Resize an image maintaining aspect ratio
+#[allow(dead_code)]
+fn calculate_resize_dimensions(
+    original_width: u32,
+    original_height: u32,
+    max_width: u32,
+    max_height: u32,
+) -> (u32, u32) {
+    let ratio =
+        (original_width as f32 / max_width as f32).max(original_height as f32 / max_height as f32);
+
+    if ratio > 1.0 {
+        let new_width = (original_width as f32 / ratio) as u32;
+        let new_height = (original_height as f32 / ratio) as u32;
+        (new_width, new_height)
+    } else {
+        (original_width, original_height)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resize_dimensions() {
+        // Image larger than max
+        let (w, h) = calculate_resize_dimensions(2000, 1500, 1024, 1024);
+        assert!(w <= 1024);
+        assert!(h <= 1024);
+        assert_eq!(w as f32 / h as f32, 2000.0 / 1500.0); // Maintain aspect ratio
+
+        // Image smaller than max
+        let (w, h) = calculate_resize_dimensions(800, 600, 1024, 1024);
+        assert_eq!(w, 800);
+        assert_eq!(h, 600);
+    }
+
+    /// Builds a minimal little-endian TIFF block (IFD0 plus optional GPS IFD)
+    fn tiff(ifd0: &[(u16, u16, Vec<u8>)], gps: &[(u16, u16, Vec<u8>)]) -> Vec<u8> {
+        let ifd_len = |n: usize| 2 + 12 * n + 4;
+        let mut ifd0 = ifd0.to_vec();
+        if !gps.is_empty() {
+            let gps_offset = 8 + ifd_len(ifd0.len() + 1);
+            ifd0.push((0x8825, 4, (gps_offset as u32).to_le_bytes().to_vec()));
+        }
+        let data_offset = 8
+            + ifd_len(ifd0.len())
+            + if gps.is_empty() {
+                0
+            } else {
+                ifd_len(gps.len())
+            };
+
+        let mut out = b"II*\0".to_vec();
+        out.extend(8u32.to_le_bytes());
+        let mut data: Vec<u8> = Vec::new();
+        for ifd in [&ifd0[..], gps] {
+            if ifd.is_empty() {
+                continue;
+            }
+            out.extend((ifd.len() as u16).to_le_bytes());
+            for (tag, typ, value) in ifd {
+                let unit = match typ {
+                    3 => 2,
+                    4 => 4,
+                    5 => 8,
+                    _ => 1,
+                };
+                out.extend(tag.to_le_bytes());
+                out.extend(typ.to_le_bytes());
+                out.extend(((value.len() / unit) as u32).to_le_bytes());
+                if value.len() <= 4 {
+                    let mut inline = value.clone();
+                    inline.resize(4, 0);
+                    out.extend(inline);
+                } else {
+                    out.extend(((data_offset + data.len()) as u32).to_le_bytes());
+                    data.extend(value);
+                }
+            }
+            out.extend(0u32.to_le_bytes());
+        }
+        out.extend(data);
+        out
+    }
+
+    fn rational(values: &[(u32, u32)]) -> Vec<u8> {
+        values
+            .iter()
+            .flat_map(|(n, d)| n.to_le_bytes().into_iter().chain(d.to_le_bytes()))
+            .collect()
+    }
+
+    #[test]
+    fn test_metadata_from_exif() {
+        let raw = tiff(
+            &[
+                (0x010f, 2, b"Google\0".to_vec()),
+                (0x0110, 2, b"Pixel 7\0".to_vec()),
+                (0x0112, 3, 6u16.to_le_bytes().to_vec()),
+                (0x0132, 2, b"2024:05:01 08:30:00\0".to_vec()),
+            ],
+            &[
+                (0x0001, 2, b"N\0".to_vec()),
+                (0x0002, 5, rational(&[(48, 1), (30, 1), (0, 1)])),
+                (0x0003, 2, b"W\0".to_vec()),
+                (0x0004, 5, rational(&[(9, 1), (15, 1), (36, 1)])),
+            ],
+        );
+        let exif = exif::Reader::new().read_raw(raw).unwrap();
+        let meta = metadata_from_exif(&exif);
+
+        assert_eq!(meta.orientation, Some(6));
+        assert_eq!(meta.camera_model.as_deref(), Some("Google Pixel 7"));
+        assert_eq!(
+            meta.taken_at,
+            NaiveDateTime::parse_from_str("2024-05-01 08:30:00", "%Y-%m-%d %H:%M:%S").ok()
+        );
+        assert!((meta.gps_latitude.unwrap() - 48.5).abs() < 1e-9);
+        assert!((meta.gps_longitude.unwrap() + 9.26).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_read_exif_without_metadata() {
+        let dir = std::env::temp_dir().join(format!("exif-test-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("plain.jpg");
+        DynamicImage::new_rgb8(4, 2).save(&path).unwrap();
+
+        assert_eq!(read_exif(&path), PhotoMetadata::default());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_detect_format_by_magic_bytes() {
+        assert_eq!(
+            InputFormat::detect(&[0xFF, 0xD8, 0xFF, 0xE1]),
+            InputFormat::Jpeg
+        );
+        assert_eq!(
+            InputFormat::detect(b"RIFF\0\0\0\0WEBPVP8 "),
+            InputFormat::WebP
+        );
+        assert_eq!(InputFormat::detect(b"\x89PNG\r\n\x1a\n"), InputFormat::Png);
+        assert_eq!(InputFormat::detect(b"hello world!"), InputFormat::Unknown);
+        assert_eq!(InputFormat::detect(b""), InputFormat::Unknown);
+
+        // ftyp box: size, "ftyp", major brand, minor version, compatible brands
+        let ftyp = |major: &[u8], compatible: &[&[u8]]| {
+            let mut data = ((16 + 4 * compatible.len()) as u32).to_be_bytes().to_vec();
+            data.extend_from_slice(b"ftyp");
+            data.extend_from_slice(major);
+            data.extend_from_slice(&[0; 4]);
+            for brand in compatible {
+                data.extend_from_slice(brand);
+            }
+            data
+        };
+        assert_eq!(
+            InputFormat::detect(&ftyp(b"heic", &[b"mif1"])),
+            InputFormat::Heic
+        );
+        assert_eq!(
+            InputFormat::detect(&ftyp(b"mif1", &[b"heic"])),
+            InputFormat::Heic
+        );
+        assert_eq!(
+            InputFormat::detect(&ftyp(b"avif", &[b"mif1"])),
+            InputFormat::Avif
+        );
+        assert_eq!(
+            InputFormat::detect(&ftyp(b"mif1", &[b"avif"])),
+            InputFormat::Avif
+        );
+        // MP4-Video ist kein Foto
+        assert_eq!(
+            InputFormat::detect(&ftyp(b"isom", &[b"mp41"])),
+            InputFormat::Unknown
+        );
+        assert!(InputFormat::Heic.needs_transcoding());
+        assert!(!InputFormat::Jpeg.needs_transcoding());
+    }
+
+    #[test]
+    fn test_open_image_ignores_extension() {
+        let dir = std::env::temp_dir().join(format!("format-test-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        // WebP mit falscher Endung
+        let path = dir.join("photo.jpg");
+        DynamicImage::new_rgb8(6, 3)
+            .save_with_format(&path, image::ImageFormat::WebP)
+            .unwrap();
+        assert_eq!(InputFormat::of_file(&path).unwrap(), InputFormat::WebP);
+        assert_eq!(open_image(&path).unwrap().width(), 6);
+        assert!(image_path_to_data_url(path.to_str().unwrap())
+            .unwrap()
+            .starts_with("data:image/webp;"));
+
+        let jpeg = dir.join("converted");
+        transcode_to_jpeg(&path, &jpeg).unwrap();
+        assert_eq!(InputFormat::of_file(&jpeg).unwrap(), InputFormat::Jpeg);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[cfg(not(feature = "heif"))]
+    #[test]
+    fn test_heic_without_feature_is_reported() {
+        let dir = std::env::temp_dir().join(format!("heic-test-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("IMG_0001.HEIC");
+        std::fs::write(&path, b"\0\0\0\x18ftypheic\0\0\0\0mif1heic").unwrap();
+
+        assert!(matches!(
+            open_image(&path),
+            Err(AppError::ImageProcessing(msg)) if msg.contains("heif")
+        ));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_apply_orientation() {
+        // Portrait photo stored landscape: orientation 6 = rotate 90° clockwise
+        let img = apply_orientation(DynamicImage::new_rgb8(4, 2), Some(6));
+        assert_eq!((img.width(), img.height()), (2, 4));
+
+        let img = apply_orientation(DynamicImage::new_rgb8(4, 2), Some(1));
+        assert_eq!((img.width(), img.height()), (4, 2));
+
+        // Invalid values are ignored
+        let img = apply_orientation(DynamicImage::new_rgb8(4, 2), Some(42));
+        assert_eq!((img.width(), img.height()), (4, 2));
+    }
+}
@@ -0,0 +1,14 @@
+//! Datenbank, Modelle und Services des Stalltagebuchs
+//!
+//! Enthält die komplette Geschäftslogik inkl. CRDT-Sync, aber keine
+//! Dioxus-Abhängigkeit. Dadurch lässt sich der Sync-Kreislauf
+//! (Schreiben → Erfassen → Hochladen → Herunterladen → Anwenden) mit
+//! [`services::sync_backend::MemoryBackend`] ohne Nextcloud testen.
+
+pub mod database;
+pub mod error;
+pub mod image_processing;
+pub mod models;
+pub mod pdf;
+pub mod services;
+pub mod spreadsheet;
@@ -0,0 +1,85 @@
+use crate::error::AppError;
+use rusqlite::Row;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// A coop or enclosure that quails (and their egg records) belong to
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Coop {
+    pub uuid: Uuid,
+    pub name: String,
+    pub notes: Option<String>,
+}
+
+impl Coop {
+    /// Creates a new coop with generated UUID
+    pub fn new(name: String) -> Self {
+        Self {
+            uuid: super::new_entity_id(),
+            name,
+            notes: None,
+        }
+    }
+
+    /// Validates all fields of the coop
+    pub fn validate(&self) -> Result<(), AppError> {
+        if self.name.trim().is_empty() {
+            return Err(AppError::Validation("Name must not be empty".to_string()));
+        }
+        if self.name.len() > 100 {
+            return Err(AppError::Validation(
+                "Name must not exceed 100 characters".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl super::Entity for Coop {
+    const ENTITY_TYPE: &'static str = "coop";
+    const TABLE: &'static str = "coops";
+    const COLUMNS: &'static [&'static str] = &["name", "notes"];
+    const NAME: &'static str = "Coop";
+
+    fn uuid(&self) -> Uuid {
+        self.uuid
+    }
+
+    fn from_row(row: &Row) -> rusqlite::Result<Self> {
+        Self::try_from(row)
+    }
+
+    fn values(&self) -> Vec<serde_json::Value> {
+        vec![self.name.clone().into(), self.notes.clone().into()]
+    }
+
+    fn validate(&self) -> Result<(), AppError> {
+        Coop::validate(self)
+    }
+}
+
+impl<'r> TryFrom<&Row<'r>> for Coop {
+    type Error = rusqlite::Error;
+
+    fn try_from(row: &Row<'r>) -> Result<Self, Self::Error> {
+        let uuid_str: String = row.get(0)?;
+        let uuid = Uuid::parse_str(&uuid_str).map_err(|_| rusqlite::Error::InvalidQuery)?;
+        Ok(Coop {
+            uuid,
+            name: row.get(1)?,
+            notes: row.get(2)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_name() {
+        assert!(Coop::new("Stall 1".to_string()).validate().is_ok());
+        assert!(Coop::new("  ".to_string()).validate().is_err());
+        assert!(Coop::new("x".repeat(101)).validate().is_err());
+    }
+}
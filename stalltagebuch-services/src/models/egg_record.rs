@@ -11,6 +11,8 @@ pub struct EggRecord {
     pub record_date: NaiveDate,
     pub total_eggs: i32,
     pub notes: Option<String>,
+    /// Coop the eggs were collected in; `None` = whole flock
+    pub coop_id: Option<Uuid>,
 }
 
 impl EggRecord {
@@ -18,10 +20,11 @@ impl EggRecord {
     #[allow(dead_code)]
     pub fn new(record_date: NaiveDate, total_eggs: i32) -> Self {
         Self {
-            uuid: Uuid::new_v4(),
+            uuid: super::new_entity_id(),
             record_date,
             total_eggs,
             notes: None,
+            coop_id: None,
         }
     }
 
@@ -54,6 +57,34 @@ impl EggRecord {
     }
 }
 
+impl super::Entity for EggRecord {
+    const ENTITY_TYPE: &'static str = "egg";
+    const TABLE: &'static str = "egg_records";
+    const COLUMNS: &'static [&'static str] = &["record_date", "total_eggs", "notes", "coop_id"];
+    const NAME: &'static str = "Egg record";
+
+    fn uuid(&self) -> Uuid {
+        self.uuid
+    }
+
+    fn from_row(row: &Row) -> rusqlite::Result<Self> {
+        Self::try_from(row)
+    }
+
+    fn values(&self) -> Vec<serde_json::Value> {
+        vec![
+            self.record_date.format("%Y-%m-%d").to_string().into(),
+            self.total_eggs.into(),
+            self.notes.clone().into(),
+            self.coop_id.map(|u| u.to_string()).into(),
+        ]
+    }
+
+    fn validate(&self) -> Result<(), AppError> {
+        EggRecord::validate(self)
+    }
+}
+
 impl<'r> TryFrom<&Row<'r>> for EggRecord {
     type Error = rusqlite::Error;
 
@@ -63,6 +94,7 @@ impl<'r> TryFrom<&Row<'r>> for EggRecord {
         let date_str: String = row.get(1)?;
         let total_eggs: i32 = row.get(2)?;
         let notes: Option<String> = row.get(3)?;
+        let coop_id: Option<String> = row.get(4)?;
 
         let record_date = NaiveDate::parse_from_str(&date_str, "%Y-%m-%d")
             .map_err(|e| rusqlite::Error::FromSqlConversionFailure(1, Type::Text, Box::new(e)))?;
@@ -72,6 +104,7 @@ impl<'r> TryFrom<&Row<'r>> for EggRecord {
             record_date,
             total_eggs,
             notes,
+            coop_id: coop_id.and_then(|s| Uuid::parse_str(&s).ok()),
         })
     }
 }
@@ -0,0 +1,31 @@
+use crate::error::AppError;
+use rusqlite::Row;
+use uuid::Uuid;
+
+/// A synced entity stored in its own table with a `uuid` primary key
+///
+/// Declares the table layout once; `services::repository` builds the
+/// queries, the soft-delete filter and the CRDT operations from it. A new
+/// entity type only needs this impl and a branch in the sync merge.
+pub trait Entity: Sized {
+    /// Entity type in the op log (`quail`, `event`, `egg`)
+    const ENTITY_TYPE: &'static str;
+    const TABLE: &'static str;
+    /// Synced columns after `uuid`, in the order of [`Entity::values`] and
+    /// of the columns read by [`Entity::from_row`]; also the op field names
+    const COLUMNS: &'static [&'static str];
+    /// Used in `AppError::NotFound`
+    const NAME: &'static str;
+
+    fn uuid(&self) -> Uuid;
+
+    /// Reads a row of `SELECT uuid, <COLUMNS> FROM <TABLE>`
+    fn from_row(row: &Row) -> rusqlite::Result<Self>;
+
+    /// Column values as stored and as sent in operations (`Null` = empty)
+    fn values(&self) -> Vec<serde_json::Value>;
+
+    fn validate(&self) -> Result<(), AppError> {
+        Ok(())
+    }
+}
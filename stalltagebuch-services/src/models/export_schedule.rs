@@ -0,0 +1,134 @@
+use chrono::{DateTime, Duration, Months, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Wie oft automatisch exportiert wird
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ExportInterval {
+    #[default]
+    Off,
+    Weekly,
+    Monthly,
+}
+
+impl ExportInterval {
+    pub const ALL: [ExportInterval; 3] = [
+        ExportInterval::Off,
+        ExportInterval::Weekly,
+        ExportInterval::Monthly,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ExportInterval::Off => "off",
+            ExportInterval::Weekly => "weekly",
+            ExportInterval::Monthly => "monthly",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|i| i.as_str() == value)
+    }
+
+    /// Zeitpunkt des nächsten Exports nach `last`; `None` wenn abgeschaltet
+    pub fn next_after(self, last: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        match self {
+            ExportInterval::Off => None,
+            ExportInterval::Weekly => Some(last + Duration::weeks(1)),
+            ExportInterval::Monthly => last.checked_add_months(Months::new(1)),
+        }
+    }
+}
+
+/// Wohin automatische Exporte geschrieben werden
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ExportDestination {
+    /// Nur in den lokalen Export-Ordner
+    #[default]
+    Local,
+    /// Nur in den Export-Ordner der Nextcloud
+    Nextcloud,
+    Both,
+}
+
+impl ExportDestination {
+    pub const ALL: [ExportDestination; 3] = [
+        ExportDestination::Local,
+        ExportDestination::Nextcloud,
+        ExportDestination::Both,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ExportDestination::Local => "local",
+            ExportDestination::Nextcloud => "nextcloud",
+            ExportDestination::Both => "both",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|d| d.as_str() == value)
+    }
+
+    pub fn keeps_local(self) -> bool {
+        self != ExportDestination::Nextcloud
+    }
+
+    pub fn uploads(self) -> bool {
+        self != ExportDestination::Local
+    }
+}
+
+/// Zeitplan automatischer Exporte (Tabelle `export_schedule`, nur lokal)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExportSchedule {
+    pub interval: ExportInterval,
+    pub destination: ExportDestination,
+    /// Aufbewahrte automatische Exporte je Ziel
+    pub keep: u32,
+    /// Letzter erfolgreicher Export (ms seit Epoch)
+    pub last_success: Option<i64>,
+    /// Letzter Versuch, auch fehlgeschlagen (ms seit Epoch)
+    pub last_attempt: Option<i64>,
+}
+
+impl Default for ExportSchedule {
+    fn default() -> Self {
+        Self {
+            interval: ExportInterval::Off,
+            destination: ExportDestination::Local,
+            keep: 4,
+            last_success: None,
+            last_attempt: None,
+        }
+    }
+}
+
+impl ExportSchedule {
+    /// Nach einem Fehlschlag frühestens so viel später erneut versuchen
+    pub const RETRY_DELAY: Duration = Duration::hours(6);
+
+    /// Nächster planmäßiger Export; `None` wenn abgeschaltet
+    pub fn next_run(&self) -> Option<DateTime<Utc>> {
+        match self.last_success.and_then(DateTime::from_timestamp_millis) {
+            Some(last) => self.interval.next_after(last),
+            None if self.interval == ExportInterval::Off => None,
+            None => Some(DateTime::UNIX_EPOCH),
+        }
+    }
+
+    /// Frühester nächster Versuch: planmäßig, nach einem Fehlschlag aber
+    /// nicht vor Ablauf von [`Self::RETRY_DELAY`]
+    pub fn next_attempt(&self) -> Option<DateTime<Utc>> {
+        let retry = self
+            .last_attempt
+            .and_then(DateTime::from_timestamp_millis)
+            .map(|attempt| attempt + Self::RETRY_DELAY);
+        self.next_run()
+            .map(|next| retry.map_or(next, |retry| next.max(retry)))
+    }
+
+    /// Steht ein Export an?
+    pub fn is_due(&self, now: DateTime<Utc>) -> bool {
+        self.next_attempt().is_some_and(|next| next <= now)
+    }
+}
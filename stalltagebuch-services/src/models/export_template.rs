@@ -0,0 +1,114 @@
+use crate::error::AppError;
+use crate::models::EventType;
+use serde::{Deserialize, Serialize};
+
+/// Welche Einträge eine Vorlage exportiert
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum TemplateEntity {
+    Quails,
+    #[default]
+    Events,
+    EggRecords,
+}
+
+impl TemplateEntity {
+    pub const ALL: [TemplateEntity; 3] = [
+        TemplateEntity::Quails,
+        TemplateEntity::Events,
+        TemplateEntity::EggRecords,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            TemplateEntity::Quails => "quails",
+            TemplateEntity::Events => "events",
+            TemplateEntity::EggRecords => "eggs",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|e| e.as_str() == value)
+    }
+
+    /// Mögliche Spalten in der Standard-Reihenfolge; die Schlüssel stehen
+    /// auch in der Kopfzeile der CSV
+    pub fn columns(self) -> &'static [&'static str] {
+        match self {
+            TemplateEntity::Quails => &["name", "gender", "ring_color"],
+            TemplateEntity::Events => &["date", "quail", "event_type", "notes"],
+            TemplateEntity::EggRecords => &["date", "total_eggs", "notes"],
+        }
+    }
+}
+
+/// Gespeicherte Auswahl für CSV-Exporte (Tabelle `export_templates`, nur lokal)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExportTemplate {
+    /// `None` solange nicht gespeichert
+    pub id: Option<i64>,
+    pub name: String,
+    pub entity: TemplateEntity,
+    /// Spaltenschlüssel aus [`TemplateEntity::columns`], in Export-Reihenfolge
+    pub columns: Vec<String>,
+    /// Nur für Ereignisse: erlaubte Typen, leer = alle
+    pub event_types: Vec<EventType>,
+}
+
+impl ExportTemplate {
+    /// Neue Vorlage mit allen Spalten
+    pub fn new(name: String, entity: TemplateEntity) -> Self {
+        Self {
+            id: None,
+            name,
+            entity,
+            columns: entity.columns().iter().map(|c| c.to_string()).collect(),
+            event_types: Vec::new(),
+        }
+    }
+
+    /// Bericht für den Tierarzt: nur Krankheit, Genesung und Todesfälle
+    pub fn vet_report() -> Self {
+        Self {
+            event_types: vec![EventType::Sick, EventType::Healthy, EventType::Died],
+            ..Self::new("Tierarzt-Bericht".to_string(), TemplateEntity::Events)
+        }
+    }
+
+    /// Spalten in der Standard-Reihenfolge statt in Auswahl-Reihenfolge
+    pub fn normalize(&mut self) {
+        let entity = self.entity;
+        self.columns = entity
+            .columns()
+            .iter()
+            .filter(|c| self.columns.iter().any(|selected| selected == *c))
+            .map(|c| c.to_string())
+            .collect();
+        if entity != TemplateEntity::Events {
+            self.event_types.clear();
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), AppError> {
+        if self.name.trim().is_empty() {
+            return Err(AppError::Validation(
+                "Vorlage braucht einen Namen".to_string(),
+            ));
+        }
+        if self.columns.is_empty() {
+            return Err(AppError::Validation(
+                "Vorlage braucht mindestens eine Spalte".to_string(),
+            ));
+        }
+        if let Some(unknown) = self
+            .columns
+            .iter()
+            .find(|c| !self.entity.columns().contains(&c.as_str()))
+        {
+            return Err(AppError::Validation(format!(
+                "Unbekannte Spalte: {}",
+                unknown
+            )));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,40 @@
+use serde::{Deserialize, Serialize};
+
+/// Schwere eines Eintrags im dauerhaften Protokoll
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LogLevel {
+    Info,
+    Warning,
+    Error,
+}
+
+impl LogLevel {
+    pub fn as_str(&self) -> &str {
+        match self {
+            LogLevel::Info => "info",
+            LogLevel::Warning => "warning",
+            LogLevel::Error => "error",
+        }
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Self {
+        match s {
+            "warning" => LogLevel::Warning,
+            "error" => LogLevel::Error,
+            _ => LogLevel::Info,
+        }
+    }
+}
+
+/// Ein Eintrag im dauerhaften Protokoll (Tabelle `app_log`)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LogEntry {
+    pub id: i64,
+    /// Zeitpunkt in ms seit Epoch
+    pub created_at: i64,
+    /// Herkunft, z.B. "integrity"
+    pub category: String,
+    pub level: LogLevel,
+    pub message: String,
+}
@@ -0,0 +1,32 @@
+pub mod coop;
+pub mod egg_record;
+pub mod entity;
+pub mod export_schedule;
+pub mod export_template;
+pub mod log_entry;
+pub mod photo;
+pub mod quail;
+pub mod quail_event;
+pub mod sync_run;
+pub mod sync_settings;
+
+pub use coop::Coop;
+pub use egg_record::EggRecord;
+pub use entity::Entity;
+pub use export_schedule::{ExportDestination, ExportInterval, ExportSchedule};
+pub use export_template::{ExportTemplate, TemplateEntity};
+pub use log_entry::{LogEntry, LogLevel};
+pub use photo::Photo;
+pub use quail::{Gender, Quail, RingColor};
+pub use quail_event::{EventType, QuailEvent, Treatment};
+pub use sync_run::{SyncRun, TransferDay};
+pub use sync_settings::{OriginalsPolicy, SyncSettings};
+
+/// Generates the ID of a new quail, event, egg record, coop or photo
+///
+/// UUIDv7 starts with the creation time, so new rows land at the end of the
+/// primary key index and IDs sort by age. Existing (v4) IDs stay valid; IDs
+/// are only ever parsed, never checked for a version.
+pub fn new_entity_id() -> uuid::Uuid {
+    uuid::Uuid::now_v7()
+}
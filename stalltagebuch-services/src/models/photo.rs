@@ -0,0 +1,160 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Photo {
+    pub uuid: Uuid,
+    pub quail_id: Option<Uuid>,
+    pub event_id: Option<Uuid>,
+    pub path: String,
+    pub thumbnail_path: Option<String>,
+    pub thumbnail_small_path: Option<String>,
+    pub thumbnail_medium_path: Option<String>,
+    pub sync_status: Option<String>,
+    pub sync_error: Option<String>,
+    pub retry_count: Option<i32>,
+    pub metadata: PhotoMetadata,
+    pub caption: Option<String>,
+    /// Freie Schlagwörter (klein geschrieben, ohne Duplikate)
+    pub tags: Vec<String>,
+}
+
+/// EXIF-Daten, die beim Import aus dem Originalfoto gelesen werden
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct PhotoMetadata {
+    /// EXIF-Orientierung (1-8), 1 = unverändert
+    pub orientation: Option<u16>,
+    pub taken_at: Option<NaiveDateTime>,
+    pub camera_model: Option<String>,
+    pub gps_latitude: Option<f64>,
+    pub gps_longitude: Option<f64>,
+}
+
+/// Fotos, die gemeinsam angezeigt und sortiert werden
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PhotoCollection {
+    Quail(Uuid),
+    Event(Uuid),
+}
+
+/// Eine Thumbnail-Stufe: `<uuid>_<name>.webp`, längste Kante `max_px`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ThumbnailTier {
+    pub name: &'static str,
+    pub max_px: u32,
+    /// Wird hochgeladen und steht in der Datenbank (`thumbnail_<name>_path`);
+    /// alle anderen Stufen entstehen nur lokal aus dem Original
+    pub synced: bool,
+}
+
+impl ThumbnailTier {
+    pub fn file_name(&self, photo_uuid: &str) -> String {
+        format!("{}_{}.webp", photo_uuid, self.name)
+    }
+}
+
+/// Beim Import erzeugte Thumbnail-Stufen, aufsteigend nach Größe
+pub const THUMBNAIL_TIERS: &[ThumbnailTier] = &[
+    // Statistik und Listen
+    ThumbnailTier {
+        name: "tiny",
+        max_px: 96,
+        synced: false,
+    },
+    ThumbnailTier {
+        name: "small",
+        max_px: 128,
+        synced: true,
+    },
+    // Detailansichten
+    ThumbnailTier {
+        name: "medium",
+        max_px: 512,
+        synced: true,
+    },
+    // Vollbildanzeige
+    ThumbnailTier {
+        name: "large",
+        max_px: 1024,
+        synced: false,
+    },
+];
+
+/// Gewünschte Größe beim Laden eines Fotos
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PhotoSize {
+    /// Stufe aus [`THUMBNAIL_TIERS`] (nach Name)
+    Thumbnail(&'static str),
+    /// Originalfoto (JPEG)
+    Original,
+}
+
+impl PhotoSize {
+    pub const TINY: PhotoSize = PhotoSize::Thumbnail("tiny");
+    pub const SMALL: PhotoSize = PhotoSize::Thumbnail("small");
+    pub const MEDIUM: PhotoSize = PhotoSize::Thumbnail("medium");
+    pub const LARGE: PhotoSize = PhotoSize::Thumbnail("large");
+
+    /// Die zugehörige Stufe (`None` für das Original und unbekannte Namen)
+    pub fn tier(self) -> Option<ThumbnailTier> {
+        match self {
+            PhotoSize::Thumbnail(name) => THUMBNAIL_TIERS.iter().find(|t| t.name == name).copied(),
+            PhotoSize::Original => None,
+        }
+    }
+
+    /// Name der Größe in Asset-URLs (`original` oder Name der Stufe)
+    pub fn name(self) -> &'static str {
+        match self {
+            PhotoSize::Thumbnail(name) => name,
+            PhotoSize::Original => "original",
+        }
+    }
+
+    /// Gegenstück zu [`PhotoSize::name`]
+    pub fn from_name(name: &str) -> Option<PhotoSize> {
+        if name == "original" {
+            return Some(PhotoSize::Original);
+        }
+        THUMBNAIL_TIERS
+            .iter()
+            .find(|t| t.name == name)
+            .map(|t| PhotoSize::Thumbnail(t.name))
+    }
+}
+
+/// Remote-Kopie eines Fotos wurde außerhalb der App verändert
+/// (z.B. über die Nextcloud-Weboberfläche)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RemoteConflict {
+    /// Inhalt weicht vom hochgeladenen Original ab
+    Modified,
+    /// Datei fehlt auf dem Server
+    Deleted,
+}
+
+impl RemoteConflict {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            RemoteConflict::Modified => "modified",
+            RemoteConflict::Deleted => "deleted",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "modified" => Some(RemoteConflict::Modified),
+            "deleted" => Some(RemoteConflict::Deleted),
+            _ => None,
+        }
+    }
+}
+
+/// Ergebnis beim Laden eines Fotos; `T` sind die Bytes oder die Asset-URL
+#[derive(Debug, Clone, PartialEq)]
+pub enum PhotoResult<T = Vec<u8>> {
+    Available(T),
+    Downloading,
+    Failed(String, i32), // (error message, retry_count)
+}
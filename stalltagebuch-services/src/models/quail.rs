@@ -10,6 +10,11 @@ pub struct Quail {
     pub gender: Gender,
     pub ring_color: Option<RingColor>,
     pub profile_photo: Option<Uuid>,
+    /// Mother (pedigree); may point to a deleted or not yet synced quail
+    pub mother_id: Option<Uuid>,
+    pub father_id: Option<Uuid>,
+    /// Coop the quail lives in; may point to a deleted coop
+    pub coop_id: Option<Uuid>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -131,11 +136,14 @@ impl Quail {
     /// Creates a new quail with generated UUID
     pub fn new(name: String) -> Self {
         Self {
-            uuid: Uuid::new_v4(),
+            uuid: super::new_entity_id(),
             name,
             gender: Gender::Unknown,
             ring_color: None,
             profile_photo: None,
+            mother_id: None,
+            father_id: None,
+            coop_id: None,
         }
     }
 
@@ -153,8 +161,64 @@ impl Quail {
             ));
         }
 
+        // A quail cannot descend from itself
+        if self.mother_id == Some(self.uuid) || self.father_id == Some(self.uuid) {
+            return Err(AppError::Validation(
+                "A quail cannot be its own parent".to_string(),
+            ));
+        }
+        if self.mother_id.is_some() && self.mother_id == self.father_id {
+            return Err(AppError::Validation(
+                "Mother and father must be different quails".to_string(),
+            ));
+        }
+
         Ok(())
     }
+
+    /// Mother and father, if set
+    pub fn parents(&self) -> impl Iterator<Item = Uuid> {
+        self.mother_id.into_iter().chain(self.father_id)
+    }
+}
+
+impl super::Entity for Quail {
+    const ENTITY_TYPE: &'static str = "quail";
+    const TABLE: &'static str = "quails";
+    const COLUMNS: &'static [&'static str] = &[
+        "name",
+        "gender",
+        "ring_color",
+        "profile_photo",
+        "mother_id",
+        "father_id",
+        "coop_id",
+    ];
+    const NAME: &'static str = "Quail profile";
+
+    fn uuid(&self) -> Uuid {
+        self.uuid
+    }
+
+    fn from_row(row: &Row) -> rusqlite::Result<Self> {
+        Self::try_from(row)
+    }
+
+    fn values(&self) -> Vec<serde_json::Value> {
+        vec![
+            self.name.clone().into(),
+            self.gender.as_str().into(),
+            self.ring_color.as_ref().map(|c| c.as_str()).into(),
+            self.profile_photo.map(|u| u.to_string()).into(),
+            self.mother_id.map(|u| u.to_string()).into(),
+            self.father_id.map(|u| u.to_string()).into(),
+            self.coop_id.map(|u| u.to_string()).into(),
+        ]
+    }
+
+    fn validate(&self) -> Result<(), AppError> {
+        Quail::validate(self)
+    }
 }
 
 impl<'r> TryFrom<&Row<'r>> for Quail {
@@ -170,6 +234,11 @@ impl<'r> TryFrom<&Row<'r>> for Quail {
         let profile_photo = profile_photo_str
             .map(|s| Uuid::parse_str(&s).ok())
             .flatten();
+        let reference = |index: usize| -> rusqlite::Result<Option<Uuid>> {
+            Ok(row
+                .get::<_, Option<String>>(index)?
+                .and_then(|s| Uuid::parse_str(&s).ok()))
+        };
 
         Ok(Quail {
             uuid,
@@ -177,6 +246,9 @@ impl<'r> TryFrom<&Row<'r>> for Quail {
             gender: Gender::from_str(&gender_str),
             ring_color: ring_color_opt.map(|s| RingColor::from_str(&s)),
             profile_photo,
+            mother_id: reference(5)?,
+            father_id: reference(6)?,
+            coop_id: reference(7)?,
         })
     }
 }
@@ -193,6 +265,15 @@ mod tests {
         assert!(quail.uuid.is_nil() == false);
     }
 
+    #[test]
+    fn test_new_ids_sort_by_creation() {
+        let first = Quail::new("A".to_string());
+        std::thread::sleep(std::time::Duration::from_millis(2));
+        let second = Quail::new("B".to_string());
+        assert_eq!(first.uuid.get_version_num(), 7);
+        assert!(first.uuid.to_string() < second.uuid.to_string());
+    }
+
     #[test]
     fn test_validate_empty_name() {
         let mut quail = Quail::new("".to_string());
@@ -200,6 +281,22 @@ mod tests {
         assert!(quail.validate().is_err());
     }
 
+    #[test]
+    fn test_validate_parents() {
+        let mut quail = Quail::new("Küken".to_string());
+        quail.mother_id = Some(quail.uuid);
+        assert!(quail.validate().is_err());
+
+        let parent = Uuid::new_v4();
+        quail.mother_id = Some(parent);
+        quail.father_id = Some(parent);
+        assert!(quail.validate().is_err());
+
+        quail.father_id = Some(Uuid::new_v4());
+        assert!(quail.validate().is_ok());
+        assert_eq!(quail.parents().count(), 2);
+    }
+
     #[test]
     fn test_gender_conversion() {
         assert_eq!(Gender::from_str("male"), Gender::Male);
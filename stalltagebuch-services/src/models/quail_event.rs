@@ -0,0 +1,357 @@
+use crate::error::AppError;
+use chrono::NaiveDate;
+use rusqlite::types::Type;
+use rusqlite::Row;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Event in the life of a quail (status change, birth, etc.)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct QuailEvent {
+    pub uuid: Uuid,
+    pub quail_id: Uuid,
+    pub event_type: EventType,
+    pub event_date: NaiveDate,
+    pub notes: Option<String>,
+    /// Medication details, only set for [`EventType::Treatment`]
+    pub treatment: Option<Treatment>,
+}
+
+/// Medication given in a treatment event
+///
+/// The event date is the first day of the treatment. Eggs and meat must not
+/// be used during the treatment and for the withdrawal period after its last
+/// day.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Treatment {
+    pub medication: String,
+    /// Free text, e.g. "0.5 ml per litre of drinking water"
+    pub dose: Option<String>,
+    pub duration_days: u32,
+    pub egg_withdrawal_days: u32,
+    pub meat_withdrawal_days: u32,
+}
+
+impl Default for Treatment {
+    /// One day without withdrawal period, the starting point of a form
+    fn default() -> Self {
+        Self {
+            medication: String::new(),
+            dose: None,
+            duration_days: 1,
+            egg_withdrawal_days: 0,
+            meat_withdrawal_days: 0,
+        }
+    }
+}
+
+impl Treatment {
+    /// Last day of the treatment starting on `start`
+    pub fn last_day(&self, start: NaiveDate) -> NaiveDate {
+        start + chrono::Days::new(u64::from(self.duration_days.max(1) - 1))
+    }
+
+    /// Last day on which eggs must not be sold or eaten
+    pub fn eggs_withheld_until(&self, start: NaiveDate) -> NaiveDate {
+        self.last_day(start) + chrono::Days::new(u64::from(self.egg_withdrawal_days))
+    }
+
+    /// Last day on which the quail must not be slaughtered for meat
+    pub fn meat_withheld_until(&self, start: NaiveDate) -> NaiveDate {
+        self.last_day(start) + chrono::Days::new(u64::from(self.meat_withdrawal_days))
+    }
+
+    /// Validates the medication details
+    pub fn validate(&self) -> Result<(), AppError> {
+        if self.medication.trim().is_empty() {
+            return Err(AppError::Validation(
+                "Medication must not be empty".to_string(),
+            ));
+        }
+        if self.duration_days == 0 {
+            return Err(AppError::Validation(
+                "Treatment must last at least one day".to_string(),
+            ));
+        }
+        if [
+            self.duration_days,
+            self.egg_withdrawal_days,
+            self.meat_withdrawal_days,
+        ]
+        .iter()
+        .any(|days| *days > 365)
+        {
+            return Err(AppError::Validation(
+                "Treatment and withdrawal periods must not exceed 365 days".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum EventType {
+    Born,               // Birth
+    Alive,              // Living (default state)
+    Sick,               // Sick
+    Healthy,            // Recovered/Healthy
+    MarkedForSlaughter, // Marked for slaughter
+    Slaughtered,        // Slaughtered
+    Died,               // Died naturally
+    Treatment,          // Medication, see [`Treatment`]
+}
+
+impl EventType {
+    pub const ALL: [EventType; 8] = [
+        EventType::Born,
+        EventType::Alive,
+        EventType::Sick,
+        EventType::Healthy,
+        EventType::MarkedForSlaughter,
+        EventType::Slaughtered,
+        EventType::Died,
+        EventType::Treatment,
+    ];
+
+    pub fn as_str(&self) -> &str {
+        match self {
+            EventType::Born => "born",
+            EventType::Alive => "alive",
+            EventType::Sick => "sick",
+            EventType::Healthy => "healthy",
+            EventType::MarkedForSlaughter => "marked_for_slaughter",
+            EventType::Slaughtered => "slaughtered",
+            EventType::Died => "died",
+            EventType::Treatment => "treatment",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Self {
+        match s {
+            "born" | "geboren" => EventType::Born,
+            "alive" | "am_leben" => EventType::Alive,
+            "sick" | "krank" => EventType::Sick,
+            "healthy" | "gesund" => EventType::Healthy,
+            "marked_for_slaughter" | "markiert_zum_schlachten" => EventType::MarkedForSlaughter,
+            "slaughtered" | "geschlachtet" => EventType::Slaughtered,
+            "died" | "gestorben" => EventType::Died,
+            "treatment" | "behandlung" => EventType::Treatment,
+            _ => EventType::Alive,
+        }
+    }
+
+    pub fn display_name(&self) -> &str {
+        match self {
+            EventType::Born => "Geboren",
+            EventType::Alive => "Am Leben",
+            EventType::Sick => "Krank",
+            EventType::Healthy => "Gesund",
+            EventType::MarkedForSlaughter => "Markiert zum Schlachten",
+            EventType::Slaughtered => "Geschlachtet",
+            EventType::Died => "Gestorben",
+            EventType::Treatment => "Behandlung",
+        }
+    }
+
+    /// Returns true if this event type represents a final state (death)
+    #[allow(dead_code)]
+    pub fn is_final(&self) -> bool {
+        matches!(self, EventType::Slaughtered | EventType::Died)
+    }
+
+    /// Returns true if this event type is a health-related status
+    #[allow(dead_code)]
+    pub fn is_health_status(&self) -> bool {
+        matches!(self, EventType::Sick | EventType::Healthy)
+    }
+}
+
+impl QuailEvent {
+    /// Creates a new event
+    pub fn new(quail_id: Uuid, event_type: EventType, event_date: NaiveDate) -> Self {
+        Self {
+            uuid: super::new_entity_id(),
+            quail_id,
+            event_type,
+            event_date,
+            notes: None,
+            treatment: None,
+        }
+    }
+
+    /// Validates the event
+    pub fn validate(&self) -> Result<(), AppError> {
+        // Event date should not be in the future, except for planned events
+        let allows_future = matches!(self.event_type, EventType::MarkedForSlaughter);
+        if !allows_future && self.event_date > chrono::Local::now().date_naive() {
+            return Err(AppError::Validation(
+                "Event date must not be in the future".to_string(),
+            ));
+        }
+
+        match (&self.event_type, &self.treatment) {
+            (EventType::Treatment, Some(treatment)) => treatment.validate()?,
+            (EventType::Treatment, None) => {
+                return Err(AppError::Validation(
+                    "Treatment events need medication details".to_string(),
+                ));
+            }
+            (_, Some(_)) => {
+                return Err(AppError::Validation(
+                    "Only treatment events have medication details".to_string(),
+                ));
+            }
+            (_, None) => {}
+        }
+
+        // Notes should not be too long
+        if let Some(notes) = &self.notes {
+            if notes.len() > 1000 {
+                return Err(AppError::Validation(
+                    "Notes must not exceed 1000 characters".to_string(),
+                ));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl super::Entity for QuailEvent {
+    const ENTITY_TYPE: &'static str = "event";
+    const TABLE: &'static str = "quail_events";
+    const COLUMNS: &'static [&'static str] = &[
+        "quail_id",
+        "event_type",
+        "event_date",
+        "notes",
+        "medication",
+        "dose",
+        "duration_days",
+        "egg_withdrawal_days",
+        "meat_withdrawal_days",
+    ];
+    const NAME: &'static str = "Event";
+
+    fn uuid(&self) -> Uuid {
+        self.uuid
+    }
+
+    fn from_row(row: &Row) -> rusqlite::Result<Self> {
+        Self::try_from(row)
+    }
+
+    fn values(&self) -> Vec<serde_json::Value> {
+        vec![
+            self.quail_id.to_string().into(),
+            self.event_type.as_str().into(),
+            self.event_date.to_string().into(),
+            self.notes.clone().into(),
+            self.treatment.as_ref().map(|t| t.medication.clone()).into(),
+            self.treatment.as_ref().and_then(|t| t.dose.clone()).into(),
+            self.treatment.as_ref().map(|t| t.duration_days).into(),
+            self.treatment
+                .as_ref()
+                .map(|t| t.egg_withdrawal_days)
+                .into(),
+            self.treatment
+                .as_ref()
+                .map(|t| t.meat_withdrawal_days)
+                .into(),
+        ]
+    }
+
+    fn validate(&self) -> Result<(), AppError> {
+        QuailEvent::validate(self)
+    }
+}
+
+impl<'r> TryFrom<&Row<'r>> for QuailEvent {
+    type Error = rusqlite::Error;
+
+    fn try_from(row: &Row<'r>) -> Result<Self, Self::Error> {
+        let uuid_str: String = row.get(0)?;
+        let uuid = Uuid::parse_str(&uuid_str).map_err(|_| rusqlite::Error::InvalidQuery)?;
+        let quail_id_str: String = row.get(1)?;
+        let quail_id = Uuid::parse_str(&quail_id_str).map_err(|_| rusqlite::Error::InvalidQuery)?;
+        let event_type_str: String = row.get(2)?;
+        let event_date_str: String = row.get(3)?;
+        let notes: Option<String> = row.get(4)?;
+        // Medication marks a treatment; missing periods mean one treatment
+        // day without withdrawal
+        let medication: Option<String> = row.get(5)?;
+        let treatment = match medication {
+            Some(medication) => Some(Treatment {
+                medication,
+                dose: row.get(6)?,
+                duration_days: row.get::<_, Option<u32>>(7)?.unwrap_or(1),
+                egg_withdrawal_days: row.get::<_, Option<u32>>(8)?.unwrap_or_default(),
+                meat_withdrawal_days: row.get::<_, Option<u32>>(9)?.unwrap_or_default(),
+            }),
+            None => None,
+        };
+
+        let event_date = NaiveDate::parse_from_str(&event_date_str, "%Y-%m-%d")
+            .map_err(|e| rusqlite::Error::FromSqlConversionFailure(3, Type::Text, Box::new(e)))?;
+
+        Ok(QuailEvent {
+            uuid,
+            quail_id,
+            event_type: EventType::from_str(&event_type_str),
+            event_date,
+            notes,
+            treatment,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn treatment(duration_days: u32, egg_withdrawal_days: u32) -> Treatment {
+        Treatment {
+            medication: "Baytril".to_string(),
+            dose: None,
+            duration_days,
+            egg_withdrawal_days,
+            meat_withdrawal_days: 14,
+        }
+    }
+
+    #[test]
+    fn test_withdrawal_periods() {
+        let start = NaiveDate::from_ymd_opt(2025, 4, 28).unwrap();
+        let t = treatment(5, 10);
+        assert_eq!(
+            t.last_day(start),
+            NaiveDate::from_ymd_opt(2025, 5, 2).unwrap()
+        );
+        assert_eq!(
+            t.eggs_withheld_until(start),
+            NaiveDate::from_ymd_opt(2025, 5, 12).unwrap()
+        );
+        assert_eq!(
+            t.meat_withheld_until(start),
+            NaiveDate::from_ymd_opt(2025, 5, 16).unwrap()
+        );
+        // Without withdrawal only the treatment days themselves are withheld
+        assert_eq!(treatment(1, 0).eggs_withheld_until(start), start);
+    }
+
+    #[test]
+    fn test_validate_treatment_details() {
+        let start = NaiveDate::from_ymd_opt(2025, 4, 28).unwrap();
+        let mut event = QuailEvent::new(Uuid::new_v4(), EventType::Treatment, start);
+        assert!(event.validate().is_err());
+        event.treatment = Some(treatment(5, 10));
+        assert!(event.validate().is_ok());
+        event.treatment = Some(treatment(0, 10));
+        assert!(event.validate().is_err());
+
+        let mut sick = QuailEvent::new(Uuid::new_v4(), EventType::Sick, start);
+        sick.treatment = Some(treatment(5, 10));
+        assert!(sick.validate().is_err());
+    }
+}
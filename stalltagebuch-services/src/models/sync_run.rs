@@ -0,0 +1,48 @@
+use serde::{Deserialize, Serialize};
+
+/// Ein abgeschlossener Sync-Durchlauf (Tabelle `sync_history`)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SyncRun {
+    pub id: i64,
+    /// Start in ms seit Epoch
+    pub started_at: i64,
+    pub duration_ms: i64,
+    /// Vom Nutzer gestartet ("Jetzt synchronisieren") statt im Hintergrund
+    pub on_demand: bool,
+    pub operations_uploaded: usize,
+    pub operations_downloaded: usize,
+    pub photos_uploaded: usize,
+    pub photos_downloaded: usize,
+    /// Übertragene Nutzdaten (ohne HTTP-Header)
+    pub bytes_uploaded: u64,
+    pub bytes_downloaded: u64,
+    /// Anfragen an den Server
+    pub request_count: u64,
+    /// Fehler, an dem der Durchlauf abgebrochen ist
+    pub error: Option<String>,
+}
+
+/// Summierter Datenverkehr eines Tages (Tabelle `sync_transfer_daily`)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TransferDay {
+    /// Lokales Datum `YYYY-MM-DD`
+    pub day: String,
+    /// Sync-Durchläufe, übersprungene mitgezählt
+    pub runs: u64,
+    pub bytes_uploaded: u64,
+    pub bytes_downloaded: u64,
+    pub request_count: u64,
+    pub duration_ms: i64,
+}
+
+impl TransferDay {
+    pub fn total_bytes(&self) -> u64 {
+        self.bytes_uploaded + self.bytes_downloaded
+    }
+}
+
+impl SyncRun {
+    pub fn is_failure(&self) -> bool {
+        self.error.is_some()
+    }
+}
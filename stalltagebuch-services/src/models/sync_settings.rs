@@ -0,0 +1,139 @@
+use serde::{Deserialize, Serialize};
+
+/// Wann Original-Fotos übertragen werden; Metadaten und Thumbnails werden
+/// immer synchronisiert
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OriginalsPolicy {
+    /// Bei jeder Synchronisierung
+    #[default]
+    Always,
+    /// Nur über WLAN (nicht getaktete Verbindung)
+    WifiOnly,
+    /// Nur über WLAN und während das Gerät lädt
+    WifiAndCharging,
+    /// Nur bei "Jetzt synchronisieren" bzw. beim Öffnen eines Fotos
+    OnDemand,
+}
+
+impl OriginalsPolicy {
+    pub const ALL: [OriginalsPolicy; 4] = [
+        OriginalsPolicy::Always,
+        OriginalsPolicy::WifiOnly,
+        OriginalsPolicy::WifiAndCharging,
+        OriginalsPolicy::OnDemand,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            OriginalsPolicy::Always => "always",
+            OriginalsPolicy::WifiOnly => "wifi_only",
+            OriginalsPolicy::WifiAndCharging => "wifi_and_charging",
+            OriginalsPolicy::OnDemand => "on_demand",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|p| p.as_str() == value)
+    }
+}
+
+/// Synchronization settings for Nextcloud/WebDAV
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SyncSettings {
+    pub id: i64,
+    pub server_url: String,
+    pub username: String,
+    pub app_password: String,
+    pub remote_path: String,
+    pub enabled: bool,
+    pub last_sync: Option<String>,
+    pub device_id: Option<String>,
+    pub format_version: i32,
+    pub initial_upload_done: bool,
+    /// Server hat das App-Passwort abgelehnt (401), Sync pausiert bis zur erneuten Anmeldung
+    pub credentials_invalid: bool,
+    /// SHA-256-Fingerabdruck des beim Login gemerkten Server-Zertifikats (Trust on first use)
+    pub cert_fingerprint: Option<String>,
+    /// HTTP-/SOCKS-Proxy für Anmeldung und WebDAV (siehe `ProxyConfig`)
+    pub proxy_url: Option<String>,
+    /// Obergrenze für lokal gespeicherte Fotos; darüber werden synchronisierte Originale entfernt
+    pub photo_storage_limit_mb: Option<u32>,
+    /// Gleichzeitig hochgeladene Fotodateien (`None` = Standardwert)
+    pub upload_concurrency: Option<u32>,
+    /// Übertragung der Original-Fotos (Thumbnails gehen immer)
+    #[serde(default)]
+    pub originals_policy: OriginalsPolicy,
+    /// Hintergrund-Sync über getaktete Verbindungen (Mobilfunk) aussetzen
+    #[serde(default)]
+    pub pause_on_metered: bool,
+    /// Hintergrund-Sync bei niedrigem Akkustand aussetzen
+    #[serde(default)]
+    pub pause_on_low_battery: bool,
+    /// Sync vorübergehend angehalten: Änderungen sammeln sich lokal, es wird
+    /// nichts übertragen
+    #[serde(default)]
+    pub paused: bool,
+    /// Entitätstypen (siehe [`SyncSettings::ENTITY_TYPES`]), die nicht
+    /// synchronisiert werden
+    #[serde(default)]
+    pub excluded_entity_types: Vec<String>,
+    /// Nextcloud-Ordner für hochgeladene Exporte (`None` = [`SyncSettings::export_folder`])
+    #[serde(default)]
+    pub export_path: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+impl SyncSettings {
+    /// Einzeln abschaltbare Entitätstypen (wie in den Operationen)
+    pub const ENTITY_TYPES: [&'static str; 5] = ["quail", "event", "egg", "photo", "coop"];
+
+    /// Werden Änderungen dieses Entitätstyps synchronisiert?
+    pub fn syncs(&self, entity_type: &str) -> bool {
+        !self.excluded_entity_types.iter().any(|t| t == entity_type)
+    }
+
+    /// Ordner für hochgeladene Exporte: eingestellt oder `<remote_path>/exports`,
+    /// immer mit führendem und ohne abschließenden `/`
+    pub fn export_folder(&self) -> String {
+        let folder = match self.export_path.as_deref().map(str::trim) {
+            Some(path) if !path.is_empty() => path.to_string(),
+            _ => format!("{}/exports", self.remote_path.trim_end_matches('/')),
+        };
+        format!("/{}", folder.trim_matches('/'))
+    }
+
+    pub fn new(
+        server_url: String,
+        username: String,
+        app_password: String,
+        remote_path: String,
+    ) -> Self {
+        Self {
+            id: 0,
+            server_url,
+            username,
+            app_password,
+            remote_path,
+            enabled: true,
+            last_sync: None,
+            device_id: None,
+            format_version: 2,
+            initial_upload_done: false,
+            credentials_invalid: false,
+            cert_fingerprint: None,
+            proxy_url: None,
+            photo_storage_limit_mb: None,
+            upload_concurrency: None,
+            originals_policy: OriginalsPolicy::default(),
+            pause_on_metered: false,
+            pause_on_low_battery: false,
+            paused: false,
+            excluded_entity_types: Vec::new(),
+            export_path: None,
+            created_at: String::new(),
+            updated_at: String::new(),
+        }
+    }
+}
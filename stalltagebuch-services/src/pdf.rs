@@ -0,0 +1,341 @@
+//! Minimal PDF writer for the reports
+//!
+//! Supports what the flock report needs: text in the built-in Helvetica
+//! fonts, filled rectangles and lines (charts), and JPEG images. Positions
+//! are in points from the bottom left of an A4 page. No font embedding, so
+//! text is limited to the WinAnsi character set (German umlauts included);
+//! other characters are printed as `?`.
+
+use flate2::write::ZlibEncoder;
+use flate2::Compression;
+use std::io::Write;
+
+/// A4 in points
+pub const PAGE_WIDTH: f32 = 595.0;
+pub const PAGE_HEIGHT: f32 = 842.0;
+
+/// Fill/stroke colour, components 0.0–1.0
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Color(pub f32, pub f32, pub f32);
+
+impl Color {
+    pub const BLACK: Color = Color(0.0, 0.0, 0.0);
+    pub const GRAY: Color = Color(0.45, 0.45, 0.45);
+    pub const LIGHT_GRAY: Color = Color(0.88, 0.88, 0.88);
+}
+
+/// Handle of an image added with [`PdfDocument::add_jpeg`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ImageId(usize);
+
+struct Image {
+    width: u32,
+    height: u32,
+    jpeg: Vec<u8>,
+}
+
+/// Drawing operations of one page
+#[derive(Default)]
+pub struct Page {
+    content: String,
+}
+
+impl Page {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Text with its baseline at `y`
+    pub fn text(&mut self, x: f32, y: f32, size: f32, bold: bool, color: Color, text: &str) {
+        let font = if bold { "F2" } else { "F1" };
+        self.content.push_str(&format!(
+            "BT {} rg /{} {} Tf {:.2} {:.2} Td ({}) Tj ET\n",
+            rgb(color),
+            font,
+            size,
+            x,
+            y,
+            escape_text(text)
+        ));
+    }
+
+    pub fn fill_rect(&mut self, x: f32, y: f32, width: f32, height: f32, color: Color) {
+        self.content.push_str(&format!(
+            "{} rg {:.2} {:.2} {:.2} {:.2} re f\n",
+            rgb(color),
+            x,
+            y,
+            width,
+            height
+        ));
+    }
+
+    pub fn line(&mut self, from: (f32, f32), to: (f32, f32), width: f32, color: Color) {
+        self.content.push_str(&format!(
+            "{} RG {} w {:.2} {:.2} m {:.2} {:.2} l S\n",
+            rgb(color),
+            width,
+            from.0,
+            from.1,
+            to.0,
+            to.1
+        ));
+    }
+
+    /// Draws the image scaled into the box with the lower left corner at `x`/`y`
+    pub fn image(&mut self, image: ImageId, x: f32, y: f32, width: f32, height: f32) {
+        self.content.push_str(&format!(
+            "q {:.2} 0 0 {:.2} {:.2} {:.2} cm /Im{} Do Q\n",
+            width, height, x, y, image.0
+        ));
+    }
+}
+
+fn rgb(color: Color) -> String {
+    format!("{:.3} {:.3} {:.3}", color.0, color.1, color.2)
+}
+
+/// Approximate width of `text` in Helvetica (average glyph width), for
+/// right alignment and truncation
+pub fn text_width(text: &str, size: f32) -> f32 {
+    text.chars().count() as f32 * size * 0.5
+}
+
+/// Maps a character to WinAnsiEncoding
+fn win_ansi(c: char) -> u8 {
+    match c {
+        ' '..='~' => c as u8,
+        '\u{a0}'..='\u{ff}' => c as u32 as u8,
+        '€' => 0x80,
+        '‚' => 0x82,
+        '„' => 0x84,
+        '…' => 0x85,
+        '‘' => 0x91,
+        '’' => 0x92,
+        '“' => 0x93,
+        '”' => 0x94,
+        '•' => 0x95,
+        '–' => 0x96,
+        '—' => 0x97,
+        _ => b'?',
+    }
+}
+
+/// PDF string literal content: WinAnsi bytes, special characters escaped
+fn escape_text(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for byte in text.chars().map(win_ansi) {
+        match byte {
+            b'(' | b')' | b'\\' => {
+                out.push('\\');
+                out.push(byte as char);
+            }
+            0x20..=0x7e => out.push(byte as char),
+            _ => out.push_str(&format!("\\{:03o}", byte)),
+        }
+    }
+    out
+}
+
+/// Document being built: images are shared by all pages
+#[derive(Default)]
+pub struct PdfDocument {
+    pages: Vec<Page>,
+    images: Vec<Image>,
+}
+
+impl PdfDocument {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a baseline JPEG (RGB) of the given pixel size
+    pub fn add_jpeg(&mut self, width: u32, height: u32, jpeg: Vec<u8>) -> ImageId {
+        self.images.push(Image {
+            width,
+            height,
+            jpeg,
+        });
+        ImageId(self.images.len() - 1)
+    }
+
+    pub fn add_page(&mut self, page: Page) {
+        self.pages.push(page);
+    }
+
+    pub fn page_count(&self) -> usize {
+        self.pages.len()
+    }
+
+    /// Serializes the document
+    ///
+    /// Objects: 1 catalog, 2 page tree, 3/4 fonts, then the images, then
+    /// page and content stream of every page.
+    pub fn finish(self) -> Vec<u8> {
+        let mut writer = ObjectWriter::new();
+        let first_image = 5;
+        let first_page = first_image + self.images.len();
+        let page_ids: Vec<usize> = (0..self.pages.len()).map(|i| first_page + 2 * i).collect();
+
+        writer.object(1, b"<< /Type /Catalog /Pages 2 0 R >>");
+        let kids: Vec<String> = page_ids.iter().map(|id| format!("{} 0 R", id)).collect();
+        writer.object(
+            2,
+            format!(
+                "<< /Type /Pages /Kids [{}] /Count {} >>",
+                kids.join(" "),
+                page_ids.len()
+            )
+            .as_bytes(),
+        );
+        for (id, font) in [(3, "Helvetica"), (4, "Helvetica-Bold")] {
+            writer.object(
+                id,
+                format!(
+                    "<< /Type /Font /Subtype /Type1 /BaseFont /{} /Encoding /WinAnsiEncoding >>",
+                    font
+                )
+                .as_bytes(),
+            );
+        }
+
+        for (i, image) in self.images.iter().enumerate() {
+            writer.stream(
+                first_image + i,
+                &format!(
+                    "/Type /XObject /Subtype /Image /Width {} /Height {} \
+                     /ColorSpace /DeviceRGB /BitsPerComponent 8 /Filter /DCTDecode",
+                    image.width, image.height
+                ),
+                &image.jpeg,
+            );
+        }
+
+        let images: String = (0..self.images.len())
+            .map(|i| format!("/Im{} {} 0 R ", i, first_image + i))
+            .collect();
+        for (page, id) in self.pages.iter().zip(&page_ids) {
+            writer.object(
+                *id,
+                format!(
+                    "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {} {}] \
+                     /Resources << /Font << /F1 3 0 R /F2 4 0 R >> /XObject << {}>> >> \
+                     /Contents {} 0 R >>",
+                    PAGE_WIDTH,
+                    PAGE_HEIGHT,
+                    images,
+                    id + 1
+                )
+                .as_bytes(),
+            );
+            writer.stream(id + 1, "/Filter /FlateDecode", &deflate(&page.content));
+        }
+
+        writer.finish(first_page + 2 * self.pages.len())
+    }
+}
+
+fn deflate(content: &str) -> Vec<u8> {
+    let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+    // Writing into a Vec cannot fail
+    let _ = encoder.write_all(content.as_bytes());
+    encoder.finish().unwrap_or_default()
+}
+
+/// Writes numbered objects and remembers their offsets for the xref table
+struct ObjectWriter {
+    out: Vec<u8>,
+    offsets: Vec<(usize, usize)>,
+}
+
+impl ObjectWriter {
+    fn new() -> Self {
+        // Binary marker comment so tools treat the file as binary
+        Self {
+            out: b"%PDF-1.4\n%\xe2\xe3\xcf\xd3\n".to_vec(),
+            offsets: Vec::new(),
+        }
+    }
+
+    fn object(&mut self, id: usize, body: &[u8]) {
+        self.offsets.push((id, self.out.len()));
+        self.out
+            .extend_from_slice(format!("{} 0 obj\n", id).as_bytes());
+        self.out.extend_from_slice(body);
+        self.out.extend_from_slice(b"\nendobj\n");
+    }
+
+    fn stream(&mut self, id: usize, dict: &str, data: &[u8]) {
+        let mut body = format!("<< {} /Length {} >>\nstream\n", dict, data.len()).into_bytes();
+        body.extend_from_slice(data);
+        body.extend_from_slice(b"\nendstream");
+        self.object(id, &body);
+    }
+
+    /// `size` = highest object id + 1
+    fn finish(mut self, size: usize) -> Vec<u8> {
+        self.offsets.sort();
+        let xref = self.out.len();
+        let mut table = format!("xref\n0 {}\n0000000000 65535 f \n", size);
+        for (_, offset) in &self.offsets {
+            table.push_str(&format!("{:010} 00000 n \n", offset));
+        }
+        table.push_str(&format!(
+            "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{}\n%%EOF\n",
+            size, xref
+        ));
+        self.out.extend_from_slice(table.as_bytes());
+        self.out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escape_text() {
+        assert_eq!(escape_text("Größe (a\\b)"), "Gr\\366\\337e \\(a\\\\b\\)");
+        assert_eq!(escape_text("5 – 6 🥚"), "5 \\226 6 ?");
+    }
+
+    #[test]
+    fn test_xref_points_at_objects() {
+        let mut doc = PdfDocument::new();
+        let image = doc.add_jpeg(1, 1, vec![0xff, 0xd8, 0xff, 0xd9]);
+        for i in 0..2 {
+            let mut page = Page::new();
+            page.text(50.0, 800.0, 12.0, i == 0, Color::BLACK, "Wachteln");
+            page.image(image, 50.0, 700.0, 40.0, 40.0);
+            doc.add_page(page);
+        }
+        let pdf = doc.finish();
+        assert!(pdf.starts_with(b"%PDF-1.4"));
+
+        // Trailer and xref table are ASCII
+        let tail_start = pdf.len() - 40;
+        let tail = std::str::from_utf8(&pdf[tail_start..]).unwrap();
+        let startxref: usize = tail
+            .rsplit("startxref\n")
+            .next()
+            .and_then(|rest| rest.lines().next())
+            .unwrap()
+            .parse()
+            .unwrap();
+        let xref = std::str::from_utf8(&pdf[startxref..]).unwrap();
+        assert!(xref.starts_with("xref\n0 10\n"));
+        // Every entry points at "<id> 0 obj"
+        for (id, entry) in xref.lines().skip(3).take(9).enumerate() {
+            let offset: usize = entry[..10].parse().unwrap();
+            assert!(pdf[offset..].starts_with(format!("{} 0 obj", id + 1).as_bytes()));
+        }
+        let pages = format!("{} 0 obj", 2);
+        let pages_at = pdf
+            .windows(pages.len())
+            .position(|w| w == pages.as_bytes())
+            .unwrap();
+        assert!(std::str::from_utf8(&pdf[pages_at..pages_at + 60])
+            .unwrap()
+            .contains("/Count 2"));
+    }
+}
@@ -185,6 +185,22 @@ pub fn get_recent_trend(conn: &Connection, days: i32) -> Result<Vec<(String, i32
         .collect())
 }
 
+/// [`calculate_statistics`] off the UI thread, on a pooled connection
+pub async fn load_statistics(
+    start_date: Option<String>,
+    end_date: Option<String>,
+) -> Result<EggStatistics, AppError> {
+    crate::database::with_db_async(move |conn| {
+        calculate_statistics(conn, start_date.as_deref(), end_date.as_deref())
+    })
+    .await
+}
+
+/// [`get_recent_trend`] off the UI thread, on a pooled connection
+pub async fn load_recent_trend(days: i32) -> Result<Vec<(String, i32)>, AppError> {
+    crate::database::with_db_async(move |conn| get_recent_trend(conn, days)).await
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
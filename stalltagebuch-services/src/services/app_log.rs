@@ -0,0 +1,99 @@
+//! Dauerhaftes Protokoll in der Datenbank
+//!
+//! Für Ergebnisse, die man auch nach einem Neustart noch nachlesen können
+//! soll (Integritätsprüfung, Reparaturen). Laufende Diagnose gehört weiter
+//! nach `log::`, das landet auf Android nur im Logcat.
+
+use crate::error::AppError;
+use crate::models::{LogEntry, LogLevel};
+use rusqlite::{params, Connection};
+
+/// Mehr Einträge als das werden beim Schreiben entfernt (älteste zuerst)
+pub const MAX_LOG_ENTRIES: i64 = 500;
+
+/// Schreibt einen Eintrag und spiegelt ihn ins normale Log
+pub fn write(
+    conn: &Connection,
+    category: &str,
+    level: LogLevel,
+    message: &str,
+) -> Result<i64, AppError> {
+    match level {
+        LogLevel::Info => log::info!("[{}] {}", category, message),
+        LogLevel::Warning => log::warn!("[{}] {}", category, message),
+        LogLevel::Error => log::error!("[{}] {}", category, message),
+    }
+    conn.execute(
+        "INSERT INTO app_log (created_at, category, level, message) VALUES (?1, ?2, ?3, ?4)",
+        params![
+            chrono::Utc::now().timestamp_millis(),
+            category,
+            level.as_str(),
+            message
+        ],
+    )?;
+    let id = conn.last_insert_rowid();
+    conn.execute(
+        "DELETE FROM app_log WHERE id <= (SELECT MAX(id) FROM app_log) - ?1",
+        [MAX_LOG_ENTRIES],
+    )?;
+    Ok(id)
+}
+
+/// Neueste Einträge zuerst, optional nur einer Kategorie
+pub fn list(
+    conn: &Connection,
+    category: Option<&str>,
+    limit: usize,
+) -> Result<Vec<LogEntry>, AppError> {
+    let mut stmt = conn.prepare(
+        "SELECT id, created_at, category, level, message
+         FROM app_log
+         WHERE ?1 IS NULL OR category = ?1
+         ORDER BY id DESC
+         LIMIT ?2",
+    )?;
+    let rows = stmt.query_map(params![category, limit as i64], |row| {
+        Ok(LogEntry {
+            id: row.get(0)?,
+            created_at: row.get(1)?,
+            category: row.get(2)?,
+            level: LogLevel::from_str(&row.get::<_, String>(3)?),
+            message: row.get(4)?,
+        })
+    })?;
+    Ok(rows.collect::<Result<Vec<_>, _>>()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::schema;
+
+    #[test]
+    fn test_write_list_and_prune() {
+        let conn = Connection::open_in_memory().unwrap();
+        schema::init_schema(&conn).unwrap();
+
+        write(&conn, "integrity", LogLevel::Warning, "1 Problem").unwrap();
+        write(&conn, "backup", LogLevel::Info, "ok").unwrap();
+
+        let all = list(&conn, None, 10).unwrap();
+        assert_eq!(all.len(), 2);
+        assert_eq!(all[0].category, "backup");
+
+        let integrity = list(&conn, Some("integrity"), 10).unwrap();
+        assert_eq!(integrity.len(), 1);
+        assert_eq!(integrity[0].level, LogLevel::Warning);
+        assert_eq!(integrity[0].message, "1 Problem");
+
+        for i in 0..MAX_LOG_ENTRIES {
+            write(&conn, "test", LogLevel::Info, &i.to_string()).unwrap();
+        }
+        let count: i64 = conn
+            .query_row("SELECT COUNT(*) FROM app_log", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(count, MAX_LOG_ENTRIES);
+        assert!(list(&conn, Some("integrity"), 10).unwrap().is_empty());
+    }
+}
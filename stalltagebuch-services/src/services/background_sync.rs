@@ -0,0 +1,963 @@
+use crate::database;
+use crate::error::AppError;
+use crate::models::{SyncRun, SyncSettings};
+use crate::services::nextcloud_auth::{self, AuthError};
+use crate::services::photo_storage::RemotePhotoStorage;
+use crate::services::sync_backend::MEMORY_SCHEME;
+use crate::services::{
+    connectivity, download_service, op_compaction, photo_conflicts, photo_service, storage_quota,
+    sync_history, sync_progress, sync_service, transfer_stats, upload_service,
+};
+use chrono::Utc;
+use rusqlite::Connection;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Background sync configuration (reduced per requirement)
+#[cfg(not(target_os = "android"))]
+const SYNC_INTERVAL_SECONDS: u64 = 30; // 30 seconds
+/// WorkManager führt periodische Arbeit höchstens alle 15 Minuten aus
+#[cfg(target_os = "android")]
+const SYNC_INTERVAL_SECONDS: u64 = 15 * 60;
+#[cfg(not(target_os = "android"))]
+const RETRY_DELAY_SECONDS: u64 = 60; // 1 minute on error
+
+/// Spätestens nach dieser Zeit läuft ein geplanter Sync vollständig, auch
+/// wenn sich auf dem Server nichts geändert hat (Fotos, Papierkorb,
+/// Kompaktierung)
+const FULL_CYCLE_INTERVAL_MS: u64 = 15 * 60 * 1000;
+
+/// Global flag to control background sync
+static SYNC_ENABLED: AtomicBool = AtomicBool::new(false);
+static NEXT_SYNC_AT: AtomicU64 = AtomicU64::new(0); // epoch ms of next planned sync
+static LAST_FULL_CYCLE_AT: AtomicU64 = AtomicU64::new(0); // epoch ms
+
+pub fn next_sync_eta_seconds() -> Option<u64> {
+    if !SYNC_ENABLED.load(Ordering::SeqCst) {
+        return None;
+    }
+    let now_ms = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .ok()?
+        .as_millis() as u64;
+    let target = NEXT_SYNC_AT.load(Ordering::SeqCst);
+    if target == 0 || target <= now_ms {
+        Some(0)
+    } else {
+        Some((target - now_ms) / 1000)
+    }
+}
+
+pub fn sync_interval_seconds() -> u64 {
+    SYNC_INTERVAL_SECONDS
+}
+
+/// Starts the background sync
+///
+/// On Android the sync is handed to WorkManager (`SyncWorker`), which keeps
+/// running it while the app is in the background or killed. Elsewhere an
+/// in-process loop syncs at regular intervals.
+/// Call `stop_background_sync()` to stop it.
+pub fn start_background_sync() {
+    if SYNC_ENABLED.swap(true, Ordering::SeqCst) {
+        log::warn!("Background sync already running");
+        return;
+    }
+
+    log::info!(
+        "Starting background sync with {} second interval",
+        SYNC_INTERVAL_SECONDS
+    );
+
+    #[cfg(target_os = "android")]
+    match work_manager::schedule(SYNC_INTERVAL_SECONDS, pause_conditions()) {
+        Ok(()) => schedule_next_sync(SYNC_INTERVAL_SECONDS),
+        Err(e) => {
+            log::error!("Hintergrund-Sync konnte nicht eingeplant werden: {}", e);
+            SYNC_ENABLED.store(false, Ordering::SeqCst);
+        }
+    }
+
+    #[cfg(not(target_os = "android"))]
+    spawn_sync_loop();
+}
+
+#[cfg(not(target_os = "android"))]
+fn spawn_sync_loop() {
+    std::thread::spawn(move || {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("Failed to create tokio runtime");
+
+        while SYNC_ENABLED.load(Ordering::SeqCst) {
+            runtime.block_on(async {
+                // Perform sync cycle
+                match perform_sync_cycle(false).await {
+                    Ok(stats) => {
+                        log::info!("Background sync completed: {:?}", stats);
+                    }
+                    Err(e) => {
+                        log::error!("Background sync error: {}", e);
+                        // Wait shorter time before retry on error
+                        schedule_next_sync(RETRY_DELAY_SECONDS);
+                        tokio::time::sleep(Duration::from_secs(RETRY_DELAY_SECONDS)).await;
+                        return;
+                    }
+                }
+
+                // Wait for next sync interval
+                schedule_next_sync(SYNC_INTERVAL_SECONDS);
+                tokio::time::sleep(Duration::from_secs(SYNC_INTERVAL_SECONDS)).await;
+            });
+        }
+
+        log::info!("Background sync stopped");
+    });
+}
+
+/// Applies changed pause conditions of the sync settings to a running
+/// background sync
+///
+/// WorkManager gets them as constraints; the in-process loop checks them in
+/// every cycle anyway.
+pub fn reschedule_background_sync() {
+    #[cfg(target_os = "android")]
+    if SYNC_ENABLED.load(Ordering::SeqCst) {
+        if let Err(e) = work_manager::schedule(SYNC_INTERVAL_SECONDS, pause_conditions()) {
+            log::error!("Hintergrund-Sync konnte nicht neu eingeplant werden: {}", e);
+        }
+    }
+}
+
+/// (`pause_on_metered`, `pause_on_low_battery`) der gespeicherten Einstellungen
+#[cfg(target_os = "android")]
+fn pause_conditions() -> (bool, bool) {
+    database::with_db(sync_service::load_sync_settings)
+        .ok()
+        .flatten()
+        .map(|s| (s.pause_on_metered, s.pause_on_low_battery))
+        .unwrap_or((false, false))
+}
+
+/// Records the ETA of the next planned sync (shown in the settings)
+fn schedule_next_sync(delay_seconds: u64) {
+    let now_ms = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_millis() as u64;
+    NEXT_SYNC_AT.store(now_ms + delay_seconds * 1000, Ordering::SeqCst);
+}
+
+/// Stops the background sync
+pub fn stop_background_sync() {
+    if SYNC_ENABLED.swap(false, Ordering::SeqCst) {
+        log::info!("Stopping background sync");
+        #[cfg(target_os = "android")]
+        if let Err(e) = work_manager::cancel() {
+            log::error!("Hintergrund-Sync konnte nicht beendet werden: {}", e);
+        }
+    }
+}
+
+/// Result of a sync run started by the system scheduler
+#[derive(Debug, Clone, PartialEq)]
+pub enum ScheduledSyncOutcome {
+    /// Sync ran; the stats say what changed
+    Synced(SyncStats),
+    /// Sync is not configured or disabled; nothing to do
+    Skipped,
+    /// Temporary problem (network, server); try again later
+    Retry(String),
+    /// The user has to act (e.g. log in again) before syncing works again
+    Failed(String),
+}
+
+/// Runs one sync cycle for the system scheduler (Android WorkManager)
+///
+/// Blocks the calling thread; the scheduler's worker thread is meant for that.
+pub fn run_scheduled_sync() -> ScheduledSyncOutcome {
+    let runtime = match tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+    {
+        Ok(runtime) => runtime,
+        Err(e) => return ScheduledSyncOutcome::Retry(e.to_string()),
+    };
+
+    let outcome = match runtime.block_on(perform_sync_cycle(false)) {
+        Ok(stats) => ScheduledSyncOutcome::Synced(stats),
+        Err(AppError::NotFound(_) | AppError::Validation(_)) => ScheduledSyncOutcome::Skipped,
+        Err(AppError::PermissionDenied(msg)) => ScheduledSyncOutcome::Failed(msg),
+        Err(e) => ScheduledSyncOutcome::Retry(e.to_string()),
+    };
+    log::info!("Geplanter Sync beendet: {:?}", outcome);
+    schedule_next_sync(SYNC_INTERVAL_SECONDS);
+    outcome
+}
+
+/// Checks if background sync is running
+pub fn is_background_sync_running() -> bool {
+    SYNC_ENABLED.load(Ordering::SeqCst)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SyncStats {
+    pub operations_uploaded: usize,
+    pub operations_downloaded: usize,
+    pub photos_uploaded: usize,
+    pub photos_downloaded: usize,
+}
+
+/// Performs one complete sync cycle: download remote changes first, then upload local changes
+///
+/// Download-first strategy ensures we get the latest remote state before uploading,
+/// reducing conflicts and ensuring we're working with up-to-date data.
+/// An `on_demand` cycle (started by the user) uploads originals regardless of
+/// the `OriginalsPolicy`.
+///
+/// Every cycle that actually ran (not skipped as unconfigured, disabled,
+/// paused or deferred) is recorded in the sync history.
+async fn perform_sync_cycle(on_demand: bool) -> Result<SyncStats, AppError> {
+    let started_at = Utc::now().timestamp_millis();
+    let counters_before = transfer_stats::counters();
+    sync_progress::begin();
+    let result = run_sync_cycle(on_demand).await;
+    match &result {
+        Err(AppError::NotFound(_) | AppError::Validation(_)) => sync_progress::cancel(),
+        Ok(stats) => sync_progress::finish(sync_progress::SyncPhase::Done(stats.clone())),
+        Err(e) => sync_progress::finish(sync_progress::SyncPhase::Failed(e.to_string())),
+    }
+    let transfer = transfer_stats::counters().since(&counters_before);
+    let skipped = matches!(result, Err(AppError::NotFound(_) | AppError::Validation(_)));
+    if !skipped {
+        record_history(started_at, on_demand, &transfer, &result);
+    }
+    // Auch übersprungene Zyklen kosten eine Anfrage (ETag-Vorprüfung)
+    if !skipped || !transfer.is_empty() {
+        let duration_ms = Utc::now().timestamp_millis() - started_at;
+        if let Err(e) = database::with_db(|conn| {
+            transfer_stats::record_day(conn, started_at, duration_ms, &transfer)
+        }) {
+            log::error!("Datenverkehr konnte nicht gespeichert werden: {}", e);
+        }
+    }
+    result
+}
+
+fn record_history(
+    started_at: i64,
+    on_demand: bool,
+    transfer: &transfer_stats::TransferCounters,
+    result: &Result<SyncStats, AppError>,
+) {
+    let mut run = SyncRun {
+        id: 0,
+        started_at,
+        duration_ms: Utc::now().timestamp_millis() - started_at,
+        on_demand,
+        operations_uploaded: 0,
+        operations_downloaded: 0,
+        photos_uploaded: 0,
+        photos_downloaded: 0,
+        bytes_uploaded: transfer.bytes_uploaded,
+        bytes_downloaded: transfer.bytes_downloaded,
+        request_count: transfer.request_count,
+        error: None,
+    };
+    match result {
+        Ok(stats) => {
+            run.operations_uploaded = stats.operations_uploaded;
+            run.operations_downloaded = stats.operations_downloaded;
+            run.photos_uploaded = stats.photos_uploaded;
+            run.photos_downloaded = stats.photos_downloaded;
+        }
+        Err(e) => run.error = Some(e.to_string()),
+    }
+    if let Err(e) = database::with_db(|conn| sync_history::record_run(conn, &run)) {
+        log::error!("Sync-Protokoll konnte nicht geschrieben werden: {}", e);
+    }
+}
+
+async fn run_sync_cycle(on_demand: bool) -> Result<SyncStats, AppError> {
+    let conn = database::connection()?;
+
+    // Check if sync is configured and enabled
+    let settings = sync_service::load_sync_settings(&conn)?
+        .ok_or_else(|| AppError::NotFound("Sync not configured".to_string()))?;
+
+    if !settings.enabled {
+        return Err(AppError::Validation("Sync disabled".to_string()));
+    }
+    if settings.paused {
+        return Err(AppError::Validation("Sync paused".to_string()));
+    }
+
+    // Getaktete Verbindung oder leerer Akku: nur der Hintergrund-Sync wartet
+    if !on_demand {
+        if let Some(reason) = connectivity::sync_deferral(&settings, connectivity::current()) {
+            log::info!("Hintergrund-Sync ausgesetzt: {:?}", reason);
+            return Err(AppError::Validation(format!("Sync deferred: {:?}", reason)));
+        }
+    }
+
+    // Abgelehntes App-Passwort: nicht weiter gegen den Server laufen, bis neu angemeldet wurde
+    if settings.credentials_invalid {
+        return Err(AppError::PermissionDenied(
+            "Credentials invalid, re-login required".to_string(),
+        ));
+    }
+
+    // Günstige Vorprüfung: ein Listing des Sync-Ordners statt des ganzen Zyklus
+    let tree_etag = download_service::remote_tree_etag(&conn)
+        .await
+        .unwrap_or_else(|e| {
+            log::debug!("Remote tree ETag not readable: {}", e);
+            None
+        });
+    if !on_demand && is_idle(&conn, &settings, &tree_etag)? {
+        log::debug!("Keine Änderungen auf dem Server, Sync übersprungen");
+        return Err(AppError::Validation("No remote changes".to_string()));
+    }
+
+    // Wartungsmodus (WebDAV liefert nur 503) oder geändertes Zertifikat: Zyklus überspringen
+    check_server(&settings).await?;
+
+    // Phase 1: Download remote changes first (new multi-master sync)
+    let ops_downloaded = match download_service::download_and_merge_ops(&conn).await {
+        Err(AppError::PermissionDenied(msg)) => {
+            log::warn!("Server rejected credentials, pausing sync: {}", msg);
+            sync_service::set_credentials_invalid(&conn, true)?;
+            return Err(AppError::PermissionDenied(msg));
+        }
+        result => result?,
+    };
+
+    // Phase 1b: Fehlende Fotodateien (je nach Richtlinie)
+    let photos_downloaded = download_service::download_photos(&conn)
+        .await
+        .unwrap_or_else(|e| {
+            log::error!("Photo download failed: {}", e);
+            0
+        });
+
+    // Phase 2: Upload pending local data (only once, if initial upload not done yet)
+    if !settings.initial_upload_done {
+        upload_pending_local_data(&conn, on_demand)
+            .await
+            .unwrap_or_else(|e| {
+                log::error!("Upload pending local data failed: {}", e);
+            });
+        // Mark as done
+        sync_service::set_initial_upload_done(&conn).unwrap_or_else(|e| {
+            log::error!("Failed to set initial_upload_done flag: {}", e);
+        });
+    }
+
+    // Phase 2b: Upload the ops collected since the last cycle
+    sync_progress::report(sync_progress::SyncPhase::UploadingOps);
+    let ops_uploaded = upload_service::flush_pending_ops(&conn)
+        .await
+        .unwrap_or_else(|e| {
+            log::error!("Uploading collected ops failed: {}", e);
+            0
+        });
+
+    // Phase 3: Upload local photos that are missing remotely
+    let photos_uploaded = upload_service::upload_photos_batch(&conn, on_demand)
+        .await
+        .unwrap_or_else(|e| {
+            log::error!("Photo upload failed: {}", e);
+            0
+        });
+
+    // Phase 3b: Flag photos changed or deleted outside the app (Nextcloud web UI)
+    match RemotePhotoStorage::from_settings(&settings)
+        .and_then(|remote| remote.with_local_key(&conn))
+    {
+        Ok(remote) => match photo_conflicts::detect_remote_conflicts(&conn, &remote).await {
+            Ok(0) => {}
+            Ok(conflicts) => {
+                log::warn!("{} photos changed on the server outside the app", conflicts)
+            }
+            Err(e) => log::error!("Remote photo check failed: {}", e),
+        },
+        Err(e) => log::error!("Remote photo check failed: {}", e),
+    }
+
+    // Phase 4: Keep local photo storage below the configured limit
+    if let Err(e) = storage_quota::enforce_configured_limit(&conn) {
+        log::error!("Photo storage limit failed: {}", e);
+    }
+
+    // Phase 5: Delete photos whose restore window has passed
+    if let Err(e) = photo_service::purge_trash(&conn, photo_service::TRASH_RETENTION_DAYS).await {
+        log::error!("Purging photo trash failed: {}", e);
+    }
+
+    // Phase 6: Occasionally replace our old op files with a snapshot
+    if let Err(e) = op_compaction::compact_if_due(&conn).await {
+        log::error!("Op log compaction failed: {}", e);
+    }
+
+    download_service::remember_remote_tree(&conn, &tree_etag)?;
+    LAST_FULL_CYCLE_AT.store(now_ms(), Ordering::SeqCst);
+
+    Ok(SyncStats {
+        operations_uploaded: ops_uploaded,
+        operations_downloaded: ops_downloaded,
+        photos_uploaded,
+        photos_downloaded,
+    })
+}
+
+/// Kann ein geplanter Zyklus entfallen? Nur wenn der Server-Baum seit dem
+/// letzten Sync unverändert ist, lokal nichts auf den Upload wartet und der
+/// letzte vollständige Zyklus nicht zu lange her ist.
+fn is_idle(
+    conn: &Connection,
+    settings: &SyncSettings,
+    tree_etag: &Option<String>,
+) -> Result<bool, AppError> {
+    if !settings.initial_upload_done
+        || now_ms().saturating_sub(LAST_FULL_CYCLE_AT.load(Ordering::SeqCst))
+            >= FULL_CYCLE_INTERVAL_MS
+    {
+        return Ok(false);
+    }
+    if upload_service::count_pending_ops(conn)? > 0
+        || upload_service::count_pending_photos(conn)? > 0
+    {
+        return Ok(false);
+    }
+    download_service::remote_tree_unchanged(conn, tree_etag)
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+/// Checks `status.php` of the configured server (maintenance mode, pinned
+/// certificate)
+///
+/// Fails closed: if the client cannot be set up or the TLS handshake fails,
+/// the sync does not start. Only plain network errors (offline, DNS) and
+/// unreadable responses pass so the regular sync error handling applies.
+async fn check_server(settings: &SyncSettings) -> Result<(), AppError> {
+    if settings.server_url.starts_with(MEMORY_SCHEME) {
+        return Ok(());
+    }
+    let auth = nextcloud_auth::service_for_settings(settings)?;
+    match auth.server_status(&settings.server_url).await {
+        Ok(status) => match status.ensure_available(&settings.server_url) {
+            Err(AuthError::Maintenance) => Err(AuthError::Maintenance.into()),
+            _ => Ok(()),
+        },
+        Err(e @ (AuthError::CertificateChanged(_) | AuthError::TlsError(_))) => Err(e.into()),
+        Err(e) => {
+            log::debug!("status.php not readable, continuing sync: {}", e);
+            Ok(())
+        }
+    }
+}
+
+/// Uploads all local data that hasn't been synchronized yet
+///
+/// This is useful when sync is configured after local data has been created.
+/// It reads all local entities and creates CRDT operations for them.
+/// Now with atomic validation: first uploads all photos, then operations.
+/// Originals follow the `OriginalsPolicy` unless `on_demand`.
+async fn upload_pending_local_data(conn: &Connection, on_demand: bool) -> Result<(), AppError> {
+    use crate::services::{crdt_service, upload_service};
+
+    let device_id = upload_service::get_device_id(conn)?;
+
+    // Phase 1: Upload all photos first (progress see `sync_progress`)
+    log::info!("Phase 1: Uploading photos...");
+    let mut uploaded_photos = 0;
+    loop {
+        let uploaded = upload_service::upload_photos_batch(conn, on_demand).await?;
+        if uploaded == 0 {
+            break; // No more photos to upload
+        }
+        uploaded_photos += uploaded;
+    }
+
+    log::info!("Phase 1 complete: {} photos uploaded", uploaded_photos);
+
+    // Phase 2: Upload metadata operations (atomically after all photos done)
+    let mut all_ops = Vec::new();
+    let mut clock = crdt_service::load_local_clock(conn, &device_id)?;
+
+    // Upload all coops
+    let mut stmt = conn.prepare("SELECT uuid, name, notes FROM coops WHERE deleted = 0")?;
+    let coops = stmt.query_map([], |row| {
+        Ok((
+            row.get::<_, String>(0)?,
+            row.get::<_, String>(1)?,
+            row.get::<_, Option<String>>(2)?,
+        ))
+    })?;
+
+    for coop in coops {
+        let (uuid, name, notes) = coop?;
+
+        all_ops.push(crdt_service::Operation::new(
+            "coop".to_string(),
+            uuid.clone(),
+            &mut clock,
+            crdt_service::CrdtOp::LwwSet {
+                field: "name".to_string(),
+                value: serde_json::Value::String(name),
+            },
+        ));
+
+        if let Some(notes) = notes {
+            all_ops.push(crdt_service::Operation::new(
+                "coop".to_string(),
+                uuid.clone(),
+                &mut clock,
+                crdt_service::CrdtOp::LwwSet {
+                    field: "notes".to_string(),
+                    value: serde_json::Value::String(notes),
+                },
+            ));
+        }
+    }
+
+    // Upload all quails
+    let mut stmt = conn.prepare(
+        "SELECT uuid, name, gender, ring_color, profile_photo, mother_id, father_id, coop_id
+         FROM quails WHERE deleted = 0",
+    )?;
+    let quails = stmt.query_map([], |row| {
+        Ok((
+            row.get::<_, String>(0)?,
+            row.get::<_, String>(1)?,
+            row.get::<_, String>(2)?,
+            row.get::<_, Option<String>>(3)?,
+            row.get::<_, Option<String>>(4)?,
+            [
+                ("mother_id", row.get::<_, Option<String>>(5)?),
+                ("father_id", row.get::<_, Option<String>>(6)?),
+                ("coop_id", row.get::<_, Option<String>>(7)?),
+            ],
+        ))
+    })?;
+
+    for quail in quails {
+        let (uuid, name, gender, ring_color, profile_photo, references) = quail?;
+
+        all_ops.push(crdt_service::Operation::new(
+            "quail".to_string(),
+            uuid.clone(),
+            &mut clock,
+            crdt_service::CrdtOp::LwwSet {
+                field: "name".to_string(),
+                value: serde_json::Value::String(name),
+            },
+        ));
+
+        all_ops.push(crdt_service::Operation::new(
+            "quail".to_string(),
+            uuid.clone(),
+            &mut clock,
+            crdt_service::CrdtOp::LwwSet {
+                field: "gender".to_string(),
+                value: serde_json::Value::String(gender),
+            },
+        ));
+
+        if let Some(color) = ring_color {
+            all_ops.push(crdt_service::Operation::new(
+                "quail".to_string(),
+                uuid.clone(),
+                &mut clock,
+                crdt_service::CrdtOp::LwwSet {
+                    field: "ring_color".to_string(),
+                    value: serde_json::Value::String(color),
+                },
+            ));
+        }
+
+        if let Some(photo) = profile_photo {
+            all_ops.push(crdt_service::Operation::new(
+                "quail".to_string(),
+                uuid.clone(),
+                &mut clock,
+                crdt_service::CrdtOp::LwwSet {
+                    field: "profile_photo".to_string(),
+                    value: serde_json::Value::String(photo),
+                },
+            ));
+        }
+
+        for (field, reference) in references {
+            if let Some(reference) = reference {
+                all_ops.push(crdt_service::Operation::new(
+                    "quail".to_string(),
+                    uuid.clone(),
+                    &mut clock,
+                    crdt_service::CrdtOp::LwwSet {
+                        field: field.to_string(),
+                        value: serde_json::Value::String(reference),
+                    },
+                ));
+            }
+        }
+    }
+
+    // Upload all events
+    let mut stmt = conn.prepare(
+        "SELECT uuid, quail_id, event_type, event_date, notes, medication, dose,
+                duration_days, egg_withdrawal_days, meat_withdrawal_days
+         FROM quail_events WHERE deleted = 0",
+    )?;
+    let events = stmt.query_map([], |row| {
+        // Angaben zur Behandlung, nur gesetzte Felder
+        let treatment: Vec<(&str, serde_json::Value)> = [
+            (
+                "medication",
+                row.get::<_, Option<String>>(5)?.map(Into::into),
+            ),
+            ("dose", row.get::<_, Option<String>>(6)?.map(Into::into)),
+            (
+                "duration_days",
+                row.get::<_, Option<i64>>(7)?.map(Into::into),
+            ),
+            (
+                "egg_withdrawal_days",
+                row.get::<_, Option<i64>>(8)?.map(Into::into),
+            ),
+            (
+                "meat_withdrawal_days",
+                row.get::<_, Option<i64>>(9)?.map(Into::into),
+            ),
+        ]
+        .into_iter()
+        .filter_map(|(field, value)| value.map(|value| (field, value)))
+        .collect();
+        Ok((
+            row.get::<_, String>(0)?,
+            row.get::<_, String>(1)?,
+            row.get::<_, String>(2)?,
+            row.get::<_, String>(3)?,
+            row.get::<_, Option<String>>(4)?,
+            treatment,
+        ))
+    })?;
+
+    for event in events {
+        let (uuid, quail_id, event_type, event_date, notes, treatment) = event?;
+
+        all_ops.push(crdt_service::Operation::new(
+            "event".to_string(),
+            uuid.clone(),
+            &mut clock,
+            crdt_service::CrdtOp::LwwSet {
+                field: "quail_id".to_string(),
+                value: serde_json::Value::String(quail_id),
+            },
+        ));
+
+        all_ops.push(crdt_service::Operation::new(
+            "event".to_string(),
+            uuid.clone(),
+            &mut clock,
+            crdt_service::CrdtOp::LwwSet {
+                field: "event_type".to_string(),
+                value: serde_json::Value::String(event_type),
+            },
+        ));
+
+        all_ops.push(crdt_service::Operation::new(
+            "event".to_string(),
+            uuid.clone(),
+            &mut clock,
+            crdt_service::CrdtOp::LwwSet {
+                field: "event_date".to_string(),
+                value: serde_json::Value::String(event_date),
+            },
+        ));
+
+        if let Some(notes_text) = notes {
+            all_ops.push(crdt_service::Operation::new(
+                "event".to_string(),
+                uuid.clone(),
+                &mut clock,
+                crdt_service::CrdtOp::LwwSet {
+                    field: "notes".to_string(),
+                    value: serde_json::Value::String(notes_text),
+                },
+            ));
+        }
+
+        for (field, value) in treatment {
+            all_ops.push(crdt_service::Operation::new(
+                "event".to_string(),
+                uuid.clone(),
+                &mut clock,
+                crdt_service::CrdtOp::LwwSet {
+                    field: field.to_string(),
+                    value,
+                },
+            ));
+        }
+    }
+
+    // Upload all photos
+    let mut stmt = conn.prepare("SELECT uuid, quail_id, event_id, COALESCE(relative_path, path) as rel_path, thumbnail_path, trashed_at, checksum FROM photos WHERE deleted = 0")?;
+    let photos = stmt.query_map([], |row| {
+        Ok((
+            row.get::<_, String>(0)?,
+            row.get::<_, Option<String>>(1)?,
+            row.get::<_, Option<String>>(2)?,
+            row.get::<_, String>(3)?,
+            row.get::<_, Option<String>>(4)?,
+            row.get::<_, Option<i64>>(5)?,
+            row.get::<_, Option<String>>(6)?,
+        ))
+    })?;
+
+    for photo in photos {
+        let (uuid, quail_id, event_id, rel_path, thumbnail_path, trashed_at, checksum) = photo?;
+
+        all_ops.push(crdt_service::Operation::new(
+            "photo".to_string(),
+            uuid.clone(),
+            &mut clock,
+            crdt_service::CrdtOp::LwwSet {
+                field: "relative_path".to_string(),
+                value: serde_json::Value::String(rel_path),
+            },
+        ));
+
+        if let Some(qid) = quail_id {
+            all_ops.push(crdt_service::Operation::new(
+                "photo".to_string(),
+                uuid.clone(),
+                &mut clock,
+                crdt_service::CrdtOp::LwwSet {
+                    field: "quail_id".to_string(),
+                    value: serde_json::Value::String(qid),
+                },
+            ));
+        }
+
+        if let Some(eid) = event_id {
+            all_ops.push(crdt_service::Operation::new(
+                "photo".to_string(),
+                uuid.clone(),
+                &mut clock,
+                crdt_service::CrdtOp::LwwSet {
+                    field: "event_id".to_string(),
+                    value: serde_json::Value::String(eid),
+                },
+            ));
+        }
+
+        if let Some(thumb) = thumbnail_path {
+            all_ops.push(crdt_service::Operation::new(
+                "photo".to_string(),
+                uuid.clone(),
+                &mut clock,
+                crdt_service::CrdtOp::LwwSet {
+                    field: "relative_thumb".to_string(),
+                    value: serde_json::Value::String(thumb),
+                },
+            ));
+        }
+
+        if let Some(trashed_at) = trashed_at {
+            all_ops.push(crdt_service::Operation::new(
+                "photo".to_string(),
+                uuid.clone(),
+                &mut clock,
+                crdt_service::CrdtOp::LwwSet {
+                    field: "trashed_at".to_string(),
+                    value: serde_json::json!(trashed_at),
+                },
+            ));
+        }
+
+        if let Some(checksum) = checksum {
+            all_ops.push(crdt_service::Operation::new(
+                "photo".to_string(),
+                uuid.clone(),
+                &mut clock,
+                crdt_service::CrdtOp::LwwSet {
+                    field: "checksum".to_string(),
+                    value: serde_json::Value::String(checksum),
+                },
+            ));
+        }
+    }
+
+    // Upload all egg records
+    let mut stmt = conn.prepare(
+        "SELECT uuid, record_date, total_eggs, coop_id FROM egg_records WHERE deleted = 0",
+    )?;
+    let eggs = stmt.query_map([], |row| {
+        Ok((
+            row.get::<_, String>(0)?,
+            row.get::<_, String>(1)?,
+            row.get::<_, i32>(2)?,
+            row.get::<_, Option<String>>(3)?,
+        ))
+    })?;
+
+    for egg in eggs {
+        let (uuid, record_date, total_eggs, coop_id) = egg?;
+
+        all_ops.push(crdt_service::Operation::new(
+            "egg".to_string(),
+            uuid.clone(),
+            &mut clock,
+            crdt_service::CrdtOp::LwwSet {
+                field: "record_date".to_string(),
+                value: serde_json::Value::String(record_date),
+            },
+        ));
+
+        all_ops.push(crdt_service::Operation::new(
+            "egg".to_string(),
+            uuid.clone(),
+            &mut clock,
+            crdt_service::CrdtOp::LwwSet {
+                field: "total_eggs".to_string(),
+                value: serde_json::Value::Number(total_eggs.into()),
+            },
+        ));
+
+        if let Some(coop_id) = coop_id {
+            all_ops.push(crdt_service::Operation::new(
+                "egg".to_string(),
+                uuid.clone(),
+                &mut clock,
+                crdt_service::CrdtOp::LwwSet {
+                    field: "coop_id".to_string(),
+                    value: serde_json::Value::String(coop_id),
+                },
+            ));
+        }
+    }
+
+    crdt_service::save_local_clock(conn, &clock)?;
+
+    // Abgeschaltete Entitätstypen bleiben lokal
+    if let Some(settings) = sync_service::load_sync_settings(conn)? {
+        all_ops.retain(|op| settings.syncs(&op.entity_type));
+    }
+
+    // Upload in batch
+    if !all_ops.is_empty() {
+        upload_service::upload_ops_batch(conn, all_ops).await?;
+        log::info!("Uploaded pending local data successfully");
+    }
+
+    Ok(())
+}
+
+/// Triggers an immediate sync (in addition to scheduled background syncs)
+///
+/// Counts as on demand: pending originals are uploaded whatever the
+/// `OriginalsPolicy` says.
+pub async fn sync_now() -> Result<SyncStats, AppError> {
+    perform_sync_cycle(true).await
+}
+
+/// Ein- und Ausplanen des `SyncWorker` (Kotlin, siehe `MainActivity.kt`) über JNI
+#[cfg(target_os = "android")]
+mod work_manager {
+    use crate::error::AppError;
+    use jni::objects::{JClass, JObject, JValue};
+    use jni::JNIEnv;
+    use ndk_context::android_context;
+
+    const SYNC_WORKER_CLASS: &str = "dev.dioxus.main.SyncWorker";
+
+    fn jni_err(what: &str, e: jni::errors::Error) -> AppError {
+        AppError::Other(format!("{} failed: {}", what, e))
+    }
+
+    /// Lädt die Worker-Klasse über den Class-Loader der App (`FindClass` kennt
+    /// auf fremden Threads nur System-Klassen)
+    fn worker_class<'a>(
+        env: &mut JNIEnv<'a>,
+        context: &JObject<'a>,
+    ) -> Result<JClass<'a>, AppError> {
+        let loader = env
+            .call_method(context, "getClassLoader", "()Ljava/lang/ClassLoader;", &[])
+            .and_then(|v| v.l())
+            .map_err(|e| jni_err("getClassLoader", e))?;
+        let name = env
+            .new_string(SYNC_WORKER_CLASS)
+            .map_err(|e| jni_err("new_string", e))?;
+        let cls = env
+            .call_method(
+                &loader,
+                "loadClass",
+                "(Ljava/lang/String;)Ljava/lang/Class;",
+                &[JValue::Object(&name)],
+            )
+            .and_then(|v| v.l())
+            .map_err(|e| jni_err("loadClass", e))?;
+        Ok(JClass::from(cls))
+    }
+
+    /// Ruft `SyncWorker.schedule(context, interval, unmetered, batteryNotLow)`
+    /// bzw. `SyncWorker.cancel(context)` auf
+    fn call_worker(schedule: Option<(u64, bool, bool)>) -> Result<(), AppError> {
+        let vm_ptr = android_context().vm() as *mut jni::sys::JavaVM;
+        let vm = unsafe { jni::JavaVM::from_raw(vm_ptr) }
+            .map_err(|e| AppError::Other(format!("JavaVM creation failed: {}", e)))?;
+        let mut env = vm
+            .attach_current_thread()
+            .map_err(|e| AppError::Other(format!("Failed to attach thread: {}", e)))?;
+        let context =
+            unsafe { JObject::from_raw(android_context().context() as jni::sys::jobject) };
+
+        let cls = worker_class(&mut env, &context)?;
+        match schedule {
+            Some((seconds, unmetered, battery_not_low)) => env.call_static_method(
+                &cls,
+                "schedule",
+                "(Landroid/content/Context;JZZ)V",
+                &[
+                    JValue::Object(&context),
+                    JValue::Long(seconds as i64),
+                    JValue::Bool(unmetered.into()),
+                    JValue::Bool(battery_not_low.into()),
+                ],
+            ),
+            None => env.call_static_method(
+                &cls,
+                "cancel",
+                "(Landroid/content/Context;)V",
+                &[JValue::Object(&context)],
+            ),
+        }
+        .map_err(|e| jni_err("SyncWorker", e))?;
+        Ok(())
+    }
+
+    /// Plant die periodische Synchronisation ein; die Pausen-Bedingungen
+    /// (getaktet, Akku) werden zu Constraints von WorkManager
+    pub(super) fn schedule(
+        interval_seconds: u64,
+        (pause_on_metered, pause_on_low_battery): (bool, bool),
+    ) -> Result<(), AppError> {
+        call_worker(Some((
+            interval_seconds,
+            pause_on_metered,
+            pause_on_low_battery,
+        )))
+    }
+
+    pub(super) fn cancel() -> Result<(), AppError> {
+        call_worker(None)
+    }
+}
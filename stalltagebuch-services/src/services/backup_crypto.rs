@@ -0,0 +1,232 @@
+//! Passphrasen-Verschlüsselung von Export-ZIPs
+//!
+//! Sicherungen in geteiltem Cloud-Speicher enthalten sonst alle Notizen und
+//! Gesundheitsdaten im Klartext. Mit Passphrase wird das ganze ZIP mit
+//! AES-256-GCM verschlüsselt; der Schlüssel entsteht per PBKDF2 aus der
+//! Passphrase und einem zufälligen Salt je Sicherung. Aufbau der Datei:
+//! `MAGIC | Salt | Iterationen (u32 BE) | Nonce | Chiffretext`.
+//!
+//! Anders als der Sync-Schlüssel aus
+//! [`photo_crypto`](crate::services::photo_crypto) wird die Passphrase nie
+//! gespeichert. Ohne Feature `encryption` werden verschlüsselte Sicherungen
+//! erkannt, aber abgelehnt.
+
+use crate::error::AppError;
+
+/// Kennung am Anfang jeder verschlüsselten Sicherung (mit Formatversion)
+const MAGIC: &[u8] = b"STBBAK1\0";
+#[cfg(feature = "encryption")]
+const SALT_LEN: usize = 16;
+#[cfg(feature = "encryption")]
+const PASSPHRASE_ITERATIONS: u32 = 600_000;
+/// Obergrenze für die Iterationen aus dem Dateikopf, damit eine präparierte
+/// Sicherung den Import nicht beliebig lange rechnen lässt
+#[cfg(feature = "encryption")]
+const MAX_PASSPHRASE_ITERATIONS: u32 = 10 * PASSPHRASE_ITERATIONS;
+pub const MIN_PASSPHRASE_LEN: usize = 8;
+
+/// Dateiendung verschlüsselter Sicherungen (nach `.zip`)
+pub const ENCRYPTED_EXTENSION: &str = "enc";
+
+/// Passphrase einer Sicherung (erscheint nicht in `Debug`-Ausgaben)
+#[derive(Clone, PartialEq, Eq)]
+pub struct Passphrase(String);
+
+impl std::fmt::Debug for Passphrase {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Passphrase(..)")
+    }
+}
+
+impl Passphrase {
+    pub fn new(text: &str) -> Result<Self, AppError> {
+        if text.chars().count() < MIN_PASSPHRASE_LEN {
+            return Err(AppError::Validation(format!(
+                "Passphrase muss mindestens {} Zeichen lang sein",
+                MIN_PASSPHRASE_LEN
+            )));
+        }
+        Ok(Self(text.to_string()))
+    }
+}
+
+/// Lassen sich Sicherungen in diesem Build verschlüsseln?
+pub fn is_available() -> bool {
+    cfg!(feature = "encryption")
+}
+
+/// Ist `data` eine verschlüsselte Sicherung? (Erkennung am Dateikopf)
+pub fn is_encrypted(data: &[u8]) -> bool {
+    data.starts_with(MAGIC)
+}
+
+#[cfg(feature = "encryption")]
+fn derive_key(passphrase: &Passphrase, salt: &[u8], iterations: u32) -> Result<[u8; 32], AppError> {
+    let iterations = std::num::NonZeroU32::new(iterations)
+        .ok_or_else(|| AppError::Validation("Ungültige verschlüsselte Sicherung".to_string()))?;
+    let mut key = [0u8; 32];
+    ring::pbkdf2::derive(
+        ring::pbkdf2::PBKDF2_HMAC_SHA256,
+        iterations,
+        salt,
+        passphrase.0.as_bytes(),
+        &mut key,
+    );
+    Ok(key)
+}
+
+#[cfg(feature = "encryption")]
+fn aead_key(key: &[u8; 32]) -> ring::aead::LessSafeKey {
+    use ring::aead::{LessSafeKey, UnboundKey, AES_256_GCM};
+    LessSafeKey::new(UnboundKey::new(&AES_256_GCM, key).expect("key has the AEAD key length"))
+}
+
+#[cfg(feature = "encryption")]
+fn encrypt_with(
+    plain: &[u8],
+    passphrase: &Passphrase,
+    iterations: u32,
+) -> Result<Vec<u8>, AppError> {
+    use ring::aead::{Aad, Nonce, NONCE_LEN};
+    use ring::rand::{SecureRandom, SystemRandom};
+
+    let mut random = [0u8; SALT_LEN + NONCE_LEN];
+    SystemRandom::new()
+        .fill(&mut random)
+        .map_err(|_| AppError::Other("Zufallsgenerator nicht verfügbar".to_string()))?;
+    let (salt, nonce) = random.split_at(SALT_LEN);
+
+    let mut header = Vec::with_capacity(MAGIC.len() + SALT_LEN + 4);
+    header.extend_from_slice(MAGIC);
+    header.extend_from_slice(salt);
+    header.extend_from_slice(&iterations.to_be_bytes());
+
+    let key = derive_key(passphrase, salt, iterations)?;
+    let mut in_out = plain.to_vec();
+    aead_key(&key)
+        .seal_in_place_append_tag(
+            Nonce::try_assume_unique_for_key(nonce).expect("nonce has NONCE_LEN bytes"),
+            Aad::from(&header),
+            &mut in_out,
+        )
+        .map_err(|_| AppError::Other("Verschlüsselung fehlgeschlagen".to_string()))?;
+
+    let mut data = header;
+    data.extend_from_slice(nonce);
+    data.extend_from_slice(&in_out);
+    Ok(data)
+}
+
+/// Verschlüsselt eine Sicherung mit `passphrase`
+pub fn encrypt(plain: &[u8], passphrase: &Passphrase) -> Result<Vec<u8>, AppError> {
+    #[cfg(feature = "encryption")]
+    {
+        encrypt_with(plain, passphrase, PASSPHRASE_ITERATIONS)
+    }
+    #[cfg(not(feature = "encryption"))]
+    {
+        let _ = (plain, passphrase);
+        Err(unavailable())
+    }
+}
+
+/// Entschlüsselt eine mit [`encrypt`] erzeugte Sicherung
+pub fn decrypt(data: &[u8], passphrase: &Passphrase) -> Result<Vec<u8>, AppError> {
+    if !is_encrypted(data) {
+        return Err(AppError::Validation(
+            "Datei ist keine verschlüsselte Sicherung".to_string(),
+        ));
+    }
+    #[cfg(feature = "encryption")]
+    {
+        use ring::aead::{Aad, Nonce, NONCE_LEN};
+
+        let header_len = MAGIC.len() + SALT_LEN + 4;
+        if data.len() < header_len + NONCE_LEN {
+            return Err(AppError::Validation(
+                "Verschlüsselte Sicherung ist unvollständig".to_string(),
+            ));
+        }
+        let (header, rest) = data.split_at(header_len);
+        let salt = &header[MAGIC.len()..MAGIC.len() + SALT_LEN];
+        let iterations = u32::from_be_bytes(
+            header[MAGIC.len() + SALT_LEN..]
+                .try_into()
+                .expect("header ends with 4 bytes"),
+        );
+        if iterations > MAX_PASSPHRASE_ITERATIONS {
+            return Err(AppError::Validation(
+                "Ungültige verschlüsselte Sicherung".to_string(),
+            ));
+        }
+        let (nonce, ciphertext) = rest.split_at(NONCE_LEN);
+
+        let key = derive_key(passphrase, salt, iterations)?;
+        let mut in_out = ciphertext.to_vec();
+        let plain_len = aead_key(&key)
+            .open_in_place(
+                Nonce::try_assume_unique_for_key(nonce).expect("nonce has NONCE_LEN bytes"),
+                Aad::from(header),
+                &mut in_out,
+            )
+            .map_err(|_| {
+                AppError::Validation(
+                    "Sicherung konnte nicht entschlüsselt werden (falsche Passphrase?)".to_string(),
+                )
+            })?
+            .len();
+        in_out.truncate(plain_len);
+        Ok(in_out)
+    }
+    #[cfg(not(feature = "encryption"))]
+    {
+        let _ = passphrase;
+        Err(unavailable())
+    }
+}
+
+#[cfg(not(feature = "encryption"))]
+fn unavailable() -> AppError {
+    AppError::Validation(
+        "Verschlüsselte Sicherungen werden in dieser Version nicht unterstützt".to_string(),
+    )
+}
+
+#[cfg(all(test, feature = "encryption"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encrypt_roundtrip() {
+        let passphrase = Passphrase::new("Wachtelstall 2024").unwrap();
+        let plain = b"PK\x03\x04 zip bytes with notes".to_vec();
+
+        let encrypted = encrypt_with(&plain, &passphrase, 1_000).unwrap();
+        assert!(is_encrypted(&encrypted));
+        assert!(!encrypted.windows(5).any(|w| w == b"notes"));
+        assert_eq!(decrypt(&encrypted, &passphrase).unwrap(), plain);
+
+        // Zufälliges Salt: gleiche Eingabe, anderes Ergebnis
+        assert_ne!(encrypt_with(&plain, &passphrase, 1_000).unwrap(), encrypted);
+
+        let wrong = Passphrase::new("Wachtelstall 2025").unwrap();
+        assert!(matches!(
+            decrypt(&encrypted, &wrong),
+            Err(AppError::Validation(_))
+        ));
+        assert!(decrypt(&plain, &passphrase).is_err());
+        assert!(Passphrase::new("kurz").is_err());
+    }
+
+    #[test]
+    fn test_rejects_excessive_iterations() {
+        let passphrase = Passphrase::new("Wachtelstall 2024").unwrap();
+        let mut encrypted = encrypt_with(b"PK\x03\x04", &passphrase, 1_000).unwrap();
+        let offset = MAGIC.len() + SALT_LEN;
+        encrypted[offset..offset + 4].copy_from_slice(&u32::MAX.to_be_bytes());
+        assert!(matches!(
+            decrypt(&encrypted, &passphrase),
+            Err(AppError::Validation(_))
+        ));
+    }
+}
@@ -0,0 +1,248 @@
+//! Automatische lokale Sicherungen der Datenbank
+//!
+//! Einmal pro Tag wird die Datenbank per `VACUUM INTO` in das Verzeichnis
+//! `backups/` neben der Datenbank kopiert. Es bleiben die letzten
+//! [`KEEP_SNAPSHOTS`] Kopien erhalten. Anders als der ZIP-Export braucht das
+//! keinen Speicherzugriff und ist sofort wiederherstellbar, z.B. nach einem
+//! missglückten Sync. Bei verschlüsselter Datenbank sind die Kopien mit
+//! demselben Schlüssel verschlüsselt.
+
+use crate::database;
+use crate::error::AppError;
+use chrono::{Local, NaiveDateTime, Timelike};
+use rusqlite::Connection;
+use std::path::{Path, PathBuf};
+
+/// Anzahl aufbewahrter Sicherungen
+pub const KEEP_SNAPSHOTS: usize = 7;
+
+const FILE_PREFIX: &str = "stalltagebuch-";
+const FILE_SUFFIX: &str = ".db";
+const TIMESTAMP_FORMAT: &str = "%Y%m%d-%H%M%S";
+
+/// Eine Sicherung im Backup-Verzeichnis
+#[derive(Debug, Clone, PartialEq)]
+pub struct Snapshot {
+    pub path: PathBuf,
+    /// Lokale Zeit der Sicherung (aus dem Dateinamen)
+    pub created_at: NaiveDateTime,
+    pub size_bytes: u64,
+}
+
+/// Verzeichnis der Sicherungen neben der Datenbank
+pub fn backup_dir() -> PathBuf {
+    database::get_database_path()
+        .parent()
+        .map(|parent| parent.join("backups"))
+        .unwrap_or_else(|| PathBuf::from("backups"))
+}
+
+fn parse_file_name(name: &str) -> Option<NaiveDateTime> {
+    let stamp = name.strip_prefix(FILE_PREFIX)?.strip_suffix(FILE_SUFFIX)?;
+    NaiveDateTime::parse_from_str(stamp, TIMESTAMP_FORMAT).ok()
+}
+
+/// Sicherungen in `dir`, neueste zuerst
+pub fn list_snapshots(dir: &Path) -> Result<Vec<Snapshot>, AppError> {
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+    let mut snapshots = Vec::new();
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        let name = entry.file_name();
+        let Some(created_at) = name.to_str().and_then(parse_file_name) else {
+            continue;
+        };
+        snapshots.push(Snapshot {
+            path: entry.path(),
+            created_at,
+            size_bytes: entry.metadata()?.len(),
+        });
+    }
+    snapshots.sort_by_key(|s| std::cmp::Reverse(s.created_at));
+    Ok(snapshots)
+}
+
+/// Schreibt eine Kopie der Datenbank nach `dir`
+pub fn create_snapshot(conn: &Connection, dir: &Path) -> Result<Snapshot, AppError> {
+    std::fs::create_dir_all(dir)?;
+    // Auf Sekunden genau wie im Dateinamen
+    let created_at = Local::now()
+        .naive_local()
+        .with_nanosecond(0)
+        .unwrap_or_default();
+    let path = dir.join(format!(
+        "{}{}{}",
+        FILE_PREFIX,
+        created_at.format(TIMESTAMP_FORMAT),
+        FILE_SUFFIX
+    ));
+    // VACUUM INTO verweigert existierende Dateien
+    if path.exists() {
+        std::fs::remove_file(&path)?;
+    }
+    conn.execute("VACUUM INTO ?1", [path.to_string_lossy()])?;
+    let size_bytes = std::fs::metadata(&path)?.len();
+    log::info!("Sicherung angelegt: {}", path.display());
+    Ok(Snapshot {
+        path,
+        created_at,
+        size_bytes,
+    })
+}
+
+/// Löscht alle bis auf die neuesten `keep` Sicherungen
+pub fn rotate(dir: &Path, keep: usize) -> Result<usize, AppError> {
+    let mut removed = 0;
+    for snapshot in list_snapshots(dir)?.into_iter().skip(keep) {
+        std::fs::remove_file(&snapshot.path)?;
+        removed += 1;
+    }
+    Ok(removed)
+}
+
+/// Legt eine Sicherung an, falls heute noch keine existiert
+pub fn snapshot_if_due_in(conn: &Connection, dir: &Path) -> Result<Option<Snapshot>, AppError> {
+    let today = Local::now().date_naive();
+    let latest = list_snapshots(dir)?.into_iter().next();
+    if latest.is_some_and(|s| s.created_at.date() >= today) {
+        return Ok(None);
+    }
+    let snapshot = create_snapshot(conn, dir)?;
+    rotate(dir, KEEP_SNAPSHOTS)?;
+    Ok(Some(snapshot))
+}
+
+/// Tägliche Sicherung im Standardverzeichnis
+pub fn snapshot_if_due(conn: &Connection) -> Result<Option<Snapshot>, AppError> {
+    snapshot_if_due_in(conn, &backup_dir())
+}
+
+/// Ersetzt die Datenbank durch eine Sicherung
+///
+/// Die aktuelle Datei bleibt als `*.db.before-restore-<Zeit>` liegen. Nach
+/// der Wiederherstellung öffnen alle Zugriffe die neue Datei.
+pub fn restore_snapshot(snapshot: &Path) -> Result<PathBuf, AppError> {
+    restore_snapshot_to(snapshot, &database::get_database_path())
+}
+
+fn restore_snapshot_to(snapshot: &Path, db_path: &Path) -> Result<PathBuf, AppError> {
+    // Nur lesbare Sicherungen einspielen
+    {
+        let conn = database::encryption::open(snapshot)?;
+        let result: String = conn.query_row("PRAGMA quick_check", [], |row| row.get(0))?;
+        if result != "ok" {
+            return Err(AppError::Validation(format!(
+                "Sicherung ist beschädigt: {}",
+                result
+            )));
+        }
+    }
+
+    let previous = db_path.with_extension(format!(
+        "db.before-restore-{}",
+        Local::now().format(TIMESTAMP_FORMAT)
+    ));
+    if db_path.exists() {
+        // Samt WAL-Dateien, sonst würden sie in die Sicherung eingespielt
+        database::rename_database(db_path, &previous)?;
+    }
+    if let Err(e) = std::fs::copy(snapshot, db_path) {
+        // Ursprüngliche Datei zurücklegen
+        let _ = database::rename_database(&previous, db_path);
+        return Err(e.into());
+    }
+    database::reset_connections();
+    log::warn!(
+        "Sicherung {} wiederhergestellt, vorherige Datenbank unter {}",
+        snapshot.display(),
+        previous.display()
+    );
+    Ok(previous)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::schema;
+
+    fn test_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "stalltagebuch-backup-{}-{}",
+            name,
+            uuid::Uuid::new_v4()
+        ));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_daily_snapshot_and_rotation() {
+        let dir = test_dir("rotation");
+        let conn = Connection::open_in_memory().unwrap();
+        schema::init_schema(&conn).unwrap();
+
+        assert!(snapshot_if_due_in(&conn, &dir).unwrap().is_some());
+        // Heute schon gesichert
+        assert!(snapshot_if_due_in(&conn, &dir).unwrap().is_none());
+
+        // Ältere Sicherungen vortäuschen
+        for day in 1..=10 {
+            let name = format!("{}202001{:02}-120000{}", FILE_PREFIX, day, FILE_SUFFIX);
+            std::fs::write(dir.join(name), b"old").unwrap();
+        }
+        std::fs::write(dir.join("notes.txt"), b"fremd").unwrap();
+        assert_eq!(list_snapshots(&dir).unwrap().len(), 11);
+
+        assert_eq!(rotate(&dir, KEEP_SNAPSHOTS).unwrap(), 4);
+        let remaining = list_snapshots(&dir).unwrap();
+        assert_eq!(remaining.len(), KEEP_SNAPSHOTS);
+        assert_eq!(remaining[0].created_at.date(), Local::now().date_naive());
+        assert_eq!(
+            remaining[KEEP_SNAPSHOTS - 1]
+                .created_at
+                .format("%Y%m%d")
+                .to_string(),
+            "20200105"
+        );
+        assert!(dir.join("notes.txt").exists());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_restore_replaces_database() {
+        let dir = test_dir("restore");
+        let db_path = dir.join("stalltagebuch.db");
+        {
+            let conn = Connection::open(&db_path).unwrap();
+            schema::init_schema(&conn).unwrap();
+            conn.execute("INSERT INTO quails (uuid, name) VALUES ('a', 'Vorher')", [])
+                .unwrap();
+        }
+        let snapshot = {
+            let conn = Connection::open(&db_path).unwrap();
+            let snapshot = create_snapshot(&conn, &dir.join("backups")).unwrap();
+            conn.execute("UPDATE quails SET name = 'Kaputt'", [])
+                .unwrap();
+            snapshot
+        };
+
+        let previous = restore_snapshot_to(&snapshot.path, &db_path).unwrap();
+        assert!(previous.exists());
+        let conn = Connection::open(&db_path).unwrap();
+        let name: String = conn
+            .query_row("SELECT name FROM quails", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(name, "Vorher");
+
+        // Unlesbare Sicherung lässt die Datenbank unangetastet
+        let garbage = dir.join("garbage.db");
+        std::fs::write(&garbage, b"not a database").unwrap();
+        assert!(restore_snapshot_to(&garbage, &db_path).is_err());
+        assert!(db_path.exists());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
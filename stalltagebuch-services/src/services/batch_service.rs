@@ -0,0 +1,186 @@
+//! Sammelaktionen für mehrere Wachteln auf einmal
+//!
+//! Jede Aktion läuft über dieselben Wege wie die Einzelaktion, sodass jede
+//! Wachtel ihre eigenen CRDT-Operationen bekommt. Alles, was vorab prüfbar
+//! ist, wird vor der ersten Änderung geprüft; eine Sammelaktion bricht also
+//! nicht wegen eines ungültigen Datums nach der Hälfte der Wachteln ab.
+
+use crate::error::AppError;
+use crate::models::{Coop, EventType, Quail, QuailEvent, Treatment};
+use crate::services::{profile_service, repository};
+use chrono::NaiveDate;
+use rusqlite::Connection;
+use uuid::Uuid;
+
+/// Alle gewählten Wachteln; fehlt eine, wird nichts geändert
+fn require_all(conn: &Connection, quail_ids: &[Uuid]) -> Result<Vec<Quail>, AppError> {
+    quail_ids
+        .iter()
+        .map(|uuid| repository::require::<Quail>(conn, uuid))
+        .collect()
+}
+
+/// Trägt dasselbe Ereignis bei allen gewählten Wachteln ein
+///
+/// `treatment` gehört zu Behandlungen. Gibt die UUIDs der neuen Ereignisse
+/// zurück, in der Reihenfolge der Wachteln.
+pub async fn add_event(
+    conn: &Connection,
+    quail_ids: &[Uuid],
+    event_type: EventType,
+    event_date: NaiveDate,
+    notes: Option<String>,
+    treatment: Option<Treatment>,
+) -> Result<Vec<Uuid>, AppError> {
+    require_all(conn, quail_ids)?;
+    let events: Vec<QuailEvent> = quail_ids
+        .iter()
+        .map(|quail_id| QuailEvent {
+            notes: notes.clone(),
+            treatment: treatment.clone(),
+            ..QuailEvent::new(*quail_id, event_type.clone(), event_date)
+        })
+        .collect();
+    for event in &events {
+        event.validate()?;
+    }
+
+    for event in &events {
+        repository::save_with_ops(conn, event).await?;
+    }
+    Ok(events.iter().map(|event| event.uuid).collect())
+}
+
+/// Setzt den Stall aller gewählten Wachteln, `None` nimmt sie aus ihrem Stall
+///
+/// Gibt die Anzahl tatsächlich geänderter Wachteln zurück.
+pub async fn assign_coop(
+    conn: &Connection,
+    quail_ids: &[Uuid],
+    coop_id: Option<Uuid>,
+) -> Result<usize, AppError> {
+    if let Some(coop_id) = &coop_id {
+        repository::require::<Coop>(conn, coop_id)?;
+    }
+    let mut changed = 0;
+    for mut quail in require_all(conn, quail_ids)? {
+        if quail.coop_id == coop_id {
+            continue;
+        }
+        quail.coop_id = coop_id;
+        repository::save_with_ops(conn, &quail).await?;
+        changed += 1;
+    }
+    Ok(changed)
+}
+
+/// Löscht alle gewählten Wachteln samt ihren Ereignissen
+pub async fn delete_quails(conn: &Connection, quail_ids: &[Uuid]) -> Result<(), AppError> {
+    require_all(conn, quail_ids)?;
+    for uuid in quail_ids {
+        profile_service::delete_profile(conn, uuid).await?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::schema;
+    use crate::services::{coop_service, event_service};
+
+    async fn setup(names: &[&str]) -> (Connection, Vec<Uuid>) {
+        let conn = Connection::open_in_memory().unwrap();
+        schema::init_schema(&conn).unwrap();
+        let mut ids = Vec::new();
+        for name in names {
+            let quail = Quail::new(name.to_string());
+            ids.push(
+                profile_service::create_profile(&conn, &quail)
+                    .await
+                    .unwrap(),
+            );
+        }
+        (conn, ids)
+    }
+
+    #[tokio::test]
+    async fn test_add_event_to_all() {
+        let (conn, ids) = setup(&["Greta", "Ida", "Ilse"]).await;
+        let date = NaiveDate::from_ymd_opt(2025, 4, 1).unwrap();
+
+        let events = add_event(
+            &conn,
+            &ids[..2],
+            EventType::Sick,
+            date,
+            Some("Milbenbefall".to_string()),
+            None,
+        )
+        .await
+        .unwrap();
+        assert_eq!(events.len(), 2);
+        for quail_id in &ids[..2] {
+            let latest = event_service::get_latest_event(&conn, quail_id)
+                .unwrap()
+                .unwrap();
+            assert_eq!(latest.event_type, EventType::Sick);
+            assert_eq!(latest.notes.as_deref(), Some("Milbenbefall"));
+        }
+        assert!(event_service::get_latest_event(&conn, &ids[2])
+            .unwrap()
+            .is_none());
+
+        // Ungültiges Datum: keine Wachtel bekommt ein Ereignis
+        let future = chrono::Local::now().date_naive() + chrono::Days::new(3);
+        assert!(matches!(
+            add_event(&conn, &ids, EventType::Died, future, None, None).await,
+            Err(AppError::Validation(_))
+        ));
+        assert!(event_service::get_latest_event(&conn, &ids[2])
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_assign_coop_and_delete() {
+        let (conn, ids) = setup(&["Greta", "Ida", "Ilse"]).await;
+        let coop = Coop::new("Voliere".to_string());
+        coop_service::save_coop(&conn, &coop).await.unwrap();
+
+        assert_eq!(
+            assign_coop(&conn, &ids[..2], Some(coop.uuid))
+                .await
+                .unwrap(),
+            2
+        );
+        // Schon zugeordnete Wachteln werden nicht erneut gespeichert
+        assert_eq!(assign_coop(&conn, &ids, Some(coop.uuid)).await.unwrap(), 1);
+        assert_eq!(
+            coop_service::coop_members(&conn, &coop.uuid).unwrap().len(),
+            3
+        );
+        assert!(matches!(
+            assign_coop(&conn, &ids, Some(Uuid::new_v4())).await,
+            Err(AppError::NotFound(_))
+        ));
+        assert_eq!(assign_coop(&conn, &ids[..1], None).await.unwrap(), 1);
+
+        // Eine unbekannte Wachtel verhindert das Löschen aller
+        assert!(delete_quails(&conn, &[ids[0], Uuid::new_v4()])
+            .await
+            .is_err());
+        assert_eq!(
+            profile_service::list_profiles(&conn, None).unwrap().len(),
+            3
+        );
+
+        delete_quails(&conn, &ids[1..]).await.unwrap();
+        let left: Vec<Uuid> = profile_service::list_profiles(&conn, None)
+            .unwrap()
+            .into_iter()
+            .map(|q| q.uuid)
+            .collect();
+        assert_eq!(left, [ids[0]]);
+    }
+}
@@ -0,0 +1,175 @@
+//! Export-ZIP direkt in die Nextcloud hochladen
+//!
+//! Auf Android ist der lokale Export-Ordner kaum zu finden. Deshalb kann die
+//! Sicherung über das eingerichtete Sync-Backend in einen Ordner der
+//! Nextcloud geladen werden ([`SyncSettings::export_folder`]). Die Datei
+//! liegt dabei zusätzlich lokal, wie beim normalen Export.
+
+use crate::error::AppError;
+use crate::models::SyncSettings;
+use crate::services::export_import_service::{self, ExportFilter};
+use crate::services::op_compaction::ensure_dirs;
+use crate::services::sync_backend::{ConfiguredBackend, SyncBackend};
+use crate::services::sync_service;
+use rusqlite::Connection;
+use std::fs;
+use std::path::Path;
+
+/// Fortschritt eines Nextcloud-Exports
+#[derive(Debug, Clone, PartialEq)]
+pub enum CloudExportProgress {
+    /// Das ZIP wird lokal erstellt
+    Exporting,
+    /// Das ZIP wird hochgeladen
+    Uploading {
+        bytes: u64,
+    },
+    Done(CloudExport),
+}
+
+/// Ergebnis eines Nextcloud-Exports
+#[derive(Debug, Clone, PartialEq)]
+pub struct CloudExport {
+    /// Pfad auf dem Server, z.B. `/Stalltagebuch/exports/stalltagebuch-export-….zip`
+    pub remote_path: String,
+    pub bytes: u64,
+    /// Link in die Dateien-App der Nextcloud (nur bei WebDAV-Servern)
+    pub web_url: Option<String>,
+}
+
+/// Link auf `file` in `folder` in der Dateien-App der Nextcloud
+///
+/// `None` für Test-Backends (`memory://`, `file://`).
+pub fn web_url(settings: &SyncSettings, folder: &str, file: &str) -> Option<String> {
+    if !settings.server_url.starts_with("http") {
+        return None;
+    }
+    let mut url = reqwest::Url::parse(&format!(
+        "{}/index.php/apps/files/",
+        settings.server_url.trim_end_matches('/')
+    ))
+    .ok()?;
+    url.query_pairs_mut()
+        .append_pair("dir", folder)
+        .append_pair("scrollto", file);
+    Some(url.to_string())
+}
+
+/// Gespeicherte Sync-Einstellungen samt Backend, sofern nutzbar
+pub(crate) fn configured_backend(
+    conn: &Connection,
+) -> Result<(SyncSettings, ConfiguredBackend), AppError> {
+    let settings = sync_service::load_sync_settings(conn)?
+        .ok_or_else(|| AppError::NotFound("Keine Nextcloud-Verbindung eingerichtet".to_string()))?;
+    if settings.credentials_invalid {
+        return Err(AppError::PermissionDenied(
+            "Nextcloud-Zugangsdaten ungültig, bitte neu anmelden".to_string(),
+        ));
+    }
+    let backend = ConfiguredBackend::from_settings(&settings)?;
+    Ok((settings, backend))
+}
+
+/// Lädt die lokale Datei `local_path` in den Export-Ordner hoch
+///
+/// Fehlende Ordner werden angelegt, eine gleichnamige Datei überschrieben.
+pub async fn upload_export(
+    backend: &impl SyncBackend,
+    settings: &SyncSettings,
+    local_path: &Path,
+) -> Result<CloudExport, AppError> {
+    let file_name = local_path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .ok_or_else(|| AppError::Validation("Ungültiger Dateiname für den Export".to_string()))?;
+    let data = fs::read(local_path).map_err(AppError::Filesystem)?;
+    let bytes = data.len() as u64;
+
+    let folder = settings.export_folder();
+    ensure_dirs(backend, "", folder.trim_start_matches('/')).await;
+    let remote_path = format!("{}/{}", folder, file_name);
+    backend.put(&remote_path, data).await?;
+    log::info!("Export hochgeladen: {} ({} Bytes)", remote_path, bytes);
+
+    Ok(CloudExport {
+        web_url: web_url(settings, &folder, file_name),
+        remote_path,
+        bytes,
+    })
+}
+
+/// Exportiert die Auswahl `filter` und lädt das ZIP in die Nextcloud
+///
+/// `progress` erhält die Phasen in der Reihenfolge Exportieren, Hochladen,
+/// Fertig.
+pub async fn export_to_nextcloud(
+    conn: &Connection,
+    filter: &ExportFilter,
+    mut progress: impl FnMut(CloudExportProgress),
+) -> Result<CloudExport, AppError> {
+    let (settings, backend) = configured_backend(conn)?;
+
+    progress(CloudExportProgress::Exporting);
+    let local_path = export_import_service::export_selection_to_zip(conn, filter).await?;
+    let bytes = fs::metadata(&local_path).map(|m| m.len()).unwrap_or(0);
+
+    progress(CloudExportProgress::Uploading { bytes });
+    let export = upload_export(&backend, &settings, &local_path).await?;
+    progress(CloudExportProgress::Done(export.clone()));
+    Ok(export)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::sync_backend::MemoryBackend;
+
+    fn settings_for(server_url: &str) -> SyncSettings {
+        SyncSettings::new(
+            server_url.to_string(),
+            "anna".to_string(),
+            "secret".to_string(),
+            "/Stalltagebuch".to_string(),
+        )
+    }
+
+    #[test]
+    fn test_export_folder_and_web_url() {
+        let mut settings = settings_for("https://cloud.example.org/");
+        assert_eq!(settings.export_folder(), "/Stalltagebuch/exports");
+        settings.export_path = Some("Dokumente/Wachteln/".to_string());
+        assert_eq!(settings.export_folder(), "/Dokumente/Wachteln");
+
+        assert_eq!(
+            web_url(&settings, "/Dokumente/Wachteln", "export 1.zip").as_deref(),
+            Some(
+                "https://cloud.example.org/index.php/apps/files/?dir=%2FDokumente%2FWachteln&scrollto=export+1.zip"
+            )
+        );
+        assert_eq!(web_url(&settings_for("memory://x"), "/a", "b.zip"), None);
+    }
+
+    #[tokio::test]
+    async fn test_upload_export() {
+        let dir = std::env::temp_dir().join(format!("cloud-export-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let local = dir.join("stalltagebuch-export-test.zip");
+        fs::write(&local, b"zip-inhalt").unwrap();
+
+        let backend = MemoryBackend::new();
+        let export = upload_export(&backend, &settings_for("memory://upload"), &local)
+            .await
+            .unwrap();
+        assert_eq!(
+            export.remote_path,
+            "/Stalltagebuch/exports/stalltagebuch-export-test.zip"
+        );
+        assert_eq!(export.bytes, 10);
+        assert_eq!(
+            backend.get(&export.remote_path).await.unwrap(),
+            b"zip-inhalt"
+        );
+
+        fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -0,0 +1,349 @@
+//! Netzwerk- und Ladezustand für die Sync-Richtlinien
+//!
+//! Metadaten und Thumbnails werden immer synchronisiert, Original-Fotos nur,
+//! wenn es die [`OriginalsPolicy`] der Sync-Einstellungen erlaubt. Der
+//! Hintergrund-Sync pausiert zusätzlich auf Wunsch bei getakteter Verbindung
+//! oder niedrigem Akkustand (siehe [`sync_deferral`]).
+//!
+//! Den Zustand liefert ein [`ConnectivityProvider`]: auf Android fragt
+//! [`SystemConnectivity`] per JNI den `ConnectivityManager` und den
+//! `BatteryManager`, unter Linux liest es `/proc/net/route` und
+//! `/sys/class/power_supply`. Mit [`set_provider`] lässt sich ein anderer
+//! einsetzen (Tests, Demos).
+
+use crate::models::{OriginalsPolicy, SyncSettings};
+use std::sync::{Arc, RwLock};
+
+/// Ab diesem Ladestand (ohne Ladegerät) gilt der Akku als niedrig
+pub const LOW_BATTERY_PERCENT: u8 = 15;
+
+/// Momentaner Zustand des Geräts
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Connectivity {
+    /// Nicht getaktete Verbindung (WLAN, Ethernet)
+    pub unmetered: bool,
+    /// Gerät hängt am Ladegerät (oder hat keinen Akku)
+    pub charging: bool,
+    /// Ladestand in Prozent (`None` = kein Akku oder unbekannt)
+    pub battery_percent: Option<u8>,
+}
+
+impl Connectivity {
+    /// Akku fast leer und kein Ladegerät angeschlossen
+    pub fn low_battery(&self) -> bool {
+        !self.charging
+            && self
+                .battery_percent
+                .is_some_and(|percent| percent <= LOW_BATTERY_PERCENT)
+    }
+}
+
+/// Grund, aus dem der Hintergrund-Sync gerade pausiert
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyncDeferral {
+    /// Getaktete Verbindung und `pause_on_metered`
+    Metered,
+    /// Niedriger Akkustand und `pause_on_low_battery`
+    LowBattery,
+}
+
+/// Quelle für den Netzwerk- und Ladezustand
+pub trait ConnectivityProvider: Send + Sync {
+    fn current(&self) -> Connectivity;
+}
+
+/// Zustand des Betriebssystems
+pub struct SystemConnectivity;
+
+impl ConnectivityProvider for SystemConnectivity {
+    #[cfg(target_os = "android")]
+    fn current(&self) -> Connectivity {
+        android::query().unwrap_or_else(|e| {
+            // Im Zweifel keine großen Übertragungen
+            log::warn!("Verbindungsstatus nicht lesbar: {}", e);
+            Connectivity {
+                unmetered: false,
+                charging: false,
+                battery_percent: None,
+            }
+        })
+    }
+
+    #[cfg(not(target_os = "android"))]
+    fn current(&self) -> Connectivity {
+        desktop::query()
+    }
+}
+
+static PROVIDER: RwLock<Option<Arc<dyn ConnectivityProvider>>> = RwLock::new(None);
+
+/// Ersetzt den [`SystemConnectivity`]-Provider
+pub fn set_provider(provider: Arc<dyn ConnectivityProvider>) {
+    *PROVIDER.write().unwrap_or_else(|e| e.into_inner()) = Some(provider);
+}
+
+/// Aktueller Zustand laut eingesetztem Provider
+pub fn current() -> Connectivity {
+    match PROVIDER.read().unwrap_or_else(|e| e.into_inner()).as_ref() {
+        Some(provider) => provider.current(),
+        None => SystemConnectivity.current(),
+    }
+}
+
+/// Dürfen bei diesem Zustand Originale automatisch übertragen werden?
+pub fn allows_originals(policy: OriginalsPolicy, state: Connectivity) -> bool {
+    match policy {
+        OriginalsPolicy::Always => true,
+        OriginalsPolicy::WifiOnly => state.unmetered,
+        OriginalsPolicy::WifiAndCharging => state.unmetered && state.charging,
+        OriginalsPolicy::OnDemand => false,
+    }
+}
+
+/// [`allows_originals`] für den aktuellen Zustand
+pub fn originals_allowed_now(policy: OriginalsPolicy) -> bool {
+    policy == OriginalsPolicy::Always || allows_originals(policy, current())
+}
+
+/// Soll der Hintergrund-Sync bei diesem Zustand ausgesetzt werden?
+pub fn sync_deferral(settings: &SyncSettings, state: Connectivity) -> Option<SyncDeferral> {
+    if settings.pause_on_metered && !state.unmetered {
+        Some(SyncDeferral::Metered)
+    } else if settings.pause_on_low_battery && state.low_battery() {
+        Some(SyncDeferral::LowBattery)
+    } else {
+        None
+    }
+}
+
+/// Liest den Zustand unter Linux; ohne die Dateien (andere Systeme) gilt das
+/// Gerät als im WLAN und am Netz
+#[cfg(not(target_os = "android"))]
+mod desktop {
+    use super::Connectivity;
+    use std::fs;
+    use std::path::Path;
+
+    const POWER_SUPPLY_DIR: &str = "/sys/class/power_supply";
+
+    pub(super) fn query() -> Connectivity {
+        let unmetered = fs::read_to_string("/proc/net/route")
+            .ok()
+            .and_then(|table| default_route_interface(&table))
+            .map(|iface| {
+                let uevent = fs::read_to_string(format!("/sys/class/net/{}/uevent", iface))
+                    .unwrap_or_default();
+                !is_metered_interface(&iface, &uevent)
+            })
+            .unwrap_or(true);
+        let (charging, battery_percent) = power_state(Path::new(POWER_SUPPLY_DIR));
+
+        Connectivity {
+            unmetered,
+            charging,
+            battery_percent,
+        }
+    }
+
+    /// Schnittstelle der Default-Route (Ziel 00000000) aus `/proc/net/route`
+    pub(super) fn default_route_interface(table: &str) -> Option<String> {
+        table.lines().skip(1).find_map(|line| {
+            let mut cols = line.split_whitespace();
+            let iface = cols.next()?;
+            (cols.next()? == "00000000").then(|| iface.to_string())
+        })
+    }
+
+    /// Mobilfunk (WWAN) und USB-Tethering gelten als getaktet
+    pub(super) fn is_metered_interface(iface: &str, uevent: &str) -> bool {
+        uevent.lines().any(|line| line.trim() == "DEVTYPE=wwan")
+            || ["ww", "usb", "rndis"]
+                .iter()
+                .any(|prefix| iface.starts_with(prefix))
+    }
+
+    /// (lädt/kein Akku, Ladestand) aus den `power_supply`-Einträgen
+    pub(super) fn power_state(dir: &Path) -> (bool, Option<u8>) {
+        let read = |entry: &Path, name: &str| {
+            fs::read_to_string(entry.join(name))
+                .map(|v| v.trim().to_string())
+                .unwrap_or_default()
+        };
+
+        let mut mains_online = false;
+        let mut battery: Option<(bool, Option<u8>)> = None;
+        for entry in fs::read_dir(dir).into_iter().flatten().flatten() {
+            let path = entry.path();
+            match read(&path, "type").as_str() {
+                "Mains" | "USB" => mains_online |= read(&path, "online") == "1",
+                "Battery" if battery.is_none() => {
+                    let charging = matches!(read(&path, "status").as_str(), "Charging" | "Full");
+                    battery = Some((charging, read(&path, "capacity").parse().ok()));
+                }
+                _ => {}
+            }
+        }
+
+        match battery {
+            Some((charging, percent)) => (charging || mains_online, percent),
+            None => (true, None),
+        }
+    }
+}
+
+#[cfg(target_os = "android")]
+mod android {
+    use super::Connectivity;
+    use crate::error::AppError;
+    use jni::objects::{JObject, JValue};
+    use ndk_context::android_context;
+
+    pub(super) fn query() -> Result<Connectivity, AppError> {
+        let vm_ptr = android_context().vm() as *mut jni::sys::JavaVM;
+        let vm = unsafe { jni::JavaVM::from_raw(vm_ptr) }
+            .map_err(|e| AppError::Other(format!("JavaVM creation failed: {}", e)))?;
+        let mut env = vm
+            .attach_current_thread()
+            .map_err(|e| AppError::Other(format!("Failed to attach thread: {}", e)))?;
+        let context =
+            unsafe { JObject::from_raw(android_context().context() as jni::sys::jobject) };
+
+        let jni_err =
+            |what: &str, e: jni::errors::Error| AppError::Other(format!("{} failed: {}", what, e));
+
+        let mut service = |name: &str| -> Result<JObject, AppError> {
+            let name = env.new_string(name).map_err(|e| jni_err("new_string", e))?;
+            env.call_method(
+                &context,
+                "getSystemService",
+                "(Ljava/lang/String;)Ljava/lang/Object;",
+                &[JValue::Object(&name)],
+            )
+            .and_then(|v| v.l())
+            .map_err(|e| jni_err("getSystemService", e))
+        };
+        let connectivity = service("connectivity")?;
+        let battery = service("batterymanager")?;
+
+        let metered = env
+            .call_method(&connectivity, "isActiveNetworkMetered", "()Z", &[])
+            .and_then(|v| v.z())
+            .map_err(|e| jni_err("isActiveNetworkMetered", e))?;
+        let charging = env
+            .call_method(&battery, "isCharging", "()Z", &[])
+            .and_then(|v| v.z())
+            .map_err(|e| jni_err("isCharging", e))?;
+        // BatteryManager.BATTERY_PROPERTY_CAPACITY
+        let capacity = env
+            .call_method(&battery, "getIntProperty", "(I)I", &[JValue::Int(4)])
+            .and_then(|v| v.i())
+            .map_err(|e| jni_err("getIntProperty", e))?;
+
+        Ok(Connectivity {
+            unmetered: !metered,
+            charging,
+            battery_percent: u8::try_from(capacity).ok().filter(|p| *p <= 100),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_policy_decides_by_connectivity() {
+        let mobile = Connectivity {
+            unmetered: false,
+            charging: true,
+            battery_percent: Some(80),
+        };
+        let wifi = Connectivity {
+            unmetered: true,
+            charging: false,
+            battery_percent: Some(80),
+        };
+        let wifi_charging = Connectivity {
+            unmetered: true,
+            charging: true,
+            battery_percent: Some(80),
+        };
+
+        assert!(allows_originals(OriginalsPolicy::Always, mobile));
+        assert!(!allows_originals(OriginalsPolicy::WifiOnly, mobile));
+        assert!(allows_originals(OriginalsPolicy::WifiOnly, wifi));
+        assert!(!allows_originals(OriginalsPolicy::WifiAndCharging, wifi));
+        assert!(allows_originals(
+            OriginalsPolicy::WifiAndCharging,
+            wifi_charging
+        ));
+        assert!(!allows_originals(OriginalsPolicy::OnDemand, wifi_charging));
+    }
+
+    #[test]
+    fn test_sync_deferral_follows_settings() {
+        let mut settings = SyncSettings::new(
+            "https://cloud.example.com".to_string(),
+            "anna".to_string(),
+            "password".to_string(),
+            "/Stalltagebuch".to_string(),
+        );
+        let mobile_low = Connectivity {
+            unmetered: false,
+            charging: false,
+            battery_percent: Some(LOW_BATTERY_PERCENT),
+        };
+
+        assert_eq!(sync_deferral(&settings, mobile_low), None);
+
+        settings.pause_on_low_battery = true;
+        assert_eq!(
+            sync_deferral(&settings, mobile_low),
+            Some(SyncDeferral::LowBattery)
+        );
+        let plugged_in = Connectivity {
+            charging: true,
+            ..mobile_low
+        };
+        assert_eq!(sync_deferral(&settings, plugged_in), None);
+
+        settings.pause_on_metered = true;
+        assert_eq!(
+            sync_deferral(&settings, plugged_in),
+            Some(SyncDeferral::Metered)
+        );
+    }
+
+    #[cfg(not(target_os = "android"))]
+    #[test]
+    fn test_desktop_detection() {
+        let route = "Iface\tDestination\tGateway\n\
+                     wlp2s0\t0010A8C0\t00000000\n\
+                     wwan0\t00000000\t0100A8C0\n";
+        assert_eq!(
+            desktop::default_route_interface(route),
+            Some("wwan0".to_string())
+        );
+        assert!(desktop::is_metered_interface("wwan0", ""));
+        assert!(desktop::is_metered_interface("mbim0", "DEVTYPE=wwan\n"));
+        assert!(!desktop::is_metered_interface("wlp2s0", "DEVTYPE=wlan\n"));
+
+        let dir = std::env::temp_dir().join(format!("power-supply-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(dir.join("BAT0")).unwrap();
+        std::fs::write(dir.join("BAT0/type"), "Battery\n").unwrap();
+        std::fs::write(dir.join("BAT0/status"), "Discharging\n").unwrap();
+        std::fs::write(dir.join("BAT0/capacity"), "12\n").unwrap();
+        assert_eq!(desktop::power_state(&dir), (false, Some(12)));
+
+        std::fs::create_dir_all(dir.join("AC")).unwrap();
+        std::fs::write(dir.join("AC/type"), "Mains\n").unwrap();
+        std::fs::write(dir.join("AC/online"), "1\n").unwrap();
+        assert_eq!(desktop::power_state(&dir), (true, Some(12)));
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(
+            desktop::power_state(&std::env::temp_dir().join("no_power_supply")),
+            (true, None)
+        );
+    }
+}
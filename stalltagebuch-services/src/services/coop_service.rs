@@ -0,0 +1,126 @@
+//! Ställe bzw. Gehege, denen Wachteln und Eier-Einträge zugeordnet sind
+//!
+//! Ein Stall ist eine eigene synchronisierte Entität (`coop`); Wachteln und
+//! Eier-Einträge verweisen per `coop_id` ohne Fremdschlüssel darauf. Ein
+//! gelöschter Stall gibt seine Wachteln frei, Eier-Einträge behalten den
+//! Verweis, damit der Tag nicht mit dem Eintrag des ganzen Bestands
+//! zusammenfällt.
+
+use crate::error::AppError;
+use crate::models::{Coop, Quail};
+use crate::services::repository;
+use rusqlite::Connection;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// Alle Ställe, nach Namen sortiert
+pub fn list_coops(conn: &Connection) -> Result<Vec<Coop>, AppError> {
+    repository::list(conn, "1", [], "name COLLATE NOCASE")
+}
+
+pub fn get_coop(conn: &Connection, uuid: &Uuid) -> Result<Coop, AppError> {
+    repository::require(conn, uuid)
+}
+
+/// Legt den Stall an oder speichert ihn; Namen sind eindeutig
+pub async fn save_coop(conn: &Connection, coop: &Coop) -> Result<(), AppError> {
+    let mut coop = coop.clone();
+    coop.name = coop.name.trim().to_string();
+    let taken = list_coops(conn)?
+        .iter()
+        .any(|c| c.uuid != coop.uuid && c.name.to_lowercase() == coop.name.to_lowercase());
+    if taken {
+        return Err(AppError::Validation(format!(
+            "Stall „{}“ gibt es schon",
+            coop.name
+        )));
+    }
+    repository::save_with_ops(conn, &coop).await
+}
+
+/// Löscht den Stall; seine Wachteln sind danach keinem Stall zugeordnet
+pub async fn delete_coop(conn: &Connection, uuid: &Uuid) -> Result<(), AppError> {
+    for mut quail in coop_members(conn, uuid)? {
+        quail.coop_id = None;
+        repository::save_with_ops(conn, &quail).await?;
+    }
+    repository::delete_with_ops::<Coop>(conn, uuid).await
+}
+
+/// Wachteln eines Stalls, nach Namen sortiert
+pub fn coop_members(conn: &Connection, uuid: &Uuid) -> Result<Vec<Quail>, AppError> {
+    repository::list(conn, "coop_id = ?1", [uuid.to_string()], "name")
+}
+
+/// Anzahl Wachteln je Stall
+pub fn count_members(conn: &Connection) -> Result<HashMap<Uuid, usize>, AppError> {
+    let mut stmt = conn.prepare(
+        "SELECT coop_id, COUNT(*) FROM quails
+         WHERE deleted = 0 AND coop_id IS NOT NULL
+         GROUP BY coop_id",
+    )?;
+    let rows = stmt.query_map([], |row| {
+        Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?))
+    })?;
+    let mut counts = HashMap::new();
+    for row in rows {
+        let (coop_id, count) = row?;
+        if let Ok(coop_id) = Uuid::parse_str(&coop_id) {
+            counts.insert(coop_id, count as usize);
+        }
+    }
+    Ok(counts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::schema;
+    use crate::services::profile_service;
+
+    #[tokio::test]
+    async fn test_coop_membership() {
+        let conn = Connection::open_in_memory().unwrap();
+        schema::init_schema(&conn).unwrap();
+
+        let stall = Coop::new("Stall 1".to_string());
+        save_coop(&conn, &stall).await.unwrap();
+        let voliere = Coop::new("Voliere".to_string());
+        save_coop(&conn, &voliere).await.unwrap();
+        // Namen sind eindeutig (ohne Groß-/Kleinschreibung)
+        assert!(matches!(
+            save_coop(&conn, &Coop::new(" stall 1 ".to_string())).await,
+            Err(AppError::Validation(_))
+        ));
+
+        for (name, coop) in [
+            ("Greta", Some(&stall)),
+            ("Ida", Some(&stall)),
+            ("Ilse", None),
+        ] {
+            let mut quail = Quail::new(name.to_string());
+            quail.coop_id = coop.map(|c| c.uuid);
+            profile_service::create_profile(&conn, &quail)
+                .await
+                .unwrap();
+        }
+
+        let names: Vec<String> = list_coops(&conn)
+            .unwrap()
+            .into_iter()
+            .map(|c| c.name)
+            .collect();
+        assert_eq!(names, ["Stall 1", "Voliere"]);
+        assert_eq!(count_members(&conn).unwrap().get(&stall.uuid), Some(&2));
+        assert_eq!(coop_members(&conn, &voliere.uuid).unwrap().len(), 0);
+
+        // Löschen gibt die Wachteln frei
+        delete_coop(&conn, &stall.uuid).await.unwrap();
+        assert!(get_coop(&conn, &stall.uuid).is_err());
+        assert!(coop_members(&conn, &stall.uuid).unwrap().is_empty());
+        assert!(profile_service::list_profiles(&conn, None)
+            .unwrap()
+            .iter()
+            .all(|q| q.coop_id.is_none()));
+    }
+}
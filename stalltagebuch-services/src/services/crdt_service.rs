@@ -0,0 +1,574 @@
+/// CRDT service: Hybrid Logical Clock (HLC) and basic CRDT operations
+///
+/// Every synced row stores the HLC of its last change (`logical_clock` =
+/// physical ms, `clock_counter`, `clock_device`). Merges compare the full
+/// clock, so a device whose wall clock runs behind still orders its changes
+/// after everything it has already seen.
+use crate::error::AppError;
+use rusqlite::{Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+
+/// Hybrid Logical Clock for total ordering
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct HybridLogicalClock {
+    pub ts: i64,              // Wall-clock timestamp (milliseconds since epoch)
+    pub logical_counter: u32, // Logical counter for causality
+    pub device_id: String,    // Tie-breaker for deterministic ordering
+}
+
+impl HybridLogicalClock {
+    /// Create a new HLC with current wall-clock time
+    pub fn new(device_id: String) -> Self {
+        Self {
+            ts: chrono::Utc::now().timestamp_millis(),
+            logical_counter: 0,
+            device_id,
+        }
+    }
+
+    /// Advance HLC for a local event (each call yields a strictly greater clock)
+    pub fn tick(&mut self) {
+        self.tick_at(chrono::Utc::now().timestamp_millis());
+    }
+
+    /// [`tick`](Self::tick) with an explicit wall-clock time
+    pub fn tick_at(&mut self, now: i64) {
+        if now > self.ts {
+            self.ts = now;
+            self.logical_counter = 0;
+        } else {
+            // Wall clock did not advance (or runs behind): order by counter
+            self.logical_counter += 1;
+        }
+    }
+
+    /// Update HLC based on received remote clock (preserves monotonicity)
+    pub fn update(&mut self, remote: &HybridLogicalClock) {
+        self.update_at(remote, chrono::Utc::now().timestamp_millis());
+    }
+
+    /// [`update`](Self::update) with an explicit wall-clock time
+    pub fn update_at(&mut self, remote: &HybridLogicalClock, now: i64) {
+        let max_ts = self.ts.max(remote.ts).max(now);
+
+        if max_ts == self.ts && max_ts == remote.ts {
+            self.logical_counter = self.logical_counter.max(remote.logical_counter) + 1;
+        } else if max_ts == self.ts {
+            self.logical_counter += 1;
+        } else if max_ts == remote.ts {
+            self.logical_counter = remote.logical_counter + 1;
+        } else {
+            self.logical_counter = 0;
+        }
+
+        self.ts = max_ts;
+    }
+}
+
+impl PartialOrd for HybridLogicalClock {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for HybridLogicalClock {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.ts
+            .cmp(&other.ts)
+            .then_with(|| self.logical_counter.cmp(&other.logical_counter))
+            .then_with(|| self.device_id.cmp(&other.device_id))
+    }
+}
+
+/// CRDT operation types
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum CrdtOp {
+    /// Last-Writer-Wins register (for scalar fields)
+    LwwSet {
+        field: String,
+        value: serde_json::Value,
+    },
+    /// OR-Set add (for collections)
+    OrAdd {
+        field: String,
+        element: String,
+        element_id: String, // unique tag for this add
+    },
+    /// OR-Set remove (for collections)
+    OrRemove { field: String, element_id: String },
+    /// PN-Counter increment (for additive counters)
+    PnIncrement { field: String, delta: i32 },
+    /// Tombstone (soft delete)
+    Delete,
+}
+
+/// NDJSON operation log entry
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Operation {
+    pub op_id: String,       // ULID of this operation
+    pub entity_type: String, // quail | event | egg_record | photo_meta
+    pub entity_id: String,   // UUID of the entity
+    pub clock: HybridLogicalClock,
+    pub op: CrdtOp,
+}
+
+impl Operation {
+    /// Create a new operation, ticking `clock` (see [`load_local_clock`])
+    pub fn new(
+        entity_type: String,
+        entity_id: String,
+        clock: &mut HybridLogicalClock,
+        op: CrdtOp,
+    ) -> Self {
+        clock.tick();
+
+        Self {
+            op_id: ulid::Ulid::new().to_string(),
+            entity_type,
+            entity_id,
+            clock: clock.clone(),
+            op,
+        }
+    }
+}
+
+/// Table holding the entities of an operation's `entity_type`
+pub fn entity_table(entity_type: &str) -> Option<&'static str> {
+    match entity_type {
+        "quail" => Some("quails"),
+        "event" => Some("quail_events"),
+        "photo" => Some("photos"),
+        "egg" => Some("egg_records"),
+        "coop" => Some("coops"),
+        _ => None,
+    }
+}
+
+/// This device's HLC as of the last local change or merge (in `sync_checkpoint`)
+pub fn load_local_clock(
+    conn: &Connection,
+    device_id: &str,
+) -> Result<HybridLogicalClock, AppError> {
+    let stored: Option<(i64, u32)> = conn
+        .query_row(
+            "SELECT last_ts, last_logical_counter FROM sync_checkpoint WHERE id = 1",
+            [],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .optional()?;
+    let (ts, logical_counter) = stored.unwrap_or_default();
+    Ok(HybridLogicalClock {
+        ts,
+        logical_counter,
+        device_id: device_id.to_string(),
+    })
+}
+
+/// Persists the local HLC so later ticks stay ahead of it
+pub fn save_local_clock(conn: &Connection, clock: &HybridLogicalClock) -> Result<(), AppError> {
+    conn.execute(
+        "INSERT INTO sync_checkpoint (id, last_ts, last_logical_counter, last_device_id)
+         VALUES (1, ?1, ?2, ?3)
+         ON CONFLICT(id) DO UPDATE SET last_ts = ?1, last_logical_counter = ?2,
+             last_device_id = ?3, updated_at = CURRENT_TIMESTAMP",
+        rusqlite::params![clock.ts, clock.logical_counter, &clock.device_id],
+    )?;
+    Ok(())
+}
+
+/// Advances the local HLC past a clock received from another device
+pub fn observe_remote_clock(
+    conn: &Connection,
+    remote: &HybridLogicalClock,
+) -> Result<(), AppError> {
+    let device_id: Option<String> = conn
+        .query_row(
+            "SELECT last_device_id FROM sync_checkpoint WHERE id = 1",
+            [],
+            |row| row.get(0),
+        )
+        .optional()?
+        .flatten();
+    let mut clock = load_local_clock(conn, &device_id.unwrap_or_default())?;
+    clock.update(remote);
+    save_local_clock(conn, &clock)
+}
+
+/// HLC of an entity's last change, `None` if the row does not exist
+pub fn load_entity_clock(
+    conn: &Connection,
+    table: &str,
+    entity_id: &str,
+) -> Result<Option<(HybridLogicalClock, bool)>, AppError> {
+    let row = conn
+        .prepare_cached(&format!(
+            "SELECT logical_clock, clock_counter, clock_device, deleted FROM {} WHERE uuid = ?1",
+            table
+        ))?
+        .query_row([entity_id], |row| {
+            Ok((
+                HybridLogicalClock {
+                    ts: row.get(0)?,
+                    logical_counter: row.get(1)?,
+                    device_id: row.get(2)?,
+                },
+                row.get::<_, i32>(3)? == 1,
+            ))
+        })
+        .optional()?;
+    Ok(row)
+}
+
+/// LWW check: does a change with `clock` win over the stored row?
+/// Deleted rows stay deleted, also those removed locally (see [`is_deleted`]).
+pub fn is_newer(
+    conn: &Connection,
+    table: &str,
+    entity_id: &str,
+    clock: &HybridLogicalClock,
+) -> Result<bool, AppError> {
+    Ok(match load_entity_clock(conn, table, entity_id)? {
+        Some((current, deleted)) => !deleted && *clock > current,
+        None => !has_tombstone(conn, entity_id)?,
+    })
+}
+
+/// Is the entity deleted? Local deletes remove the row, their `Delete`
+/// operation stays in `op_log` as tombstone.
+pub fn is_deleted(conn: &Connection, table: &str, entity_id: &str) -> Result<bool, AppError> {
+    match load_entity_clock(conn, table, entity_id)? {
+        Some((_, true)) => Ok(true),
+        _ => has_tombstone(conn, entity_id),
+    }
+}
+
+fn has_tombstone(conn: &Connection, entity_id: &str) -> Result<bool, AppError> {
+    let delete = serde_json::to_string(&CrdtOp::Delete)
+        .map_err(|e| AppError::Other(format!("Serialize op_kind failed: {}", e)))?;
+    Ok(conn
+        .prepare_cached("SELECT 1 FROM op_log WHERE entity_id = ?1 AND op_kind = ?2")?
+        .exists(rusqlite::params![entity_id, delete])?)
+}
+
+/// Records an operation in `op_log` (local and merged ones); ignores
+/// operations that are already there
+pub fn log_operation(conn: &Connection, op: &Operation) -> Result<(), AppError> {
+    let op_kind = serde_json::to_string(&op.op)
+        .map_err(|e| AppError::Other(format!("Serialize op_kind failed: {}", e)))?;
+    conn.prepare_cached(
+        "INSERT OR IGNORE INTO op_log (
+            op_id, entity_type, entity_id, ts, logical_counter, device_id, op_kind, payload
+        ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+    )?
+    .execute(rusqlite::params![
+        &op.op_id,
+        &op.entity_type,
+        &op.entity_id,
+        op.clock.ts,
+        op.clock.logical_counter,
+        &op.clock.device_id,
+        op_kind,
+        "" // payload unused for now
+    ])?;
+    Ok(())
+}
+
+/// Is there a row for the entity (possibly soft-deleted)?
+pub fn row_exists(conn: &Connection, table: &str, entity_id: &str) -> Result<bool, AppError> {
+    Ok(conn
+        .prepare_cached(&format!("SELECT 1 FROM {} WHERE uuid = ?1", table))?
+        .exists([entity_id])?)
+}
+
+/// Makes sure a referenced quail exists, creating a placeholder if needed
+///
+/// Operations of different devices arrive in any order, so an event or photo
+/// can reference a quail that is not there yet. Placeholders carry the zero
+/// clock: every real operation for the quail wins and fills in the fields.
+/// Returns `false` if the quail was deleted locally (only its tombstone is
+/// left); the caller must then drop the reference.
+pub fn ensure_quail(conn: &Connection, quail_id: &str) -> Result<bool, AppError> {
+    if row_exists(conn, "quails", quail_id)? {
+        return Ok(true);
+    }
+    if has_tombstone(conn, quail_id)? {
+        return Ok(false);
+    }
+    log::info!(
+        "CRDT: Erstelle Platzhalter-Wachtel {} (out-of-order merge)",
+        quail_id
+    );
+    // Minimal gültiger Platzhalter: name darf nicht NULL sein
+    conn.execute(
+        "INSERT INTO quails (uuid, name, rev, logical_clock, deleted) VALUES (?1, '', 0, 0, 0)",
+        [quail_id],
+    )?;
+    Ok(true)
+}
+
+/// Like [`ensure_quail`] for a referenced photo (e.g. a profile photo); the
+/// placeholder has an empty path until the photo's own operations arrive
+pub fn ensure_photo(conn: &Connection, photo_id: &str) -> Result<bool, AppError> {
+    if row_exists(conn, "photos", photo_id)? {
+        return Ok(true);
+    }
+    if has_tombstone(conn, photo_id)? {
+        return Ok(false);
+    }
+    log::info!(
+        "CRDT: Erstelle Platzhalter für fehlendes Foto {} (out-of-order merge)",
+        photo_id
+    );
+    conn.execute(
+        "INSERT INTO photos (uuid, quail_id, event_id, path, relative_path, thumbnail_path, rev, logical_clock, deleted)
+         VALUES (?1, NULL, NULL, '', NULL, NULL, 0, 0, 0)",
+        [photo_id],
+    )?;
+    Ok(true)
+}
+
+/// Parks a reference to an entity that has not arrived yet
+///
+/// Unlike quails and photos, a missing event gets no placeholder: it would
+/// show up as an "alive" event of an empty quail. The referencing column
+/// stays NULL until [`resolve_deferred_references`] fills it in.
+pub fn defer_reference(
+    conn: &Connection,
+    table: &str,
+    entity_id: &str,
+    column: &str,
+    target_id: &str,
+) -> Result<(), AppError> {
+    log::info!(
+        "CRDT: Verweis {}.{} von {} auf fehlendes {} geparkt (out-of-order merge)",
+        table,
+        column,
+        entity_id,
+        target_id
+    );
+    conn.prepare_cached(
+        "INSERT OR REPLACE INTO deferred_references (entity_table, entity_id, column_name, target_id)
+         VALUES (?1, ?2, ?3, ?4)",
+    )?
+    .execute(rusqlite::params![table, entity_id, column, target_id])?;
+    Ok(())
+}
+
+/// Forgets parked references of an entity, e.g. because a newer operation
+/// set the column
+pub fn drop_deferred_references(
+    conn: &Connection,
+    table: &str,
+    entity_id: &str,
+    column: Option<&str>,
+) -> Result<(), AppError> {
+    conn.prepare_cached(
+        "DELETE FROM deferred_references
+         WHERE entity_table = ?1 AND entity_id = ?2 AND (?3 IS NULL OR column_name = ?3)",
+    )?
+    .execute(rusqlite::params![table, entity_id, column])?;
+    Ok(())
+}
+
+/// Fills in the references parked for `target_table`.`target_id` once the
+/// row exists; references to a deleted target are dropped
+pub fn resolve_deferred_references(
+    conn: &Connection,
+    target_table: &str,
+    target_id: &str,
+) -> Result<usize, AppError> {
+    let parked: Vec<(String, String, String)> = conn
+        .prepare_cached(
+            "SELECT entity_table, entity_id, column_name FROM deferred_references
+             WHERE target_id = ?1",
+        )?
+        .query_map([target_id], |row| {
+            Ok((row.get(0)?, row.get(1)?, row.get(2)?))
+        })?
+        .collect::<rusqlite::Result<_>>()?;
+    if parked.is_empty() {
+        return Ok(0);
+    }
+
+    let present = matches!(
+        load_entity_clock(conn, target_table, target_id)?,
+        Some((_, false))
+    );
+    let mut resolved = 0;
+    if present {
+        for (table, entity_id, column) in &parked {
+            resolved += conn
+                .prepare_cached(&format!(
+                    "UPDATE {} SET {} = ?1 WHERE uuid = ?2",
+                    table, column
+                ))?
+                .execute([target_id, entity_id.as_str()])?;
+        }
+    }
+    conn.prepare_cached("DELETE FROM deferred_references WHERE target_id = ?1")?
+        .execute([target_id])?;
+    Ok(resolved)
+}
+
+/// Stores `clock` as the HLC of the row's last change
+pub fn stamp_entity(
+    conn: &Connection,
+    table: &str,
+    entity_id: &str,
+    clock: &HybridLogicalClock,
+) -> Result<(), AppError> {
+    conn.prepare_cached(&format!(
+        "UPDATE {} SET logical_clock = ?1, clock_counter = ?2, clock_device = ?3 WHERE uuid = ?4",
+        table
+    ))?
+    .execute(rusqlite::params![
+        clock.ts,
+        clock.logical_counter,
+        &clock.device_id,
+        entity_id
+    ])?;
+    Ok(())
+}
+
+/// Apply LWW merge: compare clocks and keep the winner
+pub fn lww_merge<T: Clone>(
+    local_value: &T,
+    local_clock: &HybridLogicalClock,
+    remote_value: &T,
+    remote_clock: &HybridLogicalClock,
+) -> (T, HybridLogicalClock) {
+    if remote_clock > local_clock {
+        (remote_value.clone(), remote_clock.clone())
+    } else {
+        (local_value.clone(), local_clock.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hlc_ordering() {
+        let mut clock1 = HybridLogicalClock::new("device1".to_string());
+        let mut clock2 = HybridLogicalClock::new("device2".to_string());
+
+        clock1.tick();
+        clock2.tick();
+
+        // Assuming clock2 ticks slightly later or same time
+        assert!(clock1 <= clock2);
+    }
+
+    #[test]
+    fn test_hlc_update() {
+        let mut local = HybridLogicalClock {
+            ts: 1000,
+            logical_counter: 5,
+            device_id: "device1".to_string(),
+        };
+
+        let remote = HybridLogicalClock {
+            ts: 1000,
+            logical_counter: 3,
+            device_id: "device2".to_string(),
+        };
+
+        // Wall clock of this device runs behind both clocks
+        local.update_at(&remote, 900);
+
+        // Should increment logical counter since ts is same
+        assert_eq!(local.ts, 1000);
+        assert!(local.logical_counter > 5);
+    }
+
+    #[test]
+    fn test_tick_with_skewed_wall_clock_stays_monotonic() {
+        let mut clock = HybridLogicalClock {
+            ts: 5000,
+            logical_counter: 0,
+            device_id: "device1".to_string(),
+        };
+        let before = clock.clone();
+
+        clock.tick_at(1000);
+        assert!(clock > before);
+        assert_eq!(clock.ts, 5000);
+
+        clock.tick_at(6000);
+        assert_eq!((clock.ts, clock.logical_counter), (6000, 0));
+    }
+
+    #[test]
+    fn test_entity_clock_roundtrip() {
+        let conn = Connection::open_in_memory().unwrap();
+        crate::database::schema::init_schema(&conn).unwrap();
+        conn.execute("INSERT INTO quails (uuid, name) VALUES ('q1', 'Hanni')", [])
+            .unwrap();
+
+        let clock = HybridLogicalClock {
+            ts: 1000,
+            logical_counter: 2,
+            device_id: "device1".to_string(),
+        };
+        assert!(is_newer(&conn, "quails", "q1", &clock).unwrap());
+        stamp_entity(&conn, "quails", "q1", &clock).unwrap();
+        assert!(!is_newer(&conn, "quails", "q1", &clock).unwrap());
+
+        // Same ms, higher counter wins even though `ts` is equal
+        let later = HybridLogicalClock {
+            logical_counter: 3,
+            ..clock.clone()
+        };
+        assert!(is_newer(&conn, "quails", "q1", &later).unwrap());
+
+        save_local_clock(&conn, &later).unwrap();
+        observe_remote_clock(&conn, &clock).unwrap();
+        assert!(load_local_clock(&conn, "device1").unwrap() > later);
+    }
+
+    #[test]
+    fn test_local_delete_leaves_tombstone() {
+        let conn = Connection::open_in_memory().unwrap();
+        crate::database::schema::init_schema(&conn).unwrap();
+        let mut clock = HybridLogicalClock::new("device1".to_string());
+        let older = clock.clone();
+
+        // Lokal gelöscht: Zeile weg, nur der Delete im Op-Log
+        let delete = Operation::new(
+            "quail".to_string(),
+            "q1".to_string(),
+            &mut clock,
+            CrdtOp::Delete,
+        );
+        log_operation(&conn, &delete).unwrap();
+        log_operation(&conn, &delete).unwrap();
+
+        assert!(is_deleted(&conn, "quails", "q1").unwrap());
+        assert!(!is_newer(&conn, "quails", "q1", &older).unwrap());
+        assert!(!is_deleted(&conn, "quails", "q2").unwrap());
+        assert!(is_newer(&conn, "quails", "q2", &older).unwrap());
+    }
+
+    #[test]
+    fn test_lww_merge() {
+        let clock1 = HybridLogicalClock {
+            ts: 1000,
+            logical_counter: 0,
+            device_id: "device1".to_string(),
+        };
+
+        let clock2 = HybridLogicalClock {
+            ts: 2000,
+            logical_counter: 0,
+            device_id: "device2".to_string(),
+        };
+
+        let (winner, winner_clock) = lww_merge(&"old", &clock1, &"new", &clock2);
+
+        assert_eq!(winner, "new");
+        assert_eq!(winner_clock, clock2);
+    }
+}
@@ -0,0 +1,545 @@
+//! Zentrale Warteschlange für Foto-Downloads
+//!
+//! Früher startete jeder Aufruf von `photo_service::get_photo_with_download`
+//! einen eigenen Task; wer einen Bildschirm verließ, ließ die Downloads
+//! unsichtbar weiterlaufen. Jetzt landen alle Downloads hier:
+//!
+//! - sichtbare Fotos ([`DownloadPriority::Visible`]) kommen vor
+//!   Hintergrund-Downloads (z. B. Wiederholungen fehlgeschlagener Downloads),
+//! - höchstens [`MAX_CONCURRENT_DOWNLOADS`] Dateien werden gleichzeitig
+//!   geladen (auch die Vorab-Downloads aus `photo_prefetch` teilen sich
+//!   diese Plätze),
+//! - Downloads gehören zu einem [`DownloadScope`] (typisch: ein Bildschirm)
+//!   und werden abgebrochen, sobald alle anfragenden Scopes beendet sind.
+
+use crate::services::photo_checksum;
+use crate::services::photo_service;
+use rusqlite::params;
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, OnceLock};
+use tokio::sync::{Notify, Semaphore, SemaphorePermit};
+use tokio::time::{Duration, Instant};
+use uuid::Uuid;
+
+/// Maximal gleichzeitig laufende Downloads (app-weit)
+pub const MAX_CONCURRENT_DOWNLOADS: usize = 3;
+
+/// Reihenfolge in der Warteschlange; höhere Priorität zuerst
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum DownloadPriority {
+    #[default]
+    Background,
+    /// Gerade auf dem Bildschirm sichtbar
+    Visible,
+}
+
+/// Lebensdauer einer Gruppe von Downloads, z. B. eines Bildschirms
+///
+/// Nach [`DownloadScope::cancel`] werden wartende und laufende Downloads
+/// abgebrochen, sofern kein anderer Scope sie ebenfalls angefragt hat.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DownloadScope(u64);
+
+impl DownloadScope {
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> Self {
+        static NEXT: AtomicU64 = AtomicU64::new(1);
+        let scope = DownloadScope(NEXT.fetch_add(1, Ordering::Relaxed));
+        state().active_scopes.insert(scope.0);
+        scope
+    }
+
+    /// Bricht alle Downloads ab, die nur dieser Scope angefragt hat
+    pub fn cancel(self) {
+        let cancelled = state().cancel_scope(self.0);
+        if !cancelled.is_empty() {
+            log::debug!("{} Downloads abgebrochen", cancelled.len());
+            restore_status(&cancelled);
+        }
+    }
+}
+
+/// Priorität und Scope einer Download-Anfrage
+///
+/// Ohne Scope läuft der Download zu Ende, auch wenn niemand mehr wartet.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct DownloadRequest {
+    pub priority: DownloadPriority,
+    pub scope: Option<DownloadScope>,
+}
+
+impl DownloadRequest {
+    pub fn visible(scope: DownloadScope) -> Self {
+        DownloadRequest {
+            priority: DownloadPriority::Visible,
+            scope: Some(scope),
+        }
+    }
+
+    pub fn background(scope: DownloadScope) -> Self {
+        DownloadRequest {
+            priority: DownloadPriority::Background,
+            scope: Some(scope),
+        }
+    }
+}
+
+/// Eine herunterzuladende Datei
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct DownloadJob {
+    pub photo_uuid: Uuid,
+    /// Relativer Pfad (Original oder Thumbnail)
+    pub path: String,
+    pub retry_count: i32,
+    /// `sync_status` vor dem Download, wird bei Abbruch wiederhergestellt
+    pub previous_status: String,
+}
+
+/// Wer einen Download angefragt hat
+#[derive(Debug, Clone, PartialEq)]
+enum Owners {
+    /// Mindestens eine Anfrage ohne Scope
+    Unscoped,
+    Scopes(HashSet<u64>),
+}
+
+impl Owners {
+    fn of(scope: Option<DownloadScope>) -> Self {
+        match scope {
+            Some(scope) => Owners::Scopes(HashSet::from([scope.0])),
+            None => Owners::Unscoped,
+        }
+    }
+
+    fn add(&mut self, scope: Option<DownloadScope>) {
+        match (self, scope) {
+            (Owners::Scopes(scopes), Some(scope)) => {
+                scopes.insert(scope.0);
+            }
+            (owners, None) => *owners = Owners::Unscoped,
+            (Owners::Unscoped, Some(_)) => {}
+        }
+    }
+
+    /// Entfernt den Scope; `true`, wenn danach niemand mehr wartet
+    fn release(&mut self, scope: u64) -> bool {
+        match self {
+            Owners::Scopes(scopes) => scopes.remove(&scope) && scopes.is_empty(),
+            Owners::Unscoped => false,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct QueuedJob {
+    job: DownloadJob,
+    priority: DownloadPriority,
+    owners: Owners,
+    /// Backoff nach fehlgeschlagenen Versuchen
+    not_before: Instant,
+    /// Einreihungsreihenfolge bei gleicher Priorität
+    seq: u64,
+}
+
+#[derive(Debug)]
+struct RunningJob {
+    photo_uuid: Uuid,
+    owners: Owners,
+    cancel: Arc<Notify>,
+}
+
+#[derive(Debug, Default)]
+struct QueueState {
+    queued: Vec<QueuedJob>,
+    /// Laufende Downloads nach Pfad
+    running: HashMap<String, RunningJob>,
+    active_scopes: HashSet<u64>,
+    workers: usize,
+    next_seq: u64,
+}
+
+impl QueueState {
+    /// Reiht einen Job ein oder erweitert einen vorhandenen für dieselbe
+    /// Datei; `false`, wenn der Scope bereits beendet ist
+    fn push(&mut self, job: DownloadJob, request: DownloadRequest, not_before: Instant) -> bool {
+        if request
+            .scope
+            .is_some_and(|scope| !self.active_scopes.contains(&scope.0))
+        {
+            return false;
+        }
+        if self.promote(&job.path, request) {
+            return true;
+        }
+        self.next_seq += 1;
+        self.queued.push(QueuedJob {
+            job,
+            priority: request.priority,
+            owners: Owners::of(request.scope),
+            not_before,
+            seq: self.next_seq,
+        });
+        true
+    }
+
+    /// Hebt die Priorität eines wartenden Downloads an und merkt sich den
+    /// Scope; `false`, wenn die Datei weder wartet noch geladen wird
+    fn promote(&mut self, path: &str, request: DownloadRequest) -> bool {
+        if request
+            .scope
+            .is_some_and(|scope| !self.active_scopes.contains(&scope.0))
+        {
+            return self.running.contains_key(path)
+                || self.queued.iter().any(|q| q.job.path == path);
+        }
+        if let Some(running) = self.running.get_mut(path) {
+            running.owners.add(request.scope);
+            return true;
+        }
+        match self.queued.iter_mut().find(|q| q.job.path == path) {
+            Some(queued) => {
+                queued.priority = queued.priority.max(request.priority);
+                queued.owners.add(request.scope);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Nächster fälliger Job (höchste Priorität, dann älteste Anfrage);
+    /// sonst der Zeitpunkt des nächsten Backoff-Endes
+    fn pop_ready(&mut self, now: Instant) -> Result<QueuedJob, Option<Instant>> {
+        let next = self
+            .queued
+            .iter()
+            .enumerate()
+            .filter(|(_, q)| q.not_before <= now)
+            .max_by_key(|(_, q)| (q.priority, std::cmp::Reverse(q.seq)))
+            .map(|(index, _)| index);
+        match next {
+            Some(index) => Ok(self.queued.remove(index)),
+            None => Err(self.queued.iter().map(|q| q.not_before).min()),
+        }
+    }
+
+    /// Beendet einen Scope und gibt die nicht mehr benötigten, noch
+    /// wartenden Jobs zurück; laufende werden über ihr `cancel` gestoppt
+    fn cancel_scope(&mut self, scope: u64) -> Vec<DownloadJob> {
+        self.active_scopes.remove(&scope);
+        let mut cancelled = Vec::new();
+        self.queued.retain_mut(|q| {
+            if q.owners.release(scope) {
+                cancelled.push(q.job.clone());
+                false
+            } else {
+                true
+            }
+        });
+        for running in self.running.values_mut() {
+            if running.owners.release(scope) {
+                running.cancel.notify_one();
+            }
+        }
+        // Der Status gilt pro Foto: nur zurücksetzen, wenn keine andere
+        // Datei desselben Fotos mehr geladen wird
+        cancelled.retain(|job| !self.has_photo(&job.photo_uuid));
+        cancelled
+    }
+
+    fn has_photo(&self, photo_uuid: &Uuid) -> bool {
+        self.queued.iter().any(|q| q.job.photo_uuid == *photo_uuid)
+            || self.running.values().any(|r| r.photo_uuid == *photo_uuid)
+    }
+}
+
+fn state() -> MutexGuard<'static, QueueState> {
+    static STATE: OnceLock<Mutex<QueueState>> = OnceLock::new();
+    STATE
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+}
+
+/// Weckt Worker, die auf ein Backoff-Ende warten
+fn wake() -> &'static Notify {
+    static WAKE: OnceLock<Notify> = OnceLock::new();
+    WAKE.get_or_init(Notify::new)
+}
+
+fn slots() -> &'static Semaphore {
+    static SLOTS: Semaphore = Semaphore::const_new(MAX_CONCURRENT_DOWNLOADS);
+    &SLOTS
+}
+
+/// Wartet auf einen freien Download-Platz (für Downloads außerhalb der
+/// Warteschlange, z. B. das Vorabladen von Thumbnails)
+pub(crate) async fn download_slot() -> SemaphorePermit<'static> {
+    slots()
+        .acquire()
+        .await
+        .expect("Download-Semaphore wird nie geschlossen")
+}
+
+/// Wartezeit vor dem n-ten Wiederholungsversuch (Full Jitter)
+fn backoff(retry_count: i32) -> Duration {
+    if retry_count <= 0 {
+        return Duration::ZERO;
+    }
+    let base_delay = 60 * (1 << (retry_count - 1).min(4)); // 60s, 120s, 240s, 480s, 960s max
+    let max_delay = base_delay.min(300); // Cap at 5 minutes
+    Duration::from_secs(rand::random::<u64>() % (max_delay + 1))
+}
+
+/// Reiht einen Download ein; `false`, wenn der Scope der Anfrage schon
+/// beendet ist (dann wird nichts geladen)
+pub(crate) fn enqueue(job: DownloadJob, request: DownloadRequest) -> bool {
+    let delay = backoff(job.retry_count);
+    if !delay.is_zero() {
+        log::debug!(
+            "Photo download retry {} for {}: waiting {}s",
+            job.retry_count,
+            job.photo_uuid,
+            delay.as_secs()
+        );
+    }
+
+    let mut state = state();
+    if !state.push(job, request, Instant::now() + delay) {
+        return false;
+    }
+    while state.workers < MAX_CONCURRENT_DOWNLOADS && state.workers < state.queued.len() {
+        state.workers += 1;
+        tokio::spawn(worker());
+    }
+    drop(state);
+    wake().notify_waiters();
+    true
+}
+
+/// Siehe [`QueueState::promote`]
+pub(crate) fn promote(path: &str, request: DownloadRequest) -> bool {
+    state().promote(path, request)
+}
+
+/// Anzahl der wartenden oder laufenden Downloads
+pub fn pending_downloads() -> usize {
+    let state = state();
+    state.queued.len() + state.running.len()
+}
+
+async fn worker() {
+    loop {
+        let next = {
+            let mut state = state();
+            match state.pop_ready(Instant::now()) {
+                Ok(queued) => {
+                    let cancel = Arc::new(Notify::new());
+                    state.running.insert(
+                        queued.job.path.clone(),
+                        RunningJob {
+                            photo_uuid: queued.job.photo_uuid,
+                            owners: queued.owners,
+                            cancel: cancel.clone(),
+                        },
+                    );
+                    Ok((queued.job, cancel))
+                }
+                Err(Some(until)) => Err(until),
+                Err(None) => {
+                    state.workers -= 1;
+                    return;
+                }
+            }
+        };
+
+        let (job, cancel) = match next {
+            Ok(next) => next,
+            Err(until) => {
+                tokio::select! {
+                    _ = tokio::time::sleep_until(until) => {}
+                    _ = wake().notified() => {}
+                }
+                continue;
+            }
+        };
+
+        let download = async {
+            let _slot = download_slot().await;
+            photo_service::download_photo_from_remote(&job.photo_uuid, &job.path).await
+        };
+        let result = tokio::select! {
+            result = download => Some(result),
+            _ = cancel.notified() => None,
+        };
+
+        let still_loading = {
+            let mut state = state();
+            state.running.remove(&job.path);
+            state.has_photo(&job.photo_uuid)
+        };
+        match result {
+            Some(result) => finish(&job, result),
+            None if still_loading => log::debug!("Download abgebrochen: {}", job.path),
+            None => {
+                log::debug!("Download abgebrochen: {}", job.path);
+                restore_status(std::slice::from_ref(&job));
+            }
+        }
+    }
+}
+
+/// Schreibt das Ergebnis eines Downloads in die Datenbank
+fn finish(job: &DownloadJob, result: Result<bool, crate::error::AppError>) {
+    let Ok(conn) = crate::database::connection() else {
+        return;
+    };
+    match result {
+        Ok(false) => {
+            let _ = photo_checksum::mark_corrupt(
+                &conn,
+                &job.photo_uuid.to_string(),
+                "Prüfsumme des heruntergeladenen Originals stimmt nicht",
+            );
+        }
+        Ok(true) => {
+            log::info!("Successfully downloaded photo: {}", job.photo_uuid);
+            let _ = conn.execute(
+                "UPDATE photos SET sync_status = 'synced', retry_count = 0, sync_error = NULL, original_evicted = 0 WHERE uuid = ?1",
+                params![job.photo_uuid.to_string()],
+            );
+        }
+        Err(e) => {
+            log::error!("Failed to download photo {}: {}", job.photo_uuid, e);
+            let _ = conn.execute(
+                "UPDATE photos SET sync_status = 'download_failed', retry_count = ?1, sync_error = ?2 WHERE uuid = ?3",
+                params![job.retry_count + 1, e.to_string(), job.photo_uuid.to_string()],
+            );
+        }
+    }
+}
+
+/// Setzt `downloading` abgebrochener Downloads auf den vorherigen Status
+/// zurück, damit der nächste Aufruf den Download erneut anstößt
+fn restore_status(jobs: &[DownloadJob]) {
+    let Ok(conn) = crate::database::connection() else {
+        return;
+    };
+    for job in jobs {
+        let _ = conn.execute(
+            "UPDATE photos SET sync_status = ?1 WHERE uuid = ?2 AND sync_status = 'downloading'",
+            params![job.previous_status, job.photo_uuid.to_string()],
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn job(name: &str) -> DownloadJob {
+        DownloadJob {
+            photo_uuid: Uuid::new_v4(),
+            path: name.to_string(),
+            retry_count: 0,
+            previous_status: "synced".to_string(),
+        }
+    }
+
+    fn queue_with_scopes(scopes: &[u64]) -> QueueState {
+        QueueState {
+            active_scopes: scopes.iter().copied().collect(),
+            ..Default::default()
+        }
+    }
+
+    fn request(priority: DownloadPriority, scope: u64) -> DownloadRequest {
+        DownloadRequest {
+            priority,
+            scope: Some(DownloadScope(scope)),
+        }
+    }
+
+    #[test]
+    fn test_visible_downloads_come_first() {
+        let mut state = queue_with_scopes(&[1]);
+        let now = Instant::now();
+        state.push(job("a"), request(DownloadPriority::Background, 1), now);
+        state.push(job("b"), request(DownloadPriority::Visible, 1), now);
+        state.push(job("c"), request(DownloadPriority::Background, 1), now);
+        state.push(job("d"), request(DownloadPriority::Visible, 1), now);
+        // Ein Hintergrund-Download wird sichtbar
+        assert!(state.promote("c", request(DownloadPriority::Visible, 1)));
+
+        let order: Vec<String> = std::iter::from_fn(|| state.pop_ready(now).ok())
+            .map(|q| q.job.path)
+            .collect();
+        assert_eq!(order, vec!["b", "c", "d", "a"]);
+        assert!(!state.promote("a", request(DownloadPriority::Visible, 1)));
+    }
+
+    #[test]
+    fn test_backoff_delays_jobs() {
+        let mut state = queue_with_scopes(&[]);
+        let now = Instant::now();
+        let later = now + Duration::from_secs(60);
+        state.push(job("retry"), DownloadRequest::default(), later);
+        assert_eq!(state.pop_ready(now).err(), Some(Some(later)));
+        assert_eq!(state.pop_ready(later).unwrap().job.path, "retry");
+        assert_eq!(state.pop_ready(later).err(), Some(None));
+    }
+
+    #[test]
+    fn test_cancel_only_drops_jobs_nobody_else_wants() {
+        let mut state = queue_with_scopes(&[1, 2]);
+        let now = Instant::now();
+        state.push(job("only-1"), request(DownloadPriority::Visible, 1), now);
+        state.push(job("shared"), request(DownloadPriority::Visible, 1), now);
+        state.push(job("shared"), request(DownloadPriority::Background, 2), now);
+        state.push(job("unscoped"), request(DownloadPriority::Visible, 1), now);
+        state.promote("unscoped", DownloadRequest::default());
+        assert_eq!(state.queued.len(), 3);
+
+        let running = state.pop_ready(now).unwrap();
+        let cancel = Arc::new(Notify::new());
+        state.running.insert(
+            running.job.path.clone(),
+            RunningJob {
+                photo_uuid: running.job.photo_uuid,
+                owners: running.owners,
+                cancel: cancel.clone(),
+            },
+        );
+        assert_eq!(running.job.path, "only-1");
+
+        let cancelled = state.cancel_scope(1);
+        // Der laufende Download wird über `cancel` gestoppt
+        assert!(cancelled.is_empty());
+        let remaining: Vec<&str> = state.queued.iter().map(|q| q.job.path.as_str()).collect();
+        assert_eq!(remaining, vec!["shared", "unscoped"]);
+
+        // Ein beendeter Scope kann nichts mehr einreihen
+        assert!(!state.push(job("late"), request(DownloadPriority::Visible, 1), now));
+        let cancelled: Vec<String> = state.cancel_scope(2).into_iter().map(|j| j.path).collect();
+        assert_eq!(cancelled, vec!["shared"]);
+        assert_eq!(state.queued.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_cancel_notifies_running_download() {
+        let mut state = queue_with_scopes(&[7]);
+        let cancel = Arc::new(Notify::new());
+        state.running.insert(
+            "a".to_string(),
+            RunningJob {
+                photo_uuid: Uuid::new_v4(),
+                owners: Owners::of(Some(DownloadScope(7))),
+                cancel: cancel.clone(),
+            },
+        );
+        state.cancel_scope(7);
+        tokio::time::timeout(Duration::from_secs(1), cancel.notified())
+            .await
+            .expect("laufender Download wurde nicht abgebrochen");
+    }
+}
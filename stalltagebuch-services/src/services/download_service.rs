@@ -0,0 +1,1467 @@
+use crate::error::AppError;
+use crate::services::op_crypto::OpCipher;
+use crate::services::photo_checksum;
+use crate::services::photo_storage::{LocalPhotoStorage, PhotoStorage, RemotePhotoStorage};
+use crate::services::sync_backend::{ConfiguredBackend, RemoteEntry, SyncBackend};
+use crate::services::sync_progress::{self, SyncPhase};
+use crate::services::{
+    connectivity, crdt_service, op_compaction, sync_paths, sync_service, upload_service,
+};
+use rusqlite::Connection;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::io::Read;
+
+/// Downloads and merges operations from sync/ops/ directory
+///
+/// For every other device the latest snapshot (see [`op_compaction`]) is
+/// applied first; only op files newer than that snapshot are downloaded.
+/// Directories whose ETag matches the manifest are not listed again, so an
+/// idle sync costs only the two top-level listings.
+/// Returns the number of applied operations plus entities changed by snapshots.
+pub async fn download_and_merge_ops(conn: &Connection) -> Result<usize, AppError> {
+    let settings = sync_service::load_sync_settings(conn)?
+        .ok_or_else(|| AppError::NotFound("Sync settings not configured".to_string()))?;
+
+    if !settings.enabled {
+        return Err(AppError::Validation("Sync disabled".to_string()));
+    }
+    if settings.paused {
+        return Err(AppError::Validation("Sync paused".to_string()));
+    }
+
+    let client = ConfiguredBackend::from_settings(&settings)?;
+    let cipher = OpCipher::load(conn)?;
+    sync_progress::report(SyncPhase::Listing);
+
+    // Get local manifest from sync_checkpoint
+    let mut manifest = load_manifest(conn)?;
+
+    let base = settings.remote_path.trim_end_matches('/');
+    let ops_base_path = format!("{}/{}", base, sync_paths::OPS_DIR);
+    let own_device_id = upload_service::get_device_id(conn)?;
+
+    let control_base_path = format!("{}/{}", base, sync_paths::CONTROL_DIR);
+
+    // Devices with ops/ and devices that only have a snapshot left; skip
+    // those whose directories still carry the ETags we merged last time
+    let ops_folders = list_folders(&client, &ops_base_path).await?;
+    let control_folders = list_folders(&client, &control_base_path).await?;
+    let device_dirs: BTreeSet<String> = ops_folders
+        .keys()
+        .chain(control_folders.keys())
+        .filter(|device| {
+            dir_changed(
+                &manifest,
+                &format!("{}/{}", ops_base_path, device),
+                ops_folders.get(*device),
+            ) || dir_changed(
+                &manifest,
+                &format!("{}/{}", control_base_path, device),
+                control_folders.get(*device),
+            )
+        })
+        .cloned()
+        .collect();
+    if device_dirs.is_empty() {
+        log::debug!("No device directory changed since the last sync");
+    }
+
+    // Latest snapshot of every other device; our own state is newer anyway
+    let mut markers = HashMap::new();
+    for device_dir in device_dirs.iter().filter(|d| **d != own_device_id) {
+        if let Some(marker) = op_compaction::read_marker(&client, base, device_dir).await? {
+            markers.insert(device_dir.clone(), marker);
+        }
+    }
+
+    let mut all_ops = Vec::new();
+    let mut snapshot_entities = 0;
+
+    // Fresh install: start from the newest snapshot of any device and skip
+    // every op file its author had already merged
+    let mut merged_by_snapshot = BTreeMap::new();
+    if manifest.is_empty() {
+        if let Some(marker) = markers
+            .values()
+            .max_by(|a, b| a.snapshot_id.cmp(&b.snapshot_id))
+        {
+            let snapshot_path = format!("{}/{}", base, marker.path);
+            match fetch_snapshot(&client, &cipher, &snapshot_path).await {
+                Ok(mut snapshot) => {
+                    snapshot.entities.retain(|e| settings.syncs(&e.entity_type));
+                    snapshot_entities += op_compaction::apply_snapshot(conn, &snapshot)?;
+                    manifest.insert(snapshot_path, marker.snapshot_id.clone());
+                    merged_by_snapshot = snapshot.seen_ops;
+                    log::info!(
+                        "Bootstrapped from snapshot {} of device {}",
+                        marker.snapshot_id,
+                        snapshot.device_id
+                    );
+                }
+                Err(e @ AppError::PermissionDenied(_)) => return Err(e),
+                Err(e) => log::error!("Bootstrap snapshot {} skipped: {}", snapshot_path, e),
+            }
+        }
+    }
+
+    // Op files to download: (path, filename, etag)
+    let mut pending_files = Vec::new();
+
+    for device_dir in device_dirs {
+        let device_path = format!("{}/{}", ops_base_path, device_dir);
+        let merged_until = merged_by_snapshot.get(&device_dir);
+
+        // Bootstrap from the device's latest snapshot
+        let mut covered_by = None;
+        if let Some(marker) = markers.remove(&device_dir) {
+            let snapshot_path = format!("{}/{}", base, marker.path);
+            if manifest.get(&snapshot_path) == Some(&marker.snapshot_id) {
+                covered_by = Some(marker);
+            } else {
+                match fetch_snapshot(&client, &cipher, &snapshot_path).await {
+                    Ok(mut snapshot) => {
+                        snapshot.entities.retain(|e| settings.syncs(&e.entity_type));
+                        snapshot_entities += op_compaction::apply_snapshot(conn, &snapshot)?;
+                        manifest.insert(snapshot_path, marker.snapshot_id.clone());
+                        covered_by = Some(marker);
+                    }
+                    Err(e @ AppError::PermissionDenied(_)) => return Err(e),
+                    // Without the snapshot, read whatever ops are still there
+                    Err(e) => log::error!("Snapshot {} skipped: {}", snapshot_path, e),
+                }
+            }
+        }
+
+        // List all year-month directories for this device
+        let month_dirs = list_folders(&client, &device_path).await?;
+
+        for (month_dir, month_etag) in month_dirs {
+            let month_path = format!("{}/{}", device_path, month_dir);
+            if !dir_changed(&manifest, &month_path, Some(&month_etag)) {
+                continue;
+            }
+
+            // List all op files (NDJSON, optionally gzipped) in this month
+            let files = list_files_with_etags(&client, &month_path).await?;
+
+            for (filename, etag) in files {
+                // Already contained in the applied snapshot
+                if covered_by.as_ref().is_some_and(|m| m.covers(&filename)) {
+                    continue;
+                }
+
+                let file_path = format!("{}/{}", month_path, filename);
+
+                // Merged by the author of the bootstrap snapshot
+                if merged_until
+                    .is_some_and(|until| op_compaction::op_file_ulid(&filename) <= until.as_str())
+                {
+                    manifest.insert(file_path, etag);
+                    continue;
+                }
+
+                // Check if we already have this version
+                if manifest.get(&file_path) == Some(&etag) {
+                    continue; // Already downloaded
+                }
+
+                pending_files.push((file_path, filename, etag));
+            }
+
+            remember_dir(&mut manifest, &month_path, month_etag);
+        }
+
+        remember_dir(
+            &mut manifest,
+            &device_path,
+            ops_folders.get(&device_dir).cloned().flatten(),
+        );
+        remember_dir(
+            &mut manifest,
+            &format!("{}/{}", control_base_path, device_dir),
+            control_folders.get(&device_dir).cloned().flatten(),
+        );
+    }
+
+    let total_files = pending_files.len();
+    for (done, (file_path, filename, etag)) in pending_files.into_iter().enumerate() {
+        sync_progress::report(SyncPhase::DownloadingOps {
+            done,
+            total: total_files,
+        });
+
+        // Download and parse
+        let content_bytes = client.get(&file_path).await?;
+
+        let content_str = decode_op_file(&filename, cipher.open(content_bytes)?)?;
+
+        // Parse NDJSON
+        for line in content_str.lines() {
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let op: crdt_service::Operation = serde_json::from_str(line)
+                .map_err(|e| AppError::Other(format!("JSON parse failed: {}", e)))?;
+
+            all_ops.push(op);
+        }
+
+        // Update manifest
+        manifest.insert(file_path, etag);
+    }
+
+    // Abgeschaltete Entitätstypen nicht übernehmen
+    all_ops.retain(|op| settings.syncs(&op.entity_type));
+
+    // Sort operations by clock (deterministic total order)
+    all_ops.sort_by(|a, b| a.clock.cmp(&b.clock));
+
+    // Apply operations (multi-master CRDT only)
+    sync_progress::report(SyncPhase::Applying { ops: all_ops.len() });
+    let ops_applied = apply_operations(conn, &all_ops, &manifest)?;
+
+    // Debug: Anzahl Events nach Merge
+    if let Ok(count_events) = conn.query_row::<i64, _, _>(
+        "SELECT COUNT(*) FROM quail_events WHERE deleted = 0",
+        [],
+        |row| row.get(0),
+    ) {
+        log::info!("CRDT: Events im lokalen DB nach Merge: {}", count_events);
+    }
+
+    log::info!(
+        "Downloaded and merged {} operations and {} snapshot entities from {} files",
+        ops_applied,
+        snapshot_entities,
+        manifest.len()
+    );
+
+    Ok(ops_applied + snapshot_entities)
+}
+
+/// Lädt alle fehlenden Fotodateien (aus relative_path) herunter, sofern die
+/// Richtlinie große Übertragungen gerade erlaubt; sonst erst beim Öffnen
+/// eines Fotos
+///
+/// Returns the number of downloaded files.
+pub async fn download_photos(conn: &Connection) -> Result<usize, AppError> {
+    let settings = match sync_service::load_sync_settings(conn)? {
+        Some(s) if s.enabled && !s.paused && s.syncs("photo") => s,
+        _ => return Ok(0),
+    };
+
+    if !connectivity::originals_allowed_now(settings.originals_policy) {
+        log::info!(
+            "Original photos not downloaded (sync policy {:?})",
+            settings.originals_policy
+        );
+        return Ok(0);
+    }
+
+    let downloaded = download_missing_photos(
+        conn,
+        &LocalPhotoStorage::app_photos(),
+        &RemotePhotoStorage::from_settings(&settings)?.with_local_key(conn)?,
+    )
+    .await?;
+    log::info!("{} photos downloaded", downloaded);
+    Ok(downloaded)
+}
+
+/// Downloads a snapshot, decrypts it if needed and checks its signature
+async fn fetch_snapshot(
+    client: &impl SyncBackend,
+    cipher: &OpCipher,
+    path: &str,
+) -> Result<op_compaction::Snapshot, AppError> {
+    let data = cipher.open(client.get(path).await?)?;
+    op_compaction::verify_snapshot(&data, cipher)
+}
+
+/// Decodes an op file to NDJSON text, gunzipping `.ndjson.gz` files
+fn decode_op_file(filename: &str, data: Vec<u8>) -> Result<String, AppError> {
+    let data = if filename.ends_with(sync_paths::OP_FILE_GZ_EXT) {
+        let mut decoded = Vec::new();
+        flate2::read::GzDecoder::new(data.as_slice())
+            .read_to_end(&mut decoded)
+            .map_err(|e| AppError::Other(format!("Gzip decode failed: {}", e)))?;
+        decoded
+    } else {
+        data
+    };
+
+    String::from_utf8(data).map_err(|e| AppError::Other(format!("UTF-8 decode failed: {}", e)))
+}
+
+/// Lädt fehlende Fotodateien anhand von `relative_path` von `remote` nach `local`
+pub async fn download_missing_photos<L: PhotoStorage, R: PhotoStorage>(
+    conn: &Connection,
+    local: &L,
+    remote: &R,
+) -> Result<usize, AppError> {
+    let mut downloaded = 0usize;
+
+    // Alle Photos mit (relative_path oder path) ermitteln
+    let mut stmt = conn.prepare(
+        "SELECT uuid, COALESCE(relative_path, path) AS rel
+         FROM photos
+         WHERE deleted = 0 AND original_evicted = 0
+           AND (relative_path IS NOT NULL OR path IS NOT NULL)",
+    )?;
+
+    let rows = stmt.query_map([], |row| {
+        let uuid: String = row.get(0)?;
+        let rel: String = row.get(1)?;
+        Ok((uuid, rel))
+    })?;
+
+    let mut missing = Vec::new();
+    for row in rows {
+        let (uuid, rel) = row.map_err(|e| AppError::Other(format!("Row error: {:?}", e)))?;
+        if rel.trim().is_empty() {
+            continue;
+        }
+
+        if local.exists(&rel).await {
+            continue;
+        }
+        missing.push((uuid, rel));
+    }
+
+    let total = missing.len();
+    for (done, (uuid, rel)) in missing.into_iter().enumerate() {
+        sync_progress::report(SyncPhase::DownloadingPhotos { done, total });
+
+        // Remote Pfad: sync/photos/<uuid>.jpg (flache Struktur)
+        let photo_filename = if rel.contains('/') {
+            // Extrahiere Dateiname falls rel_path verschachtelt ist
+            rel.split('/').last().unwrap_or(&rel)
+        } else {
+            &rel
+        };
+
+        log::info!("Downloading missing photo {} -> {}", photo_filename, rel);
+
+        // Versuche Download
+        match remote.read(photo_filename).await {
+            Ok(bytes) => {
+                if !photo_checksum::matches_expected(conn, &uuid, &rel, &bytes)? {
+                    photo_checksum::mark_corrupt(
+                        conn,
+                        &uuid,
+                        "Prüfsumme des heruntergeladenen Originals stimmt nicht",
+                    )?;
+                    continue;
+                }
+                if let Err(e) = local.write(&rel, bytes).await {
+                    log::error!("Speichern Foto fehlgeschlagen {}: {:?}", rel, e);
+                    continue;
+                }
+                downloaded += 1;
+            }
+            Err(e) => {
+                log::debug!("Foto nicht gefunden remote {}: {:?}", photo_filename, e);
+            }
+        }
+    }
+
+    Ok(downloaded)
+}
+
+/// ETag des Ordners `sync/` auf dem Server
+///
+/// Nextcloud ändert es bei jeder Änderung darunter, ein Listing des
+/// Sync-Ordners genügt also als günstige Vorprüfung vor einem geplanten
+/// Sync. `None`, wenn der Ordner fehlt oder das Backend keine
+/// Verzeichnis-ETags kennt.
+pub async fn remote_tree_etag(conn: &Connection) -> Result<Option<String>, AppError> {
+    let settings = sync_service::load_sync_settings(conn)?
+        .ok_or_else(|| AppError::NotFound("Sync settings not configured".to_string()))?;
+    let client = ConfiguredBackend::from_settings(&settings)?;
+    let base = settings.remote_path.trim_end_matches('/');
+    let folders = list_folders(&client, base).await?;
+    Ok(folders.get(sync_paths::SYNC_BASE).cloned().flatten())
+}
+
+/// Manifest key of the remote tree ETag
+fn remote_tree_key(conn: &Connection) -> Result<String, AppError> {
+    let settings = sync_service::load_sync_settings(conn)?
+        .ok_or_else(|| AppError::NotFound("Sync settings not configured".to_string()))?;
+    Ok(dir_key(&format!(
+        "{}/{}",
+        settings.remote_path.trim_end_matches('/'),
+        sync_paths::SYNC_BASE
+    )))
+}
+
+/// Entspricht `etag` dem nach dem letzten vollständigen Sync gemerkten?
+pub fn remote_tree_unchanged(conn: &Connection, etag: &Option<String>) -> Result<bool, AppError> {
+    let Some(etag) = etag else {
+        return Ok(false);
+    };
+    let key = remote_tree_key(conn)?;
+    Ok(load_manifest(conn)?.get(&key) == Some(etag))
+}
+
+/// Merkt sich das vor einem erfolgreichen Sync gelesene ETag (siehe
+/// [`remote_tree_etag`]); Änderungen während des Syncs lösen so den
+/// nächsten aus
+pub fn remember_remote_tree(conn: &Connection, etag: &Option<String>) -> Result<(), AppError> {
+    let key = remote_tree_key(conn)?;
+    ensure_manifest_table(conn)?;
+    match etag {
+        Some(etag) => conn.execute(
+            "INSERT OR REPLACE INTO sync_manifest (path, etag) VALUES (?1, ?2)",
+            rusqlite::params![key, etag],
+        )?,
+        None => conn.execute("DELETE FROM sync_manifest WHERE path = ?1", [key])?,
+    };
+    Ok(())
+}
+
+/// Lists the subdirectories of `path` with their ETags
+/// Returns empty map if directory doesn't exist (404)
+async fn list_folders(
+    client: &impl SyncBackend,
+    path: &str,
+) -> Result<BTreeMap<String, Option<String>>, AppError> {
+    match client.list(path).await {
+        Ok(entries) => Ok(entries
+            .into_iter()
+            .filter_map(|e| match e {
+                RemoteEntry::Folder { name, etag } => Some((name, etag)),
+                RemoteEntry::File { .. } => None,
+            })
+            .collect()),
+        // Abgelehnte Zugangsdaten sind kein leeres Verzeichnis
+        Err(e @ AppError::PermissionDenied(_)) => Err(e),
+        Err(e) => {
+            // Directory doesn't exist yet (404) - return empty list
+            log::debug!("Directory {} doesn't exist or is empty: {:?}", path, e);
+            Ok(BTreeMap::new())
+        }
+    }
+}
+
+/// Manifest key of a directory (trailing `/`, never clashes with files)
+fn dir_key(path: &str) -> String {
+    format!("{}/", path)
+}
+
+/// Has anything below `path` changed since its ETag was remembered?
+///
+/// `listed` is the entry from the parent listing: `None` if the directory
+/// doesn't exist, `Some(None)` if the server sent no ETag.
+fn dir_changed(
+    manifest: &HashMap<String, String>,
+    path: &str,
+    listed: Option<&Option<String>>,
+) -> bool {
+    match listed {
+        None => false,
+        Some(Some(etag)) => manifest.get(&dir_key(path)) != Some(etag),
+        Some(None) => true,
+    }
+}
+
+/// Remembers the ETag of a fully merged directory
+fn remember_dir(manifest: &mut HashMap<String, String>, path: &str, etag: Option<String>) {
+    if let Some(etag) = etag {
+        manifest.insert(dir_key(path), etag);
+    }
+}
+
+/// Lists files with their ETags
+/// Returns empty Vec if directory doesn't exist (404)
+async fn list_files_with_etags(
+    client: &impl SyncBackend,
+    path: &str,
+) -> Result<Vec<(String, String)>, AppError> {
+    let list_result = match client.list(path).await {
+        Ok(result) => result,
+        Err(e @ AppError::PermissionDenied(_)) => return Err(e),
+        Err(e) => {
+            // Directory doesn't exist yet (404) - return empty list
+            log::debug!("Directory {} doesn't exist or is empty: {:?}", path, e);
+            return Ok(Vec::new());
+        }
+    };
+
+    let mut files = Vec::new();
+
+    for item in list_result {
+        if let RemoteEntry::File { name, etag } = item {
+            if sync_paths::is_op_file(&name) {
+                files.push((name, etag.unwrap_or_default()));
+            }
+        }
+    }
+
+    Ok(files)
+}
+
+/// Creates the sync_manifest table (path → last seen ETag) if it doesn't exist yet
+pub(crate) fn ensure_manifest_table(conn: &Connection) -> Result<(), AppError> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS sync_manifest (
+            path TEXT PRIMARY KEY,
+            etag TEXT NOT NULL
+        )",
+        [],
+    )?;
+    Ok(())
+}
+
+/// Forgets all seen remote files, so the next sync reads every op file again
+pub(crate) fn reset_manifest(conn: &Connection) -> Result<(), AppError> {
+    ensure_manifest_table(conn)?;
+    conn.execute("DELETE FROM sync_manifest", [])?;
+    Ok(())
+}
+
+/// Loads manifest from sync_checkpoint table
+fn load_manifest(conn: &Connection) -> Result<HashMap<String, String>, AppError> {
+    let mut manifest = HashMap::new();
+
+    ensure_manifest_table(conn)?;
+    let mut stmt = conn.prepare("SELECT path, etag FROM sync_manifest")?;
+
+    let rows = stmt.query_map([], |row| {
+        Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+    })?;
+
+    for row in rows {
+        let (path, etag) = row?;
+        manifest.insert(path, etag);
+    }
+
+    Ok(manifest)
+}
+
+/// Saves manifest to sync_manifest table (within the caller's transaction)
+fn save_manifest(conn: &Connection, manifest: &HashMap<String, String>) -> Result<(), AppError> {
+    ensure_manifest_table(conn)?;
+    let mut stmt =
+        conn.prepare_cached("INSERT OR REPLACE INTO sync_manifest (path, etag) VALUES (?1, ?2)")?;
+    for (path, etag) in manifest {
+        stmt.execute(rusqlite::params![path, etag])?;
+    }
+
+    Ok(())
+}
+
+/// Applies operations to local database and saves the manifest in the same
+/// transaction, so files are only marked as merged together with their ops
+fn apply_operations(
+    conn: &Connection,
+    ops: &[crdt_service::Operation],
+    manifest: &HashMap<String, String>,
+) -> Result<usize, AppError> {
+    let tx = conn.unchecked_transaction()?;
+    let applied = apply_operations_in(&tx, ops)?;
+    save_manifest(&tx, manifest)?;
+    tx.commit()?;
+
+    // Local changes from now on are ordered after everything merged here
+    if let Some(newest) = ops.iter().map(|op| &op.clock).max() {
+        crdt_service::observe_remote_clock(conn, newest)?;
+    }
+
+    Ok(applied)
+}
+
+/// `execute` through the statement cache: the merge runs the same few
+/// statements for every operation
+fn execute_cached(
+    conn: &Connection,
+    sql: &str,
+    params: impl rusqlite::Params,
+) -> rusqlite::Result<usize> {
+    conn.prepare_cached(sql)?.execute(params)
+}
+
+/// Applies operations within an open transaction and records them in
+/// `op_log`; operations already in `op_log` are skipped
+pub(crate) fn apply_operations_in(
+    tx: &rusqlite::Transaction,
+    ops: &[crdt_service::Operation],
+) -> Result<usize, AppError> {
+    let mut applied = 0;
+
+    for op in ops {
+        // Check if operation already applied (idempotency)
+        let already_applied: bool = tx
+            .prepare_cached("SELECT 1 FROM op_log WHERE op_id = ?1")?
+            .exists([&op.op_id])?;
+
+        if already_applied {
+            continue;
+        }
+
+        let Some(table) = crdt_service::entity_table(&op.entity_type) else {
+            log::warn!("Unknown entity type: {}", op.entity_type);
+            continue;
+        };
+
+        // LWW on the full HLC; counters are additive and always applied,
+        // deletes win over concurrent changes
+        let is_counter = matches!(op.op, crdt_service::CrdtOp::PnIncrement { .. });
+        let wins = match op.op {
+            _ if is_counter => true,
+            crdt_service::CrdtOp::Delete => !crdt_service::is_deleted(tx, table, &op.entity_id)?,
+            _ => crdt_service::is_newer(tx, table, &op.entity_id, &op.clock)?,
+        };
+        if wins {
+            match op.entity_type.as_str() {
+                "quail" => apply_quail_op(tx, op)?,
+                "event" => apply_event_op(tx, op)?,
+                "photo" => apply_photo_op(tx, op)?,
+                "coop" => apply_coop_op(tx, op)?,
+                _ => apply_egg_op(tx, op)?,
+            }
+            if !is_counter {
+                crdt_service::stamp_entity(tx, table, &op.entity_id, &op.clock)?;
+            }
+        }
+
+        crdt_service::log_operation(tx, op)?;
+
+        applied += 1;
+    }
+
+    Ok(applied)
+}
+
+/// Applies a quail operation (LWW-Register merge)
+///
+/// Like all `apply_*_op` functions only called for operations that win the
+/// HLC comparison in [`apply_operations_in`].
+fn apply_quail_op(
+    tx: &rusqlite::Transaction,
+    op: &crdt_service::Operation,
+) -> Result<(), AppError> {
+    use crate::services::crdt_service::CrdtOp;
+
+    match &op.op {
+        CrdtOp::LwwSet { field, value } => {
+            // Apply field update
+            match field.as_str() {
+                "name" => {
+                    let name = value
+                        .as_str()
+                        .ok_or_else(|| AppError::Validation("Invalid name value".to_string()))?;
+                    execute_cached(
+                        tx,
+                        // Upsert statt REPLACE: das Löschen der alten Zeile
+                        // würde per Cascade ihre Ereignisse mitnehmen
+                        "INSERT INTO quails (uuid, name, gender, rev, logical_clock, deleted)
+                         VALUES (?1, ?2, 'unknown', ?3, ?3, 0)
+                         ON CONFLICT(uuid) DO UPDATE SET name = excluded.name, rev = excluded.rev,
+                             logical_clock = excluded.logical_clock",
+                        rusqlite::params![&op.entity_id, name, op.clock.ts],
+                    )?;
+                }
+                "gender" => {
+                    let gender = value
+                        .as_str()
+                        .ok_or_else(|| AppError::Validation("Invalid gender value".to_string()))?;
+                    execute_cached(
+                        tx,
+                        "UPDATE quails SET gender = ?1, logical_clock = ?2 WHERE uuid = ?3",
+                        rusqlite::params![gender, op.clock.ts, &op.entity_id],
+                    )?;
+                }
+                "ring_color" => {
+                    let color = value.as_str();
+                    execute_cached(
+                        tx,
+                        "UPDATE quails SET ring_color = ?1, logical_clock = ?2 WHERE uuid = ?3",
+                        rusqlite::params![color, op.clock.ts, &op.entity_id],
+                    )?;
+                }
+                // Eltern und Stall ohne Fremdschlüssel: sie dürfen später ankommen
+                "mother_id" | "father_id" | "coop_id" => {
+                    let reference = value.as_str();
+                    let sql = match field.as_str() {
+                        "mother_id" => {
+                            "UPDATE quails SET mother_id = ?1, logical_clock = ?2 WHERE uuid = ?3"
+                        }
+                        "father_id" => {
+                            "UPDATE quails SET father_id = ?1, logical_clock = ?2 WHERE uuid = ?3"
+                        }
+                        _ => "UPDATE quails SET coop_id = ?1, logical_clock = ?2 WHERE uuid = ?3",
+                    };
+                    execute_cached(
+                        tx,
+                        sql,
+                        rusqlite::params![reference, op.clock.ts, &op.entity_id],
+                    )?;
+                }
+                "profile_photo" => {
+                    if let Some(photo_uuid) = value.as_str() {
+                        // Platzhalter, falls das Foto noch nicht da ist – wird
+                        // durch spätere Photo-Ops aufgefüllt
+                        if !crdt_service::ensure_photo(tx, photo_uuid)? {
+                            log::info!(
+                                "CRDT: Profilfoto {} wurde gelöscht, übersprungen",
+                                photo_uuid
+                            );
+                            return Ok(());
+                        }
+                        if let Err(e) = execute_cached(tx,
+                            "UPDATE quails SET profile_photo = ?1, logical_clock = ?2 WHERE uuid = ?3",
+                            rusqlite::params![photo_uuid, op.clock.ts, &op.entity_id],
+                        ) {
+                            log::error!("CRDT: Setzen von profile_photo {} fehlgeschlagen: {:?}", photo_uuid, e);
+                        }
+                    } else {
+                        execute_cached(tx,
+                            "UPDATE quails SET profile_photo = NULL, logical_clock = ?1 WHERE uuid = ?2",
+                            rusqlite::params![op.clock.ts, &op.entity_id],
+                        )?;
+                    }
+                }
+                _ => {
+                    log::warn!("Unknown quail field: {}", field);
+                }
+            }
+        }
+        CrdtOp::Delete => {
+            execute_cached(
+                tx,
+                "UPDATE quails SET deleted = 1, logical_clock = ?1 WHERE uuid = ?2",
+                rusqlite::params![op.clock.ts, &op.entity_id],
+            )?;
+            // Wie ON DELETE CASCADE beim lokalen Löschen
+            execute_cached(
+                tx,
+                "UPDATE quail_events SET deleted = 1 WHERE quail_id = ?1 AND deleted = 0",
+                rusqlite::params![&op.entity_id],
+            )?;
+        }
+        _ => {} // Other ops not applicable to quails
+    }
+
+    Ok(())
+}
+
+/// Applies an event operation
+fn apply_event_op(
+    tx: &rusqlite::Transaction,
+    op: &crdt_service::Operation,
+) -> Result<(), AppError> {
+    use crate::services::crdt_service::CrdtOp;
+
+    match &op.op {
+        CrdtOp::LwwSet { field, value } => {
+            match field.as_str() {
+                "quail_id" => {
+                    let quail_id = value
+                        .as_str()
+                        .ok_or_else(|| AppError::Validation("Invalid quail_id".to_string()))?;
+
+                    // Ereignisse gelöschter Wachteln wären lokal per Cascade
+                    // mitgelöscht worden
+                    if crdt_service::is_deleted(tx, "quails", quail_id)? {
+                        log::info!(
+                            "CRDT: Event {} gehört zu gelöschter Wachtel {}, übersprungen",
+                            &op.entity_id,
+                            quail_id
+                        );
+                        return Ok(());
+                    }
+
+                    // Sicherstellen, dass die referenzierte Wachtel existiert (Platzhalter bei Out-of-Order Merge)
+                    crdt_service::ensure_quail(tx, quail_id)?;
+
+                    execute_cached(tx,
+                        "INSERT INTO quail_events (uuid, quail_id, event_type, event_date, rev, logical_clock, deleted)
+                         VALUES (?1, ?2, 'alive', date('now'), ?3, ?3, 0)
+                         ON CONFLICT(uuid) DO UPDATE SET quail_id = excluded.quail_id, rev = excluded.rev,
+                             logical_clock = excluded.logical_clock",
+                        rusqlite::params![&op.entity_id, quail_id, op.clock.ts],
+                    )?;
+                    // 'alive'/heute ersetzen die übrigen Felder, die mit
+                    // aufsteigenden Uhren in derselben Op-Datei folgen. Auf das
+                    // Ereignis wartende Fotos bekommen jetzt ihren Verweis.
+                    crdt_service::resolve_deferred_references(tx, "quail_events", &op.entity_id)?;
+                }
+                "event_type" | "type" => {
+                    let event_type = value
+                        .as_str()
+                        .ok_or_else(|| AppError::Validation("Invalid event_type".to_string()))?;
+                    execute_cached(tx,
+                        "UPDATE quail_events SET event_type = ?1, logical_clock = ?2 WHERE uuid = ?3",
+                        rusqlite::params![event_type, op.clock.ts, &op.entity_id],
+                    )?;
+                }
+                "event_date" | "date" => {
+                    let event_date = value
+                        .as_str()
+                        .ok_or_else(|| AppError::Validation("Invalid event_date".to_string()))?;
+                    execute_cached(tx,
+                        "UPDATE quail_events SET event_date = ?1, logical_clock = ?2 WHERE uuid = ?3",
+                        rusqlite::params![event_date, op.clock.ts, &op.entity_id],
+                    )?;
+                }
+                "notes" => {
+                    let notes = value.as_str();
+                    execute_cached(
+                        tx,
+                        "UPDATE quail_events SET notes = ?1, logical_clock = ?2 WHERE uuid = ?3",
+                        rusqlite::params![notes, op.clock.ts, &op.entity_id],
+                    )?;
+                }
+                // Angaben zur Behandlung
+                "medication" | "dose" => {
+                    let text = value.as_str();
+                    let sql = match field.as_str() {
+                        "medication" => {
+                            "UPDATE quail_events SET medication = ?1, logical_clock = ?2 WHERE uuid = ?3"
+                        }
+                        _ => "UPDATE quail_events SET dose = ?1, logical_clock = ?2 WHERE uuid = ?3",
+                    };
+                    execute_cached(tx, sql, rusqlite::params![text, op.clock.ts, &op.entity_id])?;
+                }
+                "duration_days" | "egg_withdrawal_days" | "meat_withdrawal_days" => {
+                    let days = value.as_i64();
+                    let sql = match field.as_str() {
+                        "duration_days" => {
+                            "UPDATE quail_events SET duration_days = ?1, logical_clock = ?2 WHERE uuid = ?3"
+                        }
+                        "egg_withdrawal_days" => {
+                            "UPDATE quail_events SET egg_withdrawal_days = ?1, logical_clock = ?2 WHERE uuid = ?3"
+                        }
+                        _ => {
+                            "UPDATE quail_events SET meat_withdrawal_days = ?1, logical_clock = ?2 WHERE uuid = ?3"
+                        }
+                    };
+                    execute_cached(tx, sql, rusqlite::params![days, op.clock.ts, &op.entity_id])?;
+                }
+                _ => {
+                    log::warn!("Unknown event field: {}", field);
+                }
+            }
+        }
+        CrdtOp::Delete => {
+            execute_cached(
+                tx,
+                "UPDATE quail_events SET deleted = 1, logical_clock = ?1 WHERE uuid = ?2",
+                rusqlite::params![op.clock.ts, &op.entity_id],
+            )?;
+            // Auf dieses Ereignis wartende Verweise verwerfen
+            crdt_service::resolve_deferred_references(tx, "quail_events", &op.entity_id)?;
+        }
+        _ => {}
+    }
+
+    Ok(())
+}
+
+/// Applies a photo operation
+fn apply_photo_op(
+    tx: &rusqlite::Transaction,
+    op: &crdt_service::Operation,
+) -> Result<(), AppError> {
+    use crate::services::crdt_service::CrdtOp;
+
+    match &op.op {
+        CrdtOp::LwwSet { field, value } => {
+            match field.as_str() {
+                "quail_id" => {
+                    // Verweis auf gelöschte Wachtel wie ON DELETE SET NULL
+                    let quail_id = match value.as_str() {
+                        Some(quail_id) if crdt_service::ensure_quail(tx, quail_id)? => {
+                            Some(quail_id)
+                        }
+                        _ => None,
+                    };
+                    // Stelle sicher dass Foto-Eintrag existiert
+                    let exists: bool = tx
+                        .query_row(
+                            "SELECT 1 FROM photos WHERE uuid = ?1",
+                            rusqlite::params![&op.entity_id],
+                            |_| Ok(true),
+                        )
+                        .unwrap_or(false);
+
+                    if !exists {
+                        // Lege Platzhalter an
+                        execute_cached(tx,
+                            "INSERT INTO photos (uuid, quail_id, event_id, path, relative_path, thumbnail_path, rev, logical_clock, deleted)
+                             VALUES (?1, ?2, NULL, '', NULL, NULL, 0, ?3, 0)",
+                            rusqlite::params![&op.entity_id, quail_id, op.clock.ts],
+                        )?;
+                    } else {
+                        execute_cached(
+                            tx,
+                            "UPDATE photos SET quail_id = ?1, logical_clock = ?2 WHERE uuid = ?3",
+                            rusqlite::params![quail_id, op.clock.ts, &op.entity_id],
+                        )?;
+                    }
+                }
+                "event_id" => {
+                    // Fehlt das Ereignis noch, bleibt der Verweis geparkt
+                    // (kein Platzhalter), gelöschte Ereignisse wie ON DELETE SET NULL
+                    crdt_service::drop_deferred_references(
+                        tx,
+                        "photos",
+                        &op.entity_id,
+                        Some("event_id"),
+                    )?;
+                    let event_id = match value.as_str() {
+                        Some(event_id)
+                            if crdt_service::is_deleted(tx, "quail_events", event_id)? =>
+                        {
+                            None
+                        }
+                        Some(event_id)
+                            if !crdt_service::row_exists(tx, "quail_events", event_id)? =>
+                        {
+                            crdt_service::defer_reference(
+                                tx,
+                                "photos",
+                                &op.entity_id,
+                                "event_id",
+                                event_id,
+                            )?;
+                            None
+                        }
+                        event_id => event_id,
+                    };
+                    // Stelle sicher dass Foto-Eintrag existiert
+                    let exists: bool = tx
+                        .query_row(
+                            "SELECT 1 FROM photos WHERE uuid = ?1",
+                            rusqlite::params![&op.entity_id],
+                            |_| Ok(true),
+                        )
+                        .unwrap_or(false);
+
+                    if !exists {
+                        // Lege Platzhalter an
+                        execute_cached(tx,
+                            "INSERT INTO photos (uuid, quail_id, event_id, path, relative_path, thumbnail_path, rev, logical_clock, deleted)
+                             VALUES (?1, NULL, ?2, '', NULL, NULL, 0, ?3, 0)",
+                            rusqlite::params![&op.entity_id, event_id, op.clock.ts],
+                        )?;
+                    } else {
+                        execute_cached(
+                            tx,
+                            "UPDATE photos SET event_id = ?1, logical_clock = ?2 WHERE uuid = ?3",
+                            rusqlite::params![event_id, op.clock.ts, &op.entity_id],
+                        )?;
+                    }
+                }
+                "relative_path" | "relative" => {
+                    let path = value
+                        .as_str()
+                        .ok_or_else(|| AppError::Validation("Invalid path".to_string()))?;
+                    // Lege Eintrag an falls nicht vorhanden (ohne quail_id/event_id zu überschreiben)
+                    execute_cached(tx,
+                        "INSERT OR IGNORE INTO photos (uuid, path, relative_path, quail_id, event_id, thumbnail_path, rev, logical_clock, deleted)
+                         VALUES (?1, '', ?2, NULL, NULL, NULL, 0, ?3, 0)",
+                        rusqlite::params![&op.entity_id, path, op.clock.ts],
+                    )?;
+                    // Update nur relative_path und logical_clock, behalte quail_id/event_id
+                    execute_cached(
+                        tx,
+                        "UPDATE photos SET relative_path = ?1, logical_clock = ?2 WHERE uuid = ?3",
+                        rusqlite::params![path, op.clock.ts, &op.entity_id],
+                    )?;
+                }
+                "relative_thumb" | "thumb" => {
+                    let thumb = value.as_str();
+                    // Lege Eintrag an falls nicht vorhanden
+                    execute_cached(tx,
+                        "INSERT OR IGNORE INTO photos (uuid, path, relative_path, quail_id, event_id, thumbnail_path, rev, logical_clock, deleted)
+                         VALUES (?1, '', NULL, NULL, NULL, ?2, 0, ?3, 0)",
+                        rusqlite::params![&op.entity_id, thumb, op.clock.ts],
+                    )?;
+                    // Update thumbnail_path
+                    execute_cached(
+                        tx,
+                        "UPDATE photos SET thumbnail_path = ?1, logical_clock = ?2 WHERE uuid = ?3",
+                        rusqlite::params![thumb, op.clock.ts, &op.entity_id],
+                    )?;
+                }
+                "caption" => {
+                    // Nur bestehende Fotos; die Beschriftung kommt nach dem Anlegen
+                    execute_cached(
+                        tx,
+                        "UPDATE photos SET caption = ?1, logical_clock = ?2 WHERE uuid = ?3",
+                        rusqlite::params![value.as_str(), op.clock.ts, &op.entity_id],
+                    )?;
+                }
+                "trashed_at" => {
+                    // null = wiederhergestellt
+                    execute_cached(
+                        tx,
+                        "UPDATE photos SET trashed_at = ?1, logical_clock = ?2 WHERE uuid = ?3",
+                        rusqlite::params![value.as_i64(), op.clock.ts, &op.entity_id],
+                    )?;
+                }
+                "checksum" => {
+                    execute_cached(
+                        tx,
+                        "UPDATE photos SET checksum = ?1, logical_clock = ?2 WHERE uuid = ?3",
+                        rusqlite::params![value.as_str(), op.clock.ts, &op.entity_id],
+                    )?;
+                }
+                "position" => {
+                    execute_cached(
+                        tx,
+                        "UPDATE photos SET position = ?1, logical_clock = ?2 WHERE uuid = ?3",
+                        rusqlite::params![value.as_i64(), op.clock.ts, &op.entity_id],
+                    )?;
+                }
+                "tags" => {
+                    let tags = if value.is_array() {
+                        value.to_string()
+                    } else {
+                        "[]".to_string()
+                    };
+                    execute_cached(
+                        tx,
+                        "UPDATE photos SET tags = ?1, logical_clock = ?2 WHERE uuid = ?3",
+                        rusqlite::params![tags, op.clock.ts, &op.entity_id],
+                    )?;
+                }
+                _ => {
+                    log::warn!("Unknown photo field: {}", field);
+                }
+            }
+        }
+        CrdtOp::Delete => {
+            execute_cached(
+                tx,
+                "UPDATE photos SET deleted = 1, logical_clock = ?1 WHERE uuid = ?2",
+                rusqlite::params![op.clock.ts, &op.entity_id],
+            )?;
+        }
+        _ => {}
+    }
+
+    Ok(())
+}
+
+/// Applies a coop operation
+fn apply_coop_op(tx: &rusqlite::Transaction, op: &crdt_service::Operation) -> Result<(), AppError> {
+    use crate::services::crdt_service::CrdtOp;
+
+    match &op.op {
+        CrdtOp::LwwSet { field, value } => match field.as_str() {
+            "name" => {
+                let name = value
+                    .as_str()
+                    .ok_or_else(|| AppError::Validation("Invalid name value".to_string()))?;
+                execute_cached(
+                    tx,
+                    "INSERT INTO coops (uuid, name, rev, logical_clock, deleted)
+                     VALUES (?1, ?2, ?3, ?3, 0)
+                     ON CONFLICT(uuid) DO UPDATE SET name = excluded.name, rev = excluded.rev,
+                         logical_clock = excluded.logical_clock",
+                    rusqlite::params![&op.entity_id, name, op.clock.ts],
+                )?;
+            }
+            "notes" => {
+                execute_cached(
+                    tx,
+                    "UPDATE coops SET notes = ?1, logical_clock = ?2 WHERE uuid = ?3",
+                    rusqlite::params![value.as_str(), op.clock.ts, &op.entity_id],
+                )?;
+            }
+            _ => {
+                log::warn!("Unknown coop field: {}", field);
+            }
+        },
+        // Zugeordnete Wachteln und Eier-Einträge behalten den Verweis und
+        // gelten danach als ohne Stall
+        CrdtOp::Delete => {
+            execute_cached(
+                tx,
+                "UPDATE coops SET deleted = 1, logical_clock = ?1 WHERE uuid = ?2",
+                rusqlite::params![op.clock.ts, &op.entity_id],
+            )?;
+        }
+        _ => {}
+    }
+
+    Ok(())
+}
+
+/// Applies an egg operation
+fn apply_egg_op(tx: &rusqlite::Transaction, op: &crdt_service::Operation) -> Result<(), AppError> {
+    use crate::services::crdt_service::CrdtOp;
+
+    match &op.op {
+        CrdtOp::LwwSet { field, value } => {
+            match field.as_str() {
+                "date" | "record_date" => {
+                    let date = value
+                        .as_str()
+                        .ok_or_else(|| AppError::Validation("Invalid date".to_string()))?;
+                    // Erst sicherstellen dass Eintrag existiert
+                    execute_cached(tx,
+                        "INSERT OR IGNORE INTO egg_records (uuid, record_date, total_eggs, notes, rev, logical_clock, deleted)
+                         VALUES (?1, ?2, 0, NULL, ?3, ?3, 0)",
+                        rusqlite::params![&op.entity_id, date, op.clock.ts],
+                    )?;
+                    // Dann updaten
+                    execute_cached(
+                        tx,
+                        "UPDATE egg_records SET record_date = ?1, logical_clock = ?2 
+                         WHERE uuid = ?3",
+                        rusqlite::params![date, op.clock.ts, &op.entity_id],
+                    )?;
+                }
+                "count" | "total_eggs" => {
+                    let count = value
+                        .as_i64()
+                        .ok_or_else(|| AppError::Validation("Invalid count".to_string()))?
+                        as i32;
+                    // Erst sicherstellen dass Eintrag existiert (mit Dummy-Datum wenn nötig)
+                    execute_cached(tx,
+                        "INSERT OR IGNORE INTO egg_records (uuid, record_date, total_eggs, notes, rev, logical_clock, deleted)
+                         VALUES (?1, date('now'), ?2, NULL, ?3, ?3, 0)",
+                        rusqlite::params![&op.entity_id, count, op.clock.ts],
+                    )?;
+                    // Dann updaten
+                    execute_cached(
+                        tx,
+                        "UPDATE egg_records SET total_eggs = ?1, logical_clock = ?2 
+                         WHERE uuid = ?3",
+                        rusqlite::params![count, op.clock.ts, &op.entity_id],
+                    )?;
+                }
+                "notes" => {
+                    execute_cached(
+                        tx,
+                        "UPDATE egg_records SET notes = ?1, logical_clock = ?2 WHERE uuid = ?3",
+                        rusqlite::params![value.as_str(), op.clock.ts, &op.entity_id],
+                    )?;
+                }
+                "coop_id" => {
+                    execute_cached(
+                        tx,
+                        "UPDATE egg_records SET coop_id = ?1, logical_clock = ?2 WHERE uuid = ?3",
+                        rusqlite::params![value.as_str(), op.clock.ts, &op.entity_id],
+                    )?;
+                }
+                _ => {
+                    log::warn!("Unknown egg field: {}", field);
+                }
+            }
+        }
+        CrdtOp::PnIncrement { field, delta } => {
+            if field == "count" || field == "total_eggs" {
+                // Stelle sicher dass Eintrag existiert
+                execute_cached(tx,
+                    "INSERT OR IGNORE INTO egg_records (uuid, record_date, total_eggs, notes, rev, logical_clock, deleted)
+                     VALUES (?1, date('now'), 0, NULL, ?2, ?2, 0)",
+                    rusqlite::params![&op.entity_id, op.clock.ts],
+                )?;
+                // Increment
+                execute_cached(tx,
+                    "UPDATE egg_records SET total_eggs = total_eggs + ?1, logical_clock = ?2 WHERE uuid = ?3",
+                    rusqlite::params![delta, op.clock.ts, &op.entity_id],
+                )?;
+            }
+        }
+        CrdtOp::Delete => {
+            execute_cached(
+                tx,
+                "UPDATE egg_records SET deleted = 1, logical_clock = ?1 WHERE uuid = ?2",
+                rusqlite::params![op.clock.ts, &op.entity_id],
+            )?;
+        }
+        _ => {}
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_manifest_roundtrip() {
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+
+        let mut manifest = HashMap::new();
+        manifest.insert(
+            "sync/ops/device1/202412/01JGTEST.ndjson".to_string(),
+            "\"abc123\"".to_string(),
+        );
+        manifest.insert(
+            "sync/ops/device2/202412/01JGTEST2.ndjson".to_string(),
+            "\"def456\"".to_string(),
+        );
+
+        save_manifest(&conn, &manifest).unwrap();
+        let loaded = load_manifest(&conn).unwrap();
+
+        assert_eq!(loaded.len(), 2);
+        assert_eq!(
+            loaded.get("sync/ops/device1/202412/01JGTEST.ndjson"),
+            Some(&"\"abc123\"".to_string())
+        );
+    }
+
+    #[test]
+    fn test_gzipped_op_file_is_decoded() {
+        let ndjson = "{\"a\":1}\n{\"b\":2}\n";
+        let gzipped = upload_service::compress_ops(ndjson).unwrap();
+
+        assert_eq!(
+            decode_op_file("01JGTEST.ndjson.gz", gzipped).unwrap(),
+            ndjson
+        );
+        assert_eq!(
+            decode_op_file("01JGTEST.ndjson", ndjson.as_bytes().to_vec()).unwrap(),
+            ndjson
+        );
+    }
+
+    fn op(
+        entity_type: &str,
+        entity_id: &str,
+        ts: i64,
+        field: &str,
+        value: &str,
+    ) -> crdt_service::Operation {
+        crdt_service::Operation {
+            op_id: ulid::Ulid::new().to_string(),
+            entity_type: entity_type.to_string(),
+            entity_id: entity_id.to_string(),
+            clock: crdt_service::HybridLogicalClock {
+                ts,
+                logical_counter: 0,
+                device_id: "remote".to_string(),
+            },
+            op: crdt_service::CrdtOp::LwwSet {
+                field: field.to_string(),
+                value: value.into(),
+            },
+        }
+    }
+
+    #[test]
+    fn test_out_of_order_merge_keeps_foreign_keys() {
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        crate::database::schema::init_schema(&conn).unwrap();
+
+        // Foto eines Ereignisses kommt vor Ereignis und Wachtel an: kein
+        // sichtbares Platzhalter-Ereignis, der Verweis wartet
+        apply_operations(
+            &conn,
+            &[
+                op("photo", "p1", 3_000, "event_id", "e1"),
+                op("photo", "p2", 3_001, "event_id", "e2"),
+            ],
+            &HashMap::new(),
+        )
+        .unwrap();
+        let count = |sql: &str| -> i64 { conn.query_row(sql, [], |row| row.get(0)).unwrap() };
+        assert_eq!(count("SELECT COUNT(*) FROM quail_events"), 0);
+        assert_eq!(count("SELECT COUNT(*) FROM quails"), 0);
+        assert_eq!(
+            count("SELECT COUNT(*) FROM photos WHERE event_id IS NOT NULL"),
+            0
+        );
+
+        // Ältere Ops des Ereignisses füllen den Verweis auf, das gelöschte
+        // Ereignis verwirft ihn
+        apply_operations(
+            &conn,
+            &[
+                op("event", "e1", 2_000, "quail_id", "q1"),
+                op("event", "e1", 2_001, "event_date", "2025-03-01"),
+                op("quail", "q1", 1_000, "name", "Greta"),
+                crdt_service::Operation {
+                    op: crdt_service::CrdtOp::Delete,
+                    ..op("event", "e2", 2_500, "quail_id", "q1")
+                },
+            ],
+            &HashMap::new(),
+        )
+        .unwrap();
+        let (event_id, quail_id, name, date): (String, String, String, String) = conn
+            .query_row(
+                "SELECT p.event_id, e.quail_id, q.name, e.event_date
+                 FROM photos p JOIN quail_events e ON e.uuid = p.event_id
+                 JOIN quails q ON q.uuid = e.quail_id",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
+            )
+            .unwrap();
+        assert_eq!(
+            (
+                event_id.as_str(),
+                quail_id.as_str(),
+                name.as_str(),
+                date.as_str()
+            ),
+            ("e1", "q1", "Greta", "2025-03-01")
+        );
+        assert_eq!(count("SELECT COUNT(*) FROM deferred_references"), 0);
+        assert_eq!(count("SELECT COUNT(*) FROM quails"), 1);
+        assert!(crate::services::integrity::check(&conn)
+            .unwrap()
+            .foreign_key_violations
+            .is_empty());
+    }
+
+    #[test]
+    fn test_coop_ops_and_egg_records_per_coop() {
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        crate::database::schema::init_schema(&conn).unwrap();
+        conn.execute(
+            "INSERT INTO egg_records (uuid, record_date, total_eggs) VALUES ('e0', '2025-03-01', 5)",
+            [],
+        )
+        .unwrap();
+
+        // Eintrag desselben Tages für einen Stall: `coop_id` kommt erst nach
+        // dem Datum und darf den Eintrag des ganzen Bestands nicht verdrängen
+        apply_operations(
+            &conn,
+            &[
+                op("coop", "c1", 1_000, "name", "Voliere"),
+                op("quail", "q1", 1_001, "name", "Greta"),
+                op("quail", "q1", 1_002, "coop_id", "c1"),
+                op("egg", "e1", 1_003, "record_date", "2025-03-01"),
+                op("egg", "e1", 1_004, "coop_id", "c1"),
+            ],
+            &HashMap::new(),
+        )
+        .unwrap();
+
+        let name: String = conn
+            .query_row("SELECT name FROM coops WHERE uuid = 'c1'", [], |row| {
+                row.get(0)
+            })
+            .unwrap();
+        assert_eq!(name, "Voliere");
+        let quail_coop: String = conn
+            .query_row("SELECT coop_id FROM quails WHERE uuid = 'q1'", [], |row| {
+                row.get(0)
+            })
+            .unwrap();
+        assert_eq!(quail_coop, "c1");
+        let records: Vec<(String, Option<String>)> = conn
+            .prepare("SELECT uuid, coop_id FROM egg_records WHERE record_date = '2025-03-01' ORDER BY uuid")
+            .unwrap()
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(
+            records,
+            [
+                ("e0".to_string(), None),
+                ("e1".to_string(), Some("c1".to_string()))
+            ]
+        );
+    }
+
+    #[test]
+    fn test_treatment_ops() {
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        crate::database::schema::init_schema(&conn).unwrap();
+
+        let mut duration = op("event", "ev1", 1_005, "duration_days", "");
+        let mut egg_days = op("event", "ev1", 1_006, "egg_withdrawal_days", "");
+        for (op, days) in [(&mut duration, 5), (&mut egg_days, 7)] {
+            if let crdt_service::CrdtOp::LwwSet { value, .. } = &mut op.op {
+                *value = serde_json::Value::from(days);
+            }
+        }
+        apply_operations(
+            &conn,
+            &[
+                op("quail", "q1", 1_000, "name", "Greta"),
+                op("event", "ev1", 1_001, "quail_id", "q1"),
+                op("event", "ev1", 1_002, "event_type", "treatment"),
+                op("event", "ev1", 1_003, "event_date", "2025-05-01"),
+                op("event", "ev1", 1_004, "medication", "Baytril"),
+                duration,
+                egg_days,
+            ],
+            &HashMap::new(),
+        )
+        .unwrap();
+
+        let row: (String, i64, i64, Option<i64>) = conn
+            .query_row(
+                "SELECT medication, duration_days, egg_withdrawal_days, meat_withdrawal_days
+                 FROM quail_events WHERE uuid = 'ev1'",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
+            )
+            .unwrap();
+        assert_eq!(row, ("Baytril".to_string(), 5, 7, None));
+    }
+
+    #[test]
+    fn test_parent_ops_before_parent_exists() {
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        crate::database::schema::init_schema(&conn).unwrap();
+
+        // Die Mutter kommt erst mit einer späteren Datei an: kein Platzhalter
+        apply_operations(
+            &conn,
+            &[
+                op("quail", "q1", 1_000, "name", "Küken"),
+                op("quail", "q1", 1_001, "mother_id", "q2"),
+            ],
+            &HashMap::new(),
+        )
+        .unwrap();
+        let (mother, quails): (String, i64) = conn
+            .query_row(
+                "SELECT mother_id, (SELECT COUNT(*) FROM quails) FROM quails WHERE uuid = 'q1'",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .unwrap();
+        assert_eq!((mother.as_str(), quails), ("q2", 1));
+    }
+
+    #[test]
+    fn test_failed_merge_keeps_files_unmerged() {
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        crate::database::schema::init_schema(&conn).unwrap();
+        let manifest = HashMap::from([(
+            "sync/ops/device1/202501/01JGTEST.ndjson".to_string(),
+            "\"abc\"".to_string(),
+        )]);
+
+        // Zweite Operation ist ungültig: nichts übernehmen, Datei nicht merken
+        let mut invalid = op("quail", "q2", 2_000, "name", "x");
+        invalid.op = crdt_service::CrdtOp::LwwSet {
+            field: "name".to_string(),
+            value: serde_json::Value::Null,
+        };
+        assert!(apply_operations(
+            &conn,
+            &[op("quail", "q1", 1_000, "name", "Greta"), invalid],
+            &manifest,
+        )
+        .is_err());
+        assert!(load_manifest(&conn).unwrap().is_empty());
+        assert!(crdt_service::load_entity_clock(&conn, "quails", "q1")
+            .unwrap()
+            .is_none());
+
+        apply_operations(
+            &conn,
+            &[op("quail", "q1", 1_000, "name", "Greta")],
+            &manifest,
+        )
+        .unwrap();
+        assert_eq!(load_manifest(&conn).unwrap(), manifest);
+    }
+}
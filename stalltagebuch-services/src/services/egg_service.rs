@@ -0,0 +1,194 @@
+use crate::error::AppError;
+use crate::models::EggRecord;
+use crate::services::repository;
+use rusqlite::{params, Connection};
+use uuid::Uuid;
+
+/// The egg record of a date and coop (`None` = whole flock), if any
+pub fn find_egg_record(
+    conn: &Connection,
+    date: &str,
+    coop_id: Option<&Uuid>,
+) -> Result<Option<EggRecord>, AppError> {
+    repository::find_first(
+        conn,
+        "record_date = ?1 AND coop_id IS ?2",
+        params![date, coop_id.map(|id| id.to_string())],
+        "record_date",
+    )
+}
+
+/// Creates a new egg record; there is at most one per date and coop
+pub async fn add_egg_record(conn: &Connection, record: &EggRecord) -> Result<Uuid, AppError> {
+    let date = record.record_date.format("%Y-%m-%d").to_string();
+    if find_egg_record(conn, &date, record.coop_id.as_ref())?.is_some() {
+        return Err(AppError::Validation(format!(
+            "Record for {} already exists",
+            date
+        )));
+    }
+    repository::save_with_ops(conn, record).await?;
+    Ok(record.uuid)
+}
+
+/// Loads the egg record of a date and coop (`None` = whole flock)
+pub fn get_egg_record(
+    conn: &Connection,
+    date: &str,
+    coop_id: Option<&Uuid>,
+) -> Result<EggRecord, AppError> {
+    find_egg_record(conn, date, coop_id)?
+        .ok_or(AppError::Database(rusqlite::Error::QueryReturnedNoRows))
+}
+
+/// Updates an existing egg record
+pub async fn update_egg_record(conn: &Connection, record: &EggRecord) -> Result<(), AppError> {
+    if repository::get::<EggRecord>(conn, &record.uuid)?.is_none() {
+        return Err(AppError::NotFound(format!(
+            "Record for {} not found",
+            record.record_date.format("%Y-%m-%d")
+        )));
+    }
+    repository::save_with_ops(conn, record).await
+}
+
+/// Deletes the egg record of a date and coop (`None` = whole flock)
+pub async fn delete_egg_record(
+    conn: &Connection,
+    date: &str,
+    coop_id: Option<&Uuid>,
+) -> Result<(), AppError> {
+    let record = get_egg_record(conn, date, coop_id)?;
+    repository::delete_with_ops::<EggRecord>(conn, &record.uuid).await
+}
+
+/// Loads all egg records for a time period (sorted by date descending)
+pub fn list_egg_records(
+    conn: &Connection,
+    start_date: Option<&str>,
+    end_date: Option<&str>,
+) -> Result<Vec<EggRecord>, AppError> {
+    let order = "record_date DESC";
+    match (start_date, end_date) {
+        (Some(start), Some(end)) => {
+            repository::list(conn, "record_date BETWEEN ?1 AND ?2", [start, end], order)
+        }
+        (Some(start), None) => repository::list(conn, "record_date >= ?1", [start], order),
+        (None, Some(end)) => repository::list(conn, "record_date <= ?1", [end], order),
+        (None, None) => repository::list(conn, "1", [], order),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database;
+
+    #[tokio::test]
+    async fn test_add_and_get_egg_record() {
+        let conn = Connection::open_in_memory().unwrap();
+        database::schema::init_schema(&conn).unwrap();
+
+        let date = chrono::Local::now().date_naive();
+        let record = EggRecord::new(date, 12);
+        let id = add_egg_record(&conn, &record).await.unwrap();
+        assert!(!id.is_nil());
+
+        let date_str = record.record_date.format("%Y-%m-%d").to_string();
+        let loaded = get_egg_record(&conn, &date_str, None).unwrap();
+        assert_eq!(loaded.total_eggs, 12);
+    }
+
+    #[tokio::test]
+    async fn test_update_egg_record() {
+        let conn = Connection::open_in_memory().unwrap();
+        database::schema::init_schema(&conn).unwrap();
+
+        let date = chrono::Local::now().date_naive();
+        let mut record = EggRecord::new(date, 10);
+        add_egg_record(&conn, &record).await.unwrap();
+
+        record.total_eggs = 15;
+        record.notes = Some("Aktualisiert".to_string());
+        update_egg_record(&conn, &record).await.unwrap();
+
+        let date_str = record.record_date.format("%Y-%m-%d").to_string();
+        let loaded = get_egg_record(&conn, &date_str, None).unwrap();
+        assert_eq!(loaded.total_eggs, 15);
+        assert_eq!(loaded.notes, Some("Aktualisiert".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_delete_egg_record() {
+        let conn = Connection::open_in_memory().unwrap();
+        database::schema::init_schema(&conn).unwrap();
+
+        let date = chrono::Local::now().date_naive();
+        let record = EggRecord::new(date, 8);
+        add_egg_record(&conn, &record).await.unwrap();
+
+        let date_str = record.record_date.format("%Y-%m-%d").to_string();
+        delete_egg_record(&conn, &date_str, None).await.unwrap();
+
+        let result = get_egg_record(&conn, &date_str, None);
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_list_egg_records() {
+        let conn = Connection::open_in_memory().unwrap();
+        database::schema::init_schema(&conn).unwrap();
+
+        // Mehrere Einträge hinzufügen mit unterschiedlichen Daten
+        for i in 0..5 {
+            let date = chrono::NaiveDate::from_ymd_opt(2025, 11, 5 + i).unwrap();
+            let record = EggRecord::new(date, (i + 1) as i32 * 2);
+            add_egg_record(&conn, &record).await.unwrap();
+        }
+
+        let records = list_egg_records(&conn, None, None).unwrap();
+        assert_eq!(records.len(), 5);
+    }
+
+    #[tokio::test]
+    async fn test_egg_records_per_coop() {
+        let conn = Connection::open_in_memory().unwrap();
+        database::schema::init_schema(&conn).unwrap();
+
+        let date = chrono::NaiveDate::from_ymd_opt(2025, 11, 5).unwrap();
+        let coop = Uuid::new_v4();
+        add_egg_record(&conn, &EggRecord::new(date, 4))
+            .await
+            .unwrap();
+        let mut in_coop = EggRecord::new(date, 3);
+        in_coop.coop_id = Some(coop);
+        add_egg_record(&conn, &in_coop).await.unwrap();
+
+        // Ein Eintrag je Tag und Stall
+        let mut duplicate = EggRecord::new(date, 1);
+        duplicate.coop_id = Some(coop);
+        assert!(matches!(
+            add_egg_record(&conn, &duplicate).await,
+            Err(AppError::Validation(_))
+        ));
+
+        assert_eq!(
+            get_egg_record(&conn, "2025-11-05", Some(&coop))
+                .unwrap()
+                .total_eggs,
+            3
+        );
+        delete_egg_record(&conn, "2025-11-05", None).await.unwrap();
+        assert!(find_egg_record(&conn, "2025-11-05", None)
+            .unwrap()
+            .is_none());
+        assert!(find_egg_record(&conn, "2025-11-05", Some(&coop))
+            .unwrap()
+            .is_some());
+
+        // Nach dem Löschen darf der Tag neu angelegt werden
+        add_egg_record(&conn, &EggRecord::new(date, 5))
+            .await
+            .unwrap();
+    }
+}
@@ -0,0 +1,236 @@
+//! Änderungsverlauf einzelner Einträge
+//!
+//! Setzt die Geschichte einer Wachtel, eines Ereignisses oder eines
+//! Eier-Eintrags aus `op_log` zusammen (lokale und per Sync übernommene
+//! Operationen): welches Gerät hat wann welche Felder geändert. Das Op-Log
+//! speichert die komplette Operation in `op_kind`, daher genügt es, die
+//! Operationen wieder einzulesen. Einträge, die nur über einen Snapshot
+//! eines anderen Geräts ankamen (siehe `op_compaction`), haben keinen
+//! Verlauf vor dem Snapshot.
+
+use crate::error::AppError;
+use crate::services::crdt_service::CrdtOp;
+use crate::services::sync_service;
+use rusqlite::{params, Connection};
+use std::collections::HashMap;
+
+/// Operationen eines Geräts in diesem Abstand gelten als eine Änderung
+/// (ein Speichern erzeugt eine Operation pro Feld)
+const GROUP_WINDOW_MS: i64 = 2_000;
+
+/// Geändertes Feld mit dem neuen Wert (als Text)
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldChange {
+    pub field: String,
+    /// `None`, wenn das Feld geleert wurde
+    pub value: Option<String>,
+}
+
+/// Eine Änderung: ein Gerät, ein Zeitpunkt, ein oder mehrere Felder
+#[derive(Debug, Clone, PartialEq)]
+pub struct HistoryEntry {
+    /// Zeitpunkt laut HLC (Millisekunden seit Epoch)
+    pub ts: i64,
+    pub device_id: String,
+    /// Auf diesem Gerät geändert
+    pub own_device: bool,
+    /// Erste bekannte Änderung (in der Regel das Anlegen)
+    pub created: bool,
+    pub deleted: bool,
+    /// Nur Felder, deren Wert sich tatsächlich geändert hat
+    pub changes: Vec<FieldChange>,
+}
+
+fn value_text(value: &serde_json::Value) -> Option<String> {
+    match value {
+        serde_json::Value::Null => None,
+        serde_json::Value::String(s) => Some(s.clone()),
+        other => Some(other.to_string()),
+    }
+}
+
+/// Verlauf eines Eintrags, neueste Änderung zuerst
+///
+/// `entity_type` wie im Op-Log: `quail`, `event`, `egg` oder `photo`.
+pub fn load_history(
+    conn: &Connection,
+    entity_type: &str,
+    entity_id: &str,
+) -> Result<Vec<HistoryEntry>, AppError> {
+    let own_device_id = sync_service::load_sync_settings(conn)?.and_then(|s| s.device_id);
+
+    let mut stmt = conn.prepare(
+        "SELECT ts, device_id, op_kind
+         FROM op_log
+         WHERE entity_id = ?1 AND entity_type = ?2
+         ORDER BY ts, logical_counter, device_id, op_id",
+    )?;
+    let rows = stmt.query_map(params![entity_id, entity_type], |row| {
+        Ok((
+            row.get::<_, i64>(0)?,
+            row.get::<_, String>(1)?,
+            row.get::<_, String>(2)?,
+        ))
+    })?;
+
+    let mut entries: Vec<HistoryEntry> = Vec::new();
+    // Letzter bekannter Wert je Feld, um unveränderte Felder auszublenden
+    let mut current: HashMap<String, Option<String>> = HashMap::new();
+    for row in rows {
+        let (ts, device_id, op_kind) = row?;
+        let op: CrdtOp = match serde_json::from_str(&op_kind) {
+            Ok(op) => op,
+            Err(e) => {
+                log::warn!("Unlesbare Operation im Op-Log übersprungen: {}", e);
+                continue;
+            }
+        };
+
+        let same_change = entries.last().is_some_and(|last| {
+            last.device_id == device_id && !last.deleted && ts - last.ts <= GROUP_WINDOW_MS
+        });
+        if !same_change {
+            entries.push(HistoryEntry {
+                ts,
+                own_device: own_device_id.as_deref().is_none_or(|own| own == device_id),
+                device_id,
+                created: entries.is_empty(),
+                deleted: false,
+                changes: Vec::new(),
+            });
+        }
+        let Some(entry) = entries.last_mut() else {
+            continue;
+        };
+
+        let change = match op {
+            CrdtOp::LwwSet { field, value } => {
+                let value = value_text(&value);
+                if current.get(&field) == Some(&value) {
+                    continue;
+                }
+                current.insert(field.clone(), value.clone());
+                FieldChange { field, value }
+            }
+            CrdtOp::OrAdd { field, element, .. } => FieldChange {
+                field,
+                value: Some(format!("+ {}", element)),
+            },
+            CrdtOp::OrRemove { field, element_id } => FieldChange {
+                field,
+                value: Some(format!("− {}", element_id)),
+            },
+            CrdtOp::PnIncrement { field, delta } => FieldChange {
+                field,
+                value: Some(format!("{:+}", delta)),
+            },
+            CrdtOp::Delete => {
+                entry.deleted = true;
+                continue;
+            }
+        };
+        // Innerhalb einer Änderung zählt der letzte Wert
+        entry.changes.retain(|c| c.field != change.field);
+        entry.changes.push(change);
+    }
+
+    // Änderungen ohne neuen Wert (erneut gespeichert) nicht anzeigen
+    entries.retain(|e| e.created || e.deleted || !e.changes.is_empty());
+    entries.reverse();
+    Ok(entries)
+}
+
+/// [`load_history`] im Hintergrund (für die Detailansichten)
+pub async fn load_history_async(
+    entity_type: &'static str,
+    entity_id: String,
+) -> Result<Vec<HistoryEntry>, AppError> {
+    crate::database::with_db_async(move |conn| load_history(conn, entity_type, &entity_id)).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::schema;
+    use crate::services::crdt_service::{self, HybridLogicalClock, Operation};
+
+    fn setup() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        schema::init_schema(&conn).unwrap();
+        conn
+    }
+
+    fn log(conn: &Connection, entity_id: &str, device: &str, ts: i64, op: CrdtOp) {
+        let op = Operation {
+            op_id: ulid::Ulid::new().to_string(),
+            entity_type: "quail".to_string(),
+            entity_id: entity_id.to_string(),
+            clock: HybridLogicalClock {
+                ts,
+                logical_counter: 0,
+                device_id: device.to_string(),
+            },
+            op,
+        };
+        crdt_service::log_operation(conn, &op).unwrap();
+    }
+
+    fn set(field: &str, value: serde_json::Value) -> CrdtOp {
+        CrdtOp::LwwSet {
+            field: field.to_string(),
+            value,
+        }
+    }
+
+    #[test]
+    fn test_history_groups_and_diffs_changes() {
+        let conn = setup();
+        let q = "q1";
+        // Anlegen auf Gerät A (eine Operation pro Feld)
+        log(&conn, q, "device-a", 1_000, set("name", "Greta".into()));
+        log(&conn, q, "device-a", 1_001, set("gender", "female".into()));
+        // Erneut gespeichert, nur der Name ist neu
+        log(&conn, q, "device-a", 60_000, set("name", "Gretel".into()));
+        log(&conn, q, "device-a", 60_001, set("gender", "female".into()));
+        // Gerät B speichert unverändert, dann entfernt es den Ring
+        log(&conn, q, "device-b", 120_000, set("name", "Gretel".into()));
+        log(
+            &conn,
+            q,
+            "device-b",
+            180_000,
+            set("ring_color", serde_json::Value::Null),
+        );
+        log(&conn, q, "device-b", 240_000, CrdtOp::Delete);
+        // Anderer Eintrag
+        log(&conn, "q2", "device-a", 1_000, set("name", "Other".into()));
+
+        let history = load_history(&conn, "quail", q).unwrap();
+        assert_eq!(history.len(), 4);
+
+        assert!(history[0].deleted);
+        assert_eq!(history[0].device_id, "device-b");
+
+        assert_eq!(
+            history[1].changes,
+            vec![FieldChange {
+                field: "ring_color".to_string(),
+                value: None
+            }]
+        );
+
+        assert_eq!(history[2].ts, 60_000);
+        assert_eq!(
+            history[2].changes,
+            vec![FieldChange {
+                field: "name".to_string(),
+                value: Some("Gretel".to_string())
+            }]
+        );
+
+        assert!(history[3].created);
+        assert_eq!(history[3].changes.len(), 2);
+        // Ohne Sync stammt alles von diesem Gerät
+        assert!(history.iter().all(|e| e.own_device));
+    }
+}
@@ -0,0 +1,122 @@
+use crate::error::AppError;
+use crate::models::{EventType, QuailEvent, Treatment};
+use crate::services::repository;
+use chrono::NaiveDate;
+use rusqlite::{params, Connection, OptionalExtension};
+use uuid::Uuid;
+
+/// Creates a new event for a quail
+pub async fn create_event(
+    conn: &Connection,
+    quail_id: Uuid,
+    event_type: EventType,
+    event_date: NaiveDate,
+    notes: Option<String>,
+) -> Result<Uuid, AppError> {
+    let mut event = QuailEvent::new(quail_id, event_type, event_date);
+    event.notes = notes;
+    repository::save_with_ops(conn, &event).await?;
+    Ok(event.uuid)
+}
+
+/// Returns all events for a specific quail
+pub fn get_events_for_quail(
+    conn: &Connection,
+    quail_uuid: &Uuid,
+) -> Result<Vec<QuailEvent>, AppError> {
+    repository::list(
+        conn,
+        "quail_id = ?1",
+        [quail_uuid.to_string()],
+        "event_date DESC",
+    )
+}
+
+/// Returns the latest event for a quail
+#[allow(dead_code)]
+pub fn get_latest_event(
+    conn: &Connection,
+    quail_uuid: &Uuid,
+) -> Result<Option<QuailEvent>, AppError> {
+    repository::find_first(
+        conn,
+        "quail_id = ?1",
+        [quail_uuid.to_string()],
+        "event_date DESC",
+    )
+}
+
+/// Returns the birth date of a quail (from the "born" event)
+#[allow(dead_code)]
+pub fn get_birth_date(conn: &Connection, quail_uuid: &Uuid) -> Result<Option<NaiveDate>, AppError> {
+    let mut stmt = conn.prepare(
+        "SELECT event_date
+         FROM quail_events
+         WHERE quail_id = ?1 AND event_type = 'born'
+         LIMIT 1",
+    )?;
+
+    let date_str: Option<String> = stmt
+        .query_row(params![quail_uuid.to_string()], |row| row.get(0))
+        .optional()?;
+
+    if let Some(date_str) = &date_str {
+        let date = NaiveDate::parse_from_str(date_str, "%Y-%m-%d")
+            .map_err(|_| AppError::Database(rusqlite::Error::InvalidQuery))?;
+        Ok(Some(date))
+    } else {
+        Ok(None)
+    }
+}
+/// Updates an existing event
+#[allow(dead_code)]
+pub fn update_event(
+    conn: &Connection,
+    event_uuid: &Uuid,
+    notes: Option<String>,
+) -> Result<(), AppError> {
+    conn.execute(
+        "UPDATE quail_events 
+         SET notes = ?1
+         WHERE uuid = ?2",
+        params![notes, event_uuid.to_string()],
+    )?;
+
+    Ok(())
+}
+
+/// Deletes an event
+pub async fn delete_event(conn: &Connection, event_uuid: &Uuid) -> Result<(), AppError> {
+    repository::delete_with_ops::<QuailEvent>(conn, event_uuid).await
+}
+
+/// Gets a single event by UUID
+pub fn get_event_by_id(
+    conn: &Connection,
+    event_uuid: &Uuid,
+) -> Result<Option<QuailEvent>, AppError> {
+    repository::get(conn, event_uuid)
+}
+
+/// Full update of an event (type, date, notes, treatment details)
+///
+/// `treatment` must be set exactly for [`EventType::Treatment`].
+pub async fn update_event_full(
+    conn: &Connection,
+    event_uuid: &Uuid,
+    event_type: EventType,
+    event_date: NaiveDate,
+    notes: Option<String>,
+    treatment: Option<Treatment>,
+) -> Result<(), AppError> {
+    let existing = get_event_by_id(conn, event_uuid)?
+        .ok_or_else(|| AppError::NotFound("Event not found".to_string()))?;
+    let event = QuailEvent {
+        event_type,
+        event_date,
+        notes,
+        treatment,
+        ..existing
+    };
+    repository::save_with_ops(conn, &event).await
+}
@@ -0,0 +1,1959 @@
+// Export/Import service for full local backup
+
+use crate::error::AppError;
+use crate::services::backup_crypto::{self, Passphrase};
+use crate::services::integrity;
+use crate::services::photo_service::get_absolute_photo_path;
+use base64::Engine as _;
+use chrono::Utc;
+use rusqlite::{Connection, Row};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::{Cursor, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum ImportMode {
+    /// Bei jedem Unterschied gewinnt die Sicherung (Wiederherstellung)
+    MergePreferImport,
+    /// Entscheidungen aus [`preview_zip_import`]; Unterschiede ohne
+    /// Entscheidung behalten den lokalen Stand
+    Decisions(ImportDecisions),
+}
+
+/// Umgang mit einem Eintrag, den es lokal schon anders gibt
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum ConflictChoice {
+    KeepLocal,
+    TakeImport,
+    /// Importierten Eintrag als Kopie mit neuer UUID anlegen
+    /// (nur wo [`ImportEntity::can_keep_both`])
+    KeepBoth,
+}
+
+/// Entscheidungen je importierter UUID
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ImportDecisions {
+    choices: std::collections::HashMap<String, ConflictChoice>,
+}
+
+impl ImportDecisions {
+    /// Vorschlag der Vorschau: neuere Sicherung übernehmen, sonst lokal behalten
+    pub fn from_preview(preview: &ZipImportPreview) -> Self {
+        let mut decisions = Self::default();
+        for item in &preview.items {
+            match item.change {
+                ImportChange::Update => decisions.set(&item.uuid, ConflictChoice::TakeImport),
+                ImportChange::Conflict => decisions.set(&item.uuid, ConflictChoice::KeepLocal),
+                ImportChange::Add | ImportChange::Unchanged => {}
+            }
+        }
+        decisions
+    }
+
+    pub fn set(&mut self, uuid: &str, choice: ConflictChoice) {
+        self.choices.insert(uuid.to_string(), choice);
+    }
+
+    pub fn choice(&self, uuid: &str) -> ConflictChoice {
+        self.choices
+            .get(uuid)
+            .copied()
+            .unwrap_or(ConflictChoice::KeepLocal)
+    }
+}
+
+/// Art eines Eintrags in der Sicherung
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ImportEntity {
+    Coop,
+    Quail,
+    Event,
+    EggRecord,
+    Photo,
+}
+
+impl ImportEntity {
+    pub const ALL: [ImportEntity; 5] = [
+        ImportEntity::Coop,
+        ImportEntity::Quail,
+        ImportEntity::Event,
+        ImportEntity::EggRecord,
+        ImportEntity::Photo,
+    ];
+
+    /// Wie in den Sync-Einstellungen (`quail`, `event`, `egg`, `photo`, `coop`)
+    pub fn entity_type(self) -> &'static str {
+        match self {
+            ImportEntity::Coop => "coop",
+            ImportEntity::Quail => "quail",
+            ImportEntity::Event => "event",
+            ImportEntity::EggRecord => "egg",
+            ImportEntity::Photo => "photo",
+        }
+    }
+
+    fn table(self) -> &'static str {
+        match self {
+            ImportEntity::Coop => "coops",
+            ImportEntity::Quail => "quails",
+            ImportEntity::Event => "quail_events",
+            ImportEntity::EggRecord => "egg_records",
+            ImportEntity::Photo => "photos",
+        }
+    }
+
+    /// Spalten, deren Unterschied als Änderung zählt
+    fn compared_columns(self) -> &'static [&'static str] {
+        match self {
+            ImportEntity::Coop => &["name", "notes", "deleted"],
+            ImportEntity::Quail => &[
+                "name",
+                "gender",
+                "ring_color",
+                "profile_photo",
+                "mother_id",
+                "father_id",
+                "coop_id",
+                "deleted",
+            ],
+            ImportEntity::Event => &[
+                "quail_id",
+                "event_type",
+                "event_date",
+                "notes",
+                "medication",
+                "dose",
+                "duration_days",
+                "egg_withdrawal_days",
+                "meat_withdrawal_days",
+                "deleted",
+            ],
+            ImportEntity::EggRecord => {
+                &["record_date", "total_eggs", "notes", "coop_id", "deleted"]
+            }
+            ImportEntity::Photo => &["quail_id", "event_id", "relative_path", "deleted"],
+        }
+    }
+
+    /// Eier-Einträge gibt es nur einen pro Tag und Stall, Fotodateien heißen wie
+    /// ihre UUID: davon kann es keine Kopie geben
+    pub fn can_keep_both(self) -> bool {
+        matches!(self, ImportEntity::Quail | ImportEntity::Event)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportChange {
+    /// Gibt es lokal nicht
+    Add,
+    /// Unterschiedlich, die Sicherung ist neuer
+    Update,
+    Unchanged,
+    /// Unterschiedlich, lokal wurde seit dem Export geändert
+    Conflict,
+}
+
+/// Ein Eintrag der Sicherung im Vergleich zum lokalen Stand
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportItem {
+    pub entity: ImportEntity,
+    pub uuid: String,
+    pub change: ImportChange,
+    /// Kurzbeschreibung des importierten Eintrags
+    pub label: String,
+    /// Geänderte Felder: (Bezeichnung, lokal, Import)
+    pub differences: Vec<(String, String, String)>,
+}
+
+/// Ergebnis von [`preview_zip_import`]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ZipImportPreview {
+    pub items: Vec<ImportItem>,
+}
+
+impl ZipImportPreview {
+    pub fn count(&self, entity: ImportEntity, change: ImportChange) -> usize {
+        self.items
+            .iter()
+            .filter(|item| item.entity == entity && item.change == change)
+            .count()
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct ExportMetadata {
+    format_version: u32,
+    exported_at: String,
+    app_version: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct ExportCoops {
+    coops: Vec<serde_json::Value>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct ExportQuails {
+    quails: Vec<serde_json::Value>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct ExportEvents {
+    events: Vec<serde_json::Value>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct ExportEggRecords {
+    egg_records: Vec<serde_json::Value>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct ExportPhotos {
+    photos: Vec<serde_json::Value>,
+}
+
+fn query_table(
+    conn: &Connection,
+    sql: &str,
+    params: impl rusqlite::Params,
+) -> Result<Vec<serde_json::Value>, AppError> {
+    let mut stmt = conn.prepare(sql)?;
+    let rows = stmt.query_map(params, row_to_json)?;
+    let mut out = Vec::new();
+    for r in rows {
+        out.push(r?);
+    }
+    Ok(out)
+}
+
+fn row_to_json(row: &Row<'_>) -> rusqlite::Result<serde_json::Value> {
+    let mut obj = serde_json::Map::new();
+    for (idx, col) in row.as_ref().column_names().iter().enumerate() {
+        let col_name = col.to_string();
+        let value: rusqlite::types::Value = row.get(idx)?;
+        let json_value = match value {
+            rusqlite::types::Value::Null => serde_json::Value::Null,
+            rusqlite::types::Value::Integer(v) => serde_json::Value::from(v),
+            rusqlite::types::Value::Real(v) => serde_json::Value::from(v),
+            rusqlite::types::Value::Text(v) => serde_json::Value::from(v),
+            rusqlite::types::Value::Blob(v) => {
+                serde_json::Value::from(base64::engine::general_purpose::STANDARD.encode(v))
+            }
+        };
+        obj.insert(col_name, json_value);
+    }
+    Ok(serde_json::Value::Object(obj))
+}
+
+fn ensure_parent_dir(path: &Path) -> Result<(), AppError> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| {
+            AppError::Other(format!("Fehler beim Erstellen des Verzeichnisses: {}", e))
+        })?;
+    }
+    Ok(())
+}
+
+pub fn get_export_base_dir() -> PathBuf {
+    #[cfg(target_os = "android")]
+    {
+        PathBuf::from(
+            "/storage/emulated/0/Android/data/de.teilgedanken.stalltagebuch/files/exports",
+        )
+    }
+
+    #[cfg(not(target_os = "android"))]
+    {
+        crate::database::location::current().export_dir()
+    }
+}
+
+/// Welche Fotodateien ein Export enthält
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PhotoExport {
+    /// Originale (wie die volle Sicherung)
+    #[default]
+    Originals,
+    /// Nur die Vorschaubilder, z.B. zum Weitergeben an den Tierarzt
+    ThumbnailsOnly,
+    /// Nur die mittleren Vorschaubilder statt der Originale, damit eine
+    /// vollständige Sicherung klein genug für eine E-Mail bleibt; beim
+    /// Import holt der Sync die Originale später nach
+    Reduced,
+    /// Keine Fotos, auch keine Foto-Datensätze
+    None,
+}
+
+/// Auswahl für [`export_selection_to_zip`]; `Default` = alles
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExportFilter {
+    /// Ereignisse und Eier-Einträge ab diesem Tag (einschließlich)
+    pub start_date: Option<chrono::NaiveDate>,
+    /// ... bis zu diesem Tag (einschließlich)
+    pub end_date: Option<chrono::NaiveDate>,
+    /// Nur diese Wachteln mit ihren Ereignissen und Fotos; leer = alle
+    pub quail_ids: Vec<uuid::Uuid>,
+    pub include_egg_records: bool,
+    pub photos: PhotoExport,
+    /// Verschlüsselt das ZIP (siehe [`backup_crypto`])
+    pub passphrase: Option<Passphrase>,
+}
+
+impl Default for ExportFilter {
+    fn default() -> Self {
+        Self {
+            start_date: None,
+            end_date: None,
+            quail_ids: Vec::new(),
+            include_egg_records: true,
+            photos: PhotoExport::Originals,
+            passphrase: None,
+        }
+    }
+}
+
+fn write_zip_entry<W: Write + Seek>(
+    zip: &mut zip::ZipWriter<W>,
+    name: &str,
+    data: &[u8],
+) -> Result<(), AppError> {
+    let options: zip::write::FileOptions<'_, ()> =
+        zip::write::FileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+    zip.start_file(name, options).map_err(|e| {
+        AppError::Other(format!(
+            "Fehler beim Hinzufügen von {} zum ZIP: {}",
+            name, e
+        ))
+    })?;
+    zip.write_all(data)
+        .map_err(|e| AppError::Other(format!("Fehler beim Schreiben von {} ins ZIP: {}", name, e)))
+}
+
+fn write_zip_json<W: Write + Seek>(
+    zip: &mut zip::ZipWriter<W>,
+    name: &str,
+    value: &impl Serialize,
+) -> Result<(), AppError> {
+    let json = serde_json::to_vec_pretty(value)
+        .map_err(|e| AppError::Other(format!("Fehler beim Serialisieren von {}: {}", name, e)))?;
+    write_zip_entry(zip, name, &json)
+}
+
+/// Wachtel-Auswahl als Parameter für `json_each` (`NULL` = alle)
+const QUAIL_FILTER: &str = "(?1 IS NULL OR quail_id IN (SELECT value FROM json_each(?1)))";
+const EVENT_FILTER: &str = "(?2 IS NULL OR event_date >= ?2) AND (?3 IS NULL OR event_date <= ?3)";
+
+/// Schreibt die Sicherung mit der Auswahl `filter` in `writer`
+/// Dateien der nicht gelöschten Fotos: Originale bzw. Vorschaubilder
+fn photo_files(photos: &[serde_json::Value], mode: PhotoExport) -> Vec<String> {
+    let columns: &[&str] = match mode {
+        PhotoExport::Originals => &["relative_path", "path"],
+        PhotoExport::ThumbnailsOnly => &["thumbnail_small_path", "thumbnail_medium_path"],
+        PhotoExport::Reduced => &["thumbnail_medium_path"],
+        PhotoExport::None => &[],
+    };
+    let mut files = Vec::new();
+    for photo in photos
+        .iter()
+        .filter(|p| p.get("deleted").and_then(|v| v.as_i64()) == Some(0))
+    {
+        let names = columns
+            .iter()
+            .filter_map(|column| photo.get(*column).and_then(|v| v.as_str()))
+            .filter(|name| !name.is_empty());
+        if mode == PhotoExport::Originals {
+            // relative_path vor dem alten absoluten path (COALESCE)
+            files.extend(names.take(1).map(str::to_string));
+        } else {
+            files.extend(names.map(str::to_string));
+        }
+    }
+    files
+}
+
+fn write_export<W: Write + Seek>(
+    conn: &Connection,
+    filter: &ExportFilter,
+    writer: W,
+) -> Result<W, AppError> {
+    let mut zip = zip::ZipWriter::new(writer);
+
+    let metadata = ExportMetadata {
+        format_version: 1,
+        exported_at: Utc::now().to_rfc3339(),
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+    };
+    write_zip_json(&mut zip, "metadata.json", &metadata)?;
+
+    let quail_ids = (!filter.quail_ids.is_empty()).then(|| {
+        serde_json::Value::from(
+            filter
+                .quail_ids
+                .iter()
+                .map(|id| id.to_string())
+                .collect::<Vec<_>>(),
+        )
+        .to_string()
+    });
+    let start = filter.start_date.map(|d| d.format("%Y-%m-%d").to_string());
+    let end = filter.end_date.map(|d| d.format("%Y-%m-%d").to_string());
+    let params = rusqlite::params![quail_ids, start, end];
+
+    // Tabellen exportieren; Ställe immer alle (Wachteln und Eier verweisen darauf)
+    let coops = query_table(conn, "SELECT * FROM coops", [])?;
+    let mut quails = query_table(
+        conn,
+        "SELECT * FROM quails WHERE ?1 IS NULL OR uuid IN (SELECT value FROM json_each(?1))",
+        rusqlite::params![quail_ids],
+    )?;
+    let events = query_table(
+        conn,
+        &format!(
+            "SELECT * FROM quail_events WHERE {} AND {}",
+            QUAIL_FILTER, EVENT_FILTER
+        ),
+        params,
+    )?;
+    let egg_records = if filter.include_egg_records {
+        query_table(
+            conn,
+            "SELECT * FROM egg_records
+             WHERE (?1 IS NULL OR record_date >= ?1) AND (?2 IS NULL OR record_date <= ?2)",
+            rusqlite::params![start, end],
+        )?
+    } else {
+        Vec::new()
+    };
+    // Ereignis-Fotos folgen ihrem Ereignis, Galerie-Fotos ihrer Wachtel
+    let photos = if filter.photos == PhotoExport::None {
+        Vec::new()
+    } else {
+        query_table(
+            conn,
+            &format!(
+                "SELECT * FROM photos WHERE CASE
+                    WHEN event_id IS NOT NULL THEN event_id IN
+                        (SELECT uuid FROM quail_events WHERE {} AND {})
+                    WHEN quail_id IS NOT NULL THEN {}
+                    ELSE ?1 IS NULL
+                 END",
+                QUAIL_FILTER, EVENT_FILTER, QUAIL_FILTER
+            ),
+            params,
+        )?
+    };
+
+    // Profilfotos, die nicht mitkommen, würden beim Import zu Platzhaltern
+    let photo_ids: std::collections::HashSet<&str> = photos
+        .iter()
+        .filter_map(|p| p.get("uuid").and_then(|v| v.as_str()))
+        .collect();
+    for quail in &mut quails {
+        let missing = quail
+            .get("profile_photo")
+            .and_then(|v| v.as_str())
+            .is_some_and(|id| !photo_ids.contains(id));
+        if missing {
+            quail["profile_photo"] = serde_json::Value::Null;
+        }
+    }
+
+    let files = photo_files(&photos, filter.photos);
+
+    write_zip_json(&mut zip, "data/coops.json", &ExportCoops { coops })?;
+    write_zip_json(&mut zip, "data/quails.json", &ExportQuails { quails })?;
+    write_zip_json(&mut zip, "data/events.json", &ExportEvents { events })?;
+    write_zip_json(
+        &mut zip,
+        "data/egg_records.json",
+        &ExportEggRecords { egg_records },
+    )?;
+    write_zip_json(&mut zip, "data/photos.json", &ExportPhotos { photos })?;
+
+    for rel_path in files {
+        let abs = PathBuf::from(get_absolute_photo_path(&rel_path));
+        if abs.exists() {
+            let mut file_data = Vec::new();
+            let mut f = fs::File::open(&abs)
+                .map_err(|e| AppError::Other(format!("Fehler beim Öffnen eines Fotos: {}", e)))?;
+            f.read_to_end(&mut file_data)
+                .map_err(|e| AppError::Other(format!("Fehler beim Lesen eines Fotos: {}", e)))?;
+            write_zip_entry(&mut zip, &format!("photos/{}", rel_path), &file_data)?;
+        }
+    }
+
+    zip.finish()
+        .map_err(|e| AppError::Other(format!("Fehler beim finalisieren der ZIP-Datei: {}", e)))
+}
+
+/// Vollständige Sicherung aller Daten und Foto-Originale
+pub async fn export_to_zip(conn: &Connection) -> Result<PathBuf, AppError> {
+    export_selection_to_zip(conn, &ExportFilter::default()).await
+}
+
+/// Sicherung nur der Auswahl `filter` (Zeitraum, Wachteln, Fotos)
+///
+/// Das ZIP hat dasselbe Format wie [`export_to_zip`] und lässt sich mit
+/// [`import_from_zip`] einspielen. Wachteln kommen unabhängig vom Zeitraum
+/// mit; er gilt für Ereignisse, deren Fotos und die Eier-Einträge.
+pub async fn export_selection_to_zip(
+    conn: &Connection,
+    filter: &ExportFilter,
+) -> Result<PathBuf, AppError> {
+    let base_dir = get_export_base_dir();
+    fs::create_dir_all(&base_dir).map_err(|e| {
+        AppError::Other(format!(
+            "Fehler beim Erstellen des Export-Verzeichnisses: {}",
+            e
+        ))
+    })?;
+
+    let timestamp = Utc::now().format("%Y%m%d-%H%M%S").to_string();
+    let mut file_name = format!("stalltagebuch-export-{}.zip", timestamp);
+    if filter.passphrase.is_some() {
+        file_name = format!("{}.{}", file_name, backup_crypto::ENCRYPTED_EXTENSION);
+    }
+    let export_path = base_dir.join(file_name);
+
+    export_selection_to_path(conn, filter, &export_path)?;
+    Ok(export_path)
+}
+
+/// Wie [`export_selection_to_zip`], aber in die Datei `export_path`
+pub fn export_selection_to_path(
+    conn: &Connection,
+    filter: &ExportFilter,
+    export_path: &Path,
+) -> Result<(), AppError> {
+    ensure_parent_dir(export_path)?;
+
+    if let Some(passphrase) = &filter.passphrase {
+        let plain = write_export(conn, filter, Cursor::new(Vec::new()))?.into_inner();
+        let encrypted = backup_crypto::encrypt(&plain, passphrase)?;
+        fs::write(export_path, encrypted).map_err(|e| {
+            AppError::Other(format!("Fehler beim Erstellen der Exportdatei: {}", e))
+        })?;
+        return Ok(());
+    }
+
+    let file = fs::File::create(export_path)
+        .map_err(|e| AppError::Other(format!("Fehler beim Erstellen der Exportdatei: {}", e)))?;
+    write_export(conn, filter, file)?;
+    Ok(())
+}
+
+/// Feld für CSV (RFC 4180): in Anführungszeichen, wenn nötig
+pub(crate) fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Eier-Einträge im Zeitraum als CSV, ältester Tag zuerst
+///
+/// Datumsgrenzen wie bei [`list_egg_records`](crate::services::list_egg_records)
+/// (`YYYY-MM-DD`, jeweils einschließlich).
+pub fn egg_records_csv(
+    conn: &Connection,
+    start_date: Option<&str>,
+    end_date: Option<&str>,
+) -> Result<String, AppError> {
+    let mut records = crate::services::list_egg_records(conn, start_date, end_date)?;
+    records.reverse();
+
+    // Einträge des ganzen Bestands haben eine leere Stall-Spalte
+    let coops: std::collections::HashMap<uuid::Uuid, String> =
+        crate::services::coop_service::list_coops(conn)?
+            .into_iter()
+            .map(|coop| (coop.uuid, coop.name))
+            .collect();
+
+    let mut csv = String::from("date,total_eggs,notes,coop\r\n");
+    for record in records {
+        let coop = record
+            .coop_id
+            .and_then(|id| coops.get(&id))
+            .map(String::as_str)
+            .unwrap_or_default();
+        csv.push_str(&format!(
+            "{},{},{},{}\r\n",
+            record.record_date.format("%Y-%m-%d"),
+            record.total_eggs,
+            csv_field(record.notes.as_deref().unwrap_or_default()),
+            csv_field(coop)
+        ));
+    }
+    Ok(csv)
+}
+
+/// Schreibt [`egg_records_csv`] ins Export-Verzeichnis und gibt den Pfad zurück
+pub fn export_eggs_csv(
+    conn: &Connection,
+    start_date: Option<&str>,
+    end_date: Option<&str>,
+) -> Result<PathBuf, AppError> {
+    let csv = egg_records_csv(conn, start_date, end_date)?;
+    write_csv_export(&csv, "stalltagebuch-eier")
+}
+
+/// Schreibt eine CSV-Datei als `<prefix>-<Zeitstempel>.csv` ins
+/// Export-Verzeichnis und gibt den Pfad zurück
+pub(crate) fn write_csv_export(csv: &str, prefix: &str) -> Result<PathBuf, AppError> {
+    let base_dir = get_export_base_dir();
+    fs::create_dir_all(&base_dir).map_err(|e| {
+        AppError::Other(format!(
+            "Fehler beim Erstellen des Export-Verzeichnisses: {}",
+            e
+        ))
+    })?;
+    let timestamp = Utc::now().format("%Y%m%d-%H%M%S").to_string();
+    let export_path = base_dir.join(format!("{}-{}.csv", prefix, timestamp));
+
+    // BOM, damit Excel Umlaute in den Notizen als UTF-8 liest
+    fs::write(&export_path, format!("\u{feff}{}", csv))
+        .map_err(|e| AppError::Other(format!("Fehler beim Schreiben der CSV-Datei: {}", e)))?;
+
+    Ok(export_path)
+}
+
+/// Geöffnete Sicherung: unverschlüsselt direkt aus der Datei, sonst
+/// entschlüsselt im Speicher
+enum BackupReader {
+    File(fs::File),
+    Decrypted(Cursor<Vec<u8>>),
+}
+
+impl Read for BackupReader {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        match self {
+            BackupReader::File(file) => file.read(buf),
+            BackupReader::Decrypted(cursor) => cursor.read(buf),
+        }
+    }
+}
+
+impl Seek for BackupReader {
+    fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+        match self {
+            BackupReader::File(file) => file.seek(pos),
+            BackupReader::Decrypted(cursor) => cursor.seek(pos),
+        }
+    }
+}
+
+/// Ist die Sicherung unter `path` mit einer Passphrase verschlüsselt?
+pub fn is_encrypted_backup(path: &Path) -> Result<bool, AppError> {
+    let mut head = Vec::new();
+    fs::File::open(path)
+        .map_err(|e| AppError::Other(format!("Fehler beim Öffnen der Importdatei: {}", e)))?
+        .take(16)
+        .read_to_end(&mut head)?;
+    Ok(backup_crypto::is_encrypted(&head))
+}
+
+fn open_backup(
+    path: &Path,
+    passphrase: Option<&Passphrase>,
+) -> Result<zip::ZipArchive<BackupReader>, AppError> {
+    let reader = if is_encrypted_backup(path)? {
+        let passphrase = passphrase.ok_or_else(|| {
+            AppError::Validation("Sicherung ist verschlüsselt, Passphrase fehlt".to_string())
+        })?;
+        let data = fs::read(path)
+            .map_err(|e| AppError::Other(format!("Fehler beim Öffnen der Importdatei: {}", e)))?;
+        BackupReader::Decrypted(Cursor::new(backup_crypto::decrypt(&data, passphrase)?))
+    } else {
+        BackupReader::File(
+            fs::File::open(path).map_err(|e| {
+                AppError::Other(format!("Fehler beim Öffnen der Importdatei: {}", e))
+            })?,
+        )
+    };
+    zip::ZipArchive::new(reader)
+        .map_err(|e| AppError::Other(format!("Fehler beim Lesen der ZIP-Datei: {}", e)))
+}
+
+fn read_zip_json<R: Read + Seek>(
+    archive: &mut zip::ZipArchive<R>,
+    name: &str,
+) -> Result<Option<serde_json::Value>, AppError> {
+    match archive.by_name(name) {
+        Ok(mut f) => {
+            let mut buf = String::new();
+            f.read_to_string(&mut buf)
+                .map_err(|e| AppError::Other(format!("Fehler beim Lesen von {}: {}", name, e)))?;
+            let v: serde_json::Value = serde_json::from_str(&buf)
+                .map_err(|e| AppError::Other(format!("Fehler beim Parsen von {}: {}", name, e)))?;
+            Ok(Some(v))
+        }
+        Err(_) => Ok(None),
+    }
+}
+
+/// Zeilen einer `data/*.json` (`{"<key>": [...]}`), leer wenn sie fehlt
+fn zip_rows<R: Read + Seek>(
+    archive: &mut zip::ZipArchive<R>,
+    name: &str,
+    key: &str,
+) -> Result<Vec<serde_json::Value>, AppError> {
+    Ok(match read_zip_json(archive, name)? {
+        Some(serde_json::Value::Object(mut obj)) => match obj.remove(key) {
+            Some(serde_json::Value::Array(rows)) => rows,
+            _ => Vec::new(),
+        },
+        _ => Vec::new(),
+    })
+}
+
+/// Lokale Zeile zu einer importierten: gleiche UUID, bei Eier-Einträgen
+/// sonst derselbe Tag im selben Stall (davon gibt es nur einen)
+fn local_row(
+    conn: &Connection,
+    entity: ImportEntity,
+    row: &serde_json::Value,
+) -> Result<Option<serde_json::Value>, AppError> {
+    let select = format!(
+        "SELECT uuid, updated_at, {} FROM {}",
+        entity.compared_columns().join(", "),
+        entity.table()
+    );
+    let uuid = row.get("uuid").and_then(|v| v.as_str()).unwrap_or_default();
+    let mut found = query_table(conn, &format!("{} WHERE uuid = ?1", select), [uuid])?;
+    if found.is_empty() && entity == ImportEntity::EggRecord {
+        let date = row
+            .get("record_date")
+            .and_then(|v| v.as_str())
+            .unwrap_or_default();
+        let coop_id = row.get("coop_id").and_then(|v| v.as_str());
+        found = query_table(
+            conn,
+            &format!(
+                "{} WHERE record_date = ?1 AND coop_id IS ?2 AND deleted = 0",
+                select
+            ),
+            rusqlite::params![date, coop_id],
+        )?;
+    }
+    Ok(found.pop())
+}
+
+fn json_text(value: Option<&serde_json::Value>) -> String {
+    match value {
+        None | Some(serde_json::Value::Null) => String::new(),
+        Some(serde_json::Value::String(s)) => s.clone(),
+        Some(other) => other.to_string(),
+    }
+}
+
+fn column_label(column: &str) -> &str {
+    match column {
+        "name" => "Name",
+        "gender" => "Geschlecht",
+        "ring_color" => "Ringfarbe",
+        "profile_photo" => "Profilfoto",
+        "mother_id" => "Mutter",
+        "father_id" => "Vater",
+        "coop_id" => "Stall",
+        "deleted" => "Gelöscht",
+        "quail_id" => "Wachtel",
+        "event_id" | "event_type" => "Ereignis",
+        "event_date" | "record_date" => "Datum",
+        "notes" => "Notizen",
+        "total_eggs" => "Eier",
+        "medication" => "Medikament",
+        "dose" => "Dosis",
+        "duration_days" => "Behandlungsdauer",
+        "egg_withdrawal_days" => "Wartezeit Eier",
+        "meat_withdrawal_days" => "Wartezeit Fleisch",
+        "relative_path" => "Datei",
+        other => other,
+    }
+}
+
+/// Unterschiedliche Felder (Bezeichnung, lokal, Import)
+fn row_differences(
+    entity: ImportEntity,
+    local: &serde_json::Value,
+    imported: &serde_json::Value,
+) -> Vec<(String, String, String)> {
+    entity
+        .compared_columns()
+        .iter()
+        .filter_map(|column| {
+            let (old, new) = (
+                json_text(local.get(*column)),
+                json_text(imported.get(*column)),
+            );
+            // Fehlende Felder in alten Sicherungen zählen nicht als Änderung
+            (imported.get(*column).is_some() && old != new)
+                .then(|| (column_label(column).to_string(), old, new))
+        })
+        .collect()
+}
+
+fn describe_row(entity: ImportEntity, row: &serde_json::Value) -> String {
+    let text = |column: &str| json_text(row.get(column));
+    let label = match entity {
+        ImportEntity::Coop | ImportEntity::Quail => text("name"),
+        ImportEntity::Event => format!(
+            "{} {}",
+            text("event_date"),
+            crate::models::EventType::from_str(&text("event_type")).display_name()
+        ),
+        ImportEntity::EggRecord => format!("{}: {} Eier", text("record_date"), text("total_eggs")),
+        ImportEntity::Photo => match text("relative_path") {
+            path if path.is_empty() => text("uuid"),
+            path => path,
+        },
+    };
+    if row.get("deleted").and_then(|v| v.as_i64()) == Some(1) {
+        format!("{} (gelöscht)", label)
+    } else {
+        label
+    }
+}
+
+/// Was die Zeile lokal bewirken würde
+fn classify_row(
+    conn: &Connection,
+    entity: ImportEntity,
+    row: &serde_json::Value,
+) -> Result<ImportItem, AppError> {
+    let local = local_row(conn, entity, row)?;
+    let differences = local
+        .as_ref()
+        .map(|local| row_differences(entity, local, row))
+        .unwrap_or_default();
+    let change = match &local {
+        None => ImportChange::Add,
+        Some(_) if differences.is_empty() => ImportChange::Unchanged,
+        // CURRENT_TIMESTAMP-Format, lexikografisch vergleichbar
+        Some(local) if json_text(row.get("updated_at")) > json_text(local.get("updated_at")) => {
+            ImportChange::Update
+        }
+        Some(_) => ImportChange::Conflict,
+    };
+    Ok(ImportItem {
+        entity,
+        uuid: json_text(row.get("uuid")),
+        change,
+        label: describe_row(entity, row),
+        differences,
+    })
+}
+
+/// Vergleicht eine Sicherung mit dem lokalen Stand, ohne etwas zu ändern
+///
+/// Je Eintrag: neu, aktualisiert (Sicherung neuer), unverändert oder
+/// Konflikt (lokal neuer). Die Entscheidungen gehen als
+/// [`ImportMode::Decisions`] an [`import_from_zip`].
+pub fn preview_zip_import(
+    conn: &Connection,
+    import_path: &Path,
+    passphrase: Option<&Passphrase>,
+) -> Result<ZipImportPreview, AppError> {
+    let mut archive = open_backup(import_path, passphrase)?;
+
+    let mut preview = ZipImportPreview::default();
+    for (entity, name, key) in [
+        (ImportEntity::Coop, "data/coops.json", "coops"),
+        (ImportEntity::Quail, "data/quails.json", "quails"),
+        (ImportEntity::Event, "data/events.json", "events"),
+        (
+            ImportEntity::EggRecord,
+            "data/egg_records.json",
+            "egg_records",
+        ),
+        (ImportEntity::Photo, "data/photos.json", "photos"),
+    ] {
+        for row in zip_rows(&mut archive, name, key)? {
+            preview.items.push(classify_row(conn, entity, &row)?);
+        }
+    }
+    Ok(preview)
+}
+
+/// Wendet die Entscheidung auf eine importierte Zeile an: `None` =
+/// überspringen, sonst die (ggf. umgeschriebene) Zeile zum Upsert
+fn resolve_row(
+    conn: &Connection,
+    entity: ImportEntity,
+    row: &serde_json::Value,
+    mode: &ImportMode,
+) -> Result<Option<serde_json::Value>, AppError> {
+    let Some(local) = local_row(conn, entity, row)? else {
+        return Ok(Some(row.clone()));
+    };
+    let uuid = json_text(row.get("uuid"));
+    let choice = match mode {
+        ImportMode::MergePreferImport => ConflictChoice::TakeImport,
+        ImportMode::Decisions(decisions) => decisions.choice(&uuid),
+    };
+    let mut row = row.clone();
+    match choice {
+        ConflictChoice::KeepLocal => return Ok(None),
+        ConflictChoice::KeepBoth if entity.can_keep_both() => {
+            row["uuid"] = crate::models::new_entity_id().to_string().into();
+            if entity == ImportEntity::Quail {
+                row["name"] = format!("{} (Import)", json_text(row.get("name"))).into();
+            }
+        }
+        // Gleicher Tag, andere UUID: den lokalen Eintrag überschreiben
+        _ => row["uuid"] = local["uuid"].clone(),
+    }
+    Ok(Some(row))
+}
+
+/// Spielt eine Sicherung ein; verschlüsselte brauchen `passphrase`
+pub async fn import_from_zip(
+    conn: &Connection,
+    import_path: &Path,
+    mode: ImportMode,
+    passphrase: Option<&Passphrase>,
+) -> Result<(), AppError> {
+    let mut archive = open_backup(import_path, passphrase)?;
+
+    // Ältere Sicherungen haben noch keine Ställe
+    let coops = zip_rows(&mut archive, "data/coops.json", "coops")?;
+    let quails_v = read_zip_json(&mut archive, "data/quails.json")?;
+    let events_v = read_zip_json(&mut archive, "data/events.json")?;
+    let egg_records_v = read_zip_json(&mut archive, "data/egg_records.json")?;
+    let photos_v = read_zip_json(&mut archive, "data/photos.json")?;
+
+    let tx = conn.unchecked_transaction()?;
+    // Wachteln verweisen auf Fotos, die erst danach kommen: Fremdschlüssel
+    // erst beim Commit prüfen
+    tx.execute_batch("PRAGMA defer_foreign_keys = ON")?;
+
+    // Übernommene Datensätze überschreiben die mit derselben UUID
+    // (Upsert statt DELETE: das Löschen würde per Fremdschlüssel Ereignisse
+    // und Foto-Verweise der Wachtel mitnehmen); was übernommen wird,
+    // entscheidet resolve_row
+    for c in &coops {
+        let Some(c) = resolve_row(&tx, ImportEntity::Coop, c, &mode)? else {
+            continue;
+        };
+        let json_str = serde_json::to_string(&c).unwrap_or_default();
+        tx.execute(
+            "INSERT INTO coops (uuid, name, notes, created_at, updated_at, rev, logical_clock, deleted) VALUES (
+                json_extract(?1, '$.uuid'),
+                json_extract(?1, '$.name'),
+                json_extract(?1, '$.notes'),
+                COALESCE(json_extract(?1, '$.created_at'), CURRENT_TIMESTAMP),
+                COALESCE(json_extract(?1, '$.updated_at'), CURRENT_TIMESTAMP),
+                COALESCE(json_extract(?1, '$.rev'), 0),
+                COALESCE(json_extract(?1, '$.logical_clock'), 0),
+                COALESCE(json_extract(?1, '$.deleted'), 0)
+            )
+            ON CONFLICT(uuid) DO UPDATE SET
+                name = excluded.name,
+                notes = excluded.notes,
+                created_at = excluded.created_at,
+                updated_at = excluded.updated_at,
+                rev = excluded.rev,
+                logical_clock = excluded.logical_clock,
+                deleted = excluded.deleted",
+            [json_str],
+        )?;
+    }
+
+    if let Some(serde_json::Value::Object(obj)) = quails_v {
+        if let Some(serde_json::Value::Array(quails)) = obj.get("quails") {
+            for q in quails {
+                let Some(q) = resolve_row(&tx, ImportEntity::Quail, q, &mode)? else {
+                    continue;
+                };
+                let json_str = serde_json::to_string(&q).unwrap_or_default();
+                tx.execute("INSERT INTO quails (uuid, name, gender, ring_color, profile_photo, mother_id, father_id, coop_id, created_at, updated_at, rev, logical_clock, deleted) VALUES (
+                    json_extract(?1, '$.uuid'),
+                    json_extract(?1, '$.name'),
+                    json_extract(?1, '$.gender'),
+                    json_extract(?1, '$.ring_color'),
+                    json_extract(?1, '$.profile_photo'),
+                    json_extract(?1, '$.mother_id'),
+                    json_extract(?1, '$.father_id'),
+                    json_extract(?1, '$.coop_id'),
+                    COALESCE(json_extract(?1, '$.created_at'), CURRENT_TIMESTAMP),
+                    COALESCE(json_extract(?1, '$.updated_at'), CURRENT_TIMESTAMP),
+                    COALESCE(json_extract(?1, '$.rev'), 0),
+                    COALESCE(json_extract(?1, '$.logical_clock'), 0),
+                    COALESCE(json_extract(?1, '$.deleted'), 0)
+                )
+                ON CONFLICT(uuid) DO UPDATE SET
+                    name = excluded.name,
+                    gender = excluded.gender,
+                    ring_color = excluded.ring_color,
+                    profile_photo = excluded.profile_photo,
+                    mother_id = excluded.mother_id,
+                    father_id = excluded.father_id,
+                    coop_id = excluded.coop_id,
+                    created_at = excluded.created_at,
+                    updated_at = excluded.updated_at,
+                    rev = excluded.rev,
+                    logical_clock = excluded.logical_clock,
+                    deleted = excluded.deleted", [json_str])?;
+            }
+        }
+    }
+
+    if let Some(serde_json::Value::Object(obj)) = events_v {
+        if let Some(serde_json::Value::Array(events)) = obj.get("events") {
+            for e in events {
+                let Some(e) = resolve_row(&tx, ImportEntity::Event, e, &mode)? else {
+                    continue;
+                };
+                let json_str = serde_json::to_string(&e).unwrap_or_default();
+                tx.execute("INSERT INTO quail_events (uuid, quail_id, event_type, event_date, notes, medication, dose, duration_days, egg_withdrawal_days, meat_withdrawal_days, created_at, updated_at, rev, logical_clock, deleted) VALUES (
+                    json_extract(?1, '$.uuid'),
+                    json_extract(?1, '$.quail_id'),
+                    json_extract(?1, '$.event_type'),
+                    json_extract(?1, '$.event_date'),
+                    json_extract(?1, '$.notes'),
+                    json_extract(?1, '$.medication'),
+                    json_extract(?1, '$.dose'),
+                    json_extract(?1, '$.duration_days'),
+                    json_extract(?1, '$.egg_withdrawal_days'),
+                    json_extract(?1, '$.meat_withdrawal_days'),
+                    COALESCE(json_extract(?1, '$.created_at'), CURRENT_TIMESTAMP),
+                    COALESCE(json_extract(?1, '$.updated_at'), CURRENT_TIMESTAMP),
+                    COALESCE(json_extract(?1, '$.rev'), 0),
+                    COALESCE(json_extract(?1, '$.logical_clock'), 0),
+                    COALESCE(json_extract(?1, '$.deleted'), 0)
+                )
+                ON CONFLICT(uuid) DO UPDATE SET
+                    quail_id = excluded.quail_id,
+                    event_type = excluded.event_type,
+                    event_date = excluded.event_date,
+                    notes = excluded.notes,
+                    medication = excluded.medication,
+                    dose = excluded.dose,
+                    duration_days = excluded.duration_days,
+                    egg_withdrawal_days = excluded.egg_withdrawal_days,
+                    meat_withdrawal_days = excluded.meat_withdrawal_days,
+                    created_at = excluded.created_at,
+                    updated_at = excluded.updated_at,
+                    rev = excluded.rev,
+                    logical_clock = excluded.logical_clock,
+                    deleted = excluded.deleted", [json_str])?;
+            }
+        }
+    }
+
+    if let Some(serde_json::Value::Object(obj)) = egg_records_v {
+        if let Some(serde_json::Value::Array(records)) = obj.get("egg_records") {
+            for r in records {
+                let Some(r) = resolve_row(&tx, ImportEntity::EggRecord, r, &mode)? else {
+                    continue;
+                };
+                if let Some(uuid) = r.get("uuid").and_then(|v| v.as_str()) {
+                    tx.execute("DELETE FROM egg_records WHERE uuid = ?1", [uuid])?;
+                }
+                let json_str = serde_json::to_string(&r).unwrap_or_default();
+                tx.execute("INSERT INTO egg_records (uuid, record_date, total_eggs, notes, coop_id, created_at, updated_at, rev, logical_clock, deleted) VALUES (
+                    json_extract(?1, '$.uuid'),
+                    json_extract(?1, '$.record_date'),
+                    json_extract(?1, '$.total_eggs'),
+                    json_extract(?1, '$.notes'),
+                    json_extract(?1, '$.coop_id'),
+                    COALESCE(json_extract(?1, '$.created_at'), CURRENT_TIMESTAMP),
+                    COALESCE(json_extract(?1, '$.updated_at'), CURRENT_TIMESTAMP),
+                    COALESCE(json_extract(?1, '$.rev'), 0),
+                    COALESCE(json_extract(?1, '$.logical_clock'), 0),
+                    COALESCE(json_extract(?1, '$.deleted'), 0)
+                )", [json_str])?;
+            }
+        }
+    }
+
+    // Übernommene, nicht gelöschte Fotos mit ihrem Original
+    let mut imported_photos = Vec::new();
+    if let Some(serde_json::Value::Object(obj)) = photos_v {
+        if let Some(serde_json::Value::Array(photos)) = obj.get("photos") {
+            for p in photos {
+                let Some(p) = resolve_row(&tx, ImportEntity::Photo, p, &mode)? else {
+                    continue;
+                };
+                let original = ["relative_path", "path"]
+                    .iter()
+                    .filter_map(|column| p.get(*column).and_then(|v| v.as_str()))
+                    .find(|name| !name.is_empty());
+                let deleted = p.get("deleted").and_then(|v| v.as_i64()).unwrap_or(0) != 0;
+                if let (Some(uuid), Some(original), false) =
+                    (p.get("uuid").and_then(|v| v.as_str()), original, deleted)
+                {
+                    imported_photos.push((uuid.to_string(), original.to_string()));
+                }
+                let json_str = serde_json::to_string(&p).unwrap_or_default();
+                tx.execute("INSERT INTO photos (uuid, quail_id, event_id, path, relative_path, thumbnail_path, thumbnail_small_path, thumbnail_medium_path, sync_status, sync_error, last_sync_attempt, retry_count, created_at, updated_at, rev, logical_clock, deleted) VALUES (
+                    json_extract(?1, '$.uuid'),
+                    json_extract(?1, '$.quail_id'),
+                    json_extract(?1, '$.event_id'),
+                    json_extract(?1, '$.path'),
+                    json_extract(?1, '$.relative_path'),
+                    json_extract(?1, '$.thumbnail_path'),
+                    json_extract(?1, '$.thumbnail_small_path'),
+                    json_extract(?1, '$.thumbnail_medium_path'),
+                    COALESCE(json_extract(?1, '$.sync_status'), 'local_only'),
+                    json_extract(?1, '$.sync_error'),
+                    json_extract(?1, '$.last_sync_attempt'),
+                    COALESCE(json_extract(?1, '$.retry_count'), 0),
+                    COALESCE(json_extract(?1, '$.created_at'), CURRENT_TIMESTAMP),
+                    COALESCE(json_extract(?1, '$.updated_at'), CURRENT_TIMESTAMP),
+                    COALESCE(json_extract(?1, '$.rev'), 0),
+                    COALESCE(json_extract(?1, '$.logical_clock'), 0),
+                    COALESCE(json_extract(?1, '$.deleted'), 0)
+                )
+                ON CONFLICT(uuid) DO UPDATE SET
+                    quail_id = excluded.quail_id,
+                    event_id = excluded.event_id,
+                    path = excluded.path,
+                    relative_path = excluded.relative_path,
+                    thumbnail_path = excluded.thumbnail_path,
+                    thumbnail_small_path = excluded.thumbnail_small_path,
+                    thumbnail_medium_path = excluded.thumbnail_medium_path,
+                    sync_status = excluded.sync_status,
+                    sync_error = excluded.sync_error,
+                    last_sync_attempt = excluded.last_sync_attempt,
+                    retry_count = excluded.retry_count,
+                    created_at = excluded.created_at,
+                    updated_at = excluded.updated_at,
+                    rev = excluded.rev,
+                    logical_clock = excluded.logical_clock,
+                    deleted = excluded.deleted", [json_str])?;
+            }
+        }
+    }
+
+    // Verweise auf Einträge, die nicht im Export stehen
+    integrity::repair_orphans(&tx)?;
+    tx.commit()?;
+
+    // Fotos extrahieren
+    let photos_base = PathBuf::from(get_absolute_photo_path(""));
+    fs::create_dir_all(&photos_base).map_err(|e| {
+        AppError::Other(format!(
+            "Fehler beim Erstellen des Foto-Verzeichnisses: {}",
+            e
+        ))
+    })?;
+
+    let num_files = archive.len();
+    for i in 0..num_files {
+        let mut file = archive
+            .by_index(i)
+            .map_err(|e| AppError::Other(format!("Fehler beim Zugriff auf ZIP-Eintrag: {}", e)))?;
+        let name = file.name().to_string();
+        if name.starts_with("photos/") && !name.ends_with('/') {
+            let rel = &name["photos/".len()..];
+            let target_path = photos_base.join(rel);
+            ensure_parent_dir(&target_path)?;
+            let mut out_file = fs::File::create(&target_path).map_err(|e| {
+                AppError::Other(format!("Fehler beim Erstellen einer Fotodatei: {}", e))
+            })?;
+            std::io::copy(&mut file, &mut out_file).map_err(|e| {
+                AppError::Other(format!("Fehler beim Schreiben einer Fotodatei: {}", e))
+            })?;
+        }
+    }
+
+    mark_missing_originals(conn, &imported_photos)?;
+    Ok(())
+}
+
+/// Fotos, deren Original nicht im Export war (z.B. bei
+/// [`PhotoExport::Reduced`]), warten auf den Download: Der nächste Sync
+/// bzw. das Öffnen des Fotos holt das Original, bis dahin zeigt die App die
+/// Vorschaubilder. Als `local_only` würden sie dagegen als verloren gelten.
+fn mark_missing_originals(
+    conn: &Connection,
+    photos: &[(String, String)],
+) -> Result<usize, AppError> {
+    let mut pending = 0;
+    for (uuid, original) in photos {
+        if Path::new(&get_absolute_photo_path(original)).exists() {
+            continue;
+        }
+        pending += conn.execute(
+            "UPDATE photos SET sync_status = 'download_pending', retry_count = 0, sync_error = NULL
+             WHERE uuid = ?1 AND deleted = 0",
+            [uuid],
+        )?;
+    }
+    if pending > 0 {
+        log::info!(
+            "{} importierte Fotos ohne Original, werden später heruntergeladen",
+            pending
+        );
+    }
+    Ok(pending)
+}
+
+/// Inhalt einer Tabelle für den Tabellen-Import
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportKind {
+    Quails,
+    EggRecords,
+}
+
+impl ImportKind {
+    /// Felder, denen Spalten zugeordnet werden können
+    pub fn fields(self) -> &'static [ImportField] {
+        match self {
+            ImportKind::Quails => &[
+                ImportField::Name,
+                ImportField::Gender,
+                ImportField::RingColor,
+            ],
+            ImportKind::EggRecords => &[
+                ImportField::Date,
+                ImportField::TotalEggs,
+                ImportField::Notes,
+            ],
+        }
+    }
+}
+
+/// Zielfeld einer Tabellenspalte
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportField {
+    Name,
+    Gender,
+    RingColor,
+    Date,
+    TotalEggs,
+    Notes,
+}
+
+impl ImportField {
+    pub fn display_name(self) -> &'static str {
+        match self {
+            ImportField::Name => "Name",
+            ImportField::Gender => "Geschlecht",
+            ImportField::RingColor => "Ringfarbe",
+            ImportField::Date => "Datum",
+            ImportField::TotalEggs => "Anzahl Eier",
+            ImportField::Notes => "Notizen",
+        }
+    }
+
+    /// Ohne dieses Feld lässt sich kein Datensatz anlegen
+    pub fn is_required(self) -> bool {
+        matches!(
+            self,
+            ImportField::Name | ImportField::Date | ImportField::TotalEggs
+        )
+    }
+
+    /// Spaltenüberschriften (klein geschrieben), die [`guess_mapping`] erkennt
+    fn header_aliases(self) -> &'static [&'static str] {
+        match self {
+            ImportField::Name => &["name", "wachtel", "bezeichnung"],
+            ImportField::Gender => &["geschlecht", "gender", "sex"],
+            ImportField::RingColor => &["ringfarbe", "ring", "ring_color", "ring color", "farbe"],
+            ImportField::Date => &["datum", "date", "record_date", "tag"],
+            ImportField::TotalEggs => &["eier", "anzahl", "anzahl eier", "total_eggs", "eggs"],
+            ImportField::Notes => &["notizen", "notiz", "notes", "bemerkung", "bemerkungen"],
+        }
+    }
+}
+
+/// Zuordnung Tabellenspalte → Feld (Index = Spalte, `None` = ignorieren)
+pub type ColumnMapping = Vec<Option<ImportField>>;
+
+/// Schlägt eine Zuordnung anhand der Spaltenüberschriften vor
+pub fn guess_mapping(kind: ImportKind, headers: &[String]) -> ColumnMapping {
+    let mut mapping: ColumnMapping = vec![None; headers.len()];
+    for (column, header) in headers.iter().enumerate() {
+        let header = header.trim().to_lowercase();
+        mapping[column] = kind.fields().iter().copied().find(|field| {
+            field.header_aliases().contains(&header.as_str()) && !mapping.contains(&Some(*field))
+        });
+    }
+    mapping
+}
+
+/// Fehler in einer Tabellenzeile; `row` wie in der Tabellenkalkulation
+/// gezählt (Überschrift = Zeile 1)
+#[derive(Debug, Clone, PartialEq)]
+pub struct RowError {
+    pub row: usize,
+    pub message: String,
+}
+
+/// Ergebnis der Prüfung vor dem Import
+#[derive(Debug, Clone, Default)]
+pub struct ImportPreview {
+    pub quails: Vec<crate::models::Quail>,
+    pub egg_records: Vec<crate::models::EggRecord>,
+    pub errors: Vec<RowError>,
+}
+
+impl ImportPreview {
+    /// Anzahl der Zeilen, die importiert würden
+    pub fn valid_count(&self) -> usize {
+        self.quails.len() + self.egg_records.len()
+    }
+}
+
+/// Was [`apply_import`] getan hat
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ImportSummary {
+    pub created: usize,
+    pub updated: usize,
+    pub skipped: usize,
+}
+
+pub(crate) fn error_message(error: AppError) -> String {
+    match error {
+        AppError::Validation(message) => message,
+        other => other.to_string(),
+    }
+}
+
+pub(crate) fn parse_import_date(value: &str) -> Result<chrono::NaiveDate, String> {
+    // Uhrzeit aus Datum+Zeit-Zellen abschneiden
+    let date = value.split([' ', 'T']).next().unwrap_or_default();
+    for format in ["%Y-%m-%d", "%d.%m.%Y", "%d/%m/%Y"] {
+        if let Ok(date) = chrono::NaiveDate::parse_from_str(date, format) {
+            return Ok(date);
+        }
+    }
+    value
+        .parse::<f64>()
+        .ok()
+        .and_then(crate::spreadsheet::excel_serial_date)
+        .ok_or_else(|| format!("Ungültiges Datum „{}“", value))
+}
+
+pub(crate) fn parse_egg_count(value: &str) -> Result<i32, String> {
+    // Excel-Zahlen kommen als "12" oder "12.0", deutsche CSV auch als "12,0"
+    match value.replace(',', ".").parse::<f64>() {
+        Ok(count) if count.fract() == 0.0 && count.abs() < i32::MAX as f64 => Ok(count as i32),
+        _ => Err(format!("Ungültige Eieranzahl „{}“", value)),
+    }
+}
+
+fn parse_gender(value: &str) -> Result<crate::models::Gender, String> {
+    use crate::models::Gender;
+    match value.to_lowercase().as_str() {
+        "m" | "männlich" | "maennlich" | "male" | "hahn" => Ok(Gender::Male),
+        "w" | "f" | "weiblich" | "female" | "henne" => Ok(Gender::Female),
+        "" | "?" | "unbekannt" | "unknown" => Ok(Gender::Unknown),
+        _ => Err(format!("Unbekanntes Geschlecht „{}“", value)),
+    }
+}
+
+fn parse_ring_color(value: &str) -> Result<Option<crate::models::RingColor>, String> {
+    if value.is_empty() {
+        return Ok(None);
+    }
+    let lower = value.to_lowercase();
+    // Nicht RingColor::from_str: das fällt bei Unbekanntem still auf Lila zurück
+    crate::models::RingColor::all()
+        .iter()
+        .find(|color| color.as_str() == lower || color.display_name().to_lowercase() == lower)
+        .cloned()
+        .map(Some)
+        .ok_or_else(|| format!("Unbekannte Ringfarbe „{}“", value))
+}
+
+enum ImportRecord {
+    Quail(crate::models::Quail),
+    EggRecord(crate::models::EggRecord),
+}
+
+fn quail_from_row<'a>(
+    value: impl Fn(ImportField) -> &'a str,
+) -> Result<crate::models::Quail, String> {
+    let mut quail = crate::models::Quail::new(value(ImportField::Name).to_string());
+    quail.gender = parse_gender(value(ImportField::Gender))?;
+    quail.ring_color = parse_ring_color(value(ImportField::RingColor))?;
+    quail.validate().map_err(error_message)?;
+    Ok(quail)
+}
+
+fn egg_record_from_row<'a>(
+    value: impl Fn(ImportField) -> &'a str,
+) -> Result<crate::models::EggRecord, String> {
+    let date = parse_import_date(value(ImportField::Date))?;
+    let mut record =
+        crate::models::EggRecord::new(date, parse_egg_count(value(ImportField::TotalEggs))?);
+    let notes = value(ImportField::Notes);
+    record.notes = (!notes.is_empty()).then(|| notes.to_string());
+    record.validate().map_err(error_message)?;
+    Ok(record)
+}
+
+/// Prüft alle Zeilen der Tabelle mit der gewählten Zuordnung
+///
+/// Fehlerhafte Zeilen landen mit Begründung in `errors`, der Rest kann mit
+/// [`apply_import`] übernommen werden. Fehler nur, wenn ein Pflichtfeld
+/// keiner Spalte zugeordnet ist.
+pub fn preview_import(
+    kind: ImportKind,
+    table: &crate::spreadsheet::Table,
+    mapping: &ColumnMapping,
+) -> Result<ImportPreview, AppError> {
+    let column = |field| mapping.iter().position(|m| *m == Some(field));
+    if let Some(missing) = kind
+        .fields()
+        .iter()
+        .find(|field| field.is_required() && column(**field).is_none())
+    {
+        return Err(AppError::Validation(format!(
+            "Keine Spalte für „{}“ gewählt",
+            missing.display_name()
+        )));
+    }
+
+    let mut preview = ImportPreview::default();
+    // Schlüssel (Name bzw. Datum) → erste Zeile, für Doppelte in der Datei
+    let mut seen = std::collections::HashMap::new();
+    for index in 0..table.rows.len() {
+        let row = index + 2;
+        let value = |field| column(field).map_or("", |c| table.cell(index, c));
+        let result = match kind {
+            ImportKind::Quails => quail_from_row(value).map(|quail| {
+                (
+                    quail.name.trim().to_lowercase(),
+                    "Name",
+                    ImportRecord::Quail(quail),
+                )
+            }),
+            ImportKind::EggRecords => egg_record_from_row(value).map(|record| {
+                (
+                    record.record_date.to_string(),
+                    "Datum",
+                    ImportRecord::EggRecord(record),
+                )
+            }),
+        };
+        let message = match result {
+            Ok((key, label, record)) => match seen.entry(key) {
+                std::collections::hash_map::Entry::Occupied(first) => {
+                    format!("{} bereits in Zeile {}", label, first.get())
+                }
+                std::collections::hash_map::Entry::Vacant(slot) => {
+                    slot.insert(row);
+                    match record {
+                        ImportRecord::Quail(quail) => preview.quails.push(quail),
+                        ImportRecord::EggRecord(record) => preview.egg_records.push(record),
+                    }
+                    continue;
+                }
+            },
+            Err(message) => message,
+        };
+        preview.errors.push(RowError { row, message });
+    }
+    Ok(preview)
+}
+
+/// Übernimmt die gültigen Zeilen einer [`ImportPreview`]
+///
+/// Wachteln, deren Name es schon gibt, werden übersprungen. Eier-Einträge
+/// für einen vorhandenen Tag werden überschrieben (leere Notizen behalten
+/// die alten), unveränderte übersprungen.
+pub async fn apply_import(
+    conn: &Connection,
+    preview: &ImportPreview,
+) -> Result<ImportSummary, AppError> {
+    let mut summary = ImportSummary::default();
+
+    let existing: std::collections::HashSet<String> =
+        crate::services::list_profiles_with_status(conn, None, false)?
+            .into_iter()
+            .map(|quail| quail.name.trim().to_lowercase())
+            .collect();
+    for quail in &preview.quails {
+        if existing.contains(&quail.name.trim().to_lowercase()) {
+            summary.skipped += 1;
+        } else {
+            crate::services::create_profile(conn, quail).await?;
+            summary.created += 1;
+        }
+    }
+
+    for record in &preview.egg_records {
+        let date = record.record_date.format("%Y-%m-%d").to_string();
+        match crate::services::find_egg_record(conn, &date, record.coop_id.as_ref())? {
+            Some(current) => {
+                let updated = crate::models::EggRecord {
+                    uuid: current.uuid,
+                    notes: record.notes.clone().or(current.notes.clone()),
+                    ..record.clone()
+                };
+                if updated == current {
+                    summary.skipped += 1;
+                } else {
+                    crate::services::update_egg_record(conn, &updated).await?;
+                    summary.updated += 1;
+                }
+            }
+            None => {
+                crate::services::add_egg_record(conn, record).await?;
+                summary.created += 1;
+            }
+        }
+    }
+
+    log::info!(
+        "Tabellen-Import: {} neu, {} aktualisiert, {} übersprungen",
+        summary.created,
+        summary.updated,
+        summary.skipped
+    );
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::schema;
+    use crate::models::EggRecord;
+    use chrono::NaiveDate;
+
+    #[tokio::test]
+    async fn test_egg_records_csv() {
+        let conn = Connection::open_in_memory().unwrap();
+        schema::init_schema(&conn).unwrap();
+        for (day, eggs, notes) in [
+            (3, 5, Some("Stall \"Nord\", nass")),
+            (1, 4, None),
+            (10, 7, Some("außerhalb")),
+        ] {
+            let mut record = EggRecord::new(NaiveDate::from_ymd_opt(2025, 3, day).unwrap(), eggs);
+            record.notes = notes.map(str::to_string);
+            crate::services::add_egg_record(&conn, &record)
+                .await
+                .unwrap();
+        }
+
+        let coop = crate::models::Coop::new("Voliere".to_string());
+        crate::services::coop_service::save_coop(&conn, &coop)
+            .await
+            .unwrap();
+        let mut record = EggRecord::new(NaiveDate::from_ymd_opt(2025, 3, 1).unwrap(), 2);
+        record.coop_id = Some(coop.uuid);
+        crate::services::add_egg_record(&conn, &record)
+            .await
+            .unwrap();
+
+        let csv = egg_records_csv(&conn, Some("2025-03-01"), Some("2025-03-05")).unwrap();
+        let mut lines: Vec<&str> = csv.split_terminator("\r\n").collect();
+        // Reihenfolge zweier Einträge desselben Tages ist nicht festgelegt
+        lines[1..3].sort();
+        assert_eq!(
+            lines,
+            [
+                "date,total_eggs,notes,coop",
+                "2025-03-01,2,,Voliere",
+                "2025-03-01,4,,",
+                "2025-03-03,5,\"Stall \"\"Nord\"\", nass\",",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_guess_mapping() {
+        let headers: Vec<String> = ["Datum", "Wetter", "Anzahl", "Bemerkung"]
+            .into_iter()
+            .map(String::from)
+            .collect();
+        assert_eq!(
+            guess_mapping(ImportKind::EggRecords, &headers),
+            vec![
+                Some(ImportField::Date),
+                None,
+                Some(ImportField::TotalEggs),
+                Some(ImportField::Notes)
+            ]
+        );
+        assert_eq!(guess_mapping(ImportKind::Quails, &headers), vec![None; 4]);
+    }
+
+    #[test]
+    fn test_preview_reports_row_errors() {
+        let table = crate::spreadsheet::parse_csv(
+            "Name;Geschlecht;Ring\nErna;w;Weiß\n;m;\nOtto;Hahn;türkis\nerna;?;\nKarl;x;rot\n",
+        );
+        let mapping = guess_mapping(ImportKind::Quails, &table.headers);
+        let preview = preview_import(ImportKind::Quails, &table, &mapping).unwrap();
+        assert_eq!(preview.quails.len(), 1);
+        assert_eq!(
+            preview.quails[0].ring_color,
+            Some(crate::models::RingColor::Weiss)
+        );
+        let rows: Vec<usize> = preview.errors.iter().map(|e| e.row).collect();
+        assert_eq!(rows, vec![3, 4, 5, 6]);
+        assert_eq!(preview.errors[2].message, "Name bereits in Zeile 2");
+
+        // Ohne Datumsspalte kein Import
+        let eggs = crate::spreadsheet::parse_csv("Eier\n4\n");
+        assert!(preview_import(
+            ImportKind::EggRecords,
+            &eggs,
+            &vec![Some(ImportField::TotalEggs)]
+        )
+        .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_apply_import_updates_existing_days() {
+        let conn = Connection::open_in_memory().unwrap();
+        schema::init_schema(&conn).unwrap();
+        let mut existing = EggRecord::new(NaiveDate::from_ymd_opt(2025, 3, 1).unwrap(), 2);
+        existing.notes = Some("alt".to_string());
+        crate::services::add_egg_record(&conn, &existing)
+            .await
+            .unwrap();
+
+        let table = crate::spreadsheet::parse_csv(
+            "date,total_eggs,notes\n2025-03-01,5,\n02.03.2025,\"6,0\",neu\n45719,7,\n2025-13-01,1,\nx,1,\n",
+        );
+        let mapping = guess_mapping(ImportKind::EggRecords, &table.headers);
+        let preview = preview_import(ImportKind::EggRecords, &table, &mapping).unwrap();
+        assert_eq!(preview.errors.len(), 2);
+        assert_eq!(
+            preview.egg_records[2].record_date,
+            NaiveDate::from_ymd_opt(2025, 3, 3).unwrap()
+        );
+
+        let summary = apply_import(&conn, &preview).await.unwrap();
+        assert_eq!(
+            summary,
+            ImportSummary {
+                created: 2,
+                updated: 1,
+                skipped: 0
+            }
+        );
+        let updated = crate::services::get_egg_record(&conn, "2025-03-01", None).unwrap();
+        assert_eq!(updated.uuid, existing.uuid);
+        assert_eq!(updated.total_eggs, 5);
+        assert_eq!(updated.notes.as_deref(), Some("alt"));
+
+        // Zweiter Durchlauf ändert nichts mehr
+        let again = apply_import(&conn, &preview).await.unwrap();
+        assert_eq!(again.skipped, 3);
+    }
+
+    fn zip_json(
+        zip: &mut zip::ZipArchive<std::io::Cursor<Vec<u8>>>,
+        name: &str,
+    ) -> serde_json::Value {
+        let mut text = String::new();
+        zip.by_name(name)
+            .unwrap()
+            .read_to_string(&mut text)
+            .unwrap();
+        serde_json::from_str(&text).unwrap()
+    }
+
+    fn uuids(value: &serde_json::Value, key: &str) -> Vec<String> {
+        value[key]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|row| row["uuid"].as_str().unwrap().to_string())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_export_selection() {
+        use crate::models::{EventType, Quail, QuailEvent};
+
+        let conn = Connection::open_in_memory().unwrap();
+        schema::init_schema(&conn).unwrap();
+        let erna = Quail::new("Erna".to_string());
+        let otto = Quail::new("Otto".to_string());
+        for quail in [&erna, &otto] {
+            crate::services::create_profile(&conn, quail).await.unwrap();
+        }
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+        let old = QuailEvent::new(erna.uuid, EventType::Born, date(2024, 5, 1));
+        let sick = QuailEvent::new(erna.uuid, EventType::Sick, date(2025, 2, 1));
+        let other = QuailEvent::new(otto.uuid, EventType::Sick, date(2025, 2, 2));
+        for event in [&old, &sick, &other] {
+            conn.execute(
+                "INSERT INTO quail_events (uuid, quail_id, event_type, event_date) VALUES (?1, ?2, ?3, ?4)",
+                rusqlite::params![
+                    event.uuid.to_string(),
+                    event.quail_id.to_string(),
+                    event.event_type.as_str(),
+                    event.event_date.to_string()
+                ],
+            )
+            .unwrap();
+        }
+        for (uuid, quail, event) in [
+            ("p-gallery", Some(erna.uuid), None),
+            ("p-old", None, Some(old.uuid)),
+            ("p-sick", None, Some(sick.uuid)),
+            ("p-otto", Some(otto.uuid), None),
+        ] {
+            conn.execute(
+                "INSERT INTO photos (uuid, quail_id, event_id, path, relative_path, thumbnail_small_path)
+                 VALUES (?1, ?2, ?3, '', ?1 || '.jpg', ?1 || '_small.webp')",
+                rusqlite::params![uuid, quail.map(|q| q.to_string()), event.map(|e| e.to_string())],
+            )
+            .unwrap();
+        }
+        conn.execute(
+            "UPDATE quails SET profile_photo = 'p-otto' WHERE uuid = ?1",
+            [erna.uuid.to_string()],
+        )
+        .unwrap();
+        crate::services::add_egg_record(&conn, &EggRecord::new(date(2025, 3, 1), 4))
+            .await
+            .unwrap();
+
+        let filter = ExportFilter {
+            start_date: Some(date(2025, 1, 1)),
+            end_date: Some(date(2025, 12, 31)),
+            quail_ids: vec![erna.uuid],
+            include_egg_records: false,
+            photos: PhotoExport::ThumbnailsOnly,
+            passphrase: None,
+        };
+        let bytes = write_export(&conn, &filter, std::io::Cursor::new(Vec::new()))
+            .unwrap()
+            .into_inner();
+        let mut zip = zip::ZipArchive::new(std::io::Cursor::new(bytes)).unwrap();
+
+        let quails = zip_json(&mut zip, "data/quails.json");
+        assert_eq!(uuids(&quails, "quails"), vec![erna.uuid.to_string()]);
+        // Ottos Foto fehlt im Export, also kein Profilfoto
+        assert!(quails["quails"][0]["profile_photo"].is_null());
+        let events = zip_json(&mut zip, "data/events.json");
+        assert_eq!(uuids(&events, "events"), vec![sick.uuid.to_string()]);
+        let eggs = zip_json(&mut zip, "data/egg_records.json");
+        assert!(uuids(&eggs, "egg_records").is_empty());
+        let mut photos = uuids(&zip_json(&mut zip, "data/photos.json"), "photos");
+        photos.sort();
+        assert_eq!(photos, vec!["p-gallery", "p-sick"]);
+
+        // Ohne Auswahl alles
+        let bytes = write_export(
+            &conn,
+            &ExportFilter::default(),
+            std::io::Cursor::new(Vec::new()),
+        )
+        .unwrap()
+        .into_inner();
+        let mut zip = zip::ZipArchive::new(std::io::Cursor::new(bytes)).unwrap();
+        assert_eq!(
+            uuids(&zip_json(&mut zip, "data/events.json"), "events").len(),
+            3
+        );
+        assert_eq!(
+            uuids(&zip_json(&mut zip, "data/photos.json"), "photos").len(),
+            4
+        );
+        assert_eq!(
+            uuids(&zip_json(&mut zip, "data/egg_records.json"), "egg_records").len(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_reduced_export_contains_medium_thumbnails_only() {
+        use serde_json::json;
+
+        let photos = vec![
+            json!({"uuid": "p1", "relative_path": "p1.jpg", "path": "", "deleted": 0,
+                   "thumbnail_small_path": "p1_small.webp", "thumbnail_medium_path": "p1_medium.webp"}),
+            json!({"uuid": "p2", "relative_path": "p2.jpg", "path": "", "deleted": 1,
+                   "thumbnail_small_path": "p2_small.webp", "thumbnail_medium_path": "p2_medium.webp"}),
+        ];
+        assert_eq!(photo_files(&photos, PhotoExport::Originals), ["p1.jpg"]);
+        assert_eq!(
+            photo_files(&photos, PhotoExport::ThumbnailsOnly),
+            ["p1_small.webp", "p1_medium.webp"]
+        );
+        assert_eq!(
+            photo_files(&photos, PhotoExport::Reduced),
+            ["p1_medium.webp"]
+        );
+        assert!(photo_files(&photos, PhotoExport::None).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_import_without_originals_waits_for_download() {
+        use serde_json::json;
+
+        let conn = Connection::open_in_memory().unwrap();
+        schema::init_schema(&conn).unwrap();
+
+        let quail = uuid::Uuid::new_v4().to_string();
+        let photo = uuid::Uuid::new_v4().to_string();
+        let path =
+            std::env::temp_dir().join(format!("import-reduced-{}.zip", uuid::Uuid::new_v4()));
+        let mut zip = zip::ZipWriter::new(fs::File::create(&path).unwrap());
+        write_zip_json(
+            &mut zip,
+            "data/quails.json",
+            &json!({"quails": [{"uuid": quail, "name": "Erna", "gender": "female", "deleted": 0}]}),
+        )
+        .unwrap();
+        // Nur lokal vorhandenes Foto, dessen Original nicht im ZIP liegt
+        write_zip_json(
+            &mut zip,
+            "data/photos.json",
+            &json!({"photos": [{
+                "uuid": photo, "quail_id": quail, "path": "",
+                "relative_path": format!("{}.jpg", photo),
+                "thumbnail_medium_path": format!("{}_medium.webp", photo),
+                "sync_status": "local_only", "retry_count": 3, "deleted": 0
+            }]}),
+        )
+        .unwrap();
+        zip.finish().unwrap();
+
+        import_from_zip(&conn, &path, ImportMode::MergePreferImport, None)
+            .await
+            .unwrap();
+        let _ = fs::remove_file(&path);
+
+        let (status, retries): (String, i32) = conn
+            .query_row(
+                "SELECT sync_status, retry_count FROM photos WHERE uuid = ?1",
+                [&photo],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .unwrap();
+        assert_eq!((status.as_str(), retries), ("download_pending", 0));
+    }
+
+    #[cfg(feature = "encryption")]
+    #[tokio::test]
+    async fn test_encrypted_export_needs_passphrase() {
+        let conn = Connection::open_in_memory().unwrap();
+        schema::init_schema(&conn).unwrap();
+        let quail = crate::models::Quail::new("Erna".to_string());
+        crate::services::create_profile(&conn, &quail)
+            .await
+            .unwrap();
+
+        let passphrase = Passphrase::new("Wachtelstall 2024").unwrap();
+        let filter = ExportFilter {
+            photos: PhotoExport::None,
+            passphrase: Some(passphrase.clone()),
+            ..Default::default()
+        };
+        let path = std::env::temp_dir().join(format!("export-{}.zip.enc", uuid::Uuid::new_v4()));
+        export_selection_to_path(&conn, &filter, &path).unwrap();
+        assert!(is_encrypted_backup(&path).unwrap());
+
+        // Ohne oder mit falscher Passphrase keine Vorschau
+        assert!(preview_zip_import(&conn, &path, None).is_err());
+        let wrong = Passphrase::new("falsche Passphrase").unwrap();
+        assert!(preview_zip_import(&conn, &path, Some(&wrong)).is_err());
+
+        let preview = preview_zip_import(&conn, &path, Some(&passphrase)).unwrap();
+        assert_eq!(
+            preview.count(ImportEntity::Quail, ImportChange::Unchanged),
+            1
+        );
+        let _ = fs::remove_file(&path);
+    }
+
+    #[tokio::test]
+    async fn test_zip_import_preview_and_decisions() {
+        use crate::models::Quail;
+        use serde_json::json;
+
+        let conn = Connection::open_in_memory().unwrap();
+        schema::init_schema(&conn).unwrap();
+        let [erna, otto, hans] = ["Erna", "Otto", "Hans"].map(|name| Quail::new(name.to_string()));
+        for quail in [&erna, &otto, &hans] {
+            crate::services::create_profile(&conn, quail).await.unwrap();
+        }
+        let local_eggs = EggRecord::new(NaiveDate::from_ymd_opt(2025, 3, 1).unwrap(), 4);
+        crate::services::add_egg_record(&conn, &local_eggs)
+            .await
+            .unwrap();
+
+        let quail = |uuid: String, name: &str, updated_at: &str| json!({"uuid": uuid, "name": name, "gender": "unknown", "updated_at": updated_at, "deleted": 0});
+        let frieda = uuid::Uuid::new_v4().to_string();
+        let imported_eggs = uuid::Uuid::new_v4().to_string();
+        let path =
+            std::env::temp_dir().join(format!("import-preview-{}.zip", uuid::Uuid::new_v4()));
+        let mut zip = zip::ZipWriter::new(fs::File::create(&path).unwrap());
+        write_zip_json(
+            &mut zip,
+            "data/quails.json",
+            &json!({"quails": [
+                quail(erna.uuid.to_string(), "Erna neu", "2999-01-01 00:00:00"),
+                quail(otto.uuid.to_string(), "Otto alt", "2000-01-01 00:00:00"),
+                quail(hans.uuid.to_string(), "Hans", "2000-01-01 00:00:00"),
+                quail(frieda.clone(), "Frieda", "2000-01-01 00:00:00"),
+            ]}),
+        )
+        .unwrap();
+        write_zip_json(
+            &mut zip,
+            "data/egg_records.json",
+            &json!({"egg_records": [{
+                "uuid": imported_eggs, "record_date": "2025-03-01", "total_eggs": 6,
+                "updated_at": "2000-01-01 00:00:00", "deleted": 0
+            }]}),
+        )
+        .unwrap();
+        zip.finish().unwrap();
+
+        let preview = preview_zip_import(&conn, &path, None).unwrap();
+        let change = |uuid: &str| {
+            preview
+                .items
+                .iter()
+                .find(|item| item.uuid == uuid)
+                .unwrap()
+                .change
+        };
+        assert_eq!(change(&erna.uuid.to_string()), ImportChange::Update);
+        assert_eq!(change(&otto.uuid.to_string()), ImportChange::Conflict);
+        assert_eq!(change(&hans.uuid.to_string()), ImportChange::Unchanged);
+        assert_eq!(change(&frieda), ImportChange::Add);
+        assert_eq!(change(&imported_eggs), ImportChange::Conflict);
+        assert_eq!(preview.count(ImportEntity::Quail, ImportChange::Add), 1);
+        let otto_item = preview
+            .items
+            .iter()
+            .find(|item| item.uuid == otto.uuid.to_string())
+            .unwrap();
+        assert_eq!(
+            otto_item.differences,
+            vec![(
+                "Name".to_string(),
+                "Otto".to_string(),
+                "Otto alt".to_string()
+            )]
+        );
+
+        let mut decisions = ImportDecisions::from_preview(&preview);
+        decisions.set(&otto.uuid.to_string(), ConflictChoice::KeepBoth);
+        decisions.set(&imported_eggs, ConflictChoice::TakeImport);
+        import_from_zip(&conn, &path, ImportMode::Decisions(decisions), None)
+            .await
+            .unwrap();
+        let _ = fs::remove_file(&path);
+
+        let mut names: Vec<String> = crate::services::list_profiles_with_status(&conn, None, false)
+            .unwrap()
+            .into_iter()
+            .map(|q| q.name)
+            .collect();
+        names.sort();
+        assert_eq!(
+            names,
+            vec!["Erna neu", "Frieda", "Hans", "Otto", "Otto alt (Import)"]
+        );
+        // Gleicher Tag: der lokale Eintrag behält seine UUID
+        let eggs = crate::services::get_egg_record(&conn, "2025-03-01", None).unwrap();
+        assert_eq!((eggs.uuid, eggs.total_eggs), (local_eggs.uuid, 6));
+    }
+}
@@ -0,0 +1,354 @@
+//! CSV-Exporte nach gespeicherten Vorlagen
+//!
+//! Eine [`ExportTemplate`] legt fest, welche Einträge (Wachteln, Ereignisse
+//! oder Eier-Einträge) und welche Spalten in der CSV landen, bei Ereignissen
+//! zusätzlich welche Typen. So bekommt z.B. der Tierarzt nur Krankheit,
+//! Genesung und Todesfälle. Vorlagen liegen in der Tabelle
+//! `export_templates` und werden nicht synchronisiert. Typen und Geschlecht
+//! stehen lesbar (deutsch) in der Datei, Daten als `YYYY-MM-DD`.
+
+use crate::error::AppError;
+use crate::models::{EventType, ExportTemplate, Gender, RingColor, TemplateEntity};
+use crate::services::export_import_service::{csv_field, write_csv_export};
+use rusqlite::{params, Connection};
+use std::path::PathBuf;
+
+fn template_from_row(row: &rusqlite::Row) -> rusqlite::Result<ExportTemplate> {
+    let entity: String = row.get(2)?;
+    let columns: String = row.get(3)?;
+    let event_types: String = row.get(4)?;
+    Ok(ExportTemplate {
+        id: Some(row.get(0)?),
+        name: row.get(1)?,
+        entity: TemplateEntity::parse(&entity).unwrap_or_default(),
+        columns: serde_json::from_str(&columns).unwrap_or_default(),
+        event_types: serde_json::from_str::<Vec<String>>(&event_types)
+            .unwrap_or_default()
+            .iter()
+            .map(|t| EventType::from_str(t))
+            .collect(),
+    })
+}
+
+/// Alle Vorlagen, nach Namen sortiert
+pub fn list_templates(conn: &Connection) -> Result<Vec<ExportTemplate>, AppError> {
+    let mut stmt = conn.prepare(
+        "SELECT id, name, entity, columns, event_types FROM export_templates
+         ORDER BY name COLLATE NOCASE",
+    )?;
+    let templates = stmt
+        .query_map([], template_from_row)?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(templates)
+}
+
+/// Legt die Vorlage an oder überschreibt sie (mit `id`); gibt die `id` zurück
+pub fn save_template(conn: &Connection, template: &ExportTemplate) -> Result<i64, AppError> {
+    let mut template = template.clone();
+    template.name = template.name.trim().to_string();
+    template.normalize();
+    template.validate()?;
+
+    let taken: bool = conn.query_row(
+        "SELECT COUNT(*) FROM export_templates WHERE name = ?1 AND id IS NOT ?2",
+        params![template.name, template.id],
+        |row| row.get::<_, i64>(0).map(|c| c > 0),
+    )?;
+    if taken {
+        return Err(AppError::Validation(format!(
+            "Vorlage „{}“ gibt es schon",
+            template.name
+        )));
+    }
+
+    let columns = serde_json::to_string(&template.columns)
+        .map_err(|e| AppError::Other(format!("Fehler beim Serialisieren: {}", e)))?;
+    let event_types = serde_json::to_string(
+        &template
+            .event_types
+            .iter()
+            .map(EventType::as_str)
+            .collect::<Vec<_>>(),
+    )
+    .map_err(|e| AppError::Other(format!("Fehler beim Serialisieren: {}", e)))?;
+
+    match template.id {
+        Some(id) => {
+            let changed = conn.execute(
+                "UPDATE export_templates SET name = ?2, entity = ?3, columns = ?4, event_types = ?5
+                 WHERE id = ?1",
+                params![
+                    id,
+                    template.name,
+                    template.entity.as_str(),
+                    columns,
+                    event_types
+                ],
+            )?;
+            if changed == 0 {
+                return Err(AppError::NotFound(format!("Vorlage {}", id)));
+            }
+            Ok(id)
+        }
+        None => {
+            conn.execute(
+                "INSERT INTO export_templates (name, entity, columns, event_types)
+                 VALUES (?1, ?2, ?3, ?4)",
+                params![
+                    template.name,
+                    template.entity.as_str(),
+                    columns,
+                    event_types
+                ],
+            )?;
+            Ok(conn.last_insert_rowid())
+        }
+    }
+}
+
+pub fn delete_template(conn: &Connection, id: i64) -> Result<(), AppError> {
+    conn.execute("DELETE FROM export_templates WHERE id = ?1", [id])?;
+    Ok(())
+}
+
+/// Zeilen als Spaltenschlüssel → Wert, in der Reihenfolge der Datei
+fn load_rows(
+    conn: &Connection,
+    template: &ExportTemplate,
+) -> Result<Vec<Vec<(&'static str, String)>>, AppError> {
+    let mut rows = Vec::new();
+    match template.entity {
+        TemplateEntity::Quails => {
+            let mut stmt = conn.prepare(
+                "SELECT name, gender, ring_color FROM quails WHERE deleted = 0
+                 ORDER BY name COLLATE NOCASE",
+            )?;
+            let quails = stmt.query_map([], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, Option<String>>(2)?,
+                ))
+            })?;
+            for quail in quails {
+                let (name, gender, ring_color) = quail?;
+                rows.push(vec![
+                    ("name", name),
+                    (
+                        "gender",
+                        Gender::from_str(&gender).display_name().to_string(),
+                    ),
+                    (
+                        "ring_color",
+                        ring_color
+                            .map(|c| RingColor::from_str(&c).display_name().to_string())
+                            .unwrap_or_default(),
+                    ),
+                ]);
+            }
+        }
+        TemplateEntity::Events => {
+            let mut stmt = conn.prepare(
+                "SELECT e.event_date, q.name, e.event_type, e.notes
+                 FROM quail_events e
+                 JOIN quails q ON q.uuid = e.quail_id
+                 WHERE e.deleted = 0 AND q.deleted = 0
+                 ORDER BY e.event_date, q.name COLLATE NOCASE",
+            )?;
+            let events = stmt.query_map([], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, String>(2)?,
+                    row.get::<_, Option<String>>(3)?,
+                ))
+            })?;
+            for event in events {
+                let (date, quail, event_type, notes) = event?;
+                let event_type = EventType::from_str(&event_type);
+                if !template.event_types.is_empty() && !template.event_types.contains(&event_type) {
+                    continue;
+                }
+                rows.push(vec![
+                    ("date", date),
+                    ("quail", quail),
+                    ("event_type", event_type.display_name().to_string()),
+                    ("notes", notes.unwrap_or_default()),
+                ]);
+            }
+        }
+        TemplateEntity::EggRecords => {
+            let mut records = crate::services::list_egg_records(conn, None, None)?;
+            records.reverse();
+            for record in records {
+                rows.push(vec![
+                    ("date", record.record_date.format("%Y-%m-%d").to_string()),
+                    ("total_eggs", record.total_eggs.to_string()),
+                    ("notes", record.notes.unwrap_or_default()),
+                ]);
+            }
+        }
+    }
+    Ok(rows)
+}
+
+/// Inhalt der CSV nach der Vorlage, Kopfzeile mit den Spaltenschlüsseln
+pub fn template_csv(conn: &Connection, template: &ExportTemplate) -> Result<String, AppError> {
+    let mut template = template.clone();
+    template.normalize();
+    template.validate()?;
+
+    let mut csv = template.columns.join(",");
+    csv.push_str("\r\n");
+    for row in load_rows(conn, &template)? {
+        let fields: Vec<String> = row
+            .iter()
+            .filter(|(key, _)| template.columns.iter().any(|c| c == key))
+            .map(|(_, value)| csv_field(value))
+            .collect();
+        csv.push_str(&fields.join(","));
+        csv.push_str("\r\n");
+    }
+    Ok(csv)
+}
+
+/// Dateiname aus dem Vorlagennamen: Kleinbuchstaben, Ziffern und `-`
+fn file_prefix(name: &str) -> String {
+    let slug = name
+        .to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("-");
+    format!("stalltagebuch-{}", slug)
+}
+
+/// Schreibt [`template_csv`] ins Export-Verzeichnis und gibt den Pfad zurück
+pub fn export_template_csv(
+    conn: &Connection,
+    template: &ExportTemplate,
+) -> Result<PathBuf, AppError> {
+    let csv = template_csv(conn, template)?;
+    let path = write_csv_export(&csv, &file_prefix(&template.name))?;
+    log::info!(
+        "CSV nach Vorlage {} gespeichert: {}",
+        template.name,
+        path.display()
+    );
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::schema;
+    use crate::models::Quail;
+    use crate::services::event_service;
+    use chrono::NaiveDate;
+
+    fn day(d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, 3, d).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_vet_report_contains_only_health_events() {
+        let conn = Connection::open_in_memory().unwrap();
+        schema::init_schema(&conn).unwrap();
+
+        let greta = Quail::new("Greta".to_string());
+        crate::services::create_profile(&conn, &greta)
+            .await
+            .unwrap();
+        event_service::create_event(&conn, greta.uuid, EventType::Born, day(1), None)
+            .await
+            .unwrap();
+        event_service::create_event(
+            &conn,
+            greta.uuid,
+            EventType::Sick,
+            day(3),
+            Some("Durchfall, Futter umgestellt".to_string()),
+        )
+        .await
+        .unwrap();
+        event_service::create_event(&conn, greta.uuid, EventType::Healthy, day(5), None)
+            .await
+            .unwrap();
+
+        // Die Migration legt den Tierarzt-Bericht an
+        let templates = list_templates(&conn).unwrap();
+        assert_eq!(templates.len(), 1);
+        let mut expected = ExportTemplate::vet_report();
+        expected.id = templates[0].id;
+        assert_eq!(templates[0], expected);
+
+        let csv = template_csv(&conn, &templates[0]).unwrap();
+        assert_eq!(
+            csv,
+            "date,quail,event_type,notes\r\n\
+             2025-03-03,Greta,Krank,\"Durchfall, Futter umgestellt\"\r\n\
+             2025-03-05,Greta,Gesund,\r\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_save_update_and_delete_template() {
+        let conn = Connection::open_in_memory().unwrap();
+        schema::init_schema(&conn).unwrap();
+
+        let mut quail = Quail::new("Greta".to_string());
+        quail.gender = Gender::Female;
+        quail.ring_color = Some(RingColor::Gruen);
+        crate::services::create_profile(&conn, &quail)
+            .await
+            .unwrap();
+
+        // Spalten werden in die Standard-Reihenfolge gebracht
+        let mut template = ExportTemplate::new("Bestand".to_string(), TemplateEntity::Quails);
+        template.columns = vec!["ring_color".to_string(), "name".to_string()];
+        let id = save_template(&conn, &template).unwrap();
+        template.id = Some(id);
+        let saved = list_templates(&conn)
+            .unwrap()
+            .into_iter()
+            .find(|t| t.id == Some(id))
+            .unwrap();
+        assert_eq!(saved.columns, ["name", "ring_color"]);
+        assert_eq!(
+            template_csv(&conn, &saved).unwrap(),
+            "name,ring_color\r\nGreta,Grün\r\n"
+        );
+
+        // Doppelte Namen, leere und unbekannte Spalten werden abgelehnt
+        let duplicate = ExportTemplate::new("Bestand".to_string(), TemplateEntity::EggRecords);
+        assert!(matches!(
+            save_template(&conn, &duplicate),
+            Err(AppError::Validation(_))
+        ));
+        let mut empty = ExportTemplate::new("Leer".to_string(), TemplateEntity::EggRecords);
+        empty.columns.clear();
+        assert!(save_template(&conn, &empty).is_err());
+        let mut unknown = ExportTemplate::new("Gewicht".to_string(), TemplateEntity::Quails);
+        unknown.columns = vec!["weight".to_string()];
+        assert!(save_template(&conn, &unknown).is_err());
+
+        // Umbenennen behält die ID
+        template.name = "Wachteln".to_string();
+        assert_eq!(save_template(&conn, &template).unwrap(), id);
+        assert!(list_templates(&conn)
+            .unwrap()
+            .iter()
+            .any(|t| t.id == Some(id) && t.name == "Wachteln"));
+
+        delete_template(&conn, id).unwrap();
+        assert_eq!(list_templates(&conn).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_file_prefix() {
+        assert_eq!(
+            file_prefix("Tierarzt-Bericht März"),
+            "stalltagebuch-tierarzt-bericht-märz"
+        );
+    }
+}
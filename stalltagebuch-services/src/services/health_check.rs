@@ -0,0 +1,430 @@
+//! Startup health check and recovery actions
+//!
+//! Runs quick checks on launch (database opens, schema version sane, photo
+//! storage writable, sync credentials readable) so the app can show a guided
+//! recovery screen instead of failing with a blank screen.
+
+use crate::database::{self, schema};
+use crate::error::AppError;
+use crate::services::{export_import_service, sync_service};
+use rusqlite::Connection;
+use std::path::{Path, PathBuf};
+
+/// A single failed startup check
+#[derive(Debug, Clone, PartialEq)]
+pub enum HealthIssue {
+    /// Database file cannot be opened or migrated
+    DatabaseUnavailable(String),
+    /// Schema version is missing or newer than this build understands
+    SchemaVersion { found: i32, expected: i32 },
+    /// Photo storage directory cannot be written
+    StorageNotWritable { path: String, error: String },
+    /// Stored sync credentials cannot be read
+    CredentialsUnreadable(String),
+}
+
+/// Result of all startup checks
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct HealthReport {
+    pub issues: Vec<HealthIssue>,
+}
+
+impl HealthReport {
+    pub fn is_healthy(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    /// True if the database itself is broken (restore is the only option)
+    pub fn database_broken(&self) -> bool {
+        self.issues.iter().any(|i| {
+            matches!(
+                i,
+                HealthIssue::DatabaseUnavailable(_) | HealthIssue::SchemaVersion { .. }
+            )
+        })
+    }
+}
+
+/// Directory where photos are stored
+pub fn photo_storage_dir() -> PathBuf {
+    #[cfg(target_os = "android")]
+    {
+        PathBuf::from("/storage/emulated/0/Android/data/de.teilgedanken.stalltagebuch/files/photos")
+    }
+
+    #[cfg(not(target_os = "android"))]
+    {
+        crate::database::location::current().photo_dir()
+    }
+}
+
+/// Runs all startup checks
+pub fn run_startup_checks() -> HealthReport {
+    let mut issues = Vec::new();
+
+    // Umstellung der Datenbankverschlüsselung, bevor irgendwer die Datei öffnet
+    if let Err(e) = database::encryption::apply_pending() {
+        log::error!("Health check: Verschlüsselung nicht umgestellt: {}", e);
+    }
+
+    match database::connection() {
+        Ok(conn) => issues.extend(check_database(&conn)),
+        Err(e) => {
+            log::error!("Health check: Datenbank nicht verfügbar: {}", e);
+            issues.push(HealthIssue::DatabaseUnavailable(e.to_string()));
+        }
+    }
+
+    if let Some(issue) = check_storage_writable(&photo_storage_dir()) {
+        issues.push(issue);
+    }
+
+    if !issues.is_empty() {
+        log::warn!(
+            "Health check: {} Problem(e) gefunden: {:?}",
+            issues.len(),
+            issues
+        );
+    }
+
+    HealthReport { issues }
+}
+
+/// Checks schema version and stored sync credentials of an open database
+pub fn check_database(conn: &Connection) -> Vec<HealthIssue> {
+    let mut issues = Vec::new();
+
+    match schema::get_schema_version(conn) {
+        Ok(found) if found == schema::SCHEMA_VERSION => {}
+        Ok(found) => issues.push(HealthIssue::SchemaVersion {
+            found,
+            expected: schema::SCHEMA_VERSION,
+        }),
+        Err(e) => issues.push(HealthIssue::DatabaseUnavailable(e.to_string())),
+    }
+
+    match sync_service::load_sync_settings(conn) {
+        Ok(Some(settings))
+            if settings.username.trim().is_empty() || settings.app_password.is_empty() =>
+        {
+            issues.push(HealthIssue::CredentialsUnreadable(
+                "Benutzername oder App-Passwort fehlt".to_string(),
+            ));
+        }
+        Ok(_) => {}
+        Err(e) => issues.push(HealthIssue::CredentialsUnreadable(e.to_string())),
+    }
+
+    issues
+}
+
+/// Creates the directory if needed and writes a probe file
+pub fn check_storage_writable(dir: &Path) -> Option<HealthIssue> {
+    let probe = dir.join(".write_test");
+    let result = std::fs::create_dir_all(dir)
+        .and_then(|_| std::fs::write(&probe, b"ok"))
+        .and_then(|_| std::fs::remove_file(&probe));
+
+    result.err().map(|e| HealthIssue::StorageNotWritable {
+        path: dir.display().to_string(),
+        error: e.to_string(),
+    })
+}
+
+/// Removes sync configuration and progress so sync can be set up from scratch.
+/// Local data stays untouched; photos are queued for upload again.
+pub fn reset_sync_state(conn: &Connection) -> Result<(), AppError> {
+    conn.execute("DELETE FROM sync_settings", [])?;
+    conn.execute("DELETE FROM sync_checkpoint", [])?;
+    conn.execute("DELETE FROM sync_queue", [])?;
+    conn.execute("DELETE FROM login_flow_session", [])?;
+    // sync_manifest wird lazy angelegt
+    conn.execute("DROP TABLE IF EXISTS sync_manifest", [])?;
+    conn.execute(
+        "UPDATE photos SET sync_status = 'local_only', sync_error = NULL, retry_count = 0
+         WHERE sync_status IN ('uploading', 'synced')",
+        [],
+    )?;
+    log::info!("Sync-Status zurückgesetzt");
+    Ok(())
+}
+
+/// Default location of the backup used for recovery (same as the import in settings)
+pub fn default_backup_path() -> PathBuf {
+    export_import_service::get_export_base_dir().join("import.zip")
+}
+
+/// Replaces the database with the contents of a backup ZIP
+///
+/// The backup is imported into a separate file first. The current database
+/// is only moved aside once that file passes `PRAGMA integrity_check`, so a
+/// broken or unreadable backup leaves it untouched. Before the files are
+/// swapped, all connections are closed (see [`database::exclusive_access`]).
+pub async fn restore_from_backup(backup_path: &Path) -> Result<(), AppError> {
+    restore_from_backup_to(backup_path, &database::get_database_path()).await
+}
+
+async fn restore_from_backup_to(backup_path: &Path, db_path: &Path) -> Result<(), AppError> {
+    if !backup_path.exists() {
+        return Err(AppError::NotFound(backup_path.display().to_string()));
+    }
+
+    let staging = db_path.with_extension("db.restore");
+    remove_database_files(&staging)?;
+    if let Err(e) = import_into(backup_path, &staging).await {
+        log::error!("Wiederherstellung abgebrochen: {}", e);
+        remove_database_files(&staging)?;
+        return Err(e);
+    }
+
+    // Keine Verbindung darf die Datei (samt WAL) beim Verschieben offen halten
+    let exclusive = match database::exclusive_access_async().await {
+        Ok(exclusive) => exclusive,
+        Err(e) => {
+            log::error!("Wiederherstellung abgebrochen: {}", e);
+            remove_database_files(&staging)?;
+            return Err(e);
+        }
+    };
+    quarantine_database(db_path)?;
+    database::rename_database(&staging, db_path)?;
+    drop(exclusive);
+    log::info!("Sicherung {} wiederhergestellt", backup_path.display());
+    Ok(())
+}
+
+/// Imports the backup into a new database file and checks the result
+async fn import_into(backup_path: &Path, db_path: &Path) -> Result<(), AppError> {
+    let conn = database::encryption::open(db_path)?;
+    conn.pragma_update(None, "foreign_keys", true)?;
+    schema::init_schema(&conn)?;
+    export_import_service::import_from_zip(
+        &conn,
+        backup_path,
+        export_import_service::ImportMode::MergePreferImport,
+        None,
+    )
+    .await?;
+
+    let result: String = conn.query_row("PRAGMA integrity_check", [], |row| row.get(0))?;
+    if result != "ok" {
+        return Err(AppError::Validation(format!(
+            "Wiederhergestellte Datenbank ist beschädigt: {}",
+            result
+        )));
+    }
+    Ok(())
+}
+
+/// Moves the database file aside (`<name>.broken-<timestamp>`)
+fn quarantine_database(db_path: &Path) -> Result<Option<PathBuf>, AppError> {
+    if !db_path.exists() {
+        return Ok(None);
+    }
+
+    let target = db_path.with_extension(format!(
+        "db.broken-{}",
+        chrono::Local::now().format("%Y%m%d-%H%M%S")
+    ));
+    database::rename_database(db_path, &target)?;
+    log::warn!("Datenbank verschoben nach {}", target.display());
+    Ok(Some(target))
+}
+
+/// Deletes a database file together with its WAL files, if present
+fn remove_database_files(db_path: &Path) -> Result<(), AppError> {
+    for path in std::iter::once(db_path.to_path_buf()).chain(database::sidecar_files(db_path)) {
+        if path.exists() {
+            std::fs::remove_file(path)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Quail, SyncSettings};
+    use crate::services::profile_service;
+
+    fn setup() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        schema::init_schema(&conn).unwrap();
+        conn
+    }
+
+    #[test]
+    fn test_fresh_database_is_healthy() {
+        let conn = setup();
+        assert!(check_database(&conn).is_empty());
+    }
+
+    #[test]
+    fn test_newer_schema_version_is_reported() {
+        let conn = setup();
+        conn.execute(
+            "INSERT INTO schema_version (version) VALUES (?1)",
+            [schema::SCHEMA_VERSION + 1],
+        )
+        .unwrap();
+
+        let issues = check_database(&conn);
+        assert_eq!(
+            issues,
+            vec![HealthIssue::SchemaVersion {
+                found: schema::SCHEMA_VERSION + 1,
+                expected: schema::SCHEMA_VERSION,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_reset_sync_state_removes_settings() {
+        let conn = setup();
+        let settings = SyncSettings::new(
+            "https://cloud.example.com".to_string(),
+            "user".to_string(),
+            "pass".to_string(),
+            "/Stalltagebuch".to_string(),
+        );
+        sync_service::save_sync_settings(&conn, &settings).unwrap();
+
+        reset_sync_state(&conn).unwrap();
+
+        assert!(sync_service::load_sync_settings(&conn).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_storage_writable() {
+        let dir =
+            std::env::temp_dir().join(format!("stalltagebuch-health-{}", uuid::Uuid::new_v4()));
+        assert!(check_storage_writable(&dir).is_none());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    fn quail_names(db_path: &Path) -> Vec<String> {
+        let conn = Connection::open(db_path).unwrap();
+        profile_service::list_profiles(&conn, None)
+            .unwrap()
+            .into_iter()
+            .map(|q| q.name)
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_restore_keeps_database_until_backup_is_imported() {
+        let dir =
+            std::env::temp_dir().join(format!("stalltagebuch-restore-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let db_path = dir.join("stalltagebuch.db");
+        {
+            let conn = Connection::open(&db_path).unwrap();
+            schema::init_schema(&conn).unwrap();
+            profile_service::create_profile(&conn, &Quail::new("Greta".to_string()))
+                .await
+                .unwrap();
+        }
+
+        // Kaputte Sicherung: die Datenbank bleibt, wie sie ist
+        let broken = dir.join("broken.zip");
+        std::fs::write(&broken, b"kein ZIP").unwrap();
+        assert!(restore_from_backup_to(&broken, &db_path).await.is_err());
+        assert_eq!(quail_names(&db_path), vec!["Greta".to_string()]);
+        assert!(!db_path.with_extension("db.restore").exists());
+
+        let backup = dir.join("backup.zip");
+        {
+            let conn = setup();
+            profile_service::create_profile(&conn, &Quail::new("Ida".to_string()))
+                .await
+                .unwrap();
+            export_import_service::export_selection_to_path(
+                &conn,
+                &export_import_service::ExportFilter::default(),
+                &backup,
+            )
+            .unwrap();
+        }
+        restore_from_backup_to(&backup, &db_path).await.unwrap();
+        assert_eq!(quail_names(&db_path), vec!["Ida".to_string()]);
+        let quarantined = std::fs::read_dir(&dir)
+            .unwrap()
+            .filter(|entry| {
+                entry
+                    .as_ref()
+                    .unwrap()
+                    .file_name()
+                    .to_string_lossy()
+                    .contains(".db.broken-")
+            })
+            .count();
+        assert_eq!(quarantined, 1);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_restore_waits_for_borrowed_connection() {
+        let dir =
+            std::env::temp_dir().join(format!("stalltagebuch-restore-{}", uuid::Uuid::new_v4()));
+        database::location::set_current(database::location::DataLocation::new(&dir));
+        {
+            let conn = database::connection().unwrap();
+            profile_service::create_profile(&conn, &Quail::new("Greta".to_string()))
+                .await
+                .unwrap();
+        }
+        let backup = dir.join("backup.zip");
+        {
+            let conn = setup();
+            profile_service::create_profile(&conn, &Quail::new("Ida".to_string()))
+                .await
+                .unwrap();
+            export_import_service::export_selection_to_path(
+                &conn,
+                &export_import_service::ExportFilter::default(),
+                &backup,
+            )
+            .unwrap();
+        }
+
+        // Z.B. die Hintergrund-Synchronisation hält eine Verbindung
+        let held = database::connection().unwrap();
+        let db_dir = database::get_database_path()
+            .parent()
+            .unwrap()
+            .to_path_buf();
+        let (restored, ()) = tokio::join!(restore_from_backup(&backup), async move {
+            tokio::time::sleep(std::time::Duration::from_millis(300)).await;
+            // Die Wiederherstellung wartet, statt die Datei wegzuziehen
+            let moved_aside = std::fs::read_dir(&db_dir).unwrap().any(|entry| {
+                entry
+                    .unwrap()
+                    .file_name()
+                    .to_string_lossy()
+                    .contains(".db.broken-")
+            });
+            assert!(!moved_aside);
+            assert!(database::connection().is_err());
+            let names: Vec<String> = profile_service::list_profiles(&held, None)
+                .unwrap()
+                .into_iter()
+                .map(|q| q.name)
+                .collect();
+            assert_eq!(names, vec!["Greta".to_string()]);
+            drop(held);
+        });
+        restored.unwrap();
+
+        let conn = database::connection().unwrap();
+        let names: Vec<String> = profile_service::list_profiles(&conn, None)
+            .unwrap()
+            .into_iter()
+            .map(|q| q.name)
+            .collect();
+        assert_eq!(names, vec!["Ida".to_string()]);
+        drop(conn);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,246 @@
+//! Termine als iCalendar-Datei (`.ics`)
+//!
+//! Schlupf, geplante Schlachtungen und die übrigen Ereignisse der Wachteln
+//! werden zu ganztägigen Terminen, damit der normale Kalender den Plan des
+//! Stalls zeigt. Die Datei hat einen festen Namen und kann zusätzlich in den
+//! Export-Ordner der Nextcloud geladen werden: Über einen Freigabe-Link lässt
+//! sie sich dort in Nextcloud Calendar (oder jedem anderen Kalender)
+//! abonnieren und bleibt mit jedem Hochladen aktuell.
+
+use crate::error::AppError;
+use crate::models::EventType;
+use crate::services::cloud_export::{self, CloudExport};
+use crate::services::export_import_service;
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use rusqlite::Connection;
+use std::path::{Path, PathBuf};
+
+/// Fester Dateiname, damit Abonnements beim nächsten Hochladen gültig bleiben
+pub const FILE_NAME: &str = "stalltagebuch-termine.ics";
+
+/// Maximale Zeilenlänge in Oktetten (RFC 5545, Abschnitt 3.1)
+const MAX_LINE_OCTETS: usize = 75;
+
+/// Ein ganztägiger Termin
+struct CalendarEntry {
+    uid: String,
+    date: NaiveDate,
+    summary: String,
+    description: Option<String>,
+}
+
+fn summary(event_type: &EventType, quail_name: &str) -> String {
+    match event_type {
+        EventType::Born => format!("{} geschlüpft", quail_name),
+        EventType::MarkedForSlaughter => format!("{}: Schlachtung geplant", quail_name),
+        other => format!("{}: {}", quail_name, other.display_name()),
+    }
+}
+
+/// Ereignisse nicht gelöschter Wachteln; „Am Leben“ ist nur der
+/// Grundzustand und kein Termin
+fn load_entries(conn: &Connection) -> Result<Vec<CalendarEntry>, AppError> {
+    let mut stmt = conn.prepare(
+        "SELECT e.uuid, e.event_type, e.event_date, e.notes, q.name
+         FROM quail_events e
+         JOIN quails q ON q.uuid = e.quail_id
+         WHERE e.deleted = 0 AND q.deleted = 0 AND e.event_type != 'alive'
+         ORDER BY e.event_date, q.name",
+    )?;
+    let rows = stmt.query_map([], |row| {
+        Ok((
+            row.get::<_, String>(0)?,
+            row.get::<_, String>(1)?,
+            row.get::<_, String>(2)?,
+            row.get::<_, Option<String>>(3)?,
+            row.get::<_, String>(4)?,
+        ))
+    })?;
+
+    let mut entries = Vec::new();
+    for row in rows {
+        let (uuid, event_type, date, notes, name) = row?;
+        let Ok(date) = NaiveDate::parse_from_str(&date, "%Y-%m-%d") else {
+            log::warn!("Kalender: Ereignis {} mit ungültigem Datum {}", uuid, date);
+            continue;
+        };
+        entries.push(CalendarEntry {
+            uid: format!("{}@stalltagebuch", uuid),
+            date,
+            summary: summary(&EventType::from_str(&event_type), &name),
+            description: notes.filter(|n| !n.trim().is_empty()),
+        });
+    }
+    Ok(entries)
+}
+
+/// Maskiert Sonderzeichen in TEXT-Werten
+fn escape_text(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            ';' => escaped.push_str("\\;"),
+            ',' => escaped.push_str("\\,"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Hängt eine Inhaltszeile an, umbrochen nach höchstens 75 Oktetten
+/// (ohne Zeichen zu zerteilen)
+fn push_line(out: &mut String, line: &str) {
+    let mut octets = 0;
+    for c in line.chars() {
+        if octets + c.len_utf8() > MAX_LINE_OCTETS {
+            out.push_str("\r\n ");
+            // Das Leerzeichen der Fortsetzung zählt mit
+            octets = 1;
+        }
+        out.push(c);
+        octets += c.len_utf8();
+    }
+    out.push_str("\r\n");
+}
+
+/// Erzeugt den Kalender mit allen Terminen; `now` wird als `DTSTAMP` verwendet
+pub fn build_calendar(conn: &Connection, now: DateTime<Utc>) -> Result<String, AppError> {
+    let stamp = now.format("%Y%m%dT%H%M%SZ").to_string();
+    let mut out = String::new();
+    push_line(&mut out, "BEGIN:VCALENDAR");
+    push_line(&mut out, "VERSION:2.0");
+    push_line(&mut out, "PRODID:-//Stalltagebuch//Termine//DE");
+    push_line(&mut out, "CALSCALE:GREGORIAN");
+    push_line(&mut out, "X-WR-CALNAME:Stalltagebuch");
+
+    for entry in load_entries(conn)? {
+        push_line(&mut out, "BEGIN:VEVENT");
+        push_line(&mut out, &format!("UID:{}", entry.uid));
+        push_line(&mut out, &format!("DTSTAMP:{}", stamp));
+        push_line(
+            &mut out,
+            &format!("DTSTART;VALUE=DATE:{}", entry.date.format("%Y%m%d")),
+        );
+        push_line(
+            &mut out,
+            &format!(
+                "DTEND;VALUE=DATE:{}",
+                (entry.date + Duration::days(1)).format("%Y%m%d")
+            ),
+        );
+        push_line(
+            &mut out,
+            &format!("SUMMARY:{}", escape_text(&entry.summary)),
+        );
+        if let Some(description) = &entry.description {
+            push_line(
+                &mut out,
+                &format!("DESCRIPTION:{}", escape_text(description)),
+            );
+        }
+        push_line(&mut out, "TRANSP:TRANSPARENT");
+        push_line(&mut out, "END:VEVENT");
+    }
+
+    push_line(&mut out, "END:VCALENDAR");
+    Ok(out)
+}
+
+/// Schreibt den Kalender als [`FILE_NAME`] nach `dir`
+pub fn export_ical_to(conn: &Connection, dir: &Path) -> Result<PathBuf, AppError> {
+    let calendar = build_calendar(conn, Utc::now())?;
+    std::fs::create_dir_all(dir).map_err(AppError::Filesystem)?;
+    let path = dir.join(FILE_NAME);
+    std::fs::write(&path, calendar).map_err(AppError::Filesystem)?;
+    log::info!("Kalender gespeichert: {}", path.display());
+    Ok(path)
+}
+
+/// Schreibt den Kalender in das Export-Verzeichnis
+pub fn export_ical(conn: &Connection) -> Result<PathBuf, AppError> {
+    export_ical_to(conn, &export_import_service::get_export_base_dir())
+}
+
+/// Schreibt den Kalender lokal und lädt ihn in den Export-Ordner der
+/// Nextcloud (überschreibt die vorige Version)
+pub async fn export_ical_to_nextcloud(conn: &Connection) -> Result<CloudExport, AppError> {
+    let (settings, backend) = cloud_export::configured_backend(conn)?;
+    let local_path = export_ical(conn)?;
+    cloud_export::upload_export(&backend, &settings, &local_path).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::schema;
+    use crate::models::Quail;
+    use crate::services::event_service;
+
+    fn day(d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, 3, d).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_calendar_contains_events() {
+        let conn = Connection::open_in_memory().unwrap();
+        schema::init_schema(&conn).unwrap();
+
+        let greta = Quail::new("Greta".to_string());
+        crate::services::create_profile(&conn, &greta)
+            .await
+            .unwrap();
+        event_service::create_event(&conn, greta.uuid, EventType::Born, day(1), None)
+            .await
+            .unwrap();
+        event_service::create_event(
+            &conn,
+            greta.uuid,
+            EventType::MarkedForSlaughter,
+            day(31),
+            Some("Vorher wiegen, dann schlachten; Gewicht notieren\nZweite Zeile".to_string()),
+        )
+        .await
+        .unwrap();
+        // Grundzustand erscheint nicht im Kalender
+        event_service::create_event(&conn, greta.uuid, EventType::Alive, day(2), None)
+            .await
+            .unwrap();
+
+        let now = DateTime::parse_from_rfc3339("2025-03-05T08:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let calendar = build_calendar(&conn, now).unwrap();
+        let unfolded = calendar.replace("\r\n ", "");
+
+        assert!(calendar.starts_with("BEGIN:VCALENDAR\r\nVERSION:2.0\r\n"));
+        assert!(calendar.ends_with("END:VCALENDAR\r\n"));
+        assert_eq!(unfolded.matches("BEGIN:VEVENT").count(), 2);
+        assert!(unfolded.contains("SUMMARY:Greta geschlüpft\r\n"));
+        assert!(unfolded.contains("DTSTART;VALUE=DATE:20250331\r\nDTEND;VALUE=DATE:20250401\r\n"));
+        assert!(unfolded.contains("DTSTAMP:20250305T080000Z\r\n"));
+        assert!(unfolded.contains(
+            "DESCRIPTION:Vorher wiegen\\, dann schlachten\\; Gewicht notieren\\nZweite Zeile\r\n"
+        ));
+        // Keine Zeile länger als 75 Oktette
+        assert!(calendar
+            .split("\r\n")
+            .all(|line| line.len() <= MAX_LINE_OCTETS));
+    }
+
+    #[test]
+    fn test_push_line_folds_at_char_boundary() {
+        let mut out = String::new();
+        let line = format!("SUMMARY:{}", "ü".repeat(40));
+        push_line(&mut out, &line);
+
+        let lines: Vec<&str> = out.trim_end_matches("\r\n").split("\r\n").collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines.iter().all(|l| l.len() <= MAX_LINE_OCTETS));
+        assert!(lines[1].starts_with(' '));
+        // Entfalten ergibt die ursprüngliche Zeile
+        assert_eq!(format!("{}{}", lines[0], &lines[1][1..]), line);
+    }
+}
@@ -0,0 +1,219 @@
+//! Import aus den CSV-Exporten anderer Geflügel-Apps
+//!
+//! Wer von einer anderen App kommt, soll seine Daten nicht von Hand
+//! abtippen. Ein [`ImportAdapter`] erkennt den Export einer App an den
+//! Spaltenüberschriften und bildet ihn direkt auf Wachteln und
+//! Eier-Einträge ab; das Ergebnis ist eine [`ImportPreview`] wie beim
+//! Tabellen-Import und wird mit
+//! [`apply_import`](crate::services::export_import_service::apply_import)
+//! übernommen. Nicht erkannte Tabellen laufen weiter über die manuelle
+//! Spaltenzuordnung.
+//!
+//! Ein neuer Adapter braucht nur das Trait und einen Eintrag in [`ADAPTERS`].
+
+use crate::error::AppError;
+use crate::models::{EggRecord, Gender, Quail};
+use crate::services::export_import_service::{
+    error_message, parse_egg_count, parse_import_date, ImportPreview, RowError,
+};
+use crate::spreadsheet::Table;
+use std::collections::BTreeMap;
+
+/// Export einer anderen App
+pub trait ImportAdapter: Sync {
+    /// Anzeigename, z.B. „FlockStar-Legeliste“
+    fn name(&self) -> &'static str;
+
+    /// Stammt eine Tabelle mit diesen Spaltenüberschriften von der App?
+    fn detect(&self, headers: &[String]) -> bool;
+
+    /// Bildet die Tabelle auf Wachteln und Eier-Einträge ab; fehlerhafte
+    /// Zeilen landen in `errors`
+    fn preview(&self, table: &Table) -> Result<ImportPreview, AppError>;
+}
+
+/// Alle bekannten Adapter, in der Reihenfolge der Erkennung
+pub const ADAPTERS: &[&dyn ImportAdapter] = &[&FlockEggLog];
+
+/// Erster Adapter, der die Tabelle erkennt
+pub fn detect(headers: &[String]) -> Option<&'static dyn ImportAdapter> {
+    ADAPTERS
+        .iter()
+        .copied()
+        .find(|adapter| adapter.detect(headers))
+}
+
+/// Spalte, deren Überschrift (klein geschrieben) einem der Namen entspricht
+fn find_column(headers: &[String], aliases: &[&str]) -> Option<usize> {
+    headers
+        .iter()
+        .position(|header| aliases.contains(&header.trim().to_lowercase().as_str()))
+}
+
+/// Legeliste im Stil von FlockStar und ähnlichen Hühner-Apps
+///
+/// Eine Zeile je Tier und Tag, Spalten (englisch, Reihenfolge beliebig):
+/// `Date`, `Bird` (oder `Hen`), `Eggs` (oder `Egg Count`), optional
+/// `Notes`. Jedes genannte Tier wird eine Henne, die Eier eines Tages
+/// werden zu einem Eier-Eintrag zusammengezählt, die Notizen mit dem
+/// Tiernamen davor übernommen.
+pub struct FlockEggLog;
+
+const DATE_HEADERS: &[&str] = &["date", "collection date", "day"];
+const BIRD_HEADERS: &[&str] = &["bird", "hen", "bird name", "animal"];
+const EGG_HEADERS: &[&str] = &["eggs", "egg count", "eggs collected", "count"];
+const NOTE_HEADERS: &[&str] = &["notes", "note", "comment", "comments"];
+
+/// Zwischenstand eines Tages
+struct DayTotal {
+    /// Erste Zeile des Tages, für Fehlermeldungen
+    row: usize,
+    eggs: i32,
+    notes: Vec<String>,
+}
+
+impl ImportAdapter for FlockEggLog {
+    fn name(&self) -> &'static str {
+        "FlockStar-Legeliste"
+    }
+
+    fn detect(&self, headers: &[String]) -> bool {
+        find_column(headers, DATE_HEADERS).is_some()
+            && find_column(headers, BIRD_HEADERS).is_some()
+            && find_column(headers, EGG_HEADERS).is_some()
+    }
+
+    fn preview(&self, table: &Table) -> Result<ImportPreview, AppError> {
+        let (Some(date_col), Some(bird_col), Some(egg_col)) = (
+            find_column(&table.headers, DATE_HEADERS),
+            find_column(&table.headers, BIRD_HEADERS),
+            find_column(&table.headers, EGG_HEADERS),
+        ) else {
+            return Err(AppError::Validation(format!(
+                "Tabelle ist keine {}",
+                self.name()
+            )));
+        };
+        let note_col = find_column(&table.headers, NOTE_HEADERS);
+
+        let mut preview = ImportPreview::default();
+        // Kleingeschriebener Name → Wachtel, in der Reihenfolge des Auftretens
+        let mut birds: Vec<(String, Quail)> = Vec::new();
+        let mut days: BTreeMap<chrono::NaiveDate, DayTotal> = BTreeMap::new();
+        for index in 0..table.rows.len() {
+            let row = index + 2;
+            let bird = table.cell(index, bird_col);
+            let parsed = parse_import_date(table.cell(index, date_col)).and_then(|date| {
+                parse_egg_count(table.cell(index, egg_col)).map(|eggs| (date, eggs))
+            });
+            let (date, eggs) = match parsed {
+                Ok(parsed) => parsed,
+                Err(message) => {
+                    preview.errors.push(RowError { row, message });
+                    continue;
+                }
+            };
+
+            if !bird.is_empty() && !birds.iter().any(|(key, _)| *key == bird.to_lowercase()) {
+                let mut quail = Quail::new(bird.to_string());
+                // Wer Eier legt, ist eine Henne
+                quail.gender = Gender::Female;
+                match quail.validate() {
+                    Ok(()) => birds.push((bird.to_lowercase(), quail)),
+                    Err(e) => {
+                        preview.errors.push(RowError {
+                            row,
+                            message: error_message(e),
+                        });
+                        continue;
+                    }
+                }
+            }
+
+            let day = days.entry(date).or_insert_with(|| DayTotal {
+                row,
+                eggs: 0,
+                notes: Vec::new(),
+            });
+            day.eggs += eggs;
+            let note = note_col.map_or("", |c| table.cell(index, c));
+            if !note.is_empty() {
+                day.notes.push(if bird.is_empty() {
+                    note.to_string()
+                } else {
+                    format!("{}: {}", bird, note)
+                });
+            }
+        }
+
+        preview.quails = birds.into_iter().map(|(_, quail)| quail).collect();
+        for (date, day) in days {
+            let mut record = EggRecord::new(date, day.eggs);
+            record.notes = (!day.notes.is_empty()).then(|| day.notes.join("; "));
+            match record.validate() {
+                Ok(()) => preview.egg_records.push(record),
+                Err(e) => preview.errors.push(RowError {
+                    row: day.row,
+                    message: error_message(e),
+                }),
+            }
+        }
+        Ok(preview)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spreadsheet::parse_csv;
+    use chrono::NaiveDate;
+
+    #[test]
+    fn test_flock_egg_log() {
+        let table = parse_csv(
+            "Date,Bird,Eggs,Notes\n\
+             2025-03-01,Henrietta,1,\n\
+             2025-03-01,Goldie,1,small egg\n\
+             2025-03-02,henrietta,1,\n\
+             03/04/2025,Goldie,x,\n\
+             2025-03-02,Goldie,0,\"molting, no eggs\"\n",
+        );
+        let adapter = detect(&table.headers).unwrap();
+        assert_eq!(adapter.name(), "FlockStar-Legeliste");
+
+        let preview = adapter.preview(&table).unwrap();
+        let names: Vec<&str> = preview.quails.iter().map(|q| q.name.as_str()).collect();
+        assert_eq!(names, ["Henrietta", "Goldie"]);
+        assert!(preview.quails.iter().all(|q| q.gender == Gender::Female));
+
+        let days: Vec<(NaiveDate, i32, Option<&str>)> = preview
+            .egg_records
+            .iter()
+            .map(|r| (r.record_date, r.total_eggs, r.notes.as_deref()))
+            .collect();
+        assert_eq!(
+            days,
+            [
+                (
+                    NaiveDate::from_ymd_opt(2025, 3, 1).unwrap(),
+                    2,
+                    Some("Goldie: small egg")
+                ),
+                (
+                    NaiveDate::from_ymd_opt(2025, 3, 2).unwrap(),
+                    1,
+                    Some("Goldie: molting, no eggs")
+                ),
+            ]
+        );
+        assert_eq!(preview.errors.len(), 1);
+        assert_eq!(preview.errors[0].row, 5);
+    }
+
+    #[test]
+    fn test_unknown_table_is_not_detected() {
+        let table = parse_csv("Datum;Eier;Notizen\n2025-03-01;4;\n");
+        assert!(detect(&table.headers).is_none());
+        assert!(FlockEggLog.preview(&table).is_err());
+    }
+}
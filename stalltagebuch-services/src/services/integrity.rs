@@ -0,0 +1,399 @@
+//! Integritätsprüfung und Reparatur der Datenbank
+//!
+//! Führt `PRAGMA integrity_check` und `PRAGMA foreign_key_check` aus, statt
+//! Beschädigungen erst als zufällige Fehler in Abfragen zu bemerken. Die
+//! Reparatur baut alle Indizes neu auf und entfernt Verweise auf nicht
+//! (mehr) vorhandene Zeilen. Was danach noch übrig ist, lässt sich nur über
+//! eine Sicherung beheben. Ergebnisse landen im dauerhaften Protokoll.
+//!
+//! [`repair_orphans`] repariert dagegen so wie der CRDT-Merge: fehlende
+//! Ziele bekommen einen Platzhalter, den spätere Sync-Operationen auffüllen,
+//! Verweise auf fehlende Ereignisse warten ohne Platzhalter auf deren Ankunft.
+//! Das nutzen die Migration auf Fremdschlüssel sowie Import und Snapshots,
+//! deren Zeilen nicht in Abhängigkeitsreihenfolge ankommen.
+
+use crate::error::AppError;
+use crate::models::LogLevel;
+use crate::services::{app_log, crdt_service};
+use rusqlite::{params, Connection};
+use std::collections::HashSet;
+
+/// Kategorie im [`app_log`]
+pub const LOG_CATEGORY: &str = "integrity";
+
+/// Höchstens so viele Meldungen von `integrity_check`
+const MAX_INTEGRITY_ERRORS: i64 = 100;
+
+/// Zeile, deren Fremdschlüssel ins Leere zeigt
+#[derive(Debug, Clone, PartialEq)]
+pub struct ForeignKeyViolation {
+    pub table: String,
+    /// `None` bei Tabellen ohne rowid
+    pub rowid: Option<i64>,
+    pub parent: String,
+    /// Spalte mit dem Verweis
+    pub column: String,
+}
+
+/// Ergebnis einer Prüfung
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct IntegrityReport {
+    /// Meldungen von `integrity_check` (leer, wenn "ok")
+    pub integrity_errors: Vec<String>,
+    pub foreign_key_violations: Vec<ForeignKeyViolation>,
+}
+
+impl IntegrityReport {
+    pub fn is_ok(&self) -> bool {
+        self.integrity_errors.is_empty() && self.foreign_key_violations.is_empty()
+    }
+
+    pub fn issue_count(&self) -> usize {
+        self.integrity_errors.len() + self.foreign_key_violations.len()
+    }
+}
+
+/// Ergebnis von [`repair_orphans`]
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct OrphanRepair {
+    /// Angelegte Platzhalter (Wachteln, Fotos)
+    pub placeholders_created: usize,
+    /// Verweise auf fehlende Ereignisse, die bis zu deren Ankunft geparkt wurden
+    pub references_deferred: usize,
+    /// Verweise auf gelöschte Einträge, die auf NULL gesetzt wurden
+    pub references_cleared: usize,
+    /// Zeilen, deren Pflicht-Verweis auf einen gelöschten Eintrag zeigte
+    pub rows_deleted: usize,
+}
+
+impl OrphanRepair {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Ergebnis einer Reparatur
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct RepairSummary {
+    /// Verweise, die auf NULL gesetzt wurden
+    pub references_cleared: usize,
+    /// Zeilen, deren Pflicht-Verweis fehlte und die gelöscht wurden
+    pub rows_deleted: usize,
+    /// Prüfung nach der Reparatur
+    pub remaining: IntegrityReport,
+}
+
+fn integrity_errors(conn: &Connection) -> Result<Vec<String>, AppError> {
+    let mut stmt = conn.prepare(&format!("PRAGMA integrity_check({})", MAX_INTEGRITY_ERRORS))?;
+    let rows = stmt.query_map([], |row| row.get::<_, String>(0))?;
+    let messages = rows.collect::<Result<Vec<_>, _>>()?;
+    Ok(messages.into_iter().filter(|m| m != "ok").collect())
+}
+
+fn foreign_key_violations(conn: &Connection) -> Result<Vec<ForeignKeyViolation>, AppError> {
+    let mut stmt = conn.prepare("PRAGMA foreign_key_check")?;
+    let rows = stmt.query_map([], |row| {
+        Ok((
+            row.get::<_, String>(0)?,
+            row.get::<_, Option<i64>>(1)?,
+            row.get::<_, String>(2)?,
+            row.get::<_, i64>(3)?,
+        ))
+    })?;
+    let rows = rows.collect::<Result<Vec<_>, _>>()?;
+
+    let mut violations = Vec::with_capacity(rows.len());
+    for (table, rowid, parent, fkid) in rows {
+        let column: String = conn.query_row(
+            "SELECT \"from\" FROM pragma_foreign_key_list(?1) WHERE id = ?2",
+            params![table, fkid],
+            |row| row.get(0),
+        )?;
+        violations.push(ForeignKeyViolation {
+            table,
+            rowid,
+            parent,
+            column,
+        });
+    }
+    Ok(violations)
+}
+
+/// Prüft die Datenbank, ohne etwas zu ändern
+pub fn check(conn: &Connection) -> Result<IntegrityReport, AppError> {
+    Ok(IntegrityReport {
+        integrity_errors: integrity_errors(conn)?,
+        foreign_key_violations: foreign_key_violations(conn)?,
+    })
+}
+
+/// Prüft und schreibt das Ergebnis ins Protokoll
+pub fn check_and_log(conn: &Connection) -> Result<IntegrityReport, AppError> {
+    let report = check(conn)?;
+    if report.is_ok() {
+        app_log::write(conn, LOG_CATEGORY, LogLevel::Info, "Prüfung ohne Befund")?;
+    } else {
+        app_log::write(
+            conn,
+            LOG_CATEGORY,
+            LogLevel::Warning,
+            &format!(
+                "Prüfung: {} Problem(e)\n{}",
+                report.issue_count(),
+                describe(&report)
+            ),
+        )?;
+    }
+    Ok(report)
+}
+
+/// Eine Zeile pro Befund, für Protokoll und Anzeige
+pub fn describe(report: &IntegrityReport) -> String {
+    report
+        .integrity_errors
+        .iter()
+        .cloned()
+        .chain(report.foreign_key_violations.iter().map(|v| {
+            format!(
+                "{}.{} (rowid {}) → {} fehlt",
+                v.table,
+                v.column,
+                v.rowid
+                    .map(|r| r.to_string())
+                    .unwrap_or_else(|| "?".to_string()),
+                v.parent
+            )
+        }))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn column_nullable(conn: &Connection, table: &str, column: &str) -> Result<bool, AppError> {
+    let not_null: bool = conn.query_row(
+        "SELECT \"notnull\" FROM pragma_table_info(?1) WHERE name = ?2",
+        params![table, column],
+        |row| row.get(0),
+    )?;
+    Ok(!not_null)
+}
+
+/// Setzt den Verweis auf NULL oder löscht die Zeile, wenn er Pflicht ist;
+/// `true`, wenn die Zeile gelöscht wurde
+fn drop_reference(
+    conn: &Connection,
+    violation: &ForeignKeyViolation,
+    rowid: i64,
+) -> Result<bool, AppError> {
+    if column_nullable(conn, &violation.table, &violation.column)? {
+        conn.execute(
+            &format!(
+                "UPDATE \"{}\" SET \"{}\" = NULL WHERE rowid = ?1",
+                violation.table, violation.column
+            ),
+            [rowid],
+        )?;
+        Ok(false)
+    } else {
+        conn.execute(
+            &format!("DELETE FROM \"{}\" WHERE rowid = ?1", violation.table),
+            [rowid],
+        )?;
+        Ok(true)
+    }
+}
+
+/// Legt für verwaiste Verweise Platzhalter an
+///
+/// Wie beim Merge (siehe [`crdt_service::ensure_quail`]): Eine fehlende
+/// Wachtel oder ein fehlendes Foto wird als Platzhalter angelegt. Ein
+/// Verweis auf ein fehlendes Ereignis wird geparkt (siehe
+/// [`crdt_service::defer_reference`]) und bis dahin auf NULL gesetzt. Nur
+/// Verweise auf lokal gelöschte Einträge werden wie bei `ON DELETE` entfernt. Läuft in der Transaktion des Aufrufers; mit
+/// `PRAGMA defer_foreign_keys` lässt sich so vor dem Commit aufräumen.
+pub fn repair_orphans(conn: &Connection) -> Result<OrphanRepair, AppError> {
+    let mut summary = OrphanRepair::default();
+    let mut created = HashSet::new();
+    for violation in foreign_key_violations(conn)? {
+        let Some(rowid) = violation.rowid else {
+            continue;
+        };
+        let target: Option<String> = conn.query_row(
+            &format!(
+                "SELECT \"{}\" FROM \"{}\" WHERE rowid = ?1",
+                violation.column, violation.table
+            ),
+            [rowid],
+            |row| row.get(0),
+        )?;
+        let Some(target) = target else {
+            continue;
+        };
+        if violation.parent == "quail_events"
+            && !crdt_service::is_deleted(conn, "quail_events", &target)?
+        {
+            let entity_id: String = conn.query_row(
+                &format!("SELECT uuid FROM \"{}\" WHERE rowid = ?1", violation.table),
+                [rowid],
+                |row| row.get(0),
+            )?;
+            crdt_service::defer_reference(
+                conn,
+                &violation.table,
+                &entity_id,
+                &violation.column,
+                &target,
+            )?;
+            if drop_reference(conn, &violation, rowid)? {
+                summary.rows_deleted += 1;
+            } else {
+                summary.references_deferred += 1;
+            }
+            continue;
+        }
+        let kept = match violation.parent.as_str() {
+            "quails" => crdt_service::ensure_quail(conn, &target)?,
+            "photos" => crdt_service::ensure_photo(conn, &target)?,
+            _ => false,
+        };
+        if kept {
+            if created.insert((violation.parent.clone(), target)) {
+                summary.placeholders_created += 1;
+            }
+        } else if drop_reference(conn, &violation, rowid)? {
+            summary.rows_deleted += 1;
+        } else {
+            summary.references_cleared += 1;
+        }
+    }
+
+    if !summary.is_empty() {
+        app_log::write(
+            conn,
+            LOG_CATEGORY,
+            LogLevel::Warning,
+            &format!(
+                "Verwaiste Verweise: {} Platzhalter angelegt, {} Verweis(e) geparkt, {} Verweis(e) entfernt, {} Zeile(n) gelöscht",
+                summary.placeholders_created,
+                summary.references_deferred,
+                summary.references_cleared,
+                summary.rows_deleted
+            ),
+        )?;
+    }
+    Ok(summary)
+}
+
+/// Baut die Indizes neu auf und entfernt verwaiste Verweise
+///
+/// Optionale Verweise werden auf NULL gesetzt (z.B. ein fehlendes
+/// Profilfoto), Zeilen mit fehlendem Pflicht-Verweis gelöscht (z.B. ein
+/// Ereignis ohne Wachtel) – genau das, was `ON DELETE` sonst getan hätte.
+pub fn repair(conn: &Connection) -> Result<RepairSummary, AppError> {
+    conn.execute_batch("REINDEX")?;
+
+    let mut summary = RepairSummary::default();
+    let tx = conn.unchecked_transaction()?;
+    for violation in foreign_key_violations(&tx)? {
+        let Some(rowid) = violation.rowid else {
+            continue;
+        };
+        if drop_reference(&tx, &violation, rowid)? {
+            summary.rows_deleted += 1;
+        } else {
+            summary.references_cleared += 1;
+        }
+    }
+    tx.commit()?;
+
+    summary.remaining = check(conn)?;
+    let level = if summary.remaining.is_ok() {
+        LogLevel::Info
+    } else {
+        LogLevel::Error
+    };
+    let mut message = format!(
+        "Reparatur: Indizes neu aufgebaut, {} Verweis(e) entfernt, {} Zeile(n) gelöscht, {} Problem(e) verbleiben",
+        summary.references_cleared,
+        summary.rows_deleted,
+        summary.remaining.issue_count()
+    );
+    if !summary.remaining.is_ok() {
+        message.push('\n');
+        message.push_str(&describe(&summary.remaining));
+    }
+    app_log::write(conn, LOG_CATEGORY, level, &message)?;
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::schema;
+
+    fn setup() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        schema::init_schema(&conn).unwrap();
+        conn
+    }
+
+    #[test]
+    fn test_clean_database_is_ok() {
+        let conn = setup();
+        let report = check_and_log(&conn).unwrap();
+        assert!(report.is_ok());
+        let entries = app_log::list(&conn, Some(LOG_CATEGORY), 10).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].level, LogLevel::Info);
+    }
+
+    #[test]
+    fn test_repair_removes_dangling_references() {
+        let conn = setup();
+        // Verwaiste Zeilen wie nach einem abgebrochenen Sync ohne Fremdschlüssel
+        conn.execute("PRAGMA foreign_keys = OFF", []).unwrap();
+        conn.execute(
+            "INSERT INTO quails (uuid, name, profile_photo) VALUES ('q1', 'Greta', 'missing-photo')",
+            [],
+        )
+        .unwrap();
+        conn.execute(
+            "INSERT INTO quail_events (uuid, quail_id, event_type, event_date)
+             VALUES ('e1', 'missing-quail', 'born', '2025-01-01')",
+            [],
+        )
+        .unwrap();
+        conn.execute("PRAGMA foreign_keys = ON", []).unwrap();
+
+        let report = check_and_log(&conn).unwrap();
+        assert_eq!(report.foreign_key_violations.len(), 2);
+        assert!(report
+            .foreign_key_violations
+            .iter()
+            .any(|v| v.table == "quails" && v.column == "profile_photo"));
+        assert!(describe(&report).contains("quail_events.quail_id"));
+
+        let summary = repair(&conn).unwrap();
+        assert_eq!(summary.references_cleared, 1);
+        assert_eq!(summary.rows_deleted, 1);
+        assert!(summary.remaining.is_ok());
+
+        // Wachtel bleibt, nur ohne Profilfoto
+        let photo: Option<String> = conn
+            .query_row(
+                "SELECT profile_photo FROM quails WHERE uuid = 'q1'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(photo, None);
+        let events: i64 = conn
+            .query_row("SELECT COUNT(*) FROM quail_events", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(events, 0);
+
+        let entries = app_log::list(&conn, Some(LOG_CATEGORY), 10).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert!(entries[0].message.starts_with("Reparatur"));
+        assert_eq!(entries[1].level, LogLevel::Warning);
+    }
+}
@@ -0,0 +1,179 @@
+//! Maschinenlesbarer Export aller Daten als eine JSON-Datei
+//!
+//! Gedacht für eigene Auswertungs-Skripte und als stabiles Austauschformat
+//! (z.B. für den Umzug auf ein anderes Backend). Anders als das ZIP enthält
+//! die Datei keine Fotodateien und keine internen Spalten (CRDT-Uhren,
+//! Sync-Status), sondern genau die synchronisierten Felder jeder Entität,
+//! wie sie auch in Snapshots stehen. Verweise (`quail_id`, `event_id`,
+//! `profile_photo`, `coop_id`) sind UUIDs. Gelöschte Einträge fehlen.
+//!
+//! Aufbau:
+//!
+//! ```json
+//! {
+//!   "format": "stalltagebuch-json",
+//!   "format_version": 1,
+//!   "schema_version": 31,
+//!   "exported_at": "2025-03-05T08:00:00+00:00",
+//!   "app_version": "0.1.0",
+//!   "entities": {
+//!     "coop": [{ "uuid": "…", "name": "Stall 1", "notes": null }],
+//!     "egg": [{ "uuid": "…", "record_date": "2025-03-01", "total_eggs": 7, "notes": null, "coop_id": null }],
+//!     "event": [{ "uuid": "…", "quail_id": "…", "event_type": "born", … }],
+//!     "photo": [{ "uuid": "…", "quail_id": "…", "tags": ["brut"], … }],
+//!     "quail": [{ "uuid": "…", "name": "Greta", "gender": "female", … }]
+//!   }
+//! }
+//! ```
+
+use crate::database::schema;
+use crate::error::AppError;
+use crate::services::export_import_service;
+use crate::services::op_compaction::{sql_to_json, synced_tables};
+use chrono::{DateTime, Utc};
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Kennung im Feld `format`
+pub const FORMAT: &str = "stalltagebuch-json";
+/// Wird bei inkompatiblen Änderungen am Aufbau erhöht
+pub const FORMAT_VERSION: u32 = 1;
+
+/// Inhalt der JSON-Datei
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JsonExport {
+    pub format: String,
+    pub format_version: u32,
+    /// Datenbank-Schema zum Zeitpunkt des Exports
+    pub schema_version: i32,
+    pub exported_at: String,
+    pub app_version: String,
+    /// Entitätstyp (`quail`, `event`, `egg`, `photo`, `coop`) → Einträge
+    pub entities: BTreeMap<String, Vec<serde_json::Value>>,
+}
+
+/// Nicht gelöschte Einträge einer Tabelle mit `uuid` und den synchronisierten Spalten
+fn load_rows(
+    conn: &Connection,
+    table: &str,
+    columns: &[&str],
+) -> Result<Vec<serde_json::Value>, AppError> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT uuid, {} FROM {} WHERE deleted = 0 ORDER BY uuid",
+        columns.join(", "),
+        table
+    ))?;
+    let rows = stmt.query_map([], |row| {
+        let mut fields = serde_json::Map::new();
+        fields.insert("uuid".to_string(), sql_to_json(row.get_ref(0)?));
+        for (i, column) in columns.iter().enumerate() {
+            let mut value = sql_to_json(row.get_ref(i + 1)?);
+            // Schlagwörter liegen als JSON-Text in der Spalte
+            if *column == "tags" {
+                value = value
+                    .as_str()
+                    .and_then(|text| serde_json::from_str(text).ok())
+                    .unwrap_or_else(|| serde_json::Value::Array(Vec::new()));
+            }
+            fields.insert(column.to_string(), value);
+        }
+        Ok(serde_json::Value::Object(fields))
+    })?;
+    Ok(rows.collect::<Result<Vec<_>, _>>()?)
+}
+
+/// Liest alle Entitäten; `now` wird als `exported_at` verwendet
+pub fn build_export(conn: &Connection, now: DateTime<Utc>) -> Result<JsonExport, AppError> {
+    let mut entities = BTreeMap::new();
+    for (entity_type, table, columns) in synced_tables() {
+        entities.insert(entity_type.to_string(), load_rows(conn, table, columns)?);
+    }
+    Ok(JsonExport {
+        format: FORMAT.to_string(),
+        format_version: FORMAT_VERSION,
+        schema_version: schema::get_schema_version(conn)?,
+        exported_at: now.to_rfc3339(),
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        entities,
+    })
+}
+
+/// Schreibt den Export als `stalltagebuch-export-<Zeitstempel>.json` nach `dir`
+pub fn export_json_to(conn: &Connection, dir: &Path) -> Result<PathBuf, AppError> {
+    let now = Utc::now();
+    let export = build_export(conn, now)?;
+    let json = serde_json::to_vec_pretty(&export)
+        .map_err(|e| AppError::Other(format!("Fehler beim Serialisieren: {}", e)))?;
+
+    std::fs::create_dir_all(dir).map_err(AppError::Filesystem)?;
+    let path = dir.join(format!(
+        "stalltagebuch-export-{}.json",
+        now.format("%Y%m%d-%H%M%S")
+    ));
+    std::fs::write(&path, json).map_err(AppError::Filesystem)?;
+    log::info!("JSON-Export gespeichert: {}", path.display());
+    Ok(path)
+}
+
+/// Schreibt den Export in das Export-Verzeichnis (neben die ZIP-Sicherungen)
+pub fn export_json(conn: &Connection) -> Result<PathBuf, AppError> {
+    export_json_to(conn, &export_import_service::get_export_base_dir())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{EggRecord, EventType, Quail};
+    use crate::services::event_service;
+    use chrono::NaiveDate;
+
+    #[tokio::test]
+    async fn test_export_contains_entities_with_references() {
+        let conn = Connection::open_in_memory().unwrap();
+        schema::init_schema(&conn).unwrap();
+
+        let greta = Quail::new("Greta".to_string());
+        crate::services::create_profile(&conn, &greta)
+            .await
+            .unwrap();
+        let born = NaiveDate::from_ymd_opt(2025, 3, 1).unwrap();
+        event_service::create_event(&conn, greta.uuid, EventType::Born, born, None)
+            .await
+            .unwrap();
+        let record = EggRecord::new(born, 7);
+        crate::services::add_egg_record(&conn, &record)
+            .await
+            .unwrap();
+        // Gelöschte Wachteln fehlen
+        let gone = Quail::new("Weg".to_string());
+        crate::services::create_profile(&conn, &gone).await.unwrap();
+        crate::services::delete_profile(&conn, &gone.uuid)
+            .await
+            .unwrap();
+
+        let export = build_export(&conn, Utc::now()).unwrap();
+        assert_eq!(export.format, FORMAT);
+        assert_eq!(export.schema_version, schema::SCHEMA_VERSION);
+
+        let quails = &export.entities["quail"];
+        assert_eq!(quails.len(), 1);
+        assert_eq!(quails[0]["uuid"], greta.uuid.to_string());
+        assert_eq!(quails[0]["name"], "Greta");
+        // Nur synchronisierte Felder, keine CRDT-Spalten
+        assert!(quails[0].get("logical_clock").is_none());
+
+        let events = &export.entities["event"];
+        assert_eq!(events[0]["quail_id"], greta.uuid.to_string());
+        assert_eq!(events[0]["event_type"], "born");
+        assert_eq!(events[0]["event_date"], "2025-03-01");
+
+        assert_eq!(export.entities["egg"][0]["total_eggs"], 7);
+        assert!(export.entities["photo"].is_empty());
+
+        // Stabiles Format: lässt sich wieder einlesen
+        let json = serde_json::to_string(&export).unwrap();
+        assert_eq!(serde_json::from_str::<JsonExport>(&json).unwrap(), export);
+    }
+}
@@ -0,0 +1,56 @@
+pub mod analytics_service;
+pub mod app_log;
+pub mod background_sync;
+pub mod backup_crypto;
+pub mod backup_service;
+pub mod batch_service;
+pub mod cloud_export;
+pub mod connectivity;
+pub mod coop_service;
+pub mod crdt_service;
+pub mod download_queue;
+pub mod download_service;
+pub mod egg_service;
+pub mod entity_history;
+pub mod event_service;
+pub mod export_import_service;
+pub mod export_templates;
+pub mod health_check;
+pub mod ical_export;
+pub mod import_adapters;
+pub mod integrity;
+pub mod json_export;
+pub mod nextcloud_auth;
+pub mod op_compaction;
+pub mod op_crypto;
+pub mod operation_capture;
+pub mod pedigree;
+pub mod photo_assets;
+pub mod photo_checksum;
+pub mod photo_conflicts;
+#[cfg(feature = "encryption")]
+pub mod photo_crypto;
+pub mod photo_edit;
+pub mod photo_prefetch;
+pub mod photo_service;
+pub mod photo_storage;
+pub mod photo_sweep;
+pub mod profile_service;
+pub mod report_service;
+pub mod repository;
+pub mod scheduled_export;
+pub mod search_service;
+pub mod state_rebuild;
+pub mod storage_quota;
+pub mod sync_backend;
+pub mod sync_history;
+pub mod sync_paths;
+pub mod sync_progress;
+pub mod sync_service;
+pub mod thumbnail_queue;
+pub mod transfer_stats;
+pub mod treatment_service;
+pub mod upload_service;
+
+pub use egg_service::*;
+pub use profile_service::*;
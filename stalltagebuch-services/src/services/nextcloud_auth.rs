@@ -0,0 +1,57 @@
+//! Nextcloud-Anmeldung, siehe Crate `nextcloud-auth`
+//!
+//! Der Login Flow selbst liegt in einer eigenen Crate ohne Datenbank, damit
+//! er auch im Web-Build (`wasm32`) kompiliert. Hier kommen nur die
+//! Verbindungen zu den gespeicherten Sync-Einstellungen dazu.
+
+pub use ::nextcloud_auth::*;
+
+use crate::error::AppError;
+use crate::models::SyncSettings;
+
+impl From<AuthError> for AppError {
+    fn from(e: AuthError) -> Self {
+        match e {
+            AuthError::InvalidInput(msg) => AppError::Validation(msg),
+            e if e.is_unauthorized() => AppError::PermissionDenied(e.to_string()),
+            e => AppError::Other(e.to_string()),
+        }
+    }
+}
+
+/// Proxy aus den gespeicherten Sync-Einstellungen
+pub fn proxy_from_settings(settings: &SyncSettings) -> Result<Option<ProxyConfig>, AuthError> {
+    ProxyConfig::parse(settings.proxy_url.as_deref().unwrap_or(""))
+}
+
+/// Gemerkter Zertifikats-Fingerabdruck der Einstellungen
+pub fn pin_from_settings(settings: &SyncSettings) -> CertificatePin {
+    CertificatePin::new(settings.cert_fingerprint.clone())
+}
+
+/// Service für einen eingerichteten Sync (Proxy und gemerktes Zertifikat)
+pub fn service_for_settings(settings: &SyncSettings) -> Result<NextcloudAuthService, AuthError> {
+    let proxy = proxy_from_settings(settings)?;
+    NextcloudAuthService::with_pin(proxy.as_ref(), pin_from_settings(settings))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unauthorized_maps_to_permission_denied() {
+        assert!(matches!(
+            AppError::from(AuthError::ServerStatus(403)),
+            AppError::PermissionDenied(_)
+        ));
+        assert!(matches!(
+            AppError::from(AuthError::ServerStatus(500)),
+            AppError::Other(_)
+        ));
+        assert!(matches!(
+            AppError::from(AuthError::InvalidInput("leer".to_string())),
+            AppError::Validation(_)
+        ));
+    }
+}
@@ -0,0 +1,909 @@
+//! Kompaktierung des Operations-Logs
+//!
+//! Die NDJSON-Dateien unter `sync/ops/<device>/` wachsen sonst unbegrenzt,
+//! und jedes neue Gerät müsste alle Operationen seit Beginn abspielen. Daher
+//! schreibt jedes Gerät gelegentlich einen Snapshot des aktuellen Zustands
+//! aller Entitäten nach `sync/snapshots/<device>/<YYYYMMDD>/<ULID>.json`,
+//! zeigt mit `sync/control/<device>/latest.json` darauf und löscht danach
+//! seine eigenen Op-Dateien, die älter als der Snapshot sind.
+//!
+//! Der Download spielt zuerst den neuesten Snapshot jedes anderen Geräts ein
+//! und danach nur noch die neueren Op-Dateien. Snapshots werden wie Ops per
+//! LWW über die HLC der Entität gemergt und lassen sich daher beliebig oft
+//! anwenden.
+//!
+//! Mit Sync-Schlüssel sind Snapshots signiert (HMAC-SHA256 über Geräte-ID
+//! und Inhalt, siehe [`OpCipher::sign`]); nachträglich veränderte Dateien
+//! werden verworfen. Ohne Schlüssel tragen sie nur eine SHA-256-Prüfsumme,
+//! die abgeschnittene oder beschädigte Dateien erkennt, gegen absichtliche
+//! Änderungen auf dem Server aber nicht schützt.
+//!
+//! Es kompaktiert immer nur ein Gerät gleichzeitig: wer kompaktiert, legt
+//! vorher `sync/control/compactor.lease` an (nur wenn noch keine liegt) und
+//! löscht die Datei danach wieder. Andere Geräte sehen die Lease und
+//! verschieben ihre Kompaktierung; eine abgelaufene Lease (abgestürztes
+//! Gerät) darf übernommen werden.
+
+use crate::error::AppError;
+use crate::services::crdt_service::{self, HybridLogicalClock};
+use crate::services::op_crypto::OpCipher;
+use crate::services::sync_backend::{ConfiguredBackend, RemoteEntry, SyncBackend};
+use crate::services::{download_service, integrity, sync_paths, sync_service, upload_service};
+use rusqlite::types::{Value as SqlValue, ValueRef};
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicI64, Ordering};
+
+/// Mindestabstand zwischen zwei Snapshots eines Geräts
+pub const COMPACTION_INTERVAL_DAYS: i64 = 7;
+
+/// Unter dieser Zahl eigener Op-Dateien lohnt sich kein Snapshot
+pub const MIN_OP_FILES: usize = 50;
+
+/// Wie oft `compact_if_due` überhaupt beim Server nachsieht
+const CHECK_INTERVAL_MS: i64 = 60 * 60 * 1000;
+
+/// Gültigkeit einer Kompaktierungs-Lease
+pub const LEASE_TTL_MS: i64 = 10 * 60 * 1000;
+
+/// Zeitpunkt der letzten Prüfung in `compact_if_due` (epoch ms)
+static LAST_CHECK_MS: AtomicI64 = AtomicI64::new(0);
+
+/// Synchronisierte Tabelle mit den Spalten, die im Snapshot landen
+struct EntityTable {
+    entity_type: &'static str,
+    table: &'static str,
+    columns: &'static [&'static str],
+    /// Pflichtspalten ohne Sync-Wert, die beim Anlegen gesetzt werden
+    insert_defaults: &'static [(&'static str, &'static str)],
+}
+
+/// Reihenfolge beim Anwenden: Fotos vor Wachteln (`profile_photo`),
+/// Wachteln vor Events (`quail_id`); Ställe haben keine Abhängigkeiten
+const ENTITY_TABLES: &[EntityTable] = &[
+    EntityTable {
+        entity_type: "coop",
+        table: "coops",
+        columns: &["name", "notes"],
+        insert_defaults: &[],
+    },
+    EntityTable {
+        entity_type: "photo",
+        table: "photos",
+        columns: &[
+            "quail_id",
+            "event_id",
+            "relative_path",
+            "thumbnail_path",
+            "caption",
+            "trashed_at",
+            "checksum",
+            "position",
+            "tags",
+        ],
+        insert_defaults: &[("path", "''")],
+    },
+    EntityTable {
+        entity_type: "quail",
+        table: "quails",
+        columns: &[
+            "name",
+            "gender",
+            "ring_color",
+            "profile_photo",
+            "mother_id",
+            "father_id",
+            "coop_id",
+        ],
+        insert_defaults: &[],
+    },
+    EntityTable {
+        entity_type: "event",
+        table: "quail_events",
+        columns: &[
+            "quail_id",
+            "event_type",
+            "event_date",
+            "notes",
+            "medication",
+            "dose",
+            "duration_days",
+            "egg_withdrawal_days",
+            "meat_withdrawal_days",
+        ],
+        insert_defaults: &[],
+    },
+    EntityTable {
+        entity_type: "egg",
+        table: "egg_records",
+        columns: &["record_date", "total_eggs", "notes", "coop_id"],
+        insert_defaults: &[],
+    },
+];
+
+/// Zustand einer Entität zum Zeitpunkt des Snapshots (inkl. Tombstones)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct EntityState {
+    pub entity_type: String,
+    pub entity_id: String,
+    /// HLC der letzten Änderung (`logical_clock` = physische ms)
+    pub logical_clock: i64,
+    #[serde(default)]
+    pub clock_counter: u32,
+    #[serde(default)]
+    pub clock_device: String,
+    pub deleted: bool,
+    pub fields: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Materialisierter Zustand aus Sicht eines Geräts
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Snapshot {
+    /// ULID; alle Op-Dateien des Geräts mit älterer ULID sind enthalten
+    pub snapshot_id: String,
+    pub device_id: String,
+    pub created_at: i64,
+    pub entities: Vec<EntityState>,
+    /// Pro Gerät die neueste bereits gemergte Op-Datei (ULID); ein neues
+    /// Gerät überspringt beim Bootstrap alle Dateien bis dahin
+    #[serde(default)]
+    pub seen_ops: BTreeMap<String, String>,
+}
+
+/// Dateiformat: Inhalt als String, damit die Signatur (bzw. Prüfsumme ohne
+/// Schlüssel) unabhängig von der JSON-Formatierung prüfbar bleibt
+#[derive(Serialize, Deserialize)]
+struct SignedSnapshot {
+    signature: String,
+    payload: String,
+}
+
+/// Inhalt von `sync/control/<device>/latest.json`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SnapshotMarker {
+    pub snapshot_id: String,
+    /// Pfad des Snapshots relativ zu `remote_path`
+    pub path: String,
+}
+
+/// Inhalt von `sync/control/compactor.lease`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CompactionLease {
+    /// ULID, unterscheidet mehrere Leases desselben Geräts
+    pub lease_id: String,
+    pub device_id: String,
+    /// Ablauf in epoch ms
+    pub expires_at: i64,
+}
+
+impl CompactionLease {
+    fn new(device_id: &str) -> Self {
+        Self {
+            lease_id: ulid::Ulid::new().to_string(),
+            device_id: device_id.to_string(),
+            expires_at: chrono::Utc::now().timestamp_millis() + LEASE_TTL_MS,
+        }
+    }
+
+    pub fn is_expired(&self) -> bool {
+        self.expires_at <= chrono::Utc::now().timestamp_millis()
+    }
+}
+
+impl EntityState {
+    fn clock(&self) -> HybridLogicalClock {
+        HybridLogicalClock {
+            ts: self.logical_clock,
+            logical_counter: self.clock_counter,
+            device_id: self.clock_device.clone(),
+        }
+    }
+}
+
+impl SnapshotMarker {
+    /// Ist die Op-Datei `filename` (`<ULID>.ndjson[.gz]`) im Snapshot enthalten?
+    pub fn covers(&self, filename: &str) -> bool {
+        match (ulid_ms(filename), ulid_ms(&self.snapshot_id)) {
+            (Some(file_ms), Some(snapshot_ms)) => file_ms < snapshot_ms,
+            _ => false,
+        }
+    }
+}
+
+/// Synchronisierte Spalten eines Entitätstyps (ohne CRDT-Metadaten)
+pub(crate) fn synced_columns(entity_type: &str) -> &'static [&'static str] {
+    ENTITY_TABLES
+        .iter()
+        .find(|t| t.entity_type == entity_type)
+        .map_or(&[], |t| t.columns)
+}
+
+/// Synchronisierte Tabellen als `(Entitätstyp, Tabelle, Spalten)`, in der
+/// Reihenfolge beim Anwenden
+pub(crate) fn synced_tables(
+) -> impl Iterator<Item = (&'static str, &'static str, &'static [&'static str])> {
+    ENTITY_TABLES
+        .iter()
+        .map(|t| (t.entity_type, t.table, t.columns))
+}
+
+/// Ergebnis einer Kompaktierung
+#[derive(Debug, Clone, PartialEq)]
+pub struct CompactionReport {
+    pub snapshot_id: String,
+    pub entities: usize,
+    pub deleted_op_files: usize,
+}
+
+/// ULID einer Op-Datei (`<ULID>.ndjson[.gz]`)
+pub fn op_file_ulid(filename: &str) -> &str {
+    filename.split_once('.').map_or(filename, |(ulid, _)| ulid)
+}
+
+/// Neueste gemergte Op-Datei je Gerät laut `sync_manifest`
+fn merged_op_files(conn: &Connection) -> Result<BTreeMap<String, String>, AppError> {
+    download_service::ensure_manifest_table(conn)?;
+    let mut stmt = conn.prepare("SELECT path FROM sync_manifest")?;
+    let paths = stmt.query_map([], |row| row.get::<_, String>(0))?;
+
+    let ops_prefix = format!("{}/", sync_paths::OPS_DIR);
+    let mut seen = BTreeMap::new();
+    for path in paths {
+        let path = path?;
+        let Some((_, rest)) = path.split_once(&ops_prefix) else {
+            continue;
+        };
+        let (Some(device), Some(filename)) = (rest.split('/').next(), rest.rsplit('/').next())
+        else {
+            continue;
+        };
+        // Verzeichnis-ETags (`…/`) sind keine Op-Dateien
+        if !sync_paths::is_op_file(filename) {
+            continue;
+        }
+        let ulid = op_file_ulid(filename).to_string();
+        let newest = seen.entry(device.to_string()).or_insert_with(String::new);
+        if ulid > *newest {
+            *newest = ulid;
+        }
+    }
+    Ok(seen)
+}
+
+/// Zeitstempel (ms) der ULID am Anfang von `name`
+fn ulid_ms(name: &str) -> Option<u64> {
+    let stem = name.split('.').next().unwrap_or(name);
+    ulid::Ulid::from_string(stem).ok().map(|u| u.timestamp_ms())
+}
+
+/// Signierter Inhalt: Geräte-ID und Payload
+fn signed_content(device_id: &str, payload: &str) -> Vec<u8> {
+    format!("{}\n{}", device_id, payload).into_bytes()
+}
+
+pub(crate) fn sql_to_json(value: ValueRef<'_>) -> serde_json::Value {
+    match value {
+        ValueRef::Null | ValueRef::Blob(_) => serde_json::Value::Null,
+        ValueRef::Integer(i) => i.into(),
+        ValueRef::Real(f) => f.into(),
+        ValueRef::Text(t) => String::from_utf8_lossy(t).into_owned().into(),
+    }
+}
+
+fn json_to_sql(value: &serde_json::Value) -> SqlValue {
+    match value {
+        serde_json::Value::Null => SqlValue::Null,
+        serde_json::Value::Bool(b) => SqlValue::Integer(*b as i64),
+        serde_json::Value::Number(n) => match n.as_i64() {
+            Some(i) => SqlValue::Integer(i),
+            None => SqlValue::Real(n.as_f64().unwrap_or_default()),
+        },
+        serde_json::Value::String(s) => SqlValue::Text(s.clone()),
+        other => SqlValue::Text(other.to_string()),
+    }
+}
+
+/// Liest den aktuellen Zustand aller synchronisierten Entitäten
+pub fn build_snapshot(conn: &Connection, device_id: &str) -> Result<Snapshot, AppError> {
+    // ID vor dem Lesen des Zustands: jede ältere Op-Datei wurde erst nach
+    // der lokalen Änderung erzeugt und ist damit im Snapshot enthalten
+    let snapshot_id = ulid::Ulid::new().to_string();
+    let mut entities = Vec::new();
+    for table in ENTITY_TABLES {
+        let mut stmt = conn.prepare(&format!(
+            "SELECT uuid, logical_clock, clock_counter, clock_device, deleted, {} FROM {} ORDER BY uuid",
+            table.columns.join(", "),
+            table.table
+        ))?;
+        let rows = stmt.query_map([], |row| {
+            let mut fields = serde_json::Map::new();
+            for (i, column) in table.columns.iter().enumerate() {
+                fields.insert(column.to_string(), sql_to_json(row.get_ref(i + 5)?));
+            }
+            Ok(EntityState {
+                entity_type: table.entity_type.to_string(),
+                entity_id: row.get(0)?,
+                logical_clock: row.get(1)?,
+                clock_counter: row.get(2)?,
+                clock_device: row.get(3)?,
+                deleted: row.get::<_, i32>(4)? == 1,
+                fields,
+            })
+        })?;
+        for row in rows {
+            entities.push(row?);
+        }
+    }
+
+    Ok(Snapshot {
+        snapshot_id,
+        device_id: device_id.to_string(),
+        created_at: chrono::Utc::now().timestamp_millis(),
+        entities,
+        seen_ops: merged_op_files(conn)?,
+    })
+}
+
+/// Serialisiert und signiert einen Snapshot (ohne Schlüssel nur Prüfsumme)
+pub fn sign_snapshot(snapshot: &Snapshot, cipher: &OpCipher) -> Result<Vec<u8>, AppError> {
+    let payload = serde_json::to_string(snapshot)
+        .map_err(|e| AppError::Other(format!("JSON serialize failed: {}", e)))?;
+    let signed = SignedSnapshot {
+        signature: cipher.sign(&signed_content(&snapshot.device_id, &payload)),
+        payload,
+    };
+    serde_json::to_vec(&signed)
+        .map_err(|e| AppError::Other(format!("JSON serialize failed: {}", e)))
+}
+
+/// Prüft Signatur bzw. Prüfsumme und liefert den Snapshot
+pub fn verify_snapshot(data: &[u8], cipher: &OpCipher) -> Result<Snapshot, AppError> {
+    let signed: SignedSnapshot = serde_json::from_slice(data)
+        .map_err(|e| AppError::Validation(format!("Snapshot unlesbar: {}", e)))?;
+    let snapshot: Snapshot = serde_json::from_str(&signed.payload)
+        .map_err(|e| AppError::Validation(format!("Snapshot unlesbar: {}", e)))?;
+    if !cipher.verify(
+        &signed_content(&snapshot.device_id, &signed.payload),
+        &signed.signature,
+    ) {
+        return Err(AppError::Validation(format!(
+            "Signatur des Snapshots {} ungültig",
+            snapshot.snapshot_id
+        )));
+    }
+    Ok(snapshot)
+}
+
+/// Wendet einen Snapshot an (LWW pro Entität); liefert die Zahl geänderter Entitäten
+pub fn apply_snapshot(conn: &Connection, snapshot: &Snapshot) -> Result<usize, AppError> {
+    let tx = conn.unchecked_transaction()?;
+    // Fotos kommen vor ihren Wachteln und Ereignissen: Verweise erst beim
+    // Commit prüfen
+    tx.execute_batch("PRAGMA defer_foreign_keys = ON")?;
+    let mut applied = 0;
+
+    for table in ENTITY_TABLES {
+        for state in snapshot
+            .entities
+            .iter()
+            .filter(|s| s.entity_type == table.entity_type)
+        {
+            match apply_entity(&tx, table, state) {
+                Ok(true) => applied += 1,
+                Ok(false) => {}
+                // z.B. doppeltes Datum bei Eiern: restliche Entitäten trotzdem übernehmen
+                Err(e) => log::warn!(
+                    "Snapshot {}: {} {} nicht übernommen: {}",
+                    snapshot.snapshot_id,
+                    state.entity_type,
+                    state.entity_id,
+                    e
+                ),
+            }
+        }
+    }
+
+    // Verweise auf Einträge, die nicht im Snapshot stehen
+    integrity::repair_orphans(&tx)?;
+    tx.commit()?;
+
+    if let Some(newest) = snapshot.entities.iter().map(EntityState::clock).max() {
+        crdt_service::observe_remote_clock(conn, &newest)?;
+    }
+    Ok(applied)
+}
+
+fn apply_entity(
+    tx: &rusqlite::Transaction,
+    table: &EntityTable,
+    state: &EntityState,
+) -> Result<bool, AppError> {
+    let clock = state.clock();
+    let current = crdt_service::load_entity_clock(tx, table.table, &state.entity_id)?;
+
+    // Wie bei den Ops: nur Neueres, gelöschte Entitäten bleiben gelöscht
+    if current
+        .as_ref()
+        .is_some_and(|(current_clock, deleted)| *deleted || *current_clock >= clock)
+    {
+        return Ok(false);
+    }
+
+    let columns: Vec<&str> = table
+        .columns
+        .iter()
+        .copied()
+        .filter(|c| state.fields.contains_key(*c))
+        .collect();
+    let mut values = vec![
+        SqlValue::Text(state.entity_id.clone()),
+        SqlValue::Integer(state.logical_clock),
+        SqlValue::Integer(state.deleted as i64),
+    ];
+    values.extend(columns.iter().map(|c| json_to_sql(&state.fields[*c])));
+
+    let sql = if current.is_none() {
+        let mut names = vec!["uuid", "logical_clock", "deleted", "rev"];
+        let mut placeholders = vec!["?1".to_string(), "?2".into(), "?3".into(), "?2".into()];
+        for (i, column) in columns.iter().enumerate() {
+            names.push(column);
+            placeholders.push(format!("?{}", i + 4));
+        }
+        for (column, default) in table.insert_defaults {
+            names.push(column);
+            placeholders.push(default.to_string());
+        }
+        format!(
+            "INSERT INTO {} ({}) VALUES ({})",
+            table.table,
+            names.join(", "),
+            placeholders.join(", ")
+        )
+    } else {
+        let mut assignments = vec!["logical_clock = ?2".to_string(), "deleted = ?3".into()];
+        for (i, column) in columns.iter().enumerate() {
+            assignments.push(format!("{} = ?{}", column, i + 4));
+        }
+        format!(
+            "UPDATE {} SET {} WHERE uuid = ?1",
+            table.table,
+            assignments.join(", ")
+        )
+    };
+
+    // Meist dieselben Spalten: SQL wiederholt sich, Statement aus dem Cache
+    tx.prepare_cached(&sql)?
+        .execute(rusqlite::params_from_iter(values))?;
+    crdt_service::stamp_entity(tx, table.table, &state.entity_id, &clock)?;
+    // Neuerer Stand ersetzt geparkte Verweise; fehlende Ziele parkt
+    // `repair_orphans` danach erneut
+    crdt_service::drop_deferred_references(tx, table.table, &state.entity_id, None)?;
+    crdt_service::resolve_deferred_references(tx, table.table, &state.entity_id)?;
+    Ok(true)
+}
+
+/// Liest `sync/control/<device>/latest.json`; `None` wenn das Gerät noch
+/// keinen Snapshot geschrieben hat
+pub async fn read_marker(
+    client: &impl SyncBackend,
+    base: &str,
+    device_id: &str,
+) -> Result<Option<SnapshotMarker>, AppError> {
+    let path = format!("{}/{}", base, sync_paths::latest_marker_path(device_id));
+    match client.get(&path).await {
+        Ok(data) => match serde_json::from_slice(&data) {
+            Ok(marker) => Ok(Some(marker)),
+            Err(e) => {
+                log::warn!("Snapshot-Marker {} unlesbar: {}", path, e);
+                Ok(None)
+            }
+        },
+        Err(e @ AppError::PermissionDenied(_)) => Err(e),
+        Err(_) => Ok(None),
+    }
+}
+
+/// Legt alle Ebenen von `relative` unter `base` an (WebDAV kann nur eine pro MKCOL)
+pub(crate) async fn ensure_dirs(client: &impl SyncBackend, base: &str, relative: &str) {
+    let mut path = base.to_string();
+    for segment in relative.split('/') {
+        path = format!("{}/{}", path, segment);
+        if let Err(e) = client.mkcol(&path).await {
+            log::debug!("MKCOL '{}' note: {:?}", path, e);
+        }
+    }
+}
+
+/// Liest die aktuelle Kompaktierungs-Lease; `None` wenn keine (lesbare) liegt
+pub async fn read_lease(
+    client: &impl SyncBackend,
+    base: &str,
+) -> Result<Option<CompactionLease>, AppError> {
+    let path = format!("{}/{}", base, sync_paths::COMPACTOR_LEASE);
+    match client.get(&path).await {
+        Ok(data) => Ok(serde_json::from_slice(&data).ok()),
+        Err(e @ AppError::PermissionDenied(_)) => Err(e),
+        Err(_) => Ok(None),
+    }
+}
+
+/// Versucht die Lease zu bekommen; `None`, wenn ein anderes Gerät gerade
+/// kompaktiert
+async fn acquire_lease(
+    client: &impl SyncBackend,
+    base: &str,
+    device_id: &str,
+) -> Result<Option<CompactionLease>, AppError> {
+    let path = format!("{}/{}", base, sync_paths::COMPACTOR_LEASE);
+    ensure_dirs(client, base, sync_paths::CONTROL_DIR).await;
+    let lease = CompactionLease::new(device_id);
+    let data = serde_json::to_vec(&lease)
+        .map_err(|e| AppError::Other(format!("JSON serialize failed: {}", e)))?;
+
+    if client.put_if_absent(&path, data.clone()).await? {
+        return Ok(Some(lease));
+    }
+    match read_lease(client, base).await? {
+        Some(current) if !current.is_expired() => {
+            log::info!(
+                "Kompaktierung verschoben: {} kompaktiert gerade (Lease bis {})",
+                current.device_id,
+                current.expires_at
+            );
+            return Ok(None);
+        }
+        _ => {}
+    }
+
+    // Abgelaufen oder unlesbar: übernehmen. Räumt ein zweites Gerät
+    // gleichzeitig auf, gewinnt nur einer das erneute Anlegen
+    log::warn!("Abgelaufene Kompaktierungs-Lease wird übernommen");
+    client.delete(&path).await?;
+    if client.put_if_absent(&path, data).await? {
+        Ok(Some(lease))
+    } else {
+        Ok(None)
+    }
+}
+
+/// Gehört die Lease noch uns? Vor dem Löschen von Op-Dateien geprüft, falls
+/// sie inzwischen abgelaufen und von einem anderen Gerät übernommen wurde
+async fn holds_lease(
+    client: &impl SyncBackend,
+    base: &str,
+    lease: &CompactionLease,
+) -> Result<bool, AppError> {
+    Ok(read_lease(client, base)
+        .await?
+        .is_some_and(|current| current.lease_id == lease.lease_id && !current.is_expired()))
+}
+
+async fn release_lease(client: &impl SyncBackend, base: &str, lease: &CompactionLease) {
+    match holds_lease(client, base, lease).await {
+        Ok(true) => {
+            let path = format!("{}/{}", base, sync_paths::COMPACTOR_LEASE);
+            if let Err(e) = client.delete(&path).await {
+                log::warn!("Kompaktierungs-Lease nicht freigegeben: {:?}", e);
+            }
+        }
+        Ok(false) => {}
+        Err(e) => log::warn!("Kompaktierungs-Lease nicht lesbar: {:?}", e),
+    }
+}
+
+/// Schreibt einen Snapshot dieses Geräts und löscht die darin enthaltenen
+/// eigenen Op-Dateien
+///
+/// Ohne konfigurierten oder bei deaktiviertem Sync passiert nichts (`None`),
+/// ebenso, solange ein anderes Gerät die Kompaktierungs-Lease hält.
+pub async fn compact_ops(conn: &Connection) -> Result<Option<CompactionReport>, AppError> {
+    let Some(settings) = sync_service::load_sync_settings(conn)? else {
+        return Ok(None);
+    };
+    // Solange Entitätstypen ausgenommen sind, fehlten sie im Snapshot; die
+    // gelöschten Op-Dateien wären für neue Geräte verloren
+    if !settings.enabled || settings.paused || !settings.excluded_entity_types.is_empty() {
+        return Ok(None);
+    }
+
+    let client = ConfiguredBackend::from_settings(&settings)?;
+    let cipher = OpCipher::load(conn)?;
+    let base = settings.remote_path.trim_end_matches('/');
+    let device_id = upload_service::get_device_id(conn)?;
+    let Some(lease) = acquire_lease(&client, base, &device_id).await? else {
+        return Ok(None);
+    };
+
+    let result = compact_with_lease(conn, &client, &cipher, base, &device_id, &lease).await;
+    release_lease(&client, base, &lease).await;
+    result
+}
+
+async fn compact_with_lease(
+    conn: &Connection,
+    client: &ConfiguredBackend,
+    cipher: &OpCipher,
+    base: &str,
+    device_id: &str,
+    lease: &CompactionLease,
+) -> Result<Option<CompactionReport>, AppError> {
+    let previous = read_marker(client, base, device_id).await?;
+
+    let snapshot = build_snapshot(conn, device_id)?;
+
+    let dir = sync_paths::snapshot_path(device_id, &sync_paths::current_year_month_day());
+    ensure_dirs(client, base, &dir).await;
+    let relative = format!("{}/{}.json", dir, snapshot.snapshot_id);
+    client
+        .put(
+            &format!("{}/{}", base, relative),
+            cipher.seal(sign_snapshot(&snapshot, cipher)?)?,
+        )
+        .await?;
+
+    // Erst nach dem Snapshot umschalten, sonst zeigt der Marker ins Leere
+    let marker = SnapshotMarker {
+        snapshot_id: snapshot.snapshot_id.clone(),
+        path: relative,
+    };
+    ensure_dirs(client, base, &sync_paths::control_path(device_id)).await;
+    client
+        .put(
+            &format!("{}/{}", base, sync_paths::latest_marker_path(device_id)),
+            serde_json::to_vec(&marker)
+                .map_err(|e| AppError::Other(format!("JSON serialize failed: {}", e)))?,
+        )
+        .await?;
+
+    if let Some(previous) = previous {
+        if let Err(e) = client.delete(&format!("{}/{}", base, previous.path)).await {
+            log::debug!("Alter Snapshot {} nicht gelöscht: {:?}", previous.path, e);
+        }
+    }
+
+    // Snapshot und Marker schaden nicht, gelöschte Op-Dateien schon
+    if !holds_lease(client, base, lease).await? {
+        log::warn!("Kompaktierungs-Lease verloren, Op-Dateien bleiben erhalten");
+        return Ok(None);
+    }
+    let deleted_op_files = delete_covered_ops(client, base, device_id, &marker).await?;
+    log::info!(
+        "Op-Log kompaktiert: Snapshot {} mit {} Entitäten, {} Op-Dateien gelöscht",
+        marker.snapshot_id,
+        snapshot.entities.len(),
+        deleted_op_files
+    );
+
+    Ok(Some(CompactionReport {
+        snapshot_id: marker.snapshot_id,
+        entities: snapshot.entities.len(),
+        deleted_op_files,
+    }))
+}
+
+/// Eigene Op-Dateien (`sync/ops/<device>/<YYYYMM>/<ULID>.ndjson[.gz]`)
+async fn list_own_op_files(
+    client: &impl SyncBackend,
+    base: &str,
+    device_id: &str,
+) -> Result<Vec<String>, AppError> {
+    let device_path = format!("{}/{}/{}", base, sync_paths::OPS_DIR, device_id);
+    let months = match client.list(&device_path).await {
+        Ok(entries) => entries,
+        Err(e @ AppError::PermissionDenied(_)) => return Err(e),
+        Err(_) => return Ok(Vec::new()),
+    };
+
+    let mut files = Vec::new();
+    for month in months {
+        let RemoteEntry::Folder { name, .. } = month else {
+            continue;
+        };
+        let month_path = format!("{}/{}", device_path, name);
+        for entry in client.list(&month_path).await.unwrap_or_default() {
+            if let RemoteEntry::File { name, .. } = entry {
+                if sync_paths::is_op_file(&name) {
+                    files.push(format!("{}/{}", month_path, name));
+                }
+            }
+        }
+    }
+    Ok(files)
+}
+
+async fn delete_covered_ops(
+    client: &impl SyncBackend,
+    base: &str,
+    device_id: &str,
+    marker: &SnapshotMarker,
+) -> Result<usize, AppError> {
+    let mut deleted = 0;
+    for path in list_own_op_files(client, base, device_id).await? {
+        let filename = path.rsplit('/').next().unwrap_or(&path);
+        if !marker.covers(filename) {
+            continue;
+        }
+        match client.delete(&path).await {
+            Ok(()) => deleted += 1,
+            Err(e) => log::warn!("Op-Datei {} nicht gelöscht: {:?}", path, e),
+        }
+    }
+    Ok(deleted)
+}
+
+/// Kompaktiert, wenn der letzte Snapshot älter als
+/// [`COMPACTION_INTERVAL_DAYS`] ist und sich mindestens [`MIN_OP_FILES`]
+/// eigene Op-Dateien angesammelt haben
+///
+/// Fragt den Server höchstens einmal pro Stunde.
+pub async fn compact_if_due(conn: &Connection) -> Result<Option<CompactionReport>, AppError> {
+    let now = chrono::Utc::now().timestamp_millis();
+    if now - LAST_CHECK_MS.load(Ordering::Relaxed) < CHECK_INTERVAL_MS {
+        return Ok(None);
+    }
+    LAST_CHECK_MS.store(now, Ordering::Relaxed);
+
+    let Some(settings) = sync_service::load_sync_settings(conn)? else {
+        return Ok(None);
+    };
+    if !settings.enabled {
+        return Ok(None);
+    }
+    let client = ConfiguredBackend::from_settings(&settings)?;
+    let base = settings.remote_path.trim_end_matches('/');
+    let device_id = upload_service::get_device_id(conn)?;
+
+    if let Some(marker) = read_marker(&client, base, &device_id).await? {
+        let age_ms = ulid_ms(&marker.snapshot_id).map_or(i64::MAX, |ms| now - ms as i64);
+        if age_ms < chrono::Duration::days(COMPACTION_INTERVAL_DAYS).num_milliseconds() {
+            return Ok(None);
+        }
+    }
+    if list_own_op_files(&client, base, &device_id).await?.len() < MIN_OP_FILES {
+        return Ok(None);
+    }
+
+    compact_ops(conn).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database;
+    use crate::models::Quail;
+    use crate::services::profile_service;
+
+    fn setup() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        database::schema::init_schema(&conn).unwrap();
+        conn
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_roundtrip_and_apply() {
+        let source = setup();
+        let quail = Quail::new("Helga".to_string());
+        profile_service::create_profile(&source, &quail)
+            .await
+            .unwrap();
+        source
+            .execute(
+                "UPDATE quails SET ring_color = 'rot', logical_clock = 10 WHERE uuid = ?1",
+                [quail.uuid.to_string()],
+            )
+            .unwrap();
+
+        let snapshot = build_snapshot(&source, "device-a").unwrap();
+        let cipher = OpCipher::default();
+        let data = sign_snapshot(&snapshot, &cipher).unwrap();
+        assert_eq!(verify_snapshot(&data, &cipher).unwrap(), snapshot);
+
+        let target = setup();
+        assert_eq!(apply_snapshot(&target, &snapshot).unwrap(), 1);
+        let synced = profile_service::get_profile(&target, &quail.uuid).unwrap();
+        assert_eq!(synced.name, "Helga");
+        assert_eq!(synced.ring_color, Some(crate::models::RingColor::Rot));
+
+        // Erneutes Anwenden ändert nichts
+        assert_eq!(apply_snapshot(&target, &snapshot).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_snapshot_records_merged_op_files() {
+        let conn = setup();
+        download_service::ensure_manifest_table(&conn).unwrap();
+        for path in [
+            "/Stalltagebuch/sync/ops/device-b/202501/01JA0000000000000000000000.ndjson",
+            "/Stalltagebuch/sync/ops/device-b/202502/01JB0000000000000000000000.ndjson",
+            "/Stalltagebuch/sync/snapshots/device-c/20250101/01JC0000000000000000000000.json",
+        ] {
+            conn.execute(
+                "INSERT INTO sync_manifest (path, etag) VALUES (?1, 'e')",
+                [path],
+            )
+            .unwrap();
+        }
+
+        let snapshot = build_snapshot(&conn, "device-a").unwrap();
+        assert_eq!(
+            snapshot.seen_ops,
+            BTreeMap::from([(
+                "device-b".to_string(),
+                "01JB0000000000000000000000".to_string()
+            )])
+        );
+    }
+
+    fn empty_snapshot() -> Snapshot {
+        Snapshot {
+            snapshot_id: ulid::Ulid::new().to_string(),
+            device_id: "device-a".to_string(),
+            created_at: 0,
+            entities: Vec::new(),
+            seen_ops: BTreeMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_tampered_snapshot_is_rejected() {
+        let cipher = OpCipher::default();
+        let data = String::from_utf8(sign_snapshot(&empty_snapshot(), &cipher).unwrap()).unwrap();
+        let tampered = data.replace("device-a", "device-b");
+        assert!(matches!(
+            verify_snapshot(tampered.as_bytes(), &cipher),
+            Err(AppError::Validation(_))
+        ));
+    }
+
+    #[cfg(feature = "encryption")]
+    #[test]
+    fn test_forged_snapshot_is_rejected_with_key() {
+        use crate::services::{photo_checksum, photo_crypto::PhotoKey};
+
+        let cipher = OpCipher::with_key(Some(PhotoKey::generate().unwrap()));
+        let snapshot = empty_snapshot();
+        let data = sign_snapshot(&snapshot, &cipher).unwrap();
+        assert_eq!(verify_snapshot(&data, &cipher).unwrap(), snapshot);
+
+        // Veränderter Inhalt mit neu berechneter Prüfsumme bzw. fremdem Schlüssel
+        let mut forged = snapshot.clone();
+        forged.device_id = "device-b".to_string();
+        let payload = serde_json::to_string(&forged).unwrap();
+        let content = signed_content(&forged.device_id, &payload);
+        let other_key = OpCipher::with_key(Some(PhotoKey::generate().unwrap()));
+        for signature in [
+            photo_checksum::content_checksum(&content),
+            other_key.sign(&content),
+        ] {
+            let data = serde_json::to_vec(&SignedSnapshot {
+                signature,
+                payload: payload.clone(),
+            })
+            .unwrap();
+            assert!(matches!(
+                verify_snapshot(&data, &cipher),
+                Err(AppError::Validation(_))
+            ));
+        }
+
+        // Snapshots von vor dem Schlüssel nur während der Migration
+        let unkeyed = sign_snapshot(&snapshot, &OpCipher::default()).unwrap();
+        assert!(verify_snapshot(&unkeyed, &cipher).is_err());
+        let migrating = cipher.accepting_plaintext(true);
+        assert_eq!(verify_snapshot(&unkeyed, &migrating).unwrap(), snapshot);
+    }
+
+    #[test]
+    fn test_marker_covers_older_op_files() {
+        let older = ulid::Ulid::from_parts(1_000, 0).to_string();
+        let newer = ulid::Ulid::from_parts(3_000, 0).to_string();
+        let marker = SnapshotMarker {
+            snapshot_id: ulid::Ulid::from_parts(2_000, 0).to_string(),
+            path: String::new(),
+        };
+        assert!(marker.covers(&format!("{}.ndjson", older)));
+        assert!(!marker.covers(&format!("{}.ndjson", newer)));
+        assert!(!marker.covers("kaputt.ndjson"));
+    }
+}
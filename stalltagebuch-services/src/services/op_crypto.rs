@@ -0,0 +1,169 @@
+//! Verschlüsselung der Op-Dateien und Snapshots auf dem Server
+//!
+//! Op-Dateien und Snapshots enthalten alle Notizen und Gesundheitsdaten. Ist
+//! lokal ein Schlüssel hinterlegt (derselbe wie für die Fotos, siehe
+//! [`photo_crypto`](crate::services::photo_crypto)), werden sie vor dem Upload
+//! mit ihm verschlüsselt. Verschlüsselte Dateien erkennt der Download am
+//! Kopf der Datei, Dateinamen und Verzeichnisse bleiben unverändert.
+//!
+//! Mit Schlüssel werden unverschlüsselte Dateien abgelehnt, sonst könnte der
+//! Server beliebige Änderungen unterschieben. Ältere Dateien aus der Zeit vor
+//! dem Aktivieren liest der Download nur, wenn die Migration ausdrücklich
+//! erlaubt ist (siehe [`photo_crypto::set_accept_plaintext`]).
+//!
+//! Snapshots werden zusätzlich mit einem aus dem Schlüssel abgeleiteten
+//! HMAC-SHA256 signiert (siehe [`OpCipher::sign`]). Ohne Schlüssel bleibt
+//! nur eine SHA-256-Prüfsumme, die beschädigte, aber keine absichtlich
+//! veränderten Dateien erkennt.
+//!
+//! Ohne Feature `encryption` werden Dateien unverändert übertragen.
+
+use crate::error::AppError;
+use crate::services::photo_checksum;
+#[cfg(feature = "encryption")]
+use crate::services::photo_crypto::{self, PhotoKey};
+use rusqlite::Connection;
+
+/// Kennzeichnet eine Signatur mit Schlüssel, im Unterschied zur Prüfsumme
+#[cfg(feature = "encryption")]
+const MAC_PREFIX: &str = "hmac-sha256:";
+
+/// Ver- und Entschlüsselt Sync-Dateien mit dem lokalen Schlüssel (falls vorhanden)
+#[derive(Debug, Clone, Default)]
+pub struct OpCipher {
+    #[cfg(feature = "encryption")]
+    key: Option<PhotoKey>,
+    /// Unverschlüsselte Dateien trotz Schlüssel annehmen (Migration)
+    #[cfg(feature = "encryption")]
+    accept_plaintext: bool,
+}
+
+impl OpCipher {
+    /// Verwendet den lokal gespeicherten Schlüssel
+    pub fn load(conn: &Connection) -> Result<Self, AppError> {
+        #[cfg(feature = "encryption")]
+        {
+            Ok(Self {
+                key: photo_crypto::load_key(conn)?,
+                accept_plaintext: photo_crypto::accepts_plaintext(conn)?,
+            })
+        }
+        #[cfg(not(feature = "encryption"))]
+        {
+            let _ = conn;
+            Ok(Self::default())
+        }
+    }
+
+    #[cfg(feature = "encryption")]
+    pub fn with_key(key: Option<PhotoKey>) -> Self {
+        Self {
+            key,
+            accept_plaintext: false,
+        }
+    }
+
+    /// Nimmt unverschlüsselte Dateien auch mit Schlüssel an
+    #[cfg(feature = "encryption")]
+    pub fn accepting_plaintext(self, accept_plaintext: bool) -> Self {
+        Self {
+            accept_plaintext,
+            ..self
+        }
+    }
+
+    /// Inhalt für den Upload (ohne Schlüssel unverändert)
+    pub fn seal(&self, data: Vec<u8>) -> Result<Vec<u8>, AppError> {
+        #[cfg(feature = "encryption")]
+        if let Some(key) = &self.key {
+            return key.encrypt(&data);
+        }
+        Ok(data)
+    }
+
+    /// Heruntergeladener Inhalt im Klartext
+    ///
+    /// Unverschlüsselte Dateien werden nur ohne Schlüssel oder mit erlaubter
+    /// Migration unverändert geliefert.
+    pub fn open(&self, data: Vec<u8>) -> Result<Vec<u8>, AppError> {
+        #[cfg(feature = "encryption")]
+        {
+            if photo_crypto::is_encrypted(&data) {
+                return match &self.key {
+                    Some(key) => key.decrypt(data),
+                    None => Err(AppError::Validation(
+                        "Sync-Datei ist verschlüsselt, aber es ist kein Schlüssel hinterlegt"
+                            .to_string(),
+                    )),
+                };
+            }
+            if self.key.is_some() && !self.accept_plaintext {
+                return Err(AppError::Validation(
+                    "Unverschlüsselte Sync-Datei abgelehnt; ältere Dateien nur nach Freigabe der Migration"
+                        .to_string(),
+                ));
+            }
+        }
+        Ok(data)
+    }
+
+    /// HMAC-SHA256 über `data` mit dem Schlüssel, ohne Schlüssel nur die
+    /// SHA-256-Prüfsumme
+    pub fn sign(&self, data: &[u8]) -> String {
+        #[cfg(feature = "encryption")]
+        if let Some(key) = &self.key {
+            return format!("{}{}", MAC_PREFIX, key.mac(data));
+        }
+        photo_checksum::content_checksum(data)
+    }
+
+    /// Prüft eine Signatur von [`sign`](Self::sign)
+    ///
+    /// Mit Schlüssel zählt nur der HMAC; eine bloße Prüfsumme (Snapshot aus
+    /// der Zeit vor dem Aktivieren) nur mit erlaubter Migration.
+    pub fn verify(&self, data: &[u8], signature: &str) -> bool {
+        #[cfg(feature = "encryption")]
+        if let Some(key) = &self.key {
+            if let Some(mac) = signature.strip_prefix(MAC_PREFIX) {
+                return key.verify_mac(data, mac);
+            }
+            if !self.accept_plaintext {
+                return false;
+            }
+        }
+        photo_checksum::content_checksum(data) == signature
+    }
+}
+
+#[cfg(all(test, feature = "encryption"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sealed_files_need_the_key() {
+        let key = PhotoKey::generate().unwrap();
+        let cipher = OpCipher::with_key(Some(key.clone()));
+        let plain = b"{\"entity_type\":\"quail\",\"notes\":\"Augenentzuendung\"}\n".to_vec();
+
+        let sealed = cipher.seal(plain.clone()).unwrap();
+        assert!(!sealed.windows(5).any(|w| w == b"quail"));
+        assert_eq!(cipher.open(sealed.clone()).unwrap(), plain);
+
+        // Mit Schlüssel nur nach Freigabe der Migration Klartext annehmen
+        assert!(matches!(
+            cipher.open(plain.clone()),
+            Err(AppError::Validation(_))
+        ));
+        let migrating = cipher.clone().accepting_plaintext(true);
+        assert_eq!(migrating.open(plain.clone()).unwrap(), plain);
+        assert_eq!(migrating.open(sealed.clone()).unwrap(), plain);
+        assert_eq!(OpCipher::default().open(plain.clone()).unwrap(), plain);
+
+        assert!(matches!(
+            OpCipher::default().open(sealed.clone()),
+            Err(AppError::Validation(_))
+        ));
+        let other = OpCipher::with_key(Some(PhotoKey::generate().unwrap()));
+        assert!(other.open(sealed).is_err());
+    }
+}
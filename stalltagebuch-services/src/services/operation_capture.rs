@@ -0,0 +1,158 @@
+/// Captures local changes and converts them into CRDT operations
+use crate::error::AppError;
+use crate::services::{crdt_service, sync_service, upload_service};
+use rusqlite::Connection;
+
+/// Turns the changes of one entity into operations with ascending clocks
+/// from the local HLC, stamps the (already written) row with the last one
+/// and uploads them
+///
+/// Changes of entity types excluded from sync are only stamped locally.
+async fn record(
+    conn: &Connection,
+    entity_type: &str,
+    entity_id: &str,
+    changes: Vec<crdt_service::CrdtOp>,
+) -> Result<(), AppError> {
+    let device_id = upload_service::get_device_id(conn)?;
+    let mut clock = crdt_service::load_local_clock(conn, &device_id)?;
+
+    let operations: Vec<_> = changes
+        .into_iter()
+        .map(|op| {
+            crdt_service::Operation::new(
+                entity_type.to_string(),
+                entity_id.to_string(),
+                &mut clock,
+                op,
+            )
+        })
+        .collect();
+
+    crdt_service::save_local_clock(conn, &clock)?;
+    // Lokale Löschungen entfernen die Zeile, im Op-Log bleibt der Tombstone
+    for op in &operations {
+        crdt_service::log_operation(conn, op)?;
+    }
+    if let Some(table) = crdt_service::entity_table(entity_type) {
+        crdt_service::stamp_entity(conn, table, entity_id, &clock)?;
+    }
+
+    if let Some(settings) = sync_service::load_sync_settings(conn)? {
+        if !settings.syncs(entity_type) {
+            log::debug!(
+                "Sync of {} turned off, operations not uploaded",
+                entity_type
+            );
+            return Ok(());
+        }
+    }
+
+    upload_service::upload_ops_batch(conn, operations).await?;
+
+    Ok(())
+}
+
+fn set(field: &str, value: impl Into<serde_json::Value>) -> crdt_service::CrdtOp {
+    crdt_service::CrdtOp::LwwSet {
+        field: field.to_string(),
+        value: value.into(),
+    }
+}
+
+/// Captures the changed fields of an entity (all set fields when it was
+/// created), see `repository::save_with_ops`
+pub async fn capture_changes(
+    conn: &Connection,
+    entity_type: &str,
+    entity_id: &str,
+    changes: Vec<(&str, serde_json::Value)>,
+) -> Result<(), AppError> {
+    let changes = changes
+        .into_iter()
+        .map(|(field, value)| set(field, value))
+        .collect();
+    record(conn, entity_type, entity_id, changes).await
+}
+
+/// Captures the DELETE operation of an entity
+pub async fn capture_delete(
+    conn: &Connection,
+    entity_type: &str,
+    entity_id: &str,
+) -> Result<(), AppError> {
+    record(
+        conn,
+        entity_type,
+        entity_id,
+        vec![crdt_service::CrdtOp::Delete],
+    )
+    .await
+}
+
+/// Captures UPDATE operation for a quail field
+pub async fn capture_quail_update(
+    conn: &Connection,
+    quail_id: &str,
+    field: &str,
+    value: serde_json::Value,
+) -> Result<(), AppError> {
+    record(conn, "quail", quail_id, vec![set(field, value)]).await
+}
+
+/// Captures CREATE operation for a new photo
+pub async fn capture_photo_create(
+    conn: &Connection,
+    photo_id: &str,
+    quail_id: Option<&str>,
+    event_id: Option<&str>,
+    relative_path: &str,
+    relative_thumb: Option<&str>,
+) -> Result<(), AppError> {
+    let mut changes = vec![set("relative_path", relative_path)];
+    if let Some(qid) = quail_id {
+        changes.push(set("quail_id", qid));
+    }
+    if let Some(eid) = event_id {
+        changes.push(set("event_id", eid));
+    }
+    if let Some(thumb) = relative_thumb {
+        changes.push(set("relative_thumb", thumb));
+    }
+
+    record(conn, "photo", photo_id, changes).await
+}
+
+/// Captures UPDATE operation for a photo field (caption, tags, position, trashed_at, checksum)
+pub async fn capture_photo_update(
+    conn: &Connection,
+    photo_id: &str,
+    field: &str,
+    value: serde_json::Value,
+) -> Result<(), AppError> {
+    record(conn, "photo", photo_id, vec![set(field, value)]).await
+}
+
+/// Captures the move of a photo to another quail/event
+///
+/// quail_id, event_id and the (cleared) position are sent together with
+/// ascending clocks so receivers apply all three.
+pub async fn capture_photo_move(
+    conn: &Connection,
+    photo_id: &str,
+    quail_id: Option<&str>,
+    event_id: Option<&str>,
+) -> Result<(), AppError> {
+    let changes = vec![
+        set("quail_id", quail_id),
+        set("event_id", event_id),
+        set("position", serde_json::Value::Null),
+    ];
+
+    record(conn, "photo", photo_id, changes).await
+}
+
+/// Captures DELETE operation for a photo
+pub async fn capture_photo_delete(conn: &Connection, photo_id: &str) -> Result<(), AppError> {
+    record(conn, "photo", photo_id, vec![crdt_service::CrdtOp::Delete]).await
+}
@@ -0,0 +1,281 @@
+//! Abstammung: Eltern, Nachkommen und Stammbaum einer Wachtel
+//!
+//! Mutter und Vater stehen als UUID in der Wachtel (`mother_id`,
+//! `father_id`) und werden wie ihre übrigen Felder synchronisiert. Ohne
+//! Fremdschlüssel kann ein Elternteil gelöscht oder per Sync noch nicht
+//! angekommen sein; solche Verweise gelten hier als unbekannt.
+//!
+//! Für Züchter zählt vor allem, Inzucht zu vermeiden: [`common_ancestors`]
+//! findet gemeinsame Vorfahren zweier Tiere, z.B. eines geplanten Paares.
+
+use crate::error::AppError;
+use crate::models::{Gender, Quail};
+use crate::services::repository;
+use rusqlite::Connection;
+use std::collections::{HashMap, VecDeque};
+use uuid::Uuid;
+
+/// Generationen im Stammbaum der Detailansicht (Tier, Eltern, Großeltern)
+pub const TREE_GENERATIONS: usize = 3;
+
+/// So weit zurück werden Vorfahren verfolgt; begrenzt auch Zyklen, die
+/// durch gleichzeitige Änderungen auf zwei Geräten entstehen könnten
+const MAX_ANCESTOR_DEPTH: usize = 32;
+
+/// Tier mit seinen bekannten Vorfahren
+#[derive(Debug, Clone, PartialEq)]
+pub struct PedigreeNode {
+    pub quail: Quail,
+    pub mother: Option<Box<PedigreeNode>>,
+    pub father: Option<Box<PedigreeNode>>,
+}
+
+/// Nicht gelöschte Wachtel, `None` für unbekannte Verweise
+fn find_quail(conn: &Connection, uuid: &Uuid) -> Result<Option<Quail>, AppError> {
+    repository::find_first(conn, "uuid = ?1", [uuid.to_string()], "uuid")
+}
+
+/// Stammbaum über `generations` Generationen (1 = nur das Tier selbst)
+pub fn family_tree(
+    conn: &Connection,
+    uuid: &Uuid,
+    generations: usize,
+) -> Result<PedigreeNode, AppError> {
+    let quail = find_quail(conn, uuid)?
+        .ok_or_else(|| AppError::NotFound("Wachtel nicht gefunden".to_string()))?;
+    build_node(conn, quail, generations)
+}
+
+fn build_node(
+    conn: &Connection,
+    quail: Quail,
+    generations: usize,
+) -> Result<PedigreeNode, AppError> {
+    let parent = |id: Option<Uuid>| -> Result<Option<Box<PedigreeNode>>, AppError> {
+        if generations <= 1 {
+            return Ok(None);
+        }
+        let Some(id) = id else {
+            return Ok(None);
+        };
+        Ok(match find_quail(conn, &id)? {
+            Some(parent) => Some(Box::new(build_node(conn, parent, generations - 1)?)),
+            None => None,
+        })
+    };
+    Ok(PedigreeNode {
+        mother: parent(quail.mother_id)?,
+        father: parent(quail.father_id)?,
+        quail,
+    })
+}
+
+/// Nachkommen (Mutter oder Vater ist `uuid`), nach Namen sortiert
+pub fn offspring(conn: &Connection, uuid: &Uuid) -> Result<Vec<Quail>, AppError> {
+    repository::list(
+        conn,
+        "mother_id = ?1 OR father_id = ?1",
+        [uuid.to_string()],
+        "name",
+    )
+}
+
+/// Vorfahren mit ihrem Abstand in Generationen (Eltern = 1)
+fn ancestors(conn: &Connection, uuid: &Uuid) -> Result<HashMap<Uuid, usize>, AppError> {
+    let mut found = HashMap::new();
+    let mut queue = VecDeque::from([(*uuid, 0)]);
+    while let Some((id, depth)) = queue.pop_front() {
+        if depth >= MAX_ANCESTOR_DEPTH {
+            continue;
+        }
+        let Some(quail) = find_quail(conn, &id)? else {
+            continue;
+        };
+        for parent in quail.parents() {
+            if parent != *uuid && !found.contains_key(&parent) {
+                found.insert(parent, depth + 1);
+                queue.push_back((parent, depth + 1));
+            }
+        }
+    }
+    Ok(found)
+}
+
+/// Gemeinsame Vorfahren zweier Tiere, nächste zuerst; ist eines ein
+/// Vorfahr des anderen, steht es selbst in der Liste. Leer = nicht verwandt
+/// (soweit die Abstammung bekannt ist).
+pub fn common_ancestors(conn: &Connection, a: &Uuid, b: &Uuid) -> Result<Vec<Quail>, AppError> {
+    if a == b {
+        return Ok(Vec::new());
+    }
+    let mut of_a = ancestors(conn, a)?;
+    of_a.insert(*a, 0);
+    let mut of_b = ancestors(conn, b)?;
+    of_b.insert(*b, 0);
+
+    let mut shared: Vec<(usize, Quail)> = Vec::new();
+    for (id, depth_a) in &of_a {
+        let Some(depth_b) = of_b.get(id) else {
+            continue;
+        };
+        if let Some(quail) = find_quail(conn, id)? {
+            shared.push((depth_a + depth_b, quail));
+        }
+    }
+    shared.sort_by(|(x, qx), (y, qy)| x.cmp(y).then_with(|| qx.name.cmp(&qy.name)));
+    Ok(shared.into_iter().map(|(_, quail)| quail).collect())
+}
+
+/// Prüft neu gesetzte Eltern vor dem Speichern: Sie müssen existieren, die
+/// Mutter darf kein Hahn, der Vater keine Henne sein, und kein Tier darf
+/// von seinen eigenen Nachkommen abstammen. Unveränderte Verweise bleiben
+/// ungeprüft, damit z.B. ein später gelöschter Elternteil das Bearbeiten
+/// nicht blockiert.
+pub fn check_parents(conn: &Connection, quail: &Quail) -> Result<(), AppError> {
+    let stored = repository::get::<Quail>(conn, &quail.uuid)?;
+    let checks = [
+        (
+            quail.mother_id,
+            stored.as_ref().and_then(|q| q.mother_id),
+            Gender::Male,
+            "Mutter",
+        ),
+        (
+            quail.father_id,
+            stored.as_ref().and_then(|q| q.father_id),
+            Gender::Female,
+            "Vater",
+        ),
+    ];
+    for (parent_id, stored_id, excluded_gender, role) in checks {
+        let Some(parent_id) = parent_id else {
+            continue;
+        };
+        if Some(parent_id) == stored_id {
+            continue;
+        }
+        let parent = find_quail(conn, &parent_id)?
+            .ok_or_else(|| AppError::Validation(format!("{} nicht gefunden", role)))?;
+        if parent.gender == excluded_gender {
+            return Err(AppError::Validation(format!(
+                "{} als {} hat das falsche Geschlecht",
+                parent.name, role
+            )));
+        }
+        if ancestors(conn, &parent_id)?.contains_key(&quail.uuid) {
+            return Err(AppError::Validation(format!(
+                "{} stammt von {} ab und kann kein Elternteil sein",
+                parent.name, quail.name
+            )));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::schema;
+
+    fn setup() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        schema::init_schema(&conn).unwrap();
+        conn
+    }
+
+    async fn add(
+        conn: &Connection,
+        name: &str,
+        gender: Gender,
+        mother: Option<&Quail>,
+        father: Option<&Quail>,
+    ) -> Quail {
+        let mut quail = Quail::new(name.to_string());
+        quail.gender = gender;
+        quail.mother_id = mother.map(|q| q.uuid);
+        quail.father_id = father.map(|q| q.uuid);
+        crate::services::create_profile(conn, &quail).await.unwrap();
+        quail
+    }
+
+    #[tokio::test]
+    async fn test_family_tree_and_offspring() {
+        let conn = setup();
+        let oma = add(&conn, "Oma", Gender::Female, None, None).await;
+        let mutter = add(&conn, "Mutter", Gender::Female, Some(&oma), None).await;
+        let vater = add(&conn, "Vater", Gender::Male, None, None).await;
+        let kueken = add(&conn, "Küken", Gender::Unknown, Some(&mutter), Some(&vater)).await;
+        let bruder = add(&conn, "Bruder", Gender::Male, Some(&mutter), Some(&vater)).await;
+
+        let tree = family_tree(&conn, &kueken.uuid, TREE_GENERATIONS).unwrap();
+        let mother = tree.mother.as_ref().unwrap();
+        assert_eq!(mother.quail.name, "Mutter");
+        assert_eq!(mother.mother.as_ref().unwrap().quail.name, "Oma");
+        assert_eq!(tree.father.as_ref().unwrap().quail.name, "Vater");
+        // Begrenzt auf zwei Generationen: keine Großeltern
+        let short = family_tree(&conn, &kueken.uuid, 2).unwrap();
+        assert!(short.mother.unwrap().mother.is_none());
+
+        let names: Vec<String> = offspring(&conn, &mutter.uuid)
+            .unwrap()
+            .into_iter()
+            .map(|q| q.name)
+            .collect();
+        assert_eq!(names, ["Bruder", "Küken"]);
+
+        // Geschwister: gemeinsame Eltern zuerst, dann die Großmutter
+        let shared: Vec<String> = common_ancestors(&conn, &kueken.uuid, &bruder.uuid)
+            .unwrap()
+            .into_iter()
+            .map(|q| q.name)
+            .collect();
+        assert_eq!(shared, ["Mutter", "Vater", "Oma"]);
+        // Elternteil und Nachkomme
+        let shared = common_ancestors(&conn, &vater.uuid, &kueken.uuid).unwrap();
+        assert_eq!(shared[0].uuid, vater.uuid);
+        assert!(common_ancestors(&conn, &oma.uuid, &vater.uuid)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_check_parents() {
+        let conn = setup();
+        let hahn = add(&conn, "Hahn", Gender::Male, None, None).await;
+        let henne = add(&conn, "Henne", Gender::Female, None, None).await;
+        let kueken = add(&conn, "Küken", Gender::Female, Some(&henne), Some(&hahn)).await;
+
+        // Hahn als Mutter
+        let mut wrong = Quail::new("Falsch".to_string());
+        wrong.mother_id = Some(hahn.uuid);
+        assert!(matches!(
+            crate::services::create_profile(&conn, &wrong).await,
+            Err(AppError::Validation(_))
+        ));
+
+        // Unbekannter Elternteil
+        wrong.mother_id = Some(Uuid::new_v4());
+        assert!(check_parents(&conn, &wrong).is_err());
+
+        // Eigener Nachkomme als Mutter
+        let mut henne_edit = henne.clone();
+        henne_edit.mother_id = Some(kueken.uuid);
+        assert!(matches!(
+            crate::services::update_profile(&conn, &henne_edit).await,
+            Err(AppError::Validation(_))
+        ));
+
+        // Unveränderte Eltern bleiben gültig, auch wenn sich das Geschlecht
+        // des Elternteils später ändert
+        let mut hahn_edit = hahn.clone();
+        hahn_edit.gender = Gender::Female;
+        crate::services::update_profile(&conn, &hahn_edit)
+            .await
+            .unwrap();
+        let mut kueken_edit = kueken.clone();
+        kueken_edit.name = "Kükchen".to_string();
+        crate::services::update_profile(&conn, &kueken_edit)
+            .await
+            .unwrap();
+    }
+}
@@ -0,0 +1,85 @@
+//! Auslieferung von Fotos an die WebView über einen Asset-Handler
+//!
+//! Statt Dateien komplett zu lesen und als Base64-Data-URL in `src` zu
+//! stecken, verweisen die Bilder auf [`photo_asset_url`]. Die App registriert
+//! unter [`PHOTO_ASSET_HANDLER`] einen Handler, der die Anfrage mit
+//! [`parse_photo_asset_path`] zerlegt und die Bytes aus
+//! [`read_photo_asset`] zurückgibt.
+
+use crate::error::AppError;
+use crate::image_processing::InputFormat;
+use crate::models::photo::PhotoSize;
+use crate::services::photo_service;
+use rusqlite::Connection;
+use uuid::Uuid;
+
+/// Name des Asset-Handlers (erstes Pfadsegment der URLs)
+pub const PHOTO_ASSET_HANDLER: &str = "photos";
+
+/// URL, unter der die WebView das Foto in der gewünschten Größe lädt
+pub fn photo_asset_url(photo_uuid: &Uuid, size: PhotoSize) -> String {
+    format!("/{}/{}/{}", PHOTO_ASSET_HANDLER, photo_uuid, size.name())
+}
+
+/// Zerlegt den Pfad einer Anfrage (`…/<uuid>/<größe>`)
+pub fn parse_photo_asset_path(path: &str) -> Option<(Uuid, PhotoSize)> {
+    let mut segments = path.trim_end_matches('/').rsplit('/');
+    let size = PhotoSize::from_name(segments.next()?)?;
+    let photo_uuid = Uuid::parse_str(segments.next()?).ok()?;
+    Some((photo_uuid, size))
+}
+
+/// Liest die lokale Datei samt MIME-Typ; `None`, wenn das Foto unbekannt
+/// oder (noch) nicht heruntergeladen ist
+///
+/// Startet keine Downloads – dafür ist
+/// [`photo_service::get_photo_url_with_download`] zuständig.
+pub fn read_photo_asset(
+    conn: &Connection,
+    photo_uuid: &Uuid,
+    size: PhotoSize,
+) -> Result<Option<(Vec<u8>, &'static str)>, AppError> {
+    let file = match photo_service::resolve_photo_file(conn, photo_uuid, size) {
+        Ok(file) => file,
+        Err(AppError::NotFound(_)) => return Ok(None),
+        Err(e) => return Err(e),
+    };
+    match std::fs::read(photo_service::get_absolute_photo_path(&file.path)) {
+        Ok(bytes) => {
+            let mime = InputFormat::detect(&bytes)
+                .mime()
+                .unwrap_or("application/octet-stream");
+            Ok(Some((bytes, mime)))
+        }
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_asset_url_roundtrip() {
+        let photo_uuid = Uuid::new_v4();
+        for size in [PhotoSize::TINY, PhotoSize::LARGE, PhotoSize::Original] {
+            let url = photo_asset_url(&photo_uuid, size);
+            assert!(url.starts_with("/photos/"));
+            assert_eq!(parse_photo_asset_path(&url), Some((photo_uuid, size)));
+        }
+        // Die WebView kann Schema und Host voranstellen
+        assert_eq!(
+            parse_photo_asset_path(&format!(
+                "http://dioxus.index.html/photos/{}/small",
+                photo_uuid
+            )),
+            Some((photo_uuid, PhotoSize::SMALL))
+        );
+        assert_eq!(parse_photo_asset_path("/photos/not-a-uuid/small"), None);
+        assert_eq!(
+            parse_photo_asset_path(&format!("/photos/{}/huge", photo_uuid)),
+            None
+        );
+    }
+}
@@ -0,0 +1,174 @@
+//! Prüfsummen für die Integrität des Foto-Syncs
+//!
+//! Nach dem Upload wird das Original vom Server zurückgelesen und mit der
+//! SHA-256-Summe der lokalen Datei verglichen; erst dann gilt es als
+//! `synced`. Die Summe wird als CRDT-Feld `checksum` an die anderen Geräte
+//! verteilt, die heruntergeladene Originale damit prüfen. Abweichungen
+//! (z.B. abgeschnittene Uploads) bekommen den Status [`CORRUPT_STATUS`]
+//! und werden erneut hoch- bzw. heruntergeladen.
+
+use crate::error::AppError;
+use rusqlite::{params, Connection, OptionalExtension};
+
+/// `sync_status` eines Fotos, dessen Prüfsumme nicht gestimmt hat
+pub const CORRUPT_STATUS: &str = "corrupt";
+
+/// SHA-256 (hex, klein) des Inhalts – auch für den `OC-Checksum`-Header
+pub fn content_checksum(data: &[u8]) -> String {
+    use sha2::{Digest, Sha256};
+    Sha256::digest(data)
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+/// Gespeicherte Prüfsumme, falls `path` das Original des Fotos ist
+/// (Thumbnails und ältere Uploads haben keine)
+pub fn expected_checksum(
+    conn: &Connection,
+    photo_uuid: &str,
+    path: &str,
+) -> Result<Option<String>, AppError> {
+    Ok(conn
+        .query_row(
+            "SELECT checksum FROM photos WHERE uuid = ?1 AND COALESCE(relative_path, path) = ?2",
+            params![photo_uuid, path],
+            |row| row.get::<_, Option<String>>(0),
+        )
+        .optional()?
+        .flatten())
+}
+
+/// `true`, wenn `data` zur gespeicherten Prüfsumme passt (oder keine bekannt ist)
+pub fn matches_expected(
+    conn: &Connection,
+    photo_uuid: &str,
+    path: &str,
+    data: &[u8],
+) -> Result<bool, AppError> {
+    Ok(expected_checksum(conn, photo_uuid, path)?
+        .is_none_or(|expected| expected == content_checksum(data)))
+}
+
+/// Markiert ein Foto als beschädigt übertragen; es wird erneut versucht
+pub fn mark_corrupt(conn: &Connection, photo_uuid: &str, error: &str) -> Result<(), AppError> {
+    log::warn!("Foto {}: {}", photo_uuid, error);
+    conn.execute(
+        "UPDATE photos SET sync_status = ?1, sync_error = ?2, retry_count = COALESCE(retry_count, 0) + 1
+         WHERE uuid = ?3",
+        params![CORRUPT_STATUS, error, photo_uuid],
+    )?;
+    Ok(())
+}
+
+/// Speichert die Prüfsumme des Originals und verteilt sie per CRDT
+/// (nur wenn sie sich geändert hat)
+pub async fn record_checksum(
+    conn: &Connection,
+    photo_uuid: &str,
+    checksum: &str,
+) -> Result<(), AppError> {
+    let changed = conn.execute(
+        "UPDATE photos SET checksum = ?1 WHERE uuid = ?2 AND checksum IS NOT ?1",
+        params![checksum, photo_uuid],
+    )?;
+    if changed > 0 {
+        crate::services::operation_capture::capture_photo_update(
+            conn,
+            photo_uuid,
+            "checksum",
+            serde_json::json!(checksum),
+        )
+        .await?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::schema;
+
+    #[tokio::test]
+    async fn test_checksum_matching_and_corrupt_status() {
+        let conn = Connection::open_in_memory().unwrap();
+        schema::init_schema(&conn).unwrap();
+        conn.execute(
+            "INSERT INTO photos (uuid, path, relative_path, thumbnail_small_path, sync_status)
+             VALUES ('p1', '', 'p1.jpg', 'p1_small.webp', 'local_only')",
+            [],
+        )
+        .unwrap();
+
+        // Ohne gespeicherte Summe (ältere Uploads) wird nichts abgelehnt
+        assert!(matches_expected(&conn, "p1", "p1.jpg", b"truncated").unwrap());
+
+        record_checksum(&conn, "p1", &content_checksum(b"full photo"))
+            .await
+            .unwrap();
+        assert!(matches_expected(&conn, "p1", "p1.jpg", b"full photo").unwrap());
+        assert!(!matches_expected(&conn, "p1", "p1.jpg", b"full ph").unwrap());
+        // Thumbnails werden nicht geprüft
+        assert!(matches_expected(&conn, "p1", "p1_small.webp", b"x").unwrap());
+
+        mark_corrupt(&conn, "p1", "Prüfsumme stimmt nicht").unwrap();
+        let (status, retries): (String, i32) = conn
+            .query_row(
+                "SELECT sync_status, retry_count FROM photos WHERE uuid = 'p1'",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .unwrap();
+        assert_eq!(status, CORRUPT_STATUS);
+        assert_eq!(retries, 1);
+    }
+
+    #[test]
+    fn test_v16_migration_allows_corrupt_status() {
+        let file =
+            std::env::temp_dir().join(format!("checksum-migration-{}.db", uuid::Uuid::new_v4()));
+        {
+            let conn = Connection::open(&file).unwrap();
+            schema::init_schema(&conn).unwrap();
+            // Zustand vor v16 herstellen: alte CHECK-Liste
+            conn.execute_batch(
+                "PRAGMA writable_schema = ON;
+                 UPDATE sqlite_master SET sql = replace(sql, ', ''corrupt''', '') WHERE name = 'photos';
+                 PRAGMA writable_schema = OFF;
+                 DELETE FROM schema_version WHERE version >= 16;",
+            )
+            .unwrap();
+        }
+        let conn = Connection::open(&file).unwrap();
+        conn.execute_batch(
+            "INSERT INTO quails (uuid, name) VALUES ('q1', 'Erna');
+             INSERT INTO photos (uuid, path, relative_path, quail_id) VALUES ('p1', '', 'p1.jpg', 'q1');
+             UPDATE quails SET profile_photo = 'p1' WHERE uuid = 'q1';",
+        )
+        .unwrap();
+        assert!(mark_corrupt(&conn, "p1", "x").is_err());
+
+        schema::init_schema(&conn).unwrap();
+        mark_corrupt(&conn, "p1", "x").unwrap();
+        let profile_photo: Option<String> = conn
+            .query_row(
+                "SELECT profile_photo FROM quails WHERE uuid = 'q1'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(profile_photo.as_deref(), Some("p1"));
+        // Trigger und Indizes werden mit der Tabelle neu angelegt
+        let trigger: i32 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM sqlite_master WHERE name = 'update_photos_timestamp'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(trigger, 1);
+
+        drop(conn);
+        let _ = std::fs::remove_file(&file);
+    }
+}
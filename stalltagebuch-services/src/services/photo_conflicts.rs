@@ -0,0 +1,362 @@
+//! Erkennung von Fotodateien, die außerhalb der App geändert wurden
+//!
+//! Wird ein Original über die Nextcloud-Weboberfläche ersetzt oder gelöscht,
+//! liefen lokale und entfernte Kopie bisher unbemerkt auseinander.
+//! [`detect_remote_conflicts`] vergleicht die ETags in `sync/photos/` mit
+//! den im `sync_manifest` gemerkten Werten und markiert abweichende Fotos
+//! mit einem [`RemoteConflict`]. Geänderte ETags, deren Inhalt zur bekannten
+//! Prüfsumme passt (z.B. erneuter Upload eines anderen Geräts), gelten nicht
+//! als Konflikt. Aufgelöst wird mit [`keep_local`] oder [`adopt_remote`].
+
+use crate::error::AppError;
+use crate::models::photo::RemoteConflict;
+use crate::services::download_service::ensure_manifest_table;
+use crate::services::photo_storage::{LocalPhotoStorage, PhotoStorage, RemotePhotoStorage};
+use crate::services::sync_backend::SyncBackend;
+use crate::services::{photo_checksum, photo_service, sync_paths};
+use rusqlite::{params, Connection, OptionalExtension};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// Schlüssel des Originals im `sync_manifest`
+fn manifest_key(photo_uuid: &str) -> String {
+    format!("{}/{}.jpg", sync_paths::PHOTOS_DIR, photo_uuid)
+}
+
+fn remember_etag(conn: &Connection, photo_uuid: &str, etag: &str) -> Result<(), AppError> {
+    conn.execute(
+        "INSERT OR REPLACE INTO sync_manifest (path, etag) VALUES (?1, ?2)",
+        params![manifest_key(photo_uuid), etag],
+    )?;
+    Ok(())
+}
+
+/// Vergisst den gemerkten ETag nach einem eigenen Upload; die nächste
+/// Prüfung übernimmt den neuen Wert
+pub(crate) fn forget_etag(conn: &Connection, photo_uuid: &str) -> Result<(), AppError> {
+    ensure_manifest_table(conn)?;
+    conn.execute(
+        "DELETE FROM sync_manifest WHERE path = ?1",
+        params![manifest_key(photo_uuid)],
+    )?;
+    Ok(())
+}
+
+fn set_conflict(
+    conn: &Connection,
+    photo_uuid: &str,
+    conflict: Option<RemoteConflict>,
+) -> Result<(), AppError> {
+    let error = conflict.map(|c| match c {
+        RemoteConflict::Modified => "Remote-Kopie wurde außerhalb der App geändert",
+        RemoteConflict::Deleted => "Remote-Kopie wurde außerhalb der App gelöscht",
+    });
+    conn.execute(
+        "UPDATE photos SET remote_conflict = ?1, sync_error = ?2 WHERE uuid = ?3",
+        params![conflict.map(RemoteConflict::as_str), error, photo_uuid],
+    )?;
+    Ok(())
+}
+
+/// Markiert ein Foto, dessen Upload auf eine abweichende Datei eines anderen
+/// Geräts gestoßen ist; die Datei auf dem Server bleibt unverändert
+pub(crate) fn flag_upload_conflict(conn: &Connection, photo_uuid: &str) -> Result<(), AppError> {
+    conn.execute(
+        "UPDATE photos SET sync_status = 'synced' WHERE uuid = ?1",
+        params![photo_uuid],
+    )?;
+    set_conflict(conn, photo_uuid, Some(RemoteConflict::Modified))
+}
+
+/// Vergleicht die Originale auf dem Server mit dem Manifest und markiert
+/// geänderte oder gelöschte Fotos. Gibt die Anzahl neuer Konflikte zurück.
+pub async fn detect_remote_conflicts(
+    conn: &Connection,
+    remote: &RemotePhotoStorage<impl SyncBackend>,
+) -> Result<usize, AppError> {
+    ensure_manifest_table(conn)?;
+    // Ein nicht lesbares Verzeichnis darf nicht als "alles gelöscht" gelten
+    let remote_files: HashMap<String, Option<String>> =
+        remote.list_with_etags().await?.into_iter().collect();
+
+    let photos: Vec<(String, Option<String>, Option<String>)> = {
+        let mut stmt = conn.prepare(
+            "SELECT p.uuid, p.checksum, m.etag
+             FROM photos p
+             LEFT JOIN sync_manifest m ON m.path = ?1 || '/' || p.uuid || '.jpg'
+             WHERE p.deleted = 0 AND p.trashed_at IS NULL
+               AND p.sync_status = 'synced' AND p.remote_conflict IS NULL",
+        )?;
+        let rows = stmt.query_map([sync_paths::PHOTOS_DIR], |row| {
+            Ok((row.get(0)?, row.get(1)?, row.get(2)?))
+        })?;
+        rows.collect::<Result<_, _>>()?
+    };
+
+    let mut conflicts = 0;
+    for (photo_uuid, checksum, known_etag) in photos {
+        let conflict = match (remote_files.get(&format!("{}.jpg", photo_uuid)), known_etag) {
+            // Erstmals gesehen: Ausgangswert merken
+            (Some(Some(etag)), None) => {
+                remember_etag(conn, &photo_uuid, etag)?;
+                None
+            }
+            (Some(Some(etag)), Some(known)) if *etag == known => None,
+            (Some(Some(etag)), Some(_)) => {
+                let name = format!("{}.jpg", photo_uuid);
+                let unchanged = match (checksum, remote.read(&name).await) {
+                    (Some(checksum), Ok(data)) => {
+                        photo_checksum::content_checksum(&data) == checksum
+                    }
+                    _ => false,
+                };
+                if unchanged {
+                    remember_etag(conn, &photo_uuid, etag)?;
+                    None
+                } else {
+                    Some(RemoteConflict::Modified)
+                }
+            }
+            (None, Some(_)) => Some(RemoteConflict::Deleted),
+            // Server ohne ETags oder nie gesehen: nichts zu vergleichen
+            (Some(None), _) | (None, None) => None,
+        };
+        if let Some(conflict) = conflict {
+            log::warn!("Foto {}: Remote-Konflikt {:?}", photo_uuid, conflict);
+            set_conflict(conn, &photo_uuid, Some(conflict))?;
+            conflicts += 1;
+        }
+    }
+    Ok(conflicts)
+}
+
+/// Konflikt eines Fotos, falls vorhanden
+pub fn remote_conflict(
+    conn: &Connection,
+    photo_uuid: &Uuid,
+) -> Result<Option<RemoteConflict>, AppError> {
+    let value: Option<String> = conn
+        .query_row(
+            "SELECT remote_conflict FROM photos WHERE uuid = ?1",
+            params![photo_uuid.to_string()],
+            |row| row.get(0),
+        )
+        .optional()?
+        .flatten();
+    Ok(value.as_deref().and_then(RemoteConflict::parse))
+}
+
+/// Alle Fotos mit offenem Konflikt
+pub fn list_remote_conflicts(conn: &Connection) -> Result<Vec<(Uuid, RemoteConflict)>, AppError> {
+    let mut stmt = conn.prepare(
+        "SELECT uuid, remote_conflict FROM photos
+         WHERE deleted = 0 AND remote_conflict IS NOT NULL",
+    )?;
+    let rows = stmt.query_map([], |row| {
+        Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+    })?;
+    let mut conflicts = Vec::new();
+    for row in rows {
+        let (photo_uuid, value) = row?;
+        if let (Ok(photo_uuid), Some(conflict)) =
+            (Uuid::parse_str(&photo_uuid), RemoteConflict::parse(&value))
+        {
+            conflicts.push((photo_uuid, conflict));
+        }
+    }
+    Ok(conflicts)
+}
+
+/// Relativer Pfad des Originals und der synchronisierten Thumbnails
+fn photo_files(conn: &Connection, photo_uuid: &str) -> Result<(String, Vec<String>), AppError> {
+    let (original, small, medium): (String, Option<String>, Option<String>) = conn
+        .query_row(
+            "SELECT COALESCE(relative_path, path), thumbnail_small_path, thumbnail_medium_path
+             FROM photos WHERE uuid = ?1",
+            params![photo_uuid],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        )
+        .optional()?
+        .ok_or_else(|| AppError::NotFound("Foto nicht gefunden".into()))?;
+    Ok((original, small.into_iter().chain(medium).collect()))
+}
+
+/// Löst den Konflikt zugunsten der lokalen Kopie: Original und Thumbnails
+/// werden erneut hochgeladen
+pub async fn keep_local(
+    conn: &Connection,
+    local: &LocalPhotoStorage,
+    remote: &RemotePhotoStorage<impl SyncBackend>,
+    photo_uuid: &Uuid,
+) -> Result<(), AppError> {
+    let photo_uuid = photo_uuid.to_string();
+    let (original, thumbnails) = photo_files(conn, &photo_uuid)?;
+    let data = local.read(&original).await.map_err(|_| {
+        AppError::Validation("Das Original ist auf diesem Gerät nicht vorhanden".into())
+    })?;
+
+    let checksum = photo_checksum::content_checksum(&data);
+    remote.write(&format!("{}.jpg", photo_uuid), data).await?;
+    for thumbnail in thumbnails {
+        if let Ok(data) = local.read(&thumbnail).await {
+            if let Err(e) = remote.write(&thumbnail, data).await {
+                log::warn!("Thumbnail {} nicht hochgeladen: {}", thumbnail, e);
+            }
+        }
+    }
+
+    forget_etag(conn, &photo_uuid)?;
+    set_conflict(conn, &photo_uuid, None)?;
+    photo_checksum::record_checksum(conn, &photo_uuid, &checksum).await
+}
+
+/// Löst den Konflikt zugunsten des Servers: eine gelöschte Datei verschiebt
+/// das Foto in den Papierkorb, eine geänderte ersetzt das lokale Original
+/// (Thumbnails werden neu erzeugt und hochgeladen)
+pub async fn adopt_remote(
+    conn: &Connection,
+    local: &LocalPhotoStorage,
+    remote: &RemotePhotoStorage<impl SyncBackend>,
+    photo_uuid: &Uuid,
+) -> Result<(), AppError> {
+    let uuid = photo_uuid.to_string();
+    match remote_conflict(conn, photo_uuid)? {
+        None => Ok(()),
+        Some(RemoteConflict::Deleted) => {
+            forget_etag(conn, &uuid)?;
+            set_conflict(conn, &uuid, None)?;
+            photo_service::delete_photo(conn, photo_uuid).await
+        }
+        Some(RemoteConflict::Modified) => {
+            let (original, thumbnails) = photo_files(conn, &uuid)?;
+            let data = remote.read(&format!("{}.jpg", uuid)).await?;
+            let checksum = photo_checksum::content_checksum(&data);
+            local.write(&original, data).await?;
+
+            let original_path = local.path_of(&original);
+            photo_service::create_thumbnails(&original_path.to_string_lossy(), &uuid, None)?;
+            for thumbnail in thumbnails {
+                if let Ok(data) = local.read(&thumbnail).await {
+                    if let Err(e) = remote.write(&thumbnail, data).await {
+                        log::warn!("Thumbnail {} nicht hochgeladen: {}", thumbnail, e);
+                    }
+                }
+            }
+
+            // Der aktuelle ETag wird bei der nächsten Prüfung übernommen
+            forget_etag(conn, &uuid)?;
+            set_conflict(conn, &uuid, None)?;
+            photo_checksum::record_checksum(conn, &uuid, &checksum).await
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::schema;
+    use crate::services::sync_backend::{ConfiguredBackend, MemoryBackend};
+
+    /// Drei synchronisierte Fotos; Inhalt des Originals ist die UUID selbst
+    fn setup() -> (Connection, LocalPhotoStorage, Vec<Uuid>) {
+        let conn = Connection::open_in_memory().unwrap();
+        schema::init_schema(&conn).unwrap();
+        let uuids: Vec<Uuid> = (0..3).map(|_| Uuid::new_v4()).collect();
+        for uuid in &uuids {
+            let uuid = uuid.to_string();
+            conn.execute(
+                "INSERT INTO photos (uuid, path, relative_path, thumbnail_small_path, sync_status, checksum)
+                 VALUES (?1, '', ?1 || '.jpg', ?1 || '_small.webp', 'synced', ?2)",
+                params![uuid, photo_checksum::content_checksum(uuid.as_bytes())],
+            )
+            .unwrap();
+        }
+        let local = LocalPhotoStorage::new(
+            std::env::temp_dir().join(format!("photo-conflicts-{}", Uuid::new_v4())),
+        );
+        (conn, local, uuids)
+    }
+
+    async fn remote_with_dir() -> RemotePhotoStorage {
+        let remote = RemotePhotoStorage::new(ConfiguredBackend::Memory(MemoryBackend::new()), "/");
+        // Das Verzeichnis bleibt wie auf der Nextcloud bestehen, auch wenn es leer ist
+        remote.ensure_dir().await;
+        remote
+    }
+
+    async fn upload(storage: &impl PhotoStorage, uuid: &Uuid) {
+        let name = format!("{}.jpg", uuid);
+        storage
+            .write(&name, uuid.to_string().into_bytes())
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_out_of_band_changes_are_flagged() {
+        let (conn, _local, uuids) = setup();
+        let remote = remote_with_dir().await;
+        for uuid in &uuids {
+            upload(&remote, uuid).await;
+        }
+        // Erster Durchlauf merkt nur die ETags
+        assert_eq!(detect_remote_conflicts(&conn, &remote).await.unwrap(), 0);
+
+        // Anderes Gerät lädt denselben Inhalt erneut hoch: kein Konflikt
+        upload(&remote, &uuids[0]).await;
+        // Bearbeitet bzw. gelöscht in der Weboberfläche
+        let edited = format!("{}.jpg", uuids[1]);
+        remote.write(&edited, b"edited".to_vec()).await.unwrap();
+        remote.delete(&format!("{}.jpg", uuids[2])).await.unwrap();
+
+        assert_eq!(detect_remote_conflicts(&conn, &remote).await.unwrap(), 2);
+        assert_eq!(remote_conflict(&conn, &uuids[0]).unwrap(), None);
+        assert_eq!(
+            remote_conflict(&conn, &uuids[1]).unwrap(),
+            Some(RemoteConflict::Modified)
+        );
+        assert_eq!(
+            remote_conflict(&conn, &uuids[2]).unwrap(),
+            Some(RemoteConflict::Deleted)
+        );
+        assert_eq!(list_remote_conflicts(&conn).unwrap().len(), 2);
+        // Bereits markierte Fotos werden nicht erneut gezählt
+        assert_eq!(detect_remote_conflicts(&conn, &remote).await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_resolving_deleted_conflicts() {
+        let (conn, local, uuids) = setup();
+        let remote = remote_with_dir().await;
+        for uuid in &uuids[..2] {
+            upload(&remote, uuid).await;
+        }
+        upload(&local, &uuids[0]).await;
+        detect_remote_conflicts(&conn, &remote).await.unwrap();
+        for uuid in &uuids[..2] {
+            remote.delete(&format!("{}.jpg", uuid)).await.unwrap();
+        }
+        assert_eq!(detect_remote_conflicts(&conn, &remote).await.unwrap(), 2);
+
+        keep_local(&conn, &local, &remote, &uuids[0]).await.unwrap();
+        assert!(remote.exists(&format!("{}.jpg", uuids[0])).await);
+        assert_eq!(remote_conflict(&conn, &uuids[0]).unwrap(), None);
+        // Nach dem erneuten Upload kein neuer Konflikt
+        assert_eq!(detect_remote_conflicts(&conn, &remote).await.unwrap(), 0);
+
+        adopt_remote(&conn, &local, &remote, &uuids[1])
+            .await
+            .unwrap();
+        assert_eq!(remote_conflict(&conn, &uuids[1]).unwrap(), None);
+        let trashed: Option<i64> = conn
+            .query_row(
+                "SELECT trashed_at FROM photos WHERE uuid = ?1",
+                params![uuids[1].to_string()],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert!(trashed.is_some());
+
+        let _ = std::fs::remove_dir_all(local.path_of(""));
+    }
+}
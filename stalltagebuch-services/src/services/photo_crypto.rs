@@ -0,0 +1,458 @@
+//! Clientseitige Verschlüsselung der Fotos (Feature `encryption`)
+//!
+//! Ist lokal ein Schlüssel hinterlegt, verschlüsselt
+//! [`RemotePhotoStorage`](crate::services::photo_storage::RemotePhotoStorage)
+//! jedes Foto und Thumbnail vor dem Upload mit ChaCha20-Poly1305 und
+//! entschlüsselt es beim Download wieder. Der Server sieht nur
+//! `MAGIC | Nonce | Chiffretext`. Unverschlüsselte Dateien (vor dem
+//! Aktivieren hochgeladen) werden unverändert gelesen.
+//!
+//! Derselbe Schlüssel verschlüsselt auch Op-Dateien und Snapshots
+//! (siehe [`op_crypto`](crate::services::op_crypto)).
+//!
+//! Der Schlüssel wird nie synchronisiert; andere Geräte bekommen ihn über
+//! Export/Import in den Einstellungen oder leiten ihn aus derselben
+//! Passphrase ab. Salt und Iterationen dafür liegen je Sync-Ordner in
+//! [`KEY_PARAMS`](crate::services::sync_paths::KEY_PARAMS), zusammen mit
+//! einer Schlüsselprüfung, die eine falsche Passphrase erkennt.
+
+use crate::error::AppError;
+use crate::services::sync_backend::SyncBackend;
+use crate::services::{op_compaction, sync_paths};
+use base64::Engine;
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, CHACHA20_POLY1305, NONCE_LEN};
+use ring::rand::{SecureRandom, SystemRandom};
+use ring::{hkdf, hmac, pbkdf2};
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+
+/// Kennung am Anfang jeder verschlüsselten Datei (mit Formatversion)
+const MAGIC: &[u8] = b"STBENC1\0";
+/// Präfix des exportierten Schlüssels
+const EXPORT_PREFIX: &str = "stb-photo-key-1:";
+const KEY_LEN: usize = 32;
+const SALT_LEN: usize = 16;
+const PASSPHRASE_ITERATIONS: u32 = 600_000;
+/// Obergrenze für die Iterationen aus der (vom Server gelieferten) Parameterdatei
+const MAX_PASSPHRASE_ITERATIONS: u32 = 10 * PASSPHRASE_ITERATIONS;
+/// Wird mit dem abgeleiteten Schlüssel verschlüsselt und in der
+/// Parameterdatei abgelegt
+const KEY_CHECK: &[u8] = b"stalltagebuch-key-check";
+/// HKDF-Info für den vom Schlüssel abgeleiteten MAC-Schlüssel
+const MAC_INFO: &[u8] = b"stalltagebuch-sync-mac";
+pub const MIN_PASSPHRASE_LEN: usize = 12;
+
+/// Lokal gespeicherter Schlüssel für Fotodateien
+#[derive(Clone, PartialEq, Eq)]
+pub struct PhotoKey([u8; KEY_LEN]);
+
+impl std::fmt::Debug for PhotoKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("PhotoKey(..)")
+    }
+}
+
+impl PhotoKey {
+    pub fn generate() -> Result<Self, AppError> {
+        let mut bytes = [0u8; KEY_LEN];
+        SystemRandom::new()
+            .fill(&mut bytes)
+            .map_err(|_| AppError::Other("Zufallsgenerator nicht verfügbar".to_string()))?;
+        Ok(Self(bytes))
+    }
+
+    /// Leitet den Schlüssel mit den Parametern des Sync-Ordners ab
+    ///
+    /// Passt die Passphrase nicht zur Schlüsselprüfung, gibt es einen
+    /// Validierungsfehler statt eines falschen Schlüssels.
+    pub fn from_passphrase(passphrase: &str, params: &KeyParams) -> Result<Self, AppError> {
+        let invalid = || AppError::Validation("Ungültige Schlüsselparameter".to_string());
+        if !(1..=MAX_PASSPHRASE_ITERATIONS).contains(&params.iterations) {
+            return Err(invalid());
+        }
+        let engine = base64::engine::general_purpose::STANDARD;
+        let salt = engine.decode(&params.salt).map_err(|_| invalid())?;
+        if salt.len() < SALT_LEN {
+            return Err(invalid());
+        }
+        let check = engine.decode(&params.check).map_err(|_| invalid())?;
+
+        let key = Self::derive(passphrase, &salt, params.iterations)?;
+        match key.decrypt(check) {
+            Ok(plain) if plain == KEY_CHECK => Ok(key),
+            _ => Err(AppError::Validation(
+                "Passphrase passt nicht zum Schlüssel des Sync-Ordners".to_string(),
+            )),
+        }
+    }
+
+    fn derive(passphrase: &str, salt: &[u8], iterations: u32) -> Result<Self, AppError> {
+        let passphrase = passphrase.trim();
+        if passphrase.chars().count() < MIN_PASSPHRASE_LEN {
+            return Err(AppError::Validation(format!(
+                "Passphrase muss mindestens {} Zeichen lang sein",
+                MIN_PASSPHRASE_LEN
+            )));
+        }
+        let mut bytes = [0u8; KEY_LEN];
+        pbkdf2::derive(
+            pbkdf2::PBKDF2_HMAC_SHA256,
+            std::num::NonZeroU32::new(iterations).expect("iterations are non-zero"),
+            salt,
+            passphrase.as_bytes(),
+            &mut bytes,
+        );
+        Ok(Self(bytes))
+    }
+
+    /// Text zum Übertragen auf ein anderes Gerät
+    pub fn export(&self) -> String {
+        format!(
+            "{}{}",
+            EXPORT_PREFIX,
+            base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(self.0)
+        )
+    }
+
+    pub fn import(text: &str) -> Result<Self, AppError> {
+        let invalid = || AppError::Validation("Ungültiger Fotoschlüssel".to_string());
+        let encoded = text
+            .trim()
+            .strip_prefix(EXPORT_PREFIX)
+            .ok_or_else(invalid)?;
+        let bytes = base64::engine::general_purpose::URL_SAFE_NO_PAD
+            .decode(encoded)
+            .map_err(|_| invalid())?;
+        Ok(Self(bytes.try_into().map_err(|_| invalid())?))
+    }
+
+    fn aead_key(&self) -> LessSafeKey {
+        LessSafeKey::new(
+            UnboundKey::new(&CHACHA20_POLY1305, &self.0).expect("key has the AEAD key length"),
+        )
+    }
+
+    /// Eigener Schlüssel für HMAC, damit AEAD und MAC nicht denselben nutzen
+    fn mac_key(&self) -> hmac::Key {
+        hkdf::Salt::new(hkdf::HKDF_SHA256, &[])
+            .extract(&self.0)
+            .expand(&[MAC_INFO], hmac::HMAC_SHA256)
+            .expect("HMAC key length is a valid HKDF output length")
+            .into()
+    }
+
+    /// HMAC-SHA256 über `data` (Base64)
+    pub fn mac(&self, data: &[u8]) -> String {
+        base64::engine::general_purpose::URL_SAFE_NO_PAD
+            .encode(hmac::sign(&self.mac_key(), data).as_ref())
+    }
+
+    /// Prüft einen MAC von [`mac`](Self::mac) in konstanter Zeit
+    pub fn verify_mac(&self, data: &[u8], mac: &str) -> bool {
+        base64::engine::general_purpose::URL_SAFE_NO_PAD
+            .decode(mac)
+            .is_ok_and(|tag| hmac::verify(&self.mac_key(), data, &tag).is_ok())
+    }
+
+    pub fn encrypt(&self, plain: &[u8]) -> Result<Vec<u8>, AppError> {
+        let mut nonce = [0u8; NONCE_LEN];
+        SystemRandom::new()
+            .fill(&mut nonce)
+            .map_err(|_| AppError::Other("Zufallsgenerator nicht verfügbar".to_string()))?;
+
+        let mut in_out = plain.to_vec();
+        self.aead_key()
+            .seal_in_place_append_tag(
+                Nonce::assume_unique_for_key(nonce),
+                Aad::from(MAGIC),
+                &mut in_out,
+            )
+            .map_err(|_| AppError::Other("Verschlüsselung fehlgeschlagen".to_string()))?;
+
+        let mut data = Vec::with_capacity(MAGIC.len() + NONCE_LEN + in_out.len());
+        data.extend_from_slice(MAGIC);
+        data.extend_from_slice(&nonce);
+        data.extend_from_slice(&in_out);
+        Ok(data)
+    }
+
+    /// Entschlüsselt `data`; unverschlüsselte Dateien werden unverändert zurückgegeben
+    pub fn decrypt(&self, data: Vec<u8>) -> Result<Vec<u8>, AppError> {
+        if !is_encrypted(&data) {
+            return Ok(data);
+        }
+        let (nonce, ciphertext) = data[MAGIC.len()..].split_at(NONCE_LEN);
+        let nonce = Nonce::try_assume_unique_for_key(nonce)
+            .map_err(|_| AppError::Other("Ungültige verschlüsselte Datei".to_string()))?;
+        let mut in_out = ciphertext.to_vec();
+        let plain_len = self
+            .aead_key()
+            .open_in_place(nonce, Aad::from(MAGIC), &mut in_out)
+            .map_err(|_| {
+                AppError::Other(
+                    "Datei konnte nicht entschlüsselt werden (falscher Schlüssel?)".to_string(),
+                )
+            })?
+            .len();
+        in_out.truncate(plain_len);
+        Ok(in_out)
+    }
+}
+
+/// Parameter der Passphrase-Ableitung für einen Sync-Ordner
+///
+/// Liegt unverschlüsselt auf dem Server, damit jedes Gerät aus derselben
+/// Passphrase denselben Schlüssel ableitet. Der Salt ist je Ordner zufällig.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeyParams {
+    /// Zufälliger Salt (Base64)
+    pub salt: String,
+    pub iterations: u32,
+    /// [`KEY_CHECK`], mit dem abgeleiteten Schlüssel verschlüsselt (Base64)
+    pub check: String,
+}
+
+impl KeyParams {
+    /// Neue Parameter mit zufälligem Salt, zusammen mit dem abgeleiteten Schlüssel
+    pub fn create(passphrase: &str) -> Result<(Self, PhotoKey), AppError> {
+        Self::create_with(passphrase, PASSPHRASE_ITERATIONS)
+    }
+
+    fn create_with(passphrase: &str, iterations: u32) -> Result<(Self, PhotoKey), AppError> {
+        let mut salt = [0u8; SALT_LEN];
+        SystemRandom::new()
+            .fill(&mut salt)
+            .map_err(|_| AppError::Other("Zufallsgenerator nicht verfügbar".to_string()))?;
+        let key = PhotoKey::derive(passphrase, &salt, iterations)?;
+        let engine = base64::engine::general_purpose::STANDARD;
+        let params = Self {
+            salt: engine.encode(salt),
+            iterations,
+            check: engine.encode(key.encrypt(KEY_CHECK)?),
+        };
+        Ok((params, key))
+    }
+}
+
+/// Liest die Schlüsselparameter des Sync-Ordners; `None` wenn (noch) keine
+/// lesbar sind
+async fn read_key_params(
+    client: &impl SyncBackend,
+    path: &str,
+) -> Result<Option<KeyParams>, AppError> {
+    match client.get(path).await {
+        Ok(data) => serde_json::from_slice(&data)
+            .map(Some)
+            .map_err(|e| AppError::Validation(format!("Ungültige Schlüsselparameter: {}", e))),
+        Err(e @ AppError::PermissionDenied(_)) => Err(e),
+        Err(_) => Ok(None),
+    }
+}
+
+/// Leitet den Schlüssel für den Sync-Ordner unter `base` aus der Passphrase ab
+///
+/// Das erste Gerät legt die Parameter mit zufälligem Salt an. Legen zwei
+/// Geräte gleichzeitig an, gewinnt das erste `put_if_absent` und das andere
+/// leitet mit dessen Parametern ab.
+pub async fn key_for_sync_folder(
+    client: &impl SyncBackend,
+    base: &str,
+    passphrase: &str,
+) -> Result<PhotoKey, AppError> {
+    let path = format!("{}/{}", base, sync_paths::KEY_PARAMS);
+    for _ in 0..2 {
+        if let Some(params) = read_key_params(client, &path).await? {
+            return PhotoKey::from_passphrase(passphrase, &params);
+        }
+        let (params, key) = KeyParams::create(passphrase)?;
+        op_compaction::ensure_dirs(client, base, sync_paths::CONTROL_DIR).await;
+        let data = serde_json::to_vec(&params)
+            .map_err(|e| AppError::Other(format!("JSON serialize failed: {}", e)))?;
+        if client.put_if_absent(&path, data).await? {
+            log::info!("Schlüsselparameter für den Sync-Ordner angelegt");
+            return Ok(key);
+        }
+    }
+    Err(AppError::Other(
+        "Schlüsselparameter des Sync-Ordners nicht lesbar".to_string(),
+    ))
+}
+
+pub fn is_encrypted(data: &[u8]) -> bool {
+    data.len() >= MAGIC.len() + NONCE_LEN && data.starts_with(MAGIC)
+}
+
+/// Der lokal gespeicherte Schlüssel (`None` = Verschlüsselung aus)
+pub fn load_key(conn: &Connection) -> Result<Option<PhotoKey>, AppError> {
+    let exported: Option<String> = conn
+        .query_row("SELECT key FROM photo_encryption WHERE id = 1", [], |row| {
+            row.get(0)
+        })
+        .optional()?;
+    exported.map(|text| PhotoKey::import(&text)).transpose()
+}
+
+/// Nimmt der Download trotz Schlüssel unverschlüsselte Sync-Dateien an?
+pub fn accepts_plaintext(conn: &Connection) -> Result<bool, AppError> {
+    let accept: Option<bool> = conn
+        .query_row(
+            "SELECT accept_plaintext FROM photo_encryption WHERE id = 1",
+            [],
+            |row| row.get(0),
+        )
+        .optional()?;
+    Ok(accept.unwrap_or(false))
+}
+
+/// Erlaubt unverschlüsselte Sync-Dateien, die vor dem Aktivieren hochgeladen
+/// wurden (Migration); gilt bis zum nächsten neuen Schlüssel
+pub fn set_accept_plaintext(conn: &Connection, accept: bool) -> Result<(), AppError> {
+    conn.execute(
+        "UPDATE photo_encryption SET accept_plaintext = ?1 WHERE id = 1",
+        params![accept],
+    )?;
+    Ok(())
+}
+
+/// Speichert (bzw. ersetzt) den Schlüssel; ab dann werden Uploads verschlüsselt
+pub fn store_key(conn: &Connection, key: &PhotoKey) -> Result<(), AppError> {
+    conn.execute(
+        "INSERT OR REPLACE INTO photo_encryption (id, key) VALUES (1, ?1)",
+        params![key.export()],
+    )?;
+    Ok(())
+}
+
+/// Schaltet die Verschlüsselung für neue Uploads ab; bereits verschlüsselte
+/// Fotos sind ohne den exportierten Schlüssel nicht mehr lesbar
+pub fn remove_key(conn: &Connection) -> Result<(), AppError> {
+    conn.execute("DELETE FROM photo_encryption", [])?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::schema;
+    use crate::services::sync_backend::MemoryBackend;
+
+    #[test]
+    fn test_encrypt_roundtrip_and_plain_passthrough() {
+        let key = PhotoKey::generate().unwrap();
+        let plain = b"\xff\xd8\xff jpeg bytes".to_vec();
+
+        let encrypted = key.encrypt(&plain).unwrap();
+        assert!(is_encrypted(&encrypted));
+        assert!(!encrypted.windows(4).any(|w| w == b"jpeg"));
+        assert_eq!(key.decrypt(encrypted.clone()).unwrap(), plain);
+
+        // Alte, unverschlüsselte Uploads bleiben lesbar
+        assert_eq!(key.decrypt(plain.clone()).unwrap(), plain);
+
+        let other = PhotoKey::generate().unwrap();
+        assert!(other.decrypt(encrypted).is_err());
+    }
+
+    #[test]
+    fn test_passphrase_derivation_is_deterministic() {
+        let salt = b"0123456789abcdef";
+        let a = PhotoKey::derive("correct horse battery", salt, 1_000).unwrap();
+        let b = PhotoKey::derive("  correct horse battery ", salt, 1_000).unwrap();
+        let c = PhotoKey::derive("correct horse battery!", salt, 1_000).unwrap();
+        let d = PhotoKey::derive("correct horse battery", b"fedcba9876543210", 1_000).unwrap();
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+        assert_ne!(a, d);
+        assert!(matches!(
+            PhotoKey::derive("too short", salt, 1_000),
+            Err(AppError::Validation(_))
+        ));
+    }
+
+    #[test]
+    fn test_key_params_check_passphrase() {
+        let (params, key) = KeyParams::create_with("correct horse battery", 1_000).unwrap();
+        assert_eq!(
+            PhotoKey::from_passphrase("correct horse battery", &params).unwrap(),
+            key
+        );
+        assert!(matches!(
+            PhotoKey::from_passphrase("wrong horse battery", &params),
+            Err(AppError::Validation(_))
+        ));
+
+        // Jeder Ordner bekommt einen eigenen Salt
+        let (other, other_key) = KeyParams::create_with("correct horse battery", 1_000).unwrap();
+        assert_ne!(other.salt, params.salt);
+        assert_ne!(other_key, key);
+
+        // Der Server darf keine beliebig teure Ableitung erzwingen
+        let expensive = KeyParams {
+            iterations: MAX_PASSPHRASE_ITERATIONS + 1,
+            ..params
+        };
+        assert!(matches!(
+            PhotoKey::from_passphrase("correct horse battery", &expensive),
+            Err(AppError::Validation(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_devices_share_the_folder_params() {
+        let backend = MemoryBackend::new();
+        let params = KeyParams::create_with("correct horse battery", 1_000)
+            .unwrap()
+            .0;
+        let path = format!("Stalltagebuch/{}", sync_paths::KEY_PARAMS);
+        backend
+            .put(&path, serde_json::to_vec(&params).unwrap())
+            .await
+            .unwrap();
+
+        let a = key_for_sync_folder(&backend, "Stalltagebuch", "correct horse battery")
+            .await
+            .unwrap();
+        let b = key_for_sync_folder(&backend, "Stalltagebuch", " correct horse battery")
+            .await
+            .unwrap();
+        assert_eq!(a, b);
+        assert!(
+            key_for_sync_folder(&backend, "Stalltagebuch", "wrong horse battery")
+                .await
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_accept_plaintext_flag() {
+        let conn = Connection::open_in_memory().unwrap();
+        schema::init_schema(&conn).unwrap();
+        assert!(!accepts_plaintext(&conn).unwrap());
+
+        store_key(&conn, &PhotoKey::generate().unwrap()).unwrap();
+        set_accept_plaintext(&conn, true).unwrap();
+        assert!(accepts_plaintext(&conn).unwrap());
+
+        // Ein neuer Schlüssel setzt die Ausnahme zurück
+        store_key(&conn, &PhotoKey::generate().unwrap()).unwrap();
+        assert!(!accepts_plaintext(&conn).unwrap());
+    }
+
+    #[test]
+    fn test_key_export_import_and_storage() {
+        let conn = Connection::open_in_memory().unwrap();
+        schema::init_schema(&conn).unwrap();
+        assert_eq!(load_key(&conn).unwrap(), None);
+
+        let key = PhotoKey::generate().unwrap();
+        let exported = key.export();
+        assert_eq!(PhotoKey::import(&format!(" {}\n", exported)).unwrap(), key);
+        assert!(PhotoKey::import("stb-photo-key-1:abc").is_err());
+        assert!(PhotoKey::import(&exported[EXPORT_PREFIX.len()..]).is_err());
+
+        store_key(&conn, &key).unwrap();
+        assert_eq!(load_key(&conn).unwrap(), Some(key));
+        remove_key(&conn).unwrap();
+        assert_eq!(load_key(&conn).unwrap(), None);
+    }
+}
@@ -0,0 +1,358 @@
+//! Zuschneiden und Drehen von Fotos
+//!
+//! Originale werden nie überschrieben: [`edit_photo`] legt aus dem
+//! bearbeiteten Bild ein neues Foto in derselben Sammlung an, übernimmt
+//! Beschriftung, Tags, Position und Profilbild und verschiebt das alte Foto
+//! in den Papierkorb. Andere Geräte sehen dadurch nur ein neues Foto und ein
+//! gelöschtes (beides normale CRDT-Operationen), und die Bearbeitung lässt
+//! sich über den Papierkorb rückgängig machen.
+
+use crate::error::AppError;
+use crate::image_processing;
+use crate::models::photo::PhotoCollection;
+use crate::services::photo_service;
+use image::DynamicImage;
+use rusqlite::{params, Connection, OptionalExtension};
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+
+/// Ausschnitt als Anteile (0.0–1.0) des aufrecht gedrehten Bildes
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CropRect {
+    pub left: f32,
+    pub top: f32,
+    pub width: f32,
+    pub height: f32,
+}
+
+/// Bearbeitungsschritte; zuerst wird zugeschnitten, dann gedreht
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct EditOps {
+    /// Drehung im Uhrzeigersinn in Grad (Vielfaches von 90)
+    pub rotate: u16,
+    pub crop: Option<CropRect>,
+}
+
+impl EditOps {
+    fn validate(&self) -> Result<(), AppError> {
+        if !self.rotate.is_multiple_of(90) {
+            return Err(AppError::Validation(
+                "Drehung nur in 90°-Schritten möglich".to_string(),
+            ));
+        }
+        if let Some(crop) = self.crop {
+            let inside = |start: f32, len: f32| {
+                (0.0..1.0).contains(&start) && len > 0.0 && start + len <= 1.0 + f32::EPSILON
+            };
+            if !inside(crop.left, crop.width) || !inside(crop.top, crop.height) {
+                return Err(AppError::Validation(
+                    "Ausschnitt liegt außerhalb des Bildes".to_string(),
+                ));
+            }
+        }
+        if self.rotate.is_multiple_of(360) && self.crop.is_none() {
+            return Err(AppError::Validation("Keine Änderung".to_string()));
+        }
+        Ok(())
+    }
+
+    /// Wendet die Schritte auf ein aufrecht gedrehtes Bild an
+    fn apply(&self, mut img: DynamicImage) -> DynamicImage {
+        if let Some(crop) = self.crop {
+            let (width, height) = (img.width() as f32, img.height() as f32);
+            let x = ((crop.left * width).round() as u32).min(img.width() - 1);
+            let y = ((crop.top * height).round() as u32).min(img.height() - 1);
+            let w = ((crop.width * width).round() as u32).clamp(1, img.width() - x);
+            let h = ((crop.height * height).round() as u32).clamp(1, img.height() - y);
+            img = img.crop_imm(x, y, w, h);
+        }
+        match self.rotate % 360 {
+            90 => img.rotate90(),
+            180 => img.rotate180(),
+            270 => img.rotate270(),
+            _ => img,
+        }
+    }
+}
+
+/// Schneidet ein Foto zu und/oder dreht es; gibt die UUID der neuen
+/// Fassung zurück
+///
+/// Benötigt das Original lokal; Fotos, die nur remote liegen, müssen erst
+/// heruntergeladen werden.
+pub async fn edit_photo(
+    conn: &Connection,
+    photo_uuid: &Uuid,
+    ops: EditOps,
+) -> Result<Uuid, AppError> {
+    let photo_dir = PathBuf::from(photo_service::get_absolute_photo_path(""));
+    edit_photo_in(conn, photo_dir, photo_uuid, ops).await
+}
+
+/// Felder des Ausgangsfotos, die die neue Fassung übernimmt
+struct SourcePhoto {
+    collection: PhotoCollection,
+    file_name: String,
+    orientation: Option<u16>,
+    caption: Option<String>,
+    tags: Vec<String>,
+    position: Option<i64>,
+}
+
+fn load_source(conn: &Connection, photo_uuid: &Uuid) -> Result<SourcePhoto, AppError> {
+    let row = conn
+        .query_row(
+            "SELECT quail_id, event_id, COALESCE(relative_path, path), exif_orientation, caption, position
+             FROM photos WHERE uuid = ?1 AND deleted = 0 AND trashed_at IS NULL",
+            params![photo_uuid.to_string()],
+            |row| {
+                Ok((
+                    row.get::<_, Option<String>>(0)?,
+                    row.get::<_, Option<String>>(1)?,
+                    row.get::<_, String>(2)?,
+                    row.get::<_, Option<u16>>(3)?,
+                    row.get::<_, Option<String>>(4)?,
+                    row.get::<_, Option<i64>>(5)?,
+                ))
+            },
+        )
+        .optional()?
+        .ok_or_else(|| AppError::NotFound("Foto nicht gefunden".into()))?;
+    let (quail_id, event_id, file_name, orientation, caption, position) = row;
+
+    let parse = |id: Option<String>| id.and_then(|id| Uuid::parse_str(&id).ok());
+    let collection = match (parse(event_id), parse(quail_id)) {
+        (Some(event_id), _) => PhotoCollection::Event(event_id),
+        (None, Some(quail_id)) => PhotoCollection::Quail(quail_id),
+        (None, None) => {
+            return Err(AppError::Validation(
+                "Foto gehört zu keiner Wachtel und keinem Ereignis".to_string(),
+            ))
+        }
+    };
+    Ok(SourcePhoto {
+        collection,
+        file_name,
+        orientation,
+        caption,
+        tags: photo_service::get_photo_tags(conn, photo_uuid)?,
+        position,
+    })
+}
+
+async fn edit_photo_in(
+    conn: &Connection,
+    photo_dir: PathBuf,
+    photo_uuid: &Uuid,
+    ops: EditOps,
+) -> Result<Uuid, AppError> {
+    ops.validate()?;
+    let source = load_source(conn, photo_uuid)?;
+    let original = photo_dir.join(&source.file_name);
+    if !original.exists() {
+        return Err(AppError::Validation(
+            "Original ist nicht lokal vorhanden".to_string(),
+        ));
+    }
+
+    let orientation = source.orientation;
+    let bytes = tokio::task::spawn_blocking(move || render(&original, orientation, ops))
+        .await
+        .map_err(|e| AppError::Other(format!("Task join error: {}", e)))??;
+
+    let new_uuid =
+        photo_service::add_photo_bytes(conn, source.collection, photo_dir, bytes).await?;
+
+    // Aufnahmedaten stehen nicht mehr in den EXIF-Daten des neuen JPEGs
+    conn.execute(
+        "UPDATE photos SET (taken_at, camera_model, gps_latitude, gps_longitude) =
+            (SELECT taken_at, camera_model, gps_latitude, gps_longitude FROM photos WHERE uuid = ?2)
+         WHERE uuid = ?1",
+        params![new_uuid.to_string(), photo_uuid.to_string()],
+    )?;
+    if source.caption.is_some() {
+        photo_service::set_photo_caption(conn, &new_uuid, source.caption.as_deref()).await?;
+    }
+    if !source.tags.is_empty() {
+        photo_service::set_photo_tags(conn, &new_uuid, &source.tags).await?;
+    }
+    if let Some(position) = source.position {
+        conn.execute(
+            "UPDATE photos SET position = ?1 WHERE uuid = ?2",
+            params![position, new_uuid.to_string()],
+        )?;
+        crate::services::operation_capture::capture_photo_update(
+            conn,
+            &new_uuid.to_string(),
+            "position",
+            serde_json::json!(position),
+        )
+        .await?;
+    }
+    if let PhotoCollection::Quail(quail_id) = source.collection {
+        let profile_photo: Option<String> = conn
+            .query_row(
+                "SELECT profile_photo FROM quails WHERE uuid = ?1",
+                params![quail_id.to_string()],
+                |row| row.get(0),
+            )
+            .optional()?
+            .flatten();
+        if profile_photo == Some(photo_uuid.to_string()) {
+            photo_service::set_profile_photo(conn, &quail_id, &new_uuid).await?;
+        }
+    }
+
+    photo_service::delete_photo(conn, photo_uuid).await?;
+    log::info!("Foto {} bearbeitet, neue Fassung {}", photo_uuid, new_uuid);
+    Ok(new_uuid)
+}
+
+/// Dekodiert das Original, bearbeitet es und kodiert es als JPEG
+fn render(original: &Path, orientation: Option<u16>, ops: EditOps) -> Result<Vec<u8>, AppError> {
+    let img = image_processing::open_image(original)?;
+    let img = ops.apply(image_processing::apply_orientation(img, orientation));
+    let mut bytes = Vec::new();
+    image::codecs::jpeg::JpegEncoder::new_with_quality(&mut bytes, 90)
+        .encode_image(&img.to_rgb8())
+        .map_err(|e| AppError::ImageProcessing(format!("JPEG encode failed: {}", e)))?;
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::schema;
+    use crate::services::thumbnail_queue;
+
+    #[test]
+    fn test_invalid_ops_are_rejected() {
+        let crop = |left, top, width, height| {
+            Some(CropRect {
+                left,
+                top,
+                width,
+                height,
+            })
+        };
+        let invalid = [
+            EditOps::default(),
+            EditOps {
+                rotate: 360,
+                crop: None,
+            },
+            EditOps {
+                rotate: 45,
+                crop: None,
+            },
+            EditOps {
+                rotate: 0,
+                crop: crop(0.5, 0.0, 0.6, 1.0),
+            },
+            EditOps {
+                rotate: 0,
+                crop: crop(0.0, 0.0, 1.0, 0.0),
+            },
+        ];
+        for ops in invalid {
+            assert!(
+                matches!(ops.validate(), Err(AppError::Validation(_))),
+                "{:?}",
+                ops
+            );
+        }
+        assert!(EditOps {
+            rotate: 270,
+            crop: crop(0.0, 0.0, 1.0, 1.0)
+        }
+        .validate()
+        .is_ok());
+    }
+
+    #[test]
+    fn test_crop_then_rotate() {
+        let ops = EditOps {
+            rotate: 90,
+            crop: Some(CropRect {
+                left: 0.25,
+                top: 0.0,
+                width: 0.5,
+                height: 0.5,
+            }),
+        };
+        let img = ops.apply(DynamicImage::new_rgb8(200, 100));
+        assert_eq!((img.width(), img.height()), (50, 100));
+    }
+
+    #[tokio::test]
+    async fn test_edit_creates_new_version_and_trashes_old() {
+        let conn = Connection::open_in_memory().unwrap();
+        schema::init_schema(&conn).unwrap();
+        let dir = std::env::temp_dir().join(format!("edit-test-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let quail_id = Uuid::new_v4();
+        conn.execute(
+            "INSERT INTO quails (uuid, name) VALUES (?1, 'Erna')",
+            params![quail_id.to_string()],
+        )
+        .unwrap();
+        let mut jpeg = Vec::new();
+        image::codecs::jpeg::JpegEncoder::new(&mut jpeg)
+            .encode_image(&DynamicImage::new_rgb8(80, 40).to_rgb8())
+            .unwrap();
+        let old = photo_service::add_photo_bytes(
+            &conn,
+            PhotoCollection::Quail(quail_id),
+            dir.clone(),
+            jpeg,
+        )
+        .await
+        .unwrap();
+        photo_service::set_photo_caption(&conn, &old, Some("Im Auslauf"))
+            .await
+            .unwrap();
+        photo_service::set_photo_tags(&conn, &old, &["garten"])
+            .await
+            .unwrap();
+        photo_service::set_profile_photo(&conn, &quail_id, &old)
+            .await
+            .unwrap();
+
+        let ops = EditOps {
+            rotate: 90,
+            crop: Some(CropRect {
+                left: 0.0,
+                top: 0.0,
+                width: 0.5,
+                height: 1.0,
+            }),
+        };
+        let new = edit_photo_in(&conn, dir.clone(), &old, ops).await.unwrap();
+
+        let photos = photo_service::list_quail_photos(&conn, &quail_id).unwrap();
+        assert_eq!(photos.len(), 1);
+        assert_eq!(photos[0].uuid, new);
+        assert_eq!(photos[0].caption.as_deref(), Some("Im Auslauf"));
+        assert_eq!(photos[0].tags, vec!["garten".to_string()]);
+        assert_eq!(
+            photo_service::get_profile_photo(&conn, &quail_id)
+                .unwrap()
+                .map(|p| p.uuid),
+            Some(new)
+        );
+        assert!(photo_service::list_trashed_photos(&conn)
+            .unwrap()
+            .iter()
+            .any(|p| p.uuid == old));
+
+        let edited = image::open(dir.join(format!("{}.jpg", new))).unwrap();
+        assert_eq!((edited.width(), edited.height()), (40, 40));
+
+        // Das Original der alten Fassung bleibt für die Wiederherstellung liegen
+        assert!(dir.join(format!("{}.jpg", old)).exists());
+
+        thumbnail_queue::wait_idle().await;
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,202 @@
+//! Vorab-Download von Thumbnails für kommende Bildschirme
+//!
+//! Bisher startet jeder Detailbildschirm die Downloads erst beim Öffnen,
+//! die Bilder erscheinen dann nacheinander. [`prefetch_thumbnails`] lädt die
+//! fehlenden kleinen Thumbnails einer Liste von Fotos schon vorher in
+//! höchstens [`MAX_WORKERS`] Tasks; jeder Download belegt dabei einen der
+//! app-weiten Plätze der [`download_queue`]. Die Warteschlange ist auf
+//! [`MAX_QUEUED`] Einträge begrenzt; bei Überlauf fallen die ältesten weg,
+//! denn die zuletzt angefragten Fotos gehören zum aktuellen Bildschirm.
+
+use crate::error::AppError;
+use crate::services::{download_queue, photo_service};
+use rusqlite::{params, Connection, OptionalExtension};
+use std::collections::{HashSet, VecDeque};
+use std::sync::{Mutex, MutexGuard, OnceLock};
+use uuid::Uuid;
+
+/// Maximal gleichzeitig laufende Downloads
+pub const MAX_WORKERS: usize = 2;
+/// Maximale Länge der Warteschlange
+pub const MAX_QUEUED: usize = 64;
+
+/// Ein herunterzuladendes Thumbnail
+#[derive(Debug, Clone, PartialEq)]
+struct PrefetchJob {
+    photo_uuid: Uuid,
+    /// Relativer Pfad des kleinen Thumbnails
+    path: String,
+}
+
+#[derive(Debug, Default)]
+struct QueueState {
+    jobs: VecDeque<PrefetchJob>,
+    /// Wartende und gerade geladene Dateien
+    pending: HashSet<String>,
+    workers: usize,
+}
+
+impl QueueState {
+    /// Reiht einen Job ein; `false`, wenn die Datei schon ansteht
+    fn push(&mut self, job: PrefetchJob) -> bool {
+        if !self.pending.insert(job.path.clone()) {
+            return false;
+        }
+        if self.jobs.len() >= MAX_QUEUED {
+            if let Some(dropped) = self.jobs.pop_front() {
+                self.pending.remove(&dropped.path);
+            }
+        }
+        self.jobs.push_back(job);
+        true
+    }
+}
+
+fn state() -> MutexGuard<'static, QueueState> {
+    static STATE: OnceLock<Mutex<QueueState>> = OnceLock::new();
+    STATE
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+}
+
+/// Lädt die fehlenden kleinen Thumbnails der Fotos im Hintergrund
+///
+/// Fotos ohne Remote-Kopie (noch nicht hochgeladen) und bereits lokal
+/// vorhandene Thumbnails werden übersprungen. Gibt die Anzahl der neu
+/// eingereihten Downloads zurück.
+pub fn prefetch_thumbnails(conn: &Connection, photo_uuids: &[Uuid]) -> Result<usize, AppError> {
+    let jobs = missing_thumbnails(conn, photo_uuids, |path| {
+        std::path::Path::new(&photo_service::get_absolute_photo_path(path)).exists()
+    })?;
+
+    let mut state = state();
+    let mut queued = 0;
+    for job in jobs {
+        if state.push(job) {
+            queued += 1;
+        }
+    }
+    while state.workers < MAX_WORKERS && state.workers < state.jobs.len() {
+        state.workers += 1;
+        tokio::spawn(worker());
+    }
+    if queued > 0 {
+        log::debug!("{} Thumbnails zum Vorabladen eingereiht", queued);
+    }
+    Ok(queued)
+}
+
+/// Anzahl der wartenden oder laufenden Vorab-Downloads
+pub fn pending_prefetches() -> usize {
+    state().pending.len()
+}
+
+/// Thumbnails der Fotos, die remote liegen, aber lokal fehlen
+fn missing_thumbnails(
+    conn: &Connection,
+    photo_uuids: &[Uuid],
+    exists_locally: impl Fn(&str) -> bool,
+) -> Result<Vec<PrefetchJob>, AppError> {
+    let mut stmt = conn.prepare(
+        "SELECT thumbnail_small_path
+         FROM photos
+         WHERE uuid = ?1 AND deleted = 0 AND trashed_at IS NULL
+           AND sync_status IN ('synced', 'download_pending', 'download_failed')",
+    )?;
+    let mut jobs = Vec::new();
+    for photo_uuid in photo_uuids {
+        let path: Option<String> = stmt
+            .query_row(params![photo_uuid.to_string()], |row| row.get(0))
+            .optional()?
+            .flatten();
+        if let Some(path) = path.filter(|p| !p.is_empty() && !exists_locally(p)) {
+            jobs.push(PrefetchJob {
+                photo_uuid: *photo_uuid,
+                path,
+            });
+        }
+    }
+    Ok(jobs)
+}
+
+async fn worker() {
+    loop {
+        let job = {
+            let mut state = state();
+            match state.jobs.pop_front() {
+                Some(job) => job,
+                None => {
+                    state.workers -= 1;
+                    return;
+                }
+            }
+        };
+
+        let slot = download_queue::download_slot().await;
+        match photo_service::download_photo_from_remote(&job.photo_uuid, &job.path).await {
+            Ok(_) => log::debug!("Thumbnail vorab geladen: {}", job.path),
+            Err(e) => log::debug!("Vorabladen von {} fehlgeschlagen: {}", job.path, e),
+        }
+        drop(slot);
+        state().pending.remove(&job.path);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::schema;
+
+    fn job(n: usize) -> PrefetchJob {
+        PrefetchJob {
+            photo_uuid: Uuid::new_v4(),
+            path: format!("{}_small.webp", n),
+        }
+    }
+
+    #[test]
+    fn test_queue_is_bounded_and_deduplicated() {
+        let mut state = QueueState::default();
+        assert!(state.push(job(0)));
+        assert!(!state.push(job(0)));
+
+        for n in 1..MAX_QUEUED + 10 {
+            state.push(job(n));
+        }
+        assert_eq!(state.jobs.len(), MAX_QUEUED);
+        assert_eq!(state.pending.len(), MAX_QUEUED);
+        // Die ältesten Anfragen fallen weg
+        assert_eq!(state.jobs.front().unwrap().path, "10_small.webp");
+        assert!(state.push(job(0)));
+    }
+
+    #[test]
+    fn test_only_missing_remote_thumbnails_are_fetched() {
+        let conn = Connection::open_in_memory().unwrap();
+        schema::init_schema(&conn).unwrap();
+        let uuids: Vec<Uuid> = (0..4).map(|_| Uuid::new_v4()).collect();
+        for (uuid, status) in
+            uuids
+                .iter()
+                .zip(["synced", "local_only", "synced", "download_failed"])
+        {
+            conn.execute(
+                "INSERT INTO photos (uuid, path, relative_path, thumbnail_small_path, sync_status)
+                 VALUES (?1, '', ?2, ?3, ?4)",
+                params![
+                    uuid.to_string(),
+                    format!("{}.jpg", uuid),
+                    format!("{}_small.webp", uuid),
+                    status
+                ],
+            )
+            .unwrap();
+        }
+        let already_local = format!("{}_small.webp", uuids[2]);
+
+        let jobs = missing_thumbnails(&conn, &uuids, |path| path == already_local).unwrap();
+        let fetched: Vec<Uuid> = jobs.iter().map(|j| j.photo_uuid).collect();
+        assert_eq!(fetched, vec![uuids[0], uuids[3]]);
+    }
+}
@@ -0,0 +1,1736 @@
+use crate::error::AppError;
+use crate::image_processing;
+use crate::models::photo::{
+    PhotoCollection, PhotoMetadata, PhotoResult, PhotoSize, THUMBNAIL_TIERS,
+};
+use crate::models::Photo;
+use crate::services::download_queue::{self, DownloadJob, DownloadRequest};
+use crate::services::photo_storage::{LocalPhotoStorage, PhotoStorage, RemotePhotoStorage};
+use crate::services::thumbnail_queue::{self, ThumbnailJob};
+use crate::services::{photo_assets, photo_checksum};
+use image::{imageops::FilterType, ImageFormat};
+use rusqlite::{params, Connection, OptionalExtension};
+use std::io::Cursor;
+use uuid::Uuid;
+
+/// Returns the absolute path to a photo (for UI display)
+pub fn get_absolute_photo_path(relative_path: &str) -> String {
+    #[cfg(target_os = "android")]
+    {
+        // Photos are in /storage/emulated/0/Android/data/PACKAGE/files/photos/
+        format!(
+            "/storage/emulated/0/Android/data/de.teilgedanken.stalltagebuch/files/photos/{}",
+            relative_path
+        )
+    }
+
+    #[cfg(not(target_os = "android"))]
+    {
+        crate::database::location::current()
+            .photo_dir()
+            .join(relative_path)
+            .to_string_lossy()
+            .to_string()
+    }
+}
+
+/// Creates one WebP thumbnail per [`THUMBNAIL_TIERS`] entry from a JPEG
+/// image, rotated according to its EXIF orientation (runs in the
+/// [`thumbnail_queue`] workers)
+/// Returns the created filenames (in tier order) or error
+pub(crate) fn create_thumbnails(
+    original_path: &str,
+    uuid: &str,
+    orientation: Option<u16>,
+) -> Result<Vec<String>, AppError> {
+    log::debug!("Creating thumbnails for UUID: {}", uuid);
+
+    // Load original image
+    let img = image_processing::open_image(std::path::Path::new(original_path))?;
+    let img = image_processing::apply_orientation(img, orientation);
+
+    let parent_dir = std::path::Path::new(original_path)
+        .parent()
+        .ok_or_else(|| AppError::Other("Kein Elternverzeichnis gefunden".to_string()))?;
+
+    let mut filenames = Vec::with_capacity(THUMBNAIL_TIERS.len());
+    for tier in THUMBNAIL_TIERS {
+        let filename = tier.file_name(uuid);
+        let path = parent_dir.join(&filename);
+        let resized = img.resize(tier.max_px, tier.max_px, FilterType::Lanczos3);
+
+        let mut buffer = Cursor::new(Vec::new());
+        resized
+            .write_to(&mut buffer, ImageFormat::WebP)
+            .map_err(|e| {
+                AppError::Other(format!(
+                    "Fehler beim Schreiben des Thumbnails '{}': {}",
+                    tier.name, e
+                ))
+            })?;
+
+        std::fs::write(&path, buffer.into_inner()).map_err(|e| {
+            AppError::Other(format!(
+                "Fehler beim Speichern des Thumbnails '{}': {}",
+                tier.name, e
+            ))
+        })?;
+
+        log::debug!("Thumbnail '{}' created: {:?}", tier.name, path);
+        filenames.push(filename);
+    }
+
+    Ok(filenames)
+}
+
+/// Removes the thumbnails that only exist locally (not in the database)
+fn remove_local_only_thumbnails(uuid: &str) {
+    for tier in THUMBNAIL_TIERS.iter().filter(|t| !t.synced) {
+        let _ = std::fs::remove_file(get_absolute_photo_path(&tier.file_name(uuid)));
+    }
+}
+
+/// Absolute path of a thumbnail tier if it exists locally, otherwise the
+/// original (e.g. the `large` tier for the fullscreen viewer)
+pub fn local_photo_path(photo: &Photo, size: PhotoSize) -> String {
+    size.tier()
+        .map(|tier| get_absolute_photo_path(&tier.file_name(&photo.uuid.to_string())))
+        .filter(|path| std::path::Path::new(path).exists())
+        .unwrap_or_else(|| photo.path.clone())
+}
+
+/// Result of [`rename_photo_with_uuid`]: (filename, absolute path, EXIF)
+type ProcessedPhoto = (String, std::path::PathBuf, PhotoMetadata);
+
+/// Number of photos processed in parallel by [`import_quail_photos`]
+const IMPORT_WORKERS: usize = 3;
+
+/// Progress of a batch import, reported once per finished file
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportProgress {
+    /// Finished files so far (including this one)
+    pub done: usize,
+    pub total: usize,
+    /// Source path of the finished file
+    pub path: String,
+    /// UUID of the new photo or the error message
+    pub result: Result<Uuid, String>,
+}
+
+/// Renames a photo file with UUID and returns the new name and path
+/// together with the EXIF metadata of the original
+/// Uses spawn_blocking to avoid blocking the async runtime
+async fn rename_photo_with_uuid(original_path: &str) -> Result<ProcessedPhoto, AppError> {
+    let original_path = original_path.to_string();
+
+    tokio::task::spawn_blocking(move || {
+        log::debug!("=== rename_photo_with_uuid called ===");
+        log::debug!("Original path: {}", original_path);
+
+        let uuid = crate::models::new_entity_id().to_string();
+        let new_filename = format!("{}.jpg", uuid);
+
+        log::debug!("Generated UUID: {}", uuid);
+        log::debug!("New filename: {}", new_filename);
+
+        // File is already in the correct directory, simply rename it there
+        let old_path = std::path::Path::new(&original_path);
+
+        if let Some(parent_dir) = old_path.parent() {
+            let new_path = parent_dir.join(&new_filename);
+
+            log::debug!("Old path: {:?}", old_path);
+            log::debug!("New path: {:?}", new_path);
+            log::debug!("Checking if old_path exists: {}", old_path.exists());
+
+            if old_path.exists() {
+                let format = image_processing::InputFormat::of_file(old_path)?;
+                if format.needs_transcoding() {
+                    // HEIC/AVIF: save as JPEG, the decoder already applied the rotation
+                    log::debug!("Transcoding {:?} to JPEG...", format);
+                    let mut metadata = image_processing::read_exif(old_path);
+                    metadata.orientation = None;
+                    image_processing::transcode_to_jpeg(old_path, &new_path)?;
+                    if let Err(e) = std::fs::remove_file(old_path) {
+                        log::warn!("Could not remove original: {}", e);
+                    }
+                    return Ok((new_filename, new_path, metadata));
+                }
+
+                log::debug!("Path exists, copying...");
+                match std::fs::copy(old_path, &new_path) {
+                    Ok(_) => {
+                        log::debug!("Copy successful, removing original...");
+                        if let Err(e) = std::fs::remove_file(old_path) {
+                            log::warn!("Could not remove original: {}", e);
+                        } else {
+                            log::debug!("Original removed");
+                        }
+
+                        let metadata = image_processing::read_exif(&new_path);
+                        log::debug!("EXIF metadata: {:?}", metadata);
+
+                        log::debug!("=== rename_photo_with_uuid completed ===");
+                        return Ok((new_filename, new_path, metadata));
+                    }
+                    Err(e) => {
+                        log::error!("ERROR during copy: {}", e);
+                        return Err(AppError::Other(format!("Fehler beim Kopieren: {}", e)));
+                    }
+                }
+            } else {
+                log::error!("ERROR: Original path doesn't exist!");
+                return Err(AppError::Other(format!(
+                    "Originaldatei nicht gefunden: {}",
+                    original_path
+                )));
+            }
+        }
+
+        Err(AppError::Other(
+            "Kein Elternverzeichnis gefunden".to_string(),
+        ))
+    })
+    .await
+    .map_err(|e| AppError::Other(format!("Task join error: {}", e)))?
+}
+
+pub async fn add_quail_photo(
+    conn: &Connection,
+    quail_id: Uuid,
+    path: String,
+    _thumbnail_path: Option<String>,
+) -> Result<Uuid, AppError> {
+    log::debug!("=== add_quail_photo called ===");
+    log::debug!("Quail ID: {}, Path: {}", quail_id, path);
+
+    // Rename photo (in blocking thread); thumbnails follow in the background queue
+    let processed = rename_photo_with_uuid(&path).await?;
+    store_quail_photo(conn, quail_id, processed).await
+}
+
+/// Imports many photos for one quail at once
+///
+/// Files are copied by up to [`IMPORT_WORKERS`] blocking tasks in
+/// parallel; the database rows are written on the calling task as the
+/// files finish and the thumbnails are left to the [`thumbnail_queue`]. `on_progress` is called once per file (in completion
+/// order), a failing file does not abort the batch.
+pub async fn import_quail_photos<F>(
+    conn: &Connection,
+    quail_id: Uuid,
+    paths: Vec<String>,
+    mut on_progress: F,
+) -> Vec<ImportProgress>
+where
+    F: FnMut(&ImportProgress),
+{
+    let total = paths.len();
+    let mut pending = paths.into_iter();
+    let mut workers = tokio::task::JoinSet::new();
+    let mut finished = Vec::with_capacity(total);
+
+    loop {
+        while workers.len() < IMPORT_WORKERS {
+            let Some(path) = pending.next() else {
+                break;
+            };
+            workers.spawn(async move {
+                let processed = rename_photo_with_uuid(&path).await;
+                (path, processed)
+            });
+        }
+
+        let Some(joined) = workers.join_next().await else {
+            break;
+        };
+        let result = match joined {
+            Ok((path, Ok(processed))) => (
+                path,
+                store_quail_photo(conn, quail_id, processed)
+                    .await
+                    .map_err(|e| e.to_string()),
+            ),
+            Ok((path, Err(e))) => (path, Err(e.to_string())),
+            Err(e) => (String::new(), Err(format!("Task join error: {}", e))),
+        };
+        if let Err(e) = &result.1 {
+            log::warn!("Import of {} failed: {}", result.0, e);
+        }
+
+        let progress = ImportProgress {
+            done: finished.len() + 1,
+            total,
+            path: result.0,
+            result: result.1,
+        };
+        on_progress(&progress);
+        finished.push(progress);
+    }
+
+    finished
+}
+
+/// Inserts an already processed photo for a quail and captures the CRDT operation
+async fn store_quail_photo(
+    conn: &Connection,
+    quail_id: Uuid,
+    (new_path, absolute_path, metadata): ProcessedPhoto,
+) -> Result<Uuid, AppError> {
+    let uuid = Uuid::parse_str(new_path.trim_end_matches(".jpg"))
+        .map_err(|_| AppError::Other("Invalid UUID from filename".to_string()))?;
+    let (small_thumb, medium_thumb) = thumbnail_names(&uuid);
+    log::debug!("UUID extracted: {}", uuid);
+    log::debug!("Thumbnails: small={}, medium={}", small_thumb, medium_thumb);
+
+    conn.execute(
+        "INSERT INTO photos (uuid, quail_id, path, relative_path, thumbnail_small_path, thumbnail_medium_path, sync_status,
+                             exif_orientation, taken_at, camera_model, gps_latitude, gps_longitude)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, 'local_only', ?7, ?8, ?9, ?10, ?11)",
+        params![
+            uuid.to_string(),
+            quail_id.to_string(),
+            "",  // path is now empty, relative_path holds the filename
+            &new_path,
+            &small_thumb,
+            &medium_thumb,
+            metadata.orientation,
+            metadata.taken_at,
+            &metadata.camera_model,
+            metadata.gps_latitude,
+            metadata.gps_longitude,
+        ],
+    )?;
+
+    // Capture CRDT operation
+    crate::services::operation_capture::capture_photo_create(
+        conn,
+        &uuid.to_string(),
+        Some(&quail_id.to_string()),
+        None,
+        &new_path,
+        Some(&small_thumb),
+    )
+    .await?;
+
+    thumbnail_queue::enqueue(ThumbnailJob {
+        photo_uuid: uuid.to_string(),
+        original_path: absolute_path,
+        orientation: metadata.orientation,
+    });
+
+    Ok(uuid)
+}
+
+/// Thumbnail file names of a photo stored in the database (small, medium)
+fn thumbnail_names(uuid: &Uuid) -> (String, String) {
+    let uuid = uuid.to_string();
+    (
+        PhotoSize::SMALL
+            .tier()
+            .map(|t| t.file_name(&uuid))
+            .unwrap_or_default(),
+        PhotoSize::MEDIUM
+            .tier()
+            .map(|t| t.file_name(&uuid))
+            .unwrap_or_default(),
+    )
+}
+
+pub async fn add_event_photo(
+    conn: &Connection,
+    event_id: Uuid,
+    path: String,
+    _thumbnail_path: Option<String>,
+) -> Result<Uuid, AppError> {
+    // Rename photo (in blocking thread); thumbnails follow in the background queue
+    let (new_path, absolute_path, metadata) = rename_photo_with_uuid(&path).await?;
+    let uuid = Uuid::parse_str(new_path.trim_end_matches(".jpg"))
+        .map_err(|_| AppError::Other("Invalid UUID from filename".to_string()))?;
+    let (small_thumb, medium_thumb) = thumbnail_names(&uuid);
+
+    conn.execute(
+        "INSERT INTO photos (uuid, event_id, path, relative_path, thumbnail_small_path, thumbnail_medium_path, sync_status,
+                             exif_orientation, taken_at, camera_model, gps_latitude, gps_longitude)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, 'local_only', ?7, ?8, ?9, ?10, ?11)",
+        params![
+            uuid.to_string(),
+            event_id.to_string(),
+            "",  // path is now empty, relative_path holds the filename
+            &new_path,
+            &small_thumb,
+            &medium_thumb,
+            metadata.orientation,
+            metadata.taken_at,
+            &metadata.camera_model,
+            metadata.gps_latitude,
+            metadata.gps_longitude,
+        ],
+    )?;
+
+    // Capture CRDT operation
+    crate::services::operation_capture::capture_photo_create(
+        conn,
+        &uuid.to_string(),
+        None,
+        Some(&event_id.to_string()),
+        &new_path,
+        Some(&small_thumb),
+    )
+    .await?;
+
+    thumbnail_queue::enqueue(ThumbnailJob {
+        photo_uuid: uuid.to_string(),
+        original_path: absolute_path,
+        orientation: metadata.orientation,
+    });
+
+    Ok(uuid)
+}
+
+/// Adds a photo from raw image bytes (e.g. pasted from the clipboard) to a
+/// quail or event
+///
+/// The format is detected from the bytes; PNG/WebP are converted to JPEG,
+/// HEIC/AVIF go through the normal import transcoding.
+pub async fn add_photo_to_collection(
+    conn: &Connection,
+    collection: PhotoCollection,
+    bytes: Vec<u8>,
+) -> Result<Uuid, AppError> {
+    let photo_dir = std::path::PathBuf::from(get_absolute_photo_path(""));
+    add_photo_bytes(conn, collection, photo_dir, bytes).await
+}
+
+pub(crate) async fn add_photo_bytes(
+    conn: &Connection,
+    collection: PhotoCollection,
+    photo_dir: std::path::PathBuf,
+    bytes: Vec<u8>,
+) -> Result<Uuid, AppError> {
+    let path = tokio::task::spawn_blocking(move || write_import_file(&photo_dir, &bytes))
+        .await
+        .map_err(|e| AppError::Other(format!("Task join error: {}", e)))??;
+    let path = path.to_string_lossy().to_string();
+    match collection {
+        PhotoCollection::Quail(quail_id) => add_quail_photo(conn, quail_id, path, None).await,
+        PhotoCollection::Event(event_id) => add_event_photo(conn, event_id, path, None).await,
+    }
+}
+
+/// Writes image bytes to a temporary file in `photo_dir` that the import can
+/// move (formats the WebView cannot rely on are stored as JPEG)
+fn write_import_file(
+    photo_dir: &std::path::Path,
+    bytes: &[u8],
+) -> Result<std::path::PathBuf, AppError> {
+    let format = image_processing::InputFormat::detect(bytes);
+    let path = photo_dir.join(format!("paste-{}.tmp", Uuid::new_v4()));
+    match format {
+        image_processing::InputFormat::Jpeg
+        | image_processing::InputFormat::Heic
+        | image_processing::InputFormat::Avif => std::fs::write(&path, bytes)?,
+        image_processing::InputFormat::Png | image_processing::InputFormat::WebP => {
+            let img = image::load_from_memory(bytes).map_err(|e| {
+                AppError::ImageProcessing(format!("Fehler beim Laden des Bildes: {}", e))
+            })?;
+            let file = std::io::BufWriter::new(std::fs::File::create(&path)?);
+            image::codecs::jpeg::JpegEncoder::new_with_quality(file, 90)
+                .encode_image(&img.to_rgb8())
+                .map_err(|e| AppError::ImageProcessing(format!("JPEG encode failed: {}", e)))?;
+        }
+        image_processing::InputFormat::Gif | image_processing::InputFormat::Unknown => {
+            return Err(AppError::Validation(
+                "Kein unterstütztes Bildformat".to_string(),
+            ))
+        }
+    }
+    Ok(path)
+}
+
+/// Columns read by [`photo_from_row`]
+const PHOTO_COLUMNS: &str =
+    "uuid, quail_id, event_id, COALESCE(relative_path, path) as rel_path, thumbnail_path,
+     thumbnail_small_path, thumbnail_medium_path, sync_status, sync_error, retry_count,
+     exif_orientation, taken_at, camera_model, gps_latitude, gps_longitude, caption, tags";
+
+/// Manually ordered photos first, then the rest in import order
+const COLLECTION_ORDER: &str = "ORDER BY position IS NULL, position, created_at, rowid";
+
+fn photo_from_row(row: &rusqlite::Row) -> rusqlite::Result<Photo> {
+    let uuid_str: String = row.get(0)?;
+    let quail_id_str: Option<String> = row.get(1)?;
+    let event_id_str: Option<String> = row.get(2)?;
+    let relative_path: String = row.get(3)?;
+    let relative_thumb: Option<String> = row.get(4)?;
+    let thumbnail_small: Option<String> = row.get(5)?;
+    let thumbnail_medium: Option<String> = row.get(6)?;
+    let sync_status: Option<String> = row.get(7)?;
+    let sync_error: Option<String> = row.get(8)?;
+    let retry_count: Option<i32> = row.get(9)?;
+    let tags: Option<String> = row.get(16)?;
+
+    Ok(Photo {
+        uuid: Uuid::parse_str(&uuid_str).map_err(|_| rusqlite::Error::InvalidQuery)?,
+        quail_id: quail_id_str.and_then(|s| Uuid::parse_str(&s).ok()),
+        event_id: event_id_str.and_then(|s| Uuid::parse_str(&s).ok()),
+        path: get_absolute_photo_path(&relative_path),
+        thumbnail_path: relative_thumb.map(|t| get_absolute_photo_path(&t)),
+        thumbnail_small_path: thumbnail_small.map(|t| get_absolute_photo_path(&t)),
+        thumbnail_medium_path: thumbnail_medium.map(|t| get_absolute_photo_path(&t)),
+        sync_status,
+        sync_error,
+        retry_count,
+        metadata: PhotoMetadata {
+            orientation: row.get(10)?,
+            taken_at: row.get(11)?,
+            camera_model: row.get(12)?,
+            gps_latitude: row.get(13)?,
+            gps_longitude: row.get(14)?,
+        },
+        caption: row.get(15)?,
+        tags: tags
+            .and_then(|t| serde_json::from_str(&t).ok())
+            .unwrap_or_default(),
+    })
+}
+
+pub fn list_quail_photos(conn: &Connection, quail_uuid: &Uuid) -> Result<Vec<Photo>, AppError> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM photos WHERE quail_id = ?1 AND deleted = 0 AND trashed_at IS NULL {}",
+        PHOTO_COLUMNS, COLLECTION_ORDER
+    ))?;
+    let rows = stmt.query_map(params![quail_uuid.to_string()], photo_from_row)?;
+    Ok(rows.collect::<Result<Vec<_>, _>>()?)
+}
+
+pub fn list_event_photos(conn: &Connection, event_uuid: &Uuid) -> Result<Vec<Photo>, AppError> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM photos WHERE event_id = ?1 AND deleted = 0 AND trashed_at IS NULL {}",
+        PHOTO_COLUMNS, COLLECTION_ORDER
+    ))?;
+    let rows = stmt.query_map(params![event_uuid.to_string()], photo_from_row)?;
+    Ok(rows.collect::<Result<Vec<_>, _>>()?)
+}
+
+/// [`list_quail_photos`] off the UI thread, on a pooled connection
+pub async fn load_quail_photos(quail_uuid: Uuid) -> Result<Vec<Photo>, AppError> {
+    crate::database::with_db_async(move |conn| list_quail_photos(conn, &quail_uuid)).await
+}
+
+/// [`list_event_photos`] off the UI thread, on a pooled connection
+pub async fn load_event_photos(event_uuid: Uuid) -> Result<Vec<Photo>, AppError> {
+    crate::database::with_db_async(move |conn| list_event_photos(conn, &event_uuid)).await
+}
+
+pub fn list_collection_photos(
+    conn: &Connection,
+    collection: PhotoCollection,
+) -> Result<Vec<Photo>, AppError> {
+    match collection {
+        PhotoCollection::Quail(uuid) => list_quail_photos(conn, &uuid),
+        PhotoCollection::Event(uuid) => list_event_photos(conn, &uuid),
+    }
+}
+
+/// Column and id selecting the photos of a collection
+fn collection_filter(collection: PhotoCollection) -> (&'static str, String) {
+    match collection {
+        PhotoCollection::Quail(uuid) => ("quail_id", uuid.to_string()),
+        PhotoCollection::Event(uuid) => ("event_id", uuid.to_string()),
+    }
+}
+
+/// Up to `limit` photos of a collection starting at `offset`, in the same
+/// order as [`list_collection_photos`] (for galleries that load page by page)
+pub fn list_collection_photos_page(
+    conn: &Connection,
+    collection: PhotoCollection,
+    offset: usize,
+    limit: usize,
+) -> Result<Vec<Photo>, AppError> {
+    let (column, id) = collection_filter(collection);
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM photos WHERE {} = ?1 AND deleted = 0 AND trashed_at IS NULL {} LIMIT ?2 OFFSET ?3",
+        PHOTO_COLUMNS, column, COLLECTION_ORDER
+    ))?;
+    let rows = stmt.query_map(params![id, limit as i64, offset as i64], photo_from_row)?;
+    Ok(rows.collect::<Result<Vec<_>, _>>()?)
+}
+
+/// Number of photos in a collection (without trashed ones)
+pub fn count_collection_photos(
+    conn: &Connection,
+    collection: PhotoCollection,
+) -> Result<usize, AppError> {
+    let (column, id) = collection_filter(collection);
+    let count: i64 = conn.query_row(
+        &format!(
+            "SELECT COUNT(*) FROM photos WHERE {} = ?1 AND deleted = 0 AND trashed_at IS NULL",
+            column
+        ),
+        params![id],
+        |row| row.get(0),
+    )?;
+    Ok(count as usize)
+}
+
+/// Moves a photo to `new_index` within its collection (clamped to the end)
+/// and returns the reordered list
+///
+/// All photos of the collection get an explicit position; only changed
+/// positions are written and synced.
+pub async fn reorder_photo(
+    conn: &Connection,
+    collection: PhotoCollection,
+    photo_uuid: &Uuid,
+    new_index: usize,
+) -> Result<Vec<Photo>, AppError> {
+    let mut photos = list_collection_photos(conn, collection)?;
+    let current_index = photos
+        .iter()
+        .position(|p| p.uuid == *photo_uuid)
+        .ok_or_else(|| AppError::NotFound("Foto nicht gefunden".into()))?;
+    let photo = photos.remove(current_index);
+    photos.insert(new_index.min(photos.len()), photo);
+
+    for (index, photo) in photos.iter().enumerate() {
+        let changed = conn.execute(
+            "UPDATE photos SET position = ?1 WHERE uuid = ?2 AND position IS NOT ?1",
+            params![index as i64, photo.uuid.to_string()],
+        )?;
+        if changed > 0 {
+            crate::services::operation_capture::capture_photo_update(
+                conn,
+                &photo.uuid.to_string(),
+                "position",
+                serde_json::json!(index),
+            )
+            .await?;
+        }
+    }
+    Ok(photos)
+}
+
+/// Moves a photo to another quail or event
+///
+/// The photo is appended to the target (its manual position is cleared) and
+/// stops being the profile photo of its previous quail. Files are stored
+/// flat under `sync/photos/<uuid>` on the remote, so only the assignment
+/// changes and nothing has to be re-uploaded.
+pub async fn move_photo(
+    conn: &Connection,
+    photo_uuid: &Uuid,
+    target: PhotoCollection,
+) -> Result<(), AppError> {
+    let (quail_id, event_id): (Option<String>, Option<String>) = conn
+        .query_row(
+            "SELECT quail_id, event_id FROM photos WHERE uuid = ?1 AND deleted = 0",
+            params![photo_uuid.to_string()],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .optional()?
+        .ok_or_else(|| AppError::NotFound("Foto nicht gefunden".into()))?;
+
+    let (new_quail, new_event) = match target {
+        PhotoCollection::Quail(quail_id) => (Some(quail_id.to_string()), None),
+        PhotoCollection::Event(event_id) => (None, Some(event_id.to_string())),
+    };
+    if (&quail_id, &event_id) == (&new_quail, &new_event) {
+        return Ok(());
+    }
+
+    if let Some(old_quail) = &quail_id {
+        let cleared = conn.execute(
+            "UPDATE quails SET profile_photo = NULL WHERE uuid = ?1 AND profile_photo = ?2",
+            params![old_quail, photo_uuid.to_string()],
+        )?;
+        if cleared > 0 {
+            crate::services::operation_capture::capture_quail_update(
+                conn,
+                old_quail,
+                "profile_photo",
+                serde_json::Value::Null,
+            )
+            .await?;
+        }
+    }
+
+    conn.execute(
+        "UPDATE photos SET quail_id = ?1, event_id = ?2, position = NULL WHERE uuid = ?3",
+        params![new_quail, new_event, photo_uuid.to_string()],
+    )?;
+    crate::services::operation_capture::capture_photo_move(
+        conn,
+        &photo_uuid.to_string(),
+        new_quail.as_deref(),
+        new_event.as_deref(),
+    )
+    .await
+}
+
+/// Moves all photos of `src` (including trashed ones) to `dst` and returns
+/// how many were moved
+///
+/// The moved photos lose their manual order and follow the photos already
+/// in `dst` in import order.
+pub async fn merge_collections(
+    conn: &Connection,
+    src: PhotoCollection,
+    dst: PhotoCollection,
+) -> Result<usize, AppError> {
+    if src == dst {
+        return Ok(0);
+    }
+    let (column, id) = match src {
+        PhotoCollection::Quail(uuid) => ("quail_id", uuid),
+        PhotoCollection::Event(uuid) => ("event_id", uuid),
+    };
+    let uuids: Vec<String> = {
+        let mut stmt = conn.prepare(&format!(
+            "SELECT uuid FROM photos WHERE {} = ?1 AND deleted = 0 {}",
+            column, COLLECTION_ORDER
+        ))?;
+        let rows = stmt.query_map(params![id.to_string()], |row| row.get(0))?;
+        rows.collect::<Result<_, _>>()?
+    };
+
+    for uuid in &uuids {
+        let uuid = Uuid::parse_str(uuid)
+            .map_err(|_| AppError::Other(format!("Ungültige Foto-UUID: {}", uuid)))?;
+        move_photo(conn, &uuid, dst).await?;
+    }
+    log::info!(
+        "{} Fotos von {:?} nach {:?} verschoben",
+        uuids.len(),
+        src,
+        dst
+    );
+    Ok(uuids.len())
+}
+
+pub fn get_profile_photo(conn: &Connection, quail_uuid: &Uuid) -> Result<Option<Photo>, AppError> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM photos
+         WHERE uuid = (SELECT profile_photo FROM quails WHERE uuid = ?1) AND trashed_at IS NULL",
+        PHOTO_COLUMNS
+    ))?;
+    let res = stmt
+        .query_row(params![quail_uuid.to_string()], photo_from_row)
+        .optional()?;
+    Ok(res)
+}
+
+/// Normalizes tags: trimmed, lowercase, no empty entries or duplicates
+fn normalize_tags<S: AsRef<str>>(tags: &[S]) -> Vec<String> {
+    let mut normalized: Vec<String> = Vec::new();
+    for tag in tags {
+        let tag = tag.as_ref().trim().to_lowercase();
+        if !tag.is_empty() && !normalized.contains(&tag) {
+            normalized.push(tag);
+        }
+    }
+    normalized
+}
+
+/// Sets or clears (`None` or blank) the caption of a photo
+pub async fn set_photo_caption(
+    conn: &Connection,
+    photo_uuid: &Uuid,
+    caption: Option<&str>,
+) -> Result<(), AppError> {
+    let caption = caption.map(str::trim).filter(|c| !c.is_empty());
+    let rows = conn.execute(
+        "UPDATE photos SET caption = ?1 WHERE uuid = ?2 AND deleted = 0",
+        params![caption, photo_uuid.to_string()],
+    )?;
+    if rows == 0 {
+        return Err(AppError::NotFound("Foto nicht gefunden".into()));
+    }
+
+    crate::services::operation_capture::capture_photo_update(
+        conn,
+        &photo_uuid.to_string(),
+        "caption",
+        caption.map_or(serde_json::Value::Null, |c| c.into()),
+    )
+    .await
+}
+
+/// Replaces all tags of a photo and returns the normalized list
+pub async fn set_photo_tags<S: AsRef<str>>(
+    conn: &Connection,
+    photo_uuid: &Uuid,
+    tags: &[S],
+) -> Result<Vec<String>, AppError> {
+    let tags = normalize_tags(tags);
+    let value = serde_json::json!(tags);
+    let rows = conn.execute(
+        "UPDATE photos SET tags = ?1 WHERE uuid = ?2 AND deleted = 0",
+        params![value.to_string(), photo_uuid.to_string()],
+    )?;
+    if rows == 0 {
+        return Err(AppError::NotFound("Foto nicht gefunden".into()));
+    }
+
+    crate::services::operation_capture::capture_photo_update(
+        conn,
+        &photo_uuid.to_string(),
+        "tags",
+        value,
+    )
+    .await?;
+    Ok(tags)
+}
+
+pub fn get_photo_tags(conn: &Connection, photo_uuid: &Uuid) -> Result<Vec<String>, AppError> {
+    let tags: Option<String> = conn
+        .query_row(
+            "SELECT tags FROM photos WHERE uuid = ?1 AND deleted = 0",
+            params![photo_uuid.to_string()],
+            |row| row.get(0),
+        )
+        .optional()?
+        .ok_or_else(|| AppError::NotFound("Foto nicht gefunden".into()))?;
+    Ok(tags
+        .and_then(|t| serde_json::from_str(&t).ok())
+        .unwrap_or_default())
+}
+
+pub async fn add_photo_tag(
+    conn: &Connection,
+    photo_uuid: &Uuid,
+    tag: &str,
+) -> Result<Vec<String>, AppError> {
+    let mut tags = get_photo_tags(conn, photo_uuid)?;
+    tags.push(tag.to_string());
+    set_photo_tags(conn, photo_uuid, &tags).await
+}
+
+pub async fn remove_photo_tag(
+    conn: &Connection,
+    photo_uuid: &Uuid,
+    tag: &str,
+) -> Result<Vec<String>, AppError> {
+    let tag = tag.trim().to_lowercase();
+    let tags: Vec<String> = get_photo_tags(conn, photo_uuid)?
+        .into_iter()
+        .filter(|t| *t != tag)
+        .collect();
+    set_photo_tags(conn, photo_uuid, &tags).await
+}
+
+/// All tags in use, sorted (for suggestions and the gallery filter)
+pub fn list_all_tags(conn: &Connection) -> Result<Vec<String>, AppError> {
+    let mut stmt = conn.prepare(
+        "SELECT DISTINCT j.value FROM photos p, json_each(p.tags) j
+         WHERE p.deleted = 0 AND p.trashed_at IS NULL AND json_valid(p.tags)
+         ORDER BY j.value",
+    )?;
+    let rows = stmt.query_map([], |row| row.get(0))?;
+    Ok(rows.collect::<Result<Vec<_>, _>>()?)
+}
+
+/// Photos carrying the given tag (case-insensitive, exact match)
+pub fn find_photos_by_tag(conn: &Connection, tag: &str) -> Result<Vec<Photo>, AppError> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM photos
+         WHERE deleted = 0 AND trashed_at IS NULL AND json_valid(tags)
+           AND EXISTS (SELECT 1 FROM json_each(photos.tags) WHERE value = ?1)
+         ORDER BY COALESCE(taken_at, created_at) DESC",
+        PHOTO_COLUMNS
+    ))?;
+    let rows = stmt.query_map(params![tag.trim().to_lowercase()], photo_from_row)?;
+    Ok(rows.collect::<Result<Vec<_>, _>>()?)
+}
+
+/// Photos whose caption contains the given text (case-insensitive)
+pub fn find_photos_by_caption(conn: &Connection, query: &str) -> Result<Vec<Photo>, AppError> {
+    let query = query.trim();
+    if query.is_empty() {
+        return Ok(Vec::new());
+    }
+    let pattern = format!(
+        "%{}%",
+        query
+            .replace('\\', "\\\\")
+            .replace('%', "\\%")
+            .replace('_', "\\_")
+    );
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM photos
+         WHERE deleted = 0 AND trashed_at IS NULL AND caption LIKE ?1 ESCAPE '\\'
+         ORDER BY COALESCE(taken_at, created_at) DESC",
+        PHOTO_COLUMNS
+    ))?;
+    let rows = stmt.query_map(params![pattern], photo_from_row)?;
+    Ok(rows.collect::<Result<Vec<_>, _>>()?)
+}
+
+pub async fn set_profile_photo(
+    conn: &Connection,
+    quail_uuid: &Uuid,
+    photo_uuid: &Uuid,
+) -> Result<(), AppError> {
+    // Verify photo belongs to quail
+    let photo_quail: Option<String> = conn
+        .query_row(
+            "SELECT quail_id FROM photos WHERE uuid = ?1",
+            params![photo_uuid.to_string()],
+            |row| row.get(0),
+        )
+        .optional()?;
+
+    match photo_quail {
+        Some(qid) if qid == quail_uuid.to_string() => {
+            // Set profile_photo FK
+            let rows = conn.execute(
+                "UPDATE quails SET profile_photo = ?1 WHERE uuid = ?2",
+                params![photo_uuid.to_string(), quail_uuid.to_string()],
+            )?;
+            if rows == 0 {
+                return Err(AppError::NotFound("Wachtel nicht gefunden".into()));
+            }
+
+            // Capture CRDT operation for the update
+            crate::services::operation_capture::capture_quail_update(
+                conn,
+                &quail_uuid.to_string(),
+                "profile_photo",
+                serde_json::Value::String(photo_uuid.to_string()),
+            )
+            .await?;
+
+            Ok(())
+        }
+        Some(_) => Err(AppError::NotFound("Foto gehört nicht zur Wachtel".into())),
+        None => Err(AppError::NotFound("Foto nicht gefunden".into())),
+    }
+}
+
+/// Days a trashed photo can be restored before [`purge_trash`] removes it
+pub const TRASH_RETENTION_DAYS: i64 = 30;
+
+/// Moves a photo to the trash; it can be restored with [`restore_photo`]
+/// until [`purge_trash`] deletes it for good
+pub async fn delete_photo(conn: &Connection, photo_uuid: &Uuid) -> Result<(), AppError> {
+    let trashed_at = chrono::Utc::now().timestamp_millis();
+    let rows = conn.execute(
+        "UPDATE photos SET trashed_at = ?1 WHERE uuid = ?2 AND deleted = 0 AND trashed_at IS NULL",
+        params![trashed_at, photo_uuid.to_string()],
+    )?;
+    if rows == 0 {
+        return Err(AppError::NotFound("Foto nicht gefunden".into()));
+    }
+
+    crate::services::operation_capture::capture_photo_update(
+        conn,
+        &photo_uuid.to_string(),
+        "trashed_at",
+        serde_json::json!(trashed_at),
+    )
+    .await
+}
+
+pub async fn restore_photo(conn: &Connection, photo_uuid: &Uuid) -> Result<(), AppError> {
+    let rows = conn.execute(
+        "UPDATE photos SET trashed_at = NULL WHERE uuid = ?1 AND deleted = 0 AND trashed_at IS NOT NULL",
+        params![photo_uuid.to_string()],
+    )?;
+    if rows == 0 {
+        return Err(AppError::NotFound("Foto nicht im Papierkorb".into()));
+    }
+
+    crate::services::operation_capture::capture_photo_update(
+        conn,
+        &photo_uuid.to_string(),
+        "trashed_at",
+        serde_json::Value::Null,
+    )
+    .await
+}
+
+/// Photos in the trash, most recently deleted first
+pub fn list_trashed_photos(conn: &Connection) -> Result<Vec<Photo>, AppError> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM photos WHERE deleted = 0 AND trashed_at IS NOT NULL ORDER BY trashed_at DESC",
+        PHOTO_COLUMNS
+    ))?;
+    let rows = stmt.query_map([], photo_from_row)?;
+    Ok(rows.collect::<Result<Vec<_>, _>>()?)
+}
+
+/// Deletes photos that have been in the trash for more than `retention_days`
+/// (files, database row and CRDT delete)
+pub async fn purge_trash(conn: &Connection, retention_days: i64) -> Result<usize, AppError> {
+    let cutoff = (chrono::Utc::now() - chrono::Duration::days(retention_days)).timestamp_millis();
+    let mut stmt = conn.prepare(
+        "SELECT uuid, COALESCE(relative_path, path), thumbnail_small_path, thumbnail_medium_path
+         FROM photos
+         WHERE deleted = 0 AND trashed_at IS NOT NULL AND trashed_at <= ?1",
+    )?;
+    let expired: Vec<(String, String, Option<String>, Option<String>)> = stmt
+        .query_map(params![cutoff], |row| {
+            Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?))
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    for (uuid, relative_path, small_thumb, medium_thumb) in &expired {
+        for file in std::iter::once(relative_path)
+            .chain(small_thumb)
+            .chain(medium_thumb)
+        {
+            if !file.is_empty() {
+                let _ = std::fs::remove_file(get_absolute_photo_path(file));
+            }
+        }
+        remove_local_only_thumbnails(uuid);
+        conn.execute(
+            "UPDATE photos SET deleted = 1 WHERE uuid = ?1",
+            params![uuid],
+        )?;
+        crate::services::operation_capture::capture_photo_delete(conn, uuid).await?;
+    }
+
+    if !expired.is_empty() {
+        log::info!("Purged {} photos from the trash", expired.len());
+    }
+    Ok(expired.len())
+}
+
+/// File that is loaded for a photo in a given size, with its sync state
+pub(crate) struct PhotoFile {
+    /// Relative path of the file
+    pub path: String,
+    sync_status: Option<String>,
+    retry_count: Option<i32>,
+}
+
+/// Resolves which file to load for `size` (original while thumbnails are queued)
+pub(crate) fn resolve_photo_file(
+    conn: &Connection,
+    photo_uuid: &Uuid,
+    size: PhotoSize,
+) -> Result<PhotoFile, AppError> {
+    // Query photo info from database
+    let photo_info: Option<(String, Option<String>, Option<String>, Option<String>, Option<i32>)> = conn
+        .query_row(
+            "SELECT COALESCE(relative_path, path), thumbnail_small_path, thumbnail_medium_path, sync_status, retry_count
+             FROM photos 
+             WHERE uuid = ?1 AND deleted = 0",
+            params![photo_uuid.to_string()],
+            |row| {
+                Ok((
+                    row.get(0)?,
+                    row.get(1)?,
+                    row.get(2)?,
+                    row.get(3)?,
+                    row.get(4)?,
+                ))
+            },
+        )
+        .optional()?;
+
+    let (relative_path, small_thumb, medium_thumb, sync_status, retry_count) = match photo_info {
+        Some(info) => info,
+        None => return Err(AppError::NotFound("Foto nicht gefunden".into())),
+    };
+
+    let thumbnails_pending = thumbnail_queue::is_pending(&photo_uuid.to_string());
+    let synced_file = |name: &str| match name {
+        "small" => small_thumb.clone(),
+        "medium" => medium_thumb.clone(),
+        _ => None,
+    };
+    let path = match size.tier() {
+        Some(tier) if !thumbnails_pending => {
+            let local = tier.file_name(&photo_uuid.to_string());
+            if !tier.synced && std::path::Path::new(&get_absolute_photo_path(&local)).exists() {
+                local
+            } else {
+                // Local-only tiers are missing on other devices: next larger synced tier
+                THUMBNAIL_TIERS
+                    .iter()
+                    .filter(|t| t.synced && t.max_px >= tier.max_px)
+                    .find_map(|t| synced_file(t.name))
+                    .unwrap_or_else(|| relative_path.clone())
+            }
+        }
+        _ => relative_path.clone(),
+    };
+
+    Ok(PhotoFile {
+        path,
+        sync_status,
+        retry_count,
+    })
+}
+
+/// Get photo with on-demand download capability
+/// Returns Available(bytes), Downloading, or Failed(error, retry_count)
+///
+/// A missing file is queued in the [`download_queue`] with the priority and
+/// scope of `request`.
+pub async fn get_photo_with_download(
+    conn: &Connection,
+    photo_uuid: &Uuid,
+    size: PhotoSize,
+    request: DownloadRequest,
+) -> Result<PhotoResult, AppError> {
+    Ok(match locate_photo(conn, photo_uuid, size, request).await? {
+        PhotoResult::Available(absolute_path) => {
+            PhotoResult::Available(std::fs::read(absolute_path)?)
+        }
+        PhotoResult::Downloading => PhotoResult::Downloading,
+        PhotoResult::Failed(error, retry_count) => PhotoResult::Failed(error, retry_count),
+    })
+}
+
+/// Like [`get_photo_with_download`], but returns the asset URL the WebView
+/// loads the photo from instead of reading the whole file
+pub async fn get_photo_url_with_download(
+    conn: &Connection,
+    photo_uuid: &Uuid,
+    size: PhotoSize,
+    request: DownloadRequest,
+) -> Result<PhotoResult<String>, AppError> {
+    Ok(match locate_photo(conn, photo_uuid, size, request).await? {
+        PhotoResult::Available(_) => {
+            PhotoResult::Available(photo_assets::photo_asset_url(photo_uuid, size))
+        }
+        PhotoResult::Downloading => PhotoResult::Downloading,
+        PhotoResult::Failed(error, retry_count) => PhotoResult::Failed(error, retry_count),
+    })
+}
+
+/// Returns Available(absolute path) if the file is present locally,
+/// otherwise queues the download
+async fn locate_photo(
+    conn: &Connection,
+    photo_uuid: &Uuid,
+    size: PhotoSize,
+    request: DownloadRequest,
+) -> Result<PhotoResult<String>, AppError> {
+    let PhotoFile {
+        path: file_path,
+        sync_status,
+        retry_count,
+    } = resolve_photo_file(conn, photo_uuid, size)?;
+    let file_path = &file_path;
+
+    // Listen-Thumbnails zählen nicht als Ansehen (LRU für das Speicherlimit)
+    let list_thumbnail = size
+        .tier()
+        .zip(PhotoSize::SMALL.tier())
+        .is_some_and(|(tier, small)| tier.max_px <= small.max_px);
+    if !list_thumbnail {
+        crate::services::storage_quota::mark_viewed(conn, &photo_uuid.to_string())?;
+    }
+
+    let absolute_path = get_absolute_photo_path(file_path);
+
+    // Check if file exists locally
+    if std::path::Path::new(&absolute_path).exists() {
+        return Ok(PhotoResult::Available(absolute_path));
+    }
+
+    // File doesn't exist locally - check sync status
+    let status = sync_status.unwrap_or_else(|| "local_only".to_string());
+    let retry_count = retry_count.unwrap_or(0);
+
+    match status.as_str() {
+        // Another size of the photo may be loading, or the app quit mid-download
+        "downloading" if download_queue::promote(file_path, request) => {
+            Ok(PhotoResult::Downloading)
+        }
+        "downloading" => spawn_photo_download(
+            conn,
+            photo_uuid,
+            file_path,
+            "download_pending",
+            retry_count,
+            request,
+        ),
+        "download_failed" | photo_checksum::CORRUPT_STATUS if retry_count >= 5 => {
+            Ok(PhotoResult::Failed(
+                "Maximale Anzahl an Versuchen erreicht".to_string(),
+                retry_count,
+            ))
+        }
+        "download_failed" | "download_pending" | "synced" | photo_checksum::CORRUPT_STATUS => {
+            // Attempt download
+            spawn_photo_download(conn, photo_uuid, file_path, &status, retry_count, request)
+        }
+        _ => Ok(PhotoResult::Failed(
+            "Foto nicht remote verfügbar".to_string(),
+            retry_count,
+        )),
+    }
+}
+
+/// Queues the download of a photo file in the [`download_queue`]
+fn spawn_photo_download(
+    conn: &Connection,
+    photo_uuid: &Uuid,
+    relative_path: &str,
+    previous_status: &str,
+    retry_count: i32,
+    request: DownloadRequest,
+) -> Result<PhotoResult<String>, AppError> {
+    // Update status to downloading
+    conn.execute(
+        "UPDATE photos SET sync_status = 'downloading', last_sync_attempt = ?1 WHERE uuid = ?2",
+        params![
+            chrono::Utc::now().timestamp_millis(),
+            photo_uuid.to_string()
+        ],
+    )?;
+
+    let job = DownloadJob {
+        photo_uuid: *photo_uuid,
+        path: relative_path.to_string(),
+        retry_count,
+        previous_status: previous_status.to_string(),
+    };
+    if !download_queue::enqueue(job, request) {
+        // The requesting screen is already gone
+        conn.execute(
+            "UPDATE photos SET sync_status = ?1 WHERE uuid = ?2 AND sync_status = 'downloading'",
+            params![previous_status, photo_uuid.to_string()],
+        )?;
+    }
+
+    Ok(PhotoResult::Downloading)
+}
+
+/// Downloads a photo from remote storage
+/// Returns false (nothing saved) if the original does not match its checksum
+pub(crate) async fn download_photo_from_remote(
+    photo_uuid: &Uuid,
+    relative_path: &str,
+) -> Result<bool, AppError> {
+    // Load sync settings
+    let conn = crate::database::connection()?;
+    let settings = crate::services::sync_service::load_sync_settings(&conn)?
+        .ok_or_else(|| AppError::Other("Sync nicht konfiguriert".to_string()))?;
+
+    let remote = RemotePhotoStorage::from_settings(&settings)?.with_local_key(&conn)?;
+    let local = LocalPhotoStorage::app_photos();
+
+    // Download file and save to local storage
+    let bytes = remote.read(relative_path).await?;
+    if !photo_checksum::matches_expected(&conn, &photo_uuid.to_string(), relative_path, &bytes)? {
+        return Ok(false);
+    }
+    local.write(relative_path, bytes).await?;
+
+    log::info!(
+        "Downloaded photo {} to {}",
+        photo_uuid,
+        local.path_of(relative_path).display()
+    );
+    Ok(true)
+}
+
+/// Retry all failed downloads that haven't exceeded max retries
+/// (including corrupt downloads; a corrupt upload with its original still
+/// on this device is re-uploaded instead)
+pub async fn retry_failed_downloads(
+    conn: &Connection,
+    request: DownloadRequest,
+) -> Result<usize, AppError> {
+    let mut stmt = conn.prepare(
+        "SELECT uuid, COALESCE(relative_path, path), sync_status, retry_count
+         FROM photos 
+         WHERE sync_status IN ('download_failed', ?1) AND retry_count < 5 AND deleted = 0",
+    )?;
+
+    let photos: Vec<(Uuid, String, String, i32)> = stmt
+        .query_map([photo_checksum::CORRUPT_STATUS], |row| {
+            let uuid_str: String = row.get(0)?;
+            Ok((
+                Uuid::parse_str(&uuid_str).map_err(|_| rusqlite::Error::InvalidQuery)?,
+                row.get(1)?,
+                row.get(2)?,
+                row.get(3)?,
+            ))
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+    let photos: Vec<_> = photos
+        .into_iter()
+        .filter(|(_, relative_path, _, _)| {
+            !std::path::Path::new(&get_absolute_photo_path(relative_path)).exists()
+        })
+        .collect();
+
+    let count = photos.len();
+    log::info!("Retrying {} failed photo downloads", count);
+
+    for (uuid, relative_path, status, retry_count) in photos {
+        let _ = spawn_photo_download(conn, &uuid, &relative_path, &status, retry_count, request);
+    }
+
+    Ok(count)
+}
+
+/// Gives a photo whose download failed too often a fresh set of attempts
+/// (the user asked for a retry); the next `get_photo_*_with_download` call
+/// queues the download again
+pub fn reset_download_retries(conn: &Connection, photo_uuid: &Uuid) -> Result<(), AppError> {
+    conn.execute(
+        "UPDATE photos SET retry_count = 0, sync_error = NULL
+         WHERE uuid = ?1 AND sync_status IN ('download_failed', ?2)",
+        params![photo_uuid.to_string(), photo_checksum::CORRUPT_STATUS],
+    )?;
+    Ok(())
+}
+
+/// Cleanup orphaned photos (photos without valid quail_id or event_id references)
+pub async fn cleanup_orphaned_photos(conn: &Connection) -> Result<usize, AppError> {
+    // Find orphaned photos
+    let mut stmt = conn.prepare(
+        "SELECT uuid, COALESCE(relative_path, path), thumbnail_small_path, thumbnail_medium_path
+         FROM photos 
+         WHERE deleted = 0 AND (
+            (quail_id IS NOT NULL AND quail_id NOT IN (SELECT uuid FROM quails WHERE deleted = 0))
+            OR
+            (event_id IS NOT NULL AND event_id NOT IN (SELECT uuid FROM quail_events WHERE deleted = 0))
+         )",
+    )?;
+
+    let orphaned: Vec<(Uuid, String, Option<String>, Option<String>)> = stmt
+        .query_map([], |row| {
+            let uuid_str: String = row.get(0)?;
+            Ok((
+                Uuid::parse_str(&uuid_str).map_err(|_| rusqlite::Error::InvalidQuery)?,
+                row.get(1)?,
+                row.get(2)?,
+                row.get(3)?,
+            ))
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let count = orphaned.len();
+    log::info!("Found {} orphaned photos to clean up", count);
+
+    for (uuid, relative_path, small_thumb, medium_thumb) in orphaned {
+        // Delete physical files
+        let _ = std::fs::remove_file(get_absolute_photo_path(&relative_path));
+        if let Some(small) = small_thumb {
+            let _ = std::fs::remove_file(get_absolute_photo_path(&small));
+        }
+        if let Some(medium) = medium_thumb {
+            let _ = std::fs::remove_file(get_absolute_photo_path(&medium));
+        }
+        remove_local_only_thumbnails(&uuid.to_string());
+
+        // Mark as deleted in database
+        conn.execute(
+            "UPDATE photos SET deleted = 1 WHERE uuid = ?1",
+            params![uuid.to_string()],
+        )?;
+    }
+
+    Ok(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::schema;
+
+    /// Wachtel, auf die die Test-Fotos verweisen (Fremdschlüssel)
+    fn insert_quail(conn: &Connection) -> Uuid {
+        let uuid = Uuid::new_v4();
+        conn.execute(
+            "INSERT INTO quails (uuid, name) VALUES (?1, 'Erna')",
+            params![uuid.to_string()],
+        )
+        .unwrap();
+        uuid
+    }
+
+    #[tokio::test]
+    async fn test_import_quail_photos_reports_each_file() {
+        let conn = Connection::open_in_memory().unwrap();
+        schema::init_schema(&conn).unwrap();
+
+        let dir = std::env::temp_dir().join(format!("import-test-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let mut paths = Vec::new();
+        for i in 0..4 {
+            let path = dir.join(format!("IMG_{}.jpg", i));
+            image::DynamicImage::new_rgb8(32, 16).save(&path).unwrap();
+            paths.push(path.to_string_lossy().to_string());
+        }
+        paths.push(dir.join("missing.jpg").to_string_lossy().to_string());
+
+        let quail_id = insert_quail(&conn);
+        let mut reported = Vec::new();
+        let results =
+            import_quail_photos(&conn, quail_id, paths, |p| reported.push((p.done, p.total))).await;
+
+        assert_eq!(reported, (1..=5).map(|done| (done, 5)).collect::<Vec<_>>());
+        assert_eq!(results.iter().filter(|p| p.result.is_ok()).count(), 4);
+        let failed: Vec<_> = results.iter().filter(|p| p.result.is_err()).collect();
+        assert_eq!(failed.len(), 1);
+        assert!(failed[0].path.ends_with("missing.jpg"));
+        assert_eq!(list_quail_photos(&conn, &quail_id).unwrap().len(), 4);
+
+        thumbnail_queue::wait_idle().await;
+        for photo in results.iter().filter_map(|p| p.result.as_ref().ok()) {
+            assert!(dir.join(format!("{}_small.webp", photo)).exists());
+        }
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_add_photo_bytes_converts_pasted_images() {
+        let conn = Connection::open_in_memory().unwrap();
+        schema::init_schema(&conn).unwrap();
+        let dir = std::env::temp_dir().join(format!("paste-test-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let mut png = Vec::new();
+        image::DynamicImage::new_rgba8(40, 20)
+            .write_to(&mut Cursor::new(&mut png), ImageFormat::Png)
+            .unwrap();
+        let quail_id = insert_quail(&conn);
+        let uuid = add_photo_bytes(&conn, PhotoCollection::Quail(quail_id), dir.clone(), png)
+            .await
+            .unwrap();
+
+        let stored = dir.join(format!("{}.jpg", uuid));
+        assert_eq!(
+            image_processing::InputFormat::of_file(&stored).unwrap(),
+            image_processing::InputFormat::Jpeg
+        );
+        assert_eq!(list_quail_photos(&conn, &quail_id).unwrap().len(), 1);
+
+        let event_id = Uuid::new_v4();
+        assert!(matches!(
+            add_photo_bytes(
+                &conn,
+                PhotoCollection::Event(event_id),
+                dir.clone(),
+                b"text".to_vec()
+            )
+            .await,
+            Err(AppError::Validation(_))
+        ));
+        assert!(list_event_photos(&conn, &event_id).unwrap().is_empty());
+
+        thumbnail_queue::wait_idle().await;
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_photo_captions_and_tags() {
+        let conn = Connection::open_in_memory().unwrap();
+        schema::init_schema(&conn).unwrap();
+        let quail_id = insert_quail(&conn);
+        let (a, b) = (Uuid::new_v4(), Uuid::new_v4());
+        for uuid in [a, b] {
+            conn.execute(
+                "INSERT INTO photos (uuid, quail_id, path, relative_path) VALUES (?1, ?2, '', ?3)",
+                params![
+                    uuid.to_string(),
+                    quail_id.to_string(),
+                    format!("{}.jpg", uuid)
+                ],
+            )
+            .unwrap();
+        }
+
+        let tags = set_photo_tags(&conn, &a, &[" Brut ", "stall", "brut", ""])
+            .await
+            .unwrap();
+        assert_eq!(tags, vec!["brut", "stall"]);
+        add_photo_tag(&conn, &b, "Stall").await.unwrap();
+        assert_eq!(
+            remove_photo_tag(&conn, &a, "STALL").await.unwrap(),
+            vec!["brut"]
+        );
+        set_photo_caption(&conn, &a, Some("  Erstes Ei 100% sicher "))
+            .await
+            .unwrap();
+        set_photo_caption(&conn, &b, Some("Neuer Stall"))
+            .await
+            .unwrap();
+
+        assert_eq!(list_all_tags(&conn).unwrap(), vec!["brut", "stall"]);
+        let by_tag = find_photos_by_tag(&conn, "STALL").unwrap();
+        assert_eq!(by_tag.len(), 1);
+        assert_eq!(by_tag[0].uuid, b);
+        assert_eq!(by_tag[0].tags, vec!["stall"]);
+
+        let by_caption = find_photos_by_caption(&conn, "erstes ei").unwrap();
+        assert_eq!(by_caption.len(), 1);
+        assert_eq!(
+            by_caption[0].caption.as_deref(),
+            Some("Erstes Ei 100% sicher")
+        );
+        assert_eq!(find_photos_by_caption(&conn, "100%").unwrap().len(), 1);
+        assert!(find_photos_by_caption(&conn, "E_stes").unwrap().is_empty());
+
+        set_photo_caption(&conn, &a, Some(" ")).await.unwrap();
+        let photos = list_quail_photos(&conn, &quail_id).unwrap();
+        assert!(photos.iter().all(|p| p.uuid != a || p.caption.is_none()));
+        assert!(matches!(
+            set_photo_tags(&conn, &Uuid::new_v4(), &["x"]).await,
+            Err(AppError::NotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_reorder_photo_within_collection() {
+        let conn = Connection::open_in_memory().unwrap();
+        schema::init_schema(&conn).unwrap();
+        let quail_id = insert_quail(&conn);
+        let photos: Vec<Uuid> = (0..4).map(|_| Uuid::new_v4()).collect();
+        for uuid in &photos {
+            conn.execute(
+                "INSERT INTO photos (uuid, quail_id, path, relative_path) VALUES (?1, ?2, '', ?3)",
+                params![
+                    uuid.to_string(),
+                    quail_id.to_string(),
+                    format!("{}.jpg", uuid)
+                ],
+            )
+            .unwrap();
+        }
+        let collection = PhotoCollection::Quail(quail_id);
+        let order = |conn: &Connection| -> Vec<Uuid> {
+            list_collection_photos(conn, collection)
+                .unwrap()
+                .iter()
+                .map(|p| p.uuid)
+                .collect()
+        };
+        assert_eq!(order(&conn), photos);
+
+        // Bestes Foto nach vorne
+        reorder_photo(&conn, collection, &photos[2], 0)
+            .await
+            .unwrap();
+        assert_eq!(
+            order(&conn),
+            vec![photos[2], photos[0], photos[1], photos[3]]
+        );
+
+        // Index hinter dem Ende landet am Ende
+        let reordered = reorder_photo(&conn, collection, &photos[0], 99)
+            .await
+            .unwrap();
+        let expected = vec![photos[2], photos[1], photos[3], photos[0]];
+        assert_eq!(
+            reordered.iter().map(|p| p.uuid).collect::<Vec<_>>(),
+            expected
+        );
+        assert_eq!(order(&conn), expected);
+
+        // Neue Fotos ohne Position kommen hinten dazu
+        let new_photo = Uuid::new_v4();
+        conn.execute(
+            "INSERT INTO photos (uuid, quail_id, path) VALUES (?1, ?2, '')",
+            params![new_photo.to_string(), quail_id.to_string()],
+        )
+        .unwrap();
+        assert_eq!(order(&conn).last(), Some(&new_photo));
+
+        assert!(matches!(
+            reorder_photo(&conn, PhotoCollection::Event(quail_id), &photos[0], 0).await,
+            Err(AppError::NotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_list_collection_photos_page() {
+        let conn = Connection::open_in_memory().unwrap();
+        schema::init_schema(&conn).unwrap();
+        let quail_id = insert_quail(&conn);
+        let photos: Vec<Uuid> = (0..5).map(|_| Uuid::new_v4()).collect();
+        for (position, uuid) in photos.iter().enumerate() {
+            conn.execute(
+                "INSERT INTO photos (uuid, quail_id, path, position) VALUES (?1, ?2, '', ?3)",
+                params![uuid.to_string(), quail_id.to_string(), position as i64],
+            )
+            .unwrap();
+        }
+        // Papierkorb zählt nicht mit
+        conn.execute(
+            "INSERT INTO photos (uuid, quail_id, path, trashed_at) VALUES (?1, ?2, '', 1)",
+            params![Uuid::new_v4().to_string(), quail_id.to_string()],
+        )
+        .unwrap();
+        let collection = PhotoCollection::Quail(quail_id);
+
+        let mut paged = Vec::new();
+        for offset in (0..6).step_by(2) {
+            let page = list_collection_photos_page(&conn, collection, offset, 2).unwrap();
+            assert!(page.len() <= 2);
+            paged.extend(page.iter().map(|p| p.uuid));
+        }
+        assert_eq!(paged, photos);
+        assert_eq!(count_collection_photos(&conn, collection).unwrap(), 5);
+        assert_eq!(
+            count_collection_photos(&conn, PhotoCollection::Event(quail_id)).unwrap(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_reset_download_retries() {
+        let conn = Connection::open_in_memory().unwrap();
+        schema::init_schema(&conn).unwrap();
+        let failed = Uuid::new_v4();
+        let synced = Uuid::new_v4();
+        for (uuid, status) in [(failed, "download_failed"), (synced, "synced")] {
+            conn.execute(
+                "INSERT INTO photos (uuid, path, sync_status, sync_error, retry_count)
+                 VALUES (?1, '', ?2, 'Timeout', 5)",
+                params![uuid.to_string(), status],
+            )
+            .unwrap();
+        }
+
+        reset_download_retries(&conn, &failed).unwrap();
+        reset_download_retries(&conn, &synced).unwrap();
+
+        let retries = |uuid: Uuid| -> (i32, Option<String>) {
+            conn.query_row(
+                "SELECT retry_count, sync_error FROM photos WHERE uuid = ?1",
+                [uuid.to_string()],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .unwrap()
+        };
+        assert_eq!(retries(failed), (0, None));
+        // Nur fehlgeschlagene Downloads werden zurückgesetzt
+        assert_eq!(retries(synced), (5, Some("Timeout".to_string())));
+    }
+
+    #[tokio::test]
+    async fn test_move_and_merge_photos() {
+        let conn = Connection::open_in_memory().unwrap();
+        schema::init_schema(&conn).unwrap();
+        let wrong_quail = Uuid::new_v4();
+        let right_quail = Uuid::new_v4();
+        let event_id = Uuid::new_v4();
+        let profile = Uuid::new_v4();
+        for quail_id in [wrong_quail, right_quail] {
+            conn.execute(
+                "INSERT INTO quails (uuid, name) VALUES (?1, 'Erna')",
+                params![quail_id.to_string()],
+            )
+            .unwrap();
+        }
+        conn.execute(
+            "INSERT INTO quail_events (uuid, quail_id, event_type, event_date)
+             VALUES (?1, ?2, 'sick', '2025-01-01')",
+            params![event_id.to_string(), wrong_quail.to_string()],
+        )
+        .unwrap();
+        conn.execute(
+            "INSERT INTO photos (uuid, quail_id, path, position) VALUES (?1, ?2, '', 0)",
+            params![profile.to_string(), wrong_quail.to_string()],
+        )
+        .unwrap();
+        set_profile_photo(&conn, &wrong_quail, &profile)
+            .await
+            .unwrap();
+        let event_photos: Vec<Uuid> = (0..2).map(|_| Uuid::new_v4()).collect();
+        for uuid in &event_photos {
+            conn.execute(
+                "INSERT INTO photos (uuid, event_id, path) VALUES (?1, ?2, '')",
+                params![uuid.to_string(), event_id.to_string()],
+            )
+            .unwrap();
+        }
+        delete_photo(&conn, &event_photos[1]).await.unwrap();
+
+        move_photo(&conn, &profile, PhotoCollection::Quail(right_quail))
+            .await
+            .unwrap();
+        assert!(list_quail_photos(&conn, &wrong_quail).unwrap().is_empty());
+        assert_eq!(
+            list_quail_photos(&conn, &right_quail).unwrap()[0].uuid,
+            profile
+        );
+        assert!(get_profile_photo(&conn, &wrong_quail).unwrap().is_none());
+        // Erneutes Verschieben ins selbe Ziel ändert nichts
+        move_photo(&conn, &profile, PhotoCollection::Quail(right_quail))
+            .await
+            .unwrap();
+
+        let moved = merge_collections(
+            &conn,
+            PhotoCollection::Event(event_id),
+            PhotoCollection::Quail(right_quail),
+        )
+        .await
+        .unwrap();
+        assert_eq!(moved, 2);
+        assert!(list_event_photos(&conn, &event_id).unwrap().is_empty());
+        let gallery: Vec<Uuid> = list_quail_photos(&conn, &right_quail)
+            .unwrap()
+            .iter()
+            .map(|p| p.uuid)
+            .collect();
+        assert_eq!(gallery, vec![profile, event_photos[0]]);
+
+        // Auch das gelöschte Foto landet nach dem Wiederherstellen im Ziel
+        restore_photo(&conn, &event_photos[1]).await.unwrap();
+        assert_eq!(list_quail_photos(&conn, &right_quail).unwrap().len(), 3);
+
+        assert!(matches!(
+            move_photo(&conn, &Uuid::new_v4(), PhotoCollection::Event(event_id)).await,
+            Err(AppError::NotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_trash_restore_and_purge() {
+        let conn = Connection::open_in_memory().unwrap();
+        schema::init_schema(&conn).unwrap();
+        let quail_id = insert_quail(&conn);
+        let (kept, purged) = (Uuid::new_v4(), Uuid::new_v4());
+        for uuid in [kept, purged] {
+            conn.execute(
+                "INSERT INTO photos (uuid, quail_id, path, relative_path) VALUES (?1, ?2, '', ?3)",
+                params![
+                    uuid.to_string(),
+                    quail_id.to_string(),
+                    format!("{}.jpg", uuid)
+                ],
+            )
+            .unwrap();
+        }
+
+        delete_photo(&conn, &kept).await.unwrap();
+        delete_photo(&conn, &purged).await.unwrap();
+        assert!(list_quail_photos(&conn, &quail_id).unwrap().is_empty());
+        assert_eq!(list_trashed_photos(&conn).unwrap().len(), 2);
+        assert!(matches!(
+            delete_photo(&conn, &kept).await,
+            Err(AppError::NotFound(_))
+        ));
+
+        restore_photo(&conn, &kept).await.unwrap();
+        assert_eq!(list_quail_photos(&conn, &quail_id).unwrap()[0].uuid, kept);
+        assert!(restore_photo(&conn, &kept).await.is_err());
+
+        // Innerhalb der Frist bleibt das Foto im Papierkorb
+        assert_eq!(purge_trash(&conn, TRASH_RETENTION_DAYS).await.unwrap(), 0);
+        conn.execute(
+            "UPDATE photos SET trashed_at = trashed_at - ?1 WHERE uuid = ?2",
+            params![
+                (TRASH_RETENTION_DAYS + 1) * 24 * 60 * 60 * 1000,
+                purged.to_string()
+            ],
+        )
+        .unwrap();
+        assert_eq!(purge_trash(&conn, TRASH_RETENTION_DAYS).await.unwrap(), 1);
+        assert!(list_trashed_photos(&conn).unwrap().is_empty());
+        assert!(restore_photo(&conn, &purged).await.is_err());
+    }
+}
@@ -0,0 +1,352 @@
+//! Austauschbarer Speicher für Fotodateien
+//!
+//! Upload und Download kopieren Fotos nur noch zwischen zwei
+//! [`PhotoStorage`]-Implementierungen: [`LocalPhotoStorage`] (Dateisystem
+//! der App) und [`RemotePhotoStorage`] (`sync/photos/` auf dem
+//! [`SyncBackend`]). Ein anderer Blob-Speicher muss nur dieses Trait
+//! implementieren; die Sync-Module bleiben unverändert.
+//!
+//! Alle Pfade sind relativ zur Wurzel des jeweiligen Speichers.
+
+use crate::error::AppError;
+use crate::models::SyncSettings;
+#[cfg(feature = "encryption")]
+use crate::services::photo_crypto::PhotoKey;
+use crate::services::sync_backend::{ConfiguredBackend, RemoteEntry, SyncBackend};
+use crate::services::sync_paths;
+use rusqlite::Connection;
+use std::future::Future;
+use std::path::PathBuf;
+
+/// Lesen, Schreiben, Löschen und Auflisten von Fotodateien per relativem Pfad
+///
+/// Die Futures sind `Send`, damit Uploads parallel in einem `JoinSet` laufen können.
+pub trait PhotoStorage: Send + Sync {
+    fn read(&self, path: &str) -> impl Future<Output = Result<Vec<u8>, AppError>> + Send;
+
+    /// Schreibt eine Datei (überschreibt vorhandene)
+    fn write(&self, path: &str, data: Vec<u8>)
+        -> impl Future<Output = Result<(), AppError>> + Send;
+
+    /// Schreibt eine Datei nur, wenn unter `path` noch keine liegt;
+    /// `Ok(false)` lässt die vorhandene unverändert
+    fn write_new(
+        &self,
+        path: &str,
+        data: Vec<u8>,
+    ) -> impl Future<Output = Result<bool, AppError>> + Send {
+        async move {
+            if self.exists(path).await {
+                return Ok(false);
+            }
+            self.write(path, data).await.map(|()| true)
+        }
+    }
+
+    fn delete(&self, path: &str) -> impl Future<Output = Result<(), AppError>> + Send;
+
+    /// Dateinamen in der Wurzel des Speichers (ein noch leerer Speicher liefert `[]`)
+    fn list(&self) -> impl Future<Output = Result<Vec<String>, AppError>> + Send;
+
+    fn exists(&self, path: &str) -> impl Future<Output = bool> + Send {
+        async move { self.read(path).await.is_ok() }
+    }
+}
+
+/// Fotos im lokalen Dateisystem
+#[derive(Debug, Clone)]
+pub struct LocalPhotoStorage {
+    root: PathBuf,
+}
+
+impl LocalPhotoStorage {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// Fotoverzeichnis der App (siehe [`get_absolute_photo_path`](crate::services::photo_service::get_absolute_photo_path))
+    pub fn app_photos() -> Self {
+        Self::new(crate::services::photo_service::get_absolute_photo_path(""))
+    }
+
+    pub fn path_of(&self, path: &str) -> PathBuf {
+        self.root.join(path)
+    }
+}
+
+impl PhotoStorage for LocalPhotoStorage {
+    async fn read(&self, path: &str) -> Result<Vec<u8>, AppError> {
+        let file = self.path_of(path);
+        std::fs::read(&file).map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => AppError::NotFound(file.display().to_string()),
+            _ => AppError::Other(format!("Failed to read {}: {}", file.display(), e)),
+        })
+    }
+
+    async fn write(&self, path: &str, data: Vec<u8>) -> Result<(), AppError> {
+        let file = self.path_of(path);
+        if let Some(parent) = file.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&file, data)
+            .map_err(|e| AppError::Other(format!("Failed to save {}: {}", file.display(), e)))
+    }
+
+    async fn delete(&self, path: &str) -> Result<(), AppError> {
+        match std::fs::remove_file(self.path_of(path)) {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn list(&self) -> Result<Vec<String>, AppError> {
+        let dir = if self.root.as_os_str().is_empty() {
+            PathBuf::from(".")
+        } else {
+            self.root.clone()
+        };
+        let entries = match std::fs::read_dir(&dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
+        let mut names = Vec::new();
+        for entry in entries.flatten() {
+            if entry.file_type().map(|t| t.is_file()).unwrap_or(false) {
+                names.push(entry.file_name().to_string_lossy().to_string());
+            }
+        }
+        names.sort();
+        Ok(names)
+    }
+
+    async fn exists(&self, path: &str) -> bool {
+        self.path_of(path).exists()
+    }
+}
+
+/// Fotos in `sync/photos/` auf einem [`SyncBackend`] (standardmäßig dem
+/// laut Einstellungen gewählten)
+///
+/// Mit Feature `encryption` und lokalem Schlüssel werden die Dateien
+/// verschlüsselt abgelegt (siehe [`photo_crypto`](crate::services::photo_crypto)).
+pub struct RemotePhotoStorage<B: SyncBackend = ConfiguredBackend> {
+    backend: B,
+    dir: String,
+    #[cfg(feature = "encryption")]
+    key: Option<PhotoKey>,
+}
+
+impl<B: SyncBackend> RemotePhotoStorage<B> {
+    pub fn new(backend: B, remote_path: &str) -> Self {
+        Self {
+            backend,
+            dir: format!(
+                "{}/{}",
+                remote_path.trim_end_matches('/'),
+                sync_paths::PHOTOS_DIR
+            ),
+            #[cfg(feature = "encryption")]
+            key: None,
+        }
+    }
+
+    /// Verwendet den lokal gespeicherten Fotoschlüssel, falls vorhanden
+    /// (ohne Feature `encryption` unverändert)
+    pub fn with_local_key(self, conn: &Connection) -> Result<Self, AppError> {
+        #[cfg(feature = "encryption")]
+        {
+            let key = crate::services::photo_crypto::load_key(conn)?;
+            Ok(self.with_key(key))
+        }
+        #[cfg(not(feature = "encryption"))]
+        {
+            let _ = conn;
+            Ok(self)
+        }
+    }
+
+    #[cfg(feature = "encryption")]
+    pub fn with_key(mut self, key: Option<PhotoKey>) -> Self {
+        self.key = key;
+        self
+    }
+
+    /// Legt `sync/` und `sync/photos/` an, falls noch nicht vorhanden
+    pub async fn ensure_dir(&self) {
+        let sync_base = self.dir.trim_end_matches("/photos");
+        for dir in [sync_base, self.dir.as_str()] {
+            if let Err(e) = self.backend.mkcol(dir).await {
+                log::debug!("MKCOL {} note: {:?}", dir, e);
+            }
+        }
+    }
+
+    /// Dateien mit ETag; anders als [`PhotoStorage::list`] ist ein nicht
+    /// lesbares Verzeichnis ein Fehler und keine leere Liste
+    pub async fn list_with_etags(&self) -> Result<Vec<(String, Option<String>)>, AppError> {
+        Ok(self
+            .backend
+            .list(&self.dir)
+            .await?
+            .into_iter()
+            .filter_map(|entry| match entry {
+                RemoteEntry::File { name, etag } => Some((name, etag)),
+                RemoteEntry::Folder { .. } => None,
+            })
+            .collect())
+    }
+
+    fn path_of(&self, path: &str) -> String {
+        format!("{}/{}", self.dir, path.trim_start_matches('/'))
+    }
+}
+
+impl RemotePhotoStorage {
+    pub fn from_settings(settings: &SyncSettings) -> Result<Self, AppError> {
+        Ok(Self::new(
+            ConfiguredBackend::from_settings(settings)?,
+            &settings.remote_path,
+        ))
+    }
+}
+
+impl<B: SyncBackend> PhotoStorage for RemotePhotoStorage<B> {
+    async fn read(&self, path: &str) -> Result<Vec<u8>, AppError> {
+        let data = self.backend.get(&self.path_of(path)).await?;
+        #[cfg(feature = "encryption")]
+        if let Some(key) = &self.key {
+            return key.decrypt(data);
+        }
+        #[cfg(feature = "encryption")]
+        if crate::services::photo_crypto::is_encrypted(&data) {
+            return Err(AppError::Other(format!(
+                "{} ist verschlüsselt, aber es ist kein Fotoschlüssel hinterlegt",
+                path
+            )));
+        }
+        Ok(data)
+    }
+
+    async fn write(&self, path: &str, data: Vec<u8>) -> Result<(), AppError> {
+        #[cfg(feature = "encryption")]
+        let data = match &self.key {
+            Some(key) => key.encrypt(&data)?,
+            None => data,
+        };
+        self.backend.put(&self.path_of(path), data).await
+    }
+
+    async fn write_new(&self, path: &str, data: Vec<u8>) -> Result<bool, AppError> {
+        #[cfg(feature = "encryption")]
+        let data = match &self.key {
+            Some(key) => key.encrypt(&data)?,
+            None => data,
+        };
+        // Atomar auf dem Server statt exists + write
+        self.backend.put_if_absent(&self.path_of(path), data).await
+    }
+
+    async fn exists(&self, path: &str) -> bool {
+        // Ohne Entschlüsseln, auch fremd verschlüsselte Dateien zählen
+        self.backend.get(&self.path_of(path)).await.is_ok()
+    }
+
+    async fn delete(&self, path: &str) -> Result<(), AppError> {
+        self.backend.delete(&self.path_of(path)).await
+    }
+
+    async fn list(&self) -> Result<Vec<String>, AppError> {
+        match self.backend.list(&self.dir).await {
+            Ok(entries) => Ok(entries
+                .into_iter()
+                .filter_map(|entry| match entry {
+                    RemoteEntry::File { name, .. } => Some(name),
+                    RemoteEntry::Folder { .. } => None,
+                })
+                .collect()),
+            // Abgelehnte Zugangsdaten sind kein leeres Verzeichnis
+            Err(e @ AppError::PermissionDenied(_)) => Err(e),
+            Err(e) => {
+                log::debug!("Photo directory {} not listable yet: {:?}", self.dir, e);
+                Ok(Vec::new())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::sync_backend::MemoryBackend;
+
+    #[tokio::test]
+    async fn test_local_storage_roundtrip() {
+        let root = std::env::temp_dir().join(format!("photo-storage-{}", uuid::Uuid::new_v4()));
+        let storage = LocalPhotoStorage::new(&root);
+
+        assert!(storage.list().await.unwrap().is_empty());
+        storage.write("a.jpg", b"jpeg".to_vec()).await.unwrap();
+        storage
+            .write("a_small.webp", b"webp".to_vec())
+            .await
+            .unwrap();
+
+        assert!(storage.exists("a.jpg").await);
+        assert_eq!(storage.read("a.jpg").await.unwrap(), b"jpeg".to_vec());
+        assert_eq!(storage.list().await.unwrap(), vec!["a.jpg", "a_small.webp"]);
+
+        storage.delete("a.jpg").await.unwrap();
+        storage.delete("a.jpg").await.unwrap();
+        assert!(!storage.exists("a.jpg").await);
+        assert!(matches!(
+            storage.read("a.jpg").await,
+            Err(AppError::NotFound(_))
+        ));
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_remote_storage_uses_sync_photos_dir() {
+        let memory = MemoryBackend::new();
+        let storage = RemotePhotoStorage::new(memory.clone(), "/Stalltagebuch/");
+
+        assert!(storage.list().await.unwrap().is_empty());
+        storage.ensure_dir().await;
+        storage.write("b.jpg", b"jpeg".to_vec()).await.unwrap();
+
+        assert_eq!(memory.file_paths(), vec!["Stalltagebuch/sync/photos/b.jpg"]);
+        assert_eq!(storage.list().await.unwrap(), vec!["b.jpg"]);
+        assert!(storage.exists("b.jpg").await);
+
+        storage.delete("b.jpg").await.unwrap();
+        assert!(!storage.exists("b.jpg").await);
+
+        memory.revoke_credentials();
+        assert!(matches!(
+            storage.list().await,
+            Err(AppError::PermissionDenied(_))
+        ));
+    }
+
+    #[cfg(feature = "encryption")]
+    #[tokio::test]
+    async fn test_remote_storage_encrypts_with_key() {
+        let memory = MemoryBackend::new();
+        let key = PhotoKey::generate().unwrap();
+        let storage = RemotePhotoStorage::new(memory.clone(), "/").with_key(Some(key.clone()));
+        storage.write("c.jpg", b"jpeg".to_vec()).await.unwrap();
+        assert_eq!(storage.read("c.jpg").await.unwrap(), b"jpeg".to_vec());
+
+        // Auf dem Server liegt nur Chiffretext
+        let plain = RemotePhotoStorage::new(memory.clone(), "/");
+        let stored = SyncBackend::get(&memory, "/sync/photos/c.jpg")
+            .await
+            .unwrap();
+        assert!(crate::services::photo_crypto::is_encrypted(&stored));
+        assert!(plain.exists("c.jpg").await);
+        assert!(plain.read("c.jpg").await.is_err());
+    }
+}
@@ -0,0 +1,233 @@
+//! Abgleich von Fotoverzeichnis und Datenbank
+//!
+//! `photo_service::cleanup_orphaned_photos` räumt nur Datensätze ohne
+//! Wachtel/Ereignis auf. Nach Abstürzen bleiben aber auch Dateien ohne
+//! Datensatz liegen (abgebrochene Importe, gelöschte Fotos), und umgekehrt
+//! können nur lokal vorhandene Fotos ihr Original verloren haben.
+//! [`sweep_orphan_files`] findet beides und räumt auf Wunsch auf.
+//!
+//! Betrachtet werden nur Dateien, deren Namen die App selbst vergibt
+//! (`<uuid>.jpg`, `<uuid>_<stufe>.webp`, `paste-<uuid>.tmp`); auf dem
+//! Desktop ist das Fotoverzeichnis das Arbeitsverzeichnis.
+
+use crate::error::AppError;
+use crate::models::photo::THUMBNAIL_TIERS;
+use crate::services::photo_checksum::CORRUPT_STATUS;
+use crate::services::photo_storage::{LocalPhotoStorage, PhotoStorage};
+use rusqlite::{params, Connection};
+use std::collections::HashSet;
+use std::time::Duration;
+use uuid::Uuid;
+
+/// Jüngere Dateien gehören evtl. zu einem laufenden Import
+const GRACE_PERIOD: Duration = Duration::from_secs(10 * 60);
+
+/// Ergebnis eines Abgleichs
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct SweepReport {
+    /// Dateien ohne (nicht gelöschten) Datensatz
+    pub stray_files: Vec<String>,
+    /// Nur lokal vorhandene Fotos, deren Original fehlt
+    pub missing_originals: Vec<Uuid>,
+    /// Durch das Aufräumen freigegebener Speicher
+    pub freed_bytes: u64,
+}
+
+/// Vergleicht das Fotoverzeichnis der App mit der Datenbank
+///
+/// Mit `clean` werden verwaiste Dateien gelöscht und Fotos ohne Original
+/// (die nie hochgeladen wurden, also nirgends mehr existieren) entfernt.
+pub async fn sweep_orphan_files(conn: &Connection, clean: bool) -> Result<SweepReport, AppError> {
+    sweep_orphan_files_in(conn, &LocalPhotoStorage::app_photos(), clean, GRACE_PERIOD).await
+}
+
+/// `true` für Dateinamen, die die App im Fotoverzeichnis anlegt
+fn is_managed_file(name: &str) -> bool {
+    let is_uuid = |s: &str| Uuid::parse_str(s).is_ok();
+    if let Some(uuid) = name.strip_suffix(".jpg") {
+        return is_uuid(uuid);
+    }
+    if let Some(tmp) = name
+        .strip_prefix("paste-")
+        .and_then(|n| n.strip_suffix(".tmp"))
+    {
+        return is_uuid(tmp);
+    }
+    name.strip_suffix(".webp")
+        .and_then(|stem| stem.rsplit_once('_'))
+        .is_some_and(|(uuid, tier)| is_uuid(uuid) && THUMBNAIL_TIERS.iter().any(|t| t.name == tier))
+}
+
+async fn sweep_orphan_files_in(
+    conn: &Connection,
+    storage: &LocalPhotoStorage,
+    clean: bool,
+    grace: Duration,
+) -> Result<SweepReport, AppError> {
+    let mut known = HashSet::new();
+    let mut missing = Vec::new();
+    {
+        let mut stmt = conn.prepare(
+            "SELECT uuid, COALESCE(relative_path, path), thumbnail_small_path, thumbnail_medium_path,
+                    thumbnail_path, original_evicted = 0 AND trashed_at IS NULL
+                      AND (sync_status IN ('local_only', ?1) OR sync_status IS NULL)
+             FROM photos WHERE deleted = 0",
+        )?;
+        let rows = stmt.query_map([CORRUPT_STATUS], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                [
+                    row.get::<_, Option<String>>(2)?,
+                    row.get::<_, Option<String>>(3)?,
+                    row.get::<_, Option<String>>(4)?,
+                ],
+                row.get::<_, Option<bool>>(5)?.unwrap_or(false),
+            ))
+        })?;
+        for row in rows {
+            let (uuid, original, thumbnails, local_only) = row?;
+            if local_only && !original.is_empty() && !storage.exists(&original).await {
+                if let Ok(uuid) = Uuid::parse_str(&uuid) {
+                    missing.push(uuid);
+                }
+            }
+            known.extend(THUMBNAIL_TIERS.iter().map(|t| t.file_name(&uuid)));
+            known.extend(
+                std::iter::once(original)
+                    .chain(thumbnails.into_iter().flatten())
+                    // thumbnail_path kann absolut gespeichert sein
+                    .filter_map(|p| p.rsplit('/').next().map(str::to_string)),
+            );
+        }
+    }
+
+    let mut report = SweepReport {
+        missing_originals: missing,
+        ..Default::default()
+    };
+    for name in storage.list().await? {
+        if !is_managed_file(&name) || known.contains(&name) {
+            continue;
+        }
+        let Ok(metadata) = std::fs::metadata(storage.path_of(&name)) else {
+            continue;
+        };
+        let age = metadata
+            .modified()
+            .ok()
+            .and_then(|m| m.elapsed().ok())
+            .unwrap_or_default();
+        if age < grace {
+            continue;
+        }
+        if clean {
+            storage.delete(&name).await?;
+            report.freed_bytes += metadata.len();
+        }
+        report.stray_files.push(name);
+    }
+
+    if clean {
+        for uuid in &report.missing_originals {
+            let uuid = uuid.to_string();
+            for tier in THUMBNAIL_TIERS {
+                let _ = storage.delete(&tier.file_name(&uuid)).await;
+            }
+            conn.execute(
+                "UPDATE photos SET deleted = 1 WHERE uuid = ?1",
+                params![uuid],
+            )?;
+            crate::services::operation_capture::capture_photo_delete(conn, &uuid).await?;
+        }
+    }
+
+    if !report.stray_files.is_empty() || !report.missing_originals.is_empty() {
+        log::info!(
+            "Fotoabgleich: {} verwaiste Dateien, {} Fotos ohne Original{}",
+            report.stray_files.len(),
+            report.missing_originals.len(),
+            if clean { " (bereinigt)" } else { "" }
+        );
+    }
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::schema;
+
+    #[test]
+    fn test_only_app_file_names_are_managed() {
+        let uuid = Uuid::new_v4();
+        assert!(is_managed_file(&format!("{}.jpg", uuid)));
+        assert!(is_managed_file(&format!("{}_tiny.webp", uuid)));
+        assert!(is_managed_file(&format!("paste-{}.tmp", uuid)));
+        assert!(!is_managed_file(&format!("{}_huge.webp", uuid)));
+        assert!(!is_managed_file("Cargo.toml"));
+        assert!(!is_managed_file("holiday.jpg"));
+    }
+
+    #[tokio::test]
+    async fn test_sweep_reports_and_cleans_both_directions() {
+        let conn = Connection::open_in_memory().unwrap();
+        schema::init_schema(&conn).unwrap();
+        let storage = LocalPhotoStorage::new(
+            std::env::temp_dir().join(format!("photo-sweep-{}", Uuid::new_v4())),
+        );
+
+        let kept = Uuid::new_v4();
+        let lost = Uuid::new_v4();
+        let remote_only = Uuid::new_v4();
+        for (uuid, status) in [
+            (kept, "local_only"),
+            (lost, "local_only"),
+            (remote_only, "synced"),
+        ] {
+            conn.execute(
+                "INSERT INTO photos (uuid, path, relative_path, thumbnail_small_path, sync_status)
+                 VALUES (?1, '', ?1 || '.jpg', ?1 || '_small.webp', ?2)",
+                params![uuid.to_string(), status],
+            )
+            .unwrap();
+        }
+        let stray = format!("{}.jpg", Uuid::new_v4());
+        for name in [
+            format!("{}.jpg", kept),
+            format!("{}_small.webp", kept),
+            format!("{}_tiny.webp", kept),
+            stray.clone(),
+            "notes.txt".to_string(),
+        ] {
+            storage.write(&name, b"data".to_vec()).await.unwrap();
+        }
+
+        // Frische Dateien bleiben unangetastet (laufender Import)
+        let report = sweep_orphan_files_in(&conn, &storage, false, GRACE_PERIOD)
+            .await
+            .unwrap();
+        assert!(report.stray_files.is_empty());
+        assert_eq!(report.missing_originals, vec![lost]);
+
+        let report = sweep_orphan_files_in(&conn, &storage, true, Duration::ZERO)
+            .await
+            .unwrap();
+        assert_eq!(report.stray_files, vec![stray.clone()]);
+        assert_eq!(report.freed_bytes, 4);
+        assert!(!storage.exists(&stray).await);
+        assert!(storage.exists("notes.txt").await);
+        assert!(storage.exists(&format!("{}_tiny.webp", kept)).await);
+
+        let deleted: bool = conn
+            .query_row(
+                "SELECT deleted FROM photos WHERE uuid = ?1",
+                params![lost.to_string()],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert!(deleted);
+
+        std::fs::remove_dir_all(storage.path_of("")).unwrap();
+    }
+}
@@ -1,132 +1,31 @@
 use crate::error::AppError;
 use crate::models::Quail;
+use crate::services::{pedigree, repository};
 use rusqlite::Connection;
 use uuid::Uuid;
 
 /// Creates a new quail profile in the database
 pub async fn create_profile(conn: &Connection, quail: &Quail) -> Result<Uuid, AppError> {
-    // Validation
-    quail.validate()?;
-
-    conn.execute(
-        "INSERT INTO quails (uuid, name, gender, ring_color, profile_photo)
-         VALUES (?1, ?2, ?3, ?4, ?5)",
-        (
-            quail.uuid.to_string(),
-            &quail.name,
-            quail.gender.as_str(),
-            &quail.ring_color.as_ref().map(|c| c.as_str().to_string()),
-            quail.profile_photo.as_ref().map(|u| u.to_string()),
-        ),
-    )?;
-
-    // Capture CRDT operation
-    crate::services::operation_capture::capture_quail_create(
-        conn,
-        &quail.uuid.to_string(),
-        &quail.name,
-        quail.gender.as_str(),
-        quail.ring_color.as_ref().map(|c| c.as_str()),
-        quail
-            .profile_photo
-            .as_ref()
-            .map(|u| u.to_string())
-            .as_deref(),
-    )
-    .await?;
-
+    pedigree::check_parents(conn, quail)?;
+    repository::save_with_ops(conn, quail).await?;
     Ok(quail.uuid)
 }
 
 /// Loads a quail profile by UUID
 pub fn get_profile(conn: &Connection, uuid: &Uuid) -> Result<Quail, AppError> {
-    let mut stmt = conn.prepare(
-        "SELECT uuid, name, gender, ring_color, profile_photo
-         FROM quails WHERE uuid = ?1",
-    )?;
-
-    let quail = stmt
-        .query_row([uuid.to_string()], |row| Quail::try_from(row))
-        .map_err(|e| match e {
-            rusqlite::Error::QueryReturnedNoRows => AppError::NotFound("Quail profile".to_string()),
-            _ => AppError::Database(e),
-        })?;
-
-    Ok(quail)
+    repository::require(conn, uuid)
 }
 
 /// Updates an existing quail profile
 pub async fn update_profile(conn: &Connection, quail: &Quail) -> Result<(), AppError> {
-    // Validation
-    quail.validate()?;
-
-    let rows_affected = conn.execute(
-        "UPDATE quails 
-         SET name = ?1, gender = ?2, ring_color = ?3, profile_photo = ?4
-         WHERE uuid = ?5",
-        (
-            &quail.name,
-            quail.gender.as_str(),
-            &quail.ring_color.as_ref().map(|c| c.as_str().to_string()),
-            quail.profile_photo.as_ref().map(|u| u.to_string()),
-            quail.uuid.to_string(),
-        ),
-    )?;
-
-    if rows_affected == 0 {
-        return Err(AppError::NotFound("Quail profile".to_string()));
-    }
-
-    // Capture CRDT operations for each field
-    let quail_id = quail.uuid.to_string();
-    crate::services::operation_capture::capture_quail_update(
-        conn,
-        &quail_id,
-        "name",
-        serde_json::Value::String(quail.name.clone()),
-    )
-    .await?;
-    crate::services::operation_capture::capture_quail_update(
-        conn,
-        &quail_id,
-        "gender",
-        serde_json::Value::String(quail.gender.as_str().to_string()),
-    )
-    .await?;
-    if let Some(color) = &quail.ring_color {
-        crate::services::operation_capture::capture_quail_update(
-            conn,
-            &quail_id,
-            "ring_color",
-            serde_json::Value::String(color.as_str().to_string()),
-        )
-        .await?;
-    }
-    if let Some(photo) = &quail.profile_photo {
-        crate::services::operation_capture::capture_quail_update(
-            conn,
-            &quail_id,
-            "profile_photo",
-            serde_json::Value::String(photo.to_string()),
-        )
-        .await?;
-    }
-
-    Ok(())
+    repository::require::<Quail>(conn, &quail.uuid)?;
+    pedigree::check_parents(conn, quail)?;
+    repository::save_with_ops(conn, quail).await
 }
 
-/// Deletes a quail profile (CASCADE also deletes individual egg entries)
+/// Deletes a quail profile (CASCADE also deletes its events)
 pub async fn delete_profile(conn: &Connection, uuid: &Uuid) -> Result<(), AppError> {
-    let rows_affected = conn.execute("DELETE FROM quails WHERE uuid = ?1", [uuid.to_string()])?;
-
-    if rows_affected == 0 {
-        return Err(AppError::NotFound("Quail profile".to_string()));
-    }
-
-    // Capture CRDT deletion
-    crate::services::operation_capture::capture_quail_delete(conn, &uuid.to_string()).await?;
-
-    Ok(())
+    repository::delete_with_ops::<Quail>(conn, uuid).await
 }
 
 /// Lists all quail profiles, optionally filtered by name (default: only living ones)
@@ -142,31 +41,12 @@ pub fn list_profiles_with_status(
     name_filter: Option<&str>,
     _only_alive: bool,
 ) -> Result<Vec<Quail>, AppError> {
-    let (query, params): (&str, Vec<&str>) = match name_filter {
-        Some(filter) if !filter.trim().is_empty() => (
-            "SELECT uuid, name, gender, ring_color, profile_photo
-             FROM quails 
-             WHERE name LIKE '%' || ?1 || '%'
-             ORDER BY name",
-            vec![filter],
-        ),
-        _ => (
-            "SELECT uuid, name, gender, ring_color, profile_photo
-             FROM quails 
-             ORDER BY name",
-            vec![],
-        ),
-    };
-
-    let mut stmt = conn.prepare(query)?;
-
-    let quails = stmt
-        .query_map(rusqlite::params_from_iter(params), |row| {
-            Quail::try_from(row)
-        })?
-        .collect::<Result<Vec<_>, _>>()?;
-
-    Ok(quails)
+    match name_filter {
+        Some(filter) if !filter.trim().is_empty() => {
+            repository::list(conn, "name LIKE '%' || ?1 || '%'", [filter], "name")
+        }
+        _ => repository::list(conn, "1", [], "name"),
+    }
 }
 
 /// Counts the total number of profiles
@@ -221,6 +101,18 @@ mod tests {
         assert_eq!(loaded.gender, crate::models::Gender::Female);
     }
 
+    #[tokio::test]
+    async fn test_existing_v4_ids_still_load() {
+        let conn = setup_test_db();
+        let mut quail = Quail::new("Altbestand".to_string());
+        quail.uuid = uuid::Uuid::new_v4();
+
+        let uuid = create_profile(&conn, &quail).await.unwrap();
+        let loaded = get_profile(&conn, &uuid).unwrap();
+        assert_eq!(loaded.uuid.get_version_num(), 4);
+        assert_eq!(loaded.name, "Altbestand");
+    }
+
     #[tokio::test]
     async fn test_update_profile() {
         let conn = setup_test_db();
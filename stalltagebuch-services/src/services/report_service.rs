@@ -0,0 +1,673 @@
+//! Bestandsbericht als PDF
+//!
+//! Fasst einen Zeitraum auf wenigen Seiten zusammen: Bestand nach Status und
+//! Geschlecht, jede Wachtel mit ihrem Status am Ende des Zeitraums, die
+//! Eier-Statistik mit Balkendiagramm und auf Wunsch die Profilfotos. Die
+//! Datei landet wie die anderen Exporte im Export-Verzeichnis.
+
+use crate::error::AppError;
+use crate::models::photo::PhotoSize;
+use crate::models::{EventType, Gender, Quail};
+use crate::pdf::{self, Color, ImageId, Page, PdfDocument, PAGE_HEIGHT, PAGE_WIDTH};
+use crate::services::{analytics_service, export_import_service, photo_assets};
+use chrono::{Datelike, Local, NaiveDate, Utc};
+use rusqlite::{params, Connection, OptionalExtension};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+const MARGIN: f32 = 50.0;
+const CONTENT_WIDTH: f32 = PAGE_WIDTH - 2.0 * MARGIN;
+/// Bis zu so vielen Tagen ein Balken pro Tag, sonst pro Monat
+const DAILY_CHART_MAX_DAYS: i64 = 62;
+/// Kantenlänge der Profilfotos im Bericht (Pixel)
+const PHOTO_PIXELS: u32 = 160;
+const PHOTO_SIZE: f32 = 36.0;
+
+const ACCENT: Color = Color(0.0, 0.4, 0.8);
+const EGG_COLOR: Color = Color(1.0, 0.55, 0.0);
+
+/// Zeitraum und Umfang des Berichts
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReportOptions {
+    /// `None`: ab dem ersten Eintrag
+    pub start: Option<NaiveDate>,
+    pub end: NaiveDate,
+    pub include_photos: bool,
+}
+
+/// Zeile der Wachtel-Tabelle
+struct QuailRow {
+    quail: Quail,
+    /// Letztes Ereignis bis zum Ende des Zeitraums
+    status: Option<EventType>,
+    events_in_range: i64,
+}
+
+fn format_date(date: NaiveDate) -> String {
+    date.format("%d.%m.%Y").to_string()
+}
+
+fn status_at(conn: &Connection, quail: &Quail, end: &str) -> Result<Option<EventType>, AppError> {
+    let event_type: Option<String> = conn
+        .query_row(
+            "SELECT event_type FROM quail_events
+             WHERE quail_id = ?1 AND deleted = 0 AND event_date <= ?2
+             ORDER BY event_date DESC LIMIT 1",
+            params![quail.uuid.to_string(), end],
+            |row| row.get(0),
+        )
+        .optional()?;
+    Ok(event_type.map(|t| EventType::from_str(&t)))
+}
+
+fn load_rows(conn: &Connection, start: &str, end: &str) -> Result<Vec<QuailRow>, AppError> {
+    let quails = crate::services::list_profiles_with_status(conn, None, false)?;
+    let mut rows = Vec::with_capacity(quails.len());
+    for quail in quails {
+        let status = status_at(conn, &quail, end)?;
+        let events_in_range: i64 = conn.query_row(
+            "SELECT COUNT(*) FROM quail_events
+             WHERE quail_id = ?1 AND deleted = 0 AND event_date BETWEEN ?2 AND ?3",
+            params![quail.uuid.to_string(), start, end],
+            |row| row.get(0),
+        )?;
+        rows.push(QuailRow {
+            quail,
+            status,
+            events_in_range,
+        });
+    }
+    Ok(rows)
+}
+
+/// Profilfoto als kleines JPEG; `None`, wenn keins da ist oder es sich
+/// nicht lesen lässt
+fn load_photo(conn: &Connection, doc: &mut PdfDocument, quail: &Quail) -> Option<ImageId> {
+    let photo_uuid = quail.profile_photo?;
+    let bytes = match photo_assets::read_photo_asset(conn, &photo_uuid, PhotoSize::SMALL) {
+        Ok(Some((bytes, _))) => bytes,
+        Ok(None) => return None,
+        Err(e) => {
+            log::warn!("Bericht: Foto {} nicht lesbar: {}", photo_uuid, e);
+            return None;
+        }
+    };
+    let image = match image::load_from_memory(&bytes) {
+        Ok(image) => image.thumbnail(PHOTO_PIXELS, PHOTO_PIXELS).to_rgb8(),
+        Err(e) => {
+            log::warn!("Bericht: Foto {} nicht lesbar: {}", photo_uuid, e);
+            return None;
+        }
+    };
+    let mut jpeg = Vec::new();
+    if let Err(e) =
+        image::codecs::jpeg::JpegEncoder::new_with_quality(&mut jpeg, 80).encode_image(&image)
+    {
+        log::warn!("Bericht: Foto {} nicht kodierbar: {}", photo_uuid, e);
+        return None;
+    }
+    Some(doc.add_jpeg(image.width(), image.height(), jpeg))
+}
+
+/// Schreibt Seite für Seite von oben nach unten
+struct Layout {
+    doc: PdfDocument,
+    page: Page,
+    y: f32,
+}
+
+impl Layout {
+    fn new() -> Self {
+        Self {
+            doc: PdfDocument::new(),
+            page: Page::new(),
+            y: PAGE_HEIGHT - MARGIN,
+        }
+    }
+
+    fn next_page(&mut self) {
+        let page = std::mem::take(&mut self.page);
+        self.push_page(page);
+        self.y = PAGE_HEIGHT - MARGIN;
+    }
+
+    fn push_page(&mut self, mut page: Page) {
+        let number = format!("Seite {}", self.doc.page_count() + 1);
+        page.text(
+            PAGE_WIDTH - MARGIN - pdf::text_width(&number, 9.0),
+            MARGIN / 2.0,
+            9.0,
+            false,
+            Color::GRAY,
+            &number,
+        );
+        self.doc.add_page(page);
+    }
+
+    /// Neue Seite, wenn weniger als `height` Platz ist
+    fn reserve(&mut self, height: f32) {
+        if self.y - height < MARGIN {
+            self.next_page();
+        }
+    }
+
+    fn heading(&mut self, text: &str) {
+        self.reserve(60.0);
+        self.y -= 24.0;
+        self.page.text(MARGIN, self.y, 15.0, true, ACCENT, text);
+        self.y -= 6.0;
+        self.page
+            .line((MARGIN, self.y), (PAGE_WIDTH - MARGIN, self.y), 0.8, ACCENT);
+        self.y -= 6.0;
+    }
+
+    fn key_value(&mut self, key: &str, value: &str) {
+        self.reserve(16.0);
+        self.y -= 16.0;
+        self.page
+            .text(MARGIN, self.y, 11.0, false, Color::GRAY, key);
+        self.page
+            .text(MARGIN + 200.0, self.y, 11.0, true, Color::BLACK, value);
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        let page = std::mem::take(&mut self.page);
+        self.push_page(page);
+        self.doc.finish()
+    }
+}
+
+/// Kürzt `text` auf `width` Punkte
+fn fit(text: &str, size: f32, width: f32) -> String {
+    if pdf::text_width(text, size) <= width {
+        return text.to_string();
+    }
+    let max_chars = (width / (size * 0.5)) as usize;
+    let mut short: String = text.chars().take(max_chars.saturating_sub(1)).collect();
+    short.push('…');
+    short
+}
+
+fn flock_overview(layout: &mut Layout, rows: &[QuailRow]) {
+    layout.heading("Bestand");
+    layout.key_value("Wachteln", &rows.len().to_string());
+
+    let mut by_status: BTreeMap<&str, usize> = BTreeMap::new();
+    let mut by_gender: BTreeMap<&str, usize> = BTreeMap::new();
+    for row in rows {
+        let status = row
+            .status
+            .as_ref()
+            .map_or("Ohne Ereignis", |s| s.display_name());
+        *by_status.entry(status).or_default() += 1;
+        *by_gender
+            .entry(row.quail.gender.display_name())
+            .or_default() += 1;
+    }
+    for gender in [Gender::Female, Gender::Male, Gender::Unknown] {
+        if let Some(count) = by_gender.get(gender.display_name()) {
+            layout.key_value(gender.display_name(), &count.to_string());
+        }
+    }
+    layout.y -= 8.0;
+    for (status, count) in by_status {
+        layout.key_value(status, &count.to_string());
+    }
+}
+
+fn quail_table(conn: &Connection, layout: &mut Layout, rows: &[QuailRow], include_photos: bool) {
+    layout.heading("Wachteln");
+    if rows.is_empty() {
+        layout.key_value("Keine Wachteln erfasst", "");
+        return;
+    }
+
+    let name_x = if include_photos {
+        MARGIN + PHOTO_SIZE + 8.0
+    } else {
+        MARGIN
+    };
+    let columns = [
+        (name_x, "Name"),
+        (MARGIN + 200.0, "Geschlecht"),
+        (MARGIN + 280.0, "Ring"),
+        (MARGIN + 350.0, "Status"),
+    ];
+    let row_height = if include_photos {
+        PHOTO_SIZE + 6.0
+    } else {
+        18.0
+    };
+
+    let header = |layout: &mut Layout| {
+        layout.y -= 16.0;
+        for (x, title) in columns {
+            layout
+                .page
+                .text(x, layout.y, 10.0, true, Color::GRAY, title);
+        }
+        let events = "Ereignisse";
+        layout.page.text(
+            PAGE_WIDTH - MARGIN - pdf::text_width(events, 10.0),
+            layout.y,
+            10.0,
+            true,
+            Color::GRAY,
+            events,
+        );
+        layout.y -= 4.0;
+    };
+    header(layout);
+
+    for (i, row) in rows.iter().enumerate() {
+        if layout.y - row_height < MARGIN {
+            layout.next_page();
+            header(layout);
+        }
+        layout.y -= row_height;
+        if i % 2 == 0 {
+            layout.page.fill_rect(
+                MARGIN - 4.0,
+                layout.y - 4.0,
+                CONTENT_WIDTH + 8.0,
+                row_height,
+                Color(0.96, 0.96, 0.96),
+            );
+        }
+        let text_y = layout.y + (row_height - 10.0) / 2.0 - 1.0;
+
+        if include_photos {
+            if let Some(image) = load_photo(conn, &mut layout.doc, &row.quail) {
+                layout
+                    .page
+                    .image(image, MARGIN, layout.y - 1.0, PHOTO_SIZE, PHOTO_SIZE);
+            }
+        }
+        let name = if row.quail.name.trim().is_empty() {
+            "–".to_string()
+        } else {
+            fit(&row.quail.name, 10.0, MARGIN + 195.0 - name_x)
+        };
+        let ring = row
+            .quail
+            .ring_color
+            .as_ref()
+            .map_or("–", |c| c.display_name());
+        let status = row.status.as_ref().map_or("–", |s| s.display_name());
+        let cells = [name.as_str(), row.quail.gender.display_name(), ring, status];
+        for ((x, _), text) in columns.iter().zip(cells) {
+            layout.page.text(
+                *x,
+                text_y,
+                10.0,
+                false,
+                Color::BLACK,
+                &fit(text, 10.0, 110.0),
+            );
+        }
+        let events = row.events_in_range.to_string();
+        layout.page.text(
+            PAGE_WIDTH - MARGIN - pdf::text_width(&events, 10.0),
+            text_y,
+            10.0,
+            false,
+            Color::BLACK,
+            &events,
+        );
+    }
+}
+
+/// Eier pro Tag (kurze Zeiträume) oder pro Monat, lückenlos
+fn egg_buckets(
+    records: &[(NaiveDate, i32)],
+    start: NaiveDate,
+    end: NaiveDate,
+) -> Vec<(String, i32)> {
+    let daily = (end - start).num_days() <= DAILY_CHART_MAX_DAYS;
+    let key = |date: NaiveDate| {
+        if daily {
+            date
+        } else {
+            date.with_day(1).unwrap_or(date)
+        }
+    };
+
+    let mut buckets: BTreeMap<NaiveDate, i32> = BTreeMap::new();
+    let mut date = key(start);
+    while date <= end {
+        buckets.insert(date, 0);
+        date = if daily {
+            date + chrono::Duration::days(1)
+        } else {
+            date.checked_add_months(chrono::Months::new(1))
+                .unwrap_or(NaiveDate::MAX)
+        };
+    }
+    for (date, eggs) in records {
+        *buckets.entry(key(*date)).or_default() += eggs;
+    }
+
+    buckets
+        .into_iter()
+        .map(|(date, eggs)| {
+            let label = if daily {
+                date.format("%d.%m.").to_string()
+            } else {
+                date.format("%m/%Y").to_string()
+            };
+            (label, eggs)
+        })
+        .collect()
+}
+
+fn egg_chart(layout: &mut Layout, buckets: &[(String, i32)]) {
+    const HEIGHT: f32 = 140.0;
+    layout.reserve(HEIGHT + 40.0);
+    layout.y -= 24.0;
+
+    let max = buckets
+        .iter()
+        .map(|(_, eggs)| *eggs)
+        .max()
+        .unwrap_or(0)
+        .max(1);
+    let top = layout.y;
+    let bottom = top - HEIGHT;
+    let left = MARGIN + 24.0;
+    let width = PAGE_WIDTH - MARGIN - left;
+
+    layout
+        .page
+        .text(MARGIN, top - 8.0, 9.0, false, Color::GRAY, &max.to_string());
+    layout
+        .page
+        .text(MARGIN, bottom, 9.0, false, Color::GRAY, "0");
+    layout.page.line(
+        (left, top),
+        (PAGE_WIDTH - MARGIN, top),
+        0.3,
+        Color::LIGHT_GRAY,
+    );
+    layout.page.line(
+        (left, bottom),
+        (PAGE_WIDTH - MARGIN, bottom),
+        0.8,
+        Color::GRAY,
+    );
+
+    let slot = width / buckets.len().max(1) as f32;
+    let bar = (slot * 0.75).max(0.5);
+    for (i, (_, eggs)) in buckets.iter().enumerate() {
+        let height = HEIGHT * *eggs as f32 / max as f32;
+        if height > 0.0 {
+            let x = left + i as f32 * slot + (slot - bar) / 2.0;
+            layout.page.fill_rect(x, bottom, bar, height, EGG_COLOR);
+        }
+    }
+
+    if let (Some((first, _)), Some((last, _))) = (buckets.first(), buckets.last()) {
+        layout
+            .page
+            .text(left, bottom - 12.0, 9.0, false, Color::GRAY, first);
+        layout.page.text(
+            PAGE_WIDTH - MARGIN - pdf::text_width(last, 9.0),
+            bottom - 12.0,
+            9.0,
+            false,
+            Color::GRAY,
+            last,
+        );
+    }
+    layout.y = bottom - 16.0;
+}
+
+fn egg_section(
+    conn: &Connection,
+    layout: &mut Layout,
+    start: NaiveDate,
+    end: NaiveDate,
+) -> Result<(), AppError> {
+    let (start_str, end_str) = (start.to_string(), end.to_string());
+    layout.heading("Eier");
+
+    let stats = analytics_service::calculate_statistics(conn, Some(&start_str), Some(&end_str))?;
+    if stats.total_records == 0 {
+        layout.key_value("Keine Eier-Einträge im Zeitraum", "");
+        return Ok(());
+    }
+    layout.key_value("Eier gesamt", &stats.total_eggs.to_string());
+    layout.key_value("Einträge", &stats.total_records.to_string());
+    layout.key_value(
+        "Durchschnitt pro Tag",
+        &format!("{:.1}", stats.daily_average),
+    );
+    layout.key_value(
+        "Durchschnitt pro Woche",
+        &format!("{:.1}", stats.weekly_average),
+    );
+    layout.key_value(
+        "Durchschnitt pro Monat",
+        &format!("{:.1}", stats.monthly_average),
+    );
+    layout.key_value(
+        "Minimum / Maximum",
+        &format!("{} / {}", stats.min_eggs, stats.max_eggs),
+    );
+
+    let records: Vec<(NaiveDate, i32)> =
+        crate::services::list_egg_records(conn, Some(&start_str), Some(&end_str))?
+            .into_iter()
+            .map(|r| (r.record_date, r.total_eggs))
+            .collect();
+    egg_chart(layout, &egg_buckets(&records, start, end));
+    Ok(())
+}
+
+/// Erster Tag mit Daten (Eier oder Ereignisse), sonst `end`
+fn first_day(conn: &Connection, end: NaiveDate) -> Result<NaiveDate, AppError> {
+    let first: Option<String> = conn.query_row(
+        "SELECT MIN(d) FROM (
+             SELECT MIN(record_date) AS d FROM egg_records WHERE deleted = 0
+             UNION ALL
+             SELECT MIN(event_date) FROM quail_events WHERE deleted = 0
+         )",
+        [],
+        |row| row.get(0),
+    )?;
+    Ok(first
+        .and_then(|d| NaiveDate::parse_from_str(&d, "%Y-%m-%d").ok())
+        .filter(|d| *d <= end)
+        .unwrap_or(end))
+}
+
+/// Erzeugt den Bericht als PDF-Bytes
+pub fn build_flock_report(conn: &Connection, options: &ReportOptions) -> Result<Vec<u8>, AppError> {
+    let start = match options.start {
+        Some(start) => start,
+        None => first_day(conn, options.end)?,
+    };
+    if start > options.end {
+        return Err(AppError::Validation(
+            "Das Enddatum liegt vor dem Startdatum".to_string(),
+        ));
+    }
+    let rows = load_rows(conn, &start.to_string(), &options.end.to_string())?;
+
+    let mut layout = Layout::new();
+    layout.y -= 22.0;
+    layout.page.text(
+        MARGIN,
+        layout.y,
+        22.0,
+        true,
+        Color::BLACK,
+        "Stalltagebuch – Bestandsbericht",
+    );
+    layout.y -= 18.0;
+    layout.page.text(
+        MARGIN,
+        layout.y,
+        11.0,
+        false,
+        Color::GRAY,
+        &format!(
+            "Zeitraum {} – {} · erstellt am {}",
+            format_date(start),
+            format_date(options.end),
+            format_date(Local::now().date_naive())
+        ),
+    );
+    layout.y -= 8.0;
+
+    flock_overview(&mut layout, &rows);
+    egg_section(conn, &mut layout, start, options.end)?;
+    quail_table(conn, &mut layout, &rows, options.include_photos);
+
+    Ok(layout.finish())
+}
+
+/// Schreibt [`build_flock_report`] ins Export-Verzeichnis und gibt den Pfad zurück
+pub fn export_flock_report(
+    conn: &Connection,
+    options: &ReportOptions,
+) -> Result<PathBuf, AppError> {
+    let pdf = build_flock_report(conn, options)?;
+
+    let base_dir = export_import_service::get_export_base_dir();
+    std::fs::create_dir_all(&base_dir).map_err(|e| {
+        AppError::Other(format!(
+            "Fehler beim Erstellen des Export-Verzeichnisses: {}",
+            e
+        ))
+    })?;
+    let timestamp = Utc::now().format("%Y%m%d-%H%M%S").to_string();
+    let path = base_dir.join(format!("stalltagebuch-bericht-{}.pdf", timestamp));
+    std::fs::write(&path, pdf)
+        .map_err(|e| AppError::Other(format!("Fehler beim Schreiben des Berichts: {}", e)))?;
+
+    log::info!("Bestandsbericht gespeichert: {}", path.display());
+    Ok(path)
+}
+
+/// [`export_flock_report`] im Hintergrund (für die Statistik-Ansicht)
+pub async fn export_flock_report_async(options: ReportOptions) -> Result<PathBuf, AppError> {
+    crate::database::with_db_async(move |conn| export_flock_report(conn, &options)).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::schema;
+    use crate::models::EggRecord;
+    use flate2::read::ZlibDecoder;
+    use std::io::Read;
+
+    fn day(d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, 3, d).unwrap()
+    }
+
+    /// Text aller Seiten (entpackte Content-Streams)
+    fn page_text(pdf: &[u8]) -> String {
+        let mut text = String::new();
+        let mut rest = pdf;
+        while let Some(start) = rest.windows(7).position(|w| w == b"stream\n") {
+            rest = &rest[start + 7..];
+            let end = rest.windows(10).position(|w| w == b"\nendstream").unwrap();
+            let mut inflated = String::new();
+            if ZlibDecoder::new(&rest[..end])
+                .read_to_string(&mut inflated)
+                .is_ok()
+            {
+                text.push_str(&inflated);
+            }
+            rest = &rest[end + 10..];
+        }
+        text
+    }
+
+    #[tokio::test]
+    async fn test_report_contains_flock_and_eggs() {
+        let conn = Connection::open_in_memory().unwrap();
+        schema::init_schema(&conn).unwrap();
+
+        let greta = Quail::new("Greta".to_string());
+        crate::services::create_profile(&conn, &greta)
+            .await
+            .unwrap();
+        crate::services::event_service::create_event(
+            &conn,
+            greta.uuid,
+            EventType::Sick,
+            day(2),
+            None,
+        )
+        .await
+        .unwrap();
+        // Nach dem Zeitraum: zählt nicht
+        crate::services::event_service::create_event(
+            &conn,
+            greta.uuid,
+            EventType::Died,
+            day(20),
+            None,
+        )
+        .await
+        .unwrap();
+        for d in 1..=5 {
+            crate::services::add_egg_record(&conn, &EggRecord::new(day(d), d as i32))
+                .await
+                .unwrap();
+        }
+
+        let options = ReportOptions {
+            start: Some(day(1)),
+            end: day(10),
+            include_photos: true,
+        };
+        let pdf = build_flock_report(&conn, &options).unwrap();
+        assert!(pdf.starts_with(b"%PDF-1.4"));
+
+        let text = page_text(&pdf);
+        assert!(text.contains("(Greta)"));
+        assert!(text.contains("(Krank)"));
+        assert!(!text.contains("(Gestorben)"));
+        // 1 + 2 + 3 + 4 + 5 Eier
+        assert!(text.contains("(15)"));
+        assert!(text.contains("(Seite 1)"));
+
+        let invalid = ReportOptions {
+            start: Some(day(11)),
+            ..options
+        };
+        assert!(matches!(
+            build_flock_report(&conn, &invalid),
+            Err(AppError::Validation(_))
+        ));
+    }
+
+    #[test]
+    fn test_egg_buckets_daily_and_monthly() {
+        let records = [(day(2), 3), (day(4), 5)];
+        let daily = egg_buckets(&records, day(1), day(4));
+        assert_eq!(
+            daily,
+            vec![
+                ("01.03.".to_string(), 0),
+                ("02.03.".to_string(), 3),
+                ("03.03.".to_string(), 0),
+                ("04.03.".to_string(), 5),
+            ]
+        );
+
+        let start = NaiveDate::from_ymd_opt(2025, 1, 15).unwrap();
+        let monthly = egg_buckets(&records, start, day(31));
+        assert_eq!(
+            monthly,
+            vec![
+                ("01/2025".to_string(), 0),
+                ("02/2025".to_string(), 0),
+                ("03/2025".to_string(), 8),
+            ]
+        );
+    }
+}
@@ -0,0 +1,262 @@
+//! Gemeinsame Datenbankzugriffe für synchronisierte Entitäten
+//!
+//! Wachteln, Ereignisse und Eier-Einträge werden gleich gespeichert: Zeile
+//! schreiben, geänderte Felder als CRDT-Operationen erfassen, beim Löschen
+//! einen Tombstone hinterlassen. Die Tabellen beschreibt [`Entity`]; hier
+//! stehen die daraus gebauten Abfragen. Fotos gehen eigene Wege (Dateien,
+//! Vorschaubilder, Papierkorb), siehe `photo_service`.
+
+use crate::error::AppError;
+use crate::models::Entity;
+use crate::services::operation_capture;
+use rusqlite::types::Value as SqlValue;
+use rusqlite::{Connection, OptionalExtension, Params};
+use uuid::Uuid;
+
+fn select_sql<E: Entity>() -> String {
+    format!("SELECT uuid, {} FROM {}", E::COLUMNS.join(", "), E::TABLE)
+}
+
+fn json_to_sql(value: serde_json::Value) -> SqlValue {
+    match value {
+        serde_json::Value::Null => SqlValue::Null,
+        serde_json::Value::Bool(b) => SqlValue::Integer(b as i64),
+        serde_json::Value::Number(n) => match n.as_i64() {
+            Some(i) => SqlValue::Integer(i),
+            None => SqlValue::Real(n.as_f64().unwrap_or_default()),
+        },
+        serde_json::Value::String(s) => SqlValue::Text(s),
+        other => SqlValue::Text(other.to_string()),
+    }
+}
+
+/// Lädt eine Entität (auch per Sync gelöschte), `None` wenn es sie nicht gibt
+pub fn get<E: Entity>(conn: &Connection, uuid: &Uuid) -> Result<Option<E>, AppError> {
+    Ok(conn
+        .query_row(
+            &format!("{} WHERE uuid = ?1", select_sql::<E>()),
+            [uuid.to_string()],
+            |row| E::from_row(row),
+        )
+        .optional()?)
+}
+
+/// Wie [`get`], aber `AppError::NotFound`, wenn es sie nicht gibt
+pub fn require<E: Entity>(conn: &Connection, uuid: &Uuid) -> Result<E, AppError> {
+    get(conn, uuid)?.ok_or_else(|| AppError::NotFound(E::NAME.to_string()))
+}
+
+/// Nicht gelöschte Entitäten, die `filter` erfüllen (SQL-Bedingung, z.B.
+/// `"quail_id = ?1"`, `"1"` für alle), sortiert nach `order_by`
+pub fn list<E: Entity>(
+    conn: &Connection,
+    filter: &str,
+    params: impl Params,
+    order_by: &str,
+) -> Result<Vec<E>, AppError> {
+    let mut stmt = conn.prepare(&format!(
+        "{} WHERE deleted = 0 AND ({}) ORDER BY {}",
+        select_sql::<E>(),
+        filter,
+        order_by
+    ))?;
+    let rows = stmt.query_map(params, |row| E::from_row(row))?;
+    Ok(rows.collect::<Result<Vec<_>, _>>()?)
+}
+
+/// Erste nicht gelöschte Entität nach [`list`]
+pub fn find_first<E: Entity>(
+    conn: &Connection,
+    filter: &str,
+    params: impl Params,
+    order_by: &str,
+) -> Result<Option<E>, AppError> {
+    Ok(conn
+        .query_row(
+            &format!(
+                "{} WHERE deleted = 0 AND ({}) ORDER BY {} LIMIT 1",
+                select_sql::<E>(),
+                filter,
+                order_by
+            ),
+            params,
+            |row| E::from_row(row),
+        )
+        .optional()?)
+}
+
+/// Legt die Entität an oder schreibt ihre Änderungen und erfasst sie
+///
+/// Der Merge entscheidet pro Zeile (ein HLC je Eintrag), daher werden bei
+/// jeder Änderung alle Felder gesendet, geleerte als `null`; neue Einträge
+/// nur die gesetzten. Ohne Änderung passiert nichts.
+pub async fn save_with_ops<E: Entity>(conn: &Connection, entity: &E) -> Result<(), AppError> {
+    entity.validate()?;
+
+    let uuid = entity.uuid();
+    let values = entity.values();
+    let changes: Vec<(&str, serde_json::Value)> = match get::<E>(conn, &uuid)? {
+        None => {
+            let placeholders: Vec<String> = (0..=E::COLUMNS.len())
+                .map(|i| format!("?{}", i + 1))
+                .collect();
+            let mut params = vec![SqlValue::Text(uuid.to_string())];
+            params.extend(values.iter().cloned().map(json_to_sql));
+            conn.execute(
+                &format!(
+                    "INSERT INTO {} (uuid, {}) VALUES ({})",
+                    E::TABLE,
+                    E::COLUMNS.join(", "),
+                    placeholders.join(", ")
+                ),
+                rusqlite::params_from_iter(params),
+            )?;
+            E::COLUMNS
+                .iter()
+                .copied()
+                .zip(values)
+                .filter(|(_, value)| !value.is_null())
+                .collect()
+        }
+        Some(current) => {
+            if current.values() == values {
+                return Ok(());
+            }
+            let assignments: Vec<String> = E::COLUMNS
+                .iter()
+                .enumerate()
+                .map(|(i, column)| format!("{} = ?{}", column, i + 2))
+                .collect();
+            let mut params = vec![SqlValue::Text(uuid.to_string())];
+            params.extend(values.iter().cloned().map(json_to_sql));
+            conn.execute(
+                &format!(
+                    "UPDATE {} SET {} WHERE uuid = ?1",
+                    E::TABLE,
+                    assignments.join(", ")
+                ),
+                rusqlite::params_from_iter(params),
+            )?;
+            E::COLUMNS.iter().copied().zip(values).collect()
+        }
+    };
+
+    operation_capture::capture_changes(conn, E::ENTITY_TYPE, &uuid.to_string(), changes).await
+}
+
+/// Löscht die Zeile und erfasst den Tombstone
+///
+/// Abhängige Zeilen folgen den Fremdschlüsseln (`ON DELETE`).
+pub async fn delete_with_ops<E: Entity>(conn: &Connection, uuid: &Uuid) -> Result<(), AppError> {
+    let rows_affected = conn.execute(
+        &format!("DELETE FROM {} WHERE uuid = ?1", E::TABLE),
+        [uuid.to_string()],
+    )?;
+    if rows_affected == 0 {
+        return Err(AppError::NotFound(E::NAME.to_string()));
+    }
+    operation_capture::capture_delete(conn, E::ENTITY_TYPE, &uuid.to_string()).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::schema;
+    use crate::models::{EggRecord, Quail, RingColor};
+
+    fn setup() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        schema::init_schema(&conn).unwrap();
+        conn
+    }
+
+    fn logged_fields(conn: &Connection, uuid: &Uuid) -> Vec<String> {
+        let mut stmt = conn
+            .prepare(
+                "SELECT json_extract(op_kind, '$.field') FROM op_log
+                 WHERE entity_id = ?1 ORDER BY ts, logical_counter",
+            )
+            .unwrap();
+        stmt.query_map([uuid.to_string()], |row| {
+            Ok(row
+                .get::<_, Option<String>>(0)?
+                .unwrap_or_else(|| "-".to_string()))
+        })
+        .unwrap()
+        .collect::<Result<_, _>>()
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_save_captures_changes() {
+        let conn = setup();
+        let mut quail = Quail::new("Greta".to_string());
+        quail.ring_color = Some(RingColor::Rot);
+        save_with_ops(&conn, &quail).await.unwrap();
+        assert_eq!(
+            logged_fields(&conn, &quail.uuid),
+            vec!["name", "gender", "ring_color"]
+        );
+
+        // Unverändert: keine Operation
+        save_with_ops(&conn, &quail).await.unwrap();
+        assert_eq!(logged_fields(&conn, &quail.uuid).len(), 3);
+
+        // Geändert: alle Felder, geleerte als null
+        quail.name = "Gretel".to_string();
+        quail.ring_color = None;
+        save_with_ops(&conn, &quail).await.unwrap();
+        assert_eq!(
+            logged_fields(&conn, &quail.uuid)[3..],
+            [
+                "name",
+                "gender",
+                "ring_color",
+                "profile_photo",
+                "mother_id",
+                "father_id",
+                "coop_id"
+            ]
+        );
+        let loaded: Quail = require(&conn, &quail.uuid).unwrap();
+        assert_eq!(loaded, quail);
+
+        quail.name = " ".to_string();
+        assert!(matches!(
+            save_with_ops(&conn, &quail).await,
+            Err(AppError::Validation(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_list_skips_deleted_and_delete_leaves_tombstone() {
+        let conn = setup();
+        let day = |d| chrono::NaiveDate::from_ymd_opt(2025, 3, d).unwrap();
+        let records: Vec<EggRecord> = (1..=3).map(|d| EggRecord::new(day(d), d as i32)).collect();
+        for record in &records {
+            save_with_ops(&conn, record).await.unwrap();
+        }
+        // Per Sync gelöscht: Zeile bleibt mit deleted = 1
+        conn.execute(
+            "UPDATE egg_records SET deleted = 1 WHERE uuid = ?1",
+            [records[0].uuid.to_string()],
+        )
+        .unwrap();
+
+        let listed: Vec<EggRecord> = list(&conn, "1", [], "record_date DESC").unwrap();
+        assert_eq!(listed, vec![records[2].clone(), records[1].clone()]);
+        let first: Option<EggRecord> =
+            find_first(&conn, "total_eggs < ?1", [3], "record_date").unwrap();
+        assert_eq!(first, Some(records[1].clone()));
+
+        delete_with_ops::<EggRecord>(&conn, &records[1].uuid)
+            .await
+            .unwrap();
+        assert!(get::<EggRecord>(&conn, &records[1].uuid).unwrap().is_none());
+        assert_eq!(logged_fields(&conn, &records[1].uuid).last().unwrap(), "-");
+        assert!(matches!(
+            delete_with_ops::<EggRecord>(&conn, &records[1].uuid).await,
+            Err(AppError::NotFound(_))
+        ));
+    }
+}
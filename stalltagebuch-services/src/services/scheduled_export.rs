@@ -0,0 +1,365 @@
+//! Automatische Exporte nach Zeitplan
+//!
+//! Anders als die tägliche Datenbank-Kopie aus
+//! [`backup_service`](crate::services::backup_service) entsteht hier
+//! wöchentlich oder monatlich ein vollständiges ZIP wie beim manuellen
+//! Export, lokal und/oder im Export-Ordner der Nextcloud. Das schützt auch
+//! vor einem CRDT-Fehler, der alle synchronisierten Geräte gleichzeitig
+//! beschädigt: ältere ZIPs bleiben unberührt und lassen sich einspielen.
+//! Ergebnisse landen im dauerhaften Protokoll.
+
+use crate::error::AppError;
+use crate::models::{ExportDestination, ExportInterval, ExportSchedule, LogLevel};
+use crate::services::export_import_service::{self, ExportFilter};
+use crate::services::sync_backend::{RemoteEntry, SyncBackend};
+use crate::services::{app_log, cloud_export};
+use chrono::{DateTime, Utc};
+use rusqlite::{params, Connection, OptionalExtension};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Kategorie im [`app_log`]
+pub const LOG_CATEGORY: &str = "export";
+
+/// Automatische Exporte heißen anders als manuelle, damit das Aufräumen
+/// nur sie trifft
+const FILE_PREFIX: &str = "stalltagebuch-auto-";
+const FILE_SUFFIX: &str = ".zip";
+
+/// Ergebnis eines automatischen Exports
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScheduledExportReport {
+    pub file_name: String,
+    /// Lokale Datei, falls sie behalten wird
+    pub local_path: Option<PathBuf>,
+    /// Pfad in der Nextcloud, falls hochgeladen
+    pub remote_path: Option<String>,
+    /// Entfernte ältere automatische Exporte (lokal und in der Nextcloud)
+    pub pruned: usize,
+}
+
+impl ScheduledExportReport {
+    fn summary(&self) -> String {
+        let mut parts = vec![format!("Automatischer Export {}", self.file_name)];
+        if self.local_path.is_some() {
+            parts.push("lokal gespeichert".to_string());
+        }
+        if let Some(remote) = &self.remote_path {
+            parts.push(format!("hochgeladen nach {}", remote));
+        }
+        if self.pruned > 0 {
+            parts.push(format!("{} ältere entfernt", self.pruned));
+        }
+        parts.join(", ")
+    }
+}
+
+/// Gespeicherter Zeitplan (Standard: aus)
+pub fn load_schedule(conn: &Connection) -> Result<ExportSchedule, AppError> {
+    let schedule = conn
+        .query_row(
+            "SELECT interval, destination, keep, last_success, last_attempt
+             FROM export_schedule WHERE id = 1",
+            [],
+            |row| {
+                Ok(ExportSchedule {
+                    interval: ExportInterval::parse(&row.get::<_, String>(0)?).unwrap_or_default(),
+                    destination: ExportDestination::parse(&row.get::<_, String>(1)?)
+                        .unwrap_or_default(),
+                    keep: row.get(2)?,
+                    last_success: row.get(3)?,
+                    last_attempt: row.get(4)?,
+                })
+            },
+        )
+        .optional()?;
+    Ok(schedule.unwrap_or_default())
+}
+
+/// Speichert Intervall, Ziel und Anzahl; die Zeitstempel bleiben unverändert
+pub fn save_schedule(conn: &Connection, schedule: &ExportSchedule) -> Result<(), AppError> {
+    conn.execute(
+        "INSERT INTO export_schedule (id, interval, destination, keep) VALUES (1, ?1, ?2, ?3)
+         ON CONFLICT(id) DO UPDATE SET
+            interval = excluded.interval,
+            destination = excluded.destination,
+            keep = excluded.keep",
+        params![
+            schedule.interval.as_str(),
+            schedule.destination.as_str(),
+            schedule.keep.max(1)
+        ],
+    )?;
+    Ok(())
+}
+
+fn record_run(conn: &Connection, now: DateTime<Utc>, success: bool) -> Result<(), AppError> {
+    let millis = now.timestamp_millis();
+    conn.execute(
+        "INSERT INTO export_schedule (id, last_success, last_attempt) VALUES (1, ?1, ?2)
+         ON CONFLICT(id) DO UPDATE SET
+            last_success = COALESCE(excluded.last_success, last_success),
+            last_attempt = excluded.last_attempt",
+        params![success.then_some(millis), millis],
+    )?;
+    Ok(())
+}
+
+fn is_auto_export(name: &str) -> bool {
+    name.starts_with(FILE_PREFIX) && name.ends_with(FILE_SUFFIX)
+}
+
+/// Namen zum Löschen: alle bis auf die neuesten `keep` (Zeitstempel im
+/// Namen sortiert wie die Zeit)
+fn names_to_prune(mut names: Vec<String>, keep: u32) -> Vec<String> {
+    names.sort_unstable_by(|a, b| b.cmp(a));
+    names.into_iter().skip(keep.max(1) as usize).collect()
+}
+
+/// Löscht in `dir` alle bis auf die neuesten `keep` automatischen Exporte
+pub fn prune_local(dir: &Path, keep: u32) -> Result<usize, AppError> {
+    if !dir.exists() {
+        return Ok(0);
+    }
+    let mut names = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let name = entry?.file_name().to_string_lossy().into_owned();
+        if is_auto_export(&name) {
+            names.push(name);
+        }
+    }
+    let obsolete = names_to_prune(names, keep);
+    for name in &obsolete {
+        fs::remove_file(dir.join(name))?;
+    }
+    Ok(obsolete.len())
+}
+
+/// Wie [`prune_local`] im Nextcloud-Ordner `folder`
+async fn prune_remote(
+    backend: &impl SyncBackend,
+    folder: &str,
+    keep: u32,
+) -> Result<usize, AppError> {
+    let names = backend
+        .list(folder)
+        .await?
+        .into_iter()
+        .filter_map(|entry| match entry {
+            RemoteEntry::File { name, .. } if is_auto_export(&name) => Some(name),
+            _ => None,
+        })
+        .collect();
+    let obsolete = names_to_prune(names, keep);
+    for name in &obsolete {
+        backend.delete(&format!("{}/{}", folder, name)).await?;
+    }
+    Ok(obsolete.len())
+}
+
+async fn run_export(
+    conn: &Connection,
+    schedule: &ExportSchedule,
+    dir: &Path,
+    now: DateTime<Utc>,
+) -> Result<ScheduledExportReport, AppError> {
+    let file_name = format!(
+        "{}{}{}",
+        FILE_PREFIX,
+        now.format("%Y%m%d-%H%M%S"),
+        FILE_SUFFIX
+    );
+    let local_path = dir.join(&file_name);
+    export_import_service::export_selection_to_path(conn, &ExportFilter::default(), &local_path)?;
+
+    let mut report = ScheduledExportReport {
+        file_name,
+        local_path: None,
+        remote_path: None,
+        pruned: 0,
+    };
+    if schedule.destination.keeps_local() {
+        // Vor dem Hochladen, damit fehlschlagende Uploads keine Dateien anhäufen
+        report.pruned += prune_local(dir, schedule.keep)?;
+        report.local_path = Some(local_path.clone());
+    }
+
+    if schedule.destination.uploads() {
+        let uploaded = async {
+            let (settings, backend) = cloud_export::configured_backend(conn)?;
+            let export = cloud_export::upload_export(&backend, &settings, &local_path).await?;
+            let pruned = prune_remote(&backend, &settings.export_folder(), schedule.keep).await?;
+            Ok::<_, AppError>((export.remote_path, pruned))
+        }
+        .await;
+        if !schedule.destination.keeps_local() {
+            fs::remove_file(&local_path)?;
+        }
+        let (remote_path, pruned) = uploaded?;
+        report.remote_path = Some(remote_path);
+        report.pruned += pruned;
+    }
+    Ok(report)
+}
+
+/// Führt den automatischen Export aus, falls er laut Zeitplan ansteht
+pub async fn run_if_due(conn: &Connection) -> Result<Option<ScheduledExportReport>, AppError> {
+    run_if_due_in(
+        conn,
+        &export_import_service::get_export_base_dir(),
+        Utc::now(),
+    )
+    .await
+}
+
+/// Wie [`run_if_due`] mit lokalem Ordner `dir` und Zeitpunkt `now`
+pub async fn run_if_due_in(
+    conn: &Connection,
+    dir: &Path,
+    now: DateTime<Utc>,
+) -> Result<Option<ScheduledExportReport>, AppError> {
+    let schedule = load_schedule(conn)?;
+    if !schedule.is_due(now) {
+        return Ok(None);
+    }
+
+    let result = run_export(conn, &schedule, dir, now).await;
+    record_run(conn, now, result.is_ok())?;
+    match &result {
+        Ok(report) => app_log::write(conn, LOG_CATEGORY, LogLevel::Info, &report.summary())?,
+        Err(e) => app_log::write(
+            conn,
+            LOG_CATEGORY,
+            LogLevel::Error,
+            &format!("Automatischer Export fehlgeschlagen: {}", e),
+        )?,
+    };
+    result.map(Some)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::schema;
+    use crate::models::SyncSettings;
+    use crate::services::sync_backend::MemoryBackend;
+    use crate::services::sync_service;
+    use chrono::Duration;
+
+    fn setup() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        schema::init_schema(&conn).unwrap();
+        conn
+    }
+
+    fn test_dir() -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("scheduled-export-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[tokio::test]
+    async fn test_weekly_export_uploads_and_prunes() {
+        let conn = setup();
+        let dir = test_dir();
+        sync_service::save_sync_settings(
+            &conn,
+            &SyncSettings::new(
+                "memory://scheduled-export".to_string(),
+                "anna".to_string(),
+                "secret".to_string(),
+                "/Stalltagebuch".to_string(),
+            ),
+        )
+        .unwrap();
+        save_schedule(
+            &conn,
+            &ExportSchedule {
+                interval: ExportInterval::Weekly,
+                destination: ExportDestination::Both,
+                keep: 2,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        // Ältere automatische und ein manueller Export
+        for day in 1..=3 {
+            fs::write(
+                dir.join(format!("{}202001{:02}-120000.zip", FILE_PREFIX, day)),
+                b"alt",
+            )
+            .unwrap();
+        }
+        fs::write(
+            dir.join("stalltagebuch-export-20200101-120000.zip"),
+            b"manuell",
+        )
+        .unwrap();
+
+        let now = Utc::now();
+        let report = run_if_due_in(&conn, &dir, now).await.unwrap().unwrap();
+        assert!(report.local_path.as_ref().unwrap().exists());
+        assert_eq!(report.pruned, 2);
+        let mut remaining: Vec<String> = fs::read_dir(&dir)
+            .unwrap()
+            .map(|e| e.unwrap().file_name().to_string_lossy().into_owned())
+            .collect();
+        remaining.sort();
+        assert_eq!(
+            remaining,
+            vec![
+                format!("{}20200103-120000.zip", FILE_PREFIX),
+                report.file_name.clone(),
+                "stalltagebuch-export-20200101-120000.zip".to_string(),
+            ]
+        );
+        assert_eq!(
+            MemoryBackend::shared("scheduled-export").file_paths(),
+            vec![format!("Stalltagebuch/exports/{}", report.file_name)]
+        );
+
+        // Erst in einer Woche wieder
+        assert!(run_if_due_in(&conn, &dir, now + Duration::days(6))
+            .await
+            .unwrap()
+            .is_none());
+        assert_eq!(
+            load_schedule(&conn).unwrap().last_success,
+            Some(now.timestamp_millis())
+        );
+        let log = app_log::list(&conn, Some(LOG_CATEGORY), 10).unwrap();
+        assert_eq!(log.len(), 1);
+        assert_eq!(log[0].level, LogLevel::Info);
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[tokio::test]
+    async fn test_failed_export_is_logged_and_retried_later() {
+        let conn = setup();
+        let dir = test_dir();
+        // Nextcloud gewählt, aber nicht eingerichtet
+        save_schedule(
+            &conn,
+            &ExportSchedule {
+                interval: ExportInterval::Monthly,
+                destination: ExportDestination::Nextcloud,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let now = Utc::now();
+        assert!(run_if_due_in(&conn, &dir, now).await.is_err());
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 0);
+        let log = app_log::list(&conn, Some(LOG_CATEGORY), 10).unwrap();
+        assert_eq!(log[0].level, LogLevel::Error);
+
+        let schedule = load_schedule(&conn).unwrap();
+        assert_eq!(schedule.last_success, None);
+        assert!(!schedule.is_due(now + Duration::hours(1)));
+        assert!(schedule.is_due(now + ExportSchedule::RETRY_DELAY));
+
+        fs::remove_dir_all(&dir).ok();
+    }
+}
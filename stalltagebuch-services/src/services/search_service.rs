@@ -0,0 +1,225 @@
+//! Volltextsuche über Namen und Notizen
+//!
+//! Die FTS5-Tabelle `search_fts` enthält Wachtelnamen, Notizen der
+//! Ereignisse und Notizen der Eier-Einträge. Trigger auf den drei Tabellen
+//! halten sie aktuell (auch für Änderungen aus dem Sync), gelöschte Einträge
+//! fallen heraus.
+
+use crate::error::AppError;
+use crate::models::EventType;
+use chrono::NaiveDate;
+use rusqlite::{params, Connection};
+use uuid::Uuid;
+
+/// Höchstens so viele Treffer pro Suche
+pub const MAX_RESULTS: usize = 100;
+
+/// Art des gefundenen Eintrags
+#[derive(Debug, Clone, PartialEq)]
+pub enum SearchHitKind {
+    Quail,
+    Event(EventType),
+    EggRecord,
+}
+
+/// Ein Treffer der Volltextsuche
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchHit {
+    pub kind: SearchHitKind,
+    /// UUID der Wachtel, des Ereignisses bzw. des Eier-Eintrags
+    pub uuid: Uuid,
+    /// Zugehörige Wachtel (Wachtel selbst oder Besitzer des Ereignisses)
+    pub quail_uuid: Option<Uuid>,
+    pub quail_name: Option<String>,
+    /// Datum des Ereignisses bzw. Eier-Eintrags
+    pub date: Option<NaiveDate>,
+    /// Ausschnitt des gefundenen Textes
+    pub snippet: String,
+}
+
+/// Baut aus der Eingabe eine FTS5-Abfrage: jedes Wort als Präfix, alle
+/// Wörter müssen vorkommen. Sonderzeichen der FTS-Syntax werden entschärft.
+fn match_expression(query: &str) -> Option<String> {
+    let terms: Vec<String> = query
+        .split_whitespace()
+        .map(|term| term.replace('"', ""))
+        .filter(|term| !term.is_empty())
+        .map(|term| format!("\"{}\"*", term))
+        .collect();
+    if terms.is_empty() {
+        None
+    } else {
+        Some(terms.join(" "))
+    }
+}
+
+/// Sucht in Namen, Ereignis- und Eier-Notizen; beste Treffer zuerst
+pub fn search_all(conn: &Connection, query: &str) -> Result<Vec<SearchHit>, AppError> {
+    let Some(expression) = match_expression(query) else {
+        return Ok(Vec::new());
+    };
+
+    let mut stmt = conn.prepare(
+        "SELECT s.kind, s.uuid, snippet(search_fts, 2, '', '', '…', 12),
+                COALESCE(q.uuid, eq.uuid), COALESCE(q.name, eq.name),
+                e.event_type, COALESCE(e.event_date, r.record_date)
+         FROM search_fts s
+         LEFT JOIN quails q ON s.kind = 'quail' AND q.uuid = s.uuid
+         LEFT JOIN quail_events e ON s.kind = 'event' AND e.uuid = s.uuid
+         LEFT JOIN quails eq ON eq.uuid = e.quail_id
+         LEFT JOIN egg_records r ON s.kind = 'egg_record' AND r.uuid = s.uuid
+         WHERE search_fts MATCH ?1
+         ORDER BY rank
+         LIMIT ?2",
+    )?;
+    let rows = stmt.query_map(params![expression, MAX_RESULTS as i64], |row| {
+        let kind: String = row.get(0)?;
+        let uuid: String = row.get(1)?;
+        let quail_uuid: Option<String> = row.get(3)?;
+        let event_type: Option<String> = row.get(5)?;
+        let date: Option<String> = row.get(6)?;
+        Ok((
+            kind,
+            uuid,
+            row.get::<_, String>(2)?,
+            quail_uuid,
+            row.get::<_, Option<String>>(4)?,
+            event_type,
+            date,
+        ))
+    })?;
+
+    let mut hits = Vec::new();
+    for row in rows {
+        let (kind, uuid, snippet, quail_uuid, quail_name, event_type, date) = row?;
+        let Ok(uuid) = Uuid::parse_str(&uuid) else {
+            continue;
+        };
+        let kind = match kind.as_str() {
+            "quail" => SearchHitKind::Quail,
+            "event" => SearchHitKind::Event(EventType::from_str(
+                event_type.as_deref().unwrap_or_default(),
+            )),
+            "egg_record" => SearchHitKind::EggRecord,
+            other => {
+                log::warn!("Unbekannte Art im Suchindex: {}", other);
+                continue;
+            }
+        };
+        hits.push(SearchHit {
+            kind,
+            uuid,
+            quail_uuid: quail_uuid.and_then(|u| Uuid::parse_str(&u).ok()),
+            quail_name,
+            date: date.and_then(|d| NaiveDate::parse_from_str(&d, "%Y-%m-%d").ok()),
+            snippet,
+        });
+    }
+    Ok(hits)
+}
+
+/// [`search_all`] off the UI thread, on a pooled connection
+pub async fn search_all_async(query: String) -> Result<Vec<SearchHit>, AppError> {
+    crate::database::with_db_async(move |conn| search_all(conn, &query)).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::schema;
+    use crate::models::{EggRecord, Quail};
+
+    fn setup() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        schema::init_schema(&conn).unwrap();
+        conn
+    }
+
+    #[test]
+    fn test_match_expression() {
+        assert_eq!(match_expression("  "), None);
+        assert_eq!(
+            match_expression("Hah\"n  krank"),
+            Some("\"Hahn\"* \"krank\"*".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_search_names_and_notes() {
+        let conn = setup();
+        let quail = Quail::new("Rosalie".to_string());
+        crate::services::create_profile(&conn, &quail)
+            .await
+            .unwrap();
+        let event_uuid = crate::services::event_service::create_event(
+            &conn,
+            quail.uuid,
+            EventType::Sick,
+            NaiveDate::from_ymd_opt(2025, 3, 2).unwrap(),
+            Some("Hinkt am linken Fuß".to_string()),
+        )
+        .await
+        .unwrap();
+        let mut record = EggRecord::new(NaiveDate::from_ymd_opt(2025, 3, 3).unwrap(), 4);
+        record.notes = Some("Ein Ei mit dünner Schale".to_string());
+        crate::services::add_egg_record(&conn, &record)
+            .await
+            .unwrap();
+
+        let hits = search_all(&conn, "ros").unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].kind, SearchHitKind::Quail);
+        assert_eq!(hits[0].uuid, quail.uuid);
+
+        // Ereignis verweist auf die Wachtel
+        let hits = search_all(&conn, "linken fu").unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].kind, SearchHitKind::Event(EventType::Sick));
+        assert_eq!(hits[0].uuid, event_uuid);
+        assert_eq!(hits[0].quail_name.as_deref(), Some("Rosalie"));
+        assert_eq!(hits[0].quail_uuid, Some(quail.uuid));
+
+        // Umlaute werden ohne Akzent gefunden
+        let hits = search_all(&conn, "dunner").unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].kind, SearchHitKind::EggRecord);
+        assert_eq!(hits[0].date, Some(record.record_date));
+
+        // Umbenennen und Löschen halten den Index aktuell
+        conn.execute(
+            "UPDATE quails SET name = 'Berta' WHERE uuid = ?1",
+            [quail.uuid.to_string()],
+        )
+        .unwrap();
+        assert!(search_all(&conn, "rosalie").unwrap().is_empty());
+        assert_eq!(search_all(&conn, "berta").unwrap().len(), 1);
+
+        conn.execute(
+            "UPDATE egg_records SET deleted = 1 WHERE uuid = ?1",
+            [record.uuid.to_string()],
+        )
+        .unwrap();
+        assert!(search_all(&conn, "schale").unwrap().is_empty());
+
+        crate::services::delete_profile(&conn, &quail.uuid)
+            .await
+            .unwrap();
+        assert!(search_all(&conn, "berta").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_migration_indexes_existing_rows() {
+        let conn = setup();
+        conn.execute(
+            "INSERT INTO quails (uuid, name) VALUES (?1, 'Alte Henne')",
+            [Uuid::new_v4().to_string()],
+        )
+        .unwrap();
+        // Index verloren (z.B. ältere Datenbank): Migration baut ihn neu
+        conn.execute("DELETE FROM search_fts", []).unwrap();
+        conn.execute("DELETE FROM schema_version WHERE version >= 26", [])
+            .unwrap();
+        schema::init_schema(&conn).unwrap();
+        assert_eq!(search_all(&conn, "henne").unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,263 @@
+//! Neuaufbau der lokalen Daten aus dem Op-Log (Wartung)
+//!
+//! Ist der abgeleitete Zustand (Wachteln, Ereignisse, Fotos, Eier) durch
+//! fehlerhaftes Mergen kaputt, werden die Tabellen geleert und alle lokal
+//! bekannten Operationen (`op_log` und noch nicht hochgeladene aus
+//! `op_outbox`) in HLC-Reihenfolge erneut angewendet. Danach wird alles vom
+//! Server neu gemergt. Gerätelokale Fotospalten (Dateipfade, Sync-Status,
+//! EXIF) bleiben erhalten.
+
+use crate::error::AppError;
+use crate::models::SyncSettings;
+use crate::services::crdt_service::{self, HybridLogicalClock, Operation};
+use crate::services::{download_service, op_compaction, sync_service};
+use rusqlite::Connection;
+use std::collections::BTreeMap;
+
+/// CRDT-Metadaten der Fotos; sie kommen beim Neuaufbau aus den Operationen
+const PHOTO_CRDT_COLUMNS: &[&str] = &[
+    "uuid",
+    "rev",
+    "logical_clock",
+    "clock_counter",
+    "clock_device",
+    "deleted",
+    "updated_at",
+];
+
+/// Anzahl nicht gelöschter Entitäten eines Typs vor und nach dem Neuaufbau
+#[derive(Debug, Clone, PartialEq)]
+pub struct EntityCount {
+    pub entity_type: &'static str,
+    pub before: usize,
+    pub after: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RebuildReport {
+    /// Lokal erneut angewendete Operationen
+    pub replayed_ops: usize,
+    /// Danach vom Server gemergte Operationen und Snapshot-Entitäten
+    pub merged_remote: usize,
+    /// Server nicht erreichbar o.ä.; der lokale Neuaufbau ist trotzdem gültig
+    pub remote_error: Option<String>,
+    pub counts: Vec<EntityCount>,
+}
+
+impl RebuildReport {
+    /// Typen, deren Anzahl sich durch den Neuaufbau geändert hat
+    pub fn mismatches(&self) -> impl Iterator<Item = &EntityCount> {
+        self.counts.iter().filter(|c| c.before != c.after)
+    }
+
+    pub fn is_consistent(&self) -> bool {
+        self.mismatches().next().is_none()
+    }
+}
+
+/// Leert die abgeleiteten Tabellen und baut sie aus allen bekannten
+/// Operationen neu auf
+///
+/// Nur mit eingerichtetem, vollständigem Sync: Ohne Sync, vor dem ersten
+/// Upload oder mit ausgenommenen Entitätstypen stehen lokale Änderungen
+/// nicht im Op-Log und gingen verloren.
+pub async fn rebuild_from_op_log(conn: &Connection) -> Result<RebuildReport, AppError> {
+    let settings = sync_service::load_sync_settings(conn)?
+        .ok_or_else(|| AppError::Validation("Sync ist nicht eingerichtet".to_string()))?;
+    if !settings.enabled
+        || !settings.initial_upload_done
+        || !settings.excluded_entity_types.is_empty()
+    {
+        return Err(AppError::Validation(
+            "Neuaufbau nur mit vollständig eingerichtetem Sync möglich".to_string(),
+        ));
+    }
+
+    let before = count_entities(conn)?;
+    let ops = load_local_ops(conn)?;
+
+    let tx = conn.unchecked_transaction()?;
+    tx.execute_batch(
+        "DROP TABLE IF EXISTS temp.rebuild_photos;
+         CREATE TEMP TABLE rebuild_photos AS SELECT * FROM photos;
+         DELETE FROM quail_events;
+         DELETE FROM egg_records;
+         DELETE FROM quails;
+         DELETE FROM coops;
+         DELETE FROM photos;
+         DELETE FROM op_log;",
+    )?;
+    let replayed_ops = download_service::apply_operations_in(&tx, &ops)?;
+    restore_local_photo_columns(&tx)?;
+    tx.commit()?;
+
+    if let Some(newest) = ops.iter().map(|op| &op.clock).max() {
+        crdt_service::observe_remote_clock(conn, newest)?;
+    }
+    log::info!("Neuaufbau: {} Operationen lokal angewendet", replayed_ops);
+
+    // Alles vom Server noch einmal lesen (auch Snapshots, deren Ops lokal
+    // nie im Op-Log standen)
+    download_service::reset_manifest(conn)?;
+    let (merged_remote, remote_error) = match download_service::download_and_merge_ops(conn).await {
+        Ok(merged) => (merged, None),
+        Err(e) => {
+            log::warn!("Neuaufbau: Server-Abgleich fehlgeschlagen: {}", e);
+            (0, Some(e.to_string()))
+        }
+    };
+    restore_local_photo_columns(conn)?;
+    conn.execute("DROP TABLE IF EXISTS temp.rebuild_photos", [])?;
+
+    let after = count_entities(conn)?;
+    let counts = before
+        .into_iter()
+        .map(|(entity_type, before)| EntityCount {
+            entity_type,
+            before,
+            after: after.get(entity_type).copied().unwrap_or_default(),
+        })
+        .collect();
+
+    Ok(RebuildReport {
+        replayed_ops,
+        merged_remote,
+        remote_error,
+        counts,
+    })
+}
+
+/// Nicht gelöschte Entitäten je Typ
+fn count_entities(conn: &Connection) -> Result<BTreeMap<&'static str, usize>, AppError> {
+    let mut counts = BTreeMap::new();
+    for entity_type in SyncSettings::ENTITY_TYPES {
+        let Some(table) = crdt_service::entity_table(entity_type) else {
+            continue;
+        };
+        let count: i64 = conn.query_row(
+            &format!("SELECT COUNT(*) FROM {} WHERE deleted = 0", table),
+            [],
+            |row| row.get(0),
+        )?;
+        counts.insert(entity_type, count as usize);
+    }
+    Ok(counts)
+}
+
+/// Alle Operationen aus `op_log` und `op_outbox`, sortiert nach HLC
+fn load_local_ops(conn: &Connection) -> Result<Vec<Operation>, AppError> {
+    let mut ops = BTreeMap::new();
+
+    let mut stmt = conn.prepare(
+        "SELECT op_id, entity_type, entity_id, ts, logical_counter, device_id, op_kind FROM op_log",
+    )?;
+    let rows = stmt.query_map([], |row| {
+        Ok((
+            row.get::<_, String>(0)?,
+            row.get::<_, String>(1)?,
+            row.get::<_, String>(2)?,
+            HybridLogicalClock {
+                ts: row.get(3)?,
+                logical_counter: row.get(4)?,
+                device_id: row.get(5)?,
+            },
+            row.get::<_, String>(6)?,
+        ))
+    })?;
+    for row in rows {
+        let (op_id, entity_type, entity_id, clock, op_kind) = row?;
+        match serde_json::from_str(&op_kind) {
+            Ok(op) => {
+                ops.insert(
+                    op_id.clone(),
+                    Operation {
+                        op_id,
+                        entity_type,
+                        entity_id,
+                        clock,
+                        op,
+                    },
+                );
+            }
+            Err(e) => log::warn!("Op {} im Op-Log nicht lesbar: {}", op_id, e),
+        }
+    }
+
+    let mut stmt = conn.prepare("SELECT line FROM op_outbox ORDER BY id")?;
+    let lines = stmt.query_map([], |row| row.get::<_, String>(0))?;
+    for line in lines {
+        let line = line?;
+        match serde_json::from_str::<Operation>(&line) {
+            Ok(op) => {
+                ops.insert(op.op_id.clone(), op);
+            }
+            Err(e) => log::warn!("Op in der Outbox nicht lesbar: {}", e),
+        }
+    }
+
+    let mut ops: Vec<_> = ops.into_values().collect();
+    ops.sort_by(|a, b| a.clock.cmp(&b.clock));
+    Ok(ops)
+}
+
+/// Übernimmt gerätelokale Fotospalten aus der Sicherung vor dem Neuaufbau
+fn restore_local_photo_columns(conn: &Connection) -> Result<(), AppError> {
+    let synced = op_compaction::synced_columns("photo");
+    let mut stmt = conn.prepare("SELECT name FROM pragma_table_info('photos')")?;
+    let local: Vec<String> = stmt
+        .query_map([], |row| row.get::<_, String>(0))?
+        .collect::<Result<Vec<_>, _>>()?
+        .into_iter()
+        .filter(|c| !synced.contains(&c.as_str()) && !PHOTO_CRDT_COLUMNS.contains(&c.as_str()))
+        .collect();
+    if local.is_empty() {
+        return Ok(());
+    }
+
+    let assignments: Vec<String> = local
+        .iter()
+        .map(|c| {
+            format!(
+                "{c} = (SELECT r.{c} FROM temp.rebuild_photos r WHERE r.uuid = photos.uuid)",
+                c = c
+            )
+        })
+        .collect();
+    conn.execute(
+        &format!(
+            "UPDATE photos SET {} WHERE uuid IN (SELECT uuid FROM temp.rebuild_photos)",
+            assignments.join(", ")
+        ),
+        [],
+    )?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::schema;
+
+    #[tokio::test]
+    async fn test_rebuild_needs_complete_sync() {
+        let conn = Connection::open_in_memory().unwrap();
+        schema::init_schema(&conn).unwrap();
+        assert!(matches!(
+            rebuild_from_op_log(&conn).await,
+            Err(AppError::Validation(_))
+        ));
+
+        // Vor dem ersten Upload stehen lokale Daten noch nicht im Op-Log
+        let settings = SyncSettings::new(
+            "memory://rebuild".to_string(),
+            "user".to_string(),
+            "password".to_string(),
+            "/Stalltagebuch".to_string(),
+        );
+        sync_service::save_sync_settings(&conn, &settings).unwrap();
+        assert!(matches!(
+            rebuild_from_op_log(&conn).await,
+            Err(AppError::Validation(_))
+        ));
+    }
+}
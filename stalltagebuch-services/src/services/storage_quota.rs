@@ -0,0 +1,227 @@
+//! Obergrenze für lokal gespeicherte Fotos
+//!
+//! Ist in den Sync-Einstellungen `photo_storage_limit_mb` gesetzt und
+//! belegen die Fotodateien mehr Platz, werden die am längsten nicht
+//! angesehenen Originale entfernt – aber nur, wenn sie bereits auf dem
+//! Server liegen. Thumbnails bleiben immer lokal. Entfernte Originale
+//! bekommen `original_evicted = 1` und `sync_status = 'download_pending'`
+//! und werden beim nächsten Öffnen wieder heruntergeladen.
+
+use crate::error::AppError;
+use crate::models::photo::THUMBNAIL_TIERS;
+use crate::services::photo_storage::LocalPhotoStorage;
+use crate::services::sync_service;
+use rusqlite::{params, Connection};
+
+/// Ergebnis eines Aufräumlaufs
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct EvictionReport {
+    /// Belegter Platz vor dem Aufräumen
+    pub used_bytes: u64,
+    pub freed_bytes: u64,
+    pub evicted: usize,
+}
+
+/// Merkt sich, dass ein Foto gerade angesehen wurde (für die LRU-Reihenfolge)
+pub fn mark_viewed(conn: &Connection, photo_uuid: &str) -> Result<(), AppError> {
+    conn.execute(
+        "UPDATE photos SET last_viewed_at = ?1 WHERE uuid = ?2",
+        params![chrono::Utc::now().timestamp_millis(), photo_uuid],
+    )?;
+    Ok(())
+}
+
+/// Summe der lokal vorhandenen Fotodateien (Originale und alle Thumbnail-Stufen)
+pub fn local_usage(conn: &Connection, local: &LocalPhotoStorage) -> Result<u64, AppError> {
+    let mut stmt = conn.prepare(
+        "SELECT uuid, COALESCE(relative_path, path), thumbnail_small_path, thumbnail_medium_path
+         FROM photos
+         WHERE deleted = 0",
+    )?;
+    let rows = stmt.query_map([], |row| {
+        Ok((
+            row.get::<_, String>(0)?,
+            [
+                row.get::<_, Option<String>>(1)?,
+                row.get::<_, Option<String>>(2)?,
+                row.get::<_, Option<String>>(3)?,
+            ],
+        ))
+    })?;
+
+    let mut used = 0;
+    for row in rows {
+        let (uuid, files) = row?;
+        for file in files.into_iter().flatten() {
+            used += file_size(local, &file);
+        }
+        for tier in THUMBNAIL_TIERS.iter().filter(|t| !t.synced) {
+            used += file_size(local, &tier.file_name(&uuid));
+        }
+    }
+    Ok(used)
+}
+
+/// Entfernt synchronisierte Originale (am längsten nicht angesehen zuerst),
+/// bis höchstens `limit_bytes` belegt sind
+pub fn enforce_limit(
+    conn: &Connection,
+    local: &LocalPhotoStorage,
+    limit_bytes: u64,
+) -> Result<EvictionReport, AppError> {
+    let used_bytes = local_usage(conn, local)?;
+    let mut report = EvictionReport {
+        used_bytes,
+        ..Default::default()
+    };
+    if used_bytes <= limit_bytes {
+        return Ok(report);
+    }
+
+    let mut stmt = conn.prepare(
+        "SELECT uuid, COALESCE(relative_path, path)
+         FROM photos
+         WHERE deleted = 0 AND sync_status = 'synced' AND original_evicted = 0
+         ORDER BY COALESCE(last_viewed_at, 0) ASC, created_at ASC",
+    )?;
+    let candidates: Vec<(String, String)> = stmt
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+        .collect::<Result<_, _>>()?;
+
+    let mut used = used_bytes;
+    for (uuid, original) in candidates {
+        if used <= limit_bytes {
+            break;
+        }
+        let size = file_size(local, &original);
+        if size == 0 {
+            continue;
+        }
+        if let Err(e) = std::fs::remove_file(local.path_of(&original)) {
+            log::warn!("Original {} konnte nicht entfernt werden: {}", original, e);
+            continue;
+        }
+        conn.execute(
+            "UPDATE photos SET original_evicted = 1, sync_status = 'download_pending' WHERE uuid = ?1",
+            params![uuid],
+        )?;
+        used -= size;
+        report.freed_bytes += size;
+        report.evicted += 1;
+    }
+
+    if used > limit_bytes {
+        log::warn!(
+            "Fotospeicher weiterhin über dem Limit ({} > {} Bytes), keine weiteren synchronisierten Originale",
+            used,
+            limit_bytes
+        );
+    }
+    log::info!(
+        "Fotospeicher: {} Originale entfernt, {} Bytes frei geworden",
+        report.evicted,
+        report.freed_bytes
+    );
+    Ok(report)
+}
+
+/// Wendet das in den Sync-Einstellungen konfigurierte Limit auf das
+/// Fotoverzeichnis der App an (`None`, wenn kein Limit gesetzt ist)
+pub fn enforce_configured_limit(conn: &Connection) -> Result<Option<EvictionReport>, AppError> {
+    let Some(limit_mb) = sync_service::load_sync_settings(conn)?
+        .and_then(|settings| settings.photo_storage_limit_mb)
+    else {
+        return Ok(None);
+    };
+    enforce_limit(
+        conn,
+        &LocalPhotoStorage::app_photos(),
+        u64::from(limit_mb) * 1024 * 1024,
+    )
+    .map(Some)
+}
+
+fn file_size(local: &LocalPhotoStorage, path: &str) -> u64 {
+    if path.trim().is_empty() {
+        return 0;
+    }
+    std::fs::metadata(local.path_of(path))
+        .map(|m| m.len())
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::schema;
+
+    fn add_photo(
+        conn: &Connection,
+        local: &LocalPhotoStorage,
+        name: &str,
+        status: &str,
+        viewed: i64,
+    ) {
+        std::fs::write(local.path_of(&format!("{}.jpg", name)), vec![0u8; 1000]).unwrap();
+        std::fs::write(
+            local.path_of(&format!("{}_small.webp", name)),
+            vec![0u8; 10],
+        )
+        .unwrap();
+        conn.execute(
+            "INSERT INTO photos (uuid, path, relative_path, thumbnail_small_path, sync_status, last_viewed_at)
+             VALUES (?1, '', ?2, ?3, ?4, ?5)",
+            params![
+                name,
+                format!("{}.jpg", name),
+                format!("{}_small.webp", name),
+                status,
+                viewed
+            ],
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_evicts_least_recently_viewed_synced_originals() {
+        let conn = Connection::open_in_memory().unwrap();
+        schema::init_schema(&conn).unwrap();
+        let root = std::env::temp_dir().join(format!("quota-test-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&root).unwrap();
+        let local = LocalPhotoStorage::new(&root);
+
+        add_photo(&conn, &local, "old", "synced", 1);
+        add_photo(&conn, &local, "recent", "synced", 3);
+        add_photo(&conn, &local, "unsynced", "local_only", 0);
+        add_photo(&conn, &local, "middle", "synced", 2);
+        assert_eq!(local_usage(&conn, &local).unwrap(), 4040);
+
+        // Nichts zu tun unterhalb des Limits
+        assert_eq!(enforce_limit(&conn, &local, 5000).unwrap().evicted, 0);
+
+        let report = enforce_limit(&conn, &local, 2500).unwrap();
+        assert_eq!(report.evicted, 2);
+        assert_eq!(report.freed_bytes, 2000);
+        assert!(!local.path_of("old.jpg").exists());
+        assert!(!local.path_of("middle.jpg").exists());
+        assert!(local.path_of("recent.jpg").exists());
+        assert!(local.path_of("unsynced.jpg").exists());
+        assert!(local.path_of("old_small.webp").exists());
+
+        let status: String = conn
+            .query_row(
+                "SELECT sync_status FROM photos WHERE uuid = 'old' AND original_evicted = 1",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(status, "download_pending");
+
+        // Nicht synchronisierte Originale werden nie entfernt
+        let report = enforce_limit(&conn, &local, 0).unwrap();
+        assert_eq!(report.evicted, 1);
+        assert!(local.path_of("unsynced.jpg").exists());
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,806 @@
+//! Remote-Speicher für den Sync
+//!
+//! Upload, Download und Foto-Sync sprechen nur über das Trait
+//! [`SyncBackend`] (Auflisten mit ETags, Lesen, Schreiben, Löschen). Welche
+//! Implementierung dahinter steckt, entscheidet [`ConfiguredBackend`] anhand
+//! der Server-URL der Sync-Einstellungen:
+//! - `https://…`: [`WebDavBackend`] auf die Nextcloud (Produktion)
+//! - `memory://<name>`: prozessweiter [`MemoryBackend`], damit mehrere Geräte
+//!   (Verbindungen) in Tests denselben "Server" teilen können
+//! - `file://<pfad>`: [`LocalFolderBackend`], ein Ordner im Dateisystem
+//!   (Tests, Sync über einen gemeinsam genutzten Ordner)
+//!
+//! Ein weiteres Backend (z.B. SpacetimeDB für die geplante Migration) muss
+//! nur das Trait implementieren und in [`ConfiguredBackend`] eingehängt werden.
+//!
+//! Lehnt der Server die Zugangsdaten ab (HTTP 401, z.B. widerrufenes
+//! App-Passwort), liefern alle Methoden [`AppError::PermissionDenied`].
+
+use crate::error::AppError;
+use crate::models::SyncSettings;
+use crate::services::nextcloud_auth;
+use crate::services::{photo_checksum, transfer_stats};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::future::Future;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, OnceLock};
+
+/// URL-Schema für das In-Memory-Backend
+pub const MEMORY_SCHEME: &str = "memory://";
+
+/// URL-Schema für das Ordner-Backend
+pub const FILE_SCHEME: &str = "file://";
+
+/// Eintrag einer Verzeichnisliste (nur direkte Kinder)
+///
+/// Das ETag eines Verzeichnisses ändert sich (wie bei Nextcloud), sobald sich
+/// darunter irgendeine Datei ändert.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RemoteEntry {
+    File { name: String, etag: Option<String> },
+    Folder { name: String, etag: Option<String> },
+}
+
+impl RemoteEntry {
+    pub fn name(&self) -> &str {
+        match self {
+            RemoteEntry::File { name, .. } | RemoteEntry::Folder { name, .. } => name,
+        }
+    }
+}
+
+/// Remote-Speicher, gegen den synchronisiert wird
+///
+/// Pfade sind absolut zur Wurzel des Speichers (`/Stalltagebuch/sync/…`).
+/// Die Futures sind `Send`, damit Uploads parallel in einem `JoinSet` laufen können.
+pub trait SyncBackend: Send + Sync {
+    /// Listet die direkten Kinder eines Verzeichnisses.
+    /// Fehler (z.B. 404) werden als Err geliefert; Aufrufer behandeln das
+    /// meist als "noch leer". Ein Verzeichnis ohne ETag gilt beim Download
+    /// immer als geändert.
+    fn list(&self, path: &str) -> impl Future<Output = Result<Vec<RemoteEntry>, AppError>> + Send;
+
+    /// Lädt eine Datei herunter
+    fn get(&self, path: &str) -> impl Future<Output = Result<Vec<u8>, AppError>> + Send;
+
+    /// Lädt eine Datei hoch (überschreibt vorhandene)
+    fn put(&self, path: &str, data: Vec<u8>) -> impl Future<Output = Result<(), AppError>> + Send;
+
+    /// Lädt eine Datei nur hoch, wenn unter `path` noch keine liegt
+    ///
+    /// `Ok(false)`, wenn ein anderes Gerät schneller war; dessen Datei bleibt
+    /// unverändert.
+    fn put_if_absent(
+        &self,
+        path: &str,
+        data: Vec<u8>,
+    ) -> impl Future<Output = Result<bool, AppError>> + Send;
+
+    /// Löscht eine Datei
+    fn delete(&self, path: &str) -> impl Future<Output = Result<(), AppError>> + Send;
+
+    /// Legt ein Verzeichnis an (eine Ebene, wie WebDAV MKCOL)
+    fn mkcol(&self, path: &str) -> impl Future<Output = Result<(), AppError>> + Send;
+}
+
+/// Das laut Sync-Einstellungen gewählte Backend
+#[derive(Debug, Clone)]
+pub enum ConfiguredBackend {
+    WebDav(WebDavBackend),
+    Memory(MemoryBackend),
+    Local(LocalFolderBackend),
+}
+
+impl ConfiguredBackend {
+    /// Erstellt das Backend passend zu den gespeicherten Sync-Einstellungen
+    pub fn from_settings(settings: &SyncSettings) -> Result<Self, AppError> {
+        if let Some(name) = settings.server_url.strip_prefix(MEMORY_SCHEME) {
+            return Ok(ConfiguredBackend::Memory(MemoryBackend::shared(name)));
+        }
+        if let Some(root) = settings.server_url.strip_prefix(FILE_SCHEME) {
+            return Ok(ConfiguredBackend::Local(LocalFolderBackend::new(root)));
+        }
+        WebDavBackend::from_settings(settings).map(ConfiguredBackend::WebDav)
+    }
+}
+
+impl SyncBackend for ConfiguredBackend {
+    async fn list(&self, path: &str) -> Result<Vec<RemoteEntry>, AppError> {
+        transfer_stats::count_request(0, 0);
+        match self {
+            ConfiguredBackend::WebDav(b) => b.list(path).await,
+            ConfiguredBackend::Memory(b) => SyncBackend::list(b, path).await,
+            ConfiguredBackend::Local(b) => b.list(path).await,
+        }
+    }
+
+    async fn get(&self, path: &str) -> Result<Vec<u8>, AppError> {
+        let result = match self {
+            ConfiguredBackend::WebDav(b) => b.get(path).await,
+            ConfiguredBackend::Memory(b) => SyncBackend::get(b, path).await,
+            ConfiguredBackend::Local(b) => b.get(path).await,
+        };
+        transfer_stats::count_request(0, result.as_ref().map_or(0, Vec::len));
+        result
+    }
+
+    async fn put(&self, path: &str, data: Vec<u8>) -> Result<(), AppError> {
+        transfer_stats::count_request(data.len(), 0);
+        match self {
+            ConfiguredBackend::WebDav(b) => b.put(path, data).await,
+            ConfiguredBackend::Memory(b) => SyncBackend::put(b, path, data).await,
+            ConfiguredBackend::Local(b) => b.put(path, data).await,
+        }
+    }
+
+    async fn put_if_absent(&self, path: &str, data: Vec<u8>) -> Result<bool, AppError> {
+        transfer_stats::count_request(data.len(), 0);
+        match self {
+            ConfiguredBackend::WebDav(b) => b.put_if_absent(path, data).await,
+            ConfiguredBackend::Memory(b) => b.put_if_absent(path, data).await,
+            ConfiguredBackend::Local(b) => b.put_if_absent(path, data).await,
+        }
+    }
+
+    async fn delete(&self, path: &str) -> Result<(), AppError> {
+        transfer_stats::count_request(0, 0);
+        match self {
+            ConfiguredBackend::WebDav(b) => b.delete(path).await,
+            ConfiguredBackend::Memory(b) => SyncBackend::delete(b, path).await,
+            ConfiguredBackend::Local(b) => b.delete(path).await,
+        }
+    }
+
+    async fn mkcol(&self, path: &str) -> Result<(), AppError> {
+        transfer_stats::count_request(0, 0);
+        match self {
+            ConfiguredBackend::WebDav(b) => b.mkcol(path).await,
+            ConfiguredBackend::Memory(b) => SyncBackend::mkcol(b, path).await,
+            ConfiguredBackend::Local(b) => b.mkcol(path).await,
+        }
+    }
+}
+
+/// WebDAV-Client auf die Nextcloud
+#[derive(Debug, Clone)]
+pub struct WebDavBackend {
+    client: reqwest_dav::Client,
+}
+
+impl WebDavBackend {
+    /// Client für `remote.php/dav/files/<user>` (mit Proxy und gemerktem
+    /// Zertifikat aus den Einstellungen)
+    pub fn from_settings(settings: &SyncSettings) -> Result<Self, AppError> {
+        let webdav_url = format!(
+            "{}/remote.php/dav/files/{}",
+            settings.server_url.trim_end_matches('/'),
+            settings.username
+        );
+
+        // Dieselbe Zertifikatsprüfung wie bei der Anmeldung, schon im Handshake
+        let proxy = nextcloud_auth::proxy_from_settings(settings)?;
+        let agent = nextcloud_auth::configure_client(
+            reqwest::Client::builder(),
+            proxy.as_ref(),
+            &nextcloud_auth::pin_from_settings(settings),
+        )?
+        .build()
+        .map_err(|e| AppError::Other(format!("HTTP client error: {:?}", e)))?;
+
+        let client = reqwest_dav::ClientBuilder::new()
+            .set_agent(agent)
+            .set_host(webdav_url)
+            .set_auth(reqwest_dav::Auth::Basic(
+                settings.username.clone(),
+                settings.app_password.clone(),
+            ))
+            .build()
+            .map_err(|e| AppError::Other(format!("WebDAV client error: {:?}", e)))?;
+
+        Ok(Self { client })
+    }
+}
+
+impl SyncBackend for WebDavBackend {
+    async fn list(&self, path: &str) -> Result<Vec<RemoteEntry>, AppError> {
+        let items = self
+            .client
+            .list(path, reqwest_dav::Depth::Number(1))
+            .await
+            .map_err(|e| webdav_error("List", e))?;
+
+        let own_name = path.trim_end_matches('/').rsplit('/').next().unwrap_or("");
+        let mut entries = Vec::new();
+        for item in items {
+            match item {
+                reqwest_dav::list_cmd::ListEntity::File(file) => {
+                    let name = href_name(&file.href);
+                    if !name.is_empty() {
+                        entries.push(RemoteEntry::File {
+                            name,
+                            etag: file.tag,
+                        });
+                    }
+                }
+                reqwest_dav::list_cmd::ListEntity::Folder(folder) => {
+                    // Depth 1 liefert auch das Verzeichnis selbst
+                    let name = href_name(&folder.href);
+                    if !name.is_empty() && name != own_name {
+                        entries.push(RemoteEntry::Folder {
+                            name,
+                            etag: folder.tag,
+                        });
+                    }
+                }
+            }
+        }
+        Ok(entries)
+    }
+
+    async fn get(&self, path: &str) -> Result<Vec<u8>, AppError> {
+        let response = self
+            .client
+            .get(path)
+            .await
+            .map_err(|e| webdav_error("Download", e))?;
+        if response.status() == reqwest::StatusCode::UNAUTHORIZED {
+            return Err(AppError::PermissionDenied(format!(
+                "Download rejected (401): {}",
+                path
+            )));
+        }
+        let bytes = response
+            .bytes()
+            .await
+            .map_err(|e| AppError::Other(format!("Read response failed: {:?}", e)))?;
+        Ok(bytes.to_vec())
+    }
+
+    /// Per WebDAV mit `OC-Checksum`-Header, den Nextcloud zur Datei speichert
+    async fn put(&self, path: &str, data: Vec<u8>) -> Result<(), AppError> {
+        webdav_put(&self.client, path, data, false)
+            .await
+            .map(|_| ())
+    }
+
+    /// `If-None-Match: *`; HTTP 412 heißt, die Datei gab es schon
+    async fn put_if_absent(&self, path: &str, data: Vec<u8>) -> Result<bool, AppError> {
+        webdav_put(&self.client, path, data, true).await
+    }
+
+    async fn delete(&self, path: &str) -> Result<(), AppError> {
+        self.client
+            .delete(path)
+            .await
+            .map_err(|e| webdav_error("Delete", e))
+    }
+
+    async fn mkcol(&self, path: &str) -> Result<(), AppError> {
+        self.client
+            .mkcol(path)
+            .await
+            .map_err(|e| webdav_error("MKCOL", e))
+    }
+}
+
+/// PUT mit `OC-Checksum`; mit `if_absent` zusätzlich `If-None-Match: *`
+/// (`Ok(false)` bei HTTP 412)
+async fn webdav_put(
+    client: &reqwest_dav::Client,
+    path: &str,
+    data: Vec<u8>,
+    if_absent: bool,
+) -> Result<bool, AppError> {
+    let checksum = format!("SHA256:{}", photo_checksum::content_checksum(&data));
+    let mut request = client
+        .start_request(reqwest::Method::PUT, path)
+        .await
+        .map_err(|e| webdav_error("Upload", e))?
+        .header("OC-Checksum", checksum);
+    if if_absent {
+        request = request.header("If-None-Match", "*");
+    }
+    let response = request
+        .body(data)
+        .send()
+        .await
+        .map_err(|e| AppError::Other(format!("Upload failed: {:?}", e)))?;
+    match response.status() {
+        status if status.is_success() => Ok(true),
+        reqwest::StatusCode::PRECONDITION_FAILED if if_absent => Ok(false),
+        reqwest::StatusCode::UNAUTHORIZED => Err(AppError::PermissionDenied(format!(
+            "Upload rejected (401): {}",
+            path
+        ))),
+        status => Err(AppError::Other(format!(
+            "Upload failed ({}): {}",
+            status, path
+        ))),
+    }
+}
+
+/// reqwest_dav meldet unerwartete Statuscodes nur als `response_code` im Fehler
+fn webdav_error(action: &str, e: reqwest_dav::Error) -> AppError {
+    let detail = format!("{:?}", e);
+    if detail.contains("response_code: 401") {
+        AppError::PermissionDenied(format!("{} rejected (401): {}", action, detail))
+    } else {
+        AppError::Other(format!("{} failed: {}", action, detail))
+    }
+}
+
+fn href_name(href: &str) -> String {
+    href.trim_end_matches('/')
+        .rsplit('/')
+        .next()
+        .unwrap_or("")
+        .to_string()
+}
+
+#[derive(Debug, Default)]
+struct MemoryStore {
+    /// Pfad → (Inhalt, Version für das ETag)
+    files: BTreeMap<String, (Vec<u8>, u64)>,
+    folders: BTreeSet<String>,
+    /// Verzeichnis → Version der letzten Änderung darunter
+    folder_versions: HashMap<String, u64>,
+    next_version: u64,
+    /// Simuliert ein widerrufenes App-Passwort
+    credentials_revoked: bool,
+    /// Anzahl der Zugriffe über das [`SyncBackend`]-Trait
+    requests: u64,
+}
+
+/// In-Memory-Ersatz für die Nextcloud (Tests, Demos)
+///
+/// Klone teilen denselben Speicher. ETags ändern sich bei jedem `put`.
+#[derive(Debug, Clone, Default)]
+pub struct MemoryBackend {
+    store: Arc<Mutex<MemoryStore>>,
+}
+
+impl MemoryBackend {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Liefert das prozessweite Backend mit diesem Namen (`memory://<name>`)
+    pub fn shared(name: &str) -> Self {
+        static REGISTRY: OnceLock<Mutex<HashMap<String, MemoryBackend>>> = OnceLock::new();
+        REGISTRY
+            .get_or_init(Default::default)
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .entry(name.to_string())
+            .or_default()
+            .clone()
+    }
+
+    /// Alle gespeicherten Dateipfade (sortiert)
+    pub fn file_paths(&self) -> Vec<String> {
+        self.lock().files.keys().cloned().collect()
+    }
+
+    /// Lässt alle weiteren Zugriffe wie bei einem widerrufenen App-Passwort scheitern
+    pub fn revoke_credentials(&self) {
+        self.lock().credentials_revoked = true;
+    }
+
+    /// Bisherige Zugriffe über das [`SyncBackend`]-Trait (jeder entspricht einem HTTP-Request)
+    pub fn request_count(&self) -> u64 {
+        self.lock().requests
+    }
+
+    fn check_auth(&self) -> Result<(), AppError> {
+        let mut store = self.lock();
+        store.requests += 1;
+        if store.credentials_revoked {
+            return Err(AppError::PermissionDenied(
+                "Credentials rejected (401)".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, MemoryStore> {
+        self.store.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn list(&self, path: &str) -> Result<Vec<RemoteEntry>, AppError> {
+        let dir = normalize(path);
+        let store = self.lock();
+        let prefix = if dir.is_empty() {
+            String::new()
+        } else {
+            format!("{}/", dir)
+        };
+
+        if !dir.is_empty()
+            && !store.folders.contains(&dir)
+            && !store.folders.iter().any(|f| f.starts_with(&prefix))
+            && !store.files.keys().any(|p| p.starts_with(&prefix))
+        {
+            return Err(AppError::NotFound(path.to_string()));
+        }
+
+        let mut folders = BTreeSet::new();
+        let mut entries = Vec::new();
+        let children = store
+            .folders
+            .iter()
+            .map(|f| (f, None))
+            .chain(store.files.iter().map(|(p, (_, v))| (p, Some(*v))));
+        for (child, version) in children {
+            let Some(rest) = child.strip_prefix(&prefix) else {
+                continue;
+            };
+            match rest.split_once('/') {
+                Some((folder, _)) => {
+                    folders.insert(folder.to_string());
+                }
+                None if version.is_none() => {
+                    folders.insert(rest.to_string());
+                }
+                None => entries.push(RemoteEntry::File {
+                    name: rest.to_string(),
+                    etag: version.map(|v| format!("\"{}\"", v)),
+                }),
+            }
+        }
+        entries.extend(folders.into_iter().filter(|f| !f.is_empty()).map(|name| {
+            let etag = store
+                .folder_versions
+                .get(&format!("{}{}", prefix, name))
+                .map(|v| format!("\"{}\"", v));
+            RemoteEntry::Folder { name, etag }
+        }));
+        Ok(entries)
+    }
+
+    fn get(&self, path: &str) -> Result<Vec<u8>, AppError> {
+        self.lock()
+            .files
+            .get(&normalize(path))
+            .map(|(data, _)| data.clone())
+            .ok_or_else(|| AppError::NotFound(path.to_string()))
+    }
+
+    fn put(&self, path: &str, data: Vec<u8>) {
+        let mut store = self.lock();
+        let path = normalize(path);
+        let version = store.touch(&path);
+        store.files.insert(path, (data, version));
+    }
+
+    fn put_new(&self, path: &str, data: Vec<u8>) -> bool {
+        let mut store = self.lock();
+        let path = normalize(path);
+        if store.files.contains_key(&path) {
+            return false;
+        }
+        let version = store.touch(&path);
+        store.files.insert(path, (data, version));
+        true
+    }
+
+    fn delete(&self, path: &str) {
+        let mut store = self.lock();
+        let path = normalize(path);
+        if store.files.remove(&path).is_some() {
+            store.touch(&path);
+        }
+    }
+
+    fn mkcol(&self, path: &str) {
+        let mut store = self.lock();
+        let path = normalize(path);
+        if store.folders.insert(path.clone()) {
+            store.touch(&path);
+        }
+    }
+}
+
+impl MemoryStore {
+    /// Neue Version für eine Änderung an `path`; alle übergeordneten
+    /// Verzeichnisse bekommen damit ein neues ETag
+    fn touch(&mut self, path: &str) -> u64 {
+        self.next_version += 1;
+        let version = self.next_version;
+        let mut parent = path;
+        while let Some((dir, _)) = parent.rsplit_once('/') {
+            self.folder_versions.insert(dir.to_string(), version);
+            parent = dir;
+        }
+        version
+    }
+}
+
+impl SyncBackend for MemoryBackend {
+    async fn list(&self, path: &str) -> Result<Vec<RemoteEntry>, AppError> {
+        self.check_auth()?;
+        MemoryBackend::list(self, path)
+    }
+
+    async fn get(&self, path: &str) -> Result<Vec<u8>, AppError> {
+        self.check_auth()?;
+        MemoryBackend::get(self, path)
+    }
+
+    async fn put(&self, path: &str, data: Vec<u8>) -> Result<(), AppError> {
+        self.check_auth()?;
+        MemoryBackend::put(self, path, data);
+        Ok(())
+    }
+
+    async fn put_if_absent(&self, path: &str, data: Vec<u8>) -> Result<bool, AppError> {
+        self.check_auth()?;
+        Ok(self.put_new(path, data))
+    }
+
+    async fn delete(&self, path: &str) -> Result<(), AppError> {
+        self.check_auth()?;
+        MemoryBackend::delete(self, path);
+        Ok(())
+    }
+
+    async fn mkcol(&self, path: &str) -> Result<(), AppError> {
+        self.check_auth()?;
+        MemoryBackend::mkcol(self, path);
+        Ok(())
+    }
+}
+
+/// Sync-"Server" in einem Ordner des Dateisystems (`file://<pfad>`)
+///
+/// ETags von Dateien ergeben sich aus Änderungszeit und Größe; Verzeichnisse
+/// haben keine (das Dateisystem merkt sich keine Änderungen tiefer im Baum),
+/// der Download liest sie deshalb jedes Mal.
+#[derive(Debug, Clone)]
+pub struct LocalFolderBackend {
+    root: PathBuf,
+}
+
+impl LocalFolderBackend {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    fn path_of(&self, path: &str) -> PathBuf {
+        self.root.join(normalize(path))
+    }
+
+    fn create_parent(file: &std::path::Path) -> Result<(), AppError> {
+        if let Some(parent) = file.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        Ok(())
+    }
+}
+
+fn io_error(path: &std::path::Path, e: std::io::Error) -> AppError {
+    match e.kind() {
+        std::io::ErrorKind::NotFound => AppError::NotFound(path.display().to_string()),
+        _ => AppError::Filesystem(e),
+    }
+}
+
+impl SyncBackend for LocalFolderBackend {
+    async fn list(&self, path: &str) -> Result<Vec<RemoteEntry>, AppError> {
+        let dir = self.path_of(path);
+        let mut entries = Vec::new();
+        for entry in std::fs::read_dir(&dir).map_err(|e| io_error(&dir, e))? {
+            let entry = entry?;
+            let name = entry.file_name().to_string_lossy().to_string();
+            let meta = entry.metadata()?;
+            if meta.is_dir() {
+                entries.push(RemoteEntry::Folder { name, etag: None });
+            } else {
+                let modified = meta
+                    .modified()
+                    .ok()
+                    .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+                    .map(|d| d.as_nanos())
+                    .unwrap_or(0);
+                entries.push(RemoteEntry::File {
+                    name,
+                    etag: Some(format!("\"{}-{}\"", modified, meta.len())),
+                });
+            }
+        }
+        Ok(entries)
+    }
+
+    async fn get(&self, path: &str) -> Result<Vec<u8>, AppError> {
+        let file = self.path_of(path);
+        std::fs::read(&file).map_err(|e| io_error(&file, e))
+    }
+
+    async fn put(&self, path: &str, data: Vec<u8>) -> Result<(), AppError> {
+        let file = self.path_of(path);
+        Self::create_parent(&file)?;
+        std::fs::write(&file, data).map_err(|e| io_error(&file, e))
+    }
+
+    async fn put_if_absent(&self, path: &str, data: Vec<u8>) -> Result<bool, AppError> {
+        use std::io::Write;
+
+        let file = self.path_of(path);
+        Self::create_parent(&file)?;
+        match std::fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&file)
+        {
+            Ok(mut handle) => {
+                handle.write_all(&data)?;
+                Ok(true)
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => Ok(false),
+            Err(e) => Err(io_error(&file, e)),
+        }
+    }
+
+    async fn delete(&self, path: &str) -> Result<(), AppError> {
+        let file = self.path_of(path);
+        std::fs::remove_file(&file).map_err(|e| io_error(&file, e))
+    }
+
+    /// Legt auch den Wurzelordner an, falls er (noch) fehlt
+    async fn mkcol(&self, path: &str) -> Result<(), AppError> {
+        let dir = self.path_of(path);
+        std::fs::create_dir_all(&dir).map_err(|e| io_error(&dir, e))
+    }
+}
+
+fn normalize(path: &str) -> String {
+    path.trim_matches('/').to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_memory_list_direct_children() {
+        let backend = MemoryBackend::new();
+        backend.mkcol("/base/sync");
+        backend.put("/base/sync/ops/dev1/202501/a.ndjson", b"x".to_vec());
+        backend.put("/base/sync/photos/p.jpg", b"y".to_vec());
+
+        let mut names: Vec<String> = backend
+            .list("/base/sync")
+            .unwrap()
+            .iter()
+            .map(|e| e.name().to_string())
+            .collect();
+        names.sort();
+        assert_eq!(names, vec!["ops", "photos"]);
+
+        let files = backend.list("/base/sync/photos/").unwrap();
+        assert!(matches!(
+            &files[..],
+            [RemoteEntry::File { name, etag: Some(_) }] if name == "p.jpg"
+        ));
+        assert!(backend.list("/base/missing").is_err());
+    }
+
+    #[test]
+    fn test_memory_etag_changes_on_put() {
+        let backend = MemoryBackend::new();
+        backend.put("/a/f.ndjson", b"1".to_vec());
+        let first = backend.list("/a").unwrap();
+        backend.put("/a/f.ndjson", b"2".to_vec());
+        let second = backend.list("/a").unwrap();
+        assert_ne!(first, second);
+        assert_eq!(backend.get("/a/f.ndjson").unwrap(), b"2".to_vec());
+    }
+
+    #[tokio::test]
+    async fn test_put_if_absent_keeps_existing_file() {
+        let backend = ConfiguredBackend::Memory(MemoryBackend::new());
+        assert!(backend.put_if_absent("/a/f", b"1".to_vec()).await.unwrap());
+        assert!(!backend.put_if_absent("/a/f", b"2".to_vec()).await.unwrap());
+        assert_eq!(backend.get("/a/f").await.unwrap(), b"1".to_vec());
+    }
+
+    #[test]
+    fn test_memory_folder_etag_changes_below() {
+        let backend = MemoryBackend::new();
+        backend.put("/a/b/c/f.ndjson", b"1".to_vec());
+        backend.put("/a/x/g.ndjson", b"1".to_vec());
+        let folder_etag = |name: &str| {
+            backend
+                .list("/a")
+                .unwrap()
+                .into_iter()
+                .find_map(|e| match e {
+                    RemoteEntry::Folder { name: n, etag } if n == name => etag,
+                    _ => None,
+                })
+        };
+
+        let (b, x) = (folder_etag("b"), folder_etag("x"));
+        assert!(b.is_some());
+        backend.put("/a/b/c/h.ndjson", b"2".to_vec());
+        assert_ne!(folder_etag("b"), b);
+        assert_eq!(folder_etag("x"), x);
+
+        let b = folder_etag("b");
+        backend.delete("/a/b/c/f.ndjson");
+        assert_ne!(folder_etag("b"), b);
+    }
+
+    #[test]
+    fn test_from_settings_memory_scheme_is_shared() {
+        let settings = SyncSettings::new(
+            "memory://backend-test".to_string(),
+            "user".to_string(),
+            "pass".to_string(),
+            "/Stalltagebuch".to_string(),
+        );
+        let Ok(ConfiguredBackend::Memory(a)) = ConfiguredBackend::from_settings(&settings) else {
+            panic!("expected memory backend");
+        };
+        a.put("/x/y", vec![1]);
+        assert_eq!(
+            MemoryBackend::shared("backend-test").file_paths(),
+            vec!["x/y"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_local_folder_backend() {
+        let root = std::env::temp_dir().join(format!("sync-folder-{}", uuid::Uuid::new_v4()));
+        let settings = SyncSettings::new(
+            format!("{}{}", FILE_SCHEME, root.display()),
+            "user".to_string(),
+            "pass".to_string(),
+            "/Stalltagebuch".to_string(),
+        );
+        let backend = ConfiguredBackend::from_settings(&settings).unwrap();
+        assert!(matches!(backend, ConfiguredBackend::Local(_)));
+
+        assert!(backend.list("/Stalltagebuch/sync").await.is_err());
+        backend.mkcol("/Stalltagebuch").await.unwrap();
+        backend.mkcol("/Stalltagebuch").await.unwrap();
+        backend
+            .put("/Stalltagebuch/sync/ops/a.ndjson", b"1".to_vec())
+            .await
+            .unwrap();
+        assert!(!backend
+            .put_if_absent("/Stalltagebuch/sync/ops/a.ndjson", b"2".to_vec())
+            .await
+            .unwrap());
+        assert_eq!(
+            backend
+                .get("/Stalltagebuch/sync/ops/a.ndjson")
+                .await
+                .unwrap(),
+            b"1".to_vec()
+        );
+
+        let entries = backend.list("/Stalltagebuch/sync").await.unwrap();
+        assert_eq!(
+            entries,
+            vec![RemoteEntry::Folder {
+                name: "ops".to_string(),
+                etag: None
+            }]
+        );
+        let files = backend.list("/Stalltagebuch/sync/ops/").await.unwrap();
+        assert!(matches!(
+            &files[..],
+            [RemoteEntry::File { name, etag: Some(_) }] if name == "a.ndjson"
+        ));
+
+        backend
+            .delete("/Stalltagebuch/sync/ops/a.ndjson")
+            .await
+            .unwrap();
+        assert!(matches!(
+            backend.get("/Stalltagebuch/sync/ops/a.ndjson").await,
+            Err(AppError::NotFound(_))
+        ));
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,164 @@
+//! Dauerhaftes Protokoll der Sync-Durchläufe
+//!
+//! Jeder Durchlauf von `background_sync` (Hintergrund und "Jetzt
+//! synchronisieren") landet mit Dauer, Zählern und ggf. Fehler in
+//! `sync_history`. Alte Einträge werden beim Schreiben entfernt.
+
+use crate::error::AppError;
+use crate::models::SyncRun;
+use rusqlite::{params, Connection};
+
+/// Einträge älter als das werden gelöscht
+pub const HISTORY_RETENTION_DAYS: i64 = 30;
+/// Obergrenze unabhängig vom Alter (Desktop synchronisiert alle 30 s)
+pub const MAX_HISTORY_ENTRIES: i64 = 1000;
+
+/// Speichert einen Durchlauf und räumt alte Einträge auf
+pub fn record_run(conn: &Connection, run: &SyncRun) -> Result<i64, AppError> {
+    conn.execute(
+        "INSERT INTO sync_history (started_at, duration_ms, on_demand, operations_uploaded, operations_downloaded, photos_uploaded, photos_downloaded, bytes_uploaded, bytes_downloaded, request_count, error)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+        params![
+            run.started_at,
+            run.duration_ms,
+            run.on_demand,
+            run.operations_uploaded as i64,
+            run.operations_downloaded as i64,
+            run.photos_uploaded as i64,
+            run.photos_downloaded as i64,
+            run.bytes_uploaded as i64,
+            run.bytes_downloaded as i64,
+            run.request_count as i64,
+            run.error,
+        ],
+    )?;
+    let id = conn.last_insert_rowid();
+
+    prune(
+        conn,
+        run.started_at - HISTORY_RETENTION_DAYS * 24 * 60 * 60 * 1000,
+    )?;
+    Ok(id)
+}
+
+/// Neueste Durchläufe zuerst; mit `failures_only` nur fehlgeschlagene
+pub fn list_runs(
+    conn: &Connection,
+    failures_only: bool,
+    limit: usize,
+) -> Result<Vec<SyncRun>, AppError> {
+    let mut stmt = conn.prepare(
+        "SELECT id, started_at, duration_ms, on_demand, operations_uploaded, operations_downloaded, photos_uploaded, photos_downloaded, bytes_uploaded, bytes_downloaded, request_count, error
+         FROM sync_history
+         WHERE ?1 = 0 OR error IS NOT NULL
+         ORDER BY started_at DESC, id DESC
+         LIMIT ?2",
+    )?;
+    let rows = stmt.query_map(params![failures_only, limit as i64], |row| {
+        Ok(SyncRun {
+            id: row.get(0)?,
+            started_at: row.get(1)?,
+            duration_ms: row.get(2)?,
+            on_demand: row.get(3)?,
+            operations_uploaded: row.get::<_, i64>(4)? as usize,
+            operations_downloaded: row.get::<_, i64>(5)? as usize,
+            photos_uploaded: row.get::<_, i64>(6)? as usize,
+            photos_downloaded: row.get::<_, i64>(7)? as usize,
+            bytes_uploaded: row.get::<_, i64>(8)? as u64,
+            bytes_downloaded: row.get::<_, i64>(9)? as u64,
+            request_count: row.get::<_, i64>(10)? as u64,
+            error: row.get(11)?,
+        })
+    })?;
+    Ok(rows.collect::<Result<Vec<_>, _>>()?)
+}
+
+/// Löscht das gesamte Protokoll
+pub fn clear_history(conn: &Connection) -> Result<(), AppError> {
+    conn.execute("DELETE FROM sync_history", [])?;
+    Ok(())
+}
+
+/// Entfernt Einträge vor `cutoff_ms` und alles über [`MAX_HISTORY_ENTRIES`]
+fn prune(conn: &Connection, cutoff_ms: i64) -> Result<(), AppError> {
+    conn.execute(
+        "DELETE FROM sync_history WHERE started_at < ?1",
+        [cutoff_ms],
+    )?;
+    conn.execute(
+        "DELETE FROM sync_history WHERE id NOT IN (
+            SELECT id FROM sync_history ORDER BY started_at DESC, id DESC LIMIT ?1
+        )",
+        [MAX_HISTORY_ENTRIES],
+    )?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::schema;
+
+    fn run(started_at: i64, error: Option<&str>) -> SyncRun {
+        SyncRun {
+            id: 0,
+            started_at,
+            duration_ms: 1200,
+            on_demand: false,
+            operations_uploaded: 3,
+            operations_downloaded: 5,
+            photos_uploaded: 1,
+            photos_downloaded: 2,
+            bytes_uploaded: 2048,
+            bytes_downloaded: 4096,
+            request_count: 12,
+            error: error.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_runs_are_listed_newest_first_and_filtered() {
+        let conn = Connection::open_in_memory().unwrap();
+        schema::init_schema(&conn).unwrap();
+        let now = chrono::Utc::now().timestamp_millis();
+
+        record_run(&conn, &run(now - 2000, None)).unwrap();
+        record_run(&conn, &run(now - 1000, Some("Server nicht erreichbar"))).unwrap();
+        record_run(&conn, &run(now, None)).unwrap();
+
+        let all = list_runs(&conn, false, 10).unwrap();
+        assert_eq!(all.len(), 3);
+        assert_eq!(all[0].started_at, now);
+        assert_eq!(all[0].operations_downloaded, 5);
+        assert_eq!(all[0].photos_downloaded, 2);
+        assert_eq!(all[0].bytes_downloaded, 4096);
+        assert_eq!(all[0].request_count, 12);
+
+        let failures = list_runs(&conn, true, 10).unwrap();
+        assert_eq!(failures.len(), 1);
+        assert!(failures[0].is_failure());
+        assert_eq!(
+            failures[0].error.as_deref(),
+            Some("Server nicht erreichbar")
+        );
+
+        clear_history(&conn).unwrap();
+        assert!(list_runs(&conn, false, 10).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_old_runs_are_pruned() {
+        let conn = Connection::open_in_memory().unwrap();
+        schema::init_schema(&conn).unwrap();
+        let day = 24 * 60 * 60 * 1000;
+        let now = chrono::Utc::now().timestamp_millis();
+
+        record_run(&conn, &run(now - (HISTORY_RETENTION_DAYS + 1) * day, None)).unwrap();
+        record_run(&conn, &run(now - day, None)).unwrap();
+        record_run(&conn, &run(now, None)).unwrap();
+
+        let runs = list_runs(&conn, false, 10).unwrap();
+        assert_eq!(runs.len(), 2);
+        assert!(runs.iter().all(|r| r.started_at >= now - day));
+    }
+}
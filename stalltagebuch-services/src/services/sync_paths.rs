@@ -1,18 +1,20 @@
 /// Sync path constants for new multi-master sync layout
-/// 
+///
 /// Directory structure:
-/// ```
+/// ```text
 /// Stalltagebuch/
 /// └── sync/
 ///     ├── ops/              # append-only operations
 ///     │   └── <device-id>/
 ///     │       └── <YYYYMM>/
-///     │           └── <ULID>.ndjson
+///     │           └── <ULID>.ndjson.gz
 ///     ├── snapshots/        # materialized states
 ///     │   └── <collection>/
 ///     │       └── <YYYYMMDD>/
 ///     │           └── <ULID>.json
 ///     └── control/          # coordination (leases/markers)
+///         ├── compactor.lease   # held while a device compacts
+///         ├── encryption.json   # salt and key check for passphrases
 ///         └── <collection>/
 ///             ├── latest.json
 ///             └── compactor/<ULID>.lease
@@ -24,12 +26,32 @@ pub const SYNC_BASE: &str = "sync";
 /// Operations directory
 pub const OPS_DIR: &str = "sync/ops";
 
+/// Photo files (originals and thumbnails, flat)
+pub const PHOTOS_DIR: &str = "sync/photos";
+
 /// Snapshots directory
 pub const SNAPSHOTS_DIR: &str = "sync/snapshots";
 
 /// Control directory for coordination
 pub const CONTROL_DIR: &str = "sync/control";
 
+/// Lease file: only the device that created it may compact
+pub const COMPACTOR_LEASE: &str = "sync/control/compactor.lease";
+
+/// Salt, iterations and key check for deriving the key from a passphrase
+pub const KEY_PARAMS: &str = "sync/control/encryption.json";
+
+/// Extension of uncompressed op files (written by older app versions)
+pub const OP_FILE_EXT: &str = ".ndjson";
+
+/// Extension of gzip-compressed op files
+pub const OP_FILE_GZ_EXT: &str = ".ndjson.gz";
+
+/// Is `name` an op file, compressed or not?
+pub fn is_op_file(name: &str) -> bool {
+    name.ends_with(OP_FILE_EXT) || name.ends_with(OP_FILE_GZ_EXT)
+}
+
 /// Build ops path for a device and month
 pub fn ops_path(device_id: &str, year_month: &str) -> String {
     format!("{}/{}/{}", OPS_DIR, device_id, year_month)
@@ -75,6 +97,13 @@ mod tests {
         assert_eq!(path, "sync/ops/device-123/202511");
     }
 
+    #[test]
+    fn test_is_op_file() {
+        assert!(is_op_file("01JGTEST.ndjson"));
+        assert!(is_op_file("01JGTEST.ndjson.gz"));
+        assert!(!is_op_file("latest.json"));
+    }
+
     #[test]
     fn test_snapshot_path() {
         let path = snapshot_path("quails", "20251112");
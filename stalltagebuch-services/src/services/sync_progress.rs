@@ -0,0 +1,146 @@
+//! Fortschritt des laufenden Sync-Zyklus
+//!
+//! Ein prozessweiter `watch`-Kanal meldet die aktuelle Phase samt Zählern
+//! (z.B. "Op-Dateien 3/12"), damit die Oberfläche einen echten
+//! Fortschrittsbalken zeigen kann. Gemeldet wird nur, solange ein Zyklus
+//! aus [`background_sync`](crate::services::background_sync) läuft; direkte
+//! Aufrufe von Download/Upload (Tests, Neuaufbau) bleiben stumm.
+
+use crate::services::background_sync::SyncStats;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::OnceLock;
+use tokio::sync::watch;
+
+/// Phase eines Sync-Zyklus
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum SyncPhase {
+    /// Noch kein Zyklus gelaufen
+    #[default]
+    Idle,
+    /// Verzeichnisse auf dem Server werden gelistet
+    Listing,
+    DownloadingOps {
+        done: usize,
+        total: usize,
+    },
+    /// Heruntergeladene Operationen werden in die Datenbank übernommen
+    Applying {
+        ops: usize,
+    },
+    DownloadingPhotos {
+        done: usize,
+        total: usize,
+    },
+    UploadingOps,
+    UploadingPhotos {
+        done: usize,
+        total: usize,
+    },
+    Done(SyncStats),
+    Failed(String),
+}
+
+impl SyncPhase {
+    /// Läuft gerade ein Zyklus?
+    pub fn is_running(&self) -> bool {
+        !matches!(
+            self,
+            SyncPhase::Idle | SyncPhase::Done(_) | SyncPhase::Failed(_)
+        )
+    }
+
+    /// Anteil 0.0–1.0 für Phasen mit Zählern
+    pub fn fraction(&self) -> Option<f64> {
+        match *self {
+            SyncPhase::DownloadingOps { done, total }
+            | SyncPhase::DownloadingPhotos { done, total }
+            | SyncPhase::UploadingPhotos { done, total }
+                if total > 0 =>
+            {
+                Some(done.min(total) as f64 / total as f64)
+            }
+            _ => None,
+        }
+    }
+}
+
+static PROGRESS: OnceLock<watch::Sender<SyncPhase>> = OnceLock::new();
+static CYCLE_RUNNING: AtomicBool = AtomicBool::new(false);
+
+fn sender() -> &'static watch::Sender<SyncPhase> {
+    PROGRESS.get_or_init(|| watch::channel(SyncPhase::Idle).0)
+}
+
+/// Abonniert die Phasenwechsel; der aktuelle Stand liegt sofort vor
+pub fn subscribe() -> watch::Receiver<SyncPhase> {
+    sender().subscribe()
+}
+
+pub fn current() -> SyncPhase {
+    sender().borrow().clone()
+}
+
+/// Meldet eine Phase, sofern gerade ein Zyklus läuft
+pub(crate) fn report(phase: SyncPhase) {
+    if CYCLE_RUNNING.load(Ordering::SeqCst) {
+        sender().send_replace(phase);
+    }
+}
+
+/// Beginn eines Sync-Zyklus; die erste Phase meldet der Download
+pub(crate) fn begin() {
+    CYCLE_RUNNING.store(true, Ordering::SeqCst);
+}
+
+/// Ende eines Sync-Zyklus mit Ergebnis
+pub(crate) fn finish(phase: SyncPhase) {
+    CYCLE_RUNNING.store(false, Ordering::SeqCst);
+    sender().send_replace(phase);
+}
+
+/// Übersprungener Zyklus (nicht eingerichtet, pausiert, aufgeschoben):
+/// das letzte Ergebnis bleibt stehen
+pub(crate) fn cancel() {
+    CYCLE_RUNNING.store(false, Ordering::SeqCst);
+    sender().send_if_modified(|phase| {
+        let running = phase.is_running();
+        if running {
+            *phase = SyncPhase::Idle;
+        }
+        running
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_phases_only_reported_during_cycle() {
+        let mut rx = subscribe();
+
+        report(SyncPhase::UploadingOps);
+        assert!(!rx.borrow_and_update().is_running());
+
+        begin();
+        report(SyncPhase::DownloadingOps { done: 3, total: 12 });
+        let phase = rx.borrow_and_update().clone();
+        assert!(phase.is_running());
+        assert_eq!(phase.fraction(), Some(0.25));
+
+        let stats = SyncStats {
+            operations_uploaded: 1,
+            operations_downloaded: 12,
+            photos_uploaded: 0,
+            photos_downloaded: 0,
+        };
+        finish(SyncPhase::Done(stats.clone()));
+        report(SyncPhase::Listing);
+        assert_eq!(current(), SyncPhase::Done(stats.clone()));
+
+        // Skipped cycle keeps the last result
+        begin();
+        cancel();
+        assert_eq!(current(), SyncPhase::Done(stats));
+    }
+}
@@ -0,0 +1,415 @@
+use crate::error::AppError;
+use crate::models::{OriginalsPolicy, SyncSettings};
+use crate::services::nextcloud_auth::LoginFlowSession;
+use rusqlite::{Connection, OptionalExtension, Result};
+
+/// Login Flow, der beim Beenden der App noch auf Bestätigung wartete
+#[derive(Debug, Clone, PartialEq)]
+pub struct PendingLogin {
+    pub session: LoginFlowSession,
+    /// Vom Nutzer gewählter Remote-Ordner
+    pub remote_path: String,
+    /// Proxy, über den der Flow gestartet wurde
+    pub proxy_url: Option<String>,
+}
+
+/// Loads the synchronization settings from the database
+pub fn load_sync_settings(conn: &Connection) -> Result<Option<SyncSettings>, AppError> {
+    let mut stmt = conn.prepare(
+        "SELECT id, server_url, username, app_password, remote_path, enabled, last_sync, device_id, format_version, initial_upload_done, credentials_invalid, cert_fingerprint, proxy_url, photo_storage_limit_mb, upload_concurrency, originals_policy, pause_on_metered, pause_on_low_battery, paused, excluded_entity_types, export_path, created_at, updated_at 
+         FROM sync_settings 
+         ORDER BY id DESC 
+         LIMIT 1"
+    )?;
+
+    let result = stmt.query_row([], |row| {
+        Ok(SyncSettings {
+            id: row.get(0)?,
+            server_url: row.get(1)?,
+            username: row.get(2)?,
+            app_password: row.get(3)?,
+            remote_path: row.get(4)?,
+            enabled: row.get(5)?,
+            last_sync: row.get(6)?,
+            device_id: row.get(7)?,
+            format_version: row.get(8)?,
+            initial_upload_done: row.get(9)?,
+            credentials_invalid: row.get(10)?,
+            cert_fingerprint: row.get(11)?,
+            proxy_url: row.get(12)?,
+            photo_storage_limit_mb: row.get(13)?,
+            upload_concurrency: row.get(14)?,
+            originals_policy: OriginalsPolicy::parse(&row.get::<_, String>(15)?)
+                .unwrap_or_default(),
+            pause_on_metered: row.get(16)?,
+            pause_on_low_battery: row.get(17)?,
+            paused: row.get(18)?,
+            excluded_entity_types: split_entity_types(&row.get::<_, String>(19)?),
+            export_path: row.get(20)?,
+            created_at: row.get(21)?,
+            updated_at: row.get(22)?,
+        })
+    });
+
+    match result {
+        Ok(settings) => Ok(Some(settings)),
+        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+        Err(e) => Err(AppError::Database(e)),
+    }
+}
+
+/// Saves or updates the synchronization settings
+pub fn save_sync_settings(conn: &Connection, settings: &SyncSettings) -> Result<i64, AppError> {
+    // Check if settings already exist
+    let existing = load_sync_settings(conn)?;
+
+    if let Some(existing) = existing {
+        // Update
+        conn.execute(
+            "UPDATE sync_settings 
+             SET server_url = ?1, username = ?2, app_password = ?3, remote_path = ?4, enabled = ?5, device_id = ?6, format_version = ?7, initial_upload_done = ?8, credentials_invalid = ?9, cert_fingerprint = ?10, proxy_url = ?11, photo_storage_limit_mb = ?12, upload_concurrency = ?13, originals_policy = ?14, pause_on_metered = ?15, pause_on_low_battery = ?16, paused = ?17, excluded_entity_types = ?18, export_path = ?19
+             WHERE id = ?20",
+            rusqlite::params![
+                &settings.server_url,
+                &settings.username,
+                &settings.app_password,
+                &settings.remote_path,
+                settings.enabled,
+                &settings.device_id,
+                settings.format_version,
+                settings.initial_upload_done,
+                settings.credentials_invalid,
+                &settings.cert_fingerprint,
+                &settings.proxy_url,
+                settings.photo_storage_limit_mb,
+                settings.upload_concurrency,
+                settings.originals_policy.as_str(),
+                settings.pause_on_metered,
+                settings.pause_on_low_battery,
+                settings.paused,
+                settings.excluded_entity_types.join(","),
+                &settings.export_path,
+                existing.id,
+            ],
+        )?;
+        Ok(existing.id)
+    } else {
+        // Insert
+        conn.execute(
+            "INSERT INTO sync_settings (server_url, username, app_password, remote_path, enabled, device_id, format_version, initial_upload_done, credentials_invalid, cert_fingerprint, proxy_url, photo_storage_limit_mb, upload_concurrency, originals_policy, pause_on_metered, pause_on_low_battery, paused, excluded_entity_types, export_path)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19)",
+            rusqlite::params![
+                &settings.server_url,
+                &settings.username,
+                &settings.app_password,
+                &settings.remote_path,
+                settings.enabled,
+                &settings.device_id,
+                settings.format_version,
+                settings.initial_upload_done,
+                settings.credentials_invalid,
+                &settings.cert_fingerprint,
+                &settings.proxy_url,
+                settings.photo_storage_limit_mb,
+                settings.upload_concurrency,
+                settings.originals_policy.as_str(),
+                settings.pause_on_metered,
+                settings.pause_on_low_battery,
+                settings.paused,
+                settings.excluded_entity_types.join(","),
+                &settings.export_path,
+            ],
+        )?;
+        Ok(conn.last_insert_rowid())
+    }
+}
+
+/// Updates the timestamp of the last synchronization
+pub fn update_last_sync(conn: &Connection) -> Result<(), AppError> {
+    conn.execute(
+        "UPDATE sync_settings SET last_sync = CURRENT_TIMESTAMP WHERE id = (SELECT MAX(id) FROM sync_settings)",
+        [],
+    )?;
+    Ok(())
+}
+
+/// Enables or disables synchronization
+pub fn set_sync_enabled(conn: &Connection, enabled: bool) -> Result<(), AppError> {
+    conn.execute(
+        "UPDATE sync_settings SET enabled = ?1 WHERE id = (SELECT MAX(id) FROM sync_settings)",
+        [enabled],
+    )?;
+    Ok(())
+}
+
+/// Pauses or resumes synchronization; while paused, changes stay queued locally
+pub fn set_sync_paused(conn: &Connection, paused: bool) -> Result<(), AppError> {
+    conn.execute(
+        "UPDATE sync_settings SET paused = ?1 WHERE id = (SELECT MAX(id) FROM sync_settings)",
+        [paused],
+    )?;
+    Ok(())
+}
+
+/// Turns synchronization of one entity type on or off
+///
+/// Ops of an excluded type were neither uploaded nor merged. Turning it back
+/// on therefore forgets which remote files were already read and repeats the
+/// initial upload, so the next sync exchanges everything again (merging is
+/// idempotent).
+pub fn set_entity_type_synced(
+    conn: &Connection,
+    entity_type: &str,
+    synced: bool,
+) -> Result<(), AppError> {
+    let Some(mut settings) = load_sync_settings(conn)? else {
+        return Ok(());
+    };
+    if settings.syncs(entity_type) == synced {
+        return Ok(());
+    }
+
+    if synced {
+        settings.excluded_entity_types.retain(|t| t != entity_type);
+    } else {
+        settings.excluded_entity_types.push(entity_type.to_string());
+    }
+    conn.execute(
+        "UPDATE sync_settings SET excluded_entity_types = ?1, initial_upload_done = initial_upload_done AND ?2 WHERE id = ?3",
+        rusqlite::params![
+            settings.excluded_entity_types.join(","),
+            !synced,
+            settings.id
+        ],
+    )?;
+
+    if synced {
+        crate::services::download_service::reset_manifest(conn)?;
+    }
+    Ok(())
+}
+
+fn split_entity_types(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|t| !t.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Deletes all synchronization settings
+pub fn delete_sync_settings(conn: &Connection) -> Result<(), AppError> {
+    conn.execute("DELETE FROM sync_settings", [])?;
+    Ok(())
+}
+
+/// Marks initial upload as completed
+pub fn set_initial_upload_done(conn: &Connection) -> Result<(), AppError> {
+    conn.execute(
+        "UPDATE sync_settings SET initial_upload_done = 1 WHERE id = (SELECT MAX(id) FROM sync_settings)",
+        [],
+    )?;
+    Ok(())
+}
+
+/// Marks the stored app password as rejected (or valid again) by the server
+pub fn set_credentials_invalid(conn: &Connection, invalid: bool) -> Result<(), AppError> {
+    conn.execute(
+        "UPDATE sync_settings SET credentials_invalid = ?1 WHERE id = (SELECT MAX(id) FROM sync_settings)",
+        [invalid],
+    )?;
+    Ok(())
+}
+
+/// Stores the running login flow so it can be resumed after an app restart
+pub fn save_login_session(
+    conn: &Connection,
+    session: &LoginFlowSession,
+    remote_path: &str,
+    proxy_url: Option<&str>,
+) -> Result<(), AppError> {
+    conn.execute(
+        "INSERT OR REPLACE INTO login_flow_session (id, poll_endpoint, token, login_url, remote_path, started_at, proxy_url)
+         VALUES (1, ?1, ?2, ?3, ?4, ?5, ?6)",
+        (
+            &session.poll_endpoint,
+            &session.token,
+            &session.login_url,
+            remote_path,
+            session.started_at,
+            proxy_url,
+        ),
+    )?;
+    Ok(())
+}
+
+/// Loads a login flow that was still pending when the app was closed
+pub fn load_login_session(conn: &Connection) -> Result<Option<PendingLogin>, AppError> {
+    let pending = conn
+        .query_row(
+            "SELECT poll_endpoint, token, login_url, remote_path, started_at, proxy_url
+             FROM login_flow_session WHERE id = 1",
+            [],
+            |row| {
+                Ok(PendingLogin {
+                    session: LoginFlowSession {
+                        poll_endpoint: row.get(0)?,
+                        token: row.get(1)?,
+                        login_url: row.get(2)?,
+                        started_at: row.get(4)?,
+                    },
+                    remote_path: row.get(3)?,
+                    proxy_url: row.get(5)?,
+                })
+            },
+        )
+        .optional()?;
+    Ok(pending)
+}
+
+/// Removes the stored login flow (finished, failed or cancelled)
+pub fn clear_login_session(conn: &Connection) -> Result<(), AppError> {
+    conn.execute("DELETE FROM login_flow_session", [])?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::schema::init_schema;
+
+    #[test]
+    fn test_credentials_invalid_flag() {
+        let conn = Connection::open_in_memory().unwrap();
+        init_schema(&conn).unwrap();
+        let settings = SyncSettings::new(
+            "https://cloud.example.com".to_string(),
+            "anna".to_string(),
+            "old-password".to_string(),
+            "/Stalltagebuch".to_string(),
+        );
+        save_sync_settings(&conn, &settings).unwrap();
+
+        set_credentials_invalid(&conn, true).unwrap();
+        let mut stored = load_sync_settings(&conn).unwrap().unwrap();
+        assert!(stored.credentials_invalid);
+
+        // Neue Zugangsdaten speichern hebt die Sperre wieder auf
+        stored.app_password = "new-password".to_string();
+        stored.credentials_invalid = false;
+        save_sync_settings(&conn, &stored).unwrap();
+        assert!(
+            !load_sync_settings(&conn)
+                .unwrap()
+                .unwrap()
+                .credentials_invalid
+        );
+    }
+
+    #[test]
+    fn test_originals_policy_roundtrip() {
+        let conn = Connection::open_in_memory().unwrap();
+        init_schema(&conn).unwrap();
+        let mut settings = SyncSettings::new(
+            "https://cloud.example.com".to_string(),
+            "anna".to_string(),
+            "password".to_string(),
+            "/Stalltagebuch".to_string(),
+        );
+        save_sync_settings(&conn, &settings).unwrap();
+        assert_eq!(
+            load_sync_settings(&conn).unwrap().unwrap().originals_policy,
+            OriginalsPolicy::Always
+        );
+
+        settings.originals_policy = OriginalsPolicy::WifiAndCharging;
+        save_sync_settings(&conn, &settings).unwrap();
+        assert_eq!(
+            load_sync_settings(&conn).unwrap().unwrap().originals_policy,
+            OriginalsPolicy::WifiAndCharging
+        );
+    }
+
+    #[test]
+    fn test_pause_conditions_roundtrip() {
+        let conn = Connection::open_in_memory().unwrap();
+        init_schema(&conn).unwrap();
+        let mut settings = SyncSettings::new(
+            "https://cloud.example.com".to_string(),
+            "anna".to_string(),
+            "password".to_string(),
+            "/Stalltagebuch".to_string(),
+        );
+        save_sync_settings(&conn, &settings).unwrap();
+        let loaded = load_sync_settings(&conn).unwrap().unwrap();
+        assert!(!loaded.pause_on_metered);
+        assert!(!loaded.pause_on_low_battery);
+
+        settings.pause_on_metered = true;
+        settings.pause_on_low_battery = true;
+        save_sync_settings(&conn, &settings).unwrap();
+        let loaded = load_sync_settings(&conn).unwrap().unwrap();
+        assert!(loaded.pause_on_metered);
+        assert!(loaded.pause_on_low_battery);
+    }
+
+    #[test]
+    fn test_pause_and_entity_types_roundtrip() {
+        let conn = Connection::open_in_memory().unwrap();
+        init_schema(&conn).unwrap();
+        let mut settings = SyncSettings::new(
+            "https://cloud.example.com".to_string(),
+            "anna".to_string(),
+            "password".to_string(),
+            "/Stalltagebuch".to_string(),
+        );
+        settings.initial_upload_done = true;
+        save_sync_settings(&conn, &settings).unwrap();
+
+        set_sync_paused(&conn, true).unwrap();
+        set_entity_type_synced(&conn, "photo", false).unwrap();
+        set_entity_type_synced(&conn, "egg", false).unwrap();
+        let loaded = load_sync_settings(&conn).unwrap().unwrap();
+        assert!(loaded.paused);
+        assert_eq!(loaded.excluded_entity_types, vec!["photo", "egg"]);
+        assert!(!loaded.syncs("photo"));
+        assert!(loaded.syncs("quail"));
+        assert!(loaded.initial_upload_done);
+
+        // Wieder eingeschaltet: alles noch einmal austauschen
+        set_entity_type_synced(&conn, "photo", true).unwrap();
+        let loaded = load_sync_settings(&conn).unwrap().unwrap();
+        assert_eq!(loaded.excluded_entity_types, vec!["egg"]);
+        assert!(!loaded.initial_upload_done);
+    }
+
+    #[test]
+    fn test_login_session_roundtrip() {
+        let conn = Connection::open_in_memory().unwrap();
+        init_schema(&conn).unwrap();
+        assert!(load_login_session(&conn).unwrap().is_none());
+
+        let session = LoginFlowSession {
+            poll_endpoint: "https://cloud.example.com/login/v2/poll".to_string(),
+            token: "token".to_string(),
+            login_url: "https://cloud.example.com/login/v2/flow/abc".to_string(),
+            started_at: 1_700_000_000_000,
+        };
+        save_login_session(
+            &conn,
+            &session,
+            "/Stalltagebuch",
+            Some("socks5://10.0.0.1:1080"),
+        )
+        .unwrap();
+
+        let pending = load_login_session(&conn).unwrap().unwrap();
+        assert_eq!(pending.session, session);
+        assert_eq!(pending.remote_path, "/Stalltagebuch");
+        assert_eq!(pending.proxy_url.as_deref(), Some("socks5://10.0.0.1:1080"));
+
+        clear_login_session(&conn).unwrap();
+        assert!(load_login_session(&conn).unwrap().is_none());
+    }
+}
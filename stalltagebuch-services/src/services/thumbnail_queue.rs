@@ -0,0 +1,178 @@
+//! Hintergrund-Warteschlange für Thumbnails
+//!
+//! Der Import kopiert nur noch das Original und legt den Datensatz (mit den
+//! festen Namen `<uuid>_small.webp` / `<uuid>_medium.webp`) an. Die
+//! WebP-Dateien aller [`THUMBNAIL_TIERS`] entstehen danach in höchstens
+//! [`MAX_WORKERS`] eigenen Threads. Solange ein Foto wartet, liefert
+//! `photo_service::get_photo_with_download` das Original aus und der
+//! Foto-Upload stellt es zurück.
+
+use crate::error::AppError;
+use crate::models::photo::THUMBNAIL_TIERS;
+use crate::services::photo_service;
+use rusqlite::Connection;
+use std::collections::{HashSet, VecDeque};
+use std::path::PathBuf;
+use std::sync::{Mutex, MutexGuard, OnceLock};
+
+/// Maximal gleichzeitig laufende Thumbnail-Threads
+pub const MAX_WORKERS: usize = 2;
+
+/// Ein zu erstellendes Thumbnail-Paar
+#[derive(Debug, Clone, PartialEq)]
+pub struct ThumbnailJob {
+    pub photo_uuid: String,
+    /// Absoluter Pfad des Originals; die Thumbnails landen daneben
+    pub original_path: PathBuf,
+    pub orientation: Option<u16>,
+}
+
+#[derive(Debug, Default)]
+struct QueueState {
+    jobs: VecDeque<ThumbnailJob>,
+    /// Wartende und gerade bearbeitete Fotos
+    pending: HashSet<String>,
+    workers: usize,
+}
+
+fn state() -> MutexGuard<'static, QueueState> {
+    static STATE: OnceLock<Mutex<QueueState>> = OnceLock::new();
+    STATE
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+}
+
+/// Reiht ein Foto ein (doppelt eingereihte Fotos werden ignoriert)
+pub fn enqueue(job: ThumbnailJob) {
+    let mut state = state();
+    if !state.pending.insert(job.photo_uuid.clone()) {
+        return;
+    }
+    state.jobs.push_back(job);
+    if state.workers < MAX_WORKERS {
+        state.workers += 1;
+        std::thread::spawn(worker);
+    }
+}
+
+/// Anzahl der Fotos, deren Thumbnails noch fehlen
+pub fn pending_thumbnails() -> usize {
+    state().pending.len()
+}
+
+pub fn is_pending(photo_uuid: &str) -> bool {
+    state().pending.contains(photo_uuid)
+}
+
+/// Wartet, bis die Warteschlange leer ist
+pub async fn wait_idle() {
+    while pending_thumbnails() > 0 {
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+    }
+}
+
+fn worker() {
+    loop {
+        let job = {
+            let mut state = state();
+            match state.jobs.pop_front() {
+                Some(job) => job,
+                None => {
+                    state.workers -= 1;
+                    return;
+                }
+            }
+        };
+
+        match photo_service::create_thumbnails(
+            &job.original_path.to_string_lossy(),
+            &job.photo_uuid,
+            job.orientation,
+        ) {
+            Ok(_) => log::debug!("Thumbnails erstellt für {}", job.photo_uuid),
+            Err(e) => log::error!("Thumbnails für {} fehlgeschlagen: {}", job.photo_uuid, e),
+        }
+        state().pending.remove(&job.photo_uuid);
+    }
+}
+
+/// Reiht Fotos erneut ein, deren Original lokal liegt, denen aber eine
+/// Thumbnail-Stufe fehlt (z.B. weil die App während der Erstellung beendet
+/// wurde oder eine neue Stufe hinzugekommen ist)
+pub fn requeue_missing(conn: &Connection) -> Result<usize, AppError> {
+    let mut stmt = conn.prepare(
+        "SELECT uuid, COALESCE(relative_path, path), exif_orientation
+         FROM photos
+         WHERE deleted = 0 AND original_evicted = 0 AND thumbnail_small_path IS NOT NULL",
+    )?;
+    let rows: Vec<(String, String, Option<u16>)> = stmt
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))?
+        .collect::<Result<_, _>>()?;
+
+    let mut queued = 0;
+    for (uuid, original, orientation) in rows {
+        let original_path = PathBuf::from(photo_service::get_absolute_photo_path(&original));
+        let Some(dir) = original_path.parent() else {
+            continue;
+        };
+        let missing = THUMBNAIL_TIERS
+            .iter()
+            .any(|tier| !dir.join(tier.file_name(&uuid)).exists());
+        if !original_path.exists() || !missing {
+            continue;
+        }
+        enqueue(ThumbnailJob {
+            photo_uuid: uuid,
+            original_path,
+            orientation,
+        });
+        queued += 1;
+    }
+    if queued > 0 {
+        log::info!("{} Fotos für Thumbnails erneut eingereiht", queued);
+    }
+    Ok(queued)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_queue_creates_thumbnails_in_background() {
+        let dir = std::env::temp_dir().join(format!("thumb-queue-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let mut uuids = Vec::new();
+        for _ in 0..3 {
+            let uuid = uuid::Uuid::new_v4().to_string();
+            let original = dir.join(format!("{}.jpg", uuid));
+            image::DynamicImage::new_rgb8(600, 300)
+                .save(&original)
+                .unwrap();
+            let job = ThumbnailJob {
+                photo_uuid: uuid.clone(),
+                original_path: original,
+                orientation: Some(6),
+            };
+            enqueue(job.clone());
+            // Doppelt eingereiht zählt nur einmal
+            enqueue(job);
+            uuids.push(uuid);
+        }
+
+        wait_idle().await;
+        for uuid in &uuids {
+            assert!(!is_pending(uuid));
+            let medium = image::open(dir.join(format!("{}_medium.webp", uuid))).unwrap();
+            // Hochkant gedreht (Orientierung 6)
+            assert!(medium.height() > medium.width());
+            for tier in THUMBNAIL_TIERS {
+                assert!(dir.join(tier.file_name(uuid)).exists());
+            }
+        }
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
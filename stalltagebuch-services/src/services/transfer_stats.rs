@@ -0,0 +1,216 @@
+//! Datenverkehr des Syncs
+//!
+//! [`ConfiguredBackend`](crate::services::sync_backend::ConfiguredBackend)
+//! zählt jede Anfrage samt übertragener Nutzdaten in prozessweiten Zählern.
+//! `background_sync` nimmt vor und nach jedem Zyklus einen Stand, speichert
+//! die Differenz beim Durchlauf in `sync_history` und addiert sie auf den
+//! Tag in `sync_transfer_daily`. Die Tagessummen bleiben länger erhalten als
+//! der Verlauf, damit auch auf getakteten Verbindungen der Verbrauch eines
+//! Monats sichtbar ist.
+
+use crate::error::AppError;
+use crate::models::TransferDay;
+use chrono::{Local, TimeZone};
+use rusqlite::{params, Connection};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Tagessummen älter als das werden gelöscht
+pub const DAILY_RETENTION_DAYS: i64 = 90;
+
+static BYTES_UPLOADED: AtomicU64 = AtomicU64::new(0);
+static BYTES_DOWNLOADED: AtomicU64 = AtomicU64::new(0);
+static REQUESTS: AtomicU64 = AtomicU64::new(0);
+
+/// Stand der Zähler seit Prozessstart
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct TransferCounters {
+    pub bytes_uploaded: u64,
+    pub bytes_downloaded: u64,
+    pub request_count: u64,
+}
+
+impl TransferCounters {
+    /// Verkehr zwischen `earlier` und diesem Stand
+    pub fn since(&self, earlier: &TransferCounters) -> TransferCounters {
+        TransferCounters {
+            bytes_uploaded: self.bytes_uploaded.saturating_sub(earlier.bytes_uploaded),
+            bytes_downloaded: self
+                .bytes_downloaded
+                .saturating_sub(earlier.bytes_downloaded),
+            request_count: self.request_count.saturating_sub(earlier.request_count),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.request_count == 0
+    }
+}
+
+/// Zählt eine Anfrage an den Server
+pub(crate) fn count_request(uploaded: usize, downloaded: usize) {
+    REQUESTS.fetch_add(1, Ordering::Relaxed);
+    BYTES_UPLOADED.fetch_add(uploaded as u64, Ordering::Relaxed);
+    BYTES_DOWNLOADED.fetch_add(downloaded as u64, Ordering::Relaxed);
+}
+
+pub fn counters() -> TransferCounters {
+    TransferCounters {
+        bytes_uploaded: BYTES_UPLOADED.load(Ordering::Relaxed),
+        bytes_downloaded: BYTES_DOWNLOADED.load(Ordering::Relaxed),
+        request_count: REQUESTS.load(Ordering::Relaxed),
+    }
+}
+
+/// Lokales Datum (`YYYY-MM-DD`) eines Zeitpunkts in ms
+fn local_day(timestamp_ms: i64) -> String {
+    Local
+        .timestamp_millis_opt(timestamp_ms)
+        .single()
+        .unwrap_or_else(Local::now)
+        .format("%Y-%m-%d")
+        .to_string()
+}
+
+/// Addiert einen Durchlauf auf seinen Tag und räumt alte Tage auf
+pub fn record_day(
+    conn: &Connection,
+    started_at: i64,
+    duration_ms: i64,
+    transfer: &TransferCounters,
+) -> Result<(), AppError> {
+    conn.execute(
+        "INSERT INTO sync_transfer_daily (day, runs, bytes_uploaded, bytes_downloaded, request_count, duration_ms)
+         VALUES (?1, 1, ?2, ?3, ?4, ?5)
+         ON CONFLICT(day) DO UPDATE SET
+            runs = runs + 1,
+            bytes_uploaded = bytes_uploaded + excluded.bytes_uploaded,
+            bytes_downloaded = bytes_downloaded + excluded.bytes_downloaded,
+            request_count = request_count + excluded.request_count,
+            duration_ms = duration_ms + excluded.duration_ms",
+        params![
+            local_day(started_at),
+            transfer.bytes_uploaded as i64,
+            transfer.bytes_downloaded as i64,
+            transfer.request_count as i64,
+            duration_ms,
+        ],
+    )?;
+
+    let cutoff = local_day(started_at - DAILY_RETENTION_DAYS * 24 * 60 * 60 * 1000);
+    conn.execute("DELETE FROM sync_transfer_daily WHERE day < ?1", [cutoff])?;
+    Ok(())
+}
+
+/// Die letzten `days` Tage mit Verkehr, neuester zuerst
+pub fn list_days(conn: &Connection, days: usize) -> Result<Vec<TransferDay>, AppError> {
+    let mut stmt = conn.prepare(
+        "SELECT day, runs, bytes_uploaded, bytes_downloaded, request_count, duration_ms
+         FROM sync_transfer_daily
+         ORDER BY day DESC
+         LIMIT ?1",
+    )?;
+    let rows = stmt.query_map([days as i64], |row| {
+        Ok(TransferDay {
+            day: row.get(0)?,
+            runs: row.get::<_, i64>(1)? as u64,
+            bytes_uploaded: row.get::<_, i64>(2)? as u64,
+            bytes_downloaded: row.get::<_, i64>(3)? as u64,
+            request_count: row.get::<_, i64>(4)? as u64,
+            duration_ms: row.get(5)?,
+        })
+    })?;
+    Ok(rows.collect::<Result<Vec<_>, _>>()?)
+}
+
+/// Summe über alle Tage ab `since_day` (`YYYY-MM-DD`, einschließlich)
+pub fn total_since(conn: &Connection, since_day: &str) -> Result<TransferDay, AppError> {
+    let total = conn.query_row(
+        "SELECT COALESCE(SUM(runs), 0), COALESCE(SUM(bytes_uploaded), 0),
+                COALESCE(SUM(bytes_downloaded), 0), COALESCE(SUM(request_count), 0),
+                COALESCE(SUM(duration_ms), 0)
+         FROM sync_transfer_daily WHERE day >= ?1",
+        [since_day],
+        |row| {
+            Ok(TransferDay {
+                day: since_day.to_string(),
+                runs: row.get::<_, i64>(0)? as u64,
+                bytes_uploaded: row.get::<_, i64>(1)? as u64,
+                bytes_downloaded: row.get::<_, i64>(2)? as u64,
+                request_count: row.get::<_, i64>(3)? as u64,
+                duration_ms: row.get(4)?,
+            })
+        },
+    )?;
+    Ok(total)
+}
+
+/// Löscht alle Tagessummen
+pub fn clear(conn: &Connection) -> Result<(), AppError> {
+    conn.execute("DELETE FROM sync_transfer_daily", [])?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::schema;
+
+    fn transfer(up: u64, down: u64, requests: u64) -> TransferCounters {
+        TransferCounters {
+            bytes_uploaded: up,
+            bytes_downloaded: down,
+            request_count: requests,
+        }
+    }
+
+    #[test]
+    fn test_runs_are_summed_per_day() {
+        let conn = Connection::open_in_memory().unwrap();
+        schema::init_schema(&conn).unwrap();
+        let day = 24 * 60 * 60 * 1000;
+        let now = chrono::Utc::now().timestamp_millis();
+
+        record_day(&conn, now - day, 300, &transfer(10, 20, 2)).unwrap();
+        record_day(&conn, now, 500, &transfer(100, 1000, 5)).unwrap();
+        record_day(&conn, now, 700, &transfer(50, 0, 1)).unwrap();
+
+        let days = list_days(&conn, 7).unwrap();
+        assert_eq!(days.len(), 2);
+        assert_eq!(days[0].day, local_day(now));
+        assert_eq!(days[0].runs, 2);
+        assert_eq!(days[0].bytes_uploaded, 150);
+        assert_eq!(days[0].total_bytes(), 1150);
+        assert_eq!(days[0].request_count, 6);
+        assert_eq!(days[0].duration_ms, 1200);
+
+        let total = total_since(&conn, &local_day(now - day)).unwrap();
+        assert_eq!(total.runs, 3);
+        assert_eq!(total.total_bytes(), 1180);
+
+        // Alte Tage fallen heraus
+        record_day(
+            &conn,
+            now - (DAILY_RETENTION_DAYS + 2) * day,
+            0,
+            &transfer(1, 1, 1),
+        )
+        .unwrap();
+        record_day(&conn, now, 0, &transfer(0, 0, 1)).unwrap();
+        assert_eq!(list_days(&conn, 365).unwrap().len(), 2);
+
+        clear(&conn).unwrap();
+        assert!(list_days(&conn, 7).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_counter_difference() {
+        let before = counters();
+        count_request(100, 0);
+        count_request(0, 2048);
+        let delta = counters().since(&before);
+        // Andere Tests zählen parallel mit
+        assert!(delta.request_count >= 2);
+        assert!(delta.bytes_uploaded >= 100);
+        assert!(delta.bytes_downloaded >= 2048);
+    }
+}
@@ -0,0 +1,253 @@
+//! Behandlungen mit Medikamenten und ihre Wartezeiten
+//!
+//! Eine Behandlung ist ein Ereignis vom Typ `treatment` mit den Angaben aus
+//! [`Treatment`]. Während der Behandlung und bis zum Ende der Wartezeit
+//! dürfen Eier bzw. Fleisch der Wachtel nicht verwendet werden; daraus
+//! entstehen die Hinweise bei der Eier-Erfassung.
+
+use crate::error::AppError;
+use crate::models::{EventType, Gender, Quail, QuailEvent, Treatment};
+use crate::services::{event_service, repository};
+use chrono::NaiveDate;
+use rusqlite::Connection;
+use std::collections::BTreeMap;
+use uuid::Uuid;
+
+/// Wartezeit einer Wachtel aus einer Behandlung, die am Stichtag läuft
+#[derive(Debug, Clone, PartialEq)]
+pub struct Withdrawal {
+    pub quail_id: Uuid,
+    pub quail_name: String,
+    pub coop_id: Option<Uuid>,
+    pub medication: String,
+    /// Letzter Tag ohne Eierverkauf
+    pub eggs_until: NaiveDate,
+    /// Letzter Tag ohne Schlachtung
+    pub meat_until: NaiveDate,
+}
+
+impl Withdrawal {
+    pub fn eggs_withheld(&self, date: NaiveDate) -> bool {
+        date <= self.eggs_until
+    }
+
+    pub fn meat_withheld(&self, date: NaiveDate) -> bool {
+        date <= self.meat_until
+    }
+}
+
+/// Trägt eine Behandlung ein, `start` ist der erste Behandlungstag
+pub async fn record_treatment(
+    conn: &Connection,
+    quail_id: Uuid,
+    start: NaiveDate,
+    treatment: Treatment,
+    notes: Option<String>,
+) -> Result<Uuid, AppError> {
+    repository::require::<Quail>(conn, &quail_id)?;
+    let event = QuailEvent {
+        notes,
+        treatment: Some(treatment),
+        ..QuailEvent::new(quail_id, EventType::Treatment, start)
+    };
+    repository::save_with_ops(conn, &event).await?;
+    Ok(event.uuid)
+}
+
+/// Alle Wartezeiten, die am `date` laufen (Eier oder Fleisch)
+///
+/// Behandlungen, die erst nach `date` beginnen, zählen noch nicht.
+/// Gelöschte, gestorbene und geschlachtete Wachteln liefern nichts mehr.
+/// Sortiert nach Wachtel und Behandlungsbeginn.
+pub fn active_withdrawals(conn: &Connection, date: NaiveDate) -> Result<Vec<Withdrawal>, AppError> {
+    let events: Vec<QuailEvent> = repository::list(
+        conn,
+        "event_type = 'treatment' AND event_date <= ?1",
+        [date.to_string()],
+        "event_date",
+    )?;
+
+    let mut withdrawals = Vec::new();
+    for event in events {
+        let Some(treatment) = &event.treatment else {
+            continue;
+        };
+        let withdrawal = Withdrawal {
+            quail_id: event.quail_id,
+            quail_name: String::new(),
+            coop_id: None,
+            medication: treatment.medication.clone(),
+            eggs_until: treatment.eggs_withheld_until(event.event_date),
+            meat_until: treatment.meat_withheld_until(event.event_date),
+        };
+        if !withdrawal.eggs_withheld(date) && !withdrawal.meat_withheld(date) {
+            continue;
+        }
+        let Some(quail) = repository::find_first::<Quail>(
+            conn,
+            "uuid = ?1",
+            [event.quail_id.to_string()],
+            "uuid",
+        )?
+        else {
+            continue;
+        };
+        let gone = event_service::get_latest_event(conn, &quail.uuid)?
+            .is_some_and(|latest| latest.event_type.is_final());
+        if gone {
+            continue;
+        }
+        withdrawals.push(Withdrawal {
+            quail_name: quail.name,
+            coop_id: quail.coop_id,
+            ..withdrawal
+        });
+    }
+    withdrawals.sort_by(|a, b| a.quail_name.cmp(&b.quail_name));
+    Ok(withdrawals)
+}
+
+/// Eier-Wartezeiten am `date` je Stall: letzter Tag ohne Verkauf
+///
+/// `None` steht für Wachteln ohne Stall. Hähne legen keine Eier und werden
+/// übergangen.
+pub fn egg_withdrawals_by_coop(
+    conn: &Connection,
+    date: NaiveDate,
+) -> Result<BTreeMap<Option<Uuid>, NaiveDate>, AppError> {
+    let mut by_coop = BTreeMap::new();
+    for withdrawal in active_withdrawals(conn, date)? {
+        if !withdrawal.eggs_withheld(date) {
+            continue;
+        }
+        let quail = repository::require::<Quail>(conn, &withdrawal.quail_id)?;
+        if quail.gender == Gender::Male {
+            continue;
+        }
+        let until = by_coop
+            .entry(withdrawal.coop_id)
+            .or_insert(withdrawal.eggs_until);
+        *until = (*until).max(withdrawal.eggs_until);
+    }
+    Ok(by_coop)
+}
+
+/// Wartezeiten einer Wachtel am `date`
+pub fn withdrawals_for_quail(
+    conn: &Connection,
+    quail_id: &Uuid,
+    date: NaiveDate,
+) -> Result<Vec<Withdrawal>, AppError> {
+    Ok(active_withdrawals(conn, date)?
+        .into_iter()
+        .filter(|w| w.quail_id == *quail_id)
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::schema;
+    use crate::models::Coop;
+    use crate::services::{coop_service, profile_service};
+
+    fn date(month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, month, day).unwrap()
+    }
+
+    fn treatment(egg_days: u32, meat_days: u32) -> Treatment {
+        Treatment {
+            medication: "Baytril".to_string(),
+            dose: Some("0,5 ml/l".to_string()),
+            duration_days: 5,
+            egg_withdrawal_days: egg_days,
+            meat_withdrawal_days: meat_days,
+        }
+    }
+
+    async fn quail(conn: &Connection, name: &str, gender: Gender, coop: Option<Uuid>) -> Uuid {
+        let mut quail = Quail::new(name.to_string());
+        quail.gender = gender;
+        quail.coop_id = coop;
+        profile_service::create_profile(conn, &quail).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_active_withdrawals() {
+        let conn = Connection::open_in_memory().unwrap();
+        schema::init_schema(&conn).unwrap();
+        let greta = quail(&conn, "Greta", Gender::Female, None).await;
+        let ida = quail(&conn, "Ida", Gender::Female, None).await;
+
+        // Behandlung 1.–5. Mai, Eier bis 12. Mai, Fleisch bis 19. Mai gesperrt
+        record_treatment(&conn, greta, date(5, 1), treatment(7, 14), None)
+            .await
+            .unwrap();
+        assert!(active_withdrawals(&conn, date(4, 30)).unwrap().is_empty());
+
+        let active = active_withdrawals(&conn, date(5, 12)).unwrap();
+        assert_eq!(active.len(), 1);
+        assert_eq!(active[0].quail_name, "Greta");
+        assert_eq!(active[0].eggs_until, date(5, 12));
+        assert_eq!(active[0].meat_until, date(5, 19));
+
+        // Nach der Eier-Wartezeit bleibt nur die Fleisch-Wartezeit
+        let active = active_withdrawals(&conn, date(5, 13)).unwrap();
+        assert!(!active[0].eggs_withheld(date(5, 13)));
+        assert!(active[0].meat_withheld(date(5, 13)));
+        assert!(active_withdrawals(&conn, date(5, 20)).unwrap().is_empty());
+
+        // Ungültige Angaben werden nicht gespeichert
+        let mut invalid = treatment(7, 14);
+        invalid.medication = " ".to_string();
+        assert!(matches!(
+            record_treatment(&conn, ida, date(5, 1), invalid, None).await,
+            Err(AppError::Validation(_))
+        ));
+        assert!(withdrawals_for_quail(&conn, &ida, date(5, 2))
+            .unwrap()
+            .is_empty());
+
+        // Gestorbene Wachteln liefern keine Eier mehr
+        event_service::create_event(&conn, greta, EventType::Died, date(5, 3), None)
+            .await
+            .unwrap();
+        assert!(active_withdrawals(&conn, date(5, 4)).unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_egg_withdrawals_by_coop() {
+        let conn = Connection::open_in_memory().unwrap();
+        schema::init_schema(&conn).unwrap();
+        let coop = Coop::new("Voliere".to_string());
+        coop_service::save_coop(&conn, &coop).await.unwrap();
+        let greta = quail(&conn, "Greta", Gender::Female, Some(coop.uuid)).await;
+        let ilse = quail(&conn, "Ilse", Gender::Female, Some(coop.uuid)).await;
+        let hahn = quail(&conn, "Hahn", Gender::Male, None).await;
+        let ida = quail(&conn, "Ida", Gender::Unknown, None).await;
+
+        record_treatment(&conn, greta, date(5, 1), treatment(7, 14), None)
+            .await
+            .unwrap();
+        record_treatment(&conn, ilse, date(5, 3), treatment(2, 0), None)
+            .await
+            .unwrap();
+        record_treatment(&conn, hahn, date(5, 1), treatment(30, 30), None)
+            .await
+            .unwrap();
+
+        // Der Stall ist bis zur längsten Wartezeit gesperrt, Hähne zählen nicht
+        let by_coop = egg_withdrawals_by_coop(&conn, date(5, 4)).unwrap();
+        assert_eq!(by_coop.len(), 1);
+        assert_eq!(by_coop[&Some(coop.uuid)], date(5, 12));
+
+        record_treatment(&conn, ida, date(5, 4), treatment(0, 0), None)
+            .await
+            .unwrap();
+        let by_coop = egg_withdrawals_by_coop(&conn, date(5, 8)).unwrap();
+        assert_eq!(by_coop[&None], date(5, 8));
+        assert!(egg_withdrawals_by_coop(&conn, date(5, 13))
+            .unwrap()
+            .is_empty());
+    }
+}
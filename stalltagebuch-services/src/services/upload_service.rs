@@ -0,0 +1,760 @@
+use crate::error::AppError;
+use crate::services::op_crypto::OpCipher;
+use crate::services::photo_checksum::{self, CORRUPT_STATUS};
+use crate::services::photo_conflicts;
+use crate::services::photo_storage::{LocalPhotoStorage, PhotoStorage, RemotePhotoStorage};
+use crate::services::sync_backend::{ConfiguredBackend, SyncBackend};
+use crate::services::sync_progress::{self, SyncPhase};
+use crate::services::thumbnail_queue;
+use rusqlite::Connection;
+use std::sync::Arc;
+
+/// Liefert stabile device_id (erzeugt & speichert falls fehlend)
+pub fn get_device_id(conn: &Connection) -> Result<String, AppError> {
+    use crate::services::sync_service;
+    if let Some(mut settings) = sync_service::load_sync_settings(conn)? {
+        if let Some(id) = &settings.device_id {
+            return Ok(id.clone());
+        }
+        let new_id = uuid::Uuid::new_v4().to_string();
+        settings.device_id = Some(new_id.clone());
+        sync_service::save_sync_settings(conn, &settings)?;
+        Ok(new_id)
+    } else {
+        // Fallback: ephemeral ID (Settings noch nicht konfiguriert)
+        Ok(uuid::Uuid::new_v4().to_string())
+    }
+}
+
+/// Uncompressed size (NDJSON bytes) from which collected ops are uploaded
+pub const OP_BATCH_MAX_BYTES: usize = 64 * 1024;
+
+/// Age of the oldest collected op from which the batch is uploaded anyway
+pub const OP_BATCH_MAX_AGE_MS: i64 = 60_000;
+
+/// Queues operations for upload to sync/ops/<device>/<YYYYMM>/<ULID>.ndjson.gz
+///
+/// Ops are collected in `op_outbox` and written as one file once the batch
+/// reaches [`OP_BATCH_MAX_BYTES`] or its oldest op is older than
+/// [`OP_BATCH_MAX_AGE_MS`]; background sync flushes the rest.
+/// If sync is not configured or disabled, this function returns Ok() without error.
+/// While sync is paused the ops are only queued.
+pub async fn upload_ops_batch(
+    conn: &Connection,
+    ops: Vec<crate::services::crdt_service::Operation>,
+) -> Result<(), AppError> {
+    use crate::services::sync_service;
+
+    if ops.is_empty() {
+        return Ok(());
+    }
+
+    // If sync is not configured, just skip upload (app works locally)
+    let settings = match sync_service::load_sync_settings(conn)? {
+        Some(s) => s,
+        None => {
+            log::debug!("Sync not configured, skipping operation upload");
+            return Ok(());
+        }
+    };
+
+    // If sync is disabled, skip upload
+    if !settings.enabled {
+        log::debug!("Sync disabled, skipping operation upload");
+        return Ok(());
+    }
+
+    let now = chrono::Utc::now().timestamp_millis();
+    let mut insert =
+        conn.prepare_cached("INSERT INTO op_outbox (line, created_at) VALUES (?1, ?2)")?;
+    for op in &ops {
+        let line = serde_json::to_string(op)
+            .map_err(|e| AppError::Other(format!("JSON serialize failed: {}", e)))?;
+        insert.execute(rusqlite::params![line, now])?;
+    }
+    drop(insert);
+
+    let (pending_bytes, oldest): (i64, Option<i64>) = conn.query_row(
+        "SELECT COALESCE(SUM(LENGTH(line) + 1), 0), MIN(created_at) FROM op_outbox",
+        [],
+        |row| Ok((row.get(0)?, row.get(1)?)),
+    )?;
+    let full = pending_bytes as usize >= OP_BATCH_MAX_BYTES;
+    let stale = oldest.is_some_and(|ts| now - ts >= OP_BATCH_MAX_AGE_MS);
+    if full || stale {
+        flush_pending_ops(conn).await?;
+    }
+
+    Ok(())
+}
+
+/// Uploads all queued ops as gzip-compressed NDJSON files of at most
+/// [`OP_BATCH_MAX_BYTES`] (uncompressed) each
+///
+/// Returns the number of uploaded operations.
+pub async fn flush_pending_ops(conn: &Connection) -> Result<usize, AppError> {
+    use crate::services::{sync_paths, sync_service};
+
+    let settings = match sync_service::load_sync_settings(conn)? {
+        Some(s) if s.enabled && !s.paused => s,
+        _ => return Ok(0),
+    };
+
+    let pending = {
+        let mut stmt = conn.prepare("SELECT id, line FROM op_outbox ORDER BY id")?;
+        let rows = stmt.query_map([], |row| {
+            Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?))
+        })?;
+        rows.collect::<Result<Vec<_>, _>>()?
+    };
+    if pending.is_empty() {
+        return Ok(0);
+    }
+
+    let device_id = get_device_id(conn)?;
+    let year_month = sync_paths::current_year_month();
+    let client = ConfiguredBackend::from_settings(&settings)?;
+    let cipher = OpCipher::load(conn)?;
+
+    // Create directories if needed (WebDAV cannot create nested collections in one call)
+    let base = settings.remote_path.trim_end_matches('/');
+    let sync_base = format!("{}/sync", base);
+    let ops_base = format!("{}/ops", sync_base);
+    let device_base = format!("{}/{}", ops_base, device_id);
+    let month_base = format!("{}/{}", device_base, year_month);
+
+    // Try to create each level; ignore errors like 405 Method Not Allowed or 409 Conflict
+    for path in [&sync_base, &ops_base, &device_base, &month_base] {
+        if let Err(e) = client.mkcol(path).await {
+            // Best-effort: path may already exist; only log
+            log::debug!("MKCOL '{}' note: {:?}", path, e);
+        }
+    }
+
+    let mut uploaded = 0;
+    for batch in split_batches(&pending) {
+        let mut ndjson_content = String::new();
+        for (_, line) in batch {
+            ndjson_content.push_str(line);
+            ndjson_content.push('\n');
+        }
+
+        let content = cipher.seal(compress_ops(&ndjson_content)?)?;
+
+        // Never overwrite a file another device wrote under the same name;
+        // pick a fresh ULID instead
+        let mut full_path = None;
+        for _ in 0..MAX_FILE_ATTEMPTS {
+            let filename = format!("{}{}", ulid::Ulid::new(), sync_paths::OP_FILE_GZ_EXT);
+            let path = format!("{}/{}", month_base, filename);
+            let created = client
+                .put_if_absent(&path, content.clone())
+                .await
+                .map_err(|e| AppError::Other(format!("Upload ops batch failed: {}", e)))?;
+            if created {
+                full_path = Some(path);
+                break;
+            }
+            log::warn!("Op file {} already exists, retrying under a new name", path);
+        }
+        let full_path = full_path.ok_or_else(|| {
+            AppError::Other("Upload ops batch failed: no free file name".to_string())
+        })?;
+
+        // Erst nach erfolgreichem Upload aus der Outbox entfernen
+        let last_id = batch.last().map(|(id, _)| *id).unwrap_or_default();
+        conn.execute("DELETE FROM op_outbox WHERE id <= ?1", [last_id])?;
+        uploaded += batch.len();
+
+        log::info!(
+            "Uploaded ops batch: {} operations to {}",
+            batch.len(),
+            full_path
+        );
+    }
+
+    Ok(uploaded)
+}
+
+/// Splits queued `(id, line)` pairs into batches of at most
+/// [`OP_BATCH_MAX_BYTES`]; a single larger op gets a batch of its own
+fn split_batches(pending: &[(i64, String)]) -> Vec<&[(i64, String)]> {
+    let mut batches = Vec::new();
+    let mut start = 0;
+    let mut size = 0;
+    for (i, (_, line)) in pending.iter().enumerate() {
+        if i > start && size + line.len() + 1 > OP_BATCH_MAX_BYTES {
+            batches.push(&pending[start..i]);
+            start = i;
+            size = 0;
+        }
+        size += line.len() + 1;
+    }
+    batches.push(&pending[start..]);
+    batches
+}
+
+/// Gzip-compresses NDJSON content for an op file
+pub(crate) fn compress_ops(ndjson: &str) -> Result<Vec<u8>, AppError> {
+    use std::io::Write;
+
+    let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+    encoder.write_all(ndjson.as_bytes())?;
+    Ok(encoder.finish()?)
+}
+
+/// Counts how many photos are pending upload (sync_status='local_only')
+pub fn count_pending_photos(conn: &Connection) -> Result<usize, AppError> {
+    let count: usize = conn.query_row(
+        "SELECT COUNT(*) FROM photos 
+         WHERE deleted = 0 AND (sync_status = 'local_only' OR sync_status IS NULL)",
+        [],
+        |row| row.get(0),
+    )?;
+    Ok(count)
+}
+
+/// Counts the ops collected in `op_outbox` but not uploaded yet
+pub fn count_pending_ops(conn: &Connection) -> Result<usize, AppError> {
+    let count: usize = conn.query_row("SELECT COUNT(*) FROM op_outbox", [], |row| row.get(0))?;
+    Ok(count)
+}
+
+/// Default for `SyncSettings::upload_concurrency`
+pub const DEFAULT_UPLOAD_CONCURRENCY: usize = 3;
+
+/// Attempts per file before a photo is left for the next sync cycle
+const MAX_FILE_ATTEMPTS: u32 = 3;
+
+/// Wait before the second attempt; grows linearly with each further attempt
+const RETRY_DELAY: std::time::Duration = std::time::Duration::from_millis(500);
+
+/// Uploads binary photo files to sync/photos/ with all three versions (original + 2 thumbnails)
+///
+/// Only uploads photos with sync_status='local_only'. Originals follow the
+/// configured `OriginalsPolicy` unless the sync was requested `on_demand`.
+/// If sync is not configured or disabled, this function returns Ok(0) without error.
+pub async fn upload_photos_batch(conn: &Connection, on_demand: bool) -> Result<usize, AppError> {
+    use crate::services::{connectivity, sync_service};
+
+    // If sync is not configured, just skip upload (app works locally)
+    let settings = match sync_service::load_sync_settings(conn)? {
+        Some(s) => s,
+        None => {
+            log::debug!("Sync not configured, skipping photo upload");
+            return Ok(0);
+        }
+    };
+
+    // If sync is disabled, skip upload
+    if !settings.enabled {
+        log::debug!("Sync disabled, skipping photo upload");
+        return Ok(0);
+    }
+    if settings.paused || !settings.syncs("photo") {
+        log::debug!("Photo sync paused or turned off, skipping photo upload");
+        return Ok(0);
+    }
+
+    let concurrency = settings
+        .upload_concurrency
+        .map(|n| n as usize)
+        .filter(|n| *n > 0)
+        .unwrap_or(DEFAULT_UPLOAD_CONCURRENCY);
+
+    let originals = on_demand || connectivity::originals_allowed_now(settings.originals_policy);
+    if !originals {
+        log::info!(
+            "Original photos deferred by sync policy {:?}",
+            settings.originals_policy
+        );
+    }
+
+    let remote = RemotePhotoStorage::from_settings(&settings)?.with_local_key(conn)?;
+    // Create photos directory if needed
+    remote.ensure_dir().await;
+
+    upload_photos_between(
+        conn,
+        Arc::new(LocalPhotoStorage::app_photos()),
+        Arc::new(remote),
+        concurrency,
+        originals,
+    )
+    .await
+}
+
+/// Copies all photos with sync_status='local_only' (or 'corrupt' with a local
+/// original) from `local` to `remote`
+///
+/// Up to `concurrency` files are uploaded at the same time. The thumbnails
+/// of all photos go first, so other devices can show previews long before
+/// the (much larger) originals have arrived. Without `originals` only the
+/// thumbnails missing on `remote` are uploaded and the photos stay pending.
+pub async fn upload_photos_between<L, R>(
+    conn: &Connection,
+    local: Arc<L>,
+    remote: Arc<R>,
+    concurrency: usize,
+    originals: bool,
+) -> Result<usize, AppError>
+where
+    L: PhotoStorage + 'static,
+    R: PhotoStorage + 'static,
+{
+    use tokio::task::JoinSet;
+
+    let concurrency = concurrency.max(1);
+
+    // List existing remote photos
+    let remote_photos = list_remote_photos_simple(remote.as_ref()).await?;
+
+    // Get local photos that need upload (sync_status='local_only', 'corrupt' or NULL)
+    let mut stmt = conn.prepare(
+        "SELECT uuid, COALESCE(relative_path, path) as rel_path, thumbnail_small_path, thumbnail_medium_path,
+                sync_status = ?1
+         FROM photos 
+         WHERE deleted = 0 AND (sync_status IN ('local_only', ?1) OR sync_status IS NULL)",
+    )?;
+
+    let rows: Vec<(String, String, Option<String>, Option<String>, bool)> = stmt
+        .query_map([CORRUPT_STATUS], |row| {
+            Ok((
+                row.get(0)?,
+                row.get(1)?,
+                row.get(2)?,
+                row.get(3)?,
+                row.get::<_, Option<bool>>(4)?.unwrap_or(false),
+            ))
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let total_photos = rows.len();
+    log::info!("Found {} photos to upload", total_photos);
+
+    if total_photos == 0 {
+        return Ok(0);
+    }
+
+    let mut thumbnails = Vec::new();
+    let mut pending_originals = Vec::new();
+    for (uuid, rel_path, small_thumb, medium_thumb, corrupt) in rows {
+        // Thumbnails are still being created; upload together next cycle
+        if thumbnail_queue::is_pending(&uuid) {
+            log::debug!("Skipping photo {} until its thumbnails exist", uuid);
+            continue;
+        }
+        // Corrupt download on this device: the download retry handles it
+        if corrupt && !local.exists(&rel_path).await {
+            continue;
+        }
+        // A corrupt upload is replaced even though the file exists remotely
+        let already_uploaded = !corrupt && remote_photos.contains(&format!("{}.jpg", uuid));
+        if !already_uploaded {
+            thumbnails.extend(
+                small_thumb
+                    .into_iter()
+                    .chain(medium_thumb)
+                    // Already there from an earlier cycle that deferred the original
+                    .filter(|thumb| originals || !remote_photos.contains(thumb)),
+            );
+        }
+        if originals {
+            pending_originals.push((uuid, rel_path, already_uploaded, corrupt));
+        }
+    }
+
+    let total_originals = pending_originals.len();
+    sync_progress::report(SyncPhase::UploadingPhotos {
+        done: 0,
+        total: total_originals,
+    });
+
+    let mut join_set: JoinSet<()> = JoinSet::new();
+    for thumb_rel in thumbnails {
+        while join_set.len() >= concurrency {
+            join_set.join_next().await;
+        }
+        let local_clone = local.clone();
+        let remote_clone = remote.clone();
+        join_set.spawn(async move {
+            upload_thumbnail(&thumb_rel, local_clone.as_ref(), remote_clone.as_ref()).await
+        });
+    }
+    while join_set.join_next().await.is_some() {}
+
+    let mut join_set: JoinSet<Result<(String, bool, Option<String>), AppError>> = JoinSet::new();
+    let mut uploaded_count = 0;
+    let mut finished = 0;
+
+    for (uuid, rel_path, already_uploaded, corrupt) in pending_originals {
+        let local_clone = local.clone();
+        let remote_clone = remote.clone();
+
+        while join_set.len() >= concurrency {
+            if let Some(result) = join_set.join_next().await {
+                finished += 1;
+                sync_progress::report(SyncPhase::UploadingPhotos {
+                    done: finished,
+                    total: total_originals,
+                });
+                match result {
+                    Ok(Ok((uuid_done, true, checksum))) => {
+                        uploaded_count += 1;
+                        mark_uploaded(&uuid_done, checksum).await?;
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        join_set.spawn(async move {
+            upload_single_photo(
+                uuid,
+                rel_path,
+                already_uploaded,
+                corrupt,
+                local_clone,
+                remote_clone,
+            )
+            .await
+        });
+    }
+
+    // Wait for remaining uploads
+    while let Some(result) = join_set.join_next().await {
+        finished += 1;
+        sync_progress::report(SyncPhase::UploadingPhotos {
+            done: finished,
+            total: total_originals,
+        });
+        match result {
+            Ok(Ok((uuid_done, true, checksum))) => {
+                uploaded_count += 1;
+                mark_uploaded(&uuid_done, checksum).await?;
+            }
+            _ => {}
+        }
+    }
+
+    log::info!("Uploaded {} of {} photos", uploaded_count, total_photos);
+    Ok(uploaded_count)
+}
+
+/// Sets sync_status to 'synced' and stores the verified checksum
+async fn mark_uploaded(uuid: &str, checksum: Option<String>) -> Result<(), AppError> {
+    let conn_update = crate::database::connection()?;
+    let _ = conn_update.execute(
+        "UPDATE photos SET sync_status = 'synced', retry_count = 0, sync_error = NULL WHERE uuid = ?1",
+        rusqlite::params![uuid],
+    );
+    // Our own upload changes the ETag; not an out-of-band edit
+    photo_conflicts::forget_etag(&conn_update, uuid)?;
+    if let Some(checksum) = checksum {
+        photo_checksum::record_checksum(&conn_update, uuid, &checksum).await?;
+    }
+    Ok(())
+}
+
+/// Why a file could not be stored remotely
+#[derive(Debug, PartialEq)]
+enum PutError {
+    /// Writing failed (network, server)
+    Write(String),
+    /// Written, but the read-back content differs
+    Mismatch(String),
+    /// Another device already stored different content under this name
+    Exists(String),
+}
+
+/// Writes `data` to `remote` (reading it back if `verify` is set) with up to
+/// [`MAX_FILE_ATTEMPTS`] attempts
+///
+/// An existing remote file is only overwritten if `replace` is set or it is
+/// our own broken upload. A file another device stored in the meantime is
+/// kept: identical content counts as uploaded, anything else is
+/// [`PutError::Exists`].
+async fn put_with_retry<R: PhotoStorage>(
+    remote: &R,
+    path: &str,
+    data: &[u8],
+    verify: bool,
+    mut replace: bool,
+) -> Result<(), PutError> {
+    let expected = photo_checksum::content_checksum(data);
+    let mut last_error = PutError::Write(String::new());
+    for attempt in 1..=MAX_FILE_ATTEMPTS {
+        if attempt > 1 {
+            log::debug!(
+                "Upload of {} failed, attempt {}: {:?}",
+                path,
+                attempt,
+                last_error
+            );
+            tokio::time::sleep(RETRY_DELAY * (attempt - 1)).await;
+        }
+        let written = if replace {
+            remote.write(path, data.to_vec()).await.map(|()| true)
+        } else {
+            remote.write_new(path, data.to_vec()).await
+        };
+        let created = match written {
+            Ok(created) => created,
+            Err(e) => {
+                last_error = PutError::Write(format!("{:?}", e));
+                continue;
+            }
+        };
+        if created && !verify {
+            return Ok(());
+        }
+        // Verify before declaring success (e.g. truncated on a flaky connection)
+        let uploaded = remote
+            .read(path)
+            .await
+            .map(|data| photo_checksum::content_checksum(&data));
+        match uploaded {
+            Ok(checksum) if checksum == expected => return Ok(()),
+            Ok(checksum) if !created => return Err(PutError::Exists(checksum)),
+            _ => {}
+        }
+        // Our own upload is broken and may be replaced
+        replace = created;
+        last_error = PutError::Mismatch(format!("{:?}", uploaded));
+    }
+    Err(last_error)
+}
+
+/// Uploads one thumbnail; failures are only logged (the next cycle retries
+/// with the original)
+async fn upload_thumbnail<L: PhotoStorage, R: PhotoStorage>(
+    thumb_rel: &str,
+    local: &L,
+    remote: &R,
+) {
+    let Ok(data) = local.read(thumb_rel).await else {
+        return;
+    };
+    // Thumbnails are derived from the original and may always be replaced
+    match put_with_retry(remote, thumb_rel, &data, false, true).await {
+        Ok(()) => log::info!("Uploaded thumbnail: {}", thumb_rel),
+        Err(e) => log::warn!("Failed to upload thumbnail {}: {:?}", thumb_rel, e),
+    }
+}
+
+/// Uploads the original of a single photo (thumbnails are uploaded beforehand)
+///
+/// The original is read back and compared with the local checksum before
+/// the upload counts as successful. An existing remote file is only
+/// overwritten with `replace` (a corrupt earlier upload).
+async fn upload_single_photo<L: PhotoStorage, R: PhotoStorage>(
+    uuid: String,
+    rel_path: String,
+    already_uploaded: bool,
+    replace: bool,
+    local: Arc<L>,
+    remote: Arc<R>,
+) -> Result<(String, bool, Option<String>), AppError> {
+    // Update status to 'uploading'
+    let conn = crate::database::connection()?;
+    conn.execute(
+        "UPDATE photos SET sync_status = 'uploading', last_sync_attempt = ?1 WHERE uuid = ?2",
+        rusqlite::params![chrono::Utc::now().timestamp_millis(), &uuid],
+    )?;
+
+    let photo_name = format!("{}.jpg", uuid);
+
+    // Skip if already uploaded
+    if already_uploaded {
+        log::debug!("Photo {} already exists remotely", uuid);
+        return Ok((uuid, true, None));
+    }
+
+    if !local.exists(&rel_path).await {
+        let error_msg = format!("Photo file not found locally: {}", rel_path);
+        log::warn!("{}", error_msg);
+
+        // Mark as failed
+        conn.execute(
+            "UPDATE photos SET sync_status = 'local_only', sync_error = ?1 WHERE uuid = ?2",
+            rusqlite::params![error_msg, &uuid],
+        )?;
+
+        return Ok((uuid, false, None));
+    }
+
+    // Read and upload original
+    let data = match local.read(&rel_path).await {
+        Ok(data) => data,
+        Err(e) => {
+            let error_msg = format!("Failed to read photo: {:?}", e);
+            log::error!("{}: {}", rel_path, error_msg);
+
+            conn.execute(
+                "UPDATE photos SET sync_status = 'local_only', sync_error = ?1 WHERE uuid = ?2",
+                rusqlite::params![error_msg, &uuid],
+            )?;
+
+            return Ok((uuid, false, None));
+        }
+    };
+
+    let checksum = photo_checksum::content_checksum(&data);
+    match put_with_retry(remote.as_ref(), &photo_name, &data, true, replace).await {
+        Ok(()) => {
+            log::info!("Uploaded original photo: {}", photo_name);
+            Ok((uuid, true, Some(checksum)))
+        }
+        Err(PutError::Write(e)) => {
+            let error_msg = format!("Failed to upload original: {}", e);
+            log::error!("Photo {}: {}", uuid, error_msg);
+
+            conn.execute(
+                "UPDATE photos SET sync_status = 'local_only', sync_error = ?1,
+                        retry_count = COALESCE(retry_count, 0) + 1
+                 WHERE uuid = ?2",
+                rusqlite::params![error_msg, &uuid],
+            )?;
+
+            Ok((uuid, false, None))
+        }
+        Err(PutError::Mismatch(uploaded)) => {
+            photo_checksum::mark_corrupt(
+                &conn,
+                &uuid,
+                &format!("Checksum mismatch after upload: {}", uploaded),
+            )?;
+            Ok((uuid, false, None))
+        }
+        Err(PutError::Exists(remote_checksum)) => {
+            log::warn!(
+                "Photo {}: server already has different content ({}), not overwriting",
+                uuid,
+                remote_checksum
+            );
+            photo_conflicts::flag_upload_conflict(&conn, &uuid)?;
+            Ok((uuid, false, None))
+        }
+    }
+}
+
+/// Lists existing photo files in sync/photos/ directory
+async fn list_remote_photos_simple<R: PhotoStorage>(remote: &R) -> Result<Vec<String>, AppError> {
+    let names = remote
+        .list()
+        .await?
+        .into_iter()
+        .filter(|name| name.ends_with(".jpg") || name.ends_with(".webp"))
+        .collect();
+
+    Ok(names)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::sync_backend::MemoryBackend;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    /// Remote whose first `failures` writes fail (flaky connection)
+    struct FlakyStorage {
+        inner: RemotePhotoStorage,
+        failures: AtomicU32,
+    }
+
+    impl PhotoStorage for FlakyStorage {
+        async fn read(&self, path: &str) -> Result<Vec<u8>, AppError> {
+            self.inner.read(path).await
+        }
+
+        async fn write(&self, path: &str, data: Vec<u8>) -> Result<(), AppError> {
+            if self
+                .failures
+                .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1))
+                .is_ok()
+            {
+                return Err(AppError::Other("connection reset".into()));
+            }
+            self.inner.write(path, data).await
+        }
+
+        async fn exists(&self, path: &str) -> bool {
+            self.inner.exists(path).await
+        }
+
+        async fn delete(&self, path: &str) -> Result<(), AppError> {
+            self.inner.delete(path).await
+        }
+
+        async fn list(&self) -> Result<Vec<String>, AppError> {
+            self.inner.list().await
+        }
+    }
+
+    fn flaky(failures: u32) -> FlakyStorage {
+        FlakyStorage {
+            inner: RemotePhotoStorage::new(
+                ConfiguredBackend::Memory(MemoryBackend::new()),
+                "/photos",
+            ),
+            failures: AtomicU32::new(failures),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_put_retries_each_file() {
+        let remote = flaky(MAX_FILE_ATTEMPTS - 1);
+        put_with_retry(&remote, "a.jpg", b"jpeg", true, false)
+            .await
+            .unwrap();
+        assert_eq!(remote.read("a.jpg").await.unwrap(), b"jpeg".to_vec());
+
+        let remote = flaky(MAX_FILE_ATTEMPTS);
+        assert!(matches!(
+            put_with_retry(&remote, "b.jpg", b"jpeg", false, false).await,
+            Err(PutError::Write(_))
+        ));
+        assert!(!remote.exists("b.jpg").await);
+    }
+
+    #[tokio::test]
+    async fn test_put_keeps_file_of_other_device() {
+        let remote = flaky(0);
+        remote.write("a.jpg", b"other".to_vec()).await.unwrap();
+
+        // Gleicher Inhalt zählt als hochgeladen, anderer wird nicht überschrieben
+        put_with_retry(&remote, "a.jpg", b"other", true, false)
+            .await
+            .unwrap();
+        assert!(matches!(
+            put_with_retry(&remote, "a.jpg", b"mine", true, false).await,
+            Err(PutError::Exists(_))
+        ));
+        assert_eq!(remote.read("a.jpg").await.unwrap(), b"other".to_vec());
+
+        // Ein als defekt erkannter Upload wird ersetzt
+        put_with_retry(&remote, "a.jpg", b"mine", true, true)
+            .await
+            .unwrap();
+        assert_eq!(remote.read("a.jpg").await.unwrap(), b"mine".to_vec());
+    }
+
+    #[test]
+    fn test_split_batches_respects_size_limit() {
+        let line = "x".repeat(OP_BATCH_MAX_BYTES / 3);
+        let pending: Vec<(i64, String)> = (1..=7).map(|id| (id, line.clone())).collect();
+
+        let batches = split_batches(&pending);
+        assert_eq!(
+            batches.iter().map(|b| b.len()).collect::<Vec<_>>(),
+            vec![2, 2, 2, 1]
+        );
+
+        // Ein einzelnes übergroßes Op bekommt eine eigene Datei
+        let huge = vec![
+            (1, "x".repeat(OP_BATCH_MAX_BYTES * 2)),
+            (2, "y".to_string()),
+        ];
+        assert_eq!(split_batches(&huge).len(), 2);
+    }
+}
@@ -0,0 +1,392 @@
+//! Reading tables from CSV and XLSX files
+//!
+//! Only what the spreadsheet import needs: the first sheet as rows of text
+//! cells, the first non-empty row being the header. Cell formatting is
+//! ignored, so XLSX dates arrive as serial numbers (see
+//! [`excel_serial_date`]).
+
+use crate::error::AppError;
+use chrono::NaiveDate;
+use quick_xml::escape::resolve_predefined_entity;
+use quick_xml::events::{BytesRef, Event};
+use quick_xml::{Reader, XmlVersion};
+use std::collections::HashMap;
+use std::io::Read;
+use std::path::Path;
+
+/// Header and data rows of a sheet
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Table {
+    pub headers: Vec<String>,
+    pub rows: Vec<Vec<String>>,
+}
+
+impl Table {
+    fn from_rows(rows: Vec<Vec<String>>) -> Self {
+        let mut rows = rows
+            .into_iter()
+            .filter(|row| row.iter().any(|cell| !cell.trim().is_empty()));
+        let headers = rows
+            .next()
+            .map(|row| row.into_iter().map(|h| h.trim().to_string()).collect())
+            .unwrap_or_default();
+        Self {
+            headers,
+            rows: rows.collect(),
+        }
+    }
+
+    /// Cell of a data row, empty if the row is shorter
+    pub fn cell(&self, row: usize, column: usize) -> &str {
+        self.rows[row].get(column).map_or("", |c| c.trim())
+    }
+}
+
+fn format_error(message: impl std::fmt::Display) -> AppError {
+    AppError::Validation(format!("Tabelle nicht lesbar: {}", message))
+}
+
+/// Reads a `.xlsx` or (any other extension) CSV file
+pub fn read_table(path: &Path) -> Result<Table, AppError> {
+    let bytes = std::fs::read(path)?;
+    let is_xlsx = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| e.eq_ignore_ascii_case("xlsx"));
+    if is_xlsx {
+        read_xlsx(&bytes)
+    } else {
+        Ok(parse_csv(&decode_text(bytes)))
+    }
+}
+
+/// UTF-8 (with or without BOM); anything else is taken as Windows-1252,
+/// which is what Excel writes for "CSV" on Windows
+fn decode_text(bytes: Vec<u8>) -> String {
+    let bytes = match bytes.strip_prefix(b"\xef\xbb\xbf") {
+        Some(rest) => rest.to_vec(),
+        None => bytes,
+    };
+    match String::from_utf8(bytes) {
+        Ok(text) => text,
+        // Latin-1 covers the umlauts; the 0x80–0x9f extras are rare in data
+        Err(e) => e.into_bytes().into_iter().map(char::from).collect(),
+    }
+}
+
+/// Parses CSV (RFC 4180 quoting); the delimiter (`,`, `;` or tab) is
+/// guessed from the first line
+pub fn parse_csv(text: &str) -> Table {
+    let text = text.strip_prefix('\u{feff}').unwrap_or(text);
+    let first_line = text.lines().next().unwrap_or_default();
+    let delimiter = [';', ',', '\t']
+        .into_iter()
+        .max_by_key(|d| first_line.matches(*d).count())
+        .unwrap_or(',');
+
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut cell = String::new();
+    let mut quoted = false;
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted => {
+                if chars.peek() == Some(&'"') {
+                    chars.next();
+                    cell.push('"');
+                } else {
+                    quoted = false;
+                }
+            }
+            '"' if cell.is_empty() => quoted = true,
+            c if c == delimiter && !quoted => row.push(std::mem::take(&mut cell)),
+            '\r' if !quoted => {}
+            '\n' if !quoted => {
+                row.push(std::mem::take(&mut cell));
+                rows.push(std::mem::take(&mut row));
+            }
+            c => cell.push(c),
+        }
+    }
+    if !cell.is_empty() || !row.is_empty() {
+        row.push(cell);
+        rows.push(row);
+    }
+    Table::from_rows(rows)
+}
+
+/// Text of a character or predefined entity reference (`&amp;`, `&#228;`)
+fn resolve_ref(reference: &BytesRef) -> String {
+    if let Ok(Some(c)) = reference.resolve_char_ref() {
+        return c.to_string();
+    }
+    reference
+        .decode()
+        .ok()
+        .and_then(|name| resolve_predefined_entity(&name))
+        .unwrap_or_default()
+        .to_string()
+}
+
+fn zip_entry(archive: &mut zip::ZipArchive<std::io::Cursor<&[u8]>>, name: &str) -> Option<String> {
+    let mut file = archive.by_name(name).ok()?;
+    let mut text = String::new();
+    file.read_to_string(&mut text).ok()?;
+    Some(text)
+}
+
+/// Attribute value of a start tag
+fn attribute(tag: &quick_xml::events::BytesStart, name: &[u8]) -> Option<String> {
+    tag.try_get_attribute(name).ok().flatten().and_then(|a| {
+        a.normalized_value(XmlVersion::Implicit1_0)
+            .ok()
+            .map(|v| v.into_owned())
+    })
+}
+
+/// `xl/sharedStrings.xml`: one entry per `<si>`, rich text runs joined
+fn shared_strings(xml: &str) -> Result<Vec<String>, AppError> {
+    let mut reader = Reader::from_str(xml);
+    let mut strings = Vec::new();
+    let mut current = String::new();
+    let mut in_text = false;
+    loop {
+        match reader.read_event().map_err(format_error)? {
+            Event::Start(tag) => match tag.local_name().as_ref() {
+                b"si" => current.clear(),
+                b"t" => in_text = true,
+                _ => {}
+            },
+            Event::End(tag) => match tag.local_name().as_ref() {
+                b"si" => strings.push(std::mem::take(&mut current)),
+                b"t" => in_text = false,
+                _ => {}
+            },
+            Event::Empty(tag) if tag.local_name().as_ref() == b"si" => strings.push(String::new()),
+            Event::Text(text) if in_text => {
+                current.push_str(&text.xml10_content().map_err(format_error)?)
+            }
+            Event::CData(text) if in_text => {
+                current.push_str(&text.decode().map_err(format_error)?)
+            }
+            Event::GeneralRef(reference) if in_text => current.push_str(&resolve_ref(&reference)),
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+    Ok(strings)
+}
+
+/// Path of the first worksheet according to the workbook
+fn first_sheet_path(archive: &mut zip::ZipArchive<std::io::Cursor<&[u8]>>) -> Option<String> {
+    let workbook = zip_entry(archive, "xl/workbook.xml")?;
+    let mut reader = Reader::from_str(&workbook);
+    let rel_id = loop {
+        match reader.read_event().ok()? {
+            Event::Start(tag) | Event::Empty(tag) if tag.local_name().as_ref() == b"sheet" => {
+                break attribute(&tag, b"r:id")?;
+            }
+            Event::Eof => return None,
+            _ => {}
+        }
+    };
+
+    let rels = zip_entry(archive, "xl/_rels/workbook.xml.rels")?;
+    let mut reader = Reader::from_str(&rels);
+    loop {
+        match reader.read_event().ok()? {
+            Event::Start(tag) | Event::Empty(tag)
+                if tag.local_name().as_ref() == b"Relationship"
+                    && attribute(&tag, b"Id").as_deref() == Some(rel_id.as_str()) =>
+            {
+                let target = attribute(&tag, b"Target")?;
+                return Some(match target.strip_prefix('/') {
+                    Some(absolute) => absolute.to_string(),
+                    None => format!("xl/{}", target),
+                });
+            }
+            Event::Eof => return None,
+            _ => {}
+        }
+    }
+}
+
+/// Zero-based column of a cell reference like `AB12`
+fn column_index(reference: &str) -> Option<usize> {
+    let letters: Vec<u8> = reference
+        .bytes()
+        .take_while(|b| b.is_ascii_alphabetic())
+        .collect();
+    if letters.is_empty() {
+        return None;
+    }
+    let number = letters.iter().fold(0usize, |n, b| {
+        n * 26 + (b.to_ascii_uppercase() - b'A') as usize + 1
+    });
+    Some(number - 1)
+}
+
+/// Reads the first sheet of an XLSX workbook
+pub fn read_xlsx(bytes: &[u8]) -> Result<Table, AppError> {
+    let mut archive = zip::ZipArchive::new(std::io::Cursor::new(bytes)).map_err(format_error)?;
+    let strings = match zip_entry(&mut archive, "xl/sharedStrings.xml") {
+        Some(xml) => shared_strings(&xml)?,
+        None => Vec::new(),
+    };
+    let sheet_path =
+        first_sheet_path(&mut archive).unwrap_or_else(|| "xl/worksheets/sheet1.xml".to_string());
+    let sheet = zip_entry(&mut archive, &sheet_path)
+        .ok_or_else(|| format_error(format!("{} fehlt", sheet_path)))?;
+
+    let mut reader = Reader::from_str(&sheet);
+    let mut rows: Vec<Vec<String>> = Vec::new();
+    let mut row: HashMap<usize, String> = HashMap::new();
+    let mut column = 0;
+    let mut cell_type = String::new();
+    let mut value = String::new();
+    let mut in_value = false;
+
+    let finish_row = |row: &mut HashMap<usize, String>, rows: &mut Vec<Vec<String>>| {
+        let width = row.keys().max().map_or(0, |max| max + 1);
+        let mut cells = vec![String::new(); width];
+        for (index, text) in row.drain() {
+            cells[index] = text;
+        }
+        rows.push(cells);
+    };
+
+    loop {
+        match reader.read_event().map_err(format_error)? {
+            Event::Start(tag) => match tag.local_name().as_ref() {
+                b"row" => {
+                    row.clear();
+                    column = 0;
+                }
+                b"c" => {
+                    if let Some(index) = attribute(&tag, b"r").as_deref().and_then(column_index) {
+                        column = index;
+                    }
+                    cell_type = attribute(&tag, b"t").unwrap_or_default();
+                    value.clear();
+                }
+                b"v" | b"t" => in_value = true,
+                _ => {}
+            },
+            Event::Empty(tag) => match tag.local_name().as_ref() {
+                b"row" => rows.push(Vec::new()),
+                b"c" => {
+                    column = attribute(&tag, b"r")
+                        .as_deref()
+                        .and_then(column_index)
+                        .unwrap_or(column)
+                        + 1
+                }
+                _ => {}
+            },
+            Event::End(tag) => match tag.local_name().as_ref() {
+                b"v" | b"t" => in_value = false,
+                b"c" => {
+                    let text = match cell_type.as_str() {
+                        "s" => value
+                            .trim()
+                            .parse::<usize>()
+                            .ok()
+                            .and_then(|i| strings.get(i).cloned())
+                            .unwrap_or_default(),
+                        "b" => if value.trim() == "1" { "TRUE" } else { "FALSE" }.to_string(),
+                        _ => std::mem::take(&mut value),
+                    };
+                    if !text.is_empty() {
+                        row.insert(column, text);
+                    }
+                    column += 1;
+                }
+                b"row" => finish_row(&mut row, &mut rows),
+                _ => {}
+            },
+            Event::Text(text) if in_value => {
+                value.push_str(&text.xml10_content().map_err(format_error)?)
+            }
+            Event::CData(text) if in_value => value.push_str(&text.decode().map_err(format_error)?),
+            Event::GeneralRef(reference) if in_value => value.push_str(&resolve_ref(&reference)),
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+    Ok(Table::from_rows(rows))
+}
+
+/// Date of an Excel serial number (days since 1899-12-30, fraction = time)
+pub fn excel_serial_date(serial: f64) -> Option<NaiveDate> {
+    if !(1.0..2_958_466.0).contains(&serial) {
+        return None;
+    }
+    NaiveDate::from_ymd_opt(1899, 12, 30)?
+        .checked_add_days(chrono::Days::new(serial.trunc() as u64))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    #[test]
+    fn test_parse_csv_quotes_and_delimiter() {
+        let table = parse_csv(
+            "\u{feff}Datum;Eier;Notiz\r\n01.03.2025;5;\"nass; kalt\"\r\n\r\n02.03.2025;4;\"sagt \"\"hallo\"\"\nzweite Zeile\"\n",
+        );
+        assert_eq!(table.headers, vec!["Datum", "Eier", "Notiz"]);
+        assert_eq!(table.rows.len(), 2);
+        assert_eq!(table.cell(0, 2), "nass; kalt");
+        assert_eq!(table.cell(1, 2), "sagt \"hallo\"\nzweite Zeile");
+        assert_eq!(table.cell(1, 5), "");
+
+        let latin1 = decode_text(b"Name\nJ\xfcrgen\n".to_vec());
+        assert_eq!(parse_csv(&latin1).cell(0, 0), "Jürgen");
+    }
+
+    fn xlsx(files: &[(&str, &str)]) -> Vec<u8> {
+        let mut zip = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+        let options: zip::write::FileOptions<'_, ()> = zip::write::FileOptions::default();
+        for (name, content) in files {
+            zip.start_file(*name, options).unwrap();
+            zip.write_all(content.as_bytes()).unwrap();
+        }
+        zip.finish().unwrap().into_inner()
+    }
+
+    #[test]
+    fn test_read_xlsx_first_sheet() {
+        let bytes = xlsx(&[
+            (
+                "xl/workbook.xml",
+                r#"<workbook xmlns:r="r"><sheets><sheet name="Eier" sheetId="1" r:id="rId3"/></sheets></workbook>"#,
+            ),
+            (
+                "xl/_rels/workbook.xml.rels",
+                r#"<Relationships><Relationship Id="rId3" Target="worksheets/eier.xml"/></Relationships>"#,
+            ),
+            (
+                "xl/sharedStrings.xml",
+                r#"<sst><si><t>Datum</t></si><si><r><t>Ei</t></r><r><t>er</t></r></si><si><t>Tom &amp; Jerry</t></si></sst>"#,
+            ),
+            (
+                "xl/worksheets/eier.xml",
+                r#"<worksheet><sheetData>
+                    <row r="1"><c r="A1" t="s"><v>0</v></c><c r="B1" t="s"><v>1</v></c></row>
+                    <row r="2"><c r="A2"><v>45717</v></c><c r="C2" t="s"><v>2</v></c></row>
+                    <row r="3"><c r="B3" t="inlineStr"><is><t>7</t></is></c></row>
+                </sheetData></worksheet>"#,
+            ),
+        ]);
+        let table = read_xlsx(&bytes).unwrap();
+        assert_eq!(table.headers, vec!["Datum", "Eier"]);
+        assert_eq!(table.rows[0], vec!["45717", "", "Tom & Jerry"]);
+        assert_eq!(table.rows[1], vec!["", "7"]);
+        assert_eq!(
+            excel_serial_date(45717.0),
+            NaiveDate::from_ymd_opt(2025, 3, 1)
+        );
+    }
+}
@@ -0,0 +1,529 @@
+//! End-to-end tests for the sync loop: write → capture → upload → download → apply
+//!
+//! Every test gets its own `memory://` backend shared by two in-memory
+//! "devices" (SQLite connections), so no Nextcloud is needed.
+
+use chrono::NaiveDate;
+use rusqlite::Connection;
+use stalltagebuch_services::database::schema;
+use stalltagebuch_services::error::AppError;
+use stalltagebuch_services::models::{EggRecord, EventType, Quail, SyncSettings};
+use stalltagebuch_services::services::sync_backend::{MemoryBackend, SyncBackend};
+use stalltagebuch_services::services::{
+    download_service, egg_service, event_service, op_compaction, profile_service, state_rebuild,
+    sync_service, upload_service,
+};
+
+struct Device {
+    conn: Connection,
+}
+
+impl Device {
+    fn new(server: &str, device_id: &str) -> Self {
+        let conn = Connection::open_in_memory().unwrap();
+        schema::init_schema(&conn).unwrap();
+
+        let mut settings = SyncSettings::new(
+            server.to_string(),
+            "user".to_string(),
+            "app-password".to_string(),
+            "/Stalltagebuch".to_string(),
+        );
+        settings.device_id = Some(device_id.to_string());
+        sync_service::save_sync_settings(&conn, &settings).unwrap();
+
+        Self { conn }
+    }
+
+    /// Uploads the ops this device has collected so far
+    async fn push(&self) {
+        upload_service::flush_pending_ops(&self.conn).await.unwrap();
+    }
+
+    async fn pull(&self) -> usize {
+        download_service::download_and_merge_ops(&self.conn)
+            .await
+            .unwrap()
+    }
+}
+
+/// Two devices sharing a fresh in-memory server
+fn setup() -> (MemoryBackend, Device, Device) {
+    let name = uuid::Uuid::new_v4().to_string();
+    let server = format!("memory://{}", name);
+    (
+        MemoryBackend::shared(&name),
+        Device::new(&server, "device-a"),
+        Device::new(&server, "device-b"),
+    )
+}
+
+#[tokio::test]
+async fn test_created_profile_reaches_other_device() {
+    let (backend, a, b) = setup();
+
+    let quail = Quail::new("Henriette".to_string());
+    profile_service::create_profile(&a.conn, &quail)
+        .await
+        .unwrap();
+    a.push().await;
+
+    // Ops landen als gzip-komprimiertes NDJSON unter sync/ops/<device>/<YYYYMM>/
+    let files = backend.file_paths();
+    assert_eq!(files.len(), 1);
+    assert!(files[0].starts_with("Stalltagebuch/sync/ops/device-a/"));
+    assert!(files[0].ends_with(".ndjson.gz"));
+
+    assert!(b.pull().await > 0);
+
+    let synced = profile_service::get_profile(&b.conn, &quail.uuid).unwrap();
+    assert_eq!(synced.name, "Henriette");
+    assert_eq!(synced.gender, quail.gender);
+}
+
+#[tokio::test]
+async fn test_small_ops_are_batched_into_one_file() {
+    let (backend, a, b) = setup();
+
+    for name in ["Ida", "Ilse", "Irma"] {
+        profile_service::create_profile(&a.conn, &Quail::new(name.to_string()))
+            .await
+            .unwrap();
+    }
+    assert!(backend.file_paths().is_empty());
+
+    a.push().await;
+    assert_eq!(backend.file_paths().len(), 1);
+
+    b.pull().await;
+    assert_eq!(
+        profile_service::list_profiles(&b.conn, None).unwrap().len(),
+        3
+    );
+}
+
+#[tokio::test]
+async fn test_egg_record_update_and_delete_are_applied() {
+    let (_backend, a, b) = setup();
+    let date = NaiveDate::from_ymd_opt(2025, 3, 14).unwrap();
+
+    let mut record = EggRecord::new(date, 7);
+    egg_service::add_egg_record(&a.conn, &record).await.unwrap();
+    a.push().await;
+    b.pull().await;
+    assert_eq!(
+        egg_service::get_egg_record(&b.conn, "2025-03-14", None)
+            .unwrap()
+            .total_eggs,
+        7
+    );
+
+    record.total_eggs = 9;
+    egg_service::update_egg_record(&a.conn, &record)
+        .await
+        .unwrap();
+    a.push().await;
+    b.pull().await;
+    assert_eq!(
+        egg_service::get_egg_record(&b.conn, "2025-03-14", None)
+            .unwrap()
+            .total_eggs,
+        9
+    );
+
+    egg_service::delete_egg_record(&a.conn, "2025-03-14", None)
+        .await
+        .unwrap();
+    a.push().await;
+    b.pull().await;
+    let deleted: i32 = b
+        .conn
+        .query_row(
+            "SELECT deleted FROM egg_records WHERE uuid = ?1",
+            [record.uuid.to_string()],
+            |row| row.get(0),
+        )
+        .unwrap();
+    assert_eq!(deleted, 1);
+}
+
+#[tokio::test]
+async fn test_event_for_synced_quail() {
+    let (_backend, a, b) = setup();
+
+    let quail = Quail::new("Paula".to_string());
+    profile_service::create_profile(&a.conn, &quail)
+        .await
+        .unwrap();
+    let date = NaiveDate::from_ymd_opt(2025, 1, 2).unwrap();
+    let event_id = event_service::create_event(
+        &a.conn,
+        quail.uuid,
+        EventType::Born,
+        date,
+        Some("Erstes Küken".to_string()),
+    )
+    .await
+    .unwrap();
+
+    a.push().await;
+    b.pull().await;
+
+    let event = event_service::get_event_by_id(&b.conn, &event_id)
+        .unwrap()
+        .expect("event synced");
+    assert_eq!(event.quail_id, quail.uuid);
+    assert_eq!(event.event_type, EventType::Born);
+    assert_eq!(event.event_date, date);
+    assert_eq!(event.notes.as_deref(), Some("Erstes Küken"));
+}
+
+#[tokio::test]
+async fn test_bidirectional_sync_is_idempotent() {
+    let (_backend, a, b) = setup();
+
+    let from_a = Quail::new("Anni".to_string());
+    let from_b = Quail::new("Berta".to_string());
+    profile_service::create_profile(&a.conn, &from_a)
+        .await
+        .unwrap();
+    profile_service::create_profile(&b.conn, &from_b)
+        .await
+        .unwrap();
+
+    a.push().await;
+    b.push().await;
+    a.pull().await;
+    b.pull().await;
+
+    for device in [&a, &b] {
+        let mut names: Vec<String> = profile_service::list_profiles(&device.conn, None)
+            .unwrap()
+            .into_iter()
+            .map(|q| q.name)
+            .collect();
+        names.sort();
+        assert_eq!(names, vec!["Anni", "Berta"]);
+    }
+
+    // Nichts Neues auf dem Server → nichts anzuwenden
+    assert_eq!(a.pull().await, 0);
+    assert_eq!(b.pull().await, 0);
+}
+
+#[tokio::test]
+async fn test_idle_sync_skips_unchanged_directories() {
+    let (backend, a, b) = setup();
+    profile_service::create_profile(&a.conn, &Quail::new("Emma".to_string()))
+        .await
+        .unwrap();
+    a.push().await;
+
+    assert_eq!(b.pull().await, 2);
+
+    // Nur noch sync/ops und sync/control werden gelistet
+    let before = backend.request_count();
+    assert_eq!(b.pull().await, 0);
+    assert_eq!(backend.request_count() - before, 2);
+
+    // Eine neue Datei ändert die ETags bis hinauf zu sync/ops
+    profile_service::create_profile(&a.conn, &Quail::new("Elsa".to_string()))
+        .await
+        .unwrap();
+    a.push().await;
+    assert_eq!(b.pull().await, 2);
+}
+
+#[tokio::test]
+async fn test_remote_tree_etag_detects_changes() {
+    let (_backend, a, b) = setup();
+    profile_service::create_profile(&a.conn, &Quail::new("Emma".to_string()))
+        .await
+        .unwrap();
+    a.push().await;
+
+    let etag = download_service::remote_tree_etag(&b.conn).await.unwrap();
+    assert!(etag.is_some());
+    assert!(!download_service::remote_tree_unchanged(&b.conn, &etag).unwrap());
+
+    b.pull().await;
+    download_service::remember_remote_tree(&b.conn, &etag).unwrap();
+    let again = download_service::remote_tree_etag(&b.conn).await.unwrap();
+    assert!(download_service::remote_tree_unchanged(&b.conn, &again).unwrap());
+
+    // Ein Upload von a ändert das ETag von sync/
+    profile_service::create_profile(&a.conn, &Quail::new("Elsa".to_string()))
+        .await
+        .unwrap();
+    a.push().await;
+    let changed = download_service::remote_tree_etag(&b.conn).await.unwrap();
+    assert!(!download_service::remote_tree_unchanged(&b.conn, &changed).unwrap());
+}
+
+#[tokio::test]
+async fn test_disabled_sync_uploads_nothing() {
+    let (backend, a, _b) = setup();
+    sync_service::set_sync_enabled(&a.conn, false).unwrap();
+
+    profile_service::create_profile(&a.conn, &Quail::new("Lokal".to_string()))
+        .await
+        .unwrap();
+
+    a.push().await;
+    assert!(backend.file_paths().is_empty());
+}
+
+#[tokio::test]
+async fn test_paused_sync_keeps_ops_until_resumed() {
+    let (backend, a, b) = setup();
+    sync_service::set_sync_paused(&a.conn, true).unwrap();
+
+    let quail = Quail::new("Wartet".to_string());
+    profile_service::create_profile(&a.conn, &quail)
+        .await
+        .unwrap();
+    a.push().await;
+    assert!(backend.file_paths().is_empty());
+    assert!(download_service::download_and_merge_ops(&a.conn)
+        .await
+        .is_err());
+
+    sync_service::set_sync_paused(&a.conn, false).unwrap();
+    a.push().await;
+    b.pull().await;
+    assert_eq!(
+        profile_service::get_profile(&b.conn, &quail.uuid)
+            .unwrap()
+            .name,
+        "Wartet"
+    );
+}
+
+#[tokio::test]
+async fn test_excluded_entity_type_is_neither_uploaded_nor_merged() {
+    let (_backend, a, b) = setup();
+    sync_service::set_entity_type_synced(&a.conn, "egg", false).unwrap();
+    sync_service::set_entity_type_synced(&b.conn, "egg", false).unwrap();
+
+    let quail = Quail::new("Synchron".to_string());
+    profile_service::create_profile(&a.conn, &quail)
+        .await
+        .unwrap();
+    let date = NaiveDate::from_ymd_opt(2025, 5, 1).unwrap();
+    egg_service::add_egg_record(&a.conn, &EggRecord::new(date, 4))
+        .await
+        .unwrap();
+    a.push().await;
+    b.pull().await;
+
+    assert!(profile_service::get_profile(&b.conn, &quail.uuid).is_ok());
+    assert!(egg_service::get_egg_record(&b.conn, "2025-05-01", None).is_err());
+
+    // Wieder eingeschaltet: B liest alle Dateien erneut, neue Änderungen von A kommen an
+    sync_service::set_entity_type_synced(&b.conn, "egg", true).unwrap();
+    sync_service::set_entity_type_synced(&a.conn, "egg", true).unwrap();
+    let later = NaiveDate::from_ymd_opt(2025, 5, 2).unwrap();
+    egg_service::add_egg_record(&a.conn, &EggRecord::new(later, 6))
+        .await
+        .unwrap();
+    a.push().await;
+    b.pull().await;
+    assert_eq!(
+        egg_service::get_egg_record(&b.conn, "2025-05-02", None)
+            .unwrap()
+            .total_eggs,
+        6
+    );
+}
+
+#[tokio::test]
+async fn test_revoked_credentials_are_not_treated_as_empty_server() {
+    let (backend, a, b) = setup();
+    profile_service::create_profile(&a.conn, &Quail::new("Frieda".to_string()))
+        .await
+        .unwrap();
+
+    a.push().await;
+    backend.revoke_credentials();
+
+    let result = download_service::download_and_merge_ops(&b.conn).await;
+    assert!(matches!(result, Err(AppError::PermissionDenied(_))));
+}
+
+#[tokio::test]
+async fn test_new_device_bootstraps_from_snapshot() {
+    let name = uuid::Uuid::new_v4().to_string();
+    let server = format!("memory://{}", name);
+    let backend = MemoryBackend::shared(&name);
+    let a = Device::new(&server, "device-a");
+
+    let before = Quail::new("Alma".to_string());
+    profile_service::create_profile(&a.conn, &before)
+        .await
+        .unwrap();
+
+    a.push().await;
+
+    // Erst nach dem Snapshot geschriebene Ops bleiben erhalten
+    tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+    let report = op_compaction::compact_ops(&a.conn).await.unwrap().unwrap();
+    assert_eq!(report.deleted_op_files, 1);
+    tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+    let after = Quail::new("Bella".to_string());
+    profile_service::create_profile(&a.conn, &after)
+        .await
+        .unwrap();
+
+    a.push().await;
+    let files = backend.file_paths();
+    assert_eq!(
+        files
+            .iter()
+            .filter(|p| p.starts_with("Stalltagebuch/sync/ops/"))
+            .count(),
+        1
+    );
+    assert!(files
+        .iter()
+        .any(|p| p == "Stalltagebuch/sync/control/device-a/latest.json"));
+
+    let c = Device::new(&server, "device-c");
+    assert!(c.pull().await > 0);
+    let mut names: Vec<String> = profile_service::list_profiles(&c.conn, None)
+        .unwrap()
+        .into_iter()
+        .map(|q| q.name)
+        .collect();
+    names.sort();
+    assert_eq!(names, vec!["Alma", "Bella"]);
+
+    assert_eq!(c.pull().await, 0);
+}
+
+#[tokio::test]
+async fn test_compaction_waits_for_lease_of_other_device() {
+    let (backend, a, _b) = setup();
+    profile_service::create_profile(&a.conn, &Quail::new("Alma".to_string()))
+        .await
+        .unwrap();
+    a.push().await;
+
+    let lease_path = "/Stalltagebuch/sync/control/compactor.lease";
+    let mut lease = op_compaction::CompactionLease {
+        lease_id: ulid::Ulid::new().to_string(),
+        device_id: "device-b".to_string(),
+        expires_at: chrono::Utc::now().timestamp_millis() + 60_000,
+    };
+    backend
+        .put(lease_path, serde_json::to_vec(&lease).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(op_compaction::compact_ops(&a.conn).await.unwrap(), None);
+
+    // Eine abgelaufene Lease (abgestürztes Gerät) wird übernommen und freigegeben
+    lease.expires_at = 0;
+    backend
+        .put(lease_path, serde_json::to_vec(&lease).unwrap())
+        .await
+        .unwrap();
+    tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+    let report = op_compaction::compact_ops(&a.conn).await.unwrap().unwrap();
+    assert_eq!(report.deleted_op_files, 1);
+    assert!(!backend
+        .file_paths()
+        .iter()
+        .any(|path| path.ends_with("compactor.lease")));
+}
+
+#[tokio::test]
+async fn test_fresh_device_skips_ops_merged_into_snapshot() {
+    let name = uuid::Uuid::new_v4().to_string();
+    let server = format!("memory://{}", name);
+    let backend = MemoryBackend::shared(&name);
+    let a = Device::new(&server, "device-a");
+    let b = Device::new(&server, "device-b");
+
+    let quail = Quail::new("Greta".to_string());
+    profile_service::create_profile(&b.conn, &quail)
+        .await
+        .unwrap();
+    b.push().await;
+    a.pull().await;
+    op_compaction::compact_ops(&a.conn).await.unwrap().unwrap();
+
+    // Die Ops von device-b liegen noch auf dem Server, stecken aber schon im
+    // Snapshot von device-a und werden nicht erneut angewendet
+    assert!(backend
+        .file_paths()
+        .iter()
+        .any(|p| p.starts_with("Stalltagebuch/sync/ops/device-b/")));
+
+    let c = Device::new(&server, "device-c");
+    assert_eq!(c.pull().await, 1);
+    assert_eq!(
+        profile_service::get_profile(&c.conn, &quail.uuid)
+            .unwrap()
+            .name,
+        "Greta"
+    );
+    assert_eq!(c.pull().await, 0);
+}
+
+#[tokio::test]
+async fn test_rebuild_restores_state_from_op_log() {
+    let (_backend, a, b) = setup();
+    let quail = Quail::new("Original".to_string());
+    profile_service::create_profile(&a.conn, &quail)
+        .await
+        .unwrap();
+    let date = NaiveDate::from_ymd_opt(2025, 6, 1).unwrap();
+    egg_service::add_egg_record(&b.conn, &EggRecord::new(date, 3))
+        .await
+        .unwrap();
+    a.push().await;
+    b.push().await;
+    a.pull().await;
+
+    // Eine noch nicht hochgeladene Änderung bleibt erhalten
+    let pending = Quail::new("Ungesendet".to_string());
+    profile_service::create_profile(&a.conn, &pending)
+        .await
+        .unwrap();
+
+    // Abgeleiteter Zustand kaputt (z.B. durch einen LWW-Fehler)
+    a.conn
+        .execute(
+            "UPDATE quails SET name = 'kaputt' WHERE uuid = ?1",
+            [quail.uuid.to_string()],
+        )
+        .unwrap();
+    a.conn.execute("DELETE FROM egg_records", []).unwrap();
+    a.conn
+        .execute("UPDATE sync_settings SET initial_upload_done = 1", [])
+        .unwrap();
+
+    let report = state_rebuild::rebuild_from_op_log(&a.conn).await.unwrap();
+    assert!(report.remote_error.is_none());
+    assert_eq!(
+        profile_service::get_profile(&a.conn, &quail.uuid)
+            .unwrap()
+            .name,
+        "Original"
+    );
+    assert!(profile_service::get_profile(&a.conn, &pending.uuid).is_ok());
+    assert_eq!(
+        egg_service::get_egg_record(&a.conn, "2025-06-01", None)
+            .unwrap()
+            .total_eggs,
+        3
+    );
+
+    // Die gelöschten Eier fehlten vorher und sind jetzt wieder da
+    let eggs = report
+        .counts
+        .iter()
+        .find(|c| c.entity_type == "egg")
+        .unwrap();
+    assert_eq!((eggs.before, eggs.after), (0, 1));
+    assert!(!report.is_consistent());
+}
@@ -0,0 +1,281 @@
+//! Deterministic multi-device sync simulator
+//!
+//! Each run spins up several in-memory "devices" that share a `file://`
+//! folder as server. A seeded RNG decides which device writes what and in
+//! which order the devices push, pull and see duplicated op files. After a
+//! final round of full syncs every device must hold the same live data.
+//! (UUIDs and HLC timestamps still come from the system, only the sequence
+//! of actions is fixed by the seed.)
+//!
+//! A failing seed can be replayed on its own with
+//! `SIM_SEED=<seed> cargo test -p stalltagebuch-services --test sync_simulator`.
+
+use chrono::NaiveDate;
+use rand::rngs::StdRng;
+use rand::seq::IndexedRandom;
+use rand::{Rng, SeedableRng};
+use rusqlite::Connection;
+use stalltagebuch_services::database::schema;
+use stalltagebuch_services::models::{EggRecord, EventType, Gender, Quail, SyncSettings};
+use stalltagebuch_services::services::{
+    download_service, egg_service, event_service, profile_service, sync_service, upload_service,
+};
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+
+const DEVICES: usize = 3;
+const STEPS: usize = 120;
+const SEEDS: u64 = 12;
+
+const NAMES: &[&str] = &["Henriette", "Ida", "Ilse", "Irma", "Klara", "Lotte"];
+const GENDERS: &[Gender] = &[Gender::Male, Gender::Female, Gender::Unknown];
+
+struct Device {
+    conn: Connection,
+    /// Eierdaten dieses Geräts; ein Eintrag je Tag (und Stall), gleichzeitiges
+    /// Anlegen desselben Tages auf zwei Geräten ist kein CRDT-Fall
+    egg_day_offset: u64,
+}
+
+impl Device {
+    fn new(server: &str, index: usize) -> Self {
+        let conn = Connection::open_in_memory().unwrap();
+        schema::init_schema(&conn).unwrap();
+
+        let mut settings = SyncSettings::new(
+            server.to_string(),
+            "user".to_string(),
+            "app-password".to_string(),
+            "/Stalltagebuch".to_string(),
+        );
+        settings.device_id = Some(format!("device-{}", index));
+        sync_service::save_sync_settings(&conn, &settings).unwrap();
+
+        Self {
+            conn,
+            egg_day_offset: index as u64 * 1000,
+        }
+    }
+
+    async fn push(&self) {
+        upload_service::flush_pending_ops(&self.conn).await.unwrap();
+    }
+
+    async fn pull(&self) {
+        download_service::download_and_merge_ops(&self.conn)
+            .await
+            .unwrap();
+    }
+
+    fn live_uuids(&self, table: &str) -> Vec<String> {
+        let mut stmt = self
+            .conn
+            .prepare(&format!(
+                "SELECT uuid FROM {} WHERE deleted = 0 ORDER BY uuid",
+                table
+            ))
+            .unwrap();
+        stmt.query_map([], |row| row.get(0))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap()
+    }
+
+    /// Live data in the synced columns, independent of local bookkeeping
+    fn fingerprint(&self) -> Vec<String> {
+        let queries = [
+            "SELECT 'quail', uuid, name, gender, ring_color FROM quails
+             WHERE deleted = 0 ORDER BY uuid",
+            "SELECT 'event', uuid, quail_id, event_type, event_date || ' ' || COALESCE(notes, '')
+             FROM quail_events WHERE deleted = 0 ORDER BY uuid",
+            "SELECT 'egg', uuid, record_date, total_eggs, notes FROM egg_records
+             WHERE deleted = 0 ORDER BY uuid",
+        ];
+        let mut rows = Vec::new();
+        for sql in queries {
+            let mut stmt = self.conn.prepare(sql).unwrap();
+            let found = stmt
+                .query_map([], |row| {
+                    let values: Vec<String> = (0..5)
+                        .map(|i| {
+                            row.get::<_, rusqlite::types::Value>(i)
+                                .map(|v| format!("{:?}", v))
+                        })
+                        .collect::<Result<_, _>>()?;
+                    Ok(values.join("|"))
+                })
+                .unwrap();
+            for row in found {
+                rows.push(row.unwrap());
+            }
+        }
+        rows
+    }
+
+    /// Performs a random local change
+    async fn write(&self, rng: &mut StdRng) {
+        let quails = self.live_uuids("quails");
+        let events = self.live_uuids("quail_events");
+        let eggs = self.live_uuids("egg_records");
+
+        match rng.random_range(0..8) {
+            0 | 1 => {
+                let mut quail = Quail::new(NAMES.choose(rng).unwrap().to_string());
+                quail.gender = GENDERS.choose(rng).unwrap().clone();
+                profile_service::create_profile(&self.conn, &quail)
+                    .await
+                    .unwrap();
+            }
+            2 if !quails.is_empty() => {
+                let uuid = Uuid::parse_str(quails.choose(rng).unwrap()).unwrap();
+                let mut quail = profile_service::get_profile(&self.conn, &uuid).unwrap();
+                quail.name = NAMES.choose(rng).unwrap().to_string();
+                quail.gender = GENDERS.choose(rng).unwrap().clone();
+                profile_service::update_profile(&self.conn, &quail)
+                    .await
+                    .unwrap();
+            }
+            3 if !quails.is_empty() => {
+                let uuid = Uuid::parse_str(quails.choose(rng).unwrap()).unwrap();
+                profile_service::delete_profile(&self.conn, &uuid)
+                    .await
+                    .unwrap();
+            }
+            4 if !quails.is_empty() => {
+                let uuid = Uuid::parse_str(quails.choose(rng).unwrap()).unwrap();
+                let date = day(rng.random_range(0..365));
+                event_service::create_event(&self.conn, uuid, EventType::Alive, date, None)
+                    .await
+                    .unwrap();
+            }
+            5 if !events.is_empty() => {
+                let uuid = Uuid::parse_str(events.choose(rng).unwrap()).unwrap();
+                let notes = format!("Notiz {}", rng.random_range(0..100));
+                event_service::update_event_full(
+                    &self.conn,
+                    &uuid,
+                    EventType::Sick,
+                    day(rng.random_range(0..365)),
+                    Some(notes),
+                    None,
+                )
+                .await
+                .unwrap();
+            }
+            6 if !events.is_empty() => {
+                let uuid = Uuid::parse_str(events.choose(rng).unwrap()).unwrap();
+                event_service::delete_event(&self.conn, &uuid)
+                    .await
+                    .unwrap();
+            }
+            _ => {
+                let date = day(self.egg_day_offset + rng.random_range(0..20));
+                let key = date.format("%Y-%m-%d").to_string();
+                let live = egg_service::get_egg_record(&self.conn, &key, None).ok();
+                match live {
+                    None => {
+                        let record = EggRecord::new(date, rng.random_range(0..12));
+                        egg_service::add_egg_record(&self.conn, &record)
+                            .await
+                            .unwrap();
+                    }
+                    Some(_) if rng.random_bool(0.3) && !eggs.is_empty() => {
+                        egg_service::delete_egg_record(&self.conn, &key, None)
+                            .await
+                            .unwrap();
+                    }
+                    Some(mut record) => {
+                        record.total_eggs = rng.random_range(0..12);
+                        egg_service::update_egg_record(&self.conn, &record)
+                            .await
+                            .unwrap();
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn day(offset: u64) -> NaiveDate {
+    NaiveDate::from_ymd_opt(2020, 1, 1).unwrap() + chrono::Days::new(offset)
+}
+
+/// All op files below `dir`
+fn op_files(dir: &Path) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return files;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            files.extend(op_files(&path));
+        } else if path.to_string_lossy().contains(".ndjson") {
+            files.push(path);
+        }
+    }
+    files.sort();
+    files
+}
+
+/// Copies an uploaded op file under a new name, as after a retried upload
+fn duplicate_op_file(root: &Path, rng: &mut StdRng) {
+    let files = op_files(&root.join("Stalltagebuch/sync/ops"));
+    let Some(file) = files.choose(rng) else {
+        return;
+    };
+    let name = file.file_name().unwrap().to_string_lossy().to_string();
+    let (_, extension) = name.split_once('.').unwrap();
+    let copy = file.with_file_name(format!("{}.{}", ulid::Ulid::new(), extension));
+    std::fs::copy(file, copy).unwrap();
+}
+
+async fn simulate(seed: u64) {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let root = std::env::temp_dir().join(format!("sync-sim-{}-{}", seed, Uuid::new_v4()));
+    std::fs::create_dir_all(&root).unwrap();
+    let server = format!("file://{}", root.display());
+    let devices: Vec<_> = (0..DEVICES).map(|i| Device::new(&server, i)).collect();
+
+    for _ in 0..STEPS {
+        let device = devices.choose(&mut rng).unwrap();
+        match rng.random_range(0..10) {
+            0..=5 => device.write(&mut rng).await,
+            6 | 7 => device.push().await,
+            8 => device.pull().await,
+            _ => duplicate_op_file(&root, &mut rng),
+        }
+    }
+
+    // Alles hochladen, dann alles herunterladen
+    for device in &devices {
+        device.push().await;
+    }
+    for device in &devices {
+        device.pull().await;
+    }
+
+    let expected = devices[0].fingerprint();
+    for (i, device) in devices.iter().enumerate().skip(1) {
+        assert_eq!(
+            device.fingerprint(),
+            expected,
+            "seed {}: device-{} diverged from device-0",
+            seed,
+            i
+        );
+    }
+
+    std::fs::remove_dir_all(&root).ok();
+}
+
+#[tokio::test]
+async fn test_devices_converge_for_random_histories() {
+    let seeds: Vec<u64> = match std::env::var("SIM_SEED") {
+        Ok(seed) => vec![seed.parse().expect("SIM_SEED must be a number")],
+        Err(_) => (0..SEEDS).collect(),
+    };
+    for seed in seeds {
+        simulate(seed).await;
+    }
+}